@@ -0,0 +1,662 @@
+//! Transport abstraction for the API client and a programmable mock backend for tests
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Header carrying a client-chosen key so the server can deduplicate retried writes
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Future returned by [`ApiBackend::execute`]; not `Send` so it can wrap browser fetch
+pub type BackendFuture<'a> = Pin<Box<dyn Future<Output = Result<HttpResponse, String>> + 'a>>;
+
+/// Executes HTTP requests on behalf of the API client
+pub trait ApiBackend: Send + Sync {
+    /// Returns `Err` only for transport failures; HTTP error statuses are `Ok` responses
+    fn execute(&self, request: HttpRequest) -> BackendFuture<'_>;
+}
+
+/// One part of a multipart/form-data body
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipartPart {
+    pub name: String,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl MultipartPart {
+    pub fn text(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            filename: None,
+            content_type: None,
+            data: value.as_bytes().to_vec(),
+        }
+    }
+
+    pub fn file(name: &str, filename: &str, content_type: &str, data: Vec<u8>) -> Self {
+        Self {
+            name: name.to_string(),
+            filename: Some(filename.to_string()),
+            content_type: Some(content_type.to_string()),
+            data,
+        }
+    }
+}
+
+/// Request body variants supported by the backends
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum HttpBody {
+    #[default]
+    Empty,
+    Json(String),
+    Bytes(Vec<u8>),
+    Multipart(Vec<MultipartPart>),
+}
+
+/// Backend-independent HTTP request
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: HttpBody,
+}
+
+impl HttpRequest {
+    pub fn get(url: &str) -> Self {
+        Self {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: HttpBody::Empty,
+        }
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// URL without scheme, host or query string
+    pub fn path(&self) -> &str {
+        let without_query = self.url.split('?').next().unwrap_or(&self.url);
+        match without_query.find("://") {
+            Some(scheme_end) => {
+                let rest = &without_query[scheme_end + 3..];
+                rest.find('/').map(|i| &rest[i..]).unwrap_or("/")
+            }
+            None => without_query,
+        }
+    }
+}
+
+/// Builder mirroring the subset of the fetch API the client uses
+#[derive(Debug, Clone)]
+pub struct ApiRequestBuilder {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+}
+
+impl ApiRequestBuilder {
+    pub fn new(method: &str, url: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            url: url.to_string(),
+            headers: Vec::new(),
+            query: Vec::new(),
+        }
+    }
+
+    /// Set a header, replacing any existing value with the same name
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.retain(|(k, _)| !k.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn query<'a, I, V>(mut self, params: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, V)>,
+        V: AsRef<str>,
+    {
+        self.query
+            .extend(params.into_iter().map(|(k, v)| (k.to_string(), v.as_ref().to_string())));
+        self
+    }
+
+    pub fn json<T: Serialize + ?Sized>(self, value: &T) -> Result<HttpRequest, serde_json::Error> {
+        let body = serde_json::to_string(value)?;
+        Ok(self.finish(HttpBody::Json(body)))
+    }
+
+    pub fn bytes(self, data: Vec<u8>) -> HttpRequest {
+        self.finish(HttpBody::Bytes(data))
+    }
+
+    pub fn multipart(self, parts: Vec<MultipartPart>) -> HttpRequest {
+        self.finish(HttpBody::Multipart(parts))
+    }
+
+    pub fn build(self) -> HttpRequest {
+        self.finish(HttpBody::Empty)
+    }
+
+    fn finish(self, body: HttpBody) -> HttpRequest {
+        let url = if self.query.is_empty() {
+            self.url
+        } else {
+            let query: Vec<String> = self
+                .query
+                .iter()
+                .map(|(k, v)| format!("{}={}", percent_encode(k), percent_encode(v)))
+                .collect();
+            let separator = if self.url.contains('?') { '&' } else { '?' };
+            format!("{}{}{}", self.url, separator, query.join("&"))
+        };
+
+        HttpRequest {
+            method: self.method,
+            url,
+            headers: self.headers,
+            body,
+        }
+    }
+}
+
+impl From<ApiRequestBuilder> for HttpRequest {
+    fn from(builder: ApiRequestBuilder) -> Self {
+        builder.build()
+    }
+}
+
+pub(crate) fn percent_encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Backend-independent HTTP response with a fully buffered body
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        let body = serde_json::to_vec(value).unwrap_or_default();
+        Self::new(status, body).with_header("Content-Type", "application/json")
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn ok(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.clone())
+    }
+
+    /// Server-requested wait before retrying, from a 429 or 503 `Retry-After` header
+    pub fn retry_after_ms(&self) -> Option<u64> {
+        self.header("Retry-After")
+            .and_then(|value| parse_retry_after(&value, Utc::now()))
+    }
+
+    pub fn content_range(&self) -> Option<ContentRange> {
+        self.header("Content-Range").and_then(|value| ContentRange::parse(&value))
+    }
+
+    pub fn text(&self) -> Result<String, String> {
+        String::from_utf8(self.body.clone()).map_err(|e| e.to_string())
+    }
+
+    pub fn parse_json<T: DeserializeOwned>(&self) -> Result<T, String> {
+        serde_json::from_slice(&self.body).map_err(|e| e.to_string())
+    }
+
+    /// Server-provided error text, falling back to the status code
+    pub fn error_detail(&self) -> String {
+        match self.text() {
+            Ok(text) if !text.trim().is_empty() => format!("{} {}", self.status, text.trim()),
+            _ => self.status.to_string(),
+        }
+    }
+}
+
+/// Parse `Retry-After` as either delta-seconds or an HTTP-date, in milliseconds from `now`
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds.saturating_mul(1000));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((at - now).num_milliseconds().max(0) as u64)
+}
+
+/// How long the client may wait and retry after a 429 before giving up
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    /// Longest single wait; a larger `Retry-After` fails fast instead
+    pub max_wait_ms: u64,
+    /// Base delay when the server sends no `Retry-After`, doubled per attempt
+    pub default_wait_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            max_wait_ms: 30_000,
+            default_wait_ms: 1_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (0-based), or `None` to stop retrying
+    pub fn wait_for(&self, attempt: u32, retry_after_ms: Option<u64>) -> Option<u64> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        let wait = retry_after_ms
+            .unwrap_or_else(|| self.default_wait_ms.saturating_mul(1u64 << attempt.min(16)));
+        (wait <= self.max_wait_ms).then_some(wait)
+    }
+}
+
+/// Parsed `Content-Range: bytes start-end/total` header of a 206 response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    pub start: u64,
+    /// Inclusive, as on the wire
+    pub end: u64,
+    /// `None` when the server sends `*` because the length is not yet known
+    pub total: Option<u64>,
+}
+
+impl ContentRange {
+    pub fn parse(value: &str) -> Option<Self> {
+        let (range, total) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+        let (start, end) = range.split_once('-')?;
+        let start = start.trim().parse().ok()?;
+        let end = end.trim().parse().ok()?;
+        let total = match total.trim() {
+            "*" => None,
+            total => Some(total.parse().ok()?),
+        };
+
+        (start <= end).then_some(Self { start, end, total })
+    }
+
+    pub fn byte_count(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.total.is_some_and(|total| self.end + 1 >= total)
+    }
+}
+
+/// Programmed reply for a mock route
+#[derive(Debug, Clone)]
+pub enum MockReply {
+    Respond(HttpResponse),
+    /// Simulate a transport failure such as being offline
+    NetworkError(String),
+}
+
+#[derive(Debug, Clone)]
+struct MockRoute {
+    method: String,
+    path: String,
+    replies: VecDeque<MockReply>,
+    fallback: MockReply,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    routes: Vec<MockRoute>,
+    requests: Vec<HttpRequest>,
+    latency_ms: u32,
+}
+
+/// Programmable in-memory backend for component tests without a running server
+///
+/// Routes match on method and the path below `/api/<version>/` (e.g. `"dashboard/stats"`),
+/// so tests need not know the configured base URL. Unmatched requests receive a 404.
+#[derive(Debug, Clone, Default)]
+pub struct MockApiClient {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockApiClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Always answer `method path` with `status` and a JSON body
+    pub fn with_fixture<T: Serialize>(self, method: &str, path: &str, status: u16, body: &T) -> Self {
+        self.route(method, path, MockReply::Respond(HttpResponse::json(status, body)));
+        self
+    }
+
+    /// Always answer `method path` with a transport failure
+    pub fn with_failure(self, method: &str, path: &str, error: &str) -> Self {
+        self.route(method, path, MockReply::NetworkError(error.to_string()));
+        self
+    }
+
+    /// Delay every response; applied on wasm targets where a timer is available
+    pub fn with_latency(self, latency_ms: u32) -> Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.latency_ms = latency_ms;
+        }
+        self
+    }
+
+    /// Queue one-off replies for a route, served before its fixture
+    pub fn push_reply(&self, method: &str, path: &str, reply: MockReply) {
+        if let Ok(mut state) = self.state.lock() {
+            match state.routes.iter_mut().find(|r| r.method == method && r.path == path) {
+                Some(route) => route.replies.push_back(reply),
+                None => state.routes.push(MockRoute {
+                    method: method.to_string(),
+                    path: path.to_string(),
+                    replies: VecDeque::from([reply]),
+                    fallback: MockReply::Respond(HttpResponse::new(404, "Not found")),
+                }),
+            }
+        }
+    }
+
+    fn route(&self, method: &str, path: &str, reply: MockReply) {
+        if let Ok(mut state) = self.state.lock() {
+            state.routes.retain(|r| !(r.method == method && r.path == path));
+            state.routes.push(MockRoute {
+                method: method.to_string(),
+                path: path.to_string(),
+                replies: VecDeque::new(),
+                fallback: reply,
+            });
+        }
+    }
+
+    /// Every request received so far, for asserting on headers and bodies
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.state.lock().map(|s| s.requests.clone()).unwrap_or_default()
+    }
+
+    pub fn latency_ms(&self) -> u32 {
+        self.state.lock().map(|s| s.latency_ms).unwrap_or(0)
+    }
+
+    /// Resolve a request synchronously against the programmed routes
+    pub fn respond(&self, request: HttpRequest) -> Result<HttpResponse, String> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|e| format!("Mock state poisoned: {}", e))?;
+
+        let path = route_path(request.path()).to_string();
+        let method = request.method.clone();
+        state.requests.push(request);
+
+        let reply = state
+            .routes
+            .iter_mut()
+            .rev()
+            .find(|r| r.method == method && route_path(&r.path) == path)
+            .map(|route| route.replies.pop_front().unwrap_or_else(|| route.fallback.clone()))
+            .unwrap_or_else(|| MockReply::Respond(HttpResponse::new(404, "Not found")));
+
+        match reply {
+            MockReply::Respond(response) => Ok(response),
+            MockReply::NetworkError(error) => Err(error),
+        }
+    }
+}
+
+// Strip slashes and any `api/<version>/` prefix so routes can be written relative to the API root
+fn route_path(path: &str) -> &str {
+    let path = path.trim_matches('/');
+    match path.strip_prefix("api/") {
+        Some(rest) => rest.split_once('/').map(|(_, route)| route).unwrap_or(""),
+        None => path,
+    }
+}
+
+impl ApiBackend for MockApiClient {
+    fn execute(&self, request: HttpRequest) -> BackendFuture<'_> {
+        Box::pin(async move {
+            #[cfg(feature = "ui")]
+            {
+                let latency_ms = self.latency_ms();
+                if latency_ms > 0 {
+                    gloo_timers::future::TimeoutFuture::new(latency_ms).await;
+                }
+            }
+            self.respond(request)
+        })
+    }
+}
+
+/// Generate typed async endpoint methods that delegate to `self.send_json`.
+///
+/// ```ignore
+/// impl ApiClient {
+///     api_endpoints! {
+///         pub fn get_admin_patients() -> Vec<AdminPatient> = GET "admin/patients", "Failed to get admin patients";
+///         pub fn get_patient(patient_id: &str) -> ApiPatient = GET "patients/{patient_id}";
+///         pub fn book_appointment(request: BookAppointmentRequest) -> PatientAppointment
+///             = POST "appointments/book" json(request), "Appointment booking failed";
+///     }
+/// }
+/// ```
+///
+/// Path placeholders capture the method arguments by name. The implementing type provides
+/// `async fn send_json<T>(&self, method: &str, endpoint: &str, body: Option<serde_json::Value>, context: &str) -> Result<T, String>`.
+#[macro_export]
+macro_rules! api_endpoints {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty
+            = $method:ident $path:literal $(json($body:ident))? $(, $context:literal)?;
+    )*) => {
+        $(
+            $(#[$meta])*
+            $vis async fn $name(&self, $($arg: $arg_ty),*) -> Result<$ret, String> {
+                let endpoint = format!($path);
+                let body = $crate::api_endpoints!(@body $($body)?);
+                self.send_json::<$ret>(
+                    stringify!($method),
+                    &endpoint,
+                    body,
+                    $crate::api_endpoints!(@context $name $($context)?),
+                )
+                .await
+            }
+        )*
+    };
+    (@body) => { None };
+    (@body $body:ident) => {
+        Some(serde_json::to_value(&$body).map_err(|e| format!("Failed to serialize request: {}", e))?)
+    };
+    (@context $name:ident) => { concat!(stringify!($name), " failed") };
+    (@context $name:ident $context:literal) => { $context };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(parse_retry_after("120", now), Some(120_000));
+        assert_eq!(parse_retry_after("Mon, 15 Jan 2024 10:00:30 GMT", now), Some(30_000));
+        assert_eq!(parse_retry_after("Mon, 15 Jan 2024 09:00:00 GMT", now), Some(0));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_retry_policy_limits() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.wait_for(0, Some(5_000)), Some(5_000));
+        assert_eq!(policy.wait_for(0, Some(60_000)), None);
+        assert_eq!(policy.wait_for(2, None), Some(4_000));
+        assert_eq!(policy.wait_for(3, None), None);
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        let range = ContentRange::parse("bytes 0-1023/4096").unwrap();
+        assert_eq!(range, ContentRange { start: 0, end: 1023, total: Some(4096) });
+        assert_eq!(range.byte_count(), 1024);
+        assert!(!range.is_last());
+        assert!(ContentRange::parse("bytes 3072-4095/4096").unwrap().is_last());
+        assert_eq!(ContentRange::parse("bytes 0-9/*").unwrap().total, None);
+        assert!(ContentRange::parse("bytes 10-5/20").is_none());
+        assert!(ContentRange::parse("items 0-9/20").is_none());
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(output) => output,
+            std::task::Poll::Pending => panic!("mock backend future should resolve immediately"),
+        }
+    }
+
+    struct EndpointClient {
+        backend: MockApiClient,
+    }
+
+    impl EndpointClient {
+        async fn send_json<T: DeserializeOwned>(
+            &self,
+            method: &str,
+            endpoint: &str,
+            body: Option<serde_json::Value>,
+            context: &str,
+        ) -> Result<T, String> {
+            let builder = ApiRequestBuilder::new(method, &format!("http://localhost:8080/api/v1/{}", endpoint));
+            let request = match body {
+                Some(body) => builder.json(&body).map_err(|e| e.to_string())?,
+                None => builder.build(),
+            };
+            let response = self.backend.execute(request).await?;
+            if response.ok() {
+                response.parse_json()
+            } else {
+                Err(format!("{}: {}", context, response.error_detail()))
+            }
+        }
+
+        api_endpoints! {
+            fn get_item(item_id: &str) -> serde_json::Value = GET "items/{item_id}";
+            fn create_item(item: serde_json::Value) -> serde_json::Value = POST "items" json(item), "Failed to create item";
+        }
+    }
+
+    #[test]
+    fn test_api_endpoints_macro() {
+        let backend = MockApiClient::new()
+            .with_fixture("GET", "items/42", 200, &serde_json::json!({ "id": 42 }))
+            .with_fixture("POST", "items", 422, &serde_json::json!("name required"));
+        let client = EndpointClient { backend: backend.clone() };
+
+        assert_eq!(block_on(client.get_item("42")).unwrap()["id"], 42);
+        assert_eq!(block_on(client.get_item("7")).unwrap_err(), "get_item failed: 404 Not found");
+        assert_eq!(
+            block_on(client.create_item(serde_json::json!({}))).unwrap_err(),
+            "Failed to create item: 422 \"name required\""
+        );
+        assert_eq!(backend.requests()[2].body, HttpBody::Json("{}".to_string()));
+    }
+
+    #[test]
+    fn test_request_builder_encodes_query() {
+        let request = ApiRequestBuilder::new("GET", "http://localhost:8080/api/v1/providers")
+            .query([("specialty", "general medicine"), ("page", "2")])
+            .build();
+        assert_eq!(
+            request.url,
+            "http://localhost:8080/api/v1/providers?specialty=general%20medicine&page=2"
+        );
+        assert_eq!(request.path(), "/api/v1/providers");
+    }
+
+    #[test]
+    fn test_mock_fixtures_and_failures() {
+        let mock = MockApiClient::new()
+            .with_fixture("GET", "dashboard/stats", 200, &serde_json::json!({ "total_patients": 3 }))
+            .with_failure("POST", "emergency/request", "offline");
+
+        let response = block_on(mock.execute(HttpRequest::get("http://x/api/v1/dashboard/stats"))).unwrap();
+        assert!(response.ok());
+        let body: serde_json::Value = response.parse_json().unwrap();
+        assert_eq!(body["total_patients"], 3);
+
+        let request = ApiRequestBuilder::new("POST", "http://x/api/v1/emergency/request")
+            .json(&serde_json::json!({}))
+            .unwrap();
+        assert_eq!(block_on(mock.execute(request)), Err("offline".to_string()));
+
+        let missing = block_on(mock.execute(HttpRequest::get("http://x/api/v1/unknown"))).unwrap();
+        assert_eq!(missing.status(), 404);
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[test]
+    fn test_mock_queued_replies_take_precedence() {
+        let mock = MockApiClient::new().with_fixture("GET", "providers", 200, &Vec::<String>::new());
+        mock.push_reply("GET", "providers", MockReply::Respond(HttpResponse::new(503, "")));
+
+        let first = block_on(mock.execute(HttpRequest::get("http://x/api/v1/providers"))).unwrap();
+        let second = block_on(mock.execute(HttpRequest::get("http://x/api/v1/providers"))).unwrap();
+        assert_eq!(first.status(), 503);
+        assert_eq!(second.status(), 200);
+    }
+}
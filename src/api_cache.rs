@@ -0,0 +1,232 @@
+//! HTTP response cache with ETag revalidation and stale-while-revalidate support
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Freshness rules for cached responses
+#[derive(Debug, Clone)]
+pub struct CachePolicy {
+    /// How long a response is served without contacting the server
+    pub fresh_for: Duration,
+    /// How long after going stale a response may still be served while revalidating
+    pub stale_while_revalidate: Duration,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            fresh_for: Duration::seconds(30),
+            stale_while_revalidate: Duration::minutes(5),
+        }
+    }
+}
+
+/// A stored response body and its validator
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub body: String,
+    pub etag: Option<String>,
+    pub stored_at: DateTime<Utc>,
+}
+
+/// Result of looking up a cache key
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheLookup {
+    /// Serve directly, no network request needed
+    Fresh(String),
+    /// Serve immediately and revalidate in the background
+    Stale { body: String, etag: Option<String> },
+    /// Too old to serve; revalidate before responding
+    Expired { etag: Option<String> },
+    Miss,
+}
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    /// Session the entries were fetched for; see [`ResponseCache::bind_owner`]
+    owner: Option<String>,
+    responses: HashMap<String, CachedResponse>,
+}
+
+/// Shared, clonable response cache keyed by endpoint and query parameters,
+/// within the session it is bound to
+#[derive(Debug, Clone, Default)]
+pub struct ResponseCache {
+    policy: CachePolicy,
+    entries: Arc<Mutex<CacheEntries>>,
+}
+
+impl ResponseCache {
+    pub fn new(policy: CachePolicy) -> Self {
+        Self {
+            policy,
+            entries: Arc::new(Mutex::new(CacheEntries::default())),
+        }
+    }
+
+    pub fn policy(&self) -> &CachePolicy {
+        &self.policy
+    }
+
+    /// Bind the cache to the signed-in session, e.g. user id and token hash.
+    /// Responses never outlive the session that fetched them: a different
+    /// owner, or none, starts from an empty cache.
+    pub fn bind_owner(&self, owner: Option<&str>) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.owner.as_deref() != owner {
+                entries.responses.clear();
+                entries.owner = owner.map(str::to_string);
+            }
+        }
+    }
+
+    /// Build a cache key; parameters are sorted so ordering does not matter
+    pub fn key(endpoint: &str, params: &[(&str, &str)]) -> String {
+        if params.is_empty() {
+            return endpoint.to_string();
+        }
+
+        let mut sorted: Vec<_> = params.to_vec();
+        sorted.sort();
+        let query: Vec<String> = sorted.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        format!("{}?{}", endpoint, query.join("&"))
+    }
+
+    pub fn lookup(&self, key: &str) -> CacheLookup {
+        self.lookup_at(key, Utc::now())
+    }
+
+    pub fn lookup_at(&self, key: &str, now: DateTime<Utc>) -> CacheLookup {
+        let entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return CacheLookup::Miss,
+        };
+
+        let Some(entry) = entries.responses.get(key) else {
+            return CacheLookup::Miss;
+        };
+
+        let age = now - entry.stored_at;
+        if age <= self.policy.fresh_for {
+            CacheLookup::Fresh(entry.body.clone())
+        } else if age <= self.policy.fresh_for + self.policy.stale_while_revalidate {
+            CacheLookup::Stale {
+                body: entry.body.clone(),
+                etag: entry.etag.clone(),
+            }
+        } else {
+            CacheLookup::Expired {
+                etag: entry.etag.clone(),
+            }
+        }
+    }
+
+    /// Cached body regardless of freshness, used after a 304 Not Modified
+    pub fn body(&self, key: &str) -> Option<String> {
+        self.entries
+            .lock()
+            .ok()
+            .and_then(|entries| entries.responses.get(key).map(|e| e.body.clone()))
+    }
+
+    pub fn store(&self, key: &str, body: String, etag: Option<String>) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.responses.insert(
+                key.to_string(),
+                CachedResponse {
+                    body,
+                    etag,
+                    stored_at: Utc::now(),
+                },
+            );
+        }
+    }
+
+    /// Mark an entry fresh again after the server confirmed it is unchanged
+    pub fn touch(&self, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if let Some(entry) = entries.responses.get_mut(key) {
+                entry.stored_at = Utc::now();
+            }
+        }
+    }
+
+    pub fn invalidate(&self, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.responses.remove(key);
+        }
+    }
+
+    /// Drop every entry whose key starts with `prefix`, e.g. after a mutation
+    pub fn invalidate_prefix(&self, prefix: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.responses.retain(|key, _| !key.starts_with(prefix));
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.responses.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_ignores_param_order() {
+        let a = ResponseCache::key("providers", &[("city", "pune"), ("page", "2")]);
+        let b = ResponseCache::key("providers", &[("page", "2"), ("city", "pune")]);
+        assert_eq!(a, b);
+        assert_eq!(ResponseCache::key("dashboard/stats", &[]), "dashboard/stats");
+    }
+
+    #[test]
+    fn test_freshness_transitions() {
+        let cache = ResponseCache::new(CachePolicy::default());
+        assert_eq!(cache.lookup("dashboard/stats"), CacheLookup::Miss);
+
+        cache.store("dashboard/stats", "{}".to_string(), Some("\"v1\"".to_string()));
+        let now = Utc::now();
+        assert_eq!(cache.lookup_at("dashboard/stats", now), CacheLookup::Fresh("{}".to_string()));
+        assert_eq!(
+            cache.lookup_at("dashboard/stats", now + Duration::minutes(1)),
+            CacheLookup::Stale { body: "{}".to_string(), etag: Some("\"v1\"".to_string()) }
+        );
+        assert_eq!(
+            cache.lookup_at("dashboard/stats", now + Duration::hours(1)),
+            CacheLookup::Expired { etag: Some("\"v1\"".to_string()) }
+        );
+    }
+
+    #[test]
+    fn test_invalidate_prefix() {
+        let cache = ResponseCache::default();
+        cache.store("admin/providers", "[]".to_string(), None);
+        cache.store("admin/providers?page=2", "[]".to_string(), None);
+        cache.store("dashboard/stats", "{}".to_string(), None);
+
+        cache.invalidate_prefix("admin/providers");
+        assert!(cache.body("admin/providers").is_none());
+        assert!(cache.body("admin/providers?page=2").is_none());
+        assert!(cache.body("dashboard/stats").is_some());
+    }
+
+    #[test]
+    fn test_entries_stay_with_their_owner() {
+        let cache = ResponseCache::default();
+        cache.bind_owner(Some("u-1:token-a"));
+        cache.store("patient/profile", "{\"name\":\"Asha\"}".to_string(), None);
+        cache.bind_owner(Some("u-1:token-a"));
+        assert!(cache.body("patient/profile").is_some());
+
+        cache.bind_owner(Some("u-2:token-b"));
+        assert!(cache.body("patient/profile").is_none());
+        cache.store("patient/profile", "{}".to_string(), None);
+        cache.bind_owner(None);
+        assert_eq!(cache.lookup("patient/profile"), CacheLookup::Miss);
+    }
+}
@@ -2,13 +2,55 @@
 // Connects Leptos frontend applications to the MyDR24 backend API
 
 use gloo_net::http::Request;
+use leptos::prelude::{signal, ReadSignal, Set};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use wasm_bindgen_futures::spawn_local;
 
+use crate::api_backend::{
+    ApiBackend, ApiRequestBuilder, BackendFuture, HttpBody, HttpRequest, HttpResponse, MultipartPart, RetryPolicy,
+};
+use crate::api_endpoints;
+use crate::auth::{
+    AccessReason, AuthClaims, RevocationList, RevocationUpdate, BreakGlassGrant, BreakGlassRequest, BreakGlassReview, DeviceKeyPair, DeviceRegistration, LockoutStatus, LoginFailure, LoginThrottle, OidcLogin, PairingDetails, PairingProof, PairingStartRequest, PairingStatus, PairingToken, PhiAccessGuard, RegisteredDevice, RequestSigningPolicy, ResourceAttributes, Role, sign_request, token_hash,
+    WebAuthnAssertion, WebAuthnAttestation, WebAuthnCreationOptions, WebAuthnCredential, WebAuthnRequestOptions,
+    DEVICE_ID_HEADER, LAB_RESULT_RESOURCE, MEDICAL_RECORD_RESOURCE,
+};
+use crate::api_cache::{CacheLookup, CachePolicy, ResponseCache};
+use crate::api_metrics::{new_correlation_id, Instrumentation, CORRELATION_ID_HEADER};
+use crate::backend_status::{BackendStatus, HealthCheckOutcome, HealthPollConfig};
+use crate::compliance::hipaa::{AuditOutcome, AuditQuery, AuditSink, HipaaAction, HipaaAuditEntry};
+use crate::errors::{ErrorCode, SharedError, SharedResult};
+use crate::geo::GeoPoint;
+pub use crate::lab_results::{AbnormalFlag, ReferenceRange};
+use crate::lab_results::{LabResult, LabResultStatus, LabValue};
+use crate::ids::{AppointmentId, BookingId, MedicalRecordNumber, PatientId, ProviderId};
+use crate::recurrence::Recurrence;
+use crate::secrets::SecretString;
+use crate::units::{Analyte, Quantity, Unit};
+use crate::models::{
+    AppointmentSlot, AvailabilityException, BloodType, EligibilityCheckResult, Gender, InsurancePolicy, NotificationPreferences,
+    ProviderAvailability,
+};
+use crate::healthcare_service_engine::{ProviderMatch, ServiceCategory, WorkflowStepType};
+use crate::utils::contact::{EmailAddress, PhoneNumber};
+use crate::utils::validation::validate_otp_code;
+use crate::encryption::MasterKey;
+use crate::offline_queue::{EncryptedQueueStorage, MutationKind, OfflineQueue, QueueStorage, QueuedMutation, ReplayOutcome, SyncReport};
+use crate::ui::FilterState;
+use crate::websocket_simple::{ChatMessage, PaymentNotification, WebSocketConfig, WsTicket};
+
 // API Configuration
 const API_BASE_URL: &str = "http://localhost:8080";
 const API_VERSION: &str = "v1";
 
+pub use crate::api_backend::IDEMPOTENCY_KEY_HEADER;
+
+// One key per logical operation; reuse it when retrying that operation
+pub fn new_idempotency_key() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
 // Common API Response Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
@@ -25,36 +67,216 @@ pub struct HealthCheck {
     pub version: String,
 }
 
+// Server capability discovery
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FeatureFlags {
+    #[serde(default)]
+    pub payments: bool,
+    #[serde(default)]
+    pub telehealth: bool,
+    #[serde(default)]
+    pub chat: bool,
+    #[serde(default)]
+    pub emergency_dispatch: bool,
+    #[serde(default)]
+    pub lab_results: bool,
+    #[serde(default)]
+    pub document_upload: bool,
+    #[serde(default)]
+    pub offline_sync: bool,
+    // Flags this client version does not know about yet
+    #[serde(flatten)]
+    pub other: std::collections::HashMap<String, bool>,
+}
+
+impl FeatureFlags {
+    pub fn is_enabled(&self, name: &str) -> bool {
+        match name {
+            "payments" => self.payments,
+            "telehealth" => self.telehealth,
+            "chat" => self.chat,
+            "emergency_dispatch" => self.emergency_dispatch,
+            "lab_results" => self.lab_results,
+            "document_upload" => self.document_upload,
+            "offline_sync" => self.offline_sync,
+            other => self.other.get(other).copied().unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServerCapabilities {
+    pub api_version: String, // Server's preferred version, e.g. "v1"
+    pub supported_versions: Vec<String>,
+    pub server_version: String,
+    pub features: FeatureFlags,
+}
+
+impl ServerCapabilities {
+    pub fn supports_client_version(&self) -> bool {
+        self.supported_versions.iter().any(|v| v == API_VERSION)
+    }
+}
+
 // Authentication Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginRequest {
     pub email: String,
-    pub password: String,
+    pub password: SecretString,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegisterRequest {
     pub name: String,
-    pub email: String,
-    pub phone: String,
-    pub password: String,
+    pub email: EmailAddress,
+    pub phone: PhoneNumber,
+    pub password: SecretString,
     pub date_of_birth: String,
 }
 
+// Multi-step registration: start -> OTP -> email verification -> (provider onboarding) -> complete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartRegistrationRequest {
+    pub role: Role, // Patient or Provider
+    pub details: RegisterRequest,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationSession {
+    pub registration_id: String,
+    pub role: Role,
+    pub completed_steps: Vec<WorkflowStepType>,
+    pub next_step: Option<WorkflowStepType>, // None once the account can be completed
+    pub expires_at: String,
+}
+
+impl RegistrationSession {
+    pub fn is_step_complete(&self, step: &WorkflowStepType) -> bool {
+        self.completed_steps.contains(step)
+    }
+
+    pub fn is_ready_to_complete(&self) -> bool {
+        self.next_step.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtpChannel {
+    Sms,
+    WhatsApp,
+    Email,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendOtpRequest {
+    pub registration_id: String,
+    pub channel: OtpChannel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OtpChallenge {
+    pub challenge_id: String,
+    pub channel: OtpChannel,
+    pub destination_hint: String, // Masked, e.g. "+91 ******3210"
+    pub expires_at: String,
+    pub resend_after_seconds: u32,
+    pub attempts_remaining: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyOtpRequest {
+    pub challenge_id: String,
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OtpVerificationResult {
+    pub verified: bool,
+    pub attempts_remaining: u32,
+    pub session: Option<RegistrationSession>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderOnboardingRequest {
+    pub registration_id: String,
+    pub specialization: String,
+    pub license_number: String,
+    pub nmc_registration: String,
+    pub qualification: String,
+    pub experience_years: u32,
+    pub document_ids: Vec<String>, // From `upload_document`
+}
+
+// Exchanges a hospital SSO sign-in for a platform session; the server
+// re-validates the ID token against the provider's keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcLoginRequest {
+    pub issuer: String,
+    pub id_token: SecretString,
+}
+
+// Desktop side of QR pairing; `login` is set once, when an approved pairing is collected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairingPollResponse {
+    pub status: PairingStatus,
+    #[serde(default)]
+    pub login: Option<LoginResponse>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginResponse {
-    pub token: String,
+    pub token: SecretString,
     pub user: UserProfile,
     pub expires_at: String,
 }
 
 // API Error Types
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum ApiError {
+    #[error("HTTP {0}")]
     HttpError(u16),
+    #[error("Network error: {0}")]
     NetworkError(String),
+    #[error("Parse error: {0}")]
     ParseError(String),
+    #[error("Authentication error: {0}")]
     AuthError(String),
+    #[error("{}", rate_limited_message(.retry_after_ms))]
+    RateLimited { retry_after_ms: Option<u64> },
+}
+
+fn rate_limited_message(retry_after_ms: &Option<u64>) -> String {
+    match retry_after_ms {
+        Some(ms) => format!("Rate limited, retry after {}s", ms.div_ceil(1000)),
+        None => "Rate limited".to_string(),
+    }
+}
+
+impl ApiError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ApiError::HttpError(status) => ErrorCode::from_http_status(*status),
+            ApiError::NetworkError(_) => ErrorCode::Network,
+            ApiError::ParseError(_) => ErrorCode::Serialization,
+            ApiError::AuthError(_) => ErrorCode::Authentication,
+            ApiError::RateLimited { .. } => ErrorCode::RateLimited,
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.code().is_retryable()
+    }
+}
+
+impl From<ApiError> for SharedError {
+    fn from(error: ApiError) -> Self {
+        let code = error.code();
+        match error {
+            ApiError::AuthError(details) | ApiError::ParseError(details) => SharedError::new(code, details),
+            other => SharedError::new(code, other.to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,7 +284,7 @@ pub struct UserProfile {
     pub id: String,
     pub email: String,
     pub name: String,
-    pub role: String,
+    pub role: Role,
     pub phone: Option<String>,
     pub created_at: String,
 }
@@ -70,19 +292,23 @@ pub struct UserProfile {
 // Healthcare Data Types for API Client
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiPatient {
-    pub id: String,
+    pub id: PatientId,
     pub name: String,
     pub email: String,
     pub phone: String,
     pub age: Option<u32>,
-    pub medical_id: Option<String>,
+    #[serde(default)]
+    pub gender: Option<Gender>,
+    #[serde(default)]
+    pub blood_type: Option<BloodType>,
+    pub medical_id: Option<MedicalRecordNumber>,
     pub emergency_contact: Option<ApiEmergencyContact>,
     pub created_at: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiProvider {
-    pub id: String,
+    pub id: ProviderId,
     pub name: String,
     pub email: String,
     pub phone: String,
@@ -95,10 +321,263 @@ pub struct ApiProvider {
     pub created_at: String,
 }
 
+// Provider Search Types
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchQuery {
+    pub text: Option<String>, // Free-text match on provider name or specialty
+    pub specialty: Option<String>,
+    pub min_rating: Option<f32>,
+    pub near: Option<GeoPoint>,
+    pub max_distance_km: Option<f64>,
+    pub available_from: Option<String>,  // RFC 3339
+    pub available_until: Option<String>, // RFC 3339
+    pub service_category: Option<ServiceCategory>,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+impl SearchQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn specialty(mut self, specialty: &str) -> Self {
+        self.specialty = Some(specialty.to_string());
+        self
+    }
+
+    pub fn min_rating(mut self, rating: f32) -> Self {
+        self.min_rating = Some(rating);
+        self
+    }
+
+    pub fn within_km(mut self, origin: GeoPoint, max_distance_km: f64) -> Self {
+        self.near = Some(origin);
+        self.max_distance_km = Some(max_distance_km);
+        self
+    }
+
+    // Fills in distances the server left out and drops providers outside the
+    // search radius; providers without a known location are kept
+    pub fn apply_distance(&self, results: &mut Vec<ProviderSearchResult>) {
+        let Some(origin) = self.near else { return };
+        for result in results.iter_mut() {
+            if result.distance_km.is_none() {
+                result.distance_km = result
+                    .provider
+                    .location
+                    .as_ref()
+                    .map(|location| location.point().distance_km(&origin) as f32);
+            }
+        }
+        if let Some(max_distance_km) = self.max_distance_km {
+            results.retain(|result| match &result.provider.location {
+                Some(location) => location.point().within_radius_km(&origin, max_distance_km),
+                None => result.distance_km.is_none_or(|distance| f64::from(distance) <= max_distance_km),
+            });
+        }
+    }
+
+    pub fn available_between(mut self, from: &str, until: &str) -> Self {
+        self.available_from = Some(from.to_string());
+        self.available_until = Some(until.to_string());
+        self
+    }
+
+    pub fn service_category(mut self, category: ServiceCategory) -> Self {
+        self.service_category = Some(category);
+        self
+    }
+
+    pub fn page(mut self, page: u32, page_size: u32) -> Self {
+        self.page = Some(page);
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Query for a `FilterBar` with `specialty`, `service_category` and
+    /// `min_rating` facets; its date range bounds availability
+    pub fn from_filters(filters: &FilterState) -> Self {
+        Self {
+            text: filters.text.clone(),
+            specialty: filters.value("specialty").map(str::to_string),
+            min_rating: filters.value("min_rating").and_then(|rating| rating.parse().ok()),
+            available_from: filters
+                .date_range
+                .from
+                .map(|from| from.and_time(chrono::NaiveTime::MIN).and_utc().to_rfc3339()),
+            available_until: filters
+                .date_range
+                .until
+                .and_then(|until| until.and_hms_opt(23, 59, 59))
+                .map(|until| until.and_utc().to_rfc3339()),
+            service_category: filters
+                .value("service_category")
+                .and_then(|name| serde_json::from_value(serde_json::Value::String(name.to_string())).ok()),
+            ..Self::default()
+        }
+    }
+
+    pub fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(text) = &self.text {
+            params.push(("q", text.clone()));
+        }
+        if let Some(specialty) = &self.specialty {
+            params.push(("specialty", specialty.clone()));
+        }
+        if let Some(rating) = self.min_rating {
+            params.push(("min_rating", rating.to_string()));
+        }
+        if let Some(origin) = &self.near {
+            params.push(("lat", origin.latitude.to_string()));
+            params.push(("lng", origin.longitude.to_string()));
+        }
+        if let Some(distance) = self.max_distance_km {
+            params.push(("max_distance_km", distance.to_string()));
+        }
+        if let Some(from) = &self.available_from {
+            params.push(("available_from", from.clone()));
+        }
+        if let Some(until) = &self.available_until {
+            params.push(("available_until", until.clone()));
+        }
+        if let Some(category) = &self.service_category {
+            if let Ok(serde_json::Value::String(name)) = serde_json::to_value(category) {
+                params.push(("service_category", name));
+            }
+        }
+        if let Some(page) = self.page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = self.page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        params
+    }
+}
+
+// Patient Search Types
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PatientSearchQuery {
+    pub text: Option<String>, // Name, phone, email or medical ID
+    pub status: Vec<String>,
+    pub registered_from: Option<chrono::NaiveDate>,
+    pub registered_until: Option<chrono::NaiveDate>, // Inclusive
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+impl PatientSearchQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn status(mut self, status: &str) -> Self {
+        self.status.push(status.to_string());
+        self
+    }
+
+    pub fn registered_between(mut self, from: chrono::NaiveDate, until: chrono::NaiveDate) -> Self {
+        self.registered_from = Some(from);
+        self.registered_until = Some(until);
+        self
+    }
+
+    pub fn page(mut self, page: u32, page_size: u32) -> Self {
+        self.page = Some(page);
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Query for a `FilterBar` with a `status` facet; its date range bounds registration
+    pub fn from_filters(filters: &FilterState) -> Self {
+        Self {
+            text: filters.text.clone(),
+            status: filters.values("status").to_vec(),
+            registered_from: filters.date_range.from,
+            registered_until: filters.date_range.until,
+            ..Self::default()
+        }
+    }
+
+    pub fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(text) = &self.text {
+            params.push(("q", text.clone()));
+        }
+        if !self.status.is_empty() {
+            params.push(("status", self.status.join(",")));
+        }
+        if let Some(from) = self.registered_from {
+            params.push(("registered_from", from.to_string()));
+        }
+        if let Some(until) = self.registered_until {
+            params.push(("registered_until", until.to_string()));
+        }
+        if let Some(page) = self.page {
+            params.push(("page", page.to_string()));
+        }
+        if let Some(page_size) = self.page_size {
+            params.push(("page_size", page_size.to_string()));
+        }
+        params
+    }
+}
+
+// Terminology Types
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Icd10Code {
+    pub code: String, // e.g. "J45.909"
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MedicationConcept {
+    pub id: String,
+    pub name: String,
+    pub strength: Option<String>,  // e.g. "500 mg"
+    pub dose_form: Option<String>, // e.g. "tablet"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderSearchResult {
+    pub provider: ApiProvider,
+    pub match_score: f32,
+    pub distance_km: Option<f32>,
+    pub estimated_response_time: u32, // minutes
+    pub availability_status: String,
+    pub next_available_slot: Option<String>,
+}
+
+impl ProviderSearchResult {
+    // Booking UI consumes ProviderMatch; None if the provider id is not a UUID
+    pub fn to_provider_match(&self) -> Option<ProviderMatch> {
+        let provider_id = self.provider.id.to_uuid()?;
+        Some(ProviderMatch {
+            provider_id,
+            match_score: self.match_score,
+            estimated_response_time: self.estimated_response_time,
+            availability_status: self.availability_status.clone(),
+            distance_km: self.distance_km,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiEmergencyContact {
     pub name: String,
-    pub phone: String,
+    pub phone: PhoneNumber,
     pub relationship: String,
 }
 
@@ -111,6 +590,12 @@ pub struct ApiLocation {
     pub state: Option<String>,
 }
 
+impl ApiLocation {
+    pub fn point(&self) -> GeoPoint {
+        GeoPoint { latitude: self.latitude, longitude: self.longitude }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DashboardStats {
     pub total_patients: u32,
@@ -137,7 +622,7 @@ pub struct AdminDashboardStats {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminProvider {
-    pub id: String,
+    pub id: ProviderId,
     pub name: String,
     pub email: String,
     pub phone: String,
@@ -156,13 +641,14 @@ pub struct AdminProvider {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminPatient {
-    pub id: String,
+    pub id: PatientId,
     pub name: String,
     pub email: String,
     pub phone: String,
     pub age: Option<u32>,
-    pub gender: Option<String>,
-    pub medical_id: Option<String>,
+    #[serde(default)]
+    pub gender: Option<Gender>,
+    pub medical_id: Option<MedicalRecordNumber>,
     pub emergency_contact: Option<EmergencyContactInfo>,
     pub total_consultations: u32,
     pub last_consultation_date: Option<String>,
@@ -181,20 +667,68 @@ pub struct EmergencyContactInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminEmergencyCase {
     pub id: String,
-    pub patient_id: String,
+    pub patient_id: PatientId,
     pub patient_name: String,
     pub emergency_type: String,
     pub severity: String,
     pub status: String,
     pub location: String,
     pub description: String,
-    pub assigned_provider_id: Option<String>,
+    pub assigned_provider_id: Option<ProviderId>,
     pub assigned_provider_name: Option<String>,
     pub created_at: String,
     pub resolved_at: Option<String>,
     pub response_time_minutes: Option<u32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogPage {
+    pub entries: Vec<HipaaAuditEntry>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total: u64,
+}
+
+impl AuditLogPage {
+    // Pages are 1-based
+    pub fn has_next(&self) -> bool {
+        (self.page as u64) * (self.page_size as u64) < self.total
+    }
+}
+
+/// One page of a list response; pages are 1-based. Pairs with the `Pagination` component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total: u64,
+}
+
+impl<T> Paginated<T> {
+    pub fn has_next(&self) -> bool {
+        (self.page as u64) * (self.page_size as u64) < self.total
+    }
+
+    pub fn page_count(&self) -> u32 {
+        if self.page_size == 0 {
+            return 1;
+        }
+        self.total.div_ceil(self.page_size as u64).max(1) as u32
+    }
+}
+
+impl From<AuditLogPage> for Paginated<HipaaAuditEntry> {
+    fn from(page: AuditLogPage) -> Self {
+        Self {
+            items: page.entries,
+            page: page.page,
+            page_size: page.page_size,
+            total: page.total,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemHealthMetric {
     pub component: String,
@@ -208,7 +742,7 @@ pub struct SystemHealthMetric {
 // Emergency Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiEmergencyRequest {
-    pub patient_id: String,
+    pub patient_id: PatientId,
     pub emergency_type: String,
     pub severity: String,
     pub location: ApiLocation,
@@ -228,8 +762,8 @@ pub struct ApiEmergencyResponse {
 // Patient App Specific Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatientAppointment {
-    pub id: String,
-    pub provider_id: String,
+    pub id: AppointmentId,
+    pub provider_id: ProviderId,
     pub provider_name: String,
     pub provider_specialization: String,
     pub appointment_type: String, // "video", "audio", "in-person", "home-visit"
@@ -243,12 +777,15 @@ pub struct PatientAppointment {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BookAppointmentRequest {
-    pub provider_id: String,
+    pub provider_id: ProviderId,
     pub appointment_type: String,
     pub preferred_time: String,
     pub duration_minutes: u32,
     pub reason: String,
     pub notes: Option<String>,
+    // Books the whole series, e.g. weekly physiotherapy; preferred_time is the first session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -264,6 +801,16 @@ pub struct MedicalRecord {
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadMedicalRecordRequest {
+    pub record_type: String, // "consultation", "prescription", "lab-result", "diagnosis"
+    pub title: String,
+    pub description: String,
+    pub date: String,
+    pub attachment_ids: Vec<String>,
+    pub medications: Vec<Medication>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MedicalAttachment {
     pub id: String,
@@ -273,106 +820,1414 @@ pub struct MedicalAttachment {
     pub download_url: String,
 }
 
+// Lab Result Types
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Medication {
+pub struct ApiLabResult {
     pub id: String,
-    pub name: String,
-    pub dosage: String,
-    pub frequency: String,
-    pub duration: String,
-    pub instructions: String,
-    pub active: bool,
+    pub patient_id: PatientId,
+    pub order_id: Option<String>,
+    pub test_code: String, // LOINC code
+    pub test_name: String,
+    pub value: Option<f64>,
+    pub value_text: Option<String>, // Qualitative results such as "Positive"
+    pub unit: Option<String>,
+    pub reference_range: Option<ReferenceRange>,
+    pub abnormal_flag: Option<AbnormalFlag>,
+    pub status: String, // "preliminary", "final", "corrected", "cancelled"
+    pub performing_lab: Option<String>,
+    pub collected_at: String,        // RFC 3339
+    pub reported_at: Option<String>, // RFC 3339
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct HealthMetrics {
-    pub last_updated: String,
-    pub vital_signs: VitalSigns,
-    pub health_score: u32, // 0-100
-    pub upcoming_appointments: u32,
-    pub active_medications: u32,
-    pub recent_consultations: u32,
-    pub emergency_contacts: u32,
+impl ApiLabResult {
+    // Lab-reported flag wins; otherwise derive it from the numeric value and range
+    pub fn effective_flag(&self) -> Option<AbnormalFlag> {
+        self.abnormal_flag.or_else(|| match (self.value, &self.reference_range) {
+            (Some(value), Some(range)) => Some(range.classify(value)),
+            _ => None,
+        })
+    }
+
+    // Typed numeric result; the analyte comes from the LOINC code, else the test name
+    pub fn quantity(&self) -> Option<Quantity> {
+        let unit = Unit::parse(self.unit.as_deref()?)?;
+        let analyte = Analyte::from_loinc(&self.test_code).or_else(|| Analyte::infer(&self.test_name));
+        Some(Quantity::new(self.value?, unit).with_analyte(analyte))
+    }
+
+    // Collection time in UTC; `None` if the server sent something other than RFC 3339
+    pub fn collected_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::parse_from_rfc3339(&self.collected_at)
+            .ok()
+            .map(|time| time.with_timezone(&chrono::Utc))
+    }
+
+    // Same result reported in `unit`, reference range included, so results from
+    // labs using different units can be compared and trended together
+    pub fn in_unit(&self, unit: Unit) -> SharedResult<ApiLabResult> {
+        let quantity = self.quantity().ok_or_else(|| {
+            SharedError::ValidationError(format!("Lab result {} has no value in a known unit", self.id))
+        })?;
+        let convert = |value: Option<f64>| {
+            value
+                .map(|value| Quantity { value, ..quantity }.value_in(unit))
+                .transpose()
+        };
+
+        let mut result = self.clone();
+        result.value = convert(self.value)?;
+        result.unit = Some(unit.symbol().to_string());
+        if let Some(range) = result.reference_range.as_mut() {
+            range.low = convert(range.low)?;
+            range.high = convert(range.high)?;
+            range.critical_low = convert(range.critical_low)?;
+            range.critical_high = convert(range.critical_high)?;
+        }
+        Ok(result)
+    }
+
+    // Typed model shared with the diagnostics UI; unknown statuses count as preliminary
+    pub fn to_lab_result(&self) -> SharedResult<LabResult> {
+        let parse_time = |value: &str| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .map(|time| time.with_timezone(&chrono::Utc))
+                .map_err(|e| SharedError::ValidationError(format!("Invalid lab result time {}: {}", value, e)))
+        };
+        let value = LabValue::from_parts(self.value, self.unit.as_deref(), self.value_text.as_deref())
+            .ok_or_else(|| SharedError::ValidationError(format!("Lab result {} has no value", self.id)))?;
+
+        let mut result = LabResult::new(
+            self.id.clone(),
+            self.patient_id.clone(),
+            self.test_code.clone(),
+            self.test_name.clone(),
+            value,
+            parse_time(&self.collected_at)?,
+        )
+        .with_status(LabResultStatus::parse(&self.status).unwrap_or(LabResultStatus::Preliminary));
+        result.order_id = self.order_id.clone();
+        result.reference_range = self.reference_range.clone();
+        result.reported_flag = self.abnormal_flag;
+        result.performing_lab = self.performing_lab.clone();
+        result.reported_at = self.reported_at.as_deref().map(parse_time).transpose()?;
+        Ok(result)
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VitalSigns {
-    pub blood_pressure: Option<BloodPressure>,
-    pub heart_rate: Option<u32>,
-    pub temperature: Option<f32>,
-    pub weight: Option<f32>,
-    pub height: Option<f32>,
-    pub bmi: Option<f32>,
-    pub last_measured: String,
+// Wall-clock milliseconds for request timings; unlike `js_sys::Date::now` this also runs in native tests
+fn now_ms() -> f64 {
+    chrono::Utc::now().timestamp_millis() as f64
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BloodPressure {
-    pub systolic: u32,
-    pub diastolic: u32,
+// Compares instants rather than strings, so "+05:30" and "Z" timestamps interleave
+// correctly; results with an unreadable time go last
+fn newest_first(mut results: Vec<ApiLabResult>) -> Vec<ApiLabResult> {
+    results.sort_by_key(|result| std::cmp::Reverse(result.collected_time()));
+    results
 }
 
-// API Client Service
-#[derive(Debug, Clone)]
-pub struct ApiClient {
-    base_url: String,
-    auth_token: Option<String>,
+// Payment Types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    Created,
+    Pending,
+    Authorized,
+    Captured,
+    Failed,
+    Cancelled,
+    Refunded,
+    PartiallyRefunded,
 }
 
-impl ApiClient {
-    pub fn new() -> Self {
-        Self {
-            base_url: API_BASE_URL.to_string(),
-            auth_token: None,
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Created => "created",
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Authorized => "authorized",
+            PaymentStatus::Captured => "captured",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::Cancelled => "cancelled",
+            PaymentStatus::Refunded => "refunded",
+            PaymentStatus::PartiallyRefunded => "partially_refunded",
         }
     }
 
-    pub fn with_auth(token: String) -> Self {
-        Self {
-            base_url: API_BASE_URL.to_string(),
-            auth_token: Some(token),
+    // Parse the status string carried by WebSocket PaymentNotification messages
+    pub fn parse(status: &str) -> Option<Self> {
+        match status.to_ascii_lowercase().as_str() {
+            "created" => Some(PaymentStatus::Created),
+            "pending" | "processing" => Some(PaymentStatus::Pending),
+            "authorized" => Some(PaymentStatus::Authorized),
+            "captured" | "completed" | "success" | "paid" => Some(PaymentStatus::Captured),
+            "failed" => Some(PaymentStatus::Failed),
+            "cancelled" | "canceled" => Some(PaymentStatus::Cancelled),
+            "refunded" => Some(PaymentStatus::Refunded),
+            "partially_refunded" => Some(PaymentStatus::PartiallyRefunded),
+            _ => None,
         }
     }
 
-    // Helper method to build request with auth headers
-    fn build_request(&self, method: &str, endpoint: &str) -> gloo_net::http::RequestBuilder {
-        let url = format!("{}/api/{}/{}", self.base_url, API_VERSION, endpoint);
-        let mut request = match method {
-            "GET" => Request::get(&url),
-            "POST" => Request::post(&url),
-            "PUT" => Request::put(&url),
-            "DELETE" => Request::delete(&url),
-            _ => Request::get(&url),
-        };
+    // No further transitions expected without a new action (e.g. a refund request)
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            PaymentStatus::Captured
+                | PaymentStatus::Failed
+                | PaymentStatus::Cancelled
+                | PaymentStatus::Refunded
+                | PaymentStatus::PartiallyRefunded
+        )
+    }
+}
 
-        if let Some(token) = &self.auth_token {
-            request = request.header("Authorization", &format!("Bearer {}", token));
-        }
+// Booking details the payer needs to decide coverage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EligibilityCheckRequest {
+    pub policy_id: String,
+    pub service_category: ServiceCategory,
+    pub billed_amount: f64,
+    pub currency: String,
+    pub service_date: chrono::NaiveDate,
+}
 
-        request.header("Content-Type", "application/json")
-    }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePaymentIntentRequest {
+    pub booking_id: BookingId,
+    pub amount: f64,
+    pub currency: String,
+    pub payment_method: Option<String>, // "upi", "card", "netbanking", "wallet"
+    pub return_url: Option<String>,
+}
 
-    // Health Check
-    pub async fn health_check() -> Result<HealthCheck, String> {
-        let url = format!("{}/health", API_BASE_URL);
-        let response = Request::get(&url)
-            .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentIntent {
+    pub payment_id: String,
+    pub booking_id: BookingId,
+    pub amount: f64,
+    pub currency: String,
+    pub status: PaymentStatus,
+    pub gateway: String,
+    pub gateway_order_id: String,
+    pub client_secret: Option<String>,
+    pub created_at: String,
+}
 
-        if response.ok() {
-            response
-                .json::<HealthCheck>()
+// Callback payload returned by the payment gateway to the browser
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentVerificationRequest {
+    pub payment_id: String,
+    pub gateway_order_id: String,
+    pub gateway_payment_id: String,
+    pub gateway_signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentVerificationResult {
+    pub payment_id: String,
+    pub verified: bool,
+    pub status: PaymentStatus,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundRequest {
+    pub amount: Option<f64>, // None refunds the full amount
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundResponse {
+    pub refund_id: String,
+    pub payment_id: String,
+    pub amount: f64,
+    pub status: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentStatusResponse {
+    pub payment_id: String,
+    pub booking_id: BookingId,
+    pub amount: f64,
+    pub status: PaymentStatus,
+    pub gateway_response: Option<String>,
+    pub updated_at: String,
+}
+
+impl PaymentStatusResponse {
+    // Same shape the WebSocket layer delivers, so polling and push updates share handlers
+    pub fn to_notification(&self) -> PaymentNotification {
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&self.updated_at)
+            .map(|t| t.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|_| chrono::Utc::now());
+
+        PaymentNotification {
+            payment_id: self.payment_id.clone(),
+            booking_id: self.booking_id.clone(),
+            amount: self.amount,
+            status: self.status.as_str().to_string(),
+            gateway_response: self.gateway_response.clone(),
+            timestamp,
+        }
+    }
+}
+
+// Document Upload Types
+const CHUNKED_UPLOAD_THRESHOLD: usize = 8 * 1024 * 1024;
+const UPLOAD_CHUNK_SIZE: usize = 2 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DocumentCategory {
+    KycDocument,
+    LabReport,
+    Imaging,
+    Prescription,
+    Insurance,
+    Other,
+}
+
+impl DocumentCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentCategory::KycDocument => "kyc-document",
+            DocumentCategory::LabReport => "lab-report",
+            DocumentCategory::Imaging => "imaging",
+            DocumentCategory::Prescription => "prescription",
+            DocumentCategory::Insurance => "insurance",
+            DocumentCategory::Other => "other",
+        }
+    }
+
+    pub fn max_size_bytes(&self) -> u64 {
+        match self {
+            DocumentCategory::Imaging => 2 * 1024 * 1024 * 1024, // 2 GB for DICOM studies
+            DocumentCategory::LabReport => 50 * 1024 * 1024,
+            _ => 20 * 1024 * 1024,
+        }
+    }
+
+    pub fn allowed_content_types(&self) -> &'static [&'static str] {
+        match self {
+            DocumentCategory::Imaging => &["application/dicom", "image/jpeg", "image/png", "application/zip"],
+            DocumentCategory::LabReport => &["application/pdf", "image/jpeg", "image/png", "text/csv"],
+            _ => &["application/pdf", "image/jpeg", "image/png"],
+        }
+    }
+
+    // Size/type checks that only need file metadata, so oversized files are rejected unread
+    pub fn check(&self, content_type: &str, size: u64) -> Result<(), String> {
+        if size == 0 {
+            return Err("File is empty".to_string());
+        }
+        if size > self.max_size_bytes() {
+            return Err(format!(
+                "File too large: {} bytes (limit {} bytes for {})",
+                size,
+                self.max_size_bytes(),
+                self.as_str()
+            ));
+        }
+        if !self.allowed_content_types().contains(&content_type) {
+            return Err(format!(
+                "Unsupported file type '{}' for {}",
+                content_type,
+                self.as_str()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UploadFile {
+    pub filename: String,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+impl UploadFile {
+    pub fn new(filename: impl Into<String>, content_type: impl Into<String>, bytes: Vec<u8>) -> Self {
+        Self {
+            filename: filename.into(),
+            content_type: content_type.into(),
+            bytes,
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.bytes.len() as u64
+    }
+
+    // Client-side size/type checks before any bytes leave the device
+    pub fn validate_for(&self, category: DocumentCategory) -> Result<(), String> {
+        category.check(&self.content_type, self.size())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UploadProgress {
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+}
+
+impl UploadProgress {
+    pub fn percent(&self) -> f32 {
+        if self.total_bytes == 0 {
+            100.0
+        } else {
+            (self.bytes_sent as f32 / self.total_bytes as f32) * 100.0
+        }
+    }
+}
+
+pub type UploadProgressCallback = Arc<dyn Fn(UploadProgress) + Send + Sync + 'static>;
+
+// Streaming Download Types
+const DOWNLOAD_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DownloadProgress {
+    pub bytes_received: u64,
+    pub total_bytes: Option<u64>, // None until the server reports a length
+}
+
+impl DownloadProgress {
+    pub fn percent(&self) -> Option<f32> {
+        self.total_bytes.map(|total| {
+            if total == 0 {
+                100.0
+            } else {
+                (self.bytes_received as f32 / total as f32) * 100.0
+            }
+        })
+    }
+}
+
+pub type DownloadProgressCallback = Arc<dyn Fn(DownloadProgress) + Send + Sync + 'static>;
+
+// Downloaded content kept in a browser Blob rather than wasm memory
+#[derive(Debug, Clone)]
+pub struct DownloadedFile {
+    pub blob: web_sys::Blob,
+    pub size: u64,
+    pub content_type: Option<String>,
+}
+
+impl DownloadedFile {
+    // Object URL for an `<a download>` link; revoke it once the download starts
+    pub fn object_url(&self) -> Result<String, String> {
+        web_sys::Url::create_object_url_with_blob(&self.blob).map_err(|e| format!("Object URL error: {:?}", e))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadedDocument {
+    pub id: String,
+    pub patient_id: PatientId,
+    pub category: DocumentCategory,
+    pub filename: String,
+    pub file_type: String,
+    pub file_size: u64,
+    pub download_url: String,
+    pub uploaded_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSession {
+    pub upload_id: String,
+    pub received_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CreateUploadSessionRequest {
+    patient_id: PatientId,
+    category: DocumentCategory,
+    filename: String,
+    content_type: String,
+    total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Medication {
+    pub id: String,
+    pub name: String,
+    pub dosage: String,
+    pub frequency: String,
+    pub duration: String,
+    pub instructions: String,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthMetrics {
+    pub last_updated: String,
+    pub vital_signs: VitalSigns,
+    pub health_score: u32, // 0-100
+    pub upcoming_appointments: u32,
+    pub active_medications: u32,
+    pub recent_consultations: u32,
+    pub emergency_contacts: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VitalSigns {
+    pub blood_pressure: Option<BloodPressure>,
+    pub heart_rate: Option<u32>,
+    pub temperature: Option<f32>,
+    pub weight: Option<f32>,
+    pub height: Option<f32>,
+    pub bmi: Option<f32>,
+    pub last_measured: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BloodPressure {
+    pub systolic: u32,
+    pub diastolic: u32,
+}
+
+// Interceptor Types
+// Hooks only ever see request metadata, never bodies, so they are safe to log without PHI review
+#[derive(Debug, Clone)]
+pub struct OutgoingRequest {
+    pub method: String,
+    pub endpoint: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl OutgoingRequest {
+    // Add or replace a header; names are case-insensitive
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        self.headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+
+    pub fn remove_header(&mut self, name: &str) {
+        self.headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseInfo {
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub elapsed_ms: f64,
+    pub correlation_id: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestFailure {
+    pub method: String,
+    pub path: String,
+    pub error: String,
+    pub elapsed_ms: f64,
+    pub correlation_id: Option<String>,
+}
+
+pub type RequestHook = Arc<dyn Fn(&mut OutgoingRequest) + Send + Sync + 'static>;
+pub type ResponseHook = Arc<dyn Fn(&ResponseInfo) + Send + Sync + 'static>;
+pub type ErrorHook = Arc<dyn Fn(&RequestFailure) + Send + Sync + 'static>;
+
+#[derive(Clone, Default)]
+struct Interceptors {
+    on_request: Vec<RequestHook>,
+    on_response: Vec<ResponseHook>,
+    on_error: Vec<ErrorHook>,
+}
+
+// Default backend: browser fetch via gloo-net
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchBackend;
+
+impl ApiBackend for FetchBackend {
+    fn execute(&self, request: HttpRequest) -> BackendFuture<'_> {
+        Box::pin(async move {
+            let mut builder = match request.method.as_str() {
+                "POST" => Request::post(&request.url),
+                "PUT" => Request::put(&request.url),
+                "PATCH" => Request::patch(&request.url),
+                "DELETE" => Request::delete(&request.url),
+                _ => Request::get(&request.url),
+            };
+            for (name, value) in &request.headers {
+                builder = builder.header(name, value);
+            }
+
+            let built = match request.body {
+                HttpBody::Empty => builder.build(),
+                HttpBody::Json(body) => builder.body(body),
+                HttpBody::Bytes(bytes) => builder.body(js_sys::Uint8Array::from(bytes.as_slice())),
+                HttpBody::Multipart(parts) => builder.body(multipart_form(&parts)?),
+            };
+            let response = built
+                .map_err(|e| e.to_string())?
+                .send()
                 .await
+                .map_err(|e| e.to_string())?;
+
+            let status = response.status();
+            let headers = response.headers().entries().collect();
+            let body = response.binary().await.map_err(|e| e.to_string())?;
+            Ok(HttpResponse { status, headers, body })
+        })
+    }
+}
+
+// Offline Sync Types
+pub type ConflictCallback = Arc<dyn Fn(QueuedMutation, String) + Send + Sync + 'static>;
+
+// Persists the offline queue in browser localStorage under a fixed key. Only
+// constructed sealed, since queued mutations carry PHI.
+#[derive(Debug, Clone)]
+pub struct LocalStorageQueueStorage {
+    key: String,
+}
+
+impl LocalStorageQueueStorage {
+    pub fn encrypted(key: &str, master_key: MasterKey) -> EncryptedQueueStorage<Self> {
+        EncryptedQueueStorage::new(Self { key: key.to_string() }, master_key)
+    }
+
+    // Under the default key
+    pub fn encrypted_default(master_key: MasterKey) -> EncryptedQueueStorage<Self> {
+        Self::encrypted("mydr24_offline_queue", master_key)
+    }
+
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+    }
+}
+
+impl QueueStorage for LocalStorageQueueStorage {
+    fn load(&self) -> Option<String> {
+        Self::storage().and_then(|s| s.get_item(&self.key).ok().flatten())
+    }
+
+    fn save(&self, data: &str) -> SharedResult<()> {
+        let storage = Self::storage()
+            .ok_or_else(|| SharedError::ConfigurationError("localStorage unavailable".to_string()))?;
+        storage
+            .set_item(&self.key, data)
+            .map_err(|e| SharedError::InternalError(format!("Failed to persist offline queue: {:?}", e)))
+    }
+}
+
+// API Client Service
+#[derive(Clone)]
+pub struct ApiClient {
+    base_url: String,
+    auth_token: Option<SecretString>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    access_guard: Option<Arc<PhiAccessGuard>>,
+    access_reason: Option<AccessReason>,
+    device_key: Option<Arc<DeviceKeyPair>>,
+    request_signing: Option<RequestSigningPolicy>,
+    revocations: Option<RevocationList>,
+    login_throttle: Option<LoginThrottle>,
+    cache: Option<ResponseCache>,
+    offline_queue: Option<OfflineQueue>,
+    conflict_handler: Option<ConflictCallback>,
+    interceptors: Interceptors,
+    instrumentation: Option<Instrumentation>,
+    retry_policy: Option<RetryPolicy>,
+    capabilities: Arc<std::sync::RwLock<Option<ServerCapabilities>>>,
+    backend: Arc<dyn ApiBackend>,
+}
+
+impl std::fmt::Debug for ApiClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiClient")
+            .field("base_url", &self.base_url)
+            .field("auth_token", &self.auth_token.as_ref().map(|_| "[REDACTED]"))
+            .field("audit_sink", &self.audit_sink.is_some())
+            .field("access_guard", &self.access_guard.is_some())
+            .field("access_reason", &self.access_reason)
+            .field("device_id", &self.device_key.as_ref().map(|key| key.device_id()))
+            .field("request_signing", &self.request_signing.as_ref().map(|p| p.endpoints.len()))
+            .field("revocations", &self.revocations)
+            .field("login_throttle", &self.login_throttle)
+            .field("cache", &self.cache.as_ref().map(|c| c.policy()))
+            .field("offline_queue", &self.offline_queue)
+            .field("interceptors", &(
+                self.interceptors.on_request.len(),
+                self.interceptors.on_response.len(),
+                self.interceptors.on_error.len(),
+            ))
+            .field("instrumentation", &self.instrumentation.is_some())
+            .field("retry_policy", &self.retry_policy)
+            .field("capabilities", &self.capabilities())
+            .finish()
+    }
+}
+
+impl ApiClient {
+    pub fn new() -> Self {
+        Self {
+            base_url: API_BASE_URL.to_string(),
+            auth_token: None,
+            audit_sink: None,
+            access_guard: None,
+            access_reason: None,
+            device_key: None,
+            request_signing: None,
+            revocations: None,
+            login_throttle: None,
+            cache: None,
+            offline_queue: None,
+            conflict_handler: None,
+            interceptors: Interceptors::default(),
+            instrumentation: None,
+            retry_policy: None,
+            capabilities: Arc::new(std::sync::RwLock::new(None)),
+            backend: Arc::new(FetchBackend),
+        }
+    }
+
+    pub fn with_auth(token: impl Into<SecretString>) -> Self {
+        Self {
+            auth_token: Some(token.into()),
+            ..Self::new()
+        }
+    }
+
+    // Identity from the bearer token, for UI decisions only; the server checks the signature.
+    // `None` once the token is revoked, so the UI falls back to the sign-in screen.
+    pub fn auth_claims(&self) -> Option<AuthClaims> {
+        self.auth_token
+            .as_ref()
+            .and_then(|token| AuthClaims::from_token_unverified(token.expose()).ok())
+            .filter(|claims| !self.revocations.as_ref().is_some_and(|list| list.is_revoked(claims)))
+    }
+
+    // The cached token, unless the revocation list says it was killed server-side
+    fn bearer_token(&self) -> Option<&str> {
+        let token = self.auth_token.as_ref()?.expose();
+        let Some(revocations) = &self.revocations else {
+            return Some(token);
+        };
+        match AuthClaims::from_token_unverified(token) {
+            Ok(claims) if revocations.is_revoked(&claims) => {
+                log::warn!("Not sending revoked token for user {}", claims.user_id);
+                None
+            }
+            _ => Some(token),
+        }
+    }
+
+    // Check cached tokens against this list before use; keep it current with
+    // `sync_revocations` and `SimpleWebSocketClient::subscribe_revocations`
+    pub fn with_revocation_list(mut self, revocations: RevocationList) -> Self {
+        self.revocations = Some(revocations);
+        self
+    }
+
+    pub fn revocation_list(&self) -> Option<&RevocationList> {
+        self.revocations.as_ref()
+    }
+
+    // Refuse sign-in attempts locally while the account is delayed or locked,
+    // and remember the lockout the server reports so the UI can count down
+    pub fn with_login_throttle(mut self, throttle: LoginThrottle) -> Self {
+        self.login_throttle = Some(throttle);
+        self
+    }
+
+    // Status for the sign-in form's countdown; `None` without a throttle
+    pub fn lockout_status(&self, email: &str) -> Option<LockoutStatus> {
+        let throttle = self.login_throttle.as_ref()?;
+        Some(throttle.status(email, chrono::Utc::now()))
+    }
+
+    // Swap the HTTP transport, e.g. for `MockApiClient` in component tests
+    pub fn with_backend(mut self, backend: Arc<dyn ApiBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    // Wait and retry on 429 within the policy; without one, 429s fail immediately
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    // Route HIPAA audit entries for PHI endpoints to the given sink; PHI endpoints refuse to run without one
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    // Check PHI reads against attribute-based policies before they are sent
+    pub fn with_access_guard(mut self, guard: PhiAccessGuard) -> Self {
+        self.access_guard = Some(Arc::new(guard));
+        self
+    }
+
+    // Reason given in the access-reason prompt, added to the audit entries of
+    // PHI requests made through this client; use on a clone per record opened
+    pub fn with_access_reason(mut self, reason: AccessReason) -> Self {
+        self.access_reason = Some(reason);
+        self
+    }
+
+    // Identify requests with this device; register it once with `register_device`
+    pub fn with_device_key(mut self, key: Arc<DeviceKeyPair>) -> Self {
+        self.device_key = Some(key);
+        self
+    }
+
+    pub fn device_key(&self) -> Option<&DeviceKeyPair> {
+        self.device_key.as_deref()
+    }
+
+    // Sign matching high-sensitivity requests (prescriptions, record exports) with the device key
+    pub fn with_request_signing(mut self, policy: RequestSigningPolicy) -> Self {
+        self.request_signing = Some(policy);
+        self
+    }
+
+    // Opt in to ETag-aware response caching for read-heavy endpoints
+    pub fn with_cache(mut self, policy: CachePolicy) -> Self {
+        self.cache = Some(ResponseCache::new(policy));
+        self
+    }
+
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    // Queue failed mutations while offline and replay them on reconnect
+    pub fn with_offline_queue(mut self, queue: OfflineQueue) -> Self {
+        self.offline_queue = Some(queue);
+        self
+    }
+
+    // Called when a replayed mutation is rejected because server state changed
+    pub fn on_conflict<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(QueuedMutation, String) + Send + Sync + 'static,
+    {
+        self.conflict_handler = Some(Arc::new(callback));
+        self
+    }
+
+    // Inspect or modify outgoing request metadata (correlation IDs, custom auth schemes)
+    pub fn on_request<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut OutgoingRequest) + Send + Sync + 'static,
+    {
+        self.interceptors.on_request.push(Arc::new(hook));
+        self
+    }
+
+    // Observe every HTTP response, including non-2xx statuses
+    pub fn on_response<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ResponseInfo) + Send + Sync + 'static,
+    {
+        self.interceptors.on_response.push(Arc::new(hook));
+        self
+    }
+
+    // Observe transport-level failures (no HTTP response received)
+    pub fn on_error<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RequestFailure) + Send + Sync + 'static,
+    {
+        self.interceptors.on_error.push(Arc::new(hook));
+        self
+    }
+
+    // Tag every request with a correlation ID and record timing metrics and completion events
+    pub fn with_instrumentation(mut self, instrumentation: Instrumentation) -> Self {
+        self.instrumentation = Some(instrumentation);
+        self
+    }
+
+    pub fn instrumentation(&self) -> Option<&Instrumentation> {
+        self.instrumentation.as_ref()
+    }
+
+    // Send a request through the backend and the response and error hooks
+    async fn dispatch(&self, request: impl Into<HttpRequest>) -> Result<HttpResponse, ApiError> {
+        self.send(request.into(), true).await
+    }
+
+    // `dispatch` without the 429 retry: the caller gets the rate-limited response itself, e.g. a
+    // login lockout whose body must reach the throttle, and the request is never resent
+    async fn dispatch_once(&self, request: impl Into<HttpRequest>) -> Result<HttpResponse, ApiError> {
+        self.send(request.into(), false).await
+    }
+
+    async fn send(&self, mut request: HttpRequest, retry_rate_limits: bool) -> Result<HttpResponse, ApiError> {
+        let method = request.method.clone();
+        let path = request.path().to_string();
+
+        // Keep an ID set by an on_request hook so callers can correlate with their own logs
+        let correlation_id = match (&self.instrumentation, request.header(CORRELATION_ID_HEADER)) {
+            (_, Some(existing)) => Some(existing.to_string()),
+            (Some(_), None) => {
+                let id = new_correlation_id();
+                request.headers.push((CORRELATION_ID_HEADER.to_string(), id.clone()));
+                Some(id)
+            }
+            (None, None) => None,
+        };
+
+        let started = now_ms();
+        let result = self.execute_with_retry(request, retry_rate_limits).await;
+        let elapsed_ms = now_ms() - started;
+
+        if let Some(instrumentation) = &self.instrumentation {
+            let outcome = result.as_ref().map(|r| r.status()).map_err(ApiError::to_string);
+            instrumentation.complete(
+                correlation_id.clone().unwrap_or_else(new_correlation_id),
+                &method,
+                &path,
+                outcome.as_ref().copied().map_err(String::as_str),
+                elapsed_ms,
+            );
+        }
+
+        match &result {
+            Ok(response) => {
+                let info = ResponseInfo { method, path, status: response.status(), elapsed_ms, correlation_id };
+                for hook in &self.interceptors.on_response {
+                    hook(&info);
+                }
+            }
+            Err(error) => {
+                let failure = RequestFailure { method, path, error: error.to_string(), elapsed_ms, correlation_id };
+                for hook in &self.interceptors.on_error {
+                    hook(&failure);
+                }
+            }
+        }
+
+        result
+    }
+
+    // Rate-limited responses become `ApiError::RateLimited` once the retry policy is exhausted;
+    // unlike `NetworkError` the server was reached, so callers must not queue them for offline replay.
+    // With `retry_rate_limits` off a 429 is returned as a response on the first attempt.
+    async fn execute_with_retry(&self, request: HttpRequest, retry_rate_limits: bool) -> Result<HttpResponse, ApiError> {
+        let mut attempt = 0;
+        loop {
+            let mut attempt_request = request.clone();
+            self.sign_if_required(&mut attempt_request).map_err(ApiError::AuthError)?;
+            let response = self.backend.execute(attempt_request).await.map_err(ApiError::NetworkError)?;
+            if response.status() != 429 || !retry_rate_limits {
+                return Ok(response);
+            }
+
+            let retry_after_ms = response.retry_after_ms();
+            let wait_ms = self
+                .retry_policy
+                .as_ref()
+                .and_then(|policy| policy.wait_for(attempt, retry_after_ms));
+
+            match wait_ms {
+                Some(wait_ms) => {
+                    gloo_timers::future::TimeoutFuture::new(wait_ms.min(u32::MAX as u64) as u32).await;
+                    attempt += 1;
+                }
+                None => return Err(ApiError::RateLimited { retry_after_ms }),
+            }
+        }
+    }
+
+    // Sign each attempt separately so the server's replay guard accepts retries
+    fn sign_if_required(&self, request: &mut HttpRequest) -> Result<(), String> {
+        let Some(policy) = &self.request_signing else {
+            return Ok(());
+        };
+        let prefix = format!("/api/{}/", API_VERSION);
+        let required = request
+            .path()
+            .strip_prefix(&prefix)
+            .is_some_and(|endpoint| policy.requires_signature(&request.method, endpoint));
+        if !required {
+            return Ok(());
+        }
+        let key = self
+            .device_key
+            .as_ref()
+            .ok_or("Request requires a device signature; use with_device_key")?;
+        sign_request(request, key, chrono::Utc::now());
+        Ok(())
+    }
+
+    pub fn offline_queue(&self) -> Option<&OfflineQueue> {
+        self.offline_queue.as_ref()
+    }
+
+    // Persist a mutation that failed at the network level; returns the error to surface
+    fn queue_offline<B: Serialize>(
+        &self,
+        kind: MutationKind,
+        method: &str,
+        endpoint: &str,
+        body: &B,
+        idempotency_key: Option<&str>,
+        error: ApiError,
+    ) -> String {
+        let error_text = error.to_string();
+        let (Some(queue), ApiError::NetworkError(_)) = (&self.offline_queue, error) else {
+            return error_text;
+        };
+
+        let body = serde_json::to_value(body).unwrap_or(serde_json::Value::Null);
+        let mut mutation = QueuedMutation::new(kind, method, endpoint, body);
+        // Replay with the key of the failed attempt so the server can dedupe if it did arrive
+        if let Some(key) = idempotency_key {
+            mutation = mutation.with_idempotency_key(key);
+        }
+        match queue.enqueue(mutation) {
+            Ok(id) => format!("{} (queued for sync as {})", error_text, id),
+            Err(e) => format!("{} (failed to queue for sync: {})", error_text, e),
+        }
+    }
+
+    // `Err` means the server gave no verdict on this mutation, so the sync pass should stop
+    async fn replay_mutation(&self, mutation: &QueuedMutation) -> Result<ReplayOutcome, ApiError> {
+        let request = match self
+            .build_request(&mutation.method, &mutation.endpoint)
+            .header(IDEMPOTENCY_KEY_HEADER, &mutation.idempotency_key)
+            .json(&mutation.body)
+        {
+            Ok(request) => request,
+            Err(e) => return Ok(ReplayOutcome::Conflict(format!("Failed to serialize queued mutation: {}", e))),
+        };
+
+        match self.dispatch(request).await {
+            Ok(response) if response.ok() => Ok(ReplayOutcome::Succeeded),
+            Ok(response) if matches!(response.status(), 409 | 412 | 422) => {
+                let error_text = response
+                    .text()
+                    .unwrap_or_else(|_| format!("HTTP {}", response.status()));
+                Ok(ReplayOutcome::Conflict(error_text))
+            }
+            Ok(response) => Ok(ReplayOutcome::RetryLater(format!("HTTP {}", response.status()))),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Replay queued mutations in priority order; stops early if still offline
+    pub async fn sync_offline_queue(&self) -> SyncReport {
+        let mut report = SyncReport::default();
+        let Some(queue) = &self.offline_queue else {
+            return report;
+        };
+
+        for mutation in queue.pending() {
+            let outcome = match self.replay_mutation(&mutation).await {
+                Ok(outcome) => outcome,
+                // The server asked us to back off; that is not a failure of this mutation
+                Err(ApiError::RateLimited { .. }) => break,
+                Err(error) => {
+                    if let Err(e) = queue.record_failure(mutation.id, &error.to_string()) {
+                        log::error!("Failed to update offline queue: {}", e);
+                    }
+                    break;
+                }
+            };
+            if let Err(e) = queue.apply_outcome(&mutation, &outcome) {
+                log::error!("Failed to update offline queue: {}", e);
+            }
+
+            match outcome {
+                ReplayOutcome::Succeeded => report.succeeded += 1,
+                ReplayOutcome::Conflict(reason) => {
+                    report.conflicts += 1;
+                    if let Some(handler) = &self.conflict_handler {
+                        handler(mutation, reason);
+                    }
+                }
+                ReplayOutcome::RetryLater(_) => {}
+            }
+        }
+
+        report.remaining = queue.len();
+        report
+    }
+
+    // Periodically replay the queue whenever the browser reports connectivity
+    pub fn start_background_sync(&self, interval_ms: u32) {
+        if self.offline_queue.is_none() {
+            return;
+        }
+
+        let client = self.clone();
+        spawn_local(async move {
+            loop {
+                gloo_timers::future::TimeoutFuture::new(interval_ms).await;
+                let online = web_sys::window()
+                    .map(|w| w.navigator().on_line())
+                    .unwrap_or(false);
+                let pending = client.offline_queue.as_ref().map(|q| !q.is_empty()).unwrap_or(false);
+                if online && pending {
+                    let report = client.sync_offline_queue().await;
+                    log::info!(
+                        "Offline sync: {} succeeded, {} conflicts, {} remaining",
+                        report.succeeded,
+                        report.conflicts,
+                        report.remaining
+                    );
+                }
+            }
+        });
+    }
+
+    // GET through the response cache when enabled, serving stale data while revalidating
+    async fn cached_get<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+        context: &str,
+    ) -> Result<T, String> {
+        let body = match self.session_cache() {
+            None => self.conditional_get(endpoint, params, None, context).await?,
+            Some(cache) => {
+                let key = ResponseCache::key(endpoint, params);
+                match cache.lookup(&key) {
+                    CacheLookup::Fresh(body) => body,
+                    CacheLookup::Stale { body, etag } => {
+                        let client = self.clone();
+                        let endpoint = endpoint.to_string();
+                        let params: Vec<(String, String)> = params
+                            .iter()
+                            .map(|(k, v)| (k.to_string(), v.to_string()))
+                            .collect();
+                        let context = context.to_string();
+                        spawn_local(async move {
+                            let params: Vec<(&str, &str)> =
+                                params.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+                            if let Err(e) = client.conditional_get(&endpoint, &params, etag, &context).await {
+                                log::warn!("Background revalidation of {} failed: {}", endpoint, e);
+                            }
+                        });
+                        body
+                    }
+                    CacheLookup::Expired { etag } => {
+                        self.conditional_get(endpoint, params, etag, context).await?
+                    }
+                    CacheLookup::Miss => self.conditional_get(endpoint, params, None, context).await?,
+                }
+            }
+        };
+
+        serde_json::from_str::<T>(&body).map_err(|e| format!("Parse error: {}", e))
+    }
+
+    // Fetch a response body, sending If-None-Match and updating the cache when enabled
+    async fn conditional_get(
+        &self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+        etag: Option<String>,
+        context: &str,
+    ) -> Result<String, String> {
+        let mut request = self
+            .build_request("GET", endpoint)
+            .query(params.iter().copied());
+        if let Some(etag) = &etag {
+            request = request.header("If-None-Match", etag);
+        }
+
+        let response = self
+            .dispatch(request)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let key = ResponseCache::key(endpoint, params);
+        if response.status() == 304 {
+            if let Some(cache) = self.session_cache() {
+                cache.touch(&key);
+                if let Some(body) = cache.body(&key) {
+                    return Ok(body);
+                }
+            }
+            return Err(format!("{}: not modified but no cached copy", context));
+        }
+
+        if !response.ok() {
+            return Err(format!("{}: {}", context, response.status()));
+        }
+
+        let new_etag = response.header("ETag");
+        let body = response
+            .text()
+            .map_err(|e| format!("Parse error: {}", e))?;
+
+        if let Some(cache) = self.session_cache() {
+            cache.store(&key, body.clone(), new_etag);
+        }
+        Ok(body)
+    }
+
+    // The response cache, emptied first if it holds another user's or token's responses
+    fn session_cache(&self) -> Option<&ResponseCache> {
+        let cache = self.cache.as_ref()?;
+        let owner = self.auth_token.as_ref().map(|token| {
+            let user_id = self.auth_claims().map(|claims| claims.user_id).unwrap_or_default();
+            format!("{}:{}", user_id, token_hash(token.expose()))
+        });
+        cache.bind_owner(owner.as_deref());
+        Some(cache)
+    }
+
+    // Emit an audit entry for sign-in and sign-out; the server records the client IP
+    fn audit_authentication(&self, action: HipaaAction, user_id: &str, outcome: AuditOutcome, status: Option<u16>) {
+        let Some(sink) = &self.audit_sink else {
+            return;
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        let user_agent = web_sys::window().and_then(|w| w.navigator().user_agent().ok());
+        #[cfg(not(target_arch = "wasm32"))]
+        let user_agent = None;
+        let mut details = serde_json::json!({ "client": "api_client" });
+        if let Some(status) = status {
+            details["status"] = status.into();
+        }
+        if let Some(key) = &self.device_key {
+            details["device_id"] = key.device_id().into();
+        }
+        let entry = HipaaAuditEntry::authentication(action, user_id, outcome)
+            .with_request_info(None, user_agent)
+            .with_details(&details);
+        sink.record(entry);
+    }
+
+    // PHI endpoints fail closed: an access that can't be audited isn't made
+    fn require_audit_sink(&self) -> Result<(), String> {
+        match self.audit_sink {
+            Some(_) => Ok(()),
+            None => Err("No audit sink configured; PHI endpoints need with_audit_sink".to_string()),
+        }
+    }
+
+    // Emit an audit entry for PHI access through this client
+    fn audit_phi_access(
+        &self,
+        action: HipaaAction,
+        resource_type: &str,
+        resource_id: &str,
+        patient_id: &PatientId,
+        purpose: &str,
+        outcome: AuditOutcome,
+    ) {
+        let Some(sink) = &self.audit_sink else {
+            return;
+        };
+
+        let mut entry = HipaaAuditEntry::new(action, resource_type, resource_id, outcome)
+            .with_purpose(purpose);
+        match patient_id.to_uuid() {
+            Some(id) => entry = entry.with_patient(id),
+            None => {
+                entry = entry.with_details(&serde_json::json!({ "patient_ref": patient_id }));
+            }
+        }
+        if let Some(reason) = &self.access_reason {
+            entry = reason.annotate(entry);
+        }
+        sink.record(entry);
+    }
+
+    // Denied reads never reach the network; without a guard the server alone decides
+    fn check_record_access(&self, resource_type: &str, patient_id: &PatientId, purpose: &str) -> Result<(), String> {
+        let Some(guard) = &self.access_guard else {
+            return Ok(());
+        };
+        let resource = ResourceAttributes::new(resource_type, patient_id.clone());
+        guard
+            .check(&HipaaAction::Read, &resource, purpose)
+            .map_err(|e| e.to_string())
+    }
+
+    // Helper method to build request with auth headers
+    fn build_request(&self, method: &str, endpoint: &str) -> ApiRequestBuilder {
+        self.build_raw_request(method, endpoint)
+            .header("Content-Type", "application/json")
+    }
+
+    // Shared request/parse path for `api_endpoints!` methods
+    async fn send_json<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        body: Option<serde_json::Value>,
+        context: &str,
+    ) -> Result<T, String> {
+        let builder = self.build_request(method, endpoint);
+        let request = match body {
+            Some(body) => builder.json(&body).map_err(|e| format!("Failed to serialize request: {}", e))?,
+            None => builder.build(),
+        };
+
+        let response = self
+            .dispatch(request)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            response
+                .parse_json::<T>()
+                .map_err(|e| format!("Parse error: {}", e))
+        } else {
+            Err(format!("{}: {}", context, response.error_detail()))
+        }
+    }
+
+    // Request with auth headers only; the browser sets Content-Type for FormData bodies
+    fn build_raw_request(&self, method: &str, endpoint: &str) -> ApiRequestBuilder {
+        let url = format!("{}/api/{}/{}", self.base_url, API_VERSION, endpoint);
+        let mut request = ApiRequestBuilder::new(method, &url);
+
+        let mut outgoing = OutgoingRequest {
+            method: method.to_string(),
+            endpoint: endpoint.to_string(),
+            headers: Vec::new(),
+        };
+        if let Some(token) = self.bearer_token() {
+            outgoing.set_header("Authorization", &format!("Bearer {}", token));
+        }
+        if let Some(key) = &self.device_key {
+            outgoing.set_header(DEVICE_ID_HEADER, key.device_id());
+        }
+        // Non-idempotent methods get a fresh key; callers override it with `.header(IDEMPOTENCY_KEY_HEADER, ..)`
+        if matches!(method, "POST" | "PATCH") {
+            outgoing.set_header(IDEMPOTENCY_KEY_HEADER, &new_idempotency_key());
+        }
+        for hook in &self.interceptors.on_request {
+            hook(&mut outgoing);
+        }
+
+        for (name, value) in &outgoing.headers {
+            request = request.header(name, value);
+        }
+
+        request
+    }
+
+    // Health Check
+    pub async fn health_check() -> Result<HealthCheck, String> {
+        let url = format!("{}/health", API_BASE_URL);
+        let response = FetchBackend
+            .execute(HttpRequest::get(&url))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            response
+                .parse_json::<HealthCheck>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
             Err(format!("API error: {}", response.status()))
         }
     }
 
+    // Single health probe through the configured backend, classified for `BackendStatus`
+    pub async fn check_health(&self) -> HealthCheckOutcome {
+        let url = format!("{}/health", self.base_url);
+        let started = now_ms();
+        let result = self.dispatch(HttpRequest::get(&url)).await;
+        let latency_ms = now_ms() - started;
+
+        match result {
+            Ok(response) if response.ok() => match response.parse_json::<HealthCheck>() {
+                Ok(health) => HealthCheckOutcome::Reachable {
+                    server_status: health.status,
+                    version: Some(health.version),
+                    latency_ms,
+                },
+                Err(e) => HealthCheckOutcome::Reachable {
+                    server_status: format!("unparseable: {}", e),
+                    version: None,
+                    latency_ms,
+                },
+            },
+            // Answering with an error status still means the server is up, just not well
+            Ok(response) => HealthCheckOutcome::Reachable {
+                server_status: format!("HTTP {}", response.status()),
+                version: None,
+                latency_ms,
+            },
+            Err(error) => HealthCheckOutcome::Unreachable { error: error.to_string(), latency_ms },
+        }
+    }
+
+    // Poll /health in the background; the signal feeds `HealthcareAppLayout`'s status indicator.
+    // Polling stops once the signal's reactive owner is disposed.
+    pub fn start_health_polling(&self, config: HealthPollConfig) -> ReadSignal<BackendStatus> {
+        let (status, set_status) = signal(BackendStatus::default());
+        let client = self.clone();
+
+        spawn_local(async move {
+            let mut current = BackendStatus::default();
+            loop {
+                current = current.next(client.check_health().await, &config);
+                if set_status.try_set(current.clone()).is_some() {
+                    break;
+                }
+                gloo_timers::future::TimeoutFuture::new(config.interval_ms).await;
+            }
+        });
+
+        status
+    }
+
+    // Version negotiation; call at startup. Capabilities are cached and shared by clones.
+    pub async fn get_capabilities(&self) -> Result<ServerCapabilities, String> {
+        // Unversioned so clients on any version can discover what the server speaks
+        let url = format!("{}/api/capabilities", self.base_url);
+        let response = self
+            .dispatch(HttpRequest::get(&url))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if !response.ok() {
+            return Err(format!("Failed to get server capabilities: {}", response.error_detail()));
+        }
+
+        let capabilities = response
+            .parse_json::<ServerCapabilities>()
+            .map_err(|e| format!("Parse error: {}", e))?;
+        if !capabilities.supports_client_version() {
+            return Err(format!(
+                "Server supports API versions {:?}, client requires {}",
+                capabilities.supported_versions, API_VERSION
+            ));
+        }
+
+        if let Ok(mut cached) = self.capabilities.write() {
+            *cached = Some(capabilities.clone());
+        }
+        Ok(capabilities)
+    }
+
+    // Last negotiated capabilities, if `get_capabilities` has succeeded
+    pub fn capabilities(&self) -> Option<ServerCapabilities> {
+        self.capabilities.read().ok().and_then(|c| c.clone())
+    }
+
+    // Unknown until negotiated, so gated UI stays hidden rather than failing later
+    pub fn is_feature_enabled(&self, name: &str) -> bool {
+        self.capabilities()
+            .is_some_and(|capabilities| capabilities.features.is_enabled(name))
+    }
+
     // Authentication Endpoints
-    pub async fn login(&self, email: String, password: String) -> Result<LoginResponse, String> {
-        let login_request = LoginRequest { email, password };
+    pub async fn login(&self, email: String, password: impl Into<SecretString>) -> Result<LoginResponse, String> {
+        if let Some(throttle) = &self.login_throttle {
+            throttle.check(&email, chrono::Utc::now()).map_err(|e| format!("Login failed: {}", e))?;
+        }
+        let login_request = LoginRequest { email: email.clone(), password: password.into() };
         
         let request_result = self
             .build_request("POST", "auth/login")
@@ -383,104 +2238,304 @@ impl ApiClient {
             Err(e) => return Err(format!("Failed to serialize login request: {}", e)),
         };
         
-        let response = request
-            .send()
+        // A 429 here is the server's lockout, so it is recorded below rather than retried
+        let response = self
+            .dispatch_once(request)
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
-            response
-                .json::<LoginResponse>()
-                .await
-                .map_err(|e| format!("Parse error: {}", e))
+            let login = response
+                .parse_json::<LoginResponse>()
+                .map_err(|e| format!("Parse error: {}", e))?;
+            self.audit_authentication(HipaaAction::Login, &login.user.id, AuditOutcome::Success, None);
+            if let Some(throttle) = &self.login_throttle {
+                throttle.record_success(&email);
+            }
+            Ok(login)
         } else {
             let error_text = response
                 .text()
-                .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
+            self.audit_authentication(HipaaAction::FailedLogin, &email, AuditOutcome::Failure, Some(response.status()));
+            self.record_login_failure(&email, &response);
             Err(format!("Login failed: {}", error_text))
         }
     }
 
-    pub async fn get_profile(&self) -> Result<UserProfile, String> {
+    // Prefer the server's lockout status, which counts attempts from every device;
+    // fall back to Retry-After, then to counting locally
+    fn record_login_failure(&self, email: &str, response: &HttpResponse) {
+        let Some(throttle) = &self.login_throttle else {
+            return;
+        };
+        let now = chrono::Utc::now();
+        let lockout = response.parse_json::<LoginFailure>().ok().and_then(|failure| failure.lockout);
+        match (lockout, response.retry_after_ms()) {
+            (Some(status), _) => throttle.apply(email, status, now),
+            (None, Some(retry_after_ms)) => {
+                let mut status = throttle.record_failure(email, now);
+                status.retry_at = Some(now + chrono::Duration::milliseconds(retry_after_ms as i64));
+                throttle.apply(email, status, now);
+            }
+            (None, None) if response.status() == 401 => {
+                throttle.record_failure(email, now);
+            }
+            (None, None) => {}
+        }
+    }
+
+    // Single sign-on counterpart of `login`: returns the same platform session
+    pub async fn login_with_oidc(&self, sso: &OidcLogin) -> Result<LoginResponse, String> {
+        let issuer = sso.claims.iss.clone().unwrap_or_default();
+        let request = self
+            .build_request("POST", "auth/oidc/login")
+            .json(&OidcLoginRequest { issuer, id_token: sso.id_token.clone() })
+            .map_err(|e| format!("Failed to serialize login request: {}", e))?;
         let response = self
-            .build_request("GET", "auth/profile")
-            .send()
+            .dispatch(request)
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
-            response
-                .json::<UserProfile>()
-                .await
-                .map_err(|e| format!("Parse error: {}", e))
+            let login = response
+                .parse_json::<LoginResponse>()
+                .map_err(|e| format!("Parse error: {}", e))?;
+            self.audit_authentication(HipaaAction::Login, &login.user.id, AuditOutcome::Success, None);
+            Ok(login)
         } else {
-            Err(format!("Failed to get profile: {}", response.status()))
+            self.audit_authentication(HipaaAction::FailedLogin, &sso.claims.user_id, AuditOutcome::Failure, Some(response.status()));
+            Err(format!("Login failed: {}", response.error_detail()))
         }
     }
 
-    // Dashboard Endpoints
-    pub async fn get_dashboard_stats(&self) -> Result<DashboardStats, String> {
+    // Ends the server session for the current token and drops cached responses; audited even if the request fails
+    pub async fn logout(&self) -> Result<(), String> {
+        let user_id = self.auth_claims().map(|claims| claims.user_id).unwrap_or_default();
+        let request = self.build_request("POST", "auth/logout").build();
+        let result = match self.dispatch(request).await {
+            Ok(response) if response.ok() => Ok(()),
+            Ok(response) => Err(format!("Logout failed: {}", response.error_detail())),
+            Err(e) => Err(e.to_string()),
+        };
+        let outcome = if result.is_ok() { AuditOutcome::Success } else { AuditOutcome::Failure };
+        self.audit_authentication(HipaaAction::Logout, &user_id, outcome, None);
+        // Cached responses are the signed-out user's data, whether or not the server heard us
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+        result
+    }
+
+    api_endpoints! {
+        pub fn get_profile() -> UserProfile = GET "auth/profile", "Failed to get profile";
+        // Exchange the bearer token for a short-lived WebSocket ticket
+        pub fn get_ws_ticket() -> WsTicket = POST "auth/ws-ticket", "Failed to get WebSocket ticket";
+    }
+
+    // Attach a fresh ticket so the WebSocket URL never carries the bearer token,
+    // and the device key so the connect handshake is signed
+    pub async fn websocket_config(&self, config: WebSocketConfig) -> Result<WebSocketConfig, String> {
+        let ticket = self.get_ws_ticket().await?;
+        let config = config.with_ticket(ticket);
+        Ok(match &self.device_key {
+            Some(key) => config.with_device_key(Arc::clone(key)),
+            None => config,
+        })
+    }
+
+    // Device Endpoints
+    api_endpoints! {
+        pub fn register_device_key(registration: DeviceRegistration) -> RegisteredDevice
+            = POST "auth/devices" json(registration), "Failed to register device";
+        pub fn get_devices() -> Vec<RegisteredDevice> = GET "auth/devices", "Failed to get devices";
+        // Ends the device's sessions server-side; the device must register again with a new key
+        pub fn revoke_device(device_id: &str) -> RegisteredDevice
+            = DELETE "auth/devices/{device_id}", "Failed to revoke device";
+    }
+
+    // QR Pairing Endpoints. The desktop starts a pairing and polls it with `complete_device_pairing`;
+    // the signed-in phone inspects the scanned code, then approves or rejects it.
+    api_endpoints! {
+        pub fn start_pairing(request: PairingStartRequest) -> PairingToken
+            = POST "auth/pairing" json(request), "Failed to start device pairing";
+        pub fn inspect_pairing(pairing_id: &str, proof: PairingProof) -> PairingDetails
+            = POST "auth/pairing/{pairing_id}/inspect" json(proof), "Pairing code not recognised";
+        pub fn approve_pairing(pairing_id: &str, proof: PairingProof) -> PairingDetails
+            = POST "auth/pairing/{pairing_id}/approve" json(proof), "Failed to approve sign-in";
+        pub fn reject_pairing(pairing_id: &str, proof: PairingProof) -> PairingDetails
+            = POST "auth/pairing/{pairing_id}/reject" json(proof), "Failed to reject sign-in";
+    }
+
+    // Start a QR sign-in for this client's device key; show `to_qr_payload()` as a QR code
+    pub async fn start_device_pairing(&self, name: &str) -> Result<PairingToken, String> {
+        let key = self.device_key.as_ref().ok_or("No device key configured; use with_device_key")?;
+        self.start_pairing(PairingStartRequest { device: key.registration(name) }).await
+    }
+
+    // Poll once; `Some` when the phone approved, after which the token is spent.
+    // Always signed with the device key from `start_device_pairing`, whatever the
+    // signing policy, as the server won't hand the session to the QR secret alone.
+    pub async fn complete_device_pairing(&self, token: &PairingToken) -> Result<Option<LoginResponse>, String> {
+        let key = self.device_key.as_ref().ok_or("No device key configured; use with_device_key")?;
+        let mut request = self
+            .build_request("POST", &format!("auth/pairing/{}/poll", token.pairing_id))
+            .json(&token.proof())
+            .map_err(|e| format!("Failed to serialize request: {}", e))?;
+        sign_request(&mut request, key, chrono::Utc::now());
         let response = self
-            .build_request("GET", "dashboard/stats")
-            .send()
+            .dispatch(request)
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
+        if !response.ok() {
+            return Err(format!("Failed to check device pairing: {}", response.error_detail()));
+        }
+        let poll = response
+            .parse_json::<PairingPollResponse>()
+            .map_err(|e| format!("Parse error: {}", e))?;
+        match (poll.status, poll.login) {
+            (PairingStatus::Approved | PairingStatus::Completed, Some(login)) => {
+                self.audit_authentication(HipaaAction::Login, &login.user.id, AuditOutcome::Success, None);
+                Ok(Some(login))
+            }
+            (PairingStatus::Pending | PairingStatus::Approved, None) => Ok(None),
+            (PairingStatus::Rejected, _) => Err("Sign-in was rejected on the phone".to_string()),
+            _ => Err("Pairing code is no longer valid; show a new one".to_string()),
+        }
+    }
 
-        if response.ok() {
-            response
-                .json::<DashboardStats>()
-                .await
-                .map_err(|e| format!("Parse error: {}", e))
-        } else {
-            Err(format!("Failed to get dashboard stats: {}", response.status()))
+    // Phone side: read a scanned QR code and fetch what is asking to sign in, for the
+    // approval screen. Show `token.confirmation_code()` next to it.
+    pub async fn inspect_pairing_qr(&self, payload: &str) -> Result<(PairingToken, PairingDetails), String> {
+        let token = PairingToken::from_qr_payload(payload).map_err(|e| e.to_string())?;
+        if token.is_expired(chrono::Utc::now()) {
+            return Err("Pairing code has expired; ask for a new one".to_string());
         }
+        let details = self.inspect_pairing(&token.pairing_id, token.proof()).await?;
+        Ok((token, details))
+    }
+
+    // Fetch revocations added since the last sync and drop entries for expired tokens.
+    // A delta that doesn't continue from the local version is followed by a full sync.
+    // Returns how many new entries arrived.
+    pub async fn sync_revocations(&self) -> Result<usize, String> {
+        let revocations = self.revocations.as_ref().ok_or("No revocation list configured; use with_revocation_list")?;
+        let now = chrono::Utc::now();
+        let added = match revocations.apply(self.fetch_revocations(revocations).await?, now) {
+            Err(_) if revocations.needs_full_sync() => revocations
+                .apply(self.fetch_revocations(revocations).await?, now)
+                .map_err(|e| e.to_string())?,
+            result => result.map_err(|e| e.to_string())?,
+        };
+        revocations.prune(now);
+        Ok(added)
+    }
+
+    async fn fetch_revocations(&self, revocations: &RevocationList) -> Result<RevocationUpdate, String> {
+        let since = revocations.version().to_string();
+        let mut request = self.build_request("GET", "auth/revocations").query([("since", since.as_str())]);
+        if revocations.needs_full_sync() {
+            request = request.query([("full", "true")]);
+        }
+        let response = self
+            .dispatch(request)
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.ok() {
+            return Err(format!("Failed to sync revocations: {}", response.error_detail()));
+        }
+        response
+            .parse_json::<RevocationUpdate>()
+            .map_err(|e| format!("Parse error: {}", e))
+    }
+
+    // Register this client's device key under the signed-in user
+    pub async fn register_device(&self, name: &str) -> Result<RegisteredDevice, String> {
+        let key = self.device_key.as_ref().ok_or("No device key configured; use with_device_key")?;
+        self.register_device_key(key.registration(name)).await
+    }
+
+    // WebAuthn Endpoints
+    api_endpoints! {
+        pub fn begin_webauthn_registration() -> WebAuthnCreationOptions
+            = POST "auth/webauthn/register/options", "Failed to start biometric registration";
+        pub fn finish_webauthn_registration(attestation: WebAuthnAttestation) -> WebAuthnCredential
+            = POST "auth/webauthn/register" json(attestation), "Failed to register biometric credential";
+        pub fn begin_webauthn_step_up() -> WebAuthnRequestOptions
+            = POST "auth/webauthn/assert/options", "Failed to start re-authentication";
+        // The server verifies the signature against the stored public key and sign count
+        pub fn finish_webauthn_step_up(assertion: WebAuthnAssertion) -> WebAuthnCredential
+            = POST "auth/webauthn/assert" json(assertion), "Re-authentication failed";
+        pub fn get_webauthn_credentials() -> Vec<WebAuthnCredential>
+            = GET "auth/webauthn/credentials", "Failed to get biometric credentials";
+        pub fn remove_webauthn_credential(credential_id: &str) -> WebAuthnCredential
+            = DELETE "auth/webauthn/credentials/{credential_id}", "Failed to remove biometric credential";
+    }
+
+    // Dashboard Endpoints
+    pub async fn get_dashboard_stats(&self) -> Result<DashboardStats, String> {
+        self.cached_get("dashboard/stats", &[], "Failed to get dashboard stats").await
     }
 
     // Admin-specific methods
     pub async fn get_admin_dashboard_stats(&self) -> Result<AdminDashboardStats, String> {
-        let response = self
-            .build_request("GET", "admin/dashboard/stats")
-            .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
+        self.cached_get("admin/dashboard/stats", &[], "Failed to get admin dashboard stats").await
+    }
 
-        if response.ok() {
-            response
-                .json::<AdminDashboardStats>()
-                .await
-                .map_err(|e| format!("Parse error: {}", e))
-        } else {
-            Err(format!("Failed to get admin dashboard stats: {}", response.status()))
-        }
+    api_endpoints! {
+        pub fn get_admin_patients() -> Vec<AdminPatient> = GET "admin/patients", "Failed to get admin patients";
+        pub fn get_admin_emergencies() -> Vec<AdminEmergencyCase> = GET "admin/emergencies", "Failed to get admin emergencies";
+        pub fn get_system_health() -> Vec<SystemHealthMetric> = GET "admin/system/health", "Failed to get system health";
+        pub fn get_audit_log_entry(entry_id: &str) -> HipaaAuditEntry
+            = GET "admin/audit-logs/{entry_id}", "Failed to get audit log entry";
     }
 
-    pub async fn get_admin_providers(&self) -> Result<Vec<AdminProvider>, String> {
+    // Break-glass Endpoints; the server applies the same policy and audit rules as BreakGlassManager
+    api_endpoints! {
+        pub fn request_break_glass(request: BreakGlassRequest) -> BreakGlassGrant
+            = POST "break-glass" json(request), "Emergency access was not granted";
+        pub fn end_break_glass(grant_id: &str) -> BreakGlassGrant
+            = POST "break-glass/{grant_id}/end", "Failed to end emergency access";
+        pub fn get_break_glass_reviews() -> Vec<BreakGlassGrant>
+            = GET "admin/break-glass/pending-review", "Failed to get emergency access reviews";
+        pub fn review_break_glass(grant_id: &str, review: BreakGlassReview) -> BreakGlassGrant
+            = POST "admin/break-glass/{grant_id}/review" json(review), "Failed to review emergency access";
+    }
+
+    // Server-side HIPAA audit log search for the compliance screen
+    pub async fn get_audit_logs(&self, query: &AuditQuery) -> Result<AuditLogPage, String> {
+        let params = query.to_query_params();
         let response = self
-            .build_request("GET", "admin/providers")
-            .send()
+            .dispatch(
+                self.build_request("GET", "admin/audit-logs")
+                    .query(params.iter().map(|(k, v)| (*k, v.as_str()))),
+            )
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
             response
-                .json::<Vec<AdminProvider>>()
-                .await
+                .parse_json::<AuditLogPage>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
-            Err(format!("Failed to get admin providers: {}", response.status()))
+            Err(format!("Failed to get audit logs: {}", response.status()))
         }
     }
 
-    pub async fn update_provider_status(&self, provider_id: &str, status: &str) -> Result<AdminProvider, String> {
+    pub async fn get_admin_providers(&self) -> Result<Vec<AdminProvider>, String> {
+        self.cached_get("admin/providers", &[], "Failed to get admin providers").await
+    }
+
+    pub async fn update_provider_status(&self, provider_id: &ProviderId, status: &str) -> Result<AdminProvider, String> {
         #[derive(Serialize)]
         struct StatusUpdate { 
             status: String 
         }
 
         let status_update = StatusUpdate { status: status.to_string() };
+        let endpoint = format!("admin/providers/{}/status", provider_id);
         let request_result = self
-            .build_request("PUT", &format!("admin/providers/{}/status", provider_id))
+            .build_request("PUT", &endpoint)
             .json(&status_update);
 
         let request = match request_result {
@@ -488,47 +2543,36 @@ impl ApiClient {
             Err(e) => return Err(format!("Failed to serialize status update: {}", e)),
         };
 
-        let response = request
-            .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
+        let response = match self.dispatch(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(self.queue_offline(MutationKind::StatusUpdate, "PUT", &endpoint, &status_update, None, e));
+            }
+        };
 
         if response.ok() {
+            if let Some(cache) = &self.cache {
+                cache.invalidate_prefix("admin/providers");
+                cache.invalidate_prefix("providers");
+            }
             response
-                .json::<AdminProvider>()
-                .await
+                .parse_json::<AdminProvider>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
             Err(format!("Failed to update provider status: {}", response.status()))
         }
     }
 
-    pub async fn get_admin_patients(&self) -> Result<Vec<AdminPatient>, String> {
-        let response = self
-            .build_request("GET", "admin/patients")
-            .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
-
-        if response.ok() {
-            response
-                .json::<Vec<AdminPatient>>()
-                .await
-                .map_err(|e| format!("Parse error: {}", e))
-        } else {
-            Err(format!("Failed to get admin patients: {}", response.status()))
-        }
-    }
-
-    pub async fn update_patient_status(&self, patient_id: &str, status: &str) -> Result<AdminPatient, String> {
+    pub async fn update_patient_status(&self, patient_id: &PatientId, status: &str) -> Result<AdminPatient, String> {
         #[derive(Serialize)]
         struct StatusUpdate { 
             status: String 
         }
 
         let status_update = StatusUpdate { status: status.to_string() };
+        let endpoint = format!("admin/patients/{}/status", patient_id);
         let request_result = self
-            .build_request("PUT", &format!("admin/patients/{}/status", patient_id))
+            .build_request("PUT", &endpoint)
             .json(&status_update);
 
         let request = match request_result {
@@ -536,131 +2580,194 @@ impl ApiClient {
             Err(e) => return Err(format!("Failed to serialize status update: {}", e)),
         };
 
-        let response = request
-            .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
+        let response = match self.dispatch(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(self.queue_offline(MutationKind::StatusUpdate, "PUT", &endpoint, &status_update, None, e));
+            }
+        };
 
         if response.ok() {
             response
-                .json::<AdminPatient>()
-                .await
+                .parse_json::<AdminPatient>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
             Err(format!("Failed to update patient status: {}", response.status()))
         }
     }
 
-    pub async fn get_admin_emergencies(&self) -> Result<Vec<AdminEmergencyCase>, String> {
+    // Patient Endpoints
+    api_endpoints! {
+        pub fn get_patients() -> Vec<ApiPatient> = GET "patients", "Failed to get patients";
+        pub fn get_patient(patient_id: &PatientId) -> ApiPatient = GET "patients/{patient_id}", "Failed to get patient";
+    }
+
+    pub async fn search_patients(&self, query: &PatientSearchQuery) -> Result<Paginated<ApiPatient>, String> {
+        let params = query.to_query_params();
         let response = self
-            .build_request("GET", "admin/emergencies")
-            .send()
+            .dispatch(
+                self.build_request("GET", "patients/search")
+                    .query(params.iter().map(|(k, v)| (*k, v.as_str()))),
+            )
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
             response
-                .json::<Vec<AdminEmergencyCase>>()
-                .await
+                .parse_json::<Paginated<ApiPatient>>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
-            Err(format!("Failed to get admin emergencies: {}", response.status()))
+            Err(format!("Failed to search patients: {}", response.status()))
         }
     }
 
-    pub async fn get_system_health(&self) -> Result<Vec<SystemHealthMetric>, String> {
-        let response = self
-            .build_request("GET", "admin/system/health")
-            .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
+    // Provider Endpoints
+    pub async fn get_providers(&self) -> Result<Vec<ApiProvider>, String> {
+        self.cached_get("providers", &[], "Failed to get providers").await
+    }
 
-        if response.ok() {
-            response
-                .json::<Vec<SystemHealthMetric>>()
-                .await
-                .map_err(|e| format!("Parse error: {}", e))
-        } else {
-            Err(format!("Failed to get system health: {}", response.status()))
-        }
+    api_endpoints! {
+        pub fn get_provider(provider_id: &ProviderId) -> ApiProvider = GET "providers/{provider_id}", "Failed to get provider";
     }
 
-    // Patient Endpoints
-    pub async fn get_patients(&self) -> Result<Vec<ApiPatient>, String> {
+    // Ranked provider search; results are ordered by match score, then distance
+    pub async fn search_providers(&self, query: SearchQuery) -> Result<Vec<ProviderSearchResult>, String> {
+        let params = query.to_query_params();
         let response = self
-            .build_request("GET", "patients")
-            .send()
+            .dispatch(
+                self.build_request("GET", "providers/search")
+                    .query(params.iter().map(|(k, v)| (*k, v.as_str()))),
+            )
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
-        if response.ok() {
-            response
-                .json::<Vec<ApiPatient>>()
-                .await
-                .map_err(|e| format!("Parse error: {}", e))
-        } else {
-            Err(format!("Failed to get patients: {}", response.status()))
+        if !response.ok() {
+            return Err(format!("Failed to search providers: {}", response.status()));
         }
+
+        let mut results = response
+            .parse_json::<Vec<ProviderSearchResult>>()
+            .map_err(|e| format!("Parse error: {}", e))?;
+
+        query.apply_distance(&mut results);
+        results.sort_by(|a, b| {
+            b.match_score
+                .total_cmp(&a.match_score)
+                .then_with(|| {
+                    let a_distance = a.distance_km.unwrap_or(f32::MAX);
+                    let b_distance = b.distance_km.unwrap_or(f32::MAX);
+                    a_distance.total_cmp(&b_distance)
+                })
+        });
+        Ok(results)
+    }
+
+    // Terminology Endpoints
+    pub async fn search_icd10_codes(&self, query: &str) -> Result<Vec<Icd10Code>, String> {
+        self.cached_get("terminology/icd10", &[("q", query)], "Failed to search ICD-10 codes").await
+    }
+
+    pub async fn search_medications(&self, query: &str) -> Result<Vec<MedicationConcept>, String> {
+        self.cached_get("terminology/medications", &[("q", query)], "Failed to search medications").await
+    }
+
+    // Availability Endpoints
+    api_endpoints! {
+        pub fn get_provider_availability(provider_id: &ProviderId) -> ProviderAvailability
+            = GET "providers/{provider_id}/availability", "Failed to get provider availability";
     }
 
-    pub async fn get_patient(&self, patient_id: &str) -> Result<ApiPatient, String> {
-        let endpoint = format!("patients/{}", patient_id);
+    // Replaces the provider's weekly schedule and exceptions
+    pub async fn publish_availability(&self, availability: &ProviderAvailability) -> Result<ProviderAvailability, String> {
+        let endpoint = format!("providers/{}/availability", availability.provider_id);
+        let request_body = self
+            .build_request("PUT", &endpoint)
+            .json(availability)
+            .map_err(|e| format!("Failed to serialize availability: {}", e))?;
+
         let response = self
-            .build_request("GET", &endpoint)
-            .send()
+            .dispatch(request_body)
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
+            if let Some(cache) = &self.cache {
+                cache.invalidate_prefix("providers");
+            }
             response
-                .json::<ApiPatient>()
-                .await
+                .parse_json::<ProviderAvailability>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
-            Err(format!("Failed to get patient: {}", response.status()))
+            Err(format!("Failed to publish availability: {}", response.status()))
         }
     }
 
-    // Provider Endpoints
-    pub async fn get_providers(&self) -> Result<Vec<ApiProvider>, String> {
+    pub async fn add_availability_exception(
+        &self,
+        provider_id: &ProviderId,
+        exception: &AvailabilityException,
+    ) -> Result<ProviderAvailability, String> {
+        let endpoint = format!("providers/{}/availability/exceptions", provider_id);
+        let request_body = self
+            .build_request("POST", &endpoint)
+            .json(exception)
+            .map_err(|e| format!("Failed to serialize availability exception: {}", e))?;
+
         let response = self
-            .build_request("GET", "providers")
-            .send()
+            .dispatch(request_body)
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
+            if let Some(cache) = &self.cache {
+                cache.invalidate_prefix("providers");
+            }
             response
-                .json::<Vec<ApiProvider>>()
-                .await
+                .parse_json::<ProviderAvailability>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
-            Err(format!("Failed to get providers: {}", response.status()))
+            Err(format!("Failed to add availability exception: {}", response.status()))
         }
     }
 
-    pub async fn get_provider(&self, provider_id: &str) -> Result<ApiProvider, String> {
-        let endpoint = format!("providers/{}", provider_id);
+    // Open (unbooked) slots for a provider on a given local date
+    pub async fn get_open_slots(
+        &self,
+        provider_id: &ProviderId,
+        date: chrono::NaiveDate,
+    ) -> Result<Vec<AppointmentSlot>, String> {
+        let endpoint = format!("providers/{}/slots", provider_id);
+        let date = date.format("%Y-%m-%d").to_string();
         let response = self
-            .build_request("GET", &endpoint)
-            .send()
+            .dispatch(self.build_request("GET", &endpoint).query([("date", date.as_str())]))
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
-            response
-                .json::<ApiProvider>()
-                .await
-                .map_err(|e| format!("Parse error: {}", e))
+            let mut slots = response
+                .parse_json::<Vec<AppointmentSlot>>()
+                .map_err(|e| format!("Parse error: {}", e))?;
+            slots.sort_by_key(|slot| slot.start_time);
+            Ok(slots)
         } else {
-            Err(format!("Failed to get provider: {}", response.status()))
+            Err(format!("Failed to get open slots: {}", response.status()))
         }
     }
 
     // Emergency Endpoints
     pub async fn create_emergency_request(&self, request: ApiEmergencyRequest) -> Result<ApiEmergencyResponse, String> {
+        self.create_emergency_request_with_key(request, &new_idempotency_key()).await
+    }
+
+    // Pass the same key when retrying so the server never opens a duplicate emergency
+    pub async fn create_emergency_request_with_key(
+        &self,
+        request: ApiEmergencyRequest,
+        idempotency_key: &str,
+    ) -> Result<ApiEmergencyResponse, String> {
         let request_result = self
             .build_request("POST", "emergency/request")
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
             .json(&request);
         
         let request_body = match request_result {
@@ -668,137 +2775,666 @@ impl ApiClient {
             Err(e) => return Err(format!("Failed to serialize emergency request: {}", e)),
         };
         
-        let response = request_body
-            .send()
-            .await
-            .map_err(|e| format!("Network error: {}", e))?;
+        let response = match self.dispatch(request_body).await {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(self.queue_offline(
+                    MutationKind::EmergencyRequest,
+                    "POST",
+                    "emergency/request",
+                    &request,
+                    Some(idempotency_key),
+                    e,
+                ));
+            }
+        };
 
         if response.ok() {
             response
-                .json::<ApiEmergencyResponse>()
-                .await
+                .parse_json::<ApiEmergencyResponse>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
             let error_text = response
                 .text()
-                .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
             Err(format!("Emergency request failed: {}", error_text))
         }
     }
 
+    // HTTP fallback for chat delivery when the WebSocket is unavailable
+    pub async fn send_chat_message(&self, message: &ChatMessage) -> Result<(), String> {
+        // The message ID already identifies the logical send
+        let idempotency_key = message.message_id.clone();
+        let request = self
+            .build_request("POST", "chat/messages")
+            .header(IDEMPOTENCY_KEY_HEADER, &idempotency_key)
+            .json(message)
+            .map_err(|e| format!("Failed to serialize chat message: {}", e))?;
+
+        let response = match self.dispatch(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(self.queue_offline(
+                    MutationKind::ChatMessage,
+                    "POST",
+                    "chat/messages",
+                    message,
+                    Some(&idempotency_key),
+                    e,
+                ));
+            }
+        };
+
+        if response.ok() {
+            Ok(())
+        } else {
+            Err(format!("Failed to send chat message: {}", response.status()))
+        }
+    }
+
     // Patient Registration
-    pub async fn register_patient(&self, request: RegisterRequest) -> Result<LoginResponse, String> {
-        let request_result = self
-            .build_request("POST", "auth/register/patient")
-            .json(&request);
-        
-        let request_body = match request_result {
-            Ok(req) => req,
-            Err(e) => return Err(format!("Failed to serialize registration request: {}", e)),
+    api_endpoints! {
+        pub fn register_patient(request: RegisterRequest) -> LoginResponse
+            = POST "auth/register/patient" json(request), "Patient registration failed";
+    }
+
+    // Multi-step registration
+    api_endpoints! {
+        pub fn start_registration(request: StartRegistrationRequest) -> RegistrationSession
+            = POST "auth/register/start" json(request), "Registration failed";
+        pub fn submit_provider_onboarding(request: ProviderOnboardingRequest) -> RegistrationSession
+            = POST "auth/register/provider/onboarding" json(request), "Provider onboarding failed";
+    }
+
+    pub async fn send_otp(&self, registration_id: &str, channel: OtpChannel) -> Result<OtpChallenge, String> {
+        let request = SendOtpRequest { registration_id: registration_id.to_string(), channel };
+        self.send_json("POST", "auth/otp/send", Some(to_json_body(&request)?), "Sending OTP failed").await
+    }
+
+    // A wrong code is not an error: check `verified` and `attempts_remaining`
+    pub async fn verify_otp(&self, challenge_id: &str, code: &str) -> Result<OtpVerificationResult, String> {
+        validate_otp_code(code).map_err(|e| e.to_string())?;
+        let request = VerifyOtpRequest { challenge_id: challenge_id.to_string(), code: code.to_string() };
+        self.send_json("POST", "auth/otp/verify", Some(to_json_body(&request)?), "OTP verification failed").await
+    }
+
+    pub async fn send_email_verification(&self, registration_id: &str) -> Result<RegistrationSession, String> {
+        let request = serde_json::json!({ "registration_id": registration_id });
+        self.send_json("POST", "auth/email/send-verification", Some(request), "Sending verification email failed")
+            .await
+    }
+
+    // Token comes from the link in the verification email
+    pub async fn verify_email(&self, token: &str) -> Result<RegistrationSession, String> {
+        let request = serde_json::json!({ "token": token });
+        self.send_json("POST", "auth/email/verify", Some(request), "Email verification failed").await
+    }
+
+    api_endpoints! {
+        pub fn complete_registration(registration_id: &str) -> LoginResponse
+            = POST "auth/register/{registration_id}/complete", "Registration failed";
+    }
+
+    // Appointment Endpoints
+    api_endpoints! {
+        pub fn get_patient_appointments() -> Vec<PatientAppointment>
+            = GET "appointments/patient", "Failed to get patient appointments";
+        pub fn book_appointment(request: BookAppointmentRequest) -> PatientAppointment
+            = POST "appointments/book" json(request), "Appointment booking failed";
+    }
+
+    // Medical Records
+    api_endpoints! {
+        pub fn get_patient_medical_records() -> Vec<MedicalRecord>
+            = GET "patient/medical-records", "Failed to get medical records";
+    }
+
+    // Audited medical records access for a specific patient
+    pub async fn get_medical_records(&self, patient_id: &PatientId, purpose: &str) -> Result<Vec<MedicalRecord>, String> {
+        self.require_audit_sink()?;
+        if let Err(denied) = self.check_record_access(MEDICAL_RECORD_RESOURCE, patient_id, purpose) {
+            self.audit_phi_access(HipaaAction::Read, "medical_records", patient_id.as_str(), patient_id, purpose, AuditOutcome::Failure);
+            return Err(denied);
+        }
+        let endpoint = format!("patients/{}/medical-records", patient_id);
+        let result = match self.dispatch(self.build_request("GET", &endpoint)).await {
+            Ok(response) if response.ok() => response
+                .parse_json::<Vec<MedicalRecord>>()
+                .map_err(|e| format!("Parse error: {}", e)),
+            Ok(response) => Err(format!("Failed to get medical records: {}", response.status())),
+            Err(e) => Err(e.to_string()),
         };
-        
-        let response = request_body
-            .send()
+
+        let outcome = if result.is_ok() { AuditOutcome::Success } else { AuditOutcome::Failure };
+        self.audit_phi_access(HipaaAction::Read, "medical_records", patient_id.as_str(), patient_id, purpose, outcome);
+        result
+    }
+
+    pub async fn get_medical_record(&self, patient_id: &PatientId, record_id: &str, purpose: &str) -> Result<MedicalRecord, String> {
+        self.require_audit_sink()?;
+        if let Err(denied) = self.check_record_access(MEDICAL_RECORD_RESOURCE, patient_id, purpose) {
+            self.audit_phi_access(HipaaAction::Read, "medical_record", record_id, patient_id, purpose, AuditOutcome::Failure);
+            return Err(denied);
+        }
+        let endpoint = format!("patients/{}/medical-records/{}", patient_id, record_id);
+        let result = match self.dispatch(self.build_request("GET", &endpoint)).await {
+            Ok(response) if response.ok() => response
+                .parse_json::<MedicalRecord>()
+                .map_err(|e| format!("Parse error: {}", e)),
+            Ok(response) => Err(format!("Failed to get medical record: {}", response.status())),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let outcome = if result.is_ok() { AuditOutcome::Success } else { AuditOutcome::Failure };
+        self.audit_phi_access(HipaaAction::Read, "medical_record", record_id, patient_id, purpose, outcome);
+        result
+    }
+
+    // Audited lab results access, newest first
+    pub async fn get_lab_results(&self, patient_id: &PatientId, purpose: &str) -> Result<Vec<ApiLabResult>, String> {
+        self.require_audit_sink()?;
+        if let Err(denied) = self.check_record_access(LAB_RESULT_RESOURCE, patient_id, purpose) {
+            self.audit_phi_access(HipaaAction::Read, "lab_results", patient_id.as_str(), patient_id, purpose, AuditOutcome::Failure);
+            return Err(denied);
+        }
+        let endpoint = format!("patients/{}/lab-results", patient_id);
+        let result = match self.dispatch(self.build_request("GET", &endpoint)).await {
+            Ok(response) if response.ok() => response
+                .parse_json::<Vec<ApiLabResult>>()
+                .map(newest_first)
+                .map_err(|e| format!("Parse error: {}", e)),
+            Ok(response) => Err(format!("Failed to get lab results: {}", response.status())),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let outcome = if result.is_ok() { AuditOutcome::Success } else { AuditOutcome::Failure };
+        self.audit_phi_access(HipaaAction::Read, "lab_results", patient_id.as_str(), patient_id, purpose, outcome);
+        result
+    }
+
+    pub async fn get_lab_result(&self, patient_id: &PatientId, result_id: &str, purpose: &str) -> Result<ApiLabResult, String> {
+        self.require_audit_sink()?;
+        if let Err(denied) = self.check_record_access(LAB_RESULT_RESOURCE, patient_id, purpose) {
+            self.audit_phi_access(HipaaAction::Read, "lab_result", result_id, patient_id, purpose, AuditOutcome::Failure);
+            return Err(denied);
+        }
+        let endpoint = format!("patients/{}/lab-results/{}", patient_id, result_id);
+        let result = match self.dispatch(self.build_request("GET", &endpoint)).await {
+            Ok(response) if response.ok() => response
+                .parse_json::<ApiLabResult>()
+                .map_err(|e| format!("Parse error: {}", e)),
+            Ok(response) => Err(format!("Failed to get lab result: {}", response.status())),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let outcome = if result.is_ok() { AuditOutcome::Success } else { AuditOutcome::Failure };
+        self.audit_phi_access(HipaaAction::Read, "lab_result", result_id, patient_id, purpose, outcome);
+        result
+    }
+
+    pub async fn upload_medical_record(
+        &self,
+        patient_id: &PatientId,
+        record: UploadMedicalRecordRequest,
+        purpose: &str,
+    ) -> Result<MedicalRecord, String> {
+        self.require_audit_sink()?;
+        let endpoint = format!("patients/{}/medical-records", patient_id);
+        let request_body = self
+            .build_request("POST", &endpoint)
+            .json(&record)
+            .map_err(|e| format!("Failed to serialize medical record: {}", e))?;
+
+        let result = match self.dispatch(request_body).await {
+            Ok(response) if response.ok() => response
+                .parse_json::<MedicalRecord>()
+                .map_err(|e| format!("Parse error: {}", e)),
+            Ok(response) => {
+                let error_text = response
+                    .text()
+                    .unwrap_or_else(|_| "Unknown error".to_string());
+                Err(format!("Medical record upload failed: {}", error_text))
+            }
+            Err(e) => Err(e.to_string()),
+        };
+
+        let (resource_id, outcome) = match &result {
+            Ok(created) => (created.id.as_str(), AuditOutcome::Success),
+            Err(_) => (patient_id.as_str(), AuditOutcome::Failure),
+        };
+        self.audit_phi_access(HipaaAction::Create, "medical_record", resource_id, patient_id, purpose, outcome);
+        result
+    }
+
+    // Notification Preferences
+    pub async fn get_notification_preferences(&self) -> Result<NotificationPreferences, String> {
+        let response = self
+            .dispatch(self.build_request("GET", "users/me/notification-preferences"))
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
             response
-                .json::<LoginResponse>()
-                .await
+                .parse_json::<NotificationPreferences>()
+                .map(NotificationPreferences::normalized)
+                .map_err(|e| format!("Parse error: {}", e))
+        } else {
+            Err(format!("Failed to get notification preferences: {}", response.status()))
+        }
+    }
+
+    pub async fn update_notification_preferences(
+        &self,
+        preferences: NotificationPreferences,
+    ) -> Result<NotificationPreferences, String> {
+        let request_body = self
+            .build_request("PUT", "users/me/notification-preferences")
+            .json(&preferences.normalized())
+            .map_err(|e| format!("Failed to serialize notification preferences: {}", e))?;
+
+        let response = self
+            .dispatch(request_body)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            response
+                .parse_json::<NotificationPreferences>()
+                .map(NotificationPreferences::normalized)
+                .map_err(|e| format!("Parse error: {}", e))
+        } else {
+            Err(format!("Failed to update notification preferences: {}", response.status()))
+        }
+    }
+
+    // Insurance Endpoints
+    api_endpoints! {
+        pub fn get_insurance_policies(patient_id: &PatientId) -> Vec<InsurancePolicy>
+            = GET "patients/{patient_id}/insurance-policies", "Failed to get insurance policies";
+        // Real-time check with the payer; pass the result to PricingEngine::apply_eligibility
+        pub fn check_eligibility(booking_id: &BookingId, request: EligibilityCheckRequest) -> EligibilityCheckResult
+            = POST "bookings/{booking_id}/eligibility" json(request), "Eligibility check failed";
+    }
+
+    // Payment Endpoints
+    pub async fn create_payment_intent(&self, request: CreatePaymentIntentRequest) -> Result<PaymentIntent, String> {
+        self.create_payment_intent_with_key(request, &new_idempotency_key()).await
+    }
+
+    // Reuse the key across retries so a flaky network never creates two intents
+    pub async fn create_payment_intent_with_key(
+        &self,
+        request: CreatePaymentIntentRequest,
+        idempotency_key: &str,
+    ) -> Result<PaymentIntent, String> {
+        let request_body = self
+            .build_request("POST", "payments/intents")
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
+            .json(&request)
+            .map_err(|e| format!("Failed to serialize payment request: {}", e))?;
+
+        let response = self
+            .dispatch(request_body)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            response
+                .parse_json::<PaymentIntent>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
             let error_text = response
                 .text()
-                .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            Err(format!("Patient registration failed: {}", error_text))
+            Err(format!("Payment initiation failed: {}", error_text))
         }
     }
 
-    // Appointment Endpoints
-    pub async fn get_patient_appointments(&self) -> Result<Vec<PatientAppointment>, String> {
+    api_endpoints! {
+        // Forward the gateway callback for server-side signature verification
+        pub fn verify_payment(request: PaymentVerificationRequest) -> PaymentVerificationResult
+            = POST "payments/verify" json(request), "Payment verification failed";
+        pub fn get_payment_status(payment_id: &str) -> PaymentStatusResponse
+            = GET "payments/{payment_id}/status", "Failed to get payment status";
+    }
+
+    pub async fn request_refund(&self, payment_id: &str, request: RefundRequest) -> Result<RefundResponse, String> {
+        self.request_refund_with_key(payment_id, request, &new_idempotency_key()).await
+    }
+
+    pub async fn request_refund_with_key(
+        &self,
+        payment_id: &str,
+        request: RefundRequest,
+        idempotency_key: &str,
+    ) -> Result<RefundResponse, String> {
+        let request_body = self
+            .build_request("POST", &format!("payments/{}/refunds", payment_id))
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
+            .json(&request)
+            .map_err(|e| format!("Failed to serialize refund request: {}", e))?;
+
         let response = self
-            .build_request("GET", "appointments/patient")
-            .send()
+            .dispatch(request_body)
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
             response
-                .json::<Vec<PatientAppointment>>()
-                .await
+                .parse_json::<RefundResponse>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
-            Err(format!("Failed to get patient appointments: {}", response.status()))
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(format!("Refund request failed: {}", error_text))
         }
     }
 
-    pub async fn book_appointment(&self, request: BookAppointmentRequest) -> Result<PatientAppointment, String> {
-        let request_result = self
-            .build_request("POST", "appointments/book")
-            .json(&request);
-        
-        let request_body = match request_result {
-            Ok(req) => req,
-            Err(e) => return Err(format!("Failed to serialize booking request: {}", e)),
-        };
-        
-        let response = request_body
-            .send()
+    // Poll until the payment reaches a terminal status, for when the WebSocket is unavailable
+    pub async fn poll_payment_status(
+        &self,
+        payment_id: &str,
+        interval_ms: u32,
+        max_attempts: u32,
+    ) -> Result<PaymentStatusResponse, String> {
+        let mut last = self.get_payment_status(payment_id).await?;
+        let mut attempts = 1;
+
+        while !last.status.is_terminal() && attempts < max_attempts {
+            gloo_timers::future::TimeoutFuture::new(interval_ms).await;
+            last = self.get_payment_status(payment_id).await?;
+            attempts += 1;
+        }
+
+        Ok(last)
+    }
+
+    // Document Uploads
+    pub async fn upload_document(
+        &self,
+        patient_id: &PatientId,
+        file: UploadFile,
+        category: DocumentCategory,
+        on_progress: Option<UploadProgressCallback>,
+    ) -> Result<UploadedDocument, String> {
+        file.validate_for(category)?;
+
+        if file.bytes.len() > CHUNKED_UPLOAD_THRESHOLD {
+            let session = self.create_upload_session(patient_id, &file, category).await?;
+            return self.upload_chunks(&session, &file, on_progress).await;
+        }
+
+        let request = self
+            .build_raw_request("POST", &format!("patients/{}/documents", patient_id))
+            .multipart(vec![
+                MultipartPart::file("file", &file.filename, &file.content_type, file.bytes.clone()),
+                MultipartPart::text("category", category.as_str()),
+            ]);
+
+        let response = self
+            .dispatch(request)
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
+            if let Some(callback) = &on_progress {
+                callback(UploadProgress { bytes_sent: file.size(), total_bytes: file.size() });
+            }
             response
-                .json::<PatientAppointment>()
-                .await
+                .parse_json::<UploadedDocument>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
             let error_text = response
                 .text()
-                .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            Err(format!("Appointment booking failed: {}", error_text))
+            Err(format!("Document upload failed: {}", error_text))
         }
     }
 
-    // Medical Records
-    pub async fn get_patient_medical_records(&self) -> Result<Vec<MedicalRecord>, String> {
+    // Continue an interrupted chunked upload from the last byte the server acknowledged
+    pub async fn resume_upload(
+        &self,
+        upload_id: &str,
+        file: UploadFile,
+        on_progress: Option<UploadProgressCallback>,
+    ) -> Result<UploadedDocument, String> {
+        let response = self
+            .dispatch(self.build_request("GET", &format!("uploads/{}", upload_id)))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if !response.ok() {
+            return Err(format!("Failed to get upload session: {}", response.status()));
+        }
+
+        let session = response
+            .parse_json::<UploadSession>()
+            .map_err(|e| format!("Parse error: {}", e))?;
+
+        if session.total_bytes != file.size() {
+            return Err("File does not match the interrupted upload".to_string());
+        }
+
+        self.upload_chunks(&session, &file, on_progress).await
+    }
+
+    async fn create_upload_session(
+        &self,
+        patient_id: &PatientId,
+        file: &UploadFile,
+        category: DocumentCategory,
+    ) -> Result<UploadSession, String> {
+        let session_request = CreateUploadSessionRequest {
+            patient_id: patient_id.clone(),
+            category,
+            filename: file.filename.clone(),
+            content_type: file.content_type.clone(),
+            total_bytes: file.size(),
+        };
+
+        let request = self
+            .build_request("POST", "uploads")
+            .json(&session_request)
+            .map_err(|e| format!("Failed to serialize upload session: {}", e))?;
+
         let response = self
-            .build_request("GET", "patient/medical-records")
-            .send()
+            .dispatch(request)
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
             response
-                .json::<Vec<MedicalRecord>>()
-                .await
+                .parse_json::<UploadSession>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
-            Err(format!("Failed to get medical records: {}", response.status()))
+            Err(format!("Failed to start upload: {}", response.status()))
         }
     }
 
-    // Health Metrics
-    pub async fn get_patient_health_metrics(&self) -> Result<HealthMetrics, String> {
+    async fn upload_chunks(
+        &self,
+        session: &UploadSession,
+        file: &UploadFile,
+        on_progress: Option<UploadProgressCallback>,
+    ) -> Result<UploadedDocument, String> {
+        let total = file.bytes.len();
+        let mut offset = session.received_bytes as usize;
+
+        while offset < total {
+            let end = (offset + UPLOAD_CHUNK_SIZE).min(total);
+            let request = self
+                .build_raw_request("PUT", &format!("uploads/{}/chunks", session.upload_id))
+                .header("Content-Type", "application/octet-stream")
+                .header("Content-Range", &format!("bytes {}-{}/{}", offset, end - 1, total))
+                .bytes(file.bytes[offset..end].to_vec());
+
+            let response = self
+                .dispatch(request)
+                .await
+                .map_err(|e| format!("Upload interrupted at byte {} (upload_id {}): {}", offset, session.upload_id, e))?;
+
+            if !response.ok() {
+                return Err(format!(
+                    "Chunk upload failed at byte {} (upload_id {}): {}",
+                    offset,
+                    session.upload_id,
+                    response.status()
+                ));
+            }
+
+            offset = end;
+            if let Some(callback) = &on_progress {
+                callback(UploadProgress { bytes_sent: offset as u64, total_bytes: total as u64 });
+            }
+        }
+
         let response = self
-            .build_request("GET", "patient/health-metrics")
-            .send()
+            .dispatch(self.build_request("POST", &format!("uploads/{}/complete", session.upload_id)))
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| e.to_string())?;
 
         if response.ok() {
             response
-                .json::<HealthMetrics>()
-                .await
+                .parse_json::<UploadedDocument>()
                 .map_err(|e| format!("Parse error: {}", e))
         } else {
-            Err(format!("Failed to get health metrics: {}", response.status()))
+            Err(format!("Failed to complete upload: {}", response.status()))
+        }
+    }
+
+    // Fetch a large resource in Range-request chunks, appending each chunk to a Blob
+    pub async fn download_to_blob(
+        &self,
+        endpoint: &str,
+        on_progress: Option<DownloadProgressCallback>,
+    ) -> Result<DownloadedFile, String> {
+        let parts = js_sys::Array::new();
+        let mut received: u64 = 0;
+        let mut total: Option<u64> = None;
+        let mut content_type: Option<String> = None;
+
+        loop {
+            let request = self
+                .build_raw_request("GET", endpoint)
+                .header("Range", &format!("bytes={}-{}", received, received + DOWNLOAD_CHUNK_SIZE - 1));
+
+            let response = self
+                .dispatch(request)
+                .await
+                .map_err(|e| format!("Download interrupted at byte {}: {}", received, e))?;
+
+            let whole_body = match response.status() {
+                206 => {
+                    let range = response
+                        .content_range()
+                        .ok_or_else(|| "Partial response without Content-Range".to_string())?;
+                    if range.start != received {
+                        return Err(format!("Expected range starting at {}, got {}", received, range.start));
+                    }
+                    total = range.total.or(total);
+                    false
+                }
+                // Server ignored the Range header and sent everything
+                200 if received == 0 => {
+                    total = Some(response.body.len() as u64);
+                    true
+                }
+                // Length was unknown and we asked past the end
+                416 if received > 0 => break,
+                status => return Err(format!("Download failed at byte {}: {}", received, status)),
+            };
+
+            if content_type.is_none() {
+                content_type = response.header("Content-Type");
+            }
+
+            let chunk_len = response.body.len() as u64;
+            parts.push(&js_sys::Uint8Array::from(response.body.as_slice()));
+            received += chunk_len;
+
+            if let Some(callback) = &on_progress {
+                callback(DownloadProgress { bytes_received: received, total_bytes: total });
+            }
+
+            if whole_body || chunk_len == 0 || total.is_some_and(|total| received >= total) {
+                break;
+            }
+        }
+
+        let options = web_sys::BlobPropertyBag::new();
+        if let Some(content_type) = &content_type {
+            options.set_type(content_type);
+        }
+        let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+            .map_err(|e| format!("Blob error: {:?}", e))?;
+
+        Ok(DownloadedFile { blob, size: received, content_type })
+    }
+
+    // GDPR Article 15 export for a patient; audited as a PHI export
+    pub async fn download_gdpr_export(
+        &self,
+        patient_id: &PatientId,
+        export_id: &str,
+        purpose: &str,
+        on_progress: Option<DownloadProgressCallback>,
+    ) -> Result<DownloadedFile, String> {
+        self.require_audit_sink()?;
+        let endpoint = format!("patients/{}/exports/{}/download", patient_id, export_id);
+        let result = self.download_to_blob(&endpoint, on_progress).await;
+
+        let outcome = if result.is_ok() { AuditOutcome::Success } else { AuditOutcome::Failure };
+        self.audit_phi_access(HipaaAction::Export, "gdpr_export", export_id, patient_id, purpose, outcome);
+        result
+    }
+
+    pub async fn download_report(
+        &self,
+        report_id: &str,
+        on_progress: Option<DownloadProgressCallback>,
+    ) -> Result<DownloadedFile, String> {
+        self.download_to_blob(&format!("admin/reports/{}/download", report_id), on_progress)
+            .await
+    }
+
+    // Health Metrics
+    api_endpoints! {
+        pub fn get_patient_health_metrics() -> HealthMetrics
+            = GET "patient/health-metrics", "Failed to get health metrics";
+    }
+}
+
+fn to_json_body<B: Serialize>(body: &B) -> Result<serde_json::Value, String> {
+    serde_json::to_value(body).map_err(|e| format!("Failed to serialize request: {}", e))
+}
+
+// Convert multipart parts into a browser FormData body
+fn multipart_form(parts: &[MultipartPart]) -> Result<web_sys::FormData, String> {
+    let form = web_sys::FormData::new().map_err(|e| format!("FormData error: {:?}", e))?;
+
+    for part in parts {
+        match &part.filename {
+            Some(filename) => {
+                let bytes = js_sys::Array::of1(&js_sys::Uint8Array::from(part.data.as_slice()));
+                let options = web_sys::BlobPropertyBag::new();
+                if let Some(content_type) = &part.content_type {
+                    options.set_type(content_type);
+                }
+                let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&bytes, &options)
+                    .map_err(|e| format!("Blob error: {:?}", e))?;
+                form.append_with_blob_and_filename(&part.name, &blob, filename)
+                    .map_err(|e| format!("FormData error: {:?}", e))?;
+            }
+            None => {
+                let value = String::from_utf8_lossy(&part.data);
+                form.append_with_str(&part.name, &value)
+                    .map_err(|e| format!("FormData error: {:?}", e))?;
+            }
         }
     }
+
+    Ok(form)
 }
 
 // Reactive API Hooks for Leptos
@@ -831,3 +3467,133 @@ pub fn spawn_api_call<F, T, E>(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_backend::MockApiClient;
+    use crate::auth::{AccessPolicySet, SubjectAttributes};
+    use crate::compliance::hipaa::MemoryAuditSink;
+    use std::future::Future;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(output) => output,
+            std::task::Poll::Pending => panic!("mock backend future should resolve immediately"),
+        }
+    }
+
+    fn lab_result(id: &str, collected_at: &str) -> ApiLabResult {
+        ApiLabResult {
+            id: id.to_string(),
+            patient_id: PatientId::new("pat-1"),
+            order_id: None,
+            test_code: "4548-4".to_string(),
+            test_name: "HbA1c".to_string(),
+            value: Some(6.1),
+            value_text: None,
+            unit: Some("%".to_string()),
+            reference_range: None,
+            abnormal_flag: None,
+            status: "final".to_string(),
+            performing_lab: None,
+            collected_at: collected_at.to_string(),
+            reported_at: None,
+        }
+    }
+
+    #[test]
+    fn test_lab_results_sort_by_instant() {
+        // 09:00 IST is 03:30 UTC, so it is older than 04:00Z despite sorting after it as text
+        let results = newest_first(vec![
+            lab_result("ist", "2026-03-01T09:00:00+05:30"),
+            lab_result("utc", "2026-03-01T04:00:00Z"),
+            lab_result("garbled", "yesterday"),
+            lab_result("earlier", "2026-02-28T23:00:00Z"),
+        ]);
+        let ids: Vec<&str> = results.iter().map(|result| result.id.as_str()).collect();
+        assert_eq!(ids, vec!["utc", "ist", "earlier", "garbled"]);
+    }
+
+    #[test]
+    fn test_to_lab_result() {
+        let mut api_result = lab_result("lab-1", "2026-03-01T09:00:00+05:30");
+        api_result.status = "amended".to_string();
+        api_result.reported_at = Some("2026-03-01T06:15:00Z".to_string());
+
+        let result = api_result.to_lab_result().unwrap();
+        assert_eq!(result.value.numeric(), Some(6.1));
+        assert_eq!(result.status, LabResultStatus::Corrected);
+        assert_eq!(result.collected_at.to_rfc3339(), "2026-03-01T03:30:00+00:00");
+        assert_eq!(result.reported_at.map(|time| time.to_rfc3339()).as_deref(), Some("2026-03-01T06:15:00+00:00"));
+
+        api_result.status = "pending-review".to_string();
+        assert_eq!(api_result.to_lab_result().unwrap().status, LabResultStatus::Preliminary);
+
+        api_result.reported_at = Some("yesterday".to_string());
+        assert!(api_result.to_lab_result().is_err());
+
+        api_result.reported_at = None;
+        api_result.value = None;
+        assert!(api_result.to_lab_result().is_err());
+    }
+
+    #[test]
+    fn test_login_applies_server_lockout() {
+        let retry_at = chrono::Utc::now() + chrono::Duration::minutes(15);
+        let failure = LoginFailure {
+            message: "Too many failed attempts".to_string(),
+            lockout: Some(LockoutStatus { failed_attempts: 5, remaining_attempts: 0, retry_at: Some(retry_at), locked: true }),
+        };
+        let backend = MockApiClient::new().with_fixture("POST", "auth/login", 429, &failure);
+        let audit = MemoryAuditSink::new();
+        let client = ApiClient::new()
+            .with_backend(Arc::new(backend.clone()))
+            .with_audit_sink(Arc::new(audit.clone()))
+            .with_retry_policy(RetryPolicy::default())
+            .with_login_throttle(LoginThrottle::new(Default::default()));
+
+        assert!(block_on(client.login("pat@example.com".to_string(), "wrong")).is_err());
+        // Not resent under the retry policy
+        assert_eq!(backend.requests().len(), 1);
+
+        let entries = audit.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, HipaaAction::FailedLogin);
+        assert_eq!(entries[0].outcome, AuditOutcome::Failure);
+        assert_eq!(entries[0].details["status"], 429);
+
+        let status = client.lockout_status("pat@example.com").unwrap();
+        assert!(status.locked);
+        assert_eq!(status.retry_at, Some(retry_at));
+
+        // The next attempt is refused locally until the lockout ends
+        assert!(block_on(client.login("pat@example.com".to_string(), "right")).is_err());
+        assert_eq!(backend.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_lab_results_denied_by_access_guard() {
+        let backend = MockApiClient::new().with_fixture("GET", "patients/p-2/lab-results", 200, &Vec::<ApiLabResult>::new());
+        let audit = MemoryAuditSink::new();
+        let client = ApiClient::new()
+            .with_backend(Arc::new(backend.clone()))
+            .with_audit_sink(Arc::new(audit.clone()))
+            .with_access_guard(PhiAccessGuard::new(
+                AccessPolicySet::default(),
+                SubjectAttributes::new("p-1", Role::Patient),
+            ));
+        let other_patient = PatientId::new("p-2");
+
+        assert!(block_on(client.get_lab_results(&other_patient, "treatment")).is_err());
+        assert!(block_on(client.get_lab_result(&other_patient, "lab-9", "treatment")).is_err());
+        assert!(backend.requests().is_empty());
+
+        let entries = audit.entries();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|entry| entry.outcome == AuditOutcome::Failure));
+        assert_eq!(entries[1].resource_id, "lab-9");
+    }
+}
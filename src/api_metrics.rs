@@ -0,0 +1,231 @@
+//! Request tracing and latency metrics for the API client
+
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::events::{ApiRequestCompleted, ClientEvent, ClientEventEmitter};
+
+/// Header used to propagate correlation IDs to the backend
+pub const CORRELATION_ID_HEADER: &str = "X-Correlation-ID";
+
+/// Samples kept per route; older samples are dropped first
+pub const MAX_SAMPLES_PER_ROUTE: usize = 500;
+
+/// Stands in for path segments that identify a record
+pub const ID_PLACEHOLDER: &str = ":id";
+
+pub fn new_correlation_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Route template for a request path, e.g. "/api/v1/patients/p-17/records/42"
+/// becomes "/api/v1/patients/:id/records/:id". Keeps one metrics series per
+/// route instead of one per record, and keeps patient ids out of dashboards.
+pub fn normalize_path(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    path.split('/')
+        .map(|segment| if is_id_segment(segment) { ID_PLACEHOLDER } else { segment })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Route names never contain digits, except API versions such as "v1"
+fn is_id_segment(segment: &str) -> bool {
+    let is_version = segment
+        .strip_prefix('v')
+        .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()));
+    !is_version && (segment.bytes().any(|b| b.is_ascii_digit()) || Uuid::parse_str(segment).is_ok())
+}
+
+/// Aggregated latency for one route
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub error_count: usize,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct RouteSamples {
+    elapsed_ms: Vec<f64>,
+    error_count: usize,
+}
+
+/// Rolling per-route latency samples, keyed by "METHOD /route" with ids
+/// replaced by [`ID_PLACEHOLDER`]
+#[derive(Debug, Clone, Default)]
+pub struct RequestMetrics {
+    routes: Arc<Mutex<HashMap<String, RouteSamples>>>,
+}
+
+impl RequestMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn route_key(method: &str, path: &str) -> String {
+        format!("{} {}", method.to_uppercase(), normalize_path(path))
+    }
+
+    pub fn record(&self, completed: &ApiRequestCompleted) {
+        let Ok(mut routes) = self.routes.lock() else {
+            return;
+        };
+
+        let samples = routes
+            .entry(Self::route_key(&completed.method, &completed.path))
+            .or_default();
+        if samples.elapsed_ms.len() >= MAX_SAMPLES_PER_ROUTE {
+            samples.elapsed_ms.remove(0);
+        }
+        samples.elapsed_ms.push(completed.elapsed_ms);
+        if !completed.is_success() {
+            samples.error_count += 1;
+        }
+    }
+
+    pub fn summary(&self, method: &str, path: &str) -> Option<LatencySummary> {
+        let routes = self.routes.lock().ok()?;
+        routes.get(&Self::route_key(method, path)).and_then(summarize)
+    }
+
+    /// Summaries for every route seen so far, for dashboards
+    pub fn snapshot(&self) -> HashMap<String, LatencySummary> {
+        self.routes
+            .lock()
+            .map(|routes| {
+                routes
+                    .iter()
+                    .filter_map(|(key, samples)| summarize(samples).map(|s| (key.clone(), s)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn reset(&self) {
+        if let Ok(mut routes) = self.routes.lock() {
+            routes.clear();
+        }
+    }
+}
+
+fn summarize(samples: &RouteSamples) -> Option<LatencySummary> {
+    if samples.elapsed_ms.is_empty() {
+        return None;
+    }
+
+    let mut sorted = samples.elapsed_ms.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let percentile = |p: f64| sorted[((sorted.len() - 1) as f64 * p).round() as usize];
+
+    Some(LatencySummary {
+        count: sorted.len(),
+        error_count: samples.error_count,
+        mean_ms: sorted.iter().sum::<f64>() / sorted.len() as f64,
+        p50_ms: percentile(0.50),
+        p95_ms: percentile(0.95),
+        max_ms: sorted[sorted.len() - 1],
+    })
+}
+
+/// Optional tracing for `ApiClient`: correlation IDs, metrics and completion events
+#[derive(Debug, Clone, Default)]
+pub struct Instrumentation {
+    metrics: RequestMetrics,
+    events: ClientEventEmitter,
+}
+
+impl Instrumentation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn metrics(&self) -> &RequestMetrics {
+        &self.metrics
+    }
+
+    pub fn events(&self) -> &ClientEventEmitter {
+        &self.events
+    }
+
+    /// Record a finished request and emit `ClientEvent::ApiRequestCompleted`
+    pub fn complete(
+        &self,
+        correlation_id: String,
+        method: &str,
+        path: &str,
+        result: Result<u16, &str>,
+        elapsed_ms: f64,
+    ) -> ApiRequestCompleted {
+        let (status, error) = match result {
+            Ok(status) => (Some(status), None),
+            Err(error) => (None, Some(error.to_string())),
+        };
+        let completed = ApiRequestCompleted {
+            correlation_id,
+            method: method.to_string(),
+            path: path.to_string(),
+            status,
+            elapsed_ms,
+            error,
+            completed_at: Utc::now(),
+        };
+
+        self.metrics.record(&completed);
+        self.events.emit(&ClientEvent::ApiRequestCompleted(completed.clone()));
+        completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_summary() {
+        let instrumentation = Instrumentation::new();
+        for elapsed in [10.0, 20.0, 30.0, 40.0] {
+            instrumentation.complete(new_correlation_id(), "get", "/api/v1/providers", Ok(200), elapsed);
+        }
+        instrumentation.complete(new_correlation_id(), "GET", "/api/v1/providers", Ok(503), 100.0);
+
+        let summary = instrumentation.metrics().summary("GET", "/api/v1/providers").unwrap();
+        assert_eq!(summary.count, 5);
+        assert_eq!(summary.error_count, 1);
+        assert_eq!(summary.p50_ms, 30.0);
+        assert_eq!(summary.max_ms, 100.0);
+        assert!(instrumentation.metrics().summary("POST", "/api/v1/providers").is_none());
+    }
+
+    #[test]
+    fn test_routes_group_by_template() {
+        let metrics = Instrumentation::new();
+        metrics.complete(new_correlation_id(), "GET", "/api/v1/patients/pat-17/medical-records/42", Ok(200), 10.0);
+        metrics.complete(new_correlation_id(), "GET", "/api/v1/patients/pat-18/medical-records/7?page=2", Ok(200), 20.0);
+        metrics.complete(new_correlation_id(), "GET", "/api/v1/patients/2c1b7e4a-9d1f-4c7e-8a55-0d6f3e9b1a20/medical-records/9", Ok(200), 30.0);
+
+        let snapshot = metrics.metrics().snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot["GET /api/v1/patients/:id/medical-records/:id"].count, 3);
+        assert_eq!(normalize_path("/api/v2/admin/providers"), "/api/v2/admin/providers");
+    }
+
+    #[test]
+    fn test_completion_event_emitted() {
+        let instrumentation = Instrumentation::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        instrumentation.events().subscribe(move |event| {
+            let ClientEvent::ApiRequestCompleted(completed) = event;
+            sink.lock().unwrap().push(completed.correlation_id.clone());
+        });
+
+        instrumentation.complete("abc".to_string(), "POST", "/api/v1/emergency/request", Err("offline"), 5.0);
+        assert_eq!(*seen.lock().unwrap(), vec!["abc".to_string()]);
+    }
+}
@@ -0,0 +1,230 @@
+//! Domain events for the appointment lifecycle, emitted by the service engine
+//! and consumed by analytics, notifications and the event store
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::errors::SharedResult;
+use crate::event_store::{append_event, DomainEvent, EventStore, StoredEvent};
+use crate::events::{EventBus, Subscription};
+use crate::healthcare_service_engine::ServiceCategory;
+use crate::ids::{AppointmentId, PatientId, ProviderId};
+
+/// A patient asked for care; no provider yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppointmentRequested {
+    pub appointment_id: AppointmentId,
+    /// Service request that created the appointment
+    pub request_id: Uuid,
+    pub patient_id: PatientId,
+    pub service_category: ServiceCategory,
+    pub is_emergency: bool,
+    pub requested_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderAssigned {
+    pub appointment_id: AppointmentId,
+    pub provider_id: ProviderId,
+    /// Set when the appointment moves from another provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_provider_id: Option<ProviderId>,
+    pub assigned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppointmentStarted {
+    pub appointment_id: AppointmentId,
+    pub provider_id: ProviderId,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppointmentCompleted {
+    pub appointment_id: AppointmentId,
+    pub provider_id: ProviderId,
+    pub completed_at: DateTime<Utc>,
+    pub duration_minutes: u32,
+    pub follow_up_needed: bool,
+}
+
+/// Who called the appointment off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelledBy {
+    Patient,
+    Provider,
+    System,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppointmentCancelled {
+    pub appointment_id: AppointmentId,
+    pub cancelled_by: CancelledBy,
+    pub reason: String,
+    pub cancelled_at: DateTime<Utc>,
+}
+
+macro_rules! appointment_event {
+    ($event:ident, $event_type:literal) => {
+        impl DomainEvent for $event {
+            const EVENT_TYPE: &'static str = $event_type;
+            const SCHEMA_VERSION: u32 = 1;
+
+            fn aggregate_id(&self) -> Option<String> {
+                Some(self.appointment_id.to_string())
+            }
+        }
+    };
+}
+
+appointment_event!(AppointmentRequested, "appointment.requested");
+appointment_event!(ProviderAssigned, "appointment.provider_assigned");
+appointment_event!(AppointmentStarted, "appointment.started");
+appointment_event!(AppointmentCompleted, "appointment.completed");
+appointment_event!(AppointmentCancelled, "appointment.cancelled");
+
+/// Any appointment lifecycle event, for consumers that handle them together
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum AppointmentEvent {
+    Requested(AppointmentRequested),
+    ProviderAssigned(ProviderAssigned),
+    Started(AppointmentStarted),
+    Completed(AppointmentCompleted),
+    Cancelled(AppointmentCancelled),
+}
+
+impl AppointmentEvent {
+    pub fn appointment_id(&self) -> &AppointmentId {
+        match self {
+            AppointmentEvent::Requested(e) => &e.appointment_id,
+            AppointmentEvent::ProviderAssigned(e) => &e.appointment_id,
+            AppointmentEvent::Started(e) => &e.appointment_id,
+            AppointmentEvent::Completed(e) => &e.appointment_id,
+            AppointmentEvent::Cancelled(e) => &e.appointment_id,
+        }
+    }
+
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            AppointmentEvent::Requested(e) => e.requested_at,
+            AppointmentEvent::ProviderAssigned(e) => e.assigned_at,
+            AppointmentEvent::Started(e) => e.started_at,
+            AppointmentEvent::Completed(e) => e.completed_at,
+            AppointmentEvent::Cancelled(e) => e.cancelled_at,
+        }
+    }
+
+    /// Decode a stored record; `None` for events outside the appointment lifecycle
+    pub fn from_stored(stored: &StoredEvent) -> SharedResult<Option<Self>> {
+        Ok(match stored.event_type.as_str() {
+            AppointmentRequested::EVENT_TYPE => stored.decode()?.map(AppointmentEvent::Requested),
+            ProviderAssigned::EVENT_TYPE => stored.decode()?.map(AppointmentEvent::ProviderAssigned),
+            AppointmentStarted::EVENT_TYPE => stored.decode()?.map(AppointmentEvent::Started),
+            AppointmentCompleted::EVENT_TYPE => stored.decode()?.map(AppointmentEvent::Completed),
+            AppointmentCancelled::EVENT_TYPE => stored.decode()?.map(AppointmentEvent::Cancelled),
+            _ => None,
+        })
+    }
+}
+
+fn record<E: DomainEvent>(bus: &EventBus, store: &Arc<dyn EventStore>) -> Subscription {
+    let store = Arc::clone(store);
+    bus.subscribe(move |event: &E| {
+        if let Err(e) = append_event(store.as_ref(), event) {
+            log::error!("Failed to record {}: {}", E::EVENT_TYPE, e);
+        }
+    })
+}
+
+/// Append every appointment lifecycle event published on `bus` to `store`
+pub fn record_appointment_events(bus: &EventBus, store: Arc<dyn EventStore>) -> Vec<Subscription> {
+    vec![
+        record::<AppointmentRequested>(bus, &store),
+        record::<ProviderAssigned>(bus, &store),
+        record::<AppointmentStarted>(bus, &store),
+        record::<AppointmentCompleted>(bus, &store),
+        record::<AppointmentCancelled>(bus, &store),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_store::{replay, MemoryEventStore};
+
+    #[test]
+    fn test_lifecycle_events_are_recorded_and_replayed() {
+        let bus = EventBus::new();
+        let store: Arc<dyn EventStore> = Arc::new(MemoryEventStore::new());
+        record_appointment_events(&bus, Arc::clone(&store));
+
+        let appointment_id = AppointmentId::new("apt-7");
+        let provider_id = ProviderId::new("dr-3");
+        bus.publish(AppointmentRequested {
+            appointment_id: appointment_id.clone(),
+            request_id: Uuid::new_v4(),
+            patient_id: PatientId::new("pat-1"),
+            service_category: ServiceCategory::HomeCareServices,
+            is_emergency: false,
+            requested_at: Utc::now(),
+        });
+        bus.publish(ProviderAssigned {
+            appointment_id: appointment_id.clone(),
+            provider_id: provider_id.clone(),
+            previous_provider_id: None,
+            assigned_at: Utc::now(),
+        });
+        bus.publish(AppointmentCancelled {
+            appointment_id: appointment_id.clone(),
+            cancelled_by: CancelledBy::Patient,
+            reason: "Feeling better".to_string(),
+            cancelled_at: Utc::now(),
+        });
+
+        let mut kinds = Vec::new();
+        replay(store.as_ref(), 0, |stored| {
+            assert_eq!(stored.aggregate_id.as_deref(), Some("apt-7"));
+            let event = AppointmentEvent::from_stored(stored)?.expect("lifecycle event");
+            assert_eq!(event.appointment_id(), &appointment_id);
+            kinds.push(stored.event_type.clone());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(kinds, ["appointment.requested", "appointment.provider_assigned", "appointment.cancelled"]);
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = AppointmentEvent::Started(AppointmentStarted {
+            appointment_id: AppointmentId::new("apt-1"),
+            provider_id: ProviderId::new("dr-1"),
+            started_at: Utc::now(),
+        });
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "started");
+        assert_eq!(json["data"]["appointment_id"], "apt-1");
+        assert_eq!(serde_json::from_value::<AppointmentEvent>(json).unwrap(), event);
+    }
+
+    #[test]
+    fn test_service_engine_emits_lifecycle_events() {
+        let bus = EventBus::new();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        bus.subscribe(move |e: &AppointmentCompleted| sink.lock().unwrap().push(e.clone()));
+
+        let engine = crate::healthcare_service_engine::HealthcareServiceEngine::new().with_event_bus(bus.clone());
+        let appointment_id = AppointmentId::generate();
+        engine.assign_provider(appointment_id.clone(), ProviderId::new("dr-1"), None);
+        let started = engine.start_appointment(appointment_id.clone(), ProviderId::new("dr-1"));
+        let completed = engine.complete_appointment(&started, true);
+
+        assert_eq!(completed.duration_minutes, 0);
+        assert_eq!(*seen.lock().unwrap(), vec![completed]);
+        assert_eq!(bus.subscriber_count::<AppointmentCompleted>(), 1);
+    }
+}
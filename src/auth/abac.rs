@@ -0,0 +1,442 @@
+//! Attribute-based access policies for PHI, checked on top of the coarse role matrix
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::break_glass::BreakGlassManager;
+use super::jwt::AuthClaims;
+use super::rbac::{Permission, Role};
+use crate::compliance::hipaa::HipaaAction;
+use crate::errors::{SharedError, SharedResult};
+use crate::ids::PatientId;
+
+/// Resource type used by the medical record policies
+pub const MEDICAL_RECORD_RESOURCE: &str = "medical_record";
+
+/// Resource type for lab results; the default policies treat them like medical records
+pub const LAB_RESULT_RESOURCE: &str = "lab_result";
+
+/// How carefully a record must be handled. Restricted covers records with
+/// extra legal protection, e.g. psychotherapy notes or HIV status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sensitivity {
+    #[default]
+    Normal,
+    Sensitive,
+    Restricted,
+}
+
+/// Who is asking
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubjectAttributes {
+    pub user_id: String,
+    pub role: Role,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    /// Patients the provider is currently caring for
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assigned_patients: Vec<PatientId>,
+}
+
+impl SubjectAttributes {
+    pub fn new(user_id: impl Into<String>, role: Role) -> Self {
+        Self {
+            user_id: user_id.into(),
+            role,
+            org_id: None,
+            assigned_patients: Vec::new(),
+        }
+    }
+
+    pub fn from_claims(claims: &AuthClaims) -> Self {
+        Self {
+            org_id: claims.org_id.clone(),
+            ..Self::new(claims.user_id.clone(), claims.role)
+        }
+    }
+
+    pub fn with_org(mut self, org_id: impl Into<String>) -> Self {
+        self.org_id = Some(org_id.into());
+        self
+    }
+
+    pub fn with_assigned_patients(mut self, patients: Vec<PatientId>) -> Self {
+        self.assigned_patients = patients;
+        self
+    }
+}
+
+/// What is being accessed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceAttributes {
+    pub resource_type: String,
+    pub patient_id: PatientId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub sensitivity: Sensitivity,
+}
+
+impl ResourceAttributes {
+    pub fn new(resource_type: impl Into<String>, patient_id: PatientId) -> Self {
+        Self {
+            resource_type: resource_type.into(),
+            patient_id,
+            org_id: None,
+            sensitivity: Sensitivity::Normal,
+        }
+    }
+
+    pub fn with_org(mut self, org_id: impl Into<String>) -> Self {
+        self.org_id = Some(org_id.into());
+        self
+    }
+
+    pub fn with_sensitivity(mut self, sensitivity: Sensitivity) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+}
+
+/// A provider's working hours, half-open `[start, end)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShiftWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl ShiftWindow {
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+/// Circumstances of the request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    pub at: DateTime<Utc>,
+    /// HIPAA purpose of use, e.g. "treatment"
+    pub purpose: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shift: Option<ShiftWindow>,
+}
+
+impl Environment {
+    pub fn new(at: DateTime<Utc>, purpose: impl Into<String>) -> Self {
+        Self { at, purpose: purpose.into(), shift: None }
+    }
+
+    pub fn with_shift(mut self, shift: Option<ShiftWindow>) -> Self {
+        self.shift = shift;
+        self
+    }
+}
+
+/// Test over the subject, resource and environment. Serialized with a
+/// `type` tag so policies can live in configuration files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    RoleIn { roles: Vec<Role> },
+    HasPermission { permission: Permission },
+    /// The subject is the patient the resource belongs to
+    IsPatient,
+    AssignedToPatient,
+    /// Subject and resource belong to the same organisation
+    SameOrganization,
+    SensitivityAtMost { level: Sensitivity },
+    /// The request falls inside the subject's current shift
+    OnShift,
+    PurposeIn { purposes: Vec<String> },
+    All { conditions: Vec<Condition> },
+    Any { conditions: Vec<Condition> },
+    Not { condition: Box<Condition> },
+}
+
+impl Condition {
+    pub fn negate(condition: Condition) -> Self {
+        Condition::Not { condition: Box::new(condition) }
+    }
+
+    pub fn matches(&self, subject: &SubjectAttributes, resource: &ResourceAttributes, environment: &Environment) -> bool {
+        match self {
+            Condition::RoleIn { roles } => roles.contains(&subject.role),
+            Condition::HasPermission { permission } => subject.role.has_permission(*permission),
+            Condition::IsPatient => subject.user_id == resource.patient_id.as_str(),
+            Condition::AssignedToPatient => subject.assigned_patients.contains(&resource.patient_id),
+            Condition::SameOrganization => subject.org_id.is_some() && subject.org_id == resource.org_id,
+            Condition::SensitivityAtMost { level } => resource.sensitivity <= *level,
+            Condition::OnShift => environment.shift.is_some_and(|shift| shift.contains(environment.at)),
+            Condition::PurposeIn { purposes } => purposes.iter().any(|p| p.eq_ignore_ascii_case(&environment.purpose)),
+            Condition::All { conditions } => conditions.iter().all(|c| c.matches(subject, resource, environment)),
+            Condition::Any { conditions } => conditions.iter().any(|c| c.matches(subject, resource, environment)),
+            Condition::Not { condition } => !condition.matches(subject, resource, environment),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyEffect {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessPolicy {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    pub effect: PolicyEffect,
+    pub actions: Vec<HipaaAction>,
+    /// Resource types the policy covers; empty means all
+    #[serde(default)]
+    pub resource_types: Vec<String>,
+    pub condition: Condition,
+}
+
+impl AccessPolicy {
+    fn applies_to(&self, action: &HipaaAction, resource: &ResourceAttributes) -> bool {
+        self.actions.contains(action)
+            && (self.resource_types.is_empty() || self.resource_types.contains(&resource.resource_type))
+    }
+}
+
+/// Outcome of evaluating a request, with the policy that decided it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessDecision {
+    pub allowed: bool,
+    pub policy_id: Option<String>,
+}
+
+impl AccessDecision {
+    pub fn into_result(self) -> SharedResult<()> {
+        if self.allowed {
+            return Ok(());
+        }
+        Err(SharedError::AuthorizationError(match self.policy_id {
+            Some(policy_id) => format!("Access denied by policy '{}'", policy_id),
+            None => "Access denied: no policy allows this request".to_string(),
+        }))
+    }
+}
+
+/// Policies evaluated deny-overrides: any matching deny wins, otherwise a
+/// matching allow is needed. Requests no policy covers are denied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessPolicySet {
+    pub policies: Vec<AccessPolicy>,
+}
+
+impl Default for AccessPolicySet {
+    /// Medical record and lab result reads: patients see their own records; clinicians see
+    /// their assigned patients' records while on shift; restricted records
+    /// are read by others only for treatment
+    fn default() -> Self {
+        let read = vec![HipaaAction::Read];
+        let records = vec![MEDICAL_RECORD_RESOURCE.to_string(), LAB_RESULT_RESOURCE.to_string()];
+        Self {
+            policies: vec![
+                AccessPolicy {
+                    id: "patient-own-records".to_string(),
+                    description: "Patients may read their own records".to_string(),
+                    effect: PolicyEffect::Allow,
+                    actions: read.clone(),
+                    resource_types: records.clone(),
+                    condition: Condition::All {
+                        conditions: vec![
+                            Condition::HasPermission { permission: Permission::ViewOwnRecords },
+                            Condition::IsPatient,
+                        ],
+                    },
+                },
+                AccessPolicy {
+                    id: "care-team-on-shift".to_string(),
+                    description: "Clinicians may read assigned patients' records during their shift".to_string(),
+                    effect: PolicyEffect::Allow,
+                    actions: read.clone(),
+                    resource_types: records.clone(),
+                    condition: Condition::All {
+                        conditions: vec![
+                            Condition::HasPermission { permission: Permission::ViewPatientRecords },
+                            Condition::AssignedToPatient,
+                            Condition::OnShift,
+                        ],
+                    },
+                },
+                AccessPolicy {
+                    id: "restricted-treatment-only".to_string(),
+                    description: "Restricted records are read by others only for treatment".to_string(),
+                    effect: PolicyEffect::Deny,
+                    actions: read,
+                    resource_types: records,
+                    condition: Condition::All {
+                        conditions: vec![
+                            Condition::negate(Condition::SensitivityAtMost { level: Sensitivity::Sensitive }),
+                            Condition::negate(Condition::IsPatient),
+                            Condition::negate(Condition::PurposeIn { purposes: vec!["treatment".to_string()] }),
+                        ],
+                    },
+                },
+            ],
+        }
+    }
+}
+
+impl AccessPolicySet {
+    /// Load policies from a JSON configuration document
+    pub fn from_json(json: &str) -> SharedResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn evaluate(
+        &self,
+        subject: &SubjectAttributes,
+        action: &HipaaAction,
+        resource: &ResourceAttributes,
+        environment: &Environment,
+    ) -> AccessDecision {
+        let mut allowed_by = None;
+        for policy in self.policies.iter().filter(|policy| policy.applies_to(action, resource)) {
+            if !policy.condition.matches(subject, resource, environment) {
+                continue;
+            }
+            match policy.effect {
+                PolicyEffect::Deny => {
+                    return AccessDecision { allowed: false, policy_id: Some(policy.id.clone()) };
+                }
+                PolicyEffect::Allow => {
+                    allowed_by.get_or_insert_with(|| policy.id.clone());
+                }
+            }
+        }
+        AccessDecision { allowed: allowed_by.is_some(), policy_id: allowed_by }
+    }
+}
+
+/// Policies bound to the signed-in user, for checking requests before they
+/// leave the client. The server enforces the same policies authoritatively.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhiAccessGuard {
+    pub policies: AccessPolicySet,
+    pub subject: SubjectAttributes,
+    pub shift: Option<ShiftWindow>,
+}
+
+impl PhiAccessGuard {
+    pub fn new(policies: AccessPolicySet, subject: SubjectAttributes) -> Self {
+        Self { policies, subject, shift: None }
+    }
+
+    pub fn with_shift(mut self, shift: ShiftWindow) -> Self {
+        self.shift = Some(shift);
+        self
+    }
+
+    pub fn check(&self, action: &HipaaAction, resource: &ResourceAttributes, purpose: &str) -> SharedResult<()> {
+        self.check_at(action, resource, purpose, Utc::now())
+    }
+
+    /// [`check`](Self::check) as of `now`
+    pub fn check_at(
+        &self,
+        action: &HipaaAction,
+        resource: &ResourceAttributes,
+        purpose: &str,
+        now: DateTime<Utc>,
+    ) -> SharedResult<()> {
+        let environment = Environment::new(now, purpose).with_shift(self.shift);
+        self.policies
+            .evaluate(&self.subject, action, resource, &environment)
+            .into_result()
+    }
+
+    /// [`check`](Self::check), falling back to the user's active break-glass
+    /// grant for the patient when the policies deny. Such access is audited
+    /// as critical and flagged for review; without a grant the policy's
+    /// denial is returned.
+    pub fn check_or_break_glass(
+        &self,
+        action: &HipaaAction,
+        resource: &ResourceAttributes,
+        purpose: &str,
+        break_glass: &mut BreakGlassManager,
+        now: DateTime<Utc>,
+    ) -> SharedResult<()> {
+        let denied = match self.check_at(action, resource, purpose, now) {
+            Ok(()) => return Ok(()),
+            Err(denied) => denied,
+        };
+        break_glass
+            .authorize(&self.subject, action, resource, now)
+            .map(|_| ())
+            .map_err(|_| denied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn record(patient: &str) -> ResourceAttributes {
+        ResourceAttributes::new(MEDICAL_RECORD_RESOURCE, PatientId::new(patient))
+    }
+
+    #[test]
+    fn test_default_medical_record_policies() {
+        let policies = AccessPolicySet::default();
+        let now = Utc::now();
+        let shift = ShiftWindow { start: now - Duration::hours(2), end: now + Duration::hours(6) };
+        let on_shift = Environment::new(now, "treatment").with_shift(Some(shift));
+        let read = HipaaAction::Read;
+
+        let patient = SubjectAttributes::new("p-1", Role::Patient);
+        assert!(policies.evaluate(&patient, &read, &record("p-1"), &on_shift).allowed);
+        assert!(!policies.evaluate(&patient, &read, &record("p-2"), &on_shift).allowed);
+        let own_lab = ResourceAttributes::new(LAB_RESULT_RESOURCE, PatientId::new("p-1"));
+        assert!(policies.evaluate(&patient, &read, &own_lab, &on_shift).allowed);
+
+        let nurse = SubjectAttributes::new("n-1", Role::Nurse).with_assigned_patients(vec![PatientId::new("p-1")]);
+        let decision = policies.evaluate(&nurse, &read, &record("p-1"), &on_shift);
+        assert_eq!(decision.policy_id.as_deref(), Some("care-team-on-shift"));
+        assert!(decision.allowed);
+        assert!(!policies.evaluate(&nurse, &read, &record("p-2"), &on_shift).allowed);
+        let off_shift = Environment::new(now + Duration::hours(7), "treatment").with_shift(Some(shift));
+        assert!(!policies.evaluate(&nurse, &read, &record("p-1"), &off_shift).allowed);
+
+        // Restricted records: treatment only, but always visible to the patient
+        let restricted = record("p-1").with_sensitivity(Sensitivity::Restricted);
+        assert!(policies.evaluate(&nurse, &read, &restricted, &on_shift).allowed);
+        let billing = Environment::new(now, "payment").with_shift(Some(shift));
+        let decision = policies.evaluate(&nurse, &read, &restricted, &billing);
+        assert_eq!(decision.policy_id.as_deref(), Some("restricted-treatment-only"));
+        assert!(decision.into_result().is_err());
+        assert!(policies.evaluate(&patient, &read, &restricted, &billing).allowed);
+
+        // An admin role outranks providers but has no clinical record access
+        let admin = SubjectAttributes::new("a-1", Role::Admin).with_assigned_patients(vec![PatientId::new("p-1")]);
+        assert!(!policies.evaluate(&admin, &read, &record("p-1"), &on_shift).allowed);
+    }
+
+    #[test]
+    fn test_policies_from_config() {
+        let json = serde_json::to_string(&AccessPolicySet::default()).unwrap();
+        assert_eq!(AccessPolicySet::from_json(&json).unwrap(), AccessPolicySet::default());
+
+        let config = r#"{"policies":[
+            {"id":"org-staff","effect":"allow","actions":["Read"],
+             "condition":{"type":"all","conditions":[
+                {"type":"role_in","roles":["org_admin"]},
+                {"type":"same_organization"}]}}
+        ]}"#;
+        let policies = AccessPolicySet::from_json(config).unwrap();
+        let manager = SubjectAttributes::new("m-1", Role::OrgAdmin).with_org("clinic-7");
+        let env = Environment::new(Utc::now(), "operations");
+        assert!(policies.evaluate(&manager, &HipaaAction::Read, &record("p-1").with_org("clinic-7"), &env).allowed);
+        assert!(!policies.evaluate(&manager, &HipaaAction::Read, &record("p-1").with_org("clinic-9"), &env).allowed);
+        assert!(!policies.evaluate(&manager, &HipaaAction::Update, &record("p-1").with_org("clinic-7"), &env).allowed);
+        assert!(AccessPolicySet::from_json(r#"{"policies":[{"id":"x","effect":"maybe"}]}"#).is_err());
+    }
+}
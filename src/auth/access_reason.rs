@@ -0,0 +1,273 @@
+//! Access reasons: providers opening a patient's records outside an active appointment must say why, and the reason is written to the audit trail
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::abac::SubjectAttributes;
+use super::rbac::Role;
+use crate::compliance::hipaa::HipaaAuditEntry;
+use crate::errors::{SharedError, SharedResult};
+use crate::ids::PatientId;
+use crate::models::{Appointment, AppointmentStatus};
+
+/// Key of the reason inside an audit entry's `details`
+pub const ACCESS_REASON_DETAILS_KEY: &str = "access_reason";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessReasonCategory {
+    /// Follow-up on care already given, e.g. reviewing results after a visit
+    ContinuityOfCare,
+    /// Asked to consult or give a second opinion
+    Consultation,
+    CareCoordination,
+    /// The patient asked, e.g. by phone or message
+    PatientRequest,
+    Prescription,
+    QualityReview,
+    Billing,
+    /// Anything else; needs a note
+    Other,
+}
+
+impl AccessReasonCategory {
+    /// In the order the prompt lists them
+    pub const ALL: [AccessReasonCategory; 8] = [
+        AccessReasonCategory::ContinuityOfCare,
+        AccessReasonCategory::Consultation,
+        AccessReasonCategory::CareCoordination,
+        AccessReasonCategory::PatientRequest,
+        AccessReasonCategory::Prescription,
+        AccessReasonCategory::QualityReview,
+        AccessReasonCategory::Billing,
+        AccessReasonCategory::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessReasonCategory::ContinuityOfCare => "continuity_of_care",
+            AccessReasonCategory::Consultation => "consultation",
+            AccessReasonCategory::CareCoordination => "care_coordination",
+            AccessReasonCategory::PatientRequest => "patient_request",
+            AccessReasonCategory::Prescription => "prescription",
+            AccessReasonCategory::QualityReview => "quality_review",
+            AccessReasonCategory::Billing => "billing",
+            AccessReasonCategory::Other => "other",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AccessReasonCategory::ContinuityOfCare => "Continuity of care",
+            AccessReasonCategory::Consultation => "Consultation or second opinion",
+            AccessReasonCategory::CareCoordination => "Care coordination",
+            AccessReasonCategory::PatientRequest => "Patient request",
+            AccessReasonCategory::Prescription => "Prescription or refill",
+            AccessReasonCategory::QualityReview => "Quality or clinical review",
+            AccessReasonCategory::Billing => "Billing",
+            AccessReasonCategory::Other => "Other",
+        }
+    }
+}
+
+/// The reason a provider gave for opening a record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessReason {
+    pub category: AccessReasonCategory,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl AccessReason {
+    pub fn new(category: AccessReasonCategory) -> Self {
+        Self { category, note: None }
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        let note = note.into().trim().to_string();
+        self.note = (!note.is_empty()).then_some(note);
+        self
+    }
+
+    /// Add the reason to the entry's details, keeping what is already there
+    pub fn annotate(&self, mut entry: HipaaAuditEntry) -> HipaaAuditEntry {
+        let reason = serde_json::to_value(self).unwrap_or(serde_json::Value::Null);
+        match &mut entry.details {
+            serde_json::Value::Object(details) => {
+                details.insert(ACCESS_REASON_DETAILS_KEY.to_string(), reason);
+            }
+            details => *details = serde_json::json!({ ACCESS_REASON_DETAILS_KEY: reason }),
+        }
+        entry
+    }
+}
+
+/// When a provider is seeing a patient, so no reason is needed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CareWindow {
+    pub patient_id: PatientId,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl CareWindow {
+    /// The appointment's time slot; `None` if it was cancelled, missed or moved
+    pub fn from_appointment(appointment: &Appointment) -> Option<Self> {
+        match appointment.status {
+            AppointmentStatus::Cancelled | AppointmentStatus::NoShow | AppointmentStatus::Rescheduled => None,
+            _ => Some(Self {
+                patient_id: appointment.patient_id.clone(),
+                starts_at: appointment.scheduled_time,
+                ends_at: appointment.scheduled_time + Duration::minutes(appointment.duration_minutes.max(0).into()),
+            }),
+        }
+    }
+}
+
+/// What the reason dialog shows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessReasonPrompt {
+    pub patient_id: PatientId,
+    pub categories: Vec<AccessReasonCategory>,
+    /// Categories that also need a free-text note
+    pub note_required_for: Vec<AccessReasonCategory>,
+    pub min_note_length: usize,
+}
+
+/// Who must give a reason and what counts as one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessReasonPolicy {
+    /// Roles never prompted, e.g. patients reading their own records
+    pub exempt_roles: Vec<Role>,
+    /// Records may be opened this long before and after an appointment
+    pub grace_minutes: u32,
+    pub note_required_for: Vec<AccessReasonCategory>,
+    pub min_note_length: usize,
+}
+
+impl Default for AccessReasonPolicy {
+    fn default() -> Self {
+        Self {
+            exempt_roles: vec![Role::Patient],
+            grace_minutes: 30,
+            note_required_for: vec![AccessReasonCategory::Other],
+            min_note_length: 10,
+        }
+    }
+}
+
+impl AccessReasonPolicy {
+    /// True if one of the user's own appointments with the patient is under way
+    pub fn in_care_window(&self, patient_id: &PatientId, windows: &[CareWindow], now: DateTime<Utc>) -> bool {
+        let grace = Duration::minutes(self.grace_minutes.into());
+        windows
+            .iter()
+            .any(|w| &w.patient_id == patient_id && w.starts_at - grace <= now && now <= w.ends_at + grace)
+    }
+
+    /// The prompt to show before opening the patient's records, or `None`
+    /// when no reason is needed
+    pub fn prompt(
+        &self,
+        subject: &SubjectAttributes,
+        patient_id: &PatientId,
+        windows: &[CareWindow],
+        now: DateTime<Utc>,
+    ) -> Option<AccessReasonPrompt> {
+        if self.exempt_roles.contains(&subject.role) || self.in_care_window(patient_id, windows, now) {
+            return None;
+        }
+        Some(AccessReasonPrompt {
+            patient_id: patient_id.clone(),
+            categories: AccessReasonCategory::ALL.to_vec(),
+            note_required_for: self.note_required_for.clone(),
+            min_note_length: self.min_note_length,
+        })
+    }
+
+    pub fn validate(&self, reason: &AccessReason) -> SharedResult<()> {
+        let note_length = reason.note.as_deref().map_or(0, |note| note.trim().chars().count());
+        if self.note_required_for.contains(&reason.category) && note_length < self.min_note_length {
+            return Err(SharedError::ValidationError(format!(
+                "Access reason '{}' needs a note of at least {} characters",
+                reason.category.label(),
+                self.min_note_length
+            )));
+        }
+        Ok(())
+    }
+
+    /// Refuse to open the records without a valid reason when one is needed
+    pub fn require(
+        &self,
+        subject: &SubjectAttributes,
+        patient_id: &PatientId,
+        windows: &[CareWindow],
+        reason: Option<&AccessReason>,
+        now: DateTime<Utc>,
+    ) -> SharedResult<()> {
+        if self.prompt(subject, patient_id, windows, now).is_none() {
+            return Ok(());
+        }
+        let reason = reason.ok_or_else(|| {
+            SharedError::AuthorizationError(format!(
+                "A reason is required to open records of patient {} outside an appointment",
+                patient_id
+            ))
+        })?;
+        self.validate(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::hipaa::{AuditOutcome, HipaaAction};
+
+    fn window(patient: &str, starts_at: DateTime<Utc>) -> CareWindow {
+        CareWindow { patient_id: PatientId::new(patient), starts_at, ends_at: starts_at + Duration::minutes(20) }
+    }
+
+    #[test]
+    fn test_prompt_outside_appointment() {
+        let now = Utc::now();
+        let policy = AccessReasonPolicy::default();
+        let doctor = SubjectAttributes::new("dr-1", Role::Physician);
+        let patient = PatientId::new("p-1");
+        let schedule = vec![window("p-1", now + Duration::minutes(20)), window("p-2", now - Duration::hours(3))];
+
+        // Within the grace period before the visit
+        assert!(policy.prompt(&doctor, &patient, &schedule, now).is_none());
+        let prompt = policy.prompt(&doctor, &PatientId::new("p-2"), &schedule, now).unwrap();
+        assert_eq!(prompt.categories.len(), AccessReasonCategory::ALL.len());
+        assert_eq!(prompt.note_required_for, vec![AccessReasonCategory::Other]);
+        assert!(policy.prompt(&doctor, &patient, &schedule, now + Duration::hours(2)).is_some());
+
+        let own = SubjectAttributes::new("p-3", Role::Patient);
+        assert!(policy.prompt(&own, &PatientId::new("p-3"), &[], now).is_none());
+    }
+
+    #[test]
+    fn test_require_and_annotate() {
+        let now = Utc::now();
+        let policy = AccessReasonPolicy::default();
+        let nurse = SubjectAttributes::new("n-1", Role::Nurse);
+        let patient = PatientId::new("p-1");
+
+        assert!(matches!(policy.require(&nurse, &patient, &[], None, now), Err(SharedError::AuthorizationError(_))));
+        let vague = AccessReason::new(AccessReasonCategory::Other).with_note("  check  ");
+        assert!(policy.require(&nurse, &patient, &[], Some(&vague), now).is_err());
+        let reason = AccessReason::new(AccessReasonCategory::PatientRequest);
+        policy.require(&nurse, &patient, &[], Some(&reason), now).unwrap();
+
+        let entry = HipaaAuditEntry::new(HipaaAction::Read, "medical_record", "r-1", AuditOutcome::Success)
+            .with_details(&serde_json::json!({ "client": "api_client" }));
+        let entry = reason.clone().with_note("Called about refill").annotate(entry);
+        assert_eq!(entry.details["client"], "api_client");
+        assert_eq!(entry.details[ACCESS_REASON_DETAILS_KEY]["category"], "patient_request");
+        assert_eq!(entry.details[ACCESS_REASON_DETAILS_KEY]["note"], "Called about refill");
+        let bare = reason.annotate(HipaaAuditEntry::new(HipaaAction::Read, "medical_record", "r-1", AuditOutcome::Success));
+        assert!(bare.details[ACCESS_REASON_DETAILS_KEY].get("note").is_none());
+    }
+}
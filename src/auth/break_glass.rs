@@ -0,0 +1,393 @@
+//! Break-glass emergency access: time-boxed, justified access to a patient's records outside normal policy, audited as critical and reviewed afterwards
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::abac::{ResourceAttributes, SubjectAttributes};
+use super::rbac::Role;
+use crate::compliance::hipaa::{AuditOutcome, AuditSeverity, AuditSink, HipaaAction, HipaaAuditEntry};
+use crate::errors::{SharedError, SharedResult};
+use crate::ids::PatientId;
+
+/// Resource type of audit entries about the grants themselves
+pub const BREAK_GLASS_RESOURCE: &str = "break_glass";
+
+/// Purpose of use recorded on every access made under a grant
+pub const BREAK_GLASS_PURPOSE: &str = "emergency";
+
+/// Who may break the glass and for how long
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BreakGlassPolicy {
+    pub eligible_roles: Vec<Role>,
+    /// Shortest free-text justification accepted
+    pub min_reason_length: usize,
+    pub default_duration_minutes: u32,
+    /// Longer requests are cut down to this
+    pub max_duration_minutes: u32,
+}
+
+impl Default for BreakGlassPolicy {
+    fn default() -> Self {
+        Self {
+            eligible_roles: vec![Role::Physician, Role::Nurse],
+            min_reason_length: 20,
+            default_duration_minutes: 30,
+            max_duration_minutes: 60,
+        }
+    }
+}
+
+/// A clinician's request for emergency access to one patient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakGlassRequest {
+    pub user_id: String,
+    pub role: Role,
+    pub patient_id: PatientId,
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_minutes: Option<u32>,
+}
+
+impl BreakGlassRequest {
+    pub fn new(subject: &SubjectAttributes, patient_id: PatientId, reason: impl Into<String>) -> Self {
+        Self {
+            user_id: subject.user_id.clone(),
+            role: subject.role,
+            patient_id,
+            reason: reason.into(),
+            duration_minutes: None,
+        }
+    }
+
+    pub fn with_duration_minutes(mut self, minutes: u32) -> Self {
+        self.duration_minutes = Some(minutes);
+        self
+    }
+}
+
+/// A privacy officer's verdict on a past grant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakGlassReview {
+    pub reviewed_by: String,
+    pub reviewed_at: DateTime<Utc>,
+    /// False if the access was not a genuine emergency
+    pub justified: bool,
+    #[serde(default)]
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakGlassGrant {
+    pub grant_id: Uuid,
+    pub user_id: String,
+    pub role: Role,
+    pub patient_id: PatientId,
+    pub reason: String,
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Set when the clinician closed the grant before it expired
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Records accessed under the grant
+    #[serde(default)]
+    pub access_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<BreakGlassReview>,
+}
+
+impl BreakGlassGrant {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.ended_at.is_none() && self.granted_at <= now && now < self.expires_at
+    }
+
+    pub fn requires_review(&self) -> bool {
+        self.review.is_none()
+    }
+}
+
+/// Issues and enforces break-glass grants. Every grant and every access under
+/// one is audited with critical severity and flagged for review, so without an
+/// audit sink no emergency access is granted or used.
+#[derive(Clone, Default)]
+pub struct BreakGlassManager {
+    pub policy: BreakGlassPolicy,
+    grants: Vec<BreakGlassGrant>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+}
+
+impl fmt::Debug for BreakGlassManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BreakGlassManager")
+            .field("policy", &self.policy)
+            .field("grants", &self.grants)
+            .field("audit_sink", &self.audit_sink.is_some())
+            .finish()
+    }
+}
+
+impl BreakGlassManager {
+    pub fn new(policy: BreakGlassPolicy) -> Self {
+        Self { policy, grants: Vec::new(), audit_sink: None }
+    }
+
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    pub fn grant(&self, grant_id: Uuid) -> Option<&BreakGlassGrant> {
+        self.grants.iter().find(|g| g.grant_id == grant_id)
+    }
+
+    /// Grant emergency access if the role is eligible and a reason is given.
+    /// The grant lasts the requested time, capped by the policy.
+    pub fn request(&mut self, request: BreakGlassRequest, now: DateTime<Utc>) -> SharedResult<BreakGlassGrant> {
+        self.require_audit_sink()?;
+        if !self.policy.eligible_roles.contains(&request.role) {
+            return Err(SharedError::AuthorizationError(format!(
+                "Role '{}' may not use emergency access",
+                request.role
+            )));
+        }
+        let reason = request.reason.trim();
+        if reason.chars().count() < self.policy.min_reason_length {
+            return Err(SharedError::ValidationError(format!(
+                "Emergency access needs a reason of at least {} characters",
+                self.policy.min_reason_length
+            )));
+        }
+        let minutes = request
+            .duration_minutes
+            .unwrap_or(self.policy.default_duration_minutes)
+            .min(self.policy.max_duration_minutes);
+        if minutes == 0 {
+            return Err(SharedError::ValidationError("Emergency access duration must be positive".to_string()));
+        }
+
+        let grant = BreakGlassGrant {
+            grant_id: Uuid::new_v4(),
+            user_id: request.user_id,
+            role: request.role,
+            patient_id: request.patient_id,
+            reason: reason.to_string(),
+            granted_at: now,
+            expires_at: now + Duration::minutes(minutes.into()),
+            ended_at: None,
+            access_count: 0,
+            review: None,
+        };
+        let entry = HipaaAuditEntry::new(
+            HipaaAction::EmergencyAccess,
+            BREAK_GLASS_RESOURCE,
+            &grant.grant_id.to_string(),
+            AuditOutcome::Success,
+        )
+        .with_details(&serde_json::json!({
+            "role": grant.role,
+            "reason": grant.reason,
+            "expires_at": grant.expires_at,
+        }));
+        self.audit_critical(entry, &grant, now);
+        self.grants.push(grant.clone());
+        Ok(grant)
+    }
+
+    /// The user's live grant for a patient, if any
+    pub fn active_grant(&self, user_id: &str, patient_id: &PatientId, now: DateTime<Utc>) -> Option<&BreakGlassGrant> {
+        self.grants
+            .iter()
+            .find(|g| g.user_id == user_id && &g.patient_id == patient_id && g.is_active(now))
+    }
+
+    /// Allow `action` on `resource` under an active grant, auditing the access.
+    /// Returns the grant used.
+    pub fn authorize(
+        &mut self,
+        subject: &SubjectAttributes,
+        action: &HipaaAction,
+        resource: &ResourceAttributes,
+        now: DateTime<Utc>,
+    ) -> SharedResult<Uuid> {
+        self.require_audit_sink()?;
+        let grant = self
+            .grants
+            .iter_mut()
+            .find(|g| g.user_id == subject.user_id && g.patient_id == resource.patient_id && g.is_active(now))
+            .ok_or_else(|| {
+                SharedError::AuthorizationError(format!("No active emergency access to patient {}", resource.patient_id))
+            })?;
+        grant.access_count += 1;
+        let grant = grant.clone();
+
+        let entry = HipaaAuditEntry::new(
+            action.clone(),
+            &resource.resource_type,
+            resource.patient_id.as_str(),
+            AuditOutcome::Success,
+        )
+        .with_purpose(BREAK_GLASS_PURPOSE)
+        .with_details(&serde_json::json!({ "grant_id": grant.grant_id, "reason": grant.reason }));
+        self.audit_critical(entry, &grant, now);
+        Ok(grant.grant_id)
+    }
+
+    /// Close a grant early once the emergency is over
+    pub fn end(&mut self, grant_id: Uuid, now: DateTime<Utc>) -> SharedResult<()> {
+        let grant = self
+            .grants
+            .iter_mut()
+            .find(|g| g.grant_id == grant_id)
+            .ok_or_else(|| SharedError::NotFoundError(format!("Break-glass grant {}", grant_id)))?;
+        if grant.is_active(now) {
+            grant.ended_at = Some(now);
+        }
+        Ok(())
+    }
+
+    /// Grants still waiting for a privacy officer, oldest first
+    pub fn pending_review(&self) -> Vec<&BreakGlassGrant> {
+        let mut pending: Vec<&BreakGlassGrant> = self.grants.iter().filter(|g| g.requires_review()).collect();
+        pending.sort_by_key(|g| g.granted_at);
+        pending
+    }
+
+    /// Record the post-hoc review. Nobody may review their own grant.
+    pub fn review(
+        &mut self,
+        grant_id: Uuid,
+        reviewer_id: &str,
+        justified: bool,
+        notes: &str,
+        now: DateTime<Utc>,
+    ) -> SharedResult<()> {
+        let grant = self
+            .grants
+            .iter_mut()
+            .find(|g| g.grant_id == grant_id)
+            .ok_or_else(|| SharedError::NotFoundError(format!("Break-glass grant {}", grant_id)))?;
+        if grant.user_id == reviewer_id {
+            return Err(SharedError::AuthorizationError("Emergency access must be reviewed by someone else".to_string()));
+        }
+        if grant.review.is_some() {
+            return Err(SharedError::ValidationError(format!("Break-glass grant {} was already reviewed", grant_id)));
+        }
+        grant.review = Some(BreakGlassReview {
+            reviewed_by: reviewer_id.to_string(),
+            reviewed_at: now,
+            justified,
+            notes: notes.to_string(),
+        });
+
+        if let Some(sink) = &self.audit_sink {
+            let mut entry =
+                HipaaAuditEntry::new(HipaaAction::Update, BREAK_GLASS_RESOURCE, &grant_id.to_string(), AuditOutcome::Success)
+                    .with_severity(if justified { AuditSeverity::Info } else { AuditSeverity::Warning })
+                    .with_details(&serde_json::json!({ "justified": justified, "notes": notes }));
+            entry.user_id = Uuid::parse_str(reviewer_id).ok();
+            entry.timestamp = now;
+            sink.record(entry);
+        }
+        Ok(())
+    }
+
+    fn require_audit_sink(&self) -> SharedResult<()> {
+        if self.audit_sink.is_none() {
+            return Err(SharedError::AuditError("Emergency access can't be audited; no audit sink configured".to_string()));
+        }
+        Ok(())
+    }
+
+    fn audit_critical(&self, entry: HipaaAuditEntry, grant: &BreakGlassGrant, now: DateTime<Utc>) {
+        let Some(sink) = &self.audit_sink else {
+            return;
+        };
+        let mut entry = entry.with_severity(AuditSeverity::Critical).flag_for_review();
+        entry.user_id = Uuid::parse_str(&grant.user_id).ok();
+        entry.patient_id = grant.patient_id.to_uuid();
+        entry.timestamp = now;
+        sink.record(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::abac::{AccessPolicySet, PhiAccessGuard, MEDICAL_RECORD_RESOURCE};
+    use crate::compliance::hipaa::{AuditQuery, MemoryAuditSink};
+
+    const REASON: &str = "Unconscious patient in ER, allergy history needed";
+
+    #[test]
+    fn test_grant_access_and_audit() {
+        let now = Utc::now();
+        let sink = MemoryAuditSink::new();
+        let mut manager = BreakGlassManager::default().with_audit_sink(Arc::new(sink.clone()));
+        let doctor = SubjectAttributes::new(Uuid::new_v4().to_string(), Role::Physician);
+        let patient = PatientId::new(Uuid::new_v4().to_string());
+        let record = ResourceAttributes::new(MEDICAL_RECORD_RESOURCE, patient.clone());
+
+        // Normal policies deny: the doctor isn't assigned to this patient
+        let guard = PhiAccessGuard::new(AccessPolicySet::default(), doctor.clone());
+        assert!(guard.check(&HipaaAction::Read, &record, "treatment").is_err());
+        assert!(manager.authorize(&doctor, &HipaaAction::Read, &record, now).is_err());
+
+        let grant = manager
+            .request(BreakGlassRequest::new(&doctor, patient.clone(), REASON).with_duration_minutes(240), now)
+            .unwrap();
+        assert_eq!(grant.expires_at, now + Duration::minutes(60));
+        assert_eq!(manager.authorize(&doctor, &HipaaAction::Read, &record, now + Duration::minutes(5)).unwrap(), grant.grant_id);
+        assert!(guard.check_or_break_glass(&HipaaAction::Read, &record, "treatment", &mut manager, now).is_ok());
+        assert_eq!(manager.grant(grant.grant_id).unwrap().access_count, 2);
+
+        // Another patient, or after expiry, is still denied
+        let other = ResourceAttributes::new(MEDICAL_RECORD_RESOURCE, PatientId::new("p-2"));
+        assert!(manager.authorize(&doctor, &HipaaAction::Read, &other, now).is_err());
+        assert!(manager.authorize(&doctor, &HipaaAction::Read, &record, now + Duration::minutes(60)).is_err());
+
+        let entries = sink.query(&AuditQuery::new().requires_review(true));
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|e| e.severity == AuditSeverity::Critical));
+        assert_eq!(entries[0].action, HipaaAction::EmergencyAccess);
+        assert_eq!(entries[0].details["reason"], REASON);
+        assert_eq!(entries[1].purpose.as_deref(), Some(BREAK_GLASS_PURPOSE));
+        assert_eq!(entries[1].patient_id, patient.to_uuid());
+    }
+
+    #[test]
+    fn test_request_validation_and_review() {
+        let now = Utc::now();
+        let nurse = SubjectAttributes::new("n-1", Role::Nurse);
+        let patient = PatientId::new("p-1");
+
+        // Unaudited emergency access is never granted
+        let mut unaudited = BreakGlassManager::default();
+        assert!(matches!(
+            unaudited.request(BreakGlassRequest::new(&nurse, patient.clone(), REASON), now),
+            Err(SharedError::AuditError(_))
+        ));
+        let mut manager = BreakGlassManager::default().with_audit_sink(Arc::new(MemoryAuditSink::new()));
+
+        let admin = SubjectAttributes::new("a-1", Role::Admin);
+        assert!(manager.request(BreakGlassRequest::new(&admin, patient.clone(), REASON), now).is_err());
+        assert!(manager.request(BreakGlassRequest::new(&nurse, patient.clone(), "  emergency  "), now).is_err());
+        assert!(manager
+            .request(BreakGlassRequest::new(&nurse, patient.clone(), REASON).with_duration_minutes(0), now)
+            .is_err());
+
+        let grant = manager.request(BreakGlassRequest::new(&nurse, patient.clone(), REASON), now).unwrap();
+        assert_eq!(grant.expires_at, now + Duration::minutes(30));
+        manager.end(grant.grant_id, now + Duration::minutes(10)).unwrap();
+        assert!(manager.active_grant("n-1", &patient, now + Duration::minutes(11)).is_none());
+
+        assert_eq!(manager.pending_review().len(), 1);
+        assert!(manager.review(grant.grant_id, "n-1", true, "", now).is_err());
+        manager.review(grant.grant_id, "privacy-officer", true, "Confirmed ER admission", now).unwrap();
+        assert!(manager.review(grant.grant_id, "privacy-officer", false, "", now).is_err());
+        assert!(manager.pending_review().is_empty());
+        assert!(manager.review(Uuid::new_v4(), "privacy-officer", true, "", now).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! End-to-end chat keys: one symmetric key per booking conversation, rotated whenever its participants change
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+
+use crate::encryption::DataKey;
+use crate::errors::{SharedError, SharedResult};
+use crate::ids::BookingId;
+use crate::secrets::SecretString;
+
+/// One generation of a booking's chat key. Epochs start at 1 and increase
+/// with every rotation.
+#[derive(Debug, Clone)]
+pub struct ChatKey {
+    pub booking_id: BookingId,
+    pub epoch: u32,
+    key: DataKey,
+}
+
+impl ChatKey {
+    pub fn generate(booking_id: BookingId, epoch: u32) -> Self {
+        Self { booking_id, epoch, key: DataKey::generate() }
+    }
+
+    /// Rebuild a key received from another participant
+    pub fn from_bytes(booking_id: BookingId, epoch: u32, key: &[u8]) -> SharedResult<Self> {
+        Ok(Self { booking_id, epoch, key: DataKey::from_bytes(key)? })
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        self.key.as_bytes()
+    }
+
+    /// Binds a ciphertext to its conversation, key epoch, message and sender,
+    /// so the relay can't replay it elsewhere or re-attribute it
+    fn associated_data(&self, message_id: &str, sender_id: &str) -> Vec<u8> {
+        format!("{}|{}|{}|{}", self.booking_id, self.epoch, message_id, sender_id).into_bytes()
+    }
+}
+
+/// Wire form of an encrypted chat message body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncryptedChatContent {
+    pub epoch: u32,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// All key epochs a participant holds for one booking's chat. New messages
+/// use the latest epoch; older ones stay so history remains readable.
+#[derive(Debug, Clone)]
+pub struct ChatKeyRing {
+    pub booking_id: BookingId,
+    participants: Vec<String>,
+    keys: Vec<ChatKey>,
+}
+
+fn normalize_participants(participants: &[String]) -> Vec<String> {
+    let mut participants = participants.to_vec();
+    participants.sort();
+    participants.dedup();
+    participants
+}
+
+impl ChatKeyRing {
+    /// Start a conversation with a fresh epoch-1 key
+    pub fn new(booking_id: BookingId, participants: &[String]) -> Self {
+        let key = ChatKey::generate(booking_id.clone(), 1);
+        Self { booking_id, participants: normalize_participants(participants), keys: vec![key] }
+    }
+
+    /// Join a conversation using a key distributed by another participant
+    pub fn from_key(key: ChatKey, participants: &[String]) -> Self {
+        Self { booking_id: key.booking_id.clone(), participants: normalize_participants(participants), keys: vec![key] }
+    }
+
+    pub fn participants(&self) -> &[String] {
+        &self.participants
+    }
+
+    pub fn current_key(&self) -> &ChatKey {
+        self.keys.last().expect("a key ring always holds at least one key")
+    }
+
+    pub fn current_epoch(&self) -> u32 {
+        self.current_key().epoch
+    }
+
+    pub fn key(&self, epoch: u32) -> Option<&ChatKey> {
+        self.keys.iter().find(|key| key.epoch == epoch)
+    }
+
+    /// Record the conversation's participants. If anyone joined or left, a new
+    /// key is generated so newcomers can't read earlier messages and leavers
+    /// can't read later ones; returns the new epoch, which must then be
+    /// distributed to the remaining participants.
+    pub fn update_participants(&mut self, participants: &[String]) -> Option<u32> {
+        let participants = normalize_participants(participants);
+        if participants == self.participants {
+            return None;
+        }
+        self.participants = participants;
+        let epoch = self.current_epoch() + 1;
+        self.keys.push(ChatKey::generate(self.booking_id.clone(), epoch));
+        Some(epoch)
+    }
+
+    /// Add a key received after another participant rotated. Receiving the
+    /// same key twice is fine; a different key for an epoch already held is
+    /// refused, as two participants must never encrypt under different keys.
+    pub fn install(&mut self, key: ChatKey) -> SharedResult<()> {
+        if key.booking_id != self.booking_id {
+            return Err(SharedError::CryptographicError(format!(
+                "Chat key is for booking {}, not {}",
+                key.booking_id, self.booking_id
+            )));
+        }
+        match self.key(key.epoch) {
+            Some(existing) if existing.as_bytes() == key.as_bytes() => Ok(()),
+            Some(_) => Err(SharedError::CryptographicError(format!(
+                "Conflicting chat key for epoch {} of booking {}",
+                key.epoch, self.booking_id
+            ))),
+            None => {
+                let position = self.keys.partition_point(|existing| existing.epoch < key.epoch);
+                self.keys.insert(position, key);
+                Ok(())
+            }
+        }
+    }
+
+    /// Encrypt a message body under the current epoch; returns the string to
+    /// send as the message content
+    pub fn encrypt(&self, message_id: &str, sender_id: &str, plaintext: &str) -> SharedResult<String> {
+        let key = self.current_key();
+        let (nonce, ciphertext) = key.key.seal(plaintext.as_bytes(), &key.associated_data(message_id, sender_id))?;
+        let content = EncryptedChatContent {
+            epoch: key.epoch,
+            nonce: general_purpose::STANDARD.encode(nonce),
+            ciphertext: general_purpose::STANDARD.encode(ciphertext),
+        };
+        Ok(serde_json::to_string(&content)?)
+    }
+
+    /// Decrypt a message body; the plaintext is wiped when the result is dropped
+    pub fn decrypt(&self, message_id: &str, sender_id: &str, content: &str) -> SharedResult<SecretString> {
+        let content: EncryptedChatContent = serde_json::from_str(content)?;
+        let key = self.key(content.epoch).ok_or_else(|| {
+            SharedError::CryptographicError(format!(
+                "No chat key for epoch {} of booking {}",
+                content.epoch, self.booking_id
+            ))
+        })?;
+        let nonce = general_purpose::STANDARD.decode(&content.nonce)?;
+        let ciphertext = general_purpose::STANDARD.decode(&content.ciphertext)?;
+        let plaintext = key.key.open(&nonce, &ciphertext, &key.associated_data(message_id, sender_id))?;
+        plaintext
+            .into_utf8()
+            .map_err(|_| SharedError::CryptographicError("Decrypted chat message is not UTF-8".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participants(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_round_trip_between_participants() {
+        let booking = BookingId::new("BK-1");
+        let patient = ChatKeyRing::new(booking.clone(), &participants(&["patient-1", "provider-1"]));
+        let key = patient.current_key();
+        let shared = ChatKey::from_bytes(booking, key.epoch, key.as_bytes()).unwrap();
+        let provider = ChatKeyRing::from_key(shared, &participants(&["provider-1", "patient-1"]));
+        assert_eq!(provider.participants(), patient.participants());
+
+        let content = patient.encrypt("m-1", "patient-1", "My chest hurts").unwrap();
+        assert!(!content.contains("chest"));
+        assert_eq!(provider.decrypt("m-1", "patient-1", &content).unwrap().expose(), "My chest hurts");
+
+        // Bound to the message id and sender
+        assert!(provider.decrypt("m-2", "patient-1", &content).is_err());
+        assert!(provider.decrypt("m-1", "provider-1", &content).is_err());
+
+        let stranger = ChatKeyRing::new(BookingId::new("BK-1"), &participants(&["patient-1", "provider-1"]));
+        assert!(stranger.decrypt("m-1", "patient-1", &content).is_err());
+    }
+
+    #[test]
+    fn test_rotation_on_participant_change() {
+        let booking = BookingId::new("BK-2");
+        let mut ring = ChatKeyRing::new(booking.clone(), &participants(&["patient-1", "provider-1"]));
+        let before = ring.encrypt("m-1", "provider-1", "Arriving at 10").unwrap();
+
+        assert_eq!(ring.update_participants(&participants(&["provider-1", "patient-1", "patient-1"])), None);
+        assert_eq!(ring.update_participants(&participants(&["patient-1", "provider-1", "nurse-1"])), Some(2));
+        assert_eq!(ring.current_epoch(), 2);
+
+        // History stays readable; new messages use the new epoch
+        assert_eq!(ring.decrypt("m-1", "provider-1", &before).unwrap().expose(), "Arriving at 10");
+        let after = ring.encrypt("m-2", "provider-1", "Nurse joining").unwrap();
+        let content: EncryptedChatContent = serde_json::from_str(&after).unwrap();
+        assert_eq!(content.epoch, 2);
+
+        // The nurse only receives epoch 2 and can't read earlier messages
+        let key = ring.current_key();
+        let nurse_key = ChatKey::from_bytes(booking, key.epoch, key.as_bytes()).unwrap();
+        let mut nurse = ChatKeyRing::from_key(nurse_key.clone(), ring.participants());
+        assert_eq!(nurse.decrypt("m-2", "provider-1", &after).unwrap().expose(), "Nurse joining");
+        assert!(nurse.decrypt("m-1", "provider-1", &before).is_err());
+
+        assert!(nurse.install(nurse_key).is_ok());
+        assert!(nurse.install(ChatKey::generate(BookingId::new("BK-2"), 2)).is_err());
+        assert_eq!(nurse.decrypt("m-2", "provider-1", &after).unwrap().expose(), "Nurse joining");
+        assert!(nurse.install(ChatKey::generate(BookingId::new("BK-3"), 3)).is_err());
+    }
+}
@@ -0,0 +1,446 @@
+//! Device identity: per-install signing keys, device registration and signed, single-use connection handshakes
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use super::request_signing::ReplayGuard;
+use super::session::DeviceInfo;
+use crate::errors::{SharedError, SharedResult};
+use crate::secrets::SecretString;
+
+pub const DEVICE_KEY_ALGORITHM: &str = "Ed25519";
+
+/// Sent with API requests so the server can tie tokens to the device they were issued to
+pub const DEVICE_ID_HEADER: &str = "X-Device-Id";
+
+/// How far a handshake's timestamp may be from the verifier's clock
+pub const HANDSHAKE_MAX_SKEW_SECONDS: i64 = 300;
+
+/// Domain separation for handshake signatures; bump on any change to the signed fields
+const HANDSHAKE_CONTEXT: &str = "MyDR24 device handshake v2";
+
+/// Random bytes in each handshake nonce
+const HANDSHAKE_NONCE_BYTES: usize = 16;
+
+/// The signing key generated on first launch. The private half never leaves
+/// the device; only the public key is registered.
+#[derive(Clone)]
+pub struct DeviceKeyPair {
+    device_id: String,
+    signing_key: SigningKey,
+}
+
+impl DeviceKeyPair {
+    pub fn generate() -> Self {
+        Self {
+            device_id: Uuid::new_v4().to_string(),
+            signing_key: SigningKey::generate(&mut OsRng),
+        }
+    }
+
+    /// Restore a key saved with [`DeviceKeyPair::secret_key_base64`]
+    pub fn from_secret_base64(device_id: impl Into<String>, secret_b64: &str) -> SharedResult<Self> {
+        let bytes = Zeroizing::new(general_purpose::STANDARD.decode(secret_b64)?);
+        let secret: Zeroizing<[u8; 32]> = Zeroizing::new(bytes.as_slice().try_into().map_err(|_| {
+            SharedError::CryptographicError(format!("Invalid device key length: {}", bytes.len()))
+        })?);
+        Ok(Self {
+            device_id: device_id.into(),
+            signing_key: SigningKey::from_bytes(&secret),
+        })
+    }
+
+    pub fn device_id(&self) -> &str {
+        &self.device_id
+    }
+
+    /// For the platform's secure storage (keychain, keystore) only
+    pub fn secret_key_base64(&self) -> SecretString {
+        let bytes = Zeroizing::new(self.signing_key.to_bytes());
+        SecretString::new(general_purpose::STANDARD.encode(bytes.as_slice()))
+    }
+
+    pub fn public_key_base64(&self) -> String {
+        general_purpose::STANDARD.encode(self.signing_key.verifying_key().as_bytes())
+    }
+
+    /// Base64 signature over `message`
+    pub fn sign(&self, message: &[u8]) -> String {
+        general_purpose::STANDARD.encode(self.signing_key.sign(message).to_bytes())
+    }
+
+    /// Request body for registering this device under the signed-in user
+    pub fn registration(&self, name: impl Into<String>) -> DeviceRegistration {
+        DeviceRegistration {
+            device: DeviceInfo::new(self.device_id.clone(), name),
+            algorithm: DEVICE_KEY_ALGORITHM.to_string(),
+            public_key: self.public_key_base64(),
+        }
+    }
+
+    /// Proof of possession of the device key for a connection by `user_id`
+    pub fn sign_handshake(&self, user_id: &str, now: DateTime<Utc>) -> DeviceHandshake {
+        self.sign_handshake_for(HandshakePurpose::Connect, user_id, None, now)
+    }
+
+    /// Proof of possession for swapping a refreshed `token` into an open
+    /// connection; the signature covers the token, so it can't be moved to another
+    pub fn sign_reauth(&self, user_id: &str, token: &str, now: DateTime<Utc>) -> DeviceHandshake {
+        self.sign_handshake_for(HandshakePurpose::Reauth, user_id, Some(token_hash(token)), now)
+    }
+
+    fn sign_handshake_for(
+        &self,
+        purpose: HandshakePurpose,
+        user_id: &str,
+        token_hash: Option<String>,
+        now: DateTime<Utc>,
+    ) -> DeviceHandshake {
+        let mut nonce = [0u8; HANDSHAKE_NONCE_BYTES];
+        OsRng.fill_bytes(&mut nonce);
+        let mut handshake = DeviceHandshake {
+            device_id: self.device_id.clone(),
+            user_id: user_id.to_string(),
+            purpose,
+            nonce: general_purpose::URL_SAFE_NO_PAD.encode(nonce),
+            token_hash,
+            timestamp: now,
+            signature: String::new(),
+        };
+        handshake.signature = self.sign(&handshake.signing_input());
+        handshake
+    }
+}
+
+impl fmt::Debug for DeviceKeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceKeyPair")
+            .field("device_id", &self.device_id)
+            .field("signing_key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceRegistration {
+    pub device: DeviceInfo,
+    pub algorithm: String,
+    pub public_key: String, // Base64 encoded
+}
+
+/// A device known to the server, as listed on the "your devices" screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegisteredDevice {
+    pub user_id: String,
+    pub device: DeviceInfo,
+    pub algorithm: String,
+    pub public_key: String, // Base64 encoded
+    pub registered_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl RegisteredDevice {
+    pub fn device_id(&self) -> &str {
+        &self.device.device_id
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    fn verifying_key(&self) -> SharedResult<VerifyingKey> {
+        if self.algorithm != DEVICE_KEY_ALGORITHM {
+            return Err(SharedError::CryptographicError(format!(
+                "Unsupported device key algorithm: {}",
+                self.algorithm
+            )));
+        }
+        let bytes = general_purpose::STANDARD.decode(&self.public_key)?;
+        let bytes: [u8; 32] = bytes.as_slice().try_into().map_err(|_| {
+            SharedError::CryptographicError(format!("Invalid device public key length: {}", bytes.len()))
+        })?;
+        VerifyingKey::from_bytes(&bytes)
+            .map_err(|_| SharedError::CryptographicError("Invalid device public key".to_string()))
+    }
+
+    /// Check a base64 signature made with this device's key
+    pub fn verify(&self, message: &[u8], signature_b64: &str) -> SharedResult<bool> {
+        let key = self.verifying_key()?;
+        let Ok(bytes) = general_purpose::STANDARD.decode(signature_b64) else {
+            return Ok(false);
+        };
+        let Ok(signature) = Signature::from_slice(&bytes) else {
+            return Ok(false);
+        };
+        Ok(key.verify(message, &signature).is_ok())
+    }
+}
+
+/// What a handshake authorizes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandshakePurpose {
+    /// Opening a WebSocket connection
+    #[default]
+    Connect,
+    /// Presenting a refreshed token on an open connection
+    Reauth,
+}
+
+impl HandshakePurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HandshakePurpose::Connect => "connect",
+            HandshakePurpose::Reauth => "reauth",
+        }
+    }
+}
+
+/// Base64 SHA-256 of a bearer token, as bound into reauth handshakes
+pub fn token_hash(token: &str) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Sent when opening a WebSocket, and again on reauth, so a stolen token
+/// alone can't connect. Each handshake carries a fresh nonce and is accepted
+/// once, so a captured frame can't be replayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceHandshake {
+    pub device_id: String,
+    pub user_id: String,
+    #[serde(default)]
+    pub purpose: HandshakePurpose,
+    pub nonce: String,
+    /// Set on reauth; see [`token_hash`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_hash: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub signature: String, // Base64 encoded
+}
+
+impl DeviceHandshake {
+    fn signing_input(&self) -> Vec<u8> {
+        format!(
+            "{}|{}|{}|{}|{}|{}|{}",
+            HANDSHAKE_CONTEXT,
+            self.purpose.as_str(),
+            self.device_id,
+            self.user_id,
+            self.nonce,
+            self.token_hash.as_deref().unwrap_or_default(),
+            self.timestamp.timestamp_millis()
+        )
+        .into_bytes()
+    }
+}
+
+/// Server-side record of each user's registered devices, and of the
+/// handshake nonces seen within the skew window
+#[derive(Debug, Clone)]
+pub struct DeviceRegistry {
+    devices: HashMap<String, RegisteredDevice>,
+    handshake_nonces: ReplayGuard,
+}
+
+impl Default for DeviceRegistry {
+    fn default() -> Self {
+        Self {
+            devices: HashMap::new(),
+            handshake_nonces: ReplayGuard::new(Duration::seconds(HANDSHAKE_MAX_SKEW_SECONDS)),
+        }
+    }
+}
+
+impl DeviceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or re-register after reinstall) a device for `user_id`
+    pub fn register(
+        &mut self,
+        user_id: &str,
+        registration: DeviceRegistration,
+        now: DateTime<Utc>,
+    ) -> SharedResult<&RegisteredDevice> {
+        let device_id = registration.device.device_id.clone();
+        if let Some(existing) = self.devices.get(&device_id) {
+            if existing.user_id != user_id {
+                return Err(SharedError::AuthorizationError(format!(
+                    "Device {} belongs to another user",
+                    device_id
+                )));
+            }
+            if existing.is_revoked() {
+                return Err(SharedError::AuthorizationError(format!("Device {} was revoked", device_id)));
+            }
+        }
+
+        let device = RegisteredDevice {
+            user_id: user_id.to_string(),
+            device: registration.device,
+            algorithm: registration.algorithm,
+            public_key: registration.public_key,
+            registered_at: now,
+            last_seen_at: None,
+            revoked_at: None,
+        };
+        device.verifying_key()?;
+        self.devices.insert(device_id.clone(), device);
+        Ok(&self.devices[&device_id])
+    }
+
+    pub fn device(&self, device_id: &str) -> Option<&RegisteredDevice> {
+        self.devices.get(device_id)
+    }
+
+    /// The user's devices that are still trusted, most recently seen first
+    pub fn devices(&self, user_id: &str) -> Vec<&RegisteredDevice> {
+        let mut devices: Vec<&RegisteredDevice> =
+            self.devices.values().filter(|d| d.user_id == user_id && !d.is_revoked()).collect();
+        devices.sort_by_key(|d| std::cmp::Reverse(d.last_seen_at.unwrap_or(d.registered_at)));
+        devices
+    }
+
+    /// Stop trusting a device; it must not be re-registered under the same id
+    pub fn revoke(&mut self, device_id: &str, now: DateTime<Utc>) -> SharedResult<()> {
+        let device = self
+            .devices
+            .get_mut(device_id)
+            .ok_or_else(|| SharedError::NotFoundError(format!("Device {}", device_id)))?;
+        device.revoked_at.get_or_insert(now);
+        Ok(())
+    }
+
+    /// Accept a connection only from a registered, unrevoked device of the
+    /// handshake's user, signed recently and not seen before
+    pub fn verify_handshake(&mut self, handshake: &DeviceHandshake, now: DateTime<Utc>) -> SharedResult<&RegisteredDevice> {
+        if handshake.purpose != HandshakePurpose::Connect {
+            return Err(SharedError::AuthenticationError("Not a connect handshake".to_string()));
+        }
+        self.verify_signed(handshake, now)
+    }
+
+    /// Accept a refreshed `token` on an open connection under the same rules,
+    /// checking the handshake was signed for this token
+    pub fn verify_reauth(&mut self, handshake: &DeviceHandshake, token: &str, now: DateTime<Utc>) -> SharedResult<&RegisteredDevice> {
+        if handshake.purpose != HandshakePurpose::Reauth {
+            return Err(SharedError::AuthenticationError("Not a reauth handshake".to_string()));
+        }
+        if handshake.token_hash.as_deref() != Some(token_hash(token).as_str()) {
+            return Err(SharedError::AuthenticationError("Reauth was signed for a different token".to_string()));
+        }
+        self.verify_signed(handshake, now)
+    }
+
+    fn verify_signed(&mut self, handshake: &DeviceHandshake, now: DateTime<Utc>) -> SharedResult<&RegisteredDevice> {
+        let device = self
+            .devices
+            .get_mut(&handshake.device_id)
+            .ok_or_else(|| SharedError::AuthenticationError("Unknown device".to_string()))?;
+        if device.is_revoked() {
+            return Err(SharedError::AuthenticationError("Device has been revoked".to_string()));
+        }
+        if device.user_id != handshake.user_id {
+            return Err(SharedError::AuthenticationError("Device is registered to another user".to_string()));
+        }
+        if (now - handshake.timestamp).abs() > Duration::seconds(HANDSHAKE_MAX_SKEW_SECONDS) {
+            return Err(SharedError::AuthenticationError("Device handshake is stale".to_string()));
+        }
+        if handshake.nonce.is_empty() {
+            return Err(SharedError::AuthenticationError("Device handshake has no nonce".to_string()));
+        }
+        if !device.verify(&handshake.signing_input(), &handshake.signature)? {
+            return Err(SharedError::AuthenticationError("Invalid device signature".to_string()));
+        }
+        // Only signed handshakes are remembered, so forgeries can't fill the cache
+        let nonce_key = format!("{}:{}", handshake.device_id, handshake.nonce);
+        self.handshake_nonces
+            .check(&nonce_key, handshake.timestamp, now)
+            .map_err(|_| SharedError::AuthenticationError("Device handshake was replayed".to_string()))?;
+        device.last_seen_at = Some(now);
+        Ok(device)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_verify_handshake() {
+        let now = Utc::now();
+        let key = DeviceKeyPair::generate();
+        let mut registry = DeviceRegistry::new();
+        registry.register("user-1", key.registration("Pixel 8"), now).unwrap();
+        assert_eq!(registry.devices("user-1").len(), 1);
+        assert!(registry.register("user-2", key.registration("Pixel 8"), now).is_err());
+
+        let handshake = key.sign_handshake("user-1", now);
+        let json = serde_json::to_string(&handshake).unwrap();
+        let handshake: DeviceHandshake = serde_json::from_str(&json).unwrap();
+        let device = registry.verify_handshake(&handshake, now + Duration::seconds(5)).unwrap();
+        assert_eq!(device.last_seen_at, Some(now + Duration::seconds(5)));
+
+        // Replayed at once or much later, claimed for another user, or signed by an unknown key
+        assert!(registry.verify_handshake(&handshake, now + Duration::seconds(6)).is_err());
+        assert!(registry.verify_handshake(&key.sign_handshake("user-1", now), now).is_ok());
+        assert!(registry.verify_handshake(&handshake, now + Duration::minutes(10)).is_err());
+        let mut other_user = handshake.clone();
+        other_user.user_id = "user-2".to_string();
+        assert!(registry.verify_handshake(&other_user, now).is_err());
+        let impostor = DeviceKeyPair::from_secret_base64(key.device_id(), DeviceKeyPair::generate().secret_key_base64().expose()).unwrap();
+        assert!(registry.verify_handshake(&impostor.sign_handshake("user-1", now), now).is_err());
+        assert!(registry.verify_handshake(&DeviceKeyPair::generate().sign_handshake("user-1", now), now).is_err());
+        let mut renonced = key.sign_handshake("user-1", now);
+        renonced.nonce = handshake.nonce.clone();
+        assert!(registry.verify_handshake(&renonced, now).is_err());
+    }
+
+    #[test]
+    fn test_reauth_is_bound_to_token() {
+        let now = Utc::now();
+        let key = DeviceKeyPair::generate();
+        let mut registry = DeviceRegistry::new();
+        registry.register("user-1", key.registration("Pixel 8"), now).unwrap();
+
+        let reauth = key.sign_reauth("user-1", "token-2", now);
+        assert!(registry.verify_handshake(&reauth, now).is_err());
+        assert!(registry.verify_reauth(&reauth, "token-3", now).is_err());
+        assert!(registry.verify_reauth(&reauth, "token-2", now).is_ok());
+        assert!(registry.verify_reauth(&reauth, "token-2", now).is_err());
+        assert!(registry.verify_reauth(&key.sign_handshake("user-1", now), "token-2", now).is_err());
+    }
+
+    #[test]
+    fn test_revocation_and_key_restore() {
+        let now = Utc::now();
+        let key = DeviceKeyPair::generate();
+        let restored = DeviceKeyPair::from_secret_base64(key.device_id(), key.secret_key_base64().expose()).unwrap();
+        assert_eq!(restored.public_key_base64(), key.public_key_base64());
+        assert!(!format!("{:?}", key).contains(key.secret_key_base64().expose()));
+
+        let mut registry = DeviceRegistry::new();
+        registry.register("user-1", key.registration("iPad"), now).unwrap();
+        registry.revoke(key.device_id(), now).unwrap();
+        assert!(registry.devices("user-1").is_empty());
+        assert!(registry.verify_handshake(&restored.sign_handshake("user-1", now), now).is_err());
+        assert!(registry.register("user-1", key.registration("iPad"), now).is_err());
+        assert!(registry.revoke("missing", now).is_err());
+
+        let mut bad_key = DeviceKeyPair::generate().registration("Laptop");
+        bad_key.public_key = "c2hvcnQ=".to_string();
+        assert!(registry.register("user-1", bad_key, now).is_err());
+    }
+}
@@ -0,0 +1,475 @@
+//! JWT parsing and validation for classic (ES256/RS256) and Dilithium-signed access tokens
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+use super::rbac::{Permission, Role};
+use super::revocation::RevocationList;
+use crate::errors::{SharedError, SharedResult};
+
+/// Allowance for clock drift between the token issuer and this device
+pub const DEFAULT_LEEWAY_SECONDS: i64 = 60;
+
+/// Signature algorithms accepted in the `alg` header. `none` and the HMAC
+/// family are deliberately absent: clients never hold a shared secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum JwtAlgorithm {
+    #[serde(rename = "ES256")]
+    Es256,
+    #[serde(rename = "RS256")]
+    Rs256,
+    #[serde(rename = "DILITHIUM2")]
+    Dilithium2,
+}
+
+impl JwtAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JwtAlgorithm::Es256 => "ES256",
+            JwtAlgorithm::Rs256 => "RS256",
+            JwtAlgorithm::Dilithium2 => "DILITHIUM2",
+        }
+    }
+
+    /// Exact, case-sensitive match as RFC 7515 requires
+    pub fn parse(alg: &str) -> Option<Self> {
+        [JwtAlgorithm::Es256, JwtAlgorithm::Rs256, JwtAlgorithm::Dilithium2]
+            .into_iter()
+            .find(|algorithm| algorithm.as_str() == alg)
+    }
+
+    pub fn is_post_quantum(&self) -> bool {
+        matches!(self, JwtAlgorithm::Dilithium2)
+    }
+}
+
+impl fmt::Display for JwtAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JwtHeader {
+    pub alg: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    /// Identifies the issuer key that signed the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+}
+
+impl JwtHeader {
+    pub fn new(algorithm: JwtAlgorithm, key_id: Option<String>) -> Self {
+        Self {
+            alg: algorithm.as_str().to_string(),
+            typ: Some("JWT".to_string()),
+            kid: key_id,
+        }
+    }
+
+    pub fn algorithm(&self) -> SharedResult<JwtAlgorithm> {
+        JwtAlgorithm::parse(&self.alg)
+            .ok_or_else(|| SharedError::AuthenticationError(format!("Unsupported token algorithm: {}", self.alg)))
+    }
+}
+
+/// Identity carried by an access token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthClaims {
+    #[serde(rename = "sub")]
+    pub user_id: String,
+    pub role: Role,
+    #[serde(rename = "org", default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    /// Read from a `scopes` array or an OAuth-style space-separated `scope` string
+    #[serde(default, alias = "scope", deserialize_with = "scope_list", skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+    /// Expiry, in seconds since the Unix epoch
+    pub exp: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, deserialize_with = "audience_list", skip_serializing_if = "Vec::is_empty")]
+    pub aud: Vec<String>,
+    /// Unique token id, used to revoke this token before it expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn scope_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(scope) => scope.split_whitespace().map(str::to_string).collect(),
+        OneOrMany::Many(scopes) => scopes,
+    })
+}
+
+fn audience_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(audience) => vec![audience],
+        OneOrMany::Many(audiences) => audiences,
+    })
+}
+
+impl AuthClaims {
+    pub fn new(user_id: impl Into<String>, role: Role, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            user_id: user_id.into(),
+            role,
+            org_id: None,
+            scopes: Vec::new(),
+            exp: expires_at.timestamp(),
+            iat: Some(Utc::now().timestamp()),
+            nbf: None,
+            iss: None,
+            aud: Vec::new(),
+            jti: None,
+        }
+    }
+
+    pub fn with_org(mut self, org_id: impl Into<String>) -> Self {
+        self.org_id = Some(org_id.into());
+        self
+    }
+
+    pub fn with_scopes(mut self, scopes: &[&str]) -> Self {
+        self.scopes = scopes.iter().map(|scope| scope.to_string()).collect();
+        self
+    }
+
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.iss = Some(issuer.into());
+        self
+    }
+
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.aud.push(audience.into());
+        self
+    }
+
+    pub fn with_token_id(mut self, jti: impl Into<String>) -> Self {
+        self.jti = Some(jti.into());
+        self
+    }
+
+    /// Claims from a token WITHOUT checking its signature. Only for UI decisions
+    /// such as which menu to show; the server remains the authority.
+    pub fn from_token_unverified(token: &str) -> SharedResult<Self> {
+        Ok(decode_jwt(token)?.claims)
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.role.has_permission(permission)
+    }
+
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.exp, 0)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now().timestamp() >= self.exp
+    }
+}
+
+/// A token split into its parts. Nothing in here is trusted until a
+/// [`TokenValidator`] has checked the signature.
+#[derive(Debug, Clone)]
+pub struct DecodedJwt<C = AuthClaims> {
+    pub header: JwtHeader,
+    pub claims: C,
+    /// `base64url(header).base64url(claims)`, the bytes the signature covers
+    pub signing_input: String,
+    pub signature: Vec<u8>,
+}
+
+fn malformed(reason: impl fmt::Display) -> SharedError {
+    SharedError::AuthenticationError(format!("Malformed token: {}", reason))
+}
+
+/// Split and decode a compact JWS without verifying it
+pub fn decode_jwt(token: &str) -> SharedResult<DecodedJwt> {
+    decode_jwt_as(token)
+}
+
+/// [`decode_jwt`] for tokens whose payload isn't an access token, e.g. an
+/// identity provider's ID token
+pub fn decode_jwt_as<C: DeserializeOwned>(token: &str) -> SharedResult<DecodedJwt<C>> {
+    let mut parts = token.trim().split('.');
+    let (Some(header_b64), Some(claims_b64), Some(signature_b64), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(malformed("expected three dot-separated parts"));
+    };
+
+    let header_json = URL_SAFE_NO_PAD.decode(header_b64).map_err(malformed)?;
+    let claims_json = URL_SAFE_NO_PAD.decode(claims_b64).map_err(malformed)?;
+    let signature = URL_SAFE_NO_PAD.decode(signature_b64).map_err(malformed)?;
+    if signature.is_empty() {
+        return Err(malformed("missing signature"));
+    }
+
+    Ok(DecodedJwt {
+        header: serde_json::from_slice(&header_json).map_err(malformed)?,
+        claims: serde_json::from_slice(&claims_json).map_err(malformed)?,
+        signing_input: format!("{}.{}", header_b64, claims_b64),
+        signature,
+    })
+}
+
+/// The part of a token that gets signed
+pub fn jwt_signing_input(header: &JwtHeader, claims: &AuthClaims) -> SharedResult<String> {
+    Ok(format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(header)?),
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims)?)
+    ))
+}
+
+/// Complete a token from its signing input and signature
+pub fn append_jwt_signature(signing_input: &str, signature: &[u8]) -> String {
+    format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature))
+}
+
+/// Checks token signatures for one algorithm. ES256/RS256 keys come from the
+/// platform's crypto provider (e.g. the backend's JWKS client); Dilithium
+/// tokens are handled by `DilithiumJwtVerifier` with the `post-quantum` feature.
+pub trait SignatureVerifier: Send + Sync {
+    fn algorithm(&self) -> JwtAlgorithm;
+
+    /// `Ok(false)` for a signature that doesn't match; `Err` for an unknown
+    /// key or unusable key material
+    fn verify(&self, key_id: Option<&str>, signing_input: &[u8], signature: &[u8]) -> SharedResult<bool>;
+}
+
+/// Validates access tokens: signature first, then expiry, issuer and audience
+#[derive(Clone)]
+pub struct TokenValidator {
+    verifiers: Vec<Arc<dyn SignatureVerifier>>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    leeway_seconds: i64,
+    revocations: Option<RevocationList>,
+}
+
+impl fmt::Debug for TokenValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenValidator")
+            .field("algorithms", &self.algorithms())
+            .field("issuer", &self.issuer)
+            .field("audience", &self.audience)
+            .field("leeway_seconds", &self.leeway_seconds)
+            .field("revocations", &self.revocations)
+            .finish()
+    }
+}
+
+impl Default for TokenValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenValidator {
+    /// A validator with no verifiers rejects every token
+    pub fn new() -> Self {
+        Self {
+            verifiers: Vec::new(),
+            issuer: None,
+            audience: None,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
+            revocations: None,
+        }
+    }
+
+    /// Accept tokens signed with the verifier's algorithm, replacing any
+    /// earlier verifier for it
+    pub fn with_verifier(mut self, verifier: Arc<dyn SignatureVerifier>) -> Self {
+        self.verifiers.retain(|existing| existing.algorithm() != verifier.algorithm());
+        self.verifiers.push(verifier);
+        self
+    }
+
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    pub fn with_leeway_seconds(mut self, leeway_seconds: i64) -> Self {
+        self.leeway_seconds = leeway_seconds.max(0);
+        self
+    }
+
+    /// Also reject tokens on this revocation list
+    pub fn with_revocation_list(mut self, revocations: RevocationList) -> Self {
+        self.revocations = Some(revocations);
+        self
+    }
+
+    pub fn algorithms(&self) -> Vec<JwtAlgorithm> {
+        self.verifiers.iter().map(|verifier| verifier.algorithm()).collect()
+    }
+
+    /// Check only the signature, for tokens whose claims are validated elsewhere
+    pub fn verify_signature<C>(&self, decoded: &DecodedJwt<C>) -> SharedResult<()> {
+        let algorithm = decoded.header.algorithm()?;
+        let verifier = self
+            .verifiers
+            .iter()
+            .find(|verifier| verifier.algorithm() == algorithm)
+            .ok_or_else(|| SharedError::AuthenticationError(format!("{} tokens are not accepted", algorithm)))?;
+        if !verifier.verify(decoded.header.kid.as_deref(), decoded.signing_input.as_bytes(), &decoded.signature)? {
+            return Err(SharedError::AuthenticationError("Invalid token signature".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self, token: &str, now: DateTime<Utc>) -> SharedResult<AuthClaims> {
+        let decoded = decode_jwt(token)?;
+        self.verify_signature(&decoded)?;
+
+        let claims = decoded.claims;
+        let now = now.timestamp();
+        if claims.exp.saturating_add(self.leeway_seconds) <= now {
+            return Err(SharedError::AuthenticationError("Token expired".to_string()));
+        }
+        if claims.nbf.is_some_and(|nbf| nbf > now.saturating_add(self.leeway_seconds)) {
+            return Err(SharedError::AuthenticationError("Token not yet valid".to_string()));
+        }
+        if let Some(issuer) = &self.issuer {
+            if claims.iss.as_ref() != Some(issuer) {
+                return Err(SharedError::AuthenticationError("Unexpected token issuer".to_string()));
+            }
+        }
+        if let Some(audience) = &self.audience {
+            if !claims.aud.contains(audience) {
+                return Err(SharedError::AuthenticationError("Token not intended for this audience".to_string()));
+            }
+        }
+        if let Some(revocations) = &self.revocations {
+            revocations.check(&claims)?;
+        }
+        Ok(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use sha2::{Digest, Sha256};
+
+    /// Stand-in for a real ES256 verifier: the "signature" is a keyed hash
+    struct HashVerifier;
+
+    fn hash_sign(signing_input: &str) -> Vec<u8> {
+        Sha256::digest(format!("test-key:{}", signing_input)).to_vec()
+    }
+
+    impl SignatureVerifier for HashVerifier {
+        fn algorithm(&self) -> JwtAlgorithm {
+            JwtAlgorithm::Es256
+        }
+
+        fn verify(&self, _key_id: Option<&str>, signing_input: &[u8], signature: &[u8]) -> SharedResult<bool> {
+            Ok(hash_sign(std::str::from_utf8(signing_input).unwrap()) == signature)
+        }
+    }
+
+    fn issue(header: &JwtHeader, claims: &AuthClaims) -> String {
+        let input = jwt_signing_input(header, claims).unwrap();
+        append_jwt_signature(&input, &hash_sign(&input))
+    }
+
+    #[test]
+    fn test_validates_signed_token() {
+        let now = Utc::now();
+        let claims = AuthClaims::new("user-42", Role::Provider, now + Duration::minutes(15))
+            .with_org("clinic-7")
+            .with_scopes(&["records:read", "chat"])
+            .with_issuer("https://auth.mydr24.com")
+            .with_audience("mydr24-app")
+            .with_token_id("tok-1");
+        let header = JwtHeader::new(JwtAlgorithm::Es256, Some("k1".to_string()));
+        let token = issue(&header, &claims);
+        let validator = TokenValidator::new()
+            .with_verifier(Arc::new(HashVerifier))
+            .with_issuer("https://auth.mydr24.com")
+            .with_audience("mydr24-app");
+
+        let validated = validator.validate(&token, now).unwrap();
+        assert_eq!(validated, claims);
+        assert!(validated.has_scope("records:read"));
+        assert!(validated.has_permission(Permission::ManageOwnSchedule));
+        assert_eq!(AuthClaims::from_token_unverified(&token).unwrap().org_id.as_deref(), Some("clinic-7"));
+
+        // Expired beyond the leeway, wrong audience, or an algorithm without a verifier
+        assert!(validator.validate(&token, now + Duration::minutes(17)).is_err());
+        assert!(validator.clone().with_audience("admin-portal").validate(&token, now).is_err());
+        let dilithium = issue(&JwtHeader::new(JwtAlgorithm::Dilithium2, None), &claims);
+        assert!(validator.validate(&dilithium, now).is_err());
+
+        // Revoked before it expired
+        let revocations = RevocationList::new();
+        let validator = validator.with_revocation_list(revocations.clone());
+        assert!(validator.validate(&token, now).is_ok());
+        revocations.revoke_token(crate::auth::RevokedToken {
+            jti: "tok-1".to_string(),
+            user_id: None,
+            expires_at: now + Duration::minutes(15),
+            revoked_at: now,
+            reason: Some("device lost".to_string()),
+        });
+        assert!(validator.validate(&token, now).is_err());
+    }
+
+    #[test]
+    fn test_rejects_tampered_and_unsigned_tokens() {
+        let now = Utc::now();
+        let validator = TokenValidator::new().with_verifier(Arc::new(HashVerifier));
+        let header = JwtHeader::new(JwtAlgorithm::Es256, None);
+        let token = issue(&header, &AuthClaims::new("user-42", Role::Patient, now + Duration::hours(1)));
+
+        // Swap in an admin payload but keep the patient's signature
+        let admin = AuthClaims::new("user-42", Role::Admin, now + Duration::hours(1));
+        let forged_input = jwt_signing_input(&header, &admin).unwrap();
+        let signature = token.rsplit('.').next().unwrap();
+        assert!(validator.validate(&format!("{}.{}", forged_input, signature), now).is_err());
+
+        let none_header = URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#);
+        let payload = token.split('.').nth(1).unwrap();
+        assert!(validator.validate(&format!("{}.{}.", none_header, payload), now).is_err());
+        assert!(validator.validate(&format!("{}.{}.c2ln", none_header, payload), now).is_err());
+        assert!(decode_jwt("not-a-token").is_err());
+
+        // OAuth servers send scopes as one space-separated string and may send a single audience
+        let oauth = URL_SAFE_NO_PAD.encode(
+            r#"{"sub":"u1","role":"patient","scope":"openid chat","aud":"mydr24-app","exp":4102444800}"#,
+        );
+        let claims = AuthClaims::from_token_unverified(&format!("{}.{}.c2ln", none_header, oauth)).unwrap();
+        assert_eq!(claims.scopes, vec!["openid", "chat"]);
+        assert_eq!(claims.aud, vec!["mydr24-app"]);
+    }
+}
@@ -0,0 +1,282 @@
+//! Sign-in throttling and account lockout: a growing delay after repeated failed attempts, then a timed lockout, with the same rules on the server and in every app
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use crate::errors::{SharedError, SharedResult};
+
+/// How quickly repeated failures slow down and then lock an account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LockoutPolicy {
+    /// Failures allowed before any delay, for typos
+    pub free_attempts: u32,
+    /// Delay after the first throttled failure; it doubles with each further one
+    pub base_delay_seconds: u32,
+    pub max_delay_seconds: u32,
+    /// Failures at which the account is locked outright
+    pub lockout_threshold: u32,
+    pub lockout_minutes: u32,
+    /// Failures older than this are forgotten
+    pub reset_after_minutes: u32,
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self {
+            free_attempts: 3,
+            base_delay_seconds: 2,
+            max_delay_seconds: 300,
+            lockout_threshold: 10,
+            lockout_minutes: 15,
+            reset_after_minutes: 60,
+        }
+    }
+}
+
+impl LockoutPolicy {
+    pub fn is_locked_after(&self, failures: u32) -> bool {
+        self.lockout_threshold > 0 && failures >= self.lockout_threshold
+    }
+
+    /// Wait imposed after `failures` consecutive failed attempts
+    pub fn delay_after(&self, failures: u32) -> Duration {
+        if self.is_locked_after(failures) {
+            return Duration::minutes(self.lockout_minutes.into());
+        }
+        if failures <= self.free_attempts {
+            return Duration::zero();
+        }
+        let doublings = (failures - self.free_attempts - 1).min(16);
+        let seconds = (u64::from(self.base_delay_seconds) << doublings).min(self.max_delay_seconds.into());
+        Duration::seconds(seconds as i64)
+    }
+
+    /// Status after `failures` failures, the last one at `last_failure_at`
+    pub fn status_after(&self, failures: u32, last_failure_at: DateTime<Utc>) -> LockoutStatus {
+        let delay = self.delay_after(failures);
+        LockoutStatus {
+            failed_attempts: failures,
+            remaining_attempts: self.lockout_threshold.saturating_sub(failures),
+            retry_at: (delay > Duration::zero()).then(|| last_failure_at + delay),
+            locked: self.is_locked_after(failures),
+        }
+    }
+}
+
+/// Throttling state of one account, as returned with a failed sign-in so
+/// every app shows the same countdown
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LockoutStatus {
+    pub failed_attempts: u32,
+    /// Failures left before the account is locked
+    pub remaining_attempts: u32,
+    /// No sign-in may be attempted before this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub locked: bool,
+}
+
+impl LockoutStatus {
+    /// Time left before the next attempt is allowed, if any
+    pub fn retry_in(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.retry_at.map(|at| at - now).filter(|left| *left > Duration::zero())
+    }
+
+    /// Whole seconds left, rounded up so a countdown never shows 0 while blocked
+    pub fn retry_in_seconds(&self, now: DateTime<Utc>) -> Option<u64> {
+        self.retry_in(now).map(|left| (left.num_milliseconds() as u64).div_ceil(1000))
+    }
+
+    pub fn is_blocked(&self, now: DateTime<Utc>) -> bool {
+        self.retry_in(now).is_some()
+    }
+
+    pub fn check(&self, now: DateTime<Utc>) -> SharedResult<()> {
+        let Some(seconds) = self.retry_in_seconds(now) else {
+            return Ok(());
+        };
+        Err(SharedError::RateLimitError(if self.locked {
+            format!("Account locked after too many failed sign-in attempts; try again in {} seconds", seconds)
+        } else {
+            format!("Too many failed sign-in attempts; try again in {} seconds", seconds)
+        }))
+    }
+}
+
+/// Error body of a rejected sign-in. `lockout` is set once the account is
+/// being throttled, so apps can show a countdown instead of a bare error.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoginFailure {
+    #[serde(default)]
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lockout: Option<LockoutStatus>,
+}
+
+#[derive(Debug, Clone)]
+struct AttemptRecord {
+    failures: u32,
+    last_failure_at: DateTime<Utc>,
+    status: LockoutStatus,
+}
+
+/// Failed sign-ins per account. Accounts are matched case-insensitively.
+/// Cheap to clone; clones share state, like `RevocationList`.
+#[derive(Clone, Default)]
+pub struct LoginThrottle {
+    policy: LockoutPolicy,
+    attempts: Arc<RwLock<HashMap<String, AttemptRecord>>>,
+}
+
+impl fmt::Debug for LoginThrottle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tracked = self.attempts.read().map(|a| a.len()).unwrap_or(0);
+        f.debug_struct("LoginThrottle").field("policy", &self.policy).field("tracked", &tracked).finish()
+    }
+}
+
+fn account_key(account: &str) -> String {
+    account.trim().to_lowercase()
+}
+
+impl LoginThrottle {
+    pub fn new(policy: LockoutPolicy) -> Self {
+        Self { policy, attempts: Arc::default() }
+    }
+
+    pub fn policy(&self) -> &LockoutPolicy {
+        &self.policy
+    }
+
+    /// Current status; a served lockout or a long quiet spell clears the count
+    pub fn status(&self, account: &str, now: DateTime<Utc>) -> LockoutStatus {
+        let Ok(attempts) = self.attempts.read() else {
+            return LockoutStatus::default();
+        };
+        match attempts.get(&account_key(account)) {
+            Some(record) if !self.is_expired(record, now) => record.status.clone(),
+            _ => self.policy.status_after(0, now),
+        }
+    }
+
+    /// Refuse an attempt while the account is delayed or locked
+    pub fn check(&self, account: &str, now: DateTime<Utc>) -> SharedResult<()> {
+        self.status(account, now).check(now)
+    }
+
+    pub fn record_failure(&self, account: &str, now: DateTime<Utc>) -> LockoutStatus {
+        let Ok(mut attempts) = self.attempts.write() else {
+            return LockoutStatus::default();
+        };
+        let failures = match attempts.get(&account_key(account)) {
+            Some(record) if !self.is_expired(record, now) => record.failures + 1,
+            _ => 1,
+        };
+        let status = self.policy.status_after(failures, now);
+        attempts.insert(account_key(account), AttemptRecord { failures, last_failure_at: now, status: status.clone() });
+        status
+    }
+
+    pub fn record_success(&self, account: &str) {
+        if let Ok(mut attempts) = self.attempts.write() {
+            attempts.remove(&account_key(account));
+        }
+    }
+
+    /// Adopt the status the server reported, which also counts attempts made
+    /// from other devices
+    pub fn apply(&self, account: &str, status: LockoutStatus, now: DateTime<Utc>) {
+        if let Ok(mut attempts) = self.attempts.write() {
+            let record = AttemptRecord { failures: status.failed_attempts, last_failure_at: now, status };
+            attempts.insert(account_key(account), record);
+        }
+    }
+
+    /// Forget accounts whose failures no longer count; returns how many
+    pub fn prune(&self, now: DateTime<Utc>) -> usize {
+        let Ok(mut attempts) = self.attempts.write() else {
+            return 0;
+        };
+        let before = attempts.len();
+        attempts.retain(|_, record| !self.is_expired(record, now));
+        before - attempts.len()
+    }
+
+    fn is_expired(&self, record: &AttemptRecord, now: DateTime<Utc>) -> bool {
+        if record.status.locked {
+            return !record.status.is_blocked(now);
+        }
+        now - record.last_failure_at >= Duration::minutes(self.policy.reset_after_minutes.into())
+            && !record.status.is_blocked(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_grows_then_locks() {
+        let policy = LockoutPolicy::default();
+        assert_eq!(policy.delay_after(3), Duration::zero());
+        assert_eq!(policy.delay_after(4), Duration::seconds(2));
+        assert_eq!(policy.delay_after(6), Duration::seconds(8));
+        assert_eq!(policy.delay_after(10), Duration::minutes(15));
+        let capped = LockoutPolicy { lockout_threshold: 0, ..LockoutPolicy::default() };
+        assert_eq!(capped.delay_after(40), Duration::seconds(300));
+
+        let now = Utc::now();
+        let throttle = LoginThrottle::new(policy);
+        for _ in 0..3 {
+            assert!(throttle.check("Dr@Example.com", now).is_ok());
+            throttle.record_failure("dr@example.com", now);
+        }
+        let status = throttle.record_failure("dr@example.com ", now);
+        assert_eq!(status.remaining_attempts, 6);
+        assert_eq!(status.retry_in(now), Some(Duration::seconds(2)));
+        assert_eq!(status.retry_in_seconds(now + Duration::milliseconds(500)), Some(2));
+        assert!(matches!(throttle.check("DR@example.com", now), Err(SharedError::RateLimitError(_))));
+        assert!(throttle.check("dr@example.com", now + Duration::seconds(2)).is_ok());
+
+        for _ in 0..6 {
+            throttle.record_failure("dr@example.com", now);
+        }
+        let locked = throttle.status("dr@example.com", now + Duration::minutes(14));
+        assert!(locked.locked && locked.is_blocked(now + Duration::minutes(14)));
+        // A served lockout starts the count again
+        assert_eq!(throttle.status("dr@example.com", now + Duration::minutes(15)).failed_attempts, 0);
+        assert!(throttle.check("other@example.com", now).is_ok());
+    }
+
+    #[test]
+    fn test_success_resets_and_server_status_applies() {
+        let now = Utc::now();
+        let throttle = LoginThrottle::default();
+        let device = throttle.clone();
+        for _ in 0..5 {
+            device.record_failure("nurse@example.com", now);
+        }
+        assert_eq!(throttle.status("nurse@example.com", now).failed_attempts, 5);
+        throttle.record_success("nurse@example.com");
+        assert_eq!(device.status("nurse@example.com", now).failed_attempts, 0);
+
+        // Old failures are forgotten
+        throttle.record_failure("nurse@example.com", now);
+        assert_eq!(throttle.prune(now + Duration::minutes(61)), 1);
+
+        let retry_at = (now + Duration::minutes(15)).to_rfc3339();
+        let json = format!(
+            r#"{{"message":"Account locked","lockout":{{"failed_attempts":10,"remaining_attempts":0,"retry_at":"{}","locked":true}}}}"#,
+            retry_at
+        );
+        let failure: LoginFailure = serde_json::from_str(&json).unwrap();
+        throttle.apply("nurse@example.com", failure.lockout.unwrap(), now);
+        let err = throttle.check("nurse@example.com", now + Duration::minutes(10)).unwrap_err();
+        assert!(err.to_string().contains("Account locked"));
+    }
+}
@@ -0,0 +1,39 @@
+//! Authentication: access tokens and their revocation, passwords and sign-in lockout, sessions, devices, QR pairing and device-signed requests, MFA, WebAuthn, OIDC single sign-on, end-to-end chat keys, role- and attribute-based access control, access reasons, break-glass emergency access, and post-quantum signatures and key exchange
+
+pub mod abac;
+pub mod access_reason;
+pub mod break_glass;
+pub mod chat_keys;
+pub mod device;
+pub mod jwt;
+pub mod lockout;
+pub mod oidc;
+pub mod pairing;
+pub mod password;
+pub mod rbac;
+pub mod request_signing;
+pub mod revocation;
+pub mod session;
+pub mod totp;
+pub mod webauthn;
+#[cfg(feature = "post-quantum")]
+pub mod post_quantum;
+
+pub use abac::*;
+pub use access_reason::*;
+pub use break_glass::*;
+pub use chat_keys::*;
+pub use device::*;
+pub use jwt::*;
+pub use lockout::*;
+pub use oidc::*;
+pub use pairing::*;
+pub use password::*;
+pub use rbac::*;
+pub use request_signing::*;
+pub use revocation::*;
+pub use session::*;
+pub use totp::*;
+pub use webauthn::*;
+#[cfg(feature = "post-quantum")]
+pub use post_quantum::*;
@@ -0,0 +1,678 @@
+//! OpenID Connect sign-in for hospital SSO: authorization code flow with PKCE, mapped onto the platform's own claims
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::jwt::{decode_jwt_as, AuthClaims, SignatureVerifier, TokenValidator, DEFAULT_LEEWAY_SECONDS};
+use super::rbac::Role;
+use crate::api_backend::{percent_encode, ApiBackend, ApiRequestBuilder, HttpRequest};
+use crate::errors::{SharedError, SharedResult};
+use crate::secrets::SecretString;
+
+/// How long a started sign-in may take before its callback is refused
+pub const AUTHORIZATION_TIMEOUT_MINUTES: i64 = 10;
+
+/// One identity provider the platform trusts, e.g. a hospital's Azure AD
+/// tenant or Keycloak realm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OidcProviderConfig {
+    /// Must equal the `issuer` in the provider's discovery document
+    pub issuer: String,
+    pub client_id: String,
+    /// Only for confidential clients; browser and mobile builds leave it unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<SecretString>,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    /// Dot-separated path to the ID token claim listing the user's groups or
+    /// roles, e.g. `roles` or `realm_access.roles`
+    pub role_claim: String,
+    /// Provider role or group name to platform role. Names that aren't mapped
+    /// are also tried as platform role names.
+    #[serde(default)]
+    pub role_mapping: HashMap<String, Role>,
+    /// Role for users with no recognised role; `None` refuses them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_role: Option<Role>,
+    /// Claim identifying the user's organisation, e.g. Azure's `tid`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_claim: Option<String>,
+}
+
+impl OidcProviderConfig {
+    pub fn new(issuer: &str, client_id: &str, redirect_uri: &str) -> Self {
+        Self {
+            issuer: issuer.trim_end_matches('/').to_string(),
+            client_id: client_id.to_string(),
+            client_secret: None,
+            redirect_uri: redirect_uri.to_string(),
+            scopes: vec!["openid".to_string(), "profile".to_string(), "email".to_string()],
+            role_claim: "roles".to_string(),
+            role_mapping: HashMap::new(),
+            default_role: None,
+            org_claim: None,
+        }
+    }
+
+    /// Azure AD (Entra ID) v2 endpoint; app roles arrive in `roles`, the tenant in `tid`
+    pub fn azure_ad(tenant_id: &str, client_id: &str, redirect_uri: &str) -> Self {
+        let issuer = format!("https://login.microsoftonline.com/{}/v2.0", tenant_id);
+        Self::new(&issuer, client_id, redirect_uri).with_org_claim("tid")
+    }
+
+    /// Keycloak realm; realm roles arrive in `realm_access.roles`
+    pub fn keycloak(base_url: &str, realm: &str, client_id: &str, redirect_uri: &str) -> Self {
+        let issuer = format!("{}/realms/{}", base_url.trim_end_matches('/'), realm);
+        Self::new(&issuer, client_id, redirect_uri).with_role_claim("realm_access.roles")
+    }
+
+    pub fn with_client_secret(mut self, client_secret: impl Into<String>) -> Self {
+        self.client_secret = Some(SecretString::new(client_secret));
+        self
+    }
+
+    pub fn with_scope(mut self, scope: &str) -> Self {
+        if !self.scopes.iter().any(|s| s == scope) {
+            self.scopes.push(scope.to_string());
+        }
+        self
+    }
+
+    pub fn with_role_claim(mut self, role_claim: &str) -> Self {
+        self.role_claim = role_claim.to_string();
+        self
+    }
+
+    pub fn map_role(mut self, provider_role: &str, role: Role) -> Self {
+        self.role_mapping.insert(provider_role.to_string(), role);
+        self
+    }
+
+    pub fn with_default_role(mut self, role: Role) -> Self {
+        self.default_role = Some(role);
+        self
+    }
+
+    pub fn with_org_claim(mut self, org_claim: &str) -> Self {
+        self.org_claim = Some(org_claim.to_string());
+        self
+    }
+
+    pub fn discovery_url(&self) -> String {
+        format!("{}/.well-known/openid-configuration", self.issuer)
+    }
+
+    /// The platform role for the provider roles in `claims`. A user holding
+    /// several gets the one granting the most permissions, or the first listed
+    /// if they grant equally many.
+    pub fn resolve_role(&self, claims: &Value) -> SharedResult<Role> {
+        let provider_roles = claim_at(claims, &self.role_claim).map(string_list).unwrap_or_default();
+        provider_roles
+            .iter()
+            .filter_map(|name| self.role_mapping.get(name).copied().or_else(|| Role::parse(name)))
+            .rev()
+            .max_by_key(|role| role.permissions().len())
+            .or(self.default_role)
+            .ok_or_else(|| {
+                SharedError::AuthorizationError(format!(
+                    "No platform role is assigned to this account at {}",
+                    self.issuer
+                ))
+            })
+    }
+}
+
+fn claim_at<'a>(claims: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(claims, |value, key| value.get(key))
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(items) => items.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The fields of `/.well-known/openid-configuration` the client needs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OidcDiscovery {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwks_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_session_endpoint: Option<String>,
+    #[serde(default)]
+    pub code_challenge_methods_supported: Vec<String>,
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// RFC 7636 proof key: the verifier stays on the device, only its hash goes
+/// into the authorization URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PkceChallenge {
+    pub verifier: SecretString,
+    pub challenge: String,
+}
+
+impl PkceChallenge {
+    pub const METHOD: &'static str = "S256";
+
+    pub fn generate() -> Self {
+        Self::from_verifier(random_token())
+    }
+
+    pub fn from_verifier(verifier: String) -> Self {
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+        Self { verifier: SecretString::new(verifier), challenge }
+    }
+}
+
+/// A sign-in in progress. Kept (e.g. in session storage) until the provider
+/// redirects back, then consumed by [`OidcClient::complete`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingAuthorization {
+    pub state: String,
+    pub nonce: String,
+    pub pkce: PkceChallenge,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PendingAuthorization {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.created_at + Duration::minutes(AUTHORIZATION_TIMEOUT_MINUTES)
+    }
+}
+
+/// Token endpoint response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OidcTokenResponse {
+    pub access_token: SecretString,
+    /// Always sent on sign-in; providers usually leave it out on refresh
+    #[serde(default)]
+    pub id_token: Option<SecretString>,
+    #[serde(default)]
+    pub token_type: Option<String>,
+    #[serde(default)]
+    pub expires_in: Option<i64>,
+    #[serde(default)]
+    pub refresh_token: Option<SecretString>,
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+/// A completed SSO sign-in. `claims` has the same shape as a password
+/// login's, so the rest of the crate doesn't care how the user signed in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcLogin {
+    pub claims: AuthClaims,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    /// The provider's ID token, exchanged with the platform backend for a
+    /// platform session
+    pub id_token: SecretString,
+    pub access_token: SecretString,
+    pub refresh_token: Option<SecretString>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// OIDC relying party for one provider
+#[derive(Clone)]
+pub struct OidcClient {
+    config: OidcProviderConfig,
+    discovery: OidcDiscovery,
+    backend: Arc<dyn ApiBackend>,
+    signature_validator: Option<TokenValidator>,
+}
+
+impl fmt::Debug for OidcClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OidcClient")
+            .field("issuer", &self.config.issuer)
+            .field("client_id", &self.config.client_id)
+            .field("signature_validator", &self.signature_validator.is_some())
+            .finish()
+    }
+}
+
+fn provider_error(response_body: &Value, fallback: String) -> SharedError {
+    let error = response_body.get("error").and_then(Value::as_str);
+    let description = response_body.get("error_description").and_then(Value::as_str);
+    SharedError::AuthenticationError(match (error, description) {
+        (Some(error), Some(description)) => format!("Sign-in failed: {} ({})", description, error),
+        (Some(error), None) => format!("Sign-in failed: {}", error),
+        _ => fallback,
+    })
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            // Hex digits are ASCII, so this never splits a multibyte character
+            b'%' if i + 2 < bytes.len() && bytes[i + 1].is_ascii_hexdigit() && bytes[i + 2].is_ascii_hexdigit() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                decoded.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                i += 2;
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Query parameters of a redirect URL, or of a bare query string
+pub fn parse_query(url: &str) -> HashMap<String, String> {
+    let query = url.split_once('?').map(|(_, q)| q).unwrap_or(url);
+    let query = query.split('#').next().unwrap_or("");
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+impl OidcClient {
+    pub fn new(config: OidcProviderConfig, discovery: OidcDiscovery, backend: Arc<dyn ApiBackend>) -> SharedResult<Self> {
+        if discovery.issuer.trim_end_matches('/') != config.issuer {
+            return Err(SharedError::ConfigurationError(format!(
+                "Discovery document is for issuer {}, expected {}",
+                discovery.issuer, config.issuer
+            )));
+        }
+        Ok(Self { config, discovery, backend, signature_validator: None })
+    }
+
+    /// Fetch the provider's discovery document and build a client from it
+    pub async fn discover(config: OidcProviderConfig, backend: Arc<dyn ApiBackend>) -> SharedResult<Self> {
+        let response = backend
+            .execute(HttpRequest::get(&config.discovery_url()))
+            .await
+            .map_err(SharedError::NetworkError)?;
+        if !response.ok() {
+            return Err(SharedError::ConfigurationError(format!(
+                "OIDC discovery for {} failed: {}",
+                config.issuer,
+                response.error_detail()
+            )));
+        }
+        let discovery: OidcDiscovery = response.parse_json().map_err(SharedError::SerializationError)?;
+        Self::new(config, discovery, backend)
+    }
+
+    /// Verify ID token signatures with the provider's keys, e.g. from a JWKS
+    /// client. Without a verifier the ID token is trusted because it came
+    /// straight from the token endpoint over TLS (OIDC Core 3.1.3.7).
+    pub fn with_verifier(mut self, verifier: Arc<dyn SignatureVerifier>) -> Self {
+        let validator = self.signature_validator.take().unwrap_or_default();
+        self.signature_validator = Some(validator.with_verifier(verifier));
+        self
+    }
+
+    pub fn config(&self) -> &OidcProviderConfig {
+        &self.config
+    }
+
+    pub fn discovery(&self) -> &OidcDiscovery {
+        &self.discovery
+    }
+
+    /// Start a sign-in: returns the URL to send the user to and the state to
+    /// keep until they come back
+    pub fn authorization_request(&self, login_hint: Option<&str>, now: DateTime<Utc>) -> (String, PendingAuthorization) {
+        let pending = PendingAuthorization {
+            state: random_token(),
+            nonce: random_token(),
+            pkce: PkceChallenge::generate(),
+            created_at: now,
+        };
+        let scope = self.config.scopes.join(" ");
+        let mut params = vec![
+            ("response_type", "code"),
+            ("client_id", self.config.client_id.as_str()),
+            ("redirect_uri", self.config.redirect_uri.as_str()),
+            ("scope", scope.as_str()),
+            ("state", pending.state.as_str()),
+            ("nonce", pending.nonce.as_str()),
+            ("code_challenge", pending.pkce.challenge.as_str()),
+            ("code_challenge_method", PkceChallenge::METHOD),
+        ];
+        if let Some(hint) = login_hint {
+            params.push(("login_hint", hint));
+        }
+        let url = ApiRequestBuilder::new("GET", &self.discovery.authorization_endpoint)
+            .query(params)
+            .build()
+            .url;
+        (url, pending)
+    }
+
+    /// Finish a sign-in from the URL the provider redirected back to
+    pub async fn complete(
+        &self,
+        pending: &PendingAuthorization,
+        callback_url: &str,
+        now: DateTime<Utc>,
+    ) -> SharedResult<OidcLogin> {
+        let params = parse_query(callback_url);
+        if params.contains_key("error") {
+            let body = serde_json::to_value(&params)?;
+            return Err(provider_error(&body, "Sign-in failed".to_string()));
+        }
+        if params.get("state") != Some(&pending.state) {
+            return Err(SharedError::AuthenticationError("Sign-in response does not match this request".to_string()));
+        }
+        if pending.is_expired(now) {
+            return Err(SharedError::AuthenticationError("Sign-in took too long; please try again".to_string()));
+        }
+        let code = params
+            .get("code")
+            .ok_or_else(|| SharedError::AuthenticationError("Sign-in response has no authorization code".to_string()))?;
+
+        let tokens = self
+            .token_request(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &self.config.redirect_uri),
+                ("code_verifier", pending.pkce.verifier.expose()),
+            ])
+            .await?;
+        self.login_from_tokens(tokens, Some(&pending.nonce), now)
+    }
+
+    /// Renew an SSO session with its refresh token. If the provider doesn't
+    /// send a new ID token, the previous one and its identity are kept.
+    pub async fn refresh(&self, previous: &OidcLogin, now: DateTime<Utc>) -> SharedResult<OidcLogin> {
+        let refresh_token = previous
+            .refresh_token
+            .as_ref()
+            .ok_or_else(|| SharedError::AuthenticationError("Sign-in has no refresh token; sign in again".to_string()))?;
+        let tokens = self
+            .token_request(&[("grant_type", "refresh_token"), ("refresh_token", refresh_token.expose())])
+            .await?;
+        if tokens.id_token.is_some() {
+            let mut login = self.login_from_tokens(tokens, None, now)?;
+            login.refresh_token = login.refresh_token.or_else(|| previous.refresh_token.clone());
+            return Ok(login);
+        }
+        Ok(OidcLogin {
+            expires_at: tokens.expires_in.map(|seconds| now + Duration::seconds(seconds)),
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token.or_else(|| previous.refresh_token.clone()),
+            ..previous.clone()
+        })
+    }
+
+    async fn token_request(&self, params: &[(&str, &str)]) -> SharedResult<OidcTokenResponse> {
+        let mut form: Vec<(&str, &str)> = params.to_vec();
+        form.push(("client_id", &self.config.client_id));
+        if let Some(secret) = &self.config.client_secret {
+            form.push(("client_secret", secret.expose()));
+        }
+        let body: Vec<String> = form
+            .iter()
+            .map(|(k, v)| format!("{}={}", percent_encode(k), percent_encode(v)))
+            .collect();
+        let request = ApiRequestBuilder::new("POST", &self.discovery.token_endpoint)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Accept", "application/json")
+            .bytes(body.join("&").into_bytes());
+
+        let response = self.backend.execute(request).await.map_err(SharedError::NetworkError)?;
+        if !response.ok() {
+            let body = response.parse_json::<Value>().unwrap_or(Value::Null);
+            return Err(provider_error(&body, format!("Token request failed: {}", response.error_detail())));
+        }
+        response.parse_json().map_err(SharedError::SerializationError)
+    }
+
+    fn login_from_tokens(
+        &self,
+        tokens: OidcTokenResponse,
+        nonce: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> SharedResult<OidcLogin> {
+        let id_token = tokens
+            .id_token
+            .ok_or_else(|| SharedError::AuthenticationError("Sign-in response has no ID token".to_string()))?;
+        let id_claims = self.validate_id_token(id_token.expose(), nonce, now)?;
+        let subject = id_claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .ok_or_else(|| SharedError::AuthenticationError("ID token has no subject".to_string()))?;
+        let exp = id_claims.get("exp").and_then(Value::as_i64).unwrap_or_default();
+        let role = self.config.resolve_role(&id_claims)?;
+
+        let mut claims = AuthClaims::new(subject, role, now)
+            .with_issuer(&self.config.issuer)
+            .with_audience(&self.config.client_id);
+        claims.exp = exp;
+        claims.iat = id_claims.get("iat").and_then(Value::as_i64);
+        if let Some(org) = self.config.org_claim.as_deref().and_then(|path| claim_at(&id_claims, path)).and_then(Value::as_str) {
+            claims = claims.with_org(org);
+        }
+        if let Some(scope) = &tokens.scope {
+            claims.scopes = scope.split_whitespace().map(str::to_string).collect();
+        }
+
+        let text_claim = |name: &str| id_claims.get(name).and_then(Value::as_str).map(str::to_string);
+        Ok(OidcLogin {
+            email: text_claim("email").or_else(|| text_claim("preferred_username")),
+            name: text_claim("name"),
+            claims,
+            expires_at: tokens.expires_in.map(|seconds| now + Duration::seconds(seconds)),
+            id_token,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+        })
+    }
+
+    /// Check the ID token's signature (when a verifier is configured), issuer,
+    /// audience, expiry and nonce; returns its raw claims
+    pub fn validate_id_token(&self, id_token: &str, nonce: Option<&str>, now: DateTime<Utc>) -> SharedResult<Value> {
+        let decoded = decode_jwt_as::<Value>(id_token)?;
+        if let Some(validator) = &self.signature_validator {
+            validator.verify_signature(&decoded)?;
+        }
+        let claims = decoded.claims;
+
+        if claims.get("iss").and_then(Value::as_str) != Some(self.discovery.issuer.as_str()) {
+            return Err(SharedError::AuthenticationError("ID token from unexpected issuer".to_string()));
+        }
+        if !claims.get("aud").map(string_list).unwrap_or_default().contains(&self.config.client_id) {
+            return Err(SharedError::AuthenticationError("ID token not intended for this client".to_string()));
+        }
+        let exp = claims
+            .get("exp")
+            .and_then(Value::as_i64)
+            .ok_or_else(|| SharedError::AuthenticationError("ID token has no expiry".to_string()))?;
+        if exp.saturating_add(DEFAULT_LEEWAY_SECONDS) <= now.timestamp() {
+            return Err(SharedError::AuthenticationError("ID token expired".to_string()));
+        }
+        if let Some(nonce) = nonce {
+            if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+                return Err(SharedError::AuthenticationError("ID token nonce mismatch".to_string()));
+            }
+        }
+        Ok(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_backend::{HttpBody, MockApiClient};
+    use serde_json::json;
+    use std::future::Future;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(output) => output,
+            std::task::Poll::Pending => panic!("mock backend future should resolve immediately"),
+        }
+    }
+
+    const ISSUER: &str = "https://sso.stmarys.example/realms/staff";
+
+    fn discovery() -> OidcDiscovery {
+        OidcDiscovery {
+            issuer: ISSUER.to_string(),
+            authorization_endpoint: format!("{}/protocol/openid-connect/auth", ISSUER),
+            token_endpoint: format!("{}/protocol/openid-connect/token", ISSUER),
+            jwks_uri: None,
+            end_session_endpoint: None,
+            code_challenge_methods_supported: vec!["S256".to_string()],
+        }
+    }
+
+    fn id_token(claims: Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256","typ":"JWT"}"#);
+        format!("{}.{}.{}", header, URL_SAFE_NO_PAD.encode(claims.to_string()), URL_SAFE_NO_PAD.encode("sig"))
+    }
+
+    fn config() -> OidcProviderConfig {
+        OidcProviderConfig::keycloak("https://sso.stmarys.example/", "staff", "mydr24-web", "https://app.mydr24.com/sso/callback")
+            .map_role("attending", Role::Physician)
+            .map_role("ward-nurse", Role::Nurse)
+            .with_org_claim("hospital")
+    }
+
+    #[test]
+    fn test_authorization_code_flow_with_pkce() {
+        let now = Utc::now();
+        let backend = MockApiClient::new().with_fixture(
+            "GET",
+            "realms/staff/.well-known/openid-configuration",
+            200,
+            &discovery(),
+        );
+        let client = block_on(OidcClient::discover(config(), Arc::new(backend.clone()))).unwrap();
+        let (url, pending) = client.authorization_request(Some("dr.lee@stmarys.example"), now);
+        let query = parse_query(&url);
+        assert!(url.starts_with(&client.discovery().authorization_endpoint));
+        assert_eq!(query["code_challenge"], pending.pkce.challenge);
+        assert_eq!(query["code_challenge_method"], "S256");
+        assert_eq!(query["scope"], "openid profile email");
+        assert_eq!(query["login_hint"], "dr.lee@stmarys.example");
+        assert!(!url.contains(pending.pkce.verifier.expose()));
+
+        let token = id_token(json!({
+            "iss": ISSUER, "aud": ["mydr24-web"], "sub": "kc-7781", "exp": (now + Duration::minutes(5)).timestamp(),
+            "nonce": pending.nonce, "email": "dr.lee@stmarys.example", "hospital": "st-marys",
+            "realm_access": { "roles": ["offline_access", "attending", "ward-nurse"] },
+        }));
+        backend.push_reply(
+            "POST",
+            "realms/staff/protocol/openid-connect/token",
+            crate::api_backend::MockReply::Respond(crate::api_backend::HttpResponse::json(
+                200,
+                &json!({ "access_token": "at-1", "id_token": token, "expires_in": 300, "scope": "openid email", "refresh_token": "rt-1" }),
+            )),
+        );
+
+        let callback = format!("https://app.mydr24.com/sso/callback?code=abc%2B1&state={}", pending.state);
+        let login = block_on(client.complete(&pending, &callback, now)).unwrap();
+        assert_eq!(login.claims.user_id, "kc-7781");
+        assert_eq!(login.claims.role, Role::Physician);
+        assert_eq!(login.claims.org_id.as_deref(), Some("st-marys"));
+        assert_eq!(login.claims.iss.as_deref(), Some(ISSUER));
+        assert!(login.claims.has_scope("email"));
+        assert_eq!(login.email.as_deref(), Some("dr.lee@stmarys.example"));
+
+        let token_request = backend.requests().into_iter().last().unwrap();
+        assert_eq!(token_request.header("Content-Type"), Some("application/x-www-form-urlencoded"));
+        let HttpBody::Bytes(form) = token_request.body else { panic!("expected a form body") };
+        let form = parse_query(&String::from_utf8(form).unwrap());
+        assert_eq!(form["code"], "abc+1");
+        assert_eq!(form["code_verifier"], pending.pkce.verifier.expose());
+        assert_eq!(PkceChallenge::from_verifier(form["code_verifier"].clone()).challenge, pending.pkce.challenge);
+
+        // Forged state, provider errors and stale sign-ins are refused before any token request
+        let forged = "https://app.mydr24.com/sso/callback?code=abc&state=other";
+        assert!(block_on(client.complete(&pending, forged, now)).is_err());
+        let denied = format!("?error=access_denied&error_description=User+cancelled&state={}", pending.state);
+        let error = block_on(client.complete(&pending, &denied, now)).unwrap_err();
+        assert!(error.to_string().contains("User cancelled"));
+        assert!(block_on(client.complete(&pending, &callback, now + Duration::minutes(11))).is_err());
+
+        // Refresh responses without an ID token keep the signed-in identity
+        backend.push_reply(
+            "POST",
+            "realms/staff/protocol/openid-connect/token",
+            crate::api_backend::MockReply::Respond(crate::api_backend::HttpResponse::json(
+                200,
+                &json!({ "access_token": "at-2", "expires_in": 300 }),
+            )),
+        );
+        let refreshed = block_on(client.refresh(&login, now + Duration::minutes(4))).unwrap();
+        assert_eq!(refreshed.access_token.expose(), "at-2");
+        assert_eq!(refreshed.id_token, login.id_token);
+        assert_eq!(refreshed.claims, login.claims);
+        assert_eq!(refreshed.refresh_token.as_ref().map(|t| t.expose()), Some("rt-1"));
+        assert_eq!(refreshed.expires_at, Some(now + Duration::minutes(9)));
+        let HttpBody::Bytes(form) = backend.requests().into_iter().last().unwrap().body else { panic!("expected a form body") };
+        assert_eq!(parse_query(&String::from_utf8(form).unwrap())["refresh_token"], "rt-1");
+    }
+
+    #[test]
+    fn test_parse_query_with_non_ascii_input() {
+        let params = parse_query("https://app.mydr24.com/sso/callback?code=x%aé&state=%é%&name=Jos%C3%A9+M");
+        assert_eq!(params["code"], "x%aé");
+        assert_eq!(params["state"], "%é%");
+        assert_eq!(params["name"], "José M");
+        assert_eq!(parse_query("?a=%zz%4")["a"], "%zz%4");
+    }
+
+    #[test]
+    fn test_id_token_validation_and_role_mapping() {
+        let now = Utc::now();
+        let client = OidcClient::new(config(), discovery(), Arc::new(MockApiClient::new())).unwrap();
+        let claims = |overrides: Value| {
+            let mut claims = json!({
+                "iss": ISSUER, "aud": "mydr24-web", "sub": "kc-1", "nonce": "n-1",
+                "exp": (now + Duration::minutes(5)).timestamp(),
+            });
+            claims.as_object_mut().unwrap().extend(overrides.as_object().unwrap().clone());
+            id_token(claims)
+        };
+        assert!(client.validate_id_token(&claims(json!({})), Some("n-1"), now).is_ok());
+        assert!(client.validate_id_token(&claims(json!({})), Some("n-2"), now).is_err());
+        assert!(client.validate_id_token(&claims(json!({ "iss": "https://evil.example" })), None, now).is_err());
+        assert!(client.validate_id_token(&claims(json!({ "aud": ["other-app"] })), None, now).is_err());
+        assert!(client.validate_id_token(&claims(json!({ "exp": now.timestamp() - 120 })), None, now).is_err());
+
+        // Mapped names, platform names, and a refusal when nothing matches
+        let config = config();
+        assert_eq!(config.resolve_role(&json!({ "realm_access": { "roles": ["ward-nurse"] } })).unwrap(), Role::Nurse);
+        assert_eq!(config.resolve_role(&json!({ "realm_access": { "roles": ["attending", "Admin"] } })).unwrap(), Role::Admin);
+        assert!(config.resolve_role(&json!({ "realm_access": { "roles": ["offline_access"] } })).is_err());
+        assert_eq!(config.with_default_role(Role::Provider).resolve_role(&json!({})).unwrap(), Role::Provider);
+
+        let azure = OidcProviderConfig::azure_ad("tenant-1", "app-1", "https://app.mydr24.com/sso/callback");
+        assert_eq!(azure.issuer, "https://login.microsoftonline.com/tenant-1/v2.0");
+        assert!(OidcClient::new(azure, discovery(), Arc::new(MockApiClient::new())).is_err());
+    }
+}
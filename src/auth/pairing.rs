@@ -0,0 +1,348 @@
+//! QR-code sign-in and device pairing: a desktop shows a short-lived pairing token as a QR code, and a signed-in phone scans and approves it
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use super::device::{DeviceRegistration, RegisteredDevice};
+use super::oidc::parse_query;
+use super::request_signing::{verify_signed_request, ReplayGuard};
+use super::session::DeviceInfo;
+use crate::api_backend::{percent_encode, HttpRequest};
+use crate::errors::{SharedError, SharedResult};
+use crate::secrets::SecretString;
+
+/// How long a QR code can be scanned, and how long the desktop then has to
+/// collect an approved sign-in
+pub const PAIRING_TTL_SECONDS: i64 = 120;
+
+/// Scheme and path of pairing QR codes, so the mobile app can recognise them
+pub const PAIRING_URI_PREFIX: &str = "mydr24://pair";
+
+const PAIRING_SECRET_BYTES: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PairingStatus {
+    /// Shown on the desktop, waiting for a phone
+    Pending,
+    /// Approved on the phone; the desktop can collect its session
+    Approved,
+    Rejected,
+    /// The desktop collected its session; the token can't be used again
+    Completed,
+    Expired,
+}
+
+/// Sent by the desktop to start pairing; it registers its own device key so
+/// the resulting session is tied to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairingStartRequest {
+    pub device: DeviceRegistration,
+}
+
+/// The pairing token the desktop renders as a QR code. Whoever holds the
+/// secret can approve or collect the pairing, so it is never logged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairingToken {
+    pub pairing_id: String,
+    pub secret: SecretString,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PairingToken {
+    /// Text to encode in the QR code
+    pub fn to_qr_payload(&self) -> SecretString {
+        SecretString::new(format!(
+            "{}?id={}&secret={}&exp={}",
+            PAIRING_URI_PREFIX,
+            percent_encode(&self.pairing_id),
+            percent_encode(self.secret.expose()),
+            self.expires_at.timestamp()
+        ))
+    }
+
+    /// Read a scanned QR code; anything that isn't a pairing code is refused
+    pub fn from_qr_payload(payload: &str) -> SharedResult<Self> {
+        let invalid = || SharedError::ValidationError("Not a MyDR24 pairing code".to_string());
+        let query = payload.trim().strip_prefix(PAIRING_URI_PREFIX).and_then(|rest| rest.strip_prefix('?')).ok_or_else(invalid)?;
+        let mut params = parse_query(query);
+        let pairing_id = params.remove("id").filter(|id| !id.is_empty()).ok_or_else(invalid)?;
+        let secret = params.remove("secret").filter(|s| !s.is_empty()).map(SecretString::new).ok_or_else(invalid)?;
+        let expires_at = params
+            .get("exp")
+            .and_then(|exp| exp.parse::<i64>().ok())
+            .and_then(|exp| Utc.timestamp_opt(exp, 0).single())
+            .ok_or_else(invalid)?;
+        Ok(Self { pairing_id, secret, expires_at })
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Six digits shown on both screens, so the user can check the phone
+    /// scanned the code on the desktop in front of them
+    pub fn confirmation_code(&self) -> String {
+        confirmation_code(&self.pairing_id, self.secret.expose())
+    }
+
+    pub fn proof(&self) -> PairingProof {
+        PairingProof { secret: self.secret.clone() }
+    }
+}
+
+fn secret_hash(secret: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn confirmation_code(pairing_id: &str, secret: &str) -> String {
+    let digest = Sha256::digest(format!("{}|{}", pairing_id, secret).as_bytes());
+    let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+    format!("{:06}", value % 1_000_000)
+}
+
+/// Request body proving the caller saw the QR code
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairingProof {
+    pub secret: SecretString,
+}
+
+/// What the phone shows before the user approves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairingDetails {
+    pub pairing_id: String,
+    pub device: DeviceInfo,
+    pub status: PairingStatus,
+    pub requested_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Server-side record of one pairing. Only a hash of the secret is kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairingSession {
+    pub pairing_id: String,
+    pub secret_hash: String,
+    pub device: DeviceRegistration,
+    pub status: PairingStatus,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+impl PairingSession {
+    /// A new pending pairing for the desktop `device`, and the token to show it
+    pub fn start(device: DeviceRegistration, now: DateTime<Utc>) -> (Self, PairingToken) {
+        let mut bytes = Zeroizing::new([0u8; PAIRING_SECRET_BYTES]);
+        OsRng.fill_bytes(bytes.as_mut_slice());
+        let token = PairingToken {
+            pairing_id: Uuid::new_v4().to_string(),
+            secret: SecretString::new(URL_SAFE_NO_PAD.encode(bytes.as_slice())),
+            expires_at: now + Duration::seconds(PAIRING_TTL_SECONDS),
+        };
+        let session = Self {
+            pairing_id: token.pairing_id.clone(),
+            secret_hash: secret_hash(token.secret.expose()),
+            device,
+            status: PairingStatus::Pending,
+            created_at: now,
+            expires_at: token.expires_at,
+            decided_by: None,
+            decided_at: None,
+        };
+        (session, token)
+    }
+
+    /// Pending and approved pairings expire; finished ones keep their status
+    pub fn status_at(&self, now: DateTime<Utc>) -> PairingStatus {
+        match self.status {
+            PairingStatus::Pending | PairingStatus::Approved if now >= self.expires_at => PairingStatus::Expired,
+            status => status,
+        }
+    }
+
+    /// Check the caller holds the QR secret and the pairing hasn't expired
+    pub fn verify(&self, proof: &PairingProof, now: DateTime<Utc>) -> SharedResult<()> {
+        if secret_hash(proof.secret.expose()) != self.secret_hash {
+            return Err(SharedError::AuthenticationError("Invalid pairing code".to_string()));
+        }
+        if self.status_at(now) == PairingStatus::Expired {
+            return Err(SharedError::AuthenticationError("Pairing code has expired; show a new one".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn details(&self, now: DateTime<Utc>) -> PairingDetails {
+        PairingDetails {
+            pairing_id: self.pairing_id.clone(),
+            device: self.device.device.clone(),
+            status: self.status_at(now),
+            requested_at: self.created_at,
+            expires_at: self.expires_at,
+        }
+    }
+
+    fn decide(&mut self, proof: &PairingProof, user_id: &str, status: PairingStatus, now: DateTime<Utc>) -> SharedResult<()> {
+        self.verify(proof, now)?;
+        if self.status != PairingStatus::Pending {
+            return Err(SharedError::ValidationError("Pairing has already been decided".to_string()));
+        }
+        self.status = status;
+        self.decided_by = Some(user_id.to_string());
+        self.decided_at = Some(now);
+        Ok(())
+    }
+
+    /// Approve on the phone signed in as `user_id`. The desktop then has a
+    /// fresh window to collect its session.
+    pub fn approve(&mut self, proof: &PairingProof, user_id: &str, now: DateTime<Utc>) -> SharedResult<()> {
+        self.decide(proof, user_id, PairingStatus::Approved, now)?;
+        self.expires_at = now + Duration::seconds(PAIRING_TTL_SECONDS);
+        Ok(())
+    }
+
+    pub fn reject(&mut self, proof: &PairingProof, user_id: &str, now: DateTime<Utc>) -> SharedResult<()> {
+        self.decide(proof, user_id, PairingStatus::Rejected, now)
+    }
+
+    /// The desktop's key as registered when the pairing started
+    fn desktop_device(&self) -> RegisteredDevice {
+        RegisteredDevice {
+            user_id: String::new(),
+            device: self.device.device.clone(),
+            algorithm: self.device.algorithm.clone(),
+            public_key: self.device.public_key.clone(),
+            registered_at: self.created_at,
+            last_seen_at: None,
+            revoked_at: None,
+        }
+    }
+
+    /// Hand an approved pairing to the desktop, once; returns the user to
+    /// sign it in as. Pending pairings return `None` so the desktop keeps polling.
+    /// The QR secret is on screen for anyone to photograph, so `poll` must also
+    /// be signed with the device key the desktop registered in `start`.
+    pub fn complete(
+        &mut self,
+        proof: &PairingProof,
+        poll: &HttpRequest,
+        replay: &mut ReplayGuard,
+        now: DateTime<Utc>,
+    ) -> SharedResult<Option<String>> {
+        self.verify(proof, now)?;
+        if !poll.path().split('/').any(|segment| segment == self.pairing_id) {
+            return Err(SharedError::AuthenticationError("Signed poll is for another pairing".to_string()));
+        }
+        verify_signed_request(poll, &self.desktop_device(), replay, now)?;
+        match self.status {
+            PairingStatus::Pending => Ok(None),
+            PairingStatus::Approved => {
+                self.status = PairingStatus::Completed;
+                Ok(self.decided_by.clone())
+            }
+            PairingStatus::Rejected => Err(SharedError::AuthorizationError("Pairing was rejected on the phone".to_string())),
+            PairingStatus::Completed | PairingStatus::Expired => {
+                Err(SharedError::AuthenticationError("Pairing code has already been used".to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_backend::ApiRequestBuilder;
+    use crate::auth::device::DeviceKeyPair;
+    use crate::auth::request_signing::sign_request;
+
+    #[test]
+    fn test_qr_round_trip() {
+        let now = Utc::now();
+        let (session, token) = PairingSession::start(DeviceKeyPair::generate().registration("Front desk PC"), now);
+        let payload = token.to_qr_payload();
+        assert!(payload.expose().starts_with("mydr24://pair?id="));
+        assert!(!format!("{:?}", token).contains(token.secret.expose()));
+
+        let scanned = PairingToken::from_qr_payload(payload.expose()).unwrap();
+        assert_eq!(scanned.pairing_id, session.pairing_id);
+        assert_eq!(scanned.expires_at.timestamp(), token.expires_at.timestamp());
+        assert_eq!(scanned.confirmation_code(), token.confirmation_code());
+        assert_eq!(token.confirmation_code().len(), 6);
+        assert!(scanned.is_expired(now + Duration::seconds(PAIRING_TTL_SECONDS)));
+
+        assert!(PairingToken::from_qr_payload("https://evil.example/pair?id=x&secret=y&exp=1").is_err());
+        assert!(PairingToken::from_qr_payload("mydr24://pair?id=x&exp=1").is_err());
+    }
+
+    fn signed_poll(pairing_id: &str, token: &PairingToken, key: &DeviceKeyPair, now: DateTime<Utc>) -> HttpRequest {
+        let url = format!("https://api.example.com/api/v1/auth/pairing/{}/poll", pairing_id);
+        let mut poll = ApiRequestBuilder::new("POST", &url).json(&token.proof()).unwrap();
+        sign_request(&mut poll, key, now);
+        poll
+    }
+
+    #[test]
+    fn test_approve_and_complete_once() {
+        let now = Utc::now();
+        let desktop = DeviceKeyPair::generate();
+        let (mut session, token) = PairingSession::start(desktop.registration("Ward laptop"), now);
+        let mut replay = ReplayGuard::default();
+        let wrong = PairingProof { secret: SecretString::new("guess") };
+        assert!(session.verify(&wrong, now).is_err());
+        assert!(session.approve(&wrong, "dr-1", now).is_err());
+
+        // The desktop polls while the phone decides
+        let poll = signed_poll(&session.pairing_id, &token, &desktop, now);
+        assert_eq!(session.complete(&token.proof(), &poll, &mut replay, now).unwrap(), None);
+        let later = now + Duration::seconds(100);
+        session.approve(&token.proof(), "dr-1", later).unwrap();
+        assert!(session.reject(&token.proof(), "dr-1", later).is_err());
+        assert_eq!(session.details(later + Duration::seconds(60)).status, PairingStatus::Approved);
+        let collect_at = later + Duration::seconds(60);
+        let poll = signed_poll(&session.pairing_id, &token, &desktop, collect_at);
+        assert_eq!(session.complete(&token.proof(), &poll, &mut replay, collect_at).unwrap().as_deref(), Some("dr-1"));
+        let again = signed_poll(&session.pairing_id, &token, &desktop, collect_at + Duration::seconds(1));
+        assert!(session.complete(&token.proof(), &again, &mut replay, collect_at + Duration::seconds(1)).is_err());
+
+        // Unclaimed codes expire; rejected ones never complete
+        let (mut stale, stale_token) = PairingSession::start(desktop.registration("Kiosk"), now);
+        assert_eq!(stale.status_at(now + Duration::minutes(3)), PairingStatus::Expired);
+        assert!(stale.approve(&stale_token.proof(), "dr-1", now + Duration::minutes(3)).is_err());
+        stale.reject(&stale_token.proof(), "dr-1", now).unwrap();
+        let poll = signed_poll(&stale.pairing_id, &stale_token, &desktop, now);
+        assert!(stale.complete(&stale_token.proof(), &poll, &mut replay, now).is_err());
+    }
+
+    #[test]
+    fn test_qr_secret_alone_cannot_collect_session() {
+        let now = Utc::now();
+        let desktop = DeviceKeyPair::generate();
+        let (mut session, token) = PairingSession::start(desktop.registration("Front desk PC"), now);
+        session.approve(&token.proof(), "dr-1", now).unwrap();
+        let mut replay = ReplayGuard::default();
+
+        // Someone who photographed the QR code polls with their own key, or unsigned
+        let onlooker = DeviceKeyPair::generate();
+        let stolen = PairingToken::from_qr_payload(token.to_qr_payload().expose()).unwrap();
+        let poll = signed_poll(&session.pairing_id, &stolen, &onlooker, now);
+        assert!(session.complete(&stolen.proof(), &poll, &mut replay, now).is_err());
+        let url = format!("https://api.example.com/api/v1/auth/pairing/{}/poll", session.pairing_id);
+        let unsigned = ApiRequestBuilder::new("POST", &url).json(&stolen.proof()).unwrap();
+        assert!(session.complete(&stolen.proof(), &unsigned, &mut replay, now).is_err());
+
+        // A desktop signature for another pairing doesn't carry over
+        let elsewhere = signed_poll("other-pairing", &token, &desktop, now);
+        assert!(session.complete(&token.proof(), &elsewhere, &mut replay, now).is_err());
+
+        // The real desktop can still collect it
+        let poll = signed_poll(&session.pairing_id, &token, &desktop, now);
+        assert_eq!(session.complete(&token.proof(), &poll, &mut replay, now).unwrap().as_deref(), Some("dr-1"));
+    }
+}
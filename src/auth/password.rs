@@ -0,0 +1,418 @@
+//! Password policy: configurable rules, a breached-password hook and a strength estimate for sign-up forms
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::errors::{SharedError, SharedResult};
+
+/// Symbols counted towards the "special character" class
+pub const PASSWORD_SYMBOLS: &str = "!@#$%^&*()_+-=[]{}|;:,.<>?/~`'\"\\ ";
+
+/// Rules a new password must satisfy. Loaded from configuration, so every
+/// field has a default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Reject passwords containing the user's name, email or phone number
+    pub reject_user_info: bool,
+    /// Lowest [`PasswordStrength::score`] accepted, 0-4
+    pub min_score: u8,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 12,
+            max_length: 128,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_symbol: true,
+            reject_user_info: true,
+            min_score: 2,
+        }
+    }
+}
+
+/// A rule a password broke
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "rule")]
+pub enum PasswordViolation {
+    TooShort { min: usize },
+    TooLong { max: usize },
+    MissingUppercase,
+    MissingLowercase,
+    MissingDigit,
+    MissingSymbol,
+    ContainsUserInfo,
+    Breached,
+    TooWeak { score: u8, min: u8 },
+}
+
+impl fmt::Display for PasswordViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordViolation::TooShort { min } => write!(f, "Password must be at least {} characters long", min),
+            PasswordViolation::TooLong { max } => write!(f, "Password must be at most {} characters long", max),
+            PasswordViolation::MissingUppercase => f.write_str("Password must contain an uppercase letter"),
+            PasswordViolation::MissingLowercase => f.write_str("Password must contain a lowercase letter"),
+            PasswordViolation::MissingDigit => f.write_str("Password must contain a digit"),
+            PasswordViolation::MissingSymbol => f.write_str("Password must contain a special character"),
+            PasswordViolation::ContainsUserInfo => f.write_str("Password must not contain your name, email or phone number"),
+            PasswordViolation::Breached => f.write_str("This password has appeared in data breaches; choose another"),
+            PasswordViolation::TooWeak { .. } => f.write_str("Password is too easy to guess"),
+        }
+    }
+}
+
+/// Hook for checking passwords against known breaches, e.g. a k-anonymity
+/// range query or a server-side bloom filter
+pub trait BreachedPasswordCheck: Send + Sync {
+    fn is_breached(&self, password: &str) -> bool;
+}
+
+/// Offline heuristic: the password is one of the most common passwords once
+/// case, leetspeak and leading or trailing digits and symbols are removed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CommonPasswordCheck;
+
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "qwerty", "qwertyuiop", "asdfghjkl", "letmein", "welcome", "admin", "administrator", "iloveyou",
+    "monkey", "dragon", "football", "baseball", "sunshine", "princess", "master", "shadow", "trustno", "abc",
+    "hello", "freedom", "whatever", "login", "starwars", "superman", "batman", "changeme", "secret", "default",
+    "summer", "winter", "spring", "autumn", "hospital", "doctor", "nurse", "health", "healthcare", "patient",
+    "medical", "mydr",
+];
+
+/// Lowercase, strip leading and trailing digits and symbols, undo common substitutions
+fn normalize_for_breach_check(password: &str) -> String {
+    password
+        .trim_matches(|c: char| !c.is_alphabetic())
+        .chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c => c,
+        })
+        .collect()
+}
+
+impl BreachedPasswordCheck for CommonPasswordCheck {
+    fn is_breached(&self, password: &str) -> bool {
+        let normalized = normalize_for_breach_check(password);
+        !normalized.is_empty() && COMMON_PASSWORDS.iter().any(|common| normalize_for_breach_check(common) == normalized)
+    }
+}
+
+/// How guessable a password is, for the strength meter on sign-up forms
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PasswordStrength {
+    /// 0 (very weak) to 4 (very strong)
+    pub score: u8,
+    pub entropy_bits: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    pub suggestions: Vec<String>,
+}
+
+impl PasswordStrength {
+    pub fn label(&self) -> &'static str {
+        match self.score {
+            0 => "Very weak",
+            1 => "Weak",
+            2 => "Fair",
+            3 => "Strong",
+            _ => "Very strong",
+        }
+    }
+}
+
+/// Checks passwords against a policy and an optional breach hook
+#[derive(Clone)]
+pub struct PasswordValidator {
+    pub policy: PasswordPolicy,
+    breach_check: Option<Arc<dyn BreachedPasswordCheck>>,
+}
+
+impl fmt::Debug for PasswordValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasswordValidator")
+            .field("policy", &self.policy)
+            .field("breach_check", &self.breach_check.is_some())
+            .finish()
+    }
+}
+
+impl Default for PasswordValidator {
+    fn default() -> Self {
+        Self::new(PasswordPolicy::default())
+    }
+}
+
+/// Lowercased pieces of the user's details worth looking for in a password:
+/// each name part, the email's local part and the phone's last digits
+fn user_info_tokens(user_inputs: &[&str]) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for input in user_inputs {
+        let input = input.trim().to_lowercase();
+        let local = input.split('@').next().unwrap_or_default();
+        let digits: String = input.chars().filter(char::is_ascii_digit).collect();
+        if digits.len() >= 7 {
+            tokens.push(digits[digits.len() - 4..].to_string());
+        }
+        tokens.extend(
+            local
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|part| part.len() >= 3 && !part.chars().all(|c| c.is_ascii_digit()))
+                .map(str::to_string),
+        );
+    }
+    tokens
+}
+
+/// Characters that belong to runs of three or more: repeats (`aaa`), steps
+/// (`abc`, `321`) or keyboard rows (`qwe`)
+fn patterned_chars(password: &str) -> usize {
+    const ROWS: &[&str] = &["qwertyuiop", "asdfghjkl", "zxcvbnm", "1234567890"];
+    let chars: Vec<char> = password.to_lowercase().chars().collect();
+    let linked = |a: char, b: char| {
+        let step = b as i32 - a as i32;
+        step.abs() <= 1
+            || ROWS.iter().any(|row| {
+                let (Some(i), Some(j)) = (row.find(a), row.find(b)) else {
+                    return false;
+                };
+                i.abs_diff(j) == 1
+            })
+    };
+
+    let mut counted = 0;
+    let mut run = 1;
+    for i in 1..=chars.len() {
+        if i < chars.len() && linked(chars[i - 1], chars[i]) {
+            run += 1;
+        } else {
+            if run >= 3 {
+                counted += run;
+            }
+            run = 1;
+        }
+    }
+    counted
+}
+
+fn contains_year(password: &str) -> bool {
+    let digits: Vec<char> = password.chars().collect();
+    digits.windows(4).any(|w| {
+        let year: String = w.iter().collect();
+        year.parse::<u32>().is_ok_and(|y| (1900..=2099).contains(&y))
+    })
+}
+
+impl PasswordValidator {
+    /// Uses [`CommonPasswordCheck`] until another hook is set
+    pub fn new(policy: PasswordPolicy) -> Self {
+        Self { policy, breach_check: Some(Arc::new(CommonPasswordCheck)) }
+    }
+
+    pub fn with_breach_check(mut self, check: Arc<dyn BreachedPasswordCheck>) -> Self {
+        self.breach_check = Some(check);
+        self
+    }
+
+    pub fn without_breach_check(mut self) -> Self {
+        self.breach_check = None;
+        self
+    }
+
+    fn is_breached(&self, password: &str) -> bool {
+        self.breach_check.as_ref().is_some_and(|check| check.is_breached(password))
+    }
+
+    /// Every rule `password` breaks. `user_inputs` are the user's name, email,
+    /// phone and similar details.
+    pub fn check(&self, password: &str, user_inputs: &[&str]) -> Vec<PasswordViolation> {
+        let policy = &self.policy;
+        let length = password.chars().count();
+        let mut violations = Vec::new();
+        if length < policy.min_length {
+            violations.push(PasswordViolation::TooShort { min: policy.min_length });
+        }
+        if length > policy.max_length {
+            violations.push(PasswordViolation::TooLong { max: policy.max_length });
+        }
+        let classes = [
+            (policy.require_uppercase, password.chars().any(char::is_uppercase), PasswordViolation::MissingUppercase),
+            (policy.require_lowercase, password.chars().any(char::is_lowercase), PasswordViolation::MissingLowercase),
+            (policy.require_digit, password.chars().any(|c| c.is_ascii_digit()), PasswordViolation::MissingDigit),
+            (policy.require_symbol, password.chars().any(|c| PASSWORD_SYMBOLS.contains(c)), PasswordViolation::MissingSymbol),
+        ];
+        for (required, present, violation) in classes {
+            if required && !present {
+                violations.push(violation);
+            }
+        }
+        let lowered = password.to_lowercase();
+        if policy.reject_user_info && user_info_tokens(user_inputs).iter().any(|token| lowered.contains(token.as_str())) {
+            violations.push(PasswordViolation::ContainsUserInfo);
+        }
+        if self.is_breached(password) {
+            violations.push(PasswordViolation::Breached);
+        }
+        let score = self.estimate_strength(password, user_inputs).score;
+        if score < policy.min_score {
+            violations.push(PasswordViolation::TooWeak { score, min: policy.min_score });
+        }
+        violations
+    }
+
+    /// `Ok(())` if the password satisfies the policy, otherwise a validation
+    /// error listing every broken rule
+    pub fn validate(&self, password: &str, user_inputs: &[&str]) -> SharedResult<()> {
+        let violations = self.check(password, user_inputs);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        Err(SharedError::ValidationError(messages.join("; ")))
+    }
+
+    /// Rough guessability estimate: character-set entropy, discounted for
+    /// patterns, personal details and common passwords
+    pub fn estimate_strength(&self, password: &str, user_inputs: &[&str]) -> PasswordStrength {
+        let length = password.chars().count();
+        let has_lower = password.chars().any(char::is_lowercase);
+        let has_upper = password.chars().any(char::is_uppercase);
+        let has_digit = password.chars().any(|c| c.is_ascii_digit());
+        let has_symbol = password.chars().any(|c| !c.is_alphanumeric());
+        let charset: u32 = [(has_lower, 26), (has_upper, 26), (has_digit, 10), (has_symbol, 33)]
+            .iter()
+            .filter(|(present, _)| *present)
+            .map(|(_, size)| size)
+            .sum();
+
+        // Patterned and personal characters add almost nothing to the search space
+        let lowered = password.to_lowercase();
+        let personal: usize = user_info_tokens(user_inputs)
+            .iter()
+            .filter(|token| lowered.contains(token.as_str()))
+            .map(|token| token.chars().count())
+            .sum();
+        let patterned = patterned_chars(password);
+        let effective = length.saturating_sub(personal + patterned) as f64 + 0.25 * (personal + patterned).min(length) as f64;
+        let entropy_bits = if charset == 0 { 0.0 } else { effective * f64::from(charset).log2() };
+
+        let mut warning = None;
+        let mut suggestions = Vec::new();
+        let breached = self.is_breached(password);
+        if breached {
+            warning = Some("This is a very common password".to_string());
+        } else if personal > 0 {
+            warning = Some("Passwords containing your name or contact details are easy to guess".to_string());
+        }
+        if length < self.policy.min_length {
+            suggestions.push(format!("Use at least {} characters; several unrelated words work well", self.policy.min_length));
+        }
+        if [has_lower, has_upper, has_digit, has_symbol].iter().filter(|present| **present).count() < 3 {
+            suggestions.push("Mix uppercase and lowercase letters, digits and symbols".to_string());
+        }
+        if patterned > 0 {
+            suggestions.push("Avoid sequences and repeats like abc, 123, qwerty or aaa".to_string());
+        }
+        if contains_year(password) {
+            suggestions.push("Avoid years and dates that are associated with you".to_string());
+        }
+        if personal > 0 {
+            suggestions.push("Don't use your name, email or phone number".to_string());
+        }
+
+        let score = if breached {
+            0
+        } else {
+            match entropy_bits {
+                bits if bits < 28.0 => 0,
+                bits if bits < 40.0 => 1,
+                bits if bits < 56.0 => 2,
+                bits if bits < 72.0 => 3,
+                _ => 4,
+            }
+        };
+        PasswordStrength { score, entropy_bits, warning, suggestions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DenyList(Vec<&'static str>);
+
+    impl BreachedPasswordCheck for DenyList {
+        fn is_breached(&self, password: &str) -> bool {
+            self.0.contains(&password)
+        }
+    }
+
+    #[test]
+    fn test_policy_violations() {
+        let validator = PasswordValidator::default();
+        assert!(validator.validate("Kettle-Harbor-91", &[]).is_ok());
+        assert_eq!(
+            validator.check("short", &[]),
+            vec![
+                PasswordViolation::TooShort { min: 12 },
+                PasswordViolation::MissingUppercase,
+                PasswordViolation::MissingDigit,
+                PasswordViolation::MissingSymbol,
+                PasswordViolation::TooWeak { score: 0, min: 2 },
+            ]
+        );
+
+        // Common passwords survive decoration but not the breach heuristic
+        assert!(validator.check("P@ssw0rd2024!", &[]).contains(&PasswordViolation::Breached));
+        assert!(validator.check("Welcome123!!", &[]).contains(&PasswordViolation::Breached));
+        assert!(validator
+            .check("Priya.Sharma#7", &["Priya Sharma", "priya.s@example.com"])
+            .contains(&PasswordViolation::ContainsUserInfo));
+
+        let custom = PasswordValidator::new(PasswordPolicy { require_symbol: false, min_score: 0, ..PasswordPolicy::default() })
+            .with_breach_check(Arc::new(DenyList(vec!["CorrectHorse9Battery"])));
+        assert_eq!(custom.check("CorrectHorse9Battery", &[]), vec![PasswordViolation::Breached]);
+        assert!(custom.check("P@ssw0rd2024!", &[]).is_empty());
+
+        let policy: PasswordPolicy = serde_json::from_str(r#"{"min_length": 16}"#).unwrap();
+        assert_eq!(policy, PasswordPolicy { min_length: 16, ..PasswordPolicy::default() });
+    }
+
+    #[test]
+    fn test_strength_estimate() {
+        let validator = PasswordValidator::default();
+        let weak = validator.estimate_strength("aaaa1234", &[]);
+        assert_eq!(weak.score, 0);
+        assert_eq!(weak.label(), "Very weak");
+        assert!(weak.suggestions.iter().any(|s| s.contains("sequences")));
+
+        let common = validator.estimate_strength("Qwerty123!", &[]);
+        assert_eq!(common.score, 0);
+        assert!(common.warning.is_some());
+
+        let personal = validator.estimate_strength("ravikumar1990", &["Ravi Kumar"]);
+        assert!(personal.score <= 1);
+        assert!(personal.suggestions.iter().any(|s| s.contains("years")));
+
+        let strong = validator.estimate_strength("vivid-Otter-lamp-37-Quartz", &[]);
+        assert_eq!(strong.score, 4);
+        assert!(strong.warning.is_none() && strong.suggestions.is_empty());
+    }
+}
@@ -0,0 +1,881 @@
+//! Post-Quantum Cryptography: Dilithium signatures, hybrid key exchange and key rotation
+
+use serde::{Deserialize, Serialize};
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+// Post-Quantum Cryptography imports (optional)
+#[cfg(feature = "post-quantum")]
+use pqcrypto_dilithium::dilithium2::{
+    keypair as dilithium_keypair, 
+    sign, 
+    open, 
+    detached_sign,
+    verify_detached_signature,
+    PublicKey as DilithiumPublicKey, 
+    SecretKey as DilithiumSecretKey,
+    SignedMessage as DilithiumSignedMessage,
+    DetachedSignature as DilithiumDetachedSignature,
+};
+#[cfg(feature = "post-quantum")]
+use pqcrypto_traits::sign::{
+    PublicKey as PQPublicKey, 
+    SecretKey as PQSecretKey,
+    SignedMessage as PQSignedMessage,
+    DetachedSignature as PQDetachedSignature,
+};
+#[cfg(feature = "post-quantum")]
+use ml_kem::{
+    kem::{Decapsulate, Encapsulate},
+    Ciphertext as KemCiphertext,
+    Encoded, EncodedSizeUser, KemCore, MlKem768,
+};
+#[cfg(feature = "post-quantum")]
+use x25519_dalek::{EphemeralSecret, PublicKey as X25519PublicKey, StaticSecret};
+#[cfg(feature = "post-quantum")]
+use hkdf::Hkdf;
+use rand::rngs::OsRng;
+use sha2::Sha256;
+use std::collections::HashMap;
+
+use super::chat_keys::ChatKey;
+use super::jwt::{append_jwt_signature, jwt_signing_input, AuthClaims, JwtAlgorithm, JwtHeader, SignatureVerifier};
+use crate::encryption::DataKey;
+use crate::ids::BookingId;
+use crate::secrets::SecretString;
+use crate::SharedError;
+
+/// Post-Quantum authentication structure
+#[derive(Debug, Clone)]
+pub struct PQAuthentication {
+    pub algorithm: String,
+    pub version: String,
+}
+
+/// Post-Quantum key pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PQKeyPair {
+    pub public_key: String,  // Base64 encoded
+    pub private_key: String, // Base64 encoded
+    pub algorithm: String,
+    pub created_at: DateTime<Utc>,
+    pub key_id: Uuid,
+}
+
+/// Authentication challenge structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthChallenge {
+    pub challenge_id: Uuid,
+    pub challenge_text: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub email: String,
+}
+
+/// Signature verification result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureVerification {
+    pub is_valid: bool,
+    pub verified_at: DateTime<Utc>,
+    pub public_key_id: Option<Uuid>,
+    pub message: String,
+}
+
+/// JWT claims for Post-Quantum authentication
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PQClaims {
+    pub sub: String,        // Subject (user ID)
+    pub email: String,      // User email
+    pub role: String,       // User role
+    pub exp: i64,          // Expiration timestamp
+    pub iat: i64,          // Issued at timestamp
+    pub iss: String,       // Issuer
+    pub aud: String,       // Audience
+    pub key_id: Uuid,      // Public key ID used for verification
+    pub algorithm: String, // PQ algorithm used
+}
+
+impl PQAuthentication {
+    /// Create new Post-Quantum authentication instance
+    pub fn new() -> Self {
+        Self {
+            algorithm: "CRYSTALS-Dilithium5".to_string(),
+            version: "1.0".to_string(),
+        }
+    }
+
+    /// Generate a new Post-Quantum key pair
+    pub fn generate_keypair(&self) -> Result<PQKeyPair, SharedError> {
+        let (public_key, private_key) = dilithium_keypair();
+        
+        let public_key_b64 = general_purpose::STANDARD.encode(public_key.as_bytes());
+        let private_key_b64 = general_purpose::STANDARD.encode(private_key.as_bytes());
+        
+        Ok(PQKeyPair {
+            public_key: public_key_b64,
+            private_key: private_key_b64,
+            algorithm: self.algorithm.clone(),
+            created_at: Utc::now(),
+            key_id: Uuid::new_v4(),
+        })
+    }
+
+    /// Sign a message using Post-Quantum cryptography
+    pub fn sign_message(&self, message: &str, private_key_b64: &str) -> Result<String, SharedError> {
+        // Decode private key
+        let private_key_bytes = general_purpose::STANDARD
+            .decode(private_key_b64)
+            .map_err(|e| SharedError::CryptographicError(format!("Invalid private key: {}", e)))?;
+        
+        if private_key_bytes.len() != 4896 {
+            return Err(SharedError::CryptographicError(
+                format!("Invalid private key length: {} (expected 4896)", private_key_bytes.len())
+            ));
+        }
+
+        let mut sk_array = [0u8; 4896];
+        sk_array.copy_from_slice(&private_key_bytes);
+        let secret_key = DilithiumSecretKey::from_bytes(&sk_array)
+            .map_err(|e| SharedError::CryptographicError(format!("Failed to construct private key: {}", e)))?;
+
+        // Sign the message
+        let signed_message = sign(message.as_bytes(), &secret_key);
+        let signature_b64 = general_purpose::STANDARD.encode(PQSignedMessage::as_bytes(&signed_message));
+        
+        Ok(signature_b64)
+    }
+
+    /// Verify a signature using Post-Quantum cryptography
+    pub fn verify_signature(&self, message: &str, signature_b64: &str, public_key_b64: &str) -> Result<SignatureVerification, SharedError> {
+        // Decode signature and public key
+        let signature_bytes = general_purpose::STANDARD
+            .decode(signature_b64)
+            .map_err(|e| SharedError::CryptographicError(format!("Invalid signature: {}", e)))?;
+        
+        let public_key_bytes = general_purpose::STANDARD
+            .decode(public_key_b64)
+            .map_err(|e| SharedError::CryptographicError(format!("Invalid public key: {}", e)))?;
+        
+        if public_key_bytes.len() != 2592 {
+            return Ok(SignatureVerification {
+                is_valid: false,
+                verified_at: Utc::now(),
+                public_key_id: None,
+                message: "Invalid public key length".to_string(),
+            });
+        }
+
+        let mut pk_array = [0u8; 2592];
+        pk_array.copy_from_slice(&public_key_bytes);
+        let public_key = DilithiumPublicKey::from_bytes(&pk_array)
+            .map_err(|e| SharedError::CryptographicError(format!("Failed to construct public key: {}", e)))?;
+
+        // Reconstruct signed message
+        let signed_message = PQSignedMessage::from_bytes(&signature_bytes)
+            .map_err(|e| SharedError::CryptographicError(format!("Invalid signed message: {}", e)))?;
+
+        // Verify signature
+        match open(&signed_message, &public_key) {
+            Ok(verified_message) => {
+                let is_valid = verified_message == message.as_bytes();
+                Ok(SignatureVerification {
+                    is_valid,
+                    verified_at: Utc::now(),
+                    public_key_id: None, // Would be set if we had key tracking
+                    message: if is_valid { "Signature verified".to_string() } else { "Message mismatch".to_string() },
+                })
+            },
+            Err(_) => Ok(SignatureVerification {
+                is_valid: false,
+                verified_at: Utc::now(),
+                public_key_id: None,
+                message: "Signature verification failed".to_string(),
+            }),
+        }
+    }
+
+    /// Create authentication challenge
+    pub fn create_challenge(&self, email: &str) -> AuthChallenge {
+        let challenge_id = Uuid::new_v4();
+        let now = Utc::now();
+        let expires_at = now + chrono::Duration::minutes(5); // 5-minute expiry
+        
+        AuthChallenge {
+            challenge_id,
+            challenge_text: format!("MyDR24-Auth-Challenge-{}-{}", email, now.timestamp()),
+            created_at: now,
+            expires_at,
+            email: email.to_string(),
+        }
+    }
+
+    /// Validate authentication challenge
+    pub fn validate_challenge(&self, challenge: &AuthChallenge) -> bool {
+        Utc::now() < challenge.expires_at
+    }
+}
+
+impl Default for PQAuthentication {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hybrid key exchange: classical X25519 combined with ML-KEM-768 (Kyber768).
+/// The session secret stays safe as long as either primitive holds.
+pub const HYBRID_KEM_ALGORITHM: &str = "X25519+ML-KEM-768";
+
+/// Domain separation for the session key derivation; bump on any change to it
+const HYBRID_KDF_INFO: &[u8] = b"MyDR24 hybrid KEM v1";
+
+type KemEncapsulationKey = <MlKem768 as KemCore>::EncapsulationKey;
+type KemDecapsulationKey = <MlKem768 as KemCore>::DecapsulationKey;
+
+/// Public half of a hybrid key pair, published so peers can start a handshake
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HybridPublicKeyBundle {
+    pub key_id: Uuid,
+    pub algorithm: String,
+    pub x25519_public_key: String, // Base64 encoded
+    pub kem_public_key: String,    // Base64 encoded
+    pub created_at: DateTime<Utc>,
+}
+
+/// Hybrid key pair held by the responder (usually the server)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HybridKeyPair {
+    pub public: HybridPublicKeyBundle,
+    pub x25519_private_key: SecretString, // Base64 encoded
+    pub kem_private_key: SecretString,    // Base64 encoded
+}
+
+impl std::fmt::Debug for HybridKeyPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HybridKeyPair")
+            .field("public", &self.public)
+            .field("x25519_private_key", &"[REDACTED]")
+            .field("kem_private_key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Initiator's handshake message; safe to send in the clear
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HybridCiphertext {
+    /// Bundle the initiator encapsulated to
+    pub key_id: Uuid,
+    pub algorithm: String,
+    pub x25519_ephemeral_key: String, // Base64 encoded
+    pub kem_ciphertext: String,       // Base64 encoded
+}
+
+/// 32-byte secret both sides derive from a hybrid handshake, zeroed on drop
+#[derive(Clone, PartialEq, Eq)]
+pub struct SessionSecret([u8; 32]);
+
+impl SessionSecret {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Drop for SessionSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SessionSecret {}
+
+impl std::fmt::Debug for SessionSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SessionSecret([REDACTED])")
+    }
+}
+
+fn decode_key<const N: usize>(b64: &str, what: &str) -> Result<[u8; N], SharedError> {
+    let bytes = Zeroizing::new(general_purpose::STANDARD.decode(b64)?);
+    bytes.as_slice().try_into().map_err(|_| {
+        SharedError::CryptographicError(format!("Invalid {} length: {} (expected {})", what, bytes.len(), N))
+    })
+}
+
+fn decode_encoded<T: EncodedSizeUser>(b64: &str, what: &str) -> Result<T, SharedError> {
+    let bytes = Zeroizing::new(general_purpose::STANDARD.decode(b64)?);
+    let encoded = Encoded::<T>::try_from(bytes.as_slice())
+        .map_err(|_| SharedError::CryptographicError(format!("Invalid {} length: {}", what, bytes.len())))?;
+    Ok(T::from_bytes(&encoded))
+}
+
+/// HKDF-SHA256 over both shared secrets, bound to the handshake transcript so
+/// a secret can't be replayed into a different exchange
+fn combine_secrets(
+    x25519_secret: &[u8],
+    kem_secret: &[u8],
+    ciphertext: &HybridCiphertext,
+    bundle: &HybridPublicKeyBundle,
+) -> Result<SessionSecret, SharedError> {
+    let mut ikm = Zeroizing::new(Vec::with_capacity(x25519_secret.len() + kem_secret.len()));
+    ikm.extend_from_slice(x25519_secret);
+    ikm.extend_from_slice(kem_secret);
+
+    let mut info = HYBRID_KDF_INFO.to_vec();
+    for part in [
+        &ciphertext.x25519_ephemeral_key,
+        &ciphertext.kem_ciphertext,
+        &bundle.x25519_public_key,
+        &bundle.kem_public_key,
+    ] {
+        info.extend_from_slice(part.as_bytes());
+    }
+
+    let mut secret = SessionSecret([0u8; 32]);
+    Hkdf::<Sha256>::new(Some(bundle.key_id.as_bytes().as_slice()), &ikm)
+        .expand(&info, &mut secret.0)
+        .map_err(|e| SharedError::CryptographicError(format!("Session key derivation failed: {}", e)))?;
+    Ok(secret)
+}
+
+impl HybridPublicKeyBundle {
+    fn check_algorithm(algorithm: &str) -> Result<(), SharedError> {
+        if algorithm == HYBRID_KEM_ALGORITHM {
+            Ok(())
+        } else {
+            Err(SharedError::CryptographicError(format!(
+                "Unsupported key exchange algorithm: {}",
+                algorithm
+            )))
+        }
+    }
+}
+
+impl PQAuthentication {
+    /// Generate a hybrid key pair whose public bundle can be shared with peers
+    pub fn generate_hybrid_keypair(&self) -> Result<HybridKeyPair, SharedError> {
+        let x25519_secret = StaticSecret::random_from_rng(OsRng);
+        let x25519_public = X25519PublicKey::from(&x25519_secret);
+        let (kem_private, kem_public) = MlKem768::generate(&mut OsRng);
+
+        Ok(HybridKeyPair {
+            public: HybridPublicKeyBundle {
+                key_id: Uuid::new_v4(),
+                algorithm: HYBRID_KEM_ALGORITHM.to_string(),
+                x25519_public_key: general_purpose::STANDARD.encode(x25519_public.as_bytes()),
+                kem_public_key: general_purpose::STANDARD.encode(kem_public.as_bytes()),
+                created_at: Utc::now(),
+            },
+            x25519_private_key: SecretString::new(general_purpose::STANDARD.encode(x25519_secret.to_bytes())),
+            kem_private_key: SecretString::new(general_purpose::STANDARD.encode(kem_private.as_bytes())),
+        })
+    }
+
+    /// Initiator side: derive a session secret for `bundle`'s owner and the
+    /// message that lets them derive the same one
+    pub fn hybrid_encapsulate(
+        &self,
+        bundle: &HybridPublicKeyBundle,
+    ) -> Result<(HybridCiphertext, SessionSecret), SharedError> {
+        HybridPublicKeyBundle::check_algorithm(&bundle.algorithm)?;
+        let peer_x25519 = X25519PublicKey::from(decode_key::<32>(&bundle.x25519_public_key, "X25519 public key")?);
+        let peer_kem: KemEncapsulationKey = decode_encoded(&bundle.kem_public_key, "ML-KEM public key")?;
+
+        let ephemeral = EphemeralSecret::random_from_rng(OsRng);
+        let ephemeral_public = X25519PublicKey::from(&ephemeral);
+        let x25519_shared = ephemeral.diffie_hellman(&peer_x25519);
+        if !x25519_shared.was_contributory() {
+            return Err(SharedError::CryptographicError("Peer X25519 key is a low-order point".to_string()));
+        }
+        let (kem_ciphertext, kem_shared) = peer_kem
+            .encapsulate(&mut OsRng)
+            .map_err(|_| SharedError::CryptographicError("ML-KEM encapsulation failed".to_string()))?;
+
+        let ciphertext = HybridCiphertext {
+            key_id: bundle.key_id,
+            algorithm: HYBRID_KEM_ALGORITHM.to_string(),
+            x25519_ephemeral_key: general_purpose::STANDARD.encode(ephemeral_public.as_bytes()),
+            kem_ciphertext: general_purpose::STANDARD.encode(kem_ciphertext.as_slice()),
+        };
+        let secret = combine_secrets(x25519_shared.as_bytes(), kem_shared.as_slice(), &ciphertext, bundle)?;
+        Ok((ciphertext, secret))
+    }
+
+    /// Responder side: recover the initiator's session secret. A tampered
+    /// ciphertext yields a different secret rather than an error, so the
+    /// mismatch surfaces when the first encrypted message fails to open.
+    pub fn hybrid_decapsulate(
+        &self,
+        keypair: &HybridKeyPair,
+        ciphertext: &HybridCiphertext,
+    ) -> Result<SessionSecret, SharedError> {
+        HybridPublicKeyBundle::check_algorithm(&ciphertext.algorithm)?;
+        if ciphertext.key_id != keypair.public.key_id {
+            return Err(SharedError::CryptographicError(format!(
+                "Handshake is for key {}, not {}",
+                ciphertext.key_id, keypair.public.key_id
+            )));
+        }
+
+        let x25519_secret = StaticSecret::from(decode_key::<32>(keypair.x25519_private_key.expose(), "X25519 private key")?);
+        let kem_private: KemDecapsulationKey = decode_encoded(keypair.kem_private_key.expose(), "ML-KEM private key")?;
+        let ephemeral_public =
+            X25519PublicKey::from(decode_key::<32>(&ciphertext.x25519_ephemeral_key, "X25519 ephemeral key")?);
+        let kem_bytes = general_purpose::STANDARD.decode(&ciphertext.kem_ciphertext)?;
+        let kem_ciphertext = KemCiphertext::<MlKem768>::try_from(kem_bytes.as_slice()).map_err(|_| {
+            SharedError::CryptographicError(format!("Invalid ML-KEM ciphertext length: {}", kem_bytes.len()))
+        })?;
+
+        let x25519_shared = x25519_secret.diffie_hellman(&ephemeral_public);
+        if !x25519_shared.was_contributory() {
+            return Err(SharedError::CryptographicError("Ephemeral X25519 key is a low-order point".to_string()));
+        }
+        let kem_shared = kem_private
+            .decapsulate(&kem_ciphertext)
+            .map_err(|_| SharedError::CryptographicError("ML-KEM decapsulation failed".to_string()))?;
+
+        combine_secrets(x25519_shared.as_bytes(), kem_shared.as_slice(), ciphertext, &keypair.public)
+    }
+}
+
+/// Where a managed key is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStatus {
+    /// Used for new handshakes and encryptions
+    Active,
+    /// Replaced by a newer key; still opens old data until the grace window ends
+    Retired,
+    /// Compromised or withdrawn; never used again
+    Revoked,
+}
+
+/// How often keys rotate and how long retired keys stay usable for decryption
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KeyRotationPolicy {
+    pub rotation_interval_days: u32,
+    pub grace_period_days: u32,
+}
+
+impl Default for KeyRotationPolicy {
+    fn default() -> Self {
+        Self {
+            rotation_interval_days: 90,
+            grace_period_days: 30,
+        }
+    }
+}
+
+/// A hybrid key pair with its lifecycle metadata
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ManagedKey {
+    pub keypair: HybridKeyPair,
+    pub status: KeyStatus,
+    pub activated_at: DateTime<Utc>,
+    /// When the key is due for rotation
+    pub expires_at: DateTime<Utc>,
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+impl ManagedKey {
+    pub fn key_id(&self) -> Uuid {
+        self.keypair.public.key_id
+    }
+
+    /// Whether data encrypted to this key may still be opened at `now`
+    pub fn can_decrypt(&self, policy: &KeyRotationPolicy, now: DateTime<Utc>) -> bool {
+        match (self.status, self.retired_at) {
+            (KeyStatus::Active, _) => true,
+            (KeyStatus::Retired, Some(retired_at)) => {
+                now < retired_at + chrono::Duration::days(policy.grace_period_days.into())
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for ManagedKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManagedKey")
+            .field("key_id", &self.key_id())
+            .field("status", &self.status)
+            .field("activated_at", &self.activated_at)
+            .field("expires_at", &self.expires_at)
+            .field("retired_at", &self.retired_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Tracks key IDs and their lifecycle: one active key for new handshakes,
+/// retired keys kept for a grace window so older records stay readable
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyManager {
+    pub policy: KeyRotationPolicy,
+    keys: Vec<ManagedKey>,
+}
+
+impl std::fmt::Debug for KeyManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyManager")
+            .field("policy", &self.policy)
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+impl KeyManager {
+    /// Manager with a freshly generated active key
+    pub fn new(policy: KeyRotationPolicy, now: DateTime<Utc>) -> Result<Self, SharedError> {
+        let mut manager = Self { policy, keys: Vec::new() };
+        manager.rotate(now)?;
+        Ok(manager)
+    }
+
+    pub fn keys(&self) -> &[ManagedKey] {
+        &self.keys
+    }
+
+    pub fn key(&self, key_id: Uuid) -> Option<&ManagedKey> {
+        self.keys.iter().find(|k| k.key_id() == key_id)
+    }
+
+    pub fn active_key(&self) -> Option<&ManagedKey> {
+        self.keys.iter().rev().find(|k| k.status == KeyStatus::Active)
+    }
+
+    /// Bundle to publish to peers starting new handshakes
+    pub fn public_bundle(&self) -> Result<&HybridPublicKeyBundle, SharedError> {
+        self.active_key()
+            .map(|k| &k.keypair.public)
+            .ok_or_else(|| SharedError::CryptographicError("No active key".to_string()))
+    }
+
+    pub fn needs_rotation(&self, now: DateTime<Utc>) -> bool {
+        self.active_key().is_none_or(|k| now >= k.expires_at)
+    }
+
+    /// Generate a new active key and retire the current one; returns the new key ID
+    pub fn rotate(&mut self, now: DateTime<Utc>) -> Result<Uuid, SharedError> {
+        let keypair = PQAuthentication::new().generate_hybrid_keypair()?;
+        for key in self.keys.iter_mut().filter(|k| k.status == KeyStatus::Active) {
+            key.status = KeyStatus::Retired;
+            key.retired_at = Some(now);
+        }
+        let key_id = keypair.public.key_id;
+        self.keys.push(ManagedKey {
+            keypair,
+            status: KeyStatus::Active,
+            activated_at: now,
+            expires_at: now + chrono::Duration::days(self.policy.rotation_interval_days.into()),
+            retired_at: None,
+        });
+        Ok(key_id)
+    }
+
+    /// Scheduled rotation: rotate only when the active key has expired
+    pub fn rotate_if_due(&mut self, now: DateTime<Utc>) -> Result<Option<Uuid>, SharedError> {
+        if self.needs_rotation(now) {
+            self.rotate(now).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Withdraw a key immediately, e.g. after a compromise. Revoking the
+    /// active key leaves no active key until the next `rotate`.
+    pub fn revoke(&mut self, key_id: Uuid, now: DateTime<Utc>) -> Result<(), SharedError> {
+        let key = self
+            .keys
+            .iter_mut()
+            .find(|k| k.key_id() == key_id)
+            .ok_or_else(|| SharedError::NotFoundError(format!("Key {}", key_id)))?;
+        key.status = KeyStatus::Revoked;
+        key.retired_at.get_or_insert(now);
+        Ok(())
+    }
+
+    /// Key pair able to open data encrypted to `key_id` at `now`
+    pub fn decryption_key(&self, key_id: Uuid, now: DateTime<Utc>) -> Result<&HybridKeyPair, SharedError> {
+        let key = self
+            .key(key_id)
+            .ok_or_else(|| SharedError::CryptographicError(format!("Unknown key {}", key_id)))?;
+        if key.can_decrypt(&self.policy, now) {
+            Ok(&key.keypair)
+        } else {
+            Err(SharedError::CryptographicError(format!(
+                "Key {} is {:?} and no longer usable for decryption",
+                key_id, key.status
+            )))
+        }
+    }
+
+    /// Respond to a handshake made with any key still inside its grace window
+    pub fn decapsulate(&self, ciphertext: &HybridCiphertext, now: DateTime<Utc>) -> Result<SessionSecret, SharedError> {
+        let keypair = self.decryption_key(ciphertext.key_id, now)?;
+        PQAuthentication::new().hybrid_decapsulate(keypair, ciphertext)
+    }
+
+    /// Drop retired and revoked keys that can no longer decrypt anything;
+    /// returns how many were removed
+    pub fn purge_expired(&mut self, now: DateTime<Utc>) -> usize {
+        let before = self.keys.len();
+        let policy = self.policy;
+        self.keys
+            .retain(|k| k.status == KeyStatus::Active || k.can_decrypt(&policy, now));
+        before - self.keys.len()
+    }
+}
+
+impl PQAuthentication {
+    /// Issue a Dilithium-signed access token; `key_id` tells verifiers which
+    /// public key to check it against
+    pub fn sign_jwt(&self, claims: &AuthClaims, key_id: &str, private_key_b64: &str) -> Result<String, SharedError> {
+        let private_key_bytes = general_purpose::STANDARD
+            .decode(private_key_b64)
+            .map_err(|e| SharedError::CryptographicError(format!("Invalid private key: {}", e)))?;
+        let secret_key = DilithiumSecretKey::from_bytes(&private_key_bytes)
+            .map_err(|e| SharedError::CryptographicError(format!("Failed to construct private key: {}", e)))?;
+
+        let header = JwtHeader::new(JwtAlgorithm::Dilithium2, Some(key_id.to_string()));
+        let signing_input = jwt_signing_input(&header, claims)?;
+        let signature = detached_sign(signing_input.as_bytes(), &secret_key);
+        Ok(append_jwt_signature(&signing_input, PQDetachedSignature::as_bytes(&signature)))
+    }
+}
+
+/// Checks Dilithium-signed tokens against the issuer's published keys, by `kid`
+#[derive(Debug, Clone, Default)]
+pub struct DilithiumJwtVerifier {
+    public_keys: HashMap<String, Vec<u8>>,
+}
+
+impl DilithiumJwtVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, key_id: impl Into<String>, public_key_b64: &str) -> Result<Self, SharedError> {
+        let public_key_bytes = general_purpose::STANDARD
+            .decode(public_key_b64)
+            .map_err(|e| SharedError::CryptographicError(format!("Invalid public key: {}", e)))?;
+        DilithiumPublicKey::from_bytes(&public_key_bytes)
+            .map_err(|e| SharedError::CryptographicError(format!("Failed to construct public key: {}", e)))?;
+        self.public_keys.insert(key_id.into(), public_key_bytes);
+        Ok(self)
+    }
+}
+
+impl SignatureVerifier for DilithiumJwtVerifier {
+    fn algorithm(&self) -> JwtAlgorithm {
+        JwtAlgorithm::Dilithium2
+    }
+
+    fn verify(&self, key_id: Option<&str>, signing_input: &[u8], signature: &[u8]) -> Result<bool, SharedError> {
+        let key_id = key_id
+            .ok_or_else(|| SharedError::AuthenticationError("Dilithium token has no key id".to_string()))?;
+        let public_key_bytes = self
+            .public_keys
+            .get(key_id)
+            .ok_or_else(|| SharedError::AuthenticationError(format!("Unknown signing key: {}", key_id)))?;
+        let public_key = DilithiumPublicKey::from_bytes(public_key_bytes)
+            .map_err(|e| SharedError::CryptographicError(format!("Failed to construct public key: {}", e)))?;
+        let Ok(signature) = DilithiumDetachedSignature::from_bytes(signature) else {
+            return Ok(false);
+        };
+        Ok(verify_detached_signature(&signature, signing_input, &public_key).is_ok())
+    }
+}
+
+/// A booking's chat key encrypted to one participant's hybrid public key.
+/// The relay only ever sees envelopes, never chat keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatKeyEnvelope {
+    pub booking_id: BookingId,
+    pub epoch: u32,
+    pub recipient_id: String,
+    pub handshake: HybridCiphertext,
+    pub nonce: String,       // Base64 encoded
+    pub wrapped_key: String, // Base64 encoded
+}
+
+impl ChatKeyEnvelope {
+    fn associated_data(&self) -> Vec<u8> {
+        format!("{}|{}|{}", self.booking_id, self.epoch, self.recipient_id).into_bytes()
+    }
+}
+
+impl PQAuthentication {
+    /// Encrypt `key` for `recipient_id`, whose published bundle is `bundle`
+    pub fn seal_chat_key(
+        &self,
+        key: &ChatKey,
+        recipient_id: &str,
+        bundle: &HybridPublicKeyBundle,
+    ) -> Result<ChatKeyEnvelope, SharedError> {
+        let (handshake, secret) = self.hybrid_encapsulate(bundle)?;
+        let mut envelope = ChatKeyEnvelope {
+            booking_id: key.booking_id.clone(),
+            epoch: key.epoch,
+            recipient_id: recipient_id.to_string(),
+            handshake,
+            nonce: String::new(),
+            wrapped_key: String::new(),
+        };
+        let (nonce, wrapped_key) =
+            DataKey::from_bytes(secret.as_bytes())?.seal(key.as_bytes(), &envelope.associated_data())?;
+        envelope.nonce = general_purpose::STANDARD.encode(nonce);
+        envelope.wrapped_key = general_purpose::STANDARD.encode(wrapped_key);
+        Ok(envelope)
+    }
+
+    /// Recover the chat key from an envelope addressed to `keypair`'s owner
+    pub fn open_chat_key(&self, envelope: &ChatKeyEnvelope, keypair: &HybridKeyPair) -> Result<ChatKey, SharedError> {
+        let secret = self.hybrid_decapsulate(keypair, &envelope.handshake)?;
+        let nonce = general_purpose::STANDARD.decode(&envelope.nonce)?;
+        let wrapped_key = general_purpose::STANDARD.decode(&envelope.wrapped_key)?;
+        let key = DataKey::from_bytes(secret.as_bytes())?.open(&nonce, &wrapped_key, &envelope.associated_data())?;
+        ChatKey::from_bytes(envelope.booking_id.clone(), envelope.epoch, key.expose())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keypair_generation() {
+        let auth = PQAuthentication::new();
+        let keypair = auth.generate_keypair().unwrap();
+        
+        assert_eq!(keypair.algorithm, "CRYSTALS-Dilithium5");
+        assert!(!keypair.public_key.is_empty());
+        assert!(!keypair.private_key.is_empty());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let auth = PQAuthentication::new();
+        let keypair = auth.generate_keypair().unwrap();
+        let message = "Test message for MyDR24";
+        
+        let signature = auth.sign_message(message, &keypair.private_key).unwrap();
+        let verification = auth.verify_signature(message, &signature, &keypair.public_key).unwrap();
+        
+        assert!(verification.is_valid);
+    }
+
+    #[test]
+    fn test_hybrid_key_exchange() {
+        let auth = PQAuthentication::new();
+        let server = auth.generate_hybrid_keypair().unwrap();
+
+        // The bundle travels as JSON
+        let json = serde_json::to_string(&server.public).unwrap();
+        let bundle: HybridPublicKeyBundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(bundle.algorithm, HYBRID_KEM_ALGORITHM);
+
+        let (ciphertext, client_secret) = auth.hybrid_encapsulate(&bundle).unwrap();
+        let server_secret = auth.hybrid_decapsulate(&server, &ciphertext).unwrap();
+        assert_eq!(client_secret, server_secret);
+        assert_eq!(format!("{:?}", server_secret), "SessionSecret([REDACTED])");
+        assert!(!format!("{:?}", server).contains(server.kem_private_key.expose()));
+
+        // A second handshake gives a fresh secret
+        let (_, other_secret) = auth.hybrid_encapsulate(&bundle).unwrap();
+        assert_ne!(other_secret, client_secret);
+
+        // Tampering changes the derived secret; the wrong key is refused
+        let mut tampered = ciphertext.clone();
+        let other_keypair = auth.generate_hybrid_keypair().unwrap();
+        tampered.x25519_ephemeral_key = other_keypair.public.x25519_public_key.clone();
+        assert_ne!(auth.hybrid_decapsulate(&server, &tampered).unwrap(), client_secret);
+        assert!(auth.hybrid_decapsulate(&other_keypair, &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_key_rotation_with_grace_window() {
+        let start = Utc::now();
+        let mut manager = KeyManager::new(KeyRotationPolicy::default(), start).unwrap();
+        let first_id = manager.public_bundle().unwrap().key_id;
+        let (old_handshake, old_secret) = PQAuthentication::new()
+            .hybrid_encapsulate(manager.public_bundle().unwrap())
+            .unwrap();
+
+        assert_eq!(manager.rotate_if_due(start + chrono::Duration::days(30)).unwrap(), None);
+        let rotated_at = start + chrono::Duration::days(90);
+        let second_id = manager.rotate_if_due(rotated_at).unwrap().expect("rotation due");
+        assert_ne!(second_id, first_id);
+        assert_eq!(manager.public_bundle().unwrap().key_id, second_id);
+        assert_eq!(manager.key(first_id).unwrap().status, KeyStatus::Retired);
+        let debug = format!("{:?}", manager);
+        assert!(debug.contains(&first_id.to_string()));
+        assert!(manager.keys().iter().all(|key| !debug.contains(key.keypair.x25519_private_key.expose())));
+
+        // Retired keys still open old handshakes until the grace window closes
+        let within_grace = rotated_at + chrono::Duration::days(29);
+        assert_eq!(manager.decapsulate(&old_handshake, within_grace).unwrap(), old_secret);
+        let after_grace = rotated_at + chrono::Duration::days(31);
+        assert!(manager.decapsulate(&old_handshake, after_grace).is_err());
+        assert_eq!(manager.purge_expired(after_grace), 1);
+        assert_eq!(manager.keys().len(), 1);
+
+        manager.revoke(second_id, after_grace).unwrap();
+        assert!(manager.public_bundle().is_err());
+        assert!(manager.needs_rotation(after_grace));
+    }
+
+    #[test]
+    fn test_dilithium_signed_jwt() {
+        let auth = PQAuthentication::new();
+        let keypair = auth.generate_keypair().unwrap();
+        let key_id = keypair.key_id.to_string();
+        let now = Utc::now();
+        let claims = AuthClaims::new("user-42", crate::auth::Role::Provider, now + chrono::Duration::minutes(15));
+        let token = auth.sign_jwt(&claims, &key_id, &keypair.private_key).unwrap();
+
+        let verifier = DilithiumJwtVerifier::new().with_key(key_id, &keypair.public_key).unwrap();
+        let validator = crate::auth::TokenValidator::new().with_verifier(std::sync::Arc::new(verifier));
+        assert_eq!(validator.validate(&token, now).unwrap(), claims);
+
+        // Another issuer's key under the same id doesn't verify
+        let other = auth.generate_keypair().unwrap();
+        let forged = auth.sign_jwt(&claims, &keypair.key_id.to_string(), &other.private_key).unwrap();
+        assert!(validator.validate(&forged, now).is_err());
+    }
+
+    #[test]
+    fn test_chat_key_distribution() {
+        let auth = PQAuthentication::new();
+        let provider = auth.generate_hybrid_keypair().unwrap();
+        let booking = BookingId::new("BK-7");
+        let participants = vec!["patient-1".to_string(), "provider-1".to_string()];
+        let ring = crate::auth::ChatKeyRing::new(booking, &participants);
+
+        let envelope = auth.seal_chat_key(ring.current_key(), "provider-1", &provider.public).unwrap();
+        let json = serde_json::to_string(&envelope).unwrap();
+        let envelope: ChatKeyEnvelope = serde_json::from_str(&json).unwrap();
+        let key = auth.open_chat_key(&envelope, &provider).unwrap();
+        assert_eq!(key.as_bytes(), ring.current_key().as_bytes());
+
+        let content = ring.encrypt("m-1", "patient-1", "Gate code is 4411").unwrap();
+        let provider_ring = crate::auth::ChatKeyRing::from_key(key, &participants);
+        assert_eq!(provider_ring.decrypt("m-1", "patient-1", &content).unwrap().expose(), "Gate code is 4411");
+
+        // Re-addressing the envelope or opening it with another key fails
+        let mut redirected = envelope.clone();
+        redirected.recipient_id = "intruder".to_string();
+        assert!(auth.open_chat_key(&redirected, &provider).is_err());
+        let other = auth.generate_hybrid_keypair().unwrap();
+        assert!(auth.open_chat_key(&envelope, &other).is_err());
+    }
+
+    #[test]
+    fn test_challenge_creation() {
+        let auth = PQAuthentication::new();
+        let challenge = auth.create_challenge("test@mydr24.com");
+        
+        assert_eq!(challenge.email, "test@mydr24.com");
+        assert!(challenge.challenge_text.contains("MyDR24-Auth-Challenge"));
+        assert!(auth.validate_challenge(&challenge));
+    }
+}
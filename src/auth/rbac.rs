@@ -0,0 +1,236 @@
+//! Role-based access control: platform roles, their hierarchy and the permissions each grants
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::errors::{SharedError, SharedResult};
+
+/// A user's role on the platform. Roles inherit everything their parent
+/// grants: admin ⊃ org_admin ⊃ provider, and each clinical role ⊃ provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Patient,
+    /// Any care provider; the clinical roles below refine it
+    Provider,
+    #[serde(alias = "doctor")]
+    Physician,
+    Nurse,
+    Technician,
+    /// Manages one organisation's staff, schedules and billing
+    OrgAdmin,
+    /// Platform operator
+    Admin,
+}
+
+/// Something a role is allowed to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    ViewOwnRecords,
+    BookAppointments,
+    ManageOwnConsents,
+    SendMessages,
+    ManageOwnSchedule,
+    ViewAssignedPatients,
+    ViewPatientRecords,
+    WriteClinicalNotes,
+    Prescribe,
+    RecordVitals,
+    UploadLabResults,
+    ManageOrgStaff,
+    ManageOrgBilling,
+    ViewOrgReports,
+    ManageUsers,
+    ViewAuditLog,
+    ConfigurePlatform,
+}
+
+impl Role {
+    pub const ALL: [Role; 7] = [
+        Role::Patient,
+        Role::Provider,
+        Role::Physician,
+        Role::Nurse,
+        Role::Technician,
+        Role::OrgAdmin,
+        Role::Admin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Patient => "patient",
+            Role::Provider => "provider",
+            Role::Physician => "physician",
+            Role::Nurse => "nurse",
+            Role::Technician => "technician",
+            Role::OrgAdmin => "org_admin",
+            Role::Admin => "admin",
+        }
+    }
+
+    /// Case-insensitive lookup of the serialized name; "doctor" is accepted for physicians
+    pub fn parse(role: &str) -> Option<Self> {
+        let role = role.trim();
+        if role.eq_ignore_ascii_case("doctor") {
+            return Some(Role::Physician);
+        }
+        Role::ALL.into_iter().find(|r| r.as_str().eq_ignore_ascii_case(role))
+    }
+
+    /// Name shown next to the user's avatar
+    pub fn label(&self) -> &'static str {
+        match self {
+            Role::Patient => "Patient",
+            Role::Provider => "Healthcare Provider",
+            Role::Physician => "Physician",
+            Role::Nurse => "Nurse",
+            Role::Technician => "Technician",
+            Role::OrgAdmin => "Organisation Administrator",
+            Role::Admin => "System Administrator",
+        }
+    }
+
+    /// The role this one directly extends
+    pub fn parent(&self) -> Option<Role> {
+        match self {
+            Role::Patient | Role::Provider => None,
+            Role::Physician | Role::Nurse | Role::Technician | Role::OrgAdmin => Some(Role::Provider),
+            Role::Admin => Some(Role::OrgAdmin),
+        }
+    }
+
+    /// True if this role is `other` or extends it, e.g. `Admin.includes(Provider)`
+    pub fn includes(&self, other: Role) -> bool {
+        std::iter::successors(Some(*self), Role::parent).any(|role| role == other)
+    }
+
+    pub fn is_provider(&self) -> bool {
+        self.includes(Role::Provider)
+    }
+
+    /// Accounts with this role must enroll a second factor before accessing
+    /// other patients' data or platform settings
+    pub fn requires_mfa(&self) -> bool {
+        *self != Role::Patient
+    }
+
+    /// Permissions granted by this role alone, without inherited ones
+    fn own_permissions(&self) -> &'static [Permission] {
+        use Permission::*;
+        match self {
+            Role::Patient => &[ViewOwnRecords, BookAppointments, ManageOwnConsents, SendMessages],
+            Role::Provider => &[SendMessages, ManageOwnSchedule, ViewAssignedPatients],
+            Role::Physician => &[ViewPatientRecords, WriteClinicalNotes, Prescribe],
+            Role::Nurse => &[ViewPatientRecords, WriteClinicalNotes, RecordVitals],
+            Role::Technician => &[UploadLabResults, RecordVitals],
+            Role::OrgAdmin => &[ManageOrgStaff, ManageOrgBilling, ViewOrgReports],
+            Role::Admin => &[ManageUsers, ViewAuditLog, ConfigurePlatform],
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        std::iter::successors(Some(*self), Role::parent).any(|role| role.own_permissions().contains(&permission))
+    }
+
+    /// Everything this role may do, including inherited permissions
+    pub fn permissions(&self) -> Vec<Permission> {
+        let mut permissions: Vec<Permission> = Vec::new();
+        for role in std::iter::successors(Some(*self), Role::parent) {
+            for permission in role.own_permissions() {
+                if !permissions.contains(permission) {
+                    permissions.push(*permission);
+                }
+            }
+        }
+        permissions
+    }
+
+    /// `Ok(())` if the role grants `permission`, otherwise an authorization error
+    pub fn require(&self, permission: Permission) -> SharedResult<()> {
+        if self.has_permission(permission) {
+            Ok(())
+        } else {
+            Err(SharedError::AuthorizationError(format!(
+                "Role '{}' lacks permission '{}'",
+                self, permission
+            )))
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ViewOwnRecords => "view_own_records",
+            Permission::BookAppointments => "book_appointments",
+            Permission::ManageOwnConsents => "manage_own_consents",
+            Permission::SendMessages => "send_messages",
+            Permission::ManageOwnSchedule => "manage_own_schedule",
+            Permission::ViewAssignedPatients => "view_assigned_patients",
+            Permission::ViewPatientRecords => "view_patient_records",
+            Permission::WriteClinicalNotes => "write_clinical_notes",
+            Permission::Prescribe => "prescribe",
+            Permission::RecordVitals => "record_vitals",
+            Permission::UploadLabResults => "upload_lab_results",
+            Permission::ManageOrgStaff => "manage_org_staff",
+            Permission::ManageOrgBilling => "manage_org_billing",
+            Permission::ViewOrgReports => "view_org_reports",
+            Permission::ManageUsers => "manage_users",
+            Permission::ViewAuditLog => "view_audit_log",
+            Permission::ConfigurePlatform => "configure_platform",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hierarchy_and_permissions() {
+        assert!(Role::Admin.includes(Role::OrgAdmin));
+        assert!(Role::Admin.includes(Role::Provider));
+        assert!(Role::OrgAdmin.includes(Role::Provider));
+        assert!(!Role::Provider.includes(Role::OrgAdmin));
+        assert!(!Role::Admin.includes(Role::Patient));
+        assert!(Role::Nurse.is_provider());
+        assert!(Role::OrgAdmin.requires_mfa() && !Role::Patient.requires_mfa());
+
+        assert!(Role::Admin.has_permission(Permission::ManageOrgStaff));
+        assert!(Role::Admin.has_permission(Permission::ViewAssignedPatients));
+        assert!(!Role::OrgAdmin.has_permission(Permission::ViewAuditLog));
+        assert!(Role::Physician.has_permission(Permission::Prescribe));
+        assert!(!Role::Nurse.has_permission(Permission::Prescribe));
+        assert!(!Role::Patient.has_permission(Permission::ViewPatientRecords));
+        assert!(Role::Technician.require(Permission::WriteClinicalNotes).is_err());
+
+        let permissions = Role::Physician.permissions();
+        assert_eq!(permissions.iter().filter(|p| **p == Permission::SendMessages).count(), 1);
+        assert!(permissions.contains(&Permission::ManageOwnSchedule));
+    }
+
+    #[test]
+    fn test_serialization_and_parsing() {
+        assert_eq!(serde_json::to_value(Role::OrgAdmin).unwrap(), "org_admin");
+        assert_eq!(serde_json::from_value::<Role>("doctor".into()).unwrap(), Role::Physician);
+        assert!(serde_json::from_value::<Role>("superuser".into()).is_err());
+        assert_eq!(serde_json::to_value(Permission::ViewAuditLog).unwrap(), "view_audit_log");
+        assert_eq!(Role::parse(" Org_Admin "), Some(Role::OrgAdmin));
+        for role in Role::ALL {
+            assert_eq!(Role::parse(&role.to_string()), Some(role));
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Device-key signatures on high-sensitivity API requests (prescriptions, record exports) and replay-window checks for verifiers
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use super::device::{DeviceKeyPair, RegisteredDevice, DEVICE_ID_HEADER};
+use crate::api_backend::{HttpBody, HttpRequest};
+use crate::errors::{SharedError, SharedResult};
+
+/// Base64 Ed25519 signature over the canonical request
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Signing time in Unix milliseconds; part of the signed input
+pub const SIGNATURE_TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+
+/// How far a signed request's timestamp may be from the verifier's clock
+pub const SIGNATURE_WINDOW_SECONDS: i64 = 300;
+
+/// Domain separation for request signatures; bump on any change to the canonical form
+const REQUEST_SIGNATURE_CONTEXT: &str = "MyDR24 request signature v1";
+
+/// One endpoint that must be signed: an HTTP method and a path relative to
+/// the API root, where `*` matches exactly one segment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedEndpoint {
+    pub method: String,
+    pub path: String,
+}
+
+impl SignedEndpoint {
+    pub fn new(method: &str, path: &str) -> Self {
+        Self { method: method.to_ascii_uppercase(), path: path.trim_matches('/').to_string() }
+    }
+
+    pub fn matches(&self, method: &str, endpoint: &str) -> bool {
+        if !self.method.eq_ignore_ascii_case(method) {
+            return false;
+        }
+        let endpoint = endpoint.split('?').next().unwrap_or(endpoint).trim_matches('/');
+        let mut pattern = self.path.split('/');
+        let mut segments = endpoint.split('/');
+        loop {
+            match (pattern.next(), segments.next()) {
+                (None, None) => return true,
+                (Some("*"), Some(segment)) if !segment.is_empty() => {}
+                (Some(expected), Some(segment)) if expected == segment => {}
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// Which API calls the client signs with its device key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestSigningPolicy {
+    pub endpoints: Vec<SignedEndpoint>,
+}
+
+impl Default for RequestSigningPolicy {
+    /// Prescription creation, medical record upload and export, and approving
+    /// or collecting a QR sign-in
+    fn default() -> Self {
+        Self {
+            endpoints: vec![
+                SignedEndpoint::new("POST", "prescriptions"),
+                SignedEndpoint::new("POST", "patients/*/prescriptions"),
+                SignedEndpoint::new("POST", "patients/*/medical-records"),
+                SignedEndpoint::new("POST", "patients/*/exports"),
+                SignedEndpoint::new("GET", "patients/*/exports/*/download"),
+                SignedEndpoint::new("POST", "auth/pairing/*/approve"),
+                SignedEndpoint::new("POST", "auth/pairing/*/poll"),
+            ],
+        }
+    }
+}
+
+impl RequestSigningPolicy {
+    /// A policy that signs nothing; add endpoints with [`Self::with_endpoint`]
+    pub fn empty() -> Self {
+        Self { endpoints: Vec::new() }
+    }
+
+    pub fn with_endpoint(mut self, method: &str, path: &str) -> Self {
+        self.endpoints.push(SignedEndpoint::new(method, path));
+        self
+    }
+
+    pub fn requires_signature(&self, method: &str, endpoint: &str) -> bool {
+        self.endpoints.iter().any(|e| e.matches(method, endpoint))
+    }
+}
+
+/// URL without scheme or host, keeping the query string
+fn path_and_query(url: &str) -> &str {
+    match url.find("://") {
+        Some(scheme_end) => {
+            let rest = &url[scheme_end + 3..];
+            rest.find(['/', '?']).map(|i| &rest[i..]).unwrap_or("/")
+        }
+        None => url,
+    }
+}
+
+fn body_digest(body: &HttpBody) -> String {
+    let mut hasher = Sha256::new();
+    match body {
+        HttpBody::Empty => {}
+        HttpBody::Json(json) => hasher.update(json.as_bytes()),
+        HttpBody::Bytes(bytes) => hasher.update(bytes),
+        // Length-prefix every field so parts can't be re-split into the same bytes
+        HttpBody::Multipart(parts) => {
+            for part in parts {
+                for field in [
+                    part.name.as_bytes(),
+                    part.filename.as_deref().unwrap_or_default().as_bytes(),
+                    part.content_type.as_deref().unwrap_or_default().as_bytes(),
+                    &part.data,
+                ] {
+                    hasher.update((field.len() as u64).to_be_bytes());
+                    hasher.update(field);
+                }
+            }
+        }
+    }
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The bytes a request signature covers: method, path and query, device,
+/// timestamp and a SHA-256 of the body. Other headers are not signed, so
+/// proxies may add or rewrite them.
+pub fn canonical_request(request: &HttpRequest, device_id: &str, timestamp: DateTime<Utc>) -> Vec<u8> {
+    format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        REQUEST_SIGNATURE_CONTEXT,
+        request.method.to_ascii_uppercase(),
+        path_and_query(&request.url),
+        device_id,
+        timestamp.timestamp_millis(),
+        body_digest(&request.body)
+    )
+    .into_bytes()
+}
+
+/// Sign `request` with the device key, replacing any earlier signature so a
+/// retried request carries a fresh timestamp
+pub fn sign_request(request: &mut HttpRequest, key: &DeviceKeyPair, now: DateTime<Utc>) {
+    let signature = key.sign(&canonical_request(request, key.device_id(), now));
+    request.headers.retain(|(name, _)| {
+        ![SIGNATURE_HEADER, SIGNATURE_TIMESTAMP_HEADER, DEVICE_ID_HEADER]
+            .iter()
+            .any(|h| name.eq_ignore_ascii_case(h))
+    });
+    request.headers.push((DEVICE_ID_HEADER.to_string(), key.device_id().to_string()));
+    request.headers.push((SIGNATURE_TIMESTAMP_HEADER.to_string(), now.timestamp_millis().to_string()));
+    request.headers.push((SIGNATURE_HEADER.to_string(), signature));
+}
+
+/// The signature headers of a received request
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestSignature {
+    pub device_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub signature: String,
+}
+
+impl RequestSignature {
+    pub fn from_request(request: &HttpRequest) -> SharedResult<Self> {
+        let header = |name: &str| {
+            request
+                .header(name)
+                .ok_or_else(|| SharedError::AuthenticationError(format!("Missing {} header", name)))
+        };
+        let millis: i64 = header(SIGNATURE_TIMESTAMP_HEADER)?
+            .parse()
+            .map_err(|_| SharedError::AuthenticationError("Invalid signature timestamp".to_string()))?;
+        let timestamp = Utc
+            .timestamp_millis_opt(millis)
+            .single()
+            .ok_or_else(|| SharedError::AuthenticationError("Invalid signature timestamp".to_string()))?;
+        Ok(Self {
+            device_id: header(DEVICE_ID_HEADER)?.to_string(),
+            timestamp,
+            signature: header(SIGNATURE_HEADER)?.to_string(),
+        })
+    }
+}
+
+/// Verifier-side memory of recently accepted signatures. A signed request is
+/// only accepted inside the window around its timestamp and only once, so a
+/// captured request can't be replayed.
+#[derive(Debug, Clone)]
+pub struct ReplayGuard {
+    window: Duration,
+    seen: HashMap<String, DateTime<Utc>>,
+}
+
+impl Default for ReplayGuard {
+    fn default() -> Self {
+        Self::new(Duration::seconds(SIGNATURE_WINDOW_SECONDS))
+    }
+}
+
+impl ReplayGuard {
+    pub fn new(window: Duration) -> Self {
+        Self { window, seen: HashMap::new() }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    pub fn is_within_window(&self, timestamp: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        (now - timestamp).abs() <= self.window
+    }
+
+    /// Accept `signature` once if its timestamp is inside the window.
+    /// Signatures older than the window are forgotten, as the timestamp
+    /// check rejects them anyway.
+    pub fn check(&mut self, signature: &str, timestamp: DateTime<Utc>, now: DateTime<Utc>) -> SharedResult<()> {
+        if !self.is_within_window(timestamp, now) {
+            return Err(SharedError::AuthenticationError("Signed request is outside the replay window".to_string()));
+        }
+        let cutoff = now - self.window;
+        self.seen.retain(|_, seen_at| *seen_at >= cutoff);
+        if self.seen.contains_key(signature) {
+            return Err(SharedError::AuthenticationError("Signed request was replayed".to_string()));
+        }
+        self.seen.insert(signature.to_string(), timestamp);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+/// Verify a signed request from `device`: the signature must cover this exact
+/// request, come from an unrevoked device, and not have been seen before.
+/// Only valid signatures are remembered, so forged ones can't fill the guard.
+pub fn verify_signed_request(
+    request: &HttpRequest,
+    device: &RegisteredDevice,
+    guard: &mut ReplayGuard,
+    now: DateTime<Utc>,
+) -> SharedResult<RequestSignature> {
+    let signed = RequestSignature::from_request(request)?;
+    if signed.device_id != device.device_id() {
+        return Err(SharedError::AuthenticationError("Request was signed by another device".to_string()));
+    }
+    if device.is_revoked() {
+        return Err(SharedError::AuthenticationError("Device has been revoked".to_string()));
+    }
+    if !guard.is_within_window(signed.timestamp, now) {
+        return Err(SharedError::AuthenticationError("Signed request is outside the replay window".to_string()));
+    }
+    if !device.verify(&canonical_request(request, &signed.device_id, signed.timestamp), &signed.signature)? {
+        return Err(SharedError::AuthenticationError("Invalid request signature".to_string()));
+    }
+    guard.check(&signed.signature, signed.timestamp, now)?;
+    Ok(signed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_backend::{ApiRequestBuilder, MultipartPart};
+    use crate::auth::device::DeviceRegistry;
+
+    #[test]
+    fn test_policy_matching() {
+        let policy = RequestSigningPolicy::default();
+        assert!(policy.requires_signature("POST", "patients/p-1/medical-records"));
+        assert!(policy.requires_signature("get", "/patients/p-1/exports/e-9/download?token=x"));
+        assert!(!policy.requires_signature("GET", "patients/p-1/medical-records"));
+        assert!(!policy.requires_signature("POST", "patients/p-1/medical-records/r-1/share"));
+        assert!(!policy.requires_signature("POST", "patients//medical-records"));
+
+        let custom = RequestSigningPolicy::empty().with_endpoint("delete", "/patients/*");
+        assert!(custom.requires_signature("DELETE", "patients/p-1"));
+        assert!(!custom.requires_signature("POST", "prescriptions"));
+    }
+
+    #[test]
+    fn test_sign_verify_and_replay() {
+        let now = Utc::now();
+        let key = DeviceKeyPair::generate();
+        let mut registry = DeviceRegistry::new();
+        let device = registry.register("dr-1", key.registration("Clinic iPad"), now).unwrap().clone();
+        let mut guard = ReplayGuard::default();
+
+        let mut request = ApiRequestBuilder::new("POST", "https://api.example.com/api/v1/prescriptions?draft=false")
+            .json(&serde_json::json!({ "drug": "amoxicillin", "dose_mg": 500 }))
+            .unwrap();
+        sign_request(&mut request, &key, now);
+        let signed = verify_signed_request(&request, &device, &mut guard, now + Duration::seconds(2)).unwrap();
+        assert_eq!(signed.device_id, key.device_id());
+        assert_eq!(guard.len(), 1);
+
+        // The same request again, or outside the window, is rejected
+        assert!(verify_signed_request(&request, &device, &mut guard, now + Duration::seconds(3)).is_err());
+        let mut late = ReplayGuard::default();
+        assert!(verify_signed_request(&request, &device, &mut late, now + Duration::minutes(6)).is_err());
+
+        // Re-signing (as on retry) gives a fresh, accepted signature
+        sign_request(&mut request, &key, now + Duration::seconds(1));
+        assert_eq!(request.headers.iter().filter(|(k, _)| k == SIGNATURE_HEADER).count(), 1);
+        assert!(verify_signed_request(&request, &device, &mut guard, now + Duration::seconds(2)).is_ok());
+
+        // Tampering with the body, query or a multipart field breaks the signature
+        let mut tampered = request.clone();
+        tampered.body = HttpBody::Json(r#"{"drug":"amoxicillin","dose_mg":5000}"#.to_string());
+        assert!(verify_signed_request(&tampered, &device, &mut ReplayGuard::default(), now).is_err());
+        let mut tampered = request.clone();
+        tampered.url = tampered.url.replace("draft=false", "draft=true");
+        assert!(verify_signed_request(&tampered, &device, &mut ReplayGuard::default(), now).is_err());
+
+        let mut upload = ApiRequestBuilder::new("POST", "https://api.example.com/api/v1/patients/p-1/medical-records").build();
+        upload.body = HttpBody::Multipart(vec![MultipartPart::file("file", "rx.pdf", "application/pdf", vec![1, 2, 3])]);
+        sign_request(&mut upload, &key, now);
+        let mut swapped = upload.clone();
+        swapped.body = HttpBody::Multipart(vec![MultipartPart::file("file", "rx.pdf", "application/pdf", vec![1, 2, 4])]);
+        assert!(verify_signed_request(&swapped, &device, &mut ReplayGuard::default(), now).is_err());
+
+        // A signature from a revoked device is refused
+        registry.revoke(key.device_id(), now).unwrap();
+        let revoked = registry.device(key.device_id()).unwrap();
+        assert!(verify_signed_request(&upload, revoked, &mut ReplayGuard::default(), now).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Token revocation list synced from the server, so compromised tokens and sessions stop working before they expire
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use super::jwt::{AuthClaims, DEFAULT_LEEWAY_SECONDS};
+use crate::errors::{SharedError, SharedResult};
+
+/// One revoked access token, identified by its `jti`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevokedToken {
+    pub jti: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// The token's own expiry; after it the entry is no longer needed
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Every token issued to a user up to `revoked_at`, e.g. after an account
+/// compromise or a password reset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevokedSubject {
+    pub user_id: String,
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// What the server sends on sync or push: the entries added between
+/// `from_version` and `version`, or the whole list when `full` is set
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RevocationUpdate {
+    pub version: u64,
+    /// Version the delta starts from; when absent it covers just one version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_version: Option<u64>,
+    #[serde(default)]
+    pub full: bool,
+    #[serde(default)]
+    pub tokens: Vec<RevokedToken>,
+    #[serde(default)]
+    pub subjects: Vec<RevokedSubject>,
+}
+
+#[derive(Debug, Default)]
+struct RevocationState {
+    version: u64,
+    synced_at: Option<DateTime<Utc>>,
+    /// Set when a delta didn't continue from `version`, so entries may be missing
+    needs_full_sync: bool,
+    tokens: HashMap<String, RevokedToken>,
+    subjects: HashMap<String, DateTime<Utc>>,
+}
+
+/// Local copy of the platform's revocation list. Cheap to clone; clones share
+/// state, so the API client, token validator and WebSocket feed can all hold one.
+#[derive(Clone, Default)]
+pub struct RevocationList {
+    state: Arc<RwLock<RevocationState>>,
+}
+
+impl fmt::Debug for RevocationList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.read();
+        let mut debug = f.debug_struct("RevocationList");
+        if let Ok(state) = state {
+            debug
+                .field("version", &state.version)
+                .field("tokens", &state.tokens.len())
+                .field("subjects", &state.subjects.len());
+        }
+        debug.finish()
+    }
+}
+
+impl RevocationList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Version of the last applied update; sent as `since` when syncing
+    pub fn version(&self) -> u64 {
+        self.state.read().map(|s| s.version).unwrap_or(0)
+    }
+
+    pub fn synced_at(&self) -> Option<DateTime<Utc>> {
+        self.state.read().ok().and_then(|s| s.synced_at)
+    }
+
+    /// True once a delta arrived that didn't continue from the current
+    /// version; the next sync should fetch the whole list
+    pub fn needs_full_sync(&self) -> bool {
+        self.state.read().map_or(true, |s| s.needs_full_sync)
+    }
+
+    /// True if the list hasn't been synced within `max_age`, or has a gap;
+    /// callers should sync before trusting a cached token again
+    pub fn is_stale(&self, max_age: Duration, now: DateTime<Utc>) -> bool {
+        self.needs_full_sync() || self.synced_at().is_none_or(|synced_at| now - synced_at > max_age)
+    }
+
+    /// Merge an update from the server. Returns how many entries were added.
+    ///
+    /// Deltas already covered by the current version are ignored. A delta
+    /// that doesn't start at the current version would leave a gap, so it is
+    /// refused and the list marked for a full sync. A full list older than
+    /// the current version is refused too, so a replayed snapshot can't
+    /// un-revoke tokens.
+    pub fn apply(&self, update: RevocationUpdate, now: DateTime<Utc>) -> SharedResult<usize> {
+        let mut state = self
+            .state
+            .write()
+            .map_err(|_| SharedError::InternalError("Revocation list lock poisoned".to_string()))?;
+        if update.full {
+            if update.version < state.version {
+                return Err(SharedError::ValidationError(format!(
+                    "Full revocation list version {} is older than {}",
+                    update.version, state.version
+                )));
+            }
+            state.tokens.clear();
+            state.subjects.clear();
+            state.needs_full_sync = false;
+        } else if update.version <= state.version {
+            return Ok(0);
+        } else if update.from_version.unwrap_or(update.version - 1) != state.version {
+            state.needs_full_sync = true;
+            return Err(SharedError::ValidationError(format!(
+                "Revocation delta to version {} does not continue from {}; full sync needed",
+                update.version, state.version
+            )));
+        } else {
+            // A delta from the current version covers whatever was missed
+            state.needs_full_sync = false;
+        }
+        let mut added = 0;
+        for token in update.tokens {
+            added += usize::from(state.tokens.insert(token.jti.clone(), token).is_none());
+        }
+        for subject in update.subjects {
+            let revoked_at = state.subjects.entry(subject.user_id).or_insert_with(|| {
+                added += 1;
+                subject.revoked_at
+            });
+            *revoked_at = (*revoked_at).max(subject.revoked_at);
+        }
+        state.version = update.version;
+        state.synced_at = Some(now);
+        Ok(added)
+    }
+
+    /// Record a revocation made on this device, e.g. the token just signed out
+    pub fn revoke_token(&self, token: RevokedToken) {
+        if let Ok(mut state) = self.state.write() {
+            state.tokens.insert(token.jti.clone(), token);
+        }
+    }
+
+    /// True if the token's id is listed, or its user's tokens were revoked
+    /// after it was issued. A token without `iat` can't prove it is newer
+    /// than a user-wide revocation, so it counts as revoked.
+    pub fn is_revoked(&self, claims: &AuthClaims) -> bool {
+        let Ok(state) = self.state.read() else {
+            return true;
+        };
+        if claims.jti.as_ref().is_some_and(|jti| state.tokens.contains_key(jti)) {
+            return true;
+        }
+        state.subjects.get(&claims.user_id).is_some_and(|revoked_at| {
+            claims.iat.is_none_or(|iat| iat <= revoked_at.timestamp())
+        })
+    }
+
+    pub fn check(&self, claims: &AuthClaims) -> SharedResult<()> {
+        if self.is_revoked(claims) {
+            return Err(SharedError::AuthenticationError("Token has been revoked".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Drop entries for tokens that have expired anyway; returns how many
+    pub fn prune(&self, now: DateTime<Utc>) -> usize {
+        let Ok(mut state) = self.state.write() else {
+            return 0;
+        };
+        let before = state.tokens.len();
+        let cutoff = now - Duration::seconds(DEFAULT_LEEWAY_SECONDS);
+        state.tokens.retain(|_, token| token.expires_at > cutoff);
+        before - state.tokens.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::rbac::Role;
+
+    fn revoked(jti: &str, now: DateTime<Utc>, expires_in: Duration) -> RevokedToken {
+        RevokedToken { jti: jti.to_string(), user_id: None, expires_at: now + expires_in, revoked_at: now, reason: None }
+    }
+
+    #[test]
+    fn test_token_and_subject_revocation() {
+        let now = Utc::now();
+        let list = RevocationList::new();
+        let mut claims = AuthClaims::new("u-1", Role::Nurse, now + Duration::minutes(15)).with_token_id("t-1");
+        claims.iat = Some(now.timestamp());
+        assert!(list.check(&claims).is_ok());
+        assert!(list.is_stale(Duration::minutes(5), now));
+
+        let update = RevocationUpdate { version: 3, from_version: Some(0), tokens: vec![revoked("t-1", now, Duration::minutes(15))], ..Default::default() };
+        assert_eq!(list.apply(update, now).unwrap(), 1);
+        assert!(list.check(&claims).is_err());
+        assert_eq!(list.version(), 3);
+        assert!(!list.is_stale(Duration::minutes(5), now));
+
+        // Revoking the user kills tokens issued before, not after
+        let other = claims.clone().with_token_id("t-2");
+        let mut reissued = other.clone();
+        reissued.iat = Some((now + Duration::minutes(2)).timestamp());
+        let subject = RevokedSubject { user_id: "u-1".to_string(), revoked_at: now + Duration::minutes(1) };
+        list.apply(RevocationUpdate { version: 4, subjects: vec![subject], ..Default::default() }, now).unwrap();
+        assert!(list.is_revoked(&other));
+        assert!(!list.is_revoked(&reissued));
+        reissued.iat = None;
+        assert!(list.is_revoked(&reissued));
+
+        // Stale deltas are ignored; a full sync replaces everything
+        assert_eq!(list.apply(RevocationUpdate { version: 2, tokens: vec![revoked("t-9", now, Duration::minutes(1))], ..Default::default() }, now).unwrap(), 0);
+        list.apply(RevocationUpdate { version: 10, full: true, ..Default::default() }, now).unwrap();
+        assert!(!list.is_revoked(&claims) && !list.is_revoked(&other));
+    }
+
+    #[test]
+    fn test_gaps_and_old_snapshots_are_refused() {
+        let now = Utc::now();
+        let list = RevocationList::new();
+        let first = RevocationUpdate { version: 5, full: true, tokens: vec![revoked("t-1", now, Duration::minutes(15))], ..Default::default() };
+        list.apply(first.clone(), now).unwrap();
+
+        // Version 6 was missed, so 7 can't be applied on top of 5
+        let gap = RevocationUpdate { version: 7, tokens: vec![revoked("t-7", now, Duration::minutes(15))], ..Default::default() };
+        assert!(list.apply(gap, now).is_err());
+        assert_eq!(list.version(), 5);
+        assert!(list.needs_full_sync() && list.is_stale(Duration::minutes(5), now));
+
+        let caught_up = RevocationUpdate { version: 7, from_version: Some(5), tokens: vec![revoked("t-7", now, Duration::minutes(15))], ..Default::default() };
+        assert_eq!(list.apply(caught_up, now).unwrap(), 1);
+        assert!(!list.needs_full_sync());
+
+        // Replaying the older snapshot would drop t-7's revocation
+        assert!(list.apply(first, now).is_err());
+        assert_eq!(list.version(), 7);
+    }
+
+    #[test]
+    fn test_prune_and_shared_state() {
+        let now = Utc::now();
+        let list = RevocationList::new();
+        let feed = list.clone();
+        feed.revoke_token(revoked("short", now, Duration::minutes(5)));
+        feed.revoke_token(revoked("long", now, Duration::hours(12)));
+
+        let claims = AuthClaims::new("u-1", Role::Patient, now + Duration::minutes(5)).with_token_id("short");
+        assert!(list.is_revoked(&claims));
+        assert_eq!(list.prune(now + Duration::minutes(30)), 1);
+        assert!(!list.is_revoked(&claims));
+
+        let update: RevocationUpdate = serde_json::from_str(r#"{"version":7,"from_version":0,"tokens":[]}"#).unwrap();
+        assert_eq!(list.apply(update, now).unwrap(), 0);
+        assert_eq!(feed.version(), 7);
+    }
+}
@@ -0,0 +1,634 @@
+//! Login sessions shared by the patient and provider apps: idle and absolute timeouts, devices and concurrent-session limits
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::rbac::Role;
+use crate::compliance::hipaa::{AuditOutcome, AuditSink, HipaaAction, HipaaAuditEntry};
+use crate::errors::{SharedError, SharedResult};
+use crate::events::EventBus;
+
+/// Timeouts and limits applied to every session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionPolicy {
+    /// Inactivity before automatic logout (HIPAA §164.312(a)(2)(iii))
+    pub idle_timeout_minutes: u32,
+    /// How long before the idle logout the UI is warned
+    pub idle_warning_minutes: u32,
+    /// Maximum session length regardless of activity
+    pub absolute_timeout_hours: u32,
+    /// Active sessions per user; signing in on another device ends the oldest
+    pub max_concurrent_sessions: usize,
+    /// How long a re-authentication (fingerprint, face, TOTP) unlocks
+    /// sensitive screens such as patient records
+    #[serde(default = "default_step_up_minutes")]
+    pub step_up_minutes: u32,
+}
+
+fn default_step_up_minutes() -> u32 {
+    5
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        Self {
+            idle_timeout_minutes: 15,
+            idle_warning_minutes: 2,
+            absolute_timeout_hours: 12,
+            max_concurrent_sessions: 3,
+            step_up_minutes: default_step_up_minutes(),
+        }
+    }
+}
+
+impl SessionPolicy {
+    fn idle_timeout(&self) -> Duration {
+        Duration::minutes(self.idle_timeout_minutes.into())
+    }
+
+    fn idle_warning(&self) -> Duration {
+        Duration::minutes(self.idle_warning_minutes.into())
+    }
+
+    fn step_up_validity(&self) -> Duration {
+        Duration::minutes(self.step_up_minutes.into())
+    }
+}
+
+/// The device a session was opened on, as shown in the "signed-in devices" list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceInfo {
+    pub device_id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+}
+
+impl DeviceInfo {
+    pub fn new(device_id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            device_id: device_id.into(),
+            name: name.into(),
+            platform: None,
+            ip_address: None,
+            user_agent: None,
+        }
+    }
+
+    pub fn with_platform(mut self, platform: impl Into<String>) -> Self {
+        self.platform = Some(platform.into());
+        self
+    }
+
+    pub fn with_ip_address(mut self, ip_address: impl Into<String>) -> Self {
+        self.ip_address = Some(ip_address.into());
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEndReason {
+    IdleTimeout,
+    AbsoluteTimeout,
+    LoggedOut,
+    /// Ended from another device's session list
+    Revoked,
+    /// Pushed out by a newer sign-in over the concurrent-session limit
+    SessionLimit,
+    /// The same device signed in again
+    Replaced,
+}
+
+/// How the user re-proved their identity during a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepUpMethod {
+    /// A platform authenticator with user verification: fingerprint, face or device PIN
+    Biometric,
+    /// A roaming WebAuthn authenticator such as a USB key
+    SecurityKey,
+    Totp,
+    Password,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub session_id: Uuid,
+    pub user_id: String,
+    pub role: Role,
+    pub device: DeviceInfo,
+    pub created_at: DateTime<Utc>,
+    pub last_activity_at: DateTime<Utc>,
+    /// Absolute end of the session, however active it is
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_reason: Option<SessionEndReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stepped_up_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_up_method: Option<StepUpMethod>,
+    #[serde(skip)]
+    idle_warning_sent: bool,
+}
+
+impl Session {
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// When the session logs out if there is no further activity
+    pub fn logout_at(&self, policy: &SessionPolicy) -> DateTime<Utc> {
+        (self.last_activity_at + policy.idle_timeout()).min(self.expires_at)
+    }
+
+    /// True if the user re-authenticated within the policy's step-up window
+    pub fn has_recent_step_up(&self, policy: &SessionPolicy, now: DateTime<Utc>) -> bool {
+        self.stepped_up_at
+            .is_some_and(|at| at <= now && now < at + policy.step_up_validity())
+    }
+
+    fn timeout_reason(&self, policy: &SessionPolicy, now: DateTime<Utc>) -> Option<SessionEndReason> {
+        if now >= self.expires_at {
+            Some(SessionEndReason::AbsoluteTimeout)
+        } else if now >= self.last_activity_at + policy.idle_timeout() {
+            Some(SessionEndReason::IdleTimeout)
+        } else {
+            None
+        }
+    }
+}
+
+/// Where a session stands at a given moment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    Active,
+    /// Still valid, but logs out at `logout_at` unless there is activity
+    IdleWarning { logout_at: DateTime<Utc> },
+    Ended(SessionEndReason),
+}
+
+/// Published on the event bus when a session nears its idle logout, so the
+/// UI can show a "still there?" prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionIdleWarning {
+    pub session_id: Uuid,
+    pub user_id: String,
+    pub logout_at: DateTime<Utc>,
+}
+
+/// Published on the event bus whenever a session ends; the app holding the
+/// session should clear its tokens and return to the sign-in screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionEnded {
+    pub session_id: Uuid,
+    pub user_id: String,
+    pub reason: SessionEndReason,
+}
+
+/// Owns the sessions of one or more users. Time is passed in so callers can
+/// drive it from a UI timer, a server clock or a test.
+#[derive(Clone, Default)]
+pub struct SessionManager {
+    pub policy: SessionPolicy,
+    sessions: Vec<Session>,
+    events: EventBus,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+}
+
+impl fmt::Debug for SessionManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionManager")
+            .field("policy", &self.policy)
+            .field("sessions", &self.sessions)
+            .field("events", &self.events)
+            .field("audit_sink", &self.audit_sink.is_some())
+            .finish()
+    }
+}
+
+impl SessionManager {
+    pub fn new(policy: SessionPolicy) -> Self {
+        Self { policy, sessions: Vec::new(), events: EventBus::new(), audit_sink: None }
+    }
+
+    /// Publish idle warnings and session ends on `events` instead of a private bus
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Record every sign-in, sign-out and rejected sign-in as a HIPAA audit entry
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    pub fn session(&self, session_id: Uuid) -> Option<&Session> {
+        self.sessions.iter().find(|s| s.session_id == session_id)
+    }
+
+    /// Start a session. A previous session on the same device is replaced, and
+    /// the least recently used sessions end if the user is over the limit.
+    pub fn create(&mut self, user_id: &str, role: Role, device: DeviceInfo, now: DateTime<Utc>) -> SharedResult<Session> {
+        if self.policy.max_concurrent_sessions == 0 {
+            return Err(SharedError::ConfigurationError("Session limit must be at least 1".to_string()));
+        }
+        self.expire(now);
+
+        let same_device: Vec<Uuid> = self
+            .active_sessions(user_id)
+            .filter(|s| s.device.device_id == device.device_id)
+            .map(|s| s.session_id)
+            .collect();
+        for session_id in same_device {
+            self.end(session_id, SessionEndReason::Replaced, now);
+        }
+
+        let mut active: Vec<(DateTime<Utc>, Uuid)> = self
+            .active_sessions(user_id)
+            .map(|s| (s.last_activity_at, s.session_id))
+            .collect();
+        active.sort();
+        let excess = (active.len() + 1).saturating_sub(self.policy.max_concurrent_sessions);
+        for (_, session_id) in active.into_iter().take(excess) {
+            self.end(session_id, SessionEndReason::SessionLimit, now);
+        }
+
+        let session = Session {
+            session_id: Uuid::new_v4(),
+            user_id: user_id.to_string(),
+            role,
+            device,
+            created_at: now,
+            last_activity_at: now,
+            expires_at: now + Duration::hours(self.policy.absolute_timeout_hours.into()),
+            ended_at: None,
+            end_reason: None,
+            stepped_up_at: None,
+            step_up_method: None,
+            idle_warning_sent: false,
+        };
+        self.audit(
+            HipaaAction::Login,
+            user_id,
+            AuditOutcome::Success,
+            &session.device,
+            now,
+            serde_json::json!({ "session_id": session.session_id, "role": role }),
+        );
+        self.sessions.push(session.clone());
+        Ok(session)
+    }
+
+    /// Audit a rejected sign-in, e.g. a wrong password or second factor.
+    /// `user_id` is whatever the user signed in with.
+    pub fn record_failed_login(&self, user_id: &str, device: &DeviceInfo, reason: &str, now: DateTime<Utc>) {
+        self.audit(
+            HipaaAction::FailedLogin,
+            user_id,
+            AuditOutcome::Failure,
+            device,
+            now,
+            serde_json::json!({ "reason": reason }),
+        );
+    }
+
+    /// Record user activity, pushing back the idle logout. Fails once the session has ended.
+    pub fn refresh(&mut self, session_id: Uuid, now: DateTime<Utc>) -> SharedResult<&Session> {
+        self.expire(now);
+        let session = self
+            .sessions
+            .iter_mut()
+            .find(|s| s.session_id == session_id)
+            .ok_or_else(|| SharedError::NotFoundError(format!("Session {}", session_id)))?;
+        if let Some(reason) = session.end_reason {
+            return Err(SharedError::AuthenticationError(format!("Session ended: {:?}", reason)));
+        }
+        session.last_activity_at = now;
+        session.idle_warning_sent = false;
+        Ok(session)
+    }
+
+    /// Record a successful re-authentication, e.g. a fingerprint check before
+    /// opening a patient record. Also counts as activity.
+    pub fn record_step_up(&mut self, session_id: Uuid, method: StepUpMethod, now: DateTime<Utc>) -> SharedResult<()> {
+        self.refresh(session_id, now)?;
+        if let Some(session) = self.sessions.iter_mut().find(|s| s.session_id == session_id) {
+            session.stepped_up_at = Some(now);
+            session.step_up_method = Some(method);
+        }
+        Ok(())
+    }
+
+    /// Gate for sensitive screens: `Ok` with the method used if the user
+    /// re-authenticated recently, otherwise an authentication error telling
+    /// the UI to prompt for one
+    pub fn require_step_up(&self, session_id: Uuid, now: DateTime<Utc>) -> SharedResult<StepUpMethod> {
+        if let SessionState::Ended(reason) = self.state(session_id, now)? {
+            return Err(SharedError::AuthenticationError(format!("Session ended: {:?}", reason)));
+        }
+        self.session(session_id)
+            .filter(|session| session.has_recent_step_up(&self.policy, now))
+            .and_then(|session| session.step_up_method)
+            .ok_or_else(|| SharedError::AuthenticationError("Re-authentication required".to_string()))
+    }
+
+    pub fn state(&self, session_id: Uuid, now: DateTime<Utc>) -> SharedResult<SessionState> {
+        let session = self
+            .session(session_id)
+            .ok_or_else(|| SharedError::NotFoundError(format!("Session {}", session_id)))?;
+        if let Some(reason) = session.end_reason.or_else(|| session.timeout_reason(&self.policy, now)) {
+            return Ok(SessionState::Ended(reason));
+        }
+        let logout_at = session.logout_at(&self.policy);
+        if now >= logout_at - self.policy.idle_warning() {
+            Ok(SessionState::IdleWarning { logout_at })
+        } else {
+            Ok(SessionState::Active)
+        }
+    }
+
+    /// End timed-out sessions and warn those close to their idle logout; call
+    /// this from a periodic timer. Returns the sessions that ended.
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Vec<Uuid> {
+        let mut ended = Vec::new();
+        let mut warnings = Vec::new();
+        for session in self.sessions.iter().filter(|s| s.is_active()) {
+            if let Some(reason) = session.timeout_reason(&self.policy, now) {
+                ended.push((session.session_id, reason));
+            }
+        }
+        for session in self.sessions.iter_mut().filter(|s| s.is_active() && !s.idle_warning_sent) {
+            let logout_at = session.logout_at(&self.policy);
+            if now >= logout_at - self.policy.idle_warning() && now < logout_at {
+                session.idle_warning_sent = true;
+                warnings.push(SessionIdleWarning {
+                    session_id: session.session_id,
+                    user_id: session.user_id.clone(),
+                    logout_at,
+                });
+            }
+        }
+        for warning in warnings {
+            self.events.publish(warning);
+        }
+        ended
+            .into_iter()
+            .filter_map(|(session_id, reason)| self.end(session_id, reason, now).then_some(session_id))
+            .collect()
+    }
+
+    /// Sign out of one session
+    pub fn logout(&mut self, session_id: Uuid, now: DateTime<Utc>) -> SharedResult<()> {
+        self.end_existing(session_id, SessionEndReason::LoggedOut, now)
+    }
+
+    /// End a session from another device's session list
+    pub fn revoke(&mut self, session_id: Uuid, now: DateTime<Utc>) -> SharedResult<()> {
+        self.end_existing(session_id, SessionEndReason::Revoked, now)
+    }
+
+    /// "Sign out everywhere else": end all of the user's sessions except `keep`.
+    /// Returns how many were ended.
+    pub fn revoke_others(&mut self, user_id: &str, keep: Uuid, now: DateTime<Utc>) -> usize {
+        let others: Vec<Uuid> = self
+            .active_sessions(user_id)
+            .filter(|s| s.session_id != keep)
+            .map(|s| s.session_id)
+            .collect();
+        others
+            .into_iter()
+            .filter(|session_id| self.end(*session_id, SessionEndReason::Revoked, now))
+            .count()
+    }
+
+    /// End every session on a device, e.g. after the device was revoked.
+    /// Returns how many were ended.
+    pub fn revoke_device(&mut self, user_id: &str, device_id: &str, now: DateTime<Utc>) -> usize {
+        let on_device: Vec<Uuid> = self
+            .active_sessions(user_id)
+            .filter(|s| s.device.device_id == device_id)
+            .map(|s| s.session_id)
+            .collect();
+        on_device
+            .into_iter()
+            .filter(|session_id| self.end(*session_id, SessionEndReason::Revoked, now))
+            .count()
+    }
+
+    /// Active sessions for the device list, most recently used first
+    pub fn devices(&self, user_id: &str) -> Vec<&Session> {
+        let mut sessions: Vec<&Session> =
+            self.sessions.iter().filter(|s| s.is_active() && s.user_id == user_id).collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity_at));
+        sessions
+    }
+
+    /// Forget sessions that ended before `before`; returns how many were dropped
+    pub fn purge_ended(&mut self, before: DateTime<Utc>) -> usize {
+        let count = self.sessions.len();
+        self.sessions.retain(|s| s.ended_at.is_none_or(|ended_at| ended_at >= before));
+        count - self.sessions.len()
+    }
+
+    fn active_sessions<'a>(&'a self, user_id: &'a str) -> impl Iterator<Item = &'a Session> + 'a {
+        self.sessions.iter().filter(move |s| s.is_active() && s.user_id == user_id)
+    }
+
+    fn end_existing(&mut self, session_id: Uuid, reason: SessionEndReason, now: DateTime<Utc>) -> SharedResult<()> {
+        if self.session(session_id).is_none() {
+            return Err(SharedError::NotFoundError(format!("Session {}", session_id)));
+        }
+        self.end(session_id, reason, now);
+        Ok(())
+    }
+
+    /// Returns false if the session had already ended
+    fn end(&mut self, session_id: Uuid, reason: SessionEndReason, now: DateTime<Utc>) -> bool {
+        let Some(session) = self.sessions.iter_mut().find(|s| s.session_id == session_id && s.is_active()) else {
+            return false;
+        };
+        session.ended_at = Some(now);
+        session.end_reason = Some(reason);
+        let event = SessionEnded { session_id, user_id: session.user_id.clone(), reason };
+        let device = session.device.clone();
+        self.audit(
+            HipaaAction::Logout,
+            &event.user_id,
+            AuditOutcome::Success,
+            &device,
+            now,
+            serde_json::json!({ "session_id": session_id, "reason": reason }),
+        );
+        self.events.publish(event);
+        true
+    }
+
+    fn audit(
+        &self,
+        action: HipaaAction,
+        user_id: &str,
+        outcome: AuditOutcome,
+        device: &DeviceInfo,
+        now: DateTime<Utc>,
+        mut details: serde_json::Value,
+    ) {
+        let Some(sink) = &self.audit_sink else {
+            return;
+        };
+        details["device_id"] = device.device_id.clone().into();
+        let mut entry = HipaaAuditEntry::authentication(action, user_id, outcome)
+            .with_request_info(device.ip_address.clone(), device.user_agent.clone())
+            .with_details(&details);
+        entry.timestamp = now;
+        sink.record(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::hipaa::MemoryAuditSink;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_idle_timeout_signals_and_refresh() {
+        let bus = EventBus::new();
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let ended = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&warnings);
+        bus.subscribe(move |w: &SessionIdleWarning| sink.lock().unwrap().push(w.logout_at));
+        let sink = Arc::clone(&ended);
+        bus.subscribe(move |e: &SessionEnded| sink.lock().unwrap().push(e.reason));
+
+        let start = Utc::now();
+        let mut manager = SessionManager::new(SessionPolicy::default()).with_event_bus(bus);
+        let session = manager.create("p-1", Role::Patient, DeviceInfo::new("phone", "Pixel 8"), start).unwrap();
+        let id = session.session_id;
+
+        assert_eq!(manager.state(id, start + Duration::minutes(5)).unwrap(), SessionState::Active);
+        manager.expire(start + Duration::minutes(14));
+        manager.expire(start + Duration::minutes(14));
+        assert_eq!(*warnings.lock().unwrap(), vec![start + Duration::minutes(15)]);
+
+        // Activity pushes the logout back
+        manager.refresh(id, start + Duration::minutes(14)).unwrap();
+        assert!(manager.expire(start + Duration::minutes(20)).is_empty());
+        assert_eq!(manager.expire(start + Duration::minutes(29)), vec![id]);
+        assert_eq!(*ended.lock().unwrap(), vec![SessionEndReason::IdleTimeout]);
+        assert!(manager.refresh(id, start + Duration::minutes(30)).is_err());
+
+        // Constant activity still ends at the absolute timeout
+        let session = manager.create("p-1", Role::Patient, DeviceInfo::new("phone", "Pixel 8"), start).unwrap();
+        for minutes in (10..=12 * 60).step_by(10) {
+            let _ = manager.refresh(session.session_id, start + Duration::minutes(minutes));
+        }
+        assert_eq!(
+            manager.state(session.session_id, start + Duration::hours(12)).unwrap(),
+            SessionState::Ended(SessionEndReason::AbsoluteTimeout)
+        );
+    }
+
+    #[test]
+    fn test_devices_limits_and_revocation() {
+        let now = Utc::now();
+        let mut manager = SessionManager::new(SessionPolicy { max_concurrent_sessions: 2, ..SessionPolicy::default() });
+        let laptop = manager.create("dr-1", Role::Physician, DeviceInfo::new("laptop", "Clinic laptop"), now).unwrap();
+        let phone = manager
+            .create("dr-1", Role::Physician, DeviceInfo::new("phone", "iPhone"), now + Duration::minutes(1))
+            .unwrap();
+        manager.create("p-9", Role::Patient, DeviceInfo::new("tablet", "iPad"), now).unwrap();
+        let devices: Vec<&str> = manager.devices("dr-1").iter().map(|s| s.device.name.as_str()).collect();
+        assert_eq!(devices, vec!["iPhone", "Clinic laptop"]);
+
+        // A third device pushes out the least recently used one
+        let ward = manager
+            .create("dr-1", Role::Physician, DeviceInfo::new("ward-pc", "Ward PC"), now + Duration::minutes(2))
+            .unwrap();
+        assert_eq!(manager.session(laptop.session_id).unwrap().end_reason, Some(SessionEndReason::SessionLimit));
+
+        // Signing in again on the same device replaces its session
+        let phone_again = manager
+            .create("dr-1", Role::Physician, DeviceInfo::new("phone", "iPhone"), now + Duration::minutes(3))
+            .unwrap();
+        assert_eq!(manager.session(phone.session_id).unwrap().end_reason, Some(SessionEndReason::Replaced));
+        assert_eq!(manager.devices("dr-1").len(), 2);
+
+        assert_eq!(manager.revoke_others("dr-1", phone_again.session_id, now + Duration::minutes(4)), 1);
+        assert!(!manager.session(ward.session_id).unwrap().is_active());
+        assert_eq!(manager.devices("p-9").len(), 1);
+        assert_eq!(manager.revoke_device("dr-1", "tablet", now + Duration::minutes(4)), 0);
+        assert_eq!(manager.revoke_device("p-9", "tablet", now + Duration::minutes(4)), 1);
+
+        manager.logout(phone_again.session_id, now + Duration::minutes(5)).unwrap();
+        assert!(manager.devices("dr-1").is_empty());
+        assert_eq!(manager.purge_ended(now + Duration::minutes(10)), 5);
+        assert!(manager.revoke(Uuid::new_v4(), now).is_err());
+    }
+
+    #[test]
+    fn test_step_up_window() {
+        let now = Utc::now();
+        let mut manager = SessionManager::new(SessionPolicy::default());
+        let session = manager.create("dr-1", Role::Physician, DeviceInfo::new("phone", "iPhone"), now).unwrap();
+        let id = session.session_id;
+        assert!(manager.require_step_up(id, now).is_err());
+
+        manager.record_step_up(id, StepUpMethod::Biometric, now + Duration::minutes(1)).unwrap();
+        assert_eq!(manager.require_step_up(id, now + Duration::minutes(5)).unwrap(), StepUpMethod::Biometric);
+        assert!(manager.require_step_up(id, now + Duration::minutes(6)).is_err());
+
+        // Ended sessions can't be stepped up
+        manager.logout(id, now + Duration::minutes(7)).unwrap();
+        assert!(manager.record_step_up(id, StepUpMethod::Totp, now + Duration::minutes(7)).is_err());
+        assert!(manager.require_step_up(id, now + Duration::minutes(7)).is_err());
+        assert!(manager.require_step_up(Uuid::new_v4(), now).is_err());
+
+        let legacy: SessionPolicy = serde_json::from_str(
+            r#"{"idle_timeout_minutes":15,"idle_warning_minutes":2,"absolute_timeout_hours":12,"max_concurrent_sessions":3}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy, SessionPolicy::default());
+    }
+
+    #[test]
+    fn test_auth_events_are_audited() {
+        let now = Utc::now();
+        let sink = MemoryAuditSink::new();
+        let mut manager = SessionManager::new(SessionPolicy::default()).with_audit_sink(Arc::new(sink.clone()));
+        let user_id = Uuid::new_v4().to_string();
+        let device = DeviceInfo::new("phone", "iPhone").with_ip_address("203.0.113.7").with_user_agent("MyDR24/2.1 iOS");
+
+        manager.record_failed_login("nurse@example.com", &device, "invalid_password", now);
+        let session = manager.create(&user_id, Role::Nurse, device, now).unwrap();
+        manager.expire(now + Duration::minutes(20));
+
+        let entries = sink.entries();
+        let actions: Vec<HipaaAction> = entries.iter().map(|e| e.action.clone()).collect();
+        assert_eq!(actions, vec![HipaaAction::FailedLogin, HipaaAction::Login, HipaaAction::Logout]);
+        assert_eq!(entries[0].resource_id, "nurse@example.com");
+        assert_eq!(entries[0].user_id, None);
+        assert_eq!(entries[0].outcome, AuditOutcome::Failure);
+        assert_eq!(entries[1].user_id, Uuid::parse_str(&user_id).ok());
+        assert_eq!(entries[1].ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(entries[1].user_agent.as_deref(), Some("MyDR24/2.1 iOS"));
+        assert_eq!(entries[2].details["reason"], "idle_timeout");
+        assert_eq!(entries[2].details["session_id"], session.session_id.to_string());
+        assert_eq!(entries[2].timestamp, now + Duration::minutes(20));
+    }
+}
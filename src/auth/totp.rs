@@ -0,0 +1,428 @@
+//! TOTP (RFC 6238) multi-factor authentication, with single-use recovery codes
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, Rng, RngCore};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::api_backend::percent_encode;
+use crate::errors::{SharedError, SharedResult};
+
+/// Secret length recommended by RFC 4226 for HMAC-SHA1
+pub const TOTP_SECRET_BYTES: usize = 20;
+
+/// Recovery codes issued per enrollment
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+/// Unambiguous characters for recovery codes (no 0/O, 1/I/L)
+const RECOVERY_ALPHABET: &[u8] = b"23456789abcdefghjkmnpqrstuvwxyz";
+const RECOVERY_CODE_LENGTH: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TotpAlgorithm {
+    /// The RFC default, and the only one every authenticator app supports
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl TotpAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TotpAlgorithm::Sha1 => "SHA1",
+            TotpAlgorithm::Sha256 => "SHA256",
+            TotpAlgorithm::Sha512 => "SHA512",
+        }
+    }
+
+    fn mac(&self, key: &[u8], message: &[u8]) -> Vec<u8> {
+        fn run<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+            let mut mac = <M as hmac::digest::KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }
+        match self {
+            TotpAlgorithm::Sha1 => run::<Hmac<sha1::Sha1>>(key, message),
+            TotpAlgorithm::Sha256 => run::<Hmac<Sha256>>(key, message),
+            TotpAlgorithm::Sha512 => run::<Hmac<Sha512>>(key, message),
+        }
+    }
+}
+
+/// Code parameters. Most authenticator apps ignore anything but the
+/// defaults, so change them only for apps known to support it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TotpConfig {
+    pub algorithm: TotpAlgorithm,
+    pub digits: u32,
+    pub period_seconds: u32,
+    /// Steps accepted either side of the current one, for clock drift
+    pub drift_steps: u32,
+}
+
+impl Default for TotpConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: TotpAlgorithm::Sha1,
+            digits: 6,
+            period_seconds: 30,
+            drift_steps: 1,
+        }
+    }
+}
+
+impl TotpConfig {
+    pub fn validate(&self) -> SharedResult<()> {
+        if !(6..=8).contains(&self.digits) {
+            return Err(SharedError::ConfigurationError("TOTP codes must have 6 to 8 digits".to_string()));
+        }
+        if self.period_seconds == 0 {
+            return Err(SharedError::ConfigurationError("TOTP period must be at least 1 second".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Time step containing `at`
+    pub fn step_at(&self, at: DateTime<Utc>) -> u64 {
+        (at.timestamp().max(0) as u64) / u64::from(self.period_seconds.max(1))
+    }
+}
+
+/// Shared TOTP key. Stored and shown as unpadded base32; never logged and
+/// zeroed on drop.
+#[derive(Clone, PartialEq, Eq)]
+pub struct TotpSecret(Vec<u8>);
+
+impl TotpSecret {
+    pub fn generate() -> Self {
+        let mut bytes = vec![0u8; TOTP_SECRET_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Base32 as typed by users or scanned from QR codes; spaces and case are ignored
+    pub fn from_base32(encoded: &str) -> SharedResult<Self> {
+        let normalized: Zeroizing<String> = Zeroizing::new(
+            encoded
+                .chars()
+                .filter(|c| !c.is_whitespace() && *c != '=')
+                .map(|c| c.to_ascii_uppercase())
+                .collect(),
+        );
+        match base32::decode(base32::Alphabet::Rfc4648 { padding: false }, &normalized) {
+            Some(bytes) if bytes.len() >= 10 => Ok(Self(bytes)),
+            Some(_) => Err(SharedError::ValidationError("TOTP secret is too short".to_string())),
+            None => Err(SharedError::ValidationError("TOTP secret is not valid base32".to_string())),
+        }
+    }
+
+    pub fn to_base32(&self) -> String {
+        base32::encode(base32::Alphabet::Rfc4648 { padding: false }, &self.0)
+    }
+}
+
+impl Drop for TotpSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for TotpSecret {}
+
+impl fmt::Debug for TotpSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TotpSecret([REDACTED])")
+    }
+}
+
+impl Serialize for TotpSecret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_base32())
+    }
+}
+
+impl<'de> Deserialize<'de> for TotpSecret {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = Zeroizing::new(String::deserialize(deserializer)?);
+        TotpSecret::from_base32(&encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+/// What the enrollment screen needs: the `otpauth://` URI to render as a QR
+/// code, and the secret for users who type it in instead
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TotpProvisioning {
+    pub uri: String,
+    pub secret: String,
+}
+
+/// A TOTP generator/verifier for one account. Stored configs are validated
+/// on load, so a tampered `digits` can't overflow code generation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "StoredTotp")]
+pub struct Totp {
+    pub secret: TotpSecret,
+    pub config: TotpConfig,
+}
+
+#[derive(Deserialize)]
+struct StoredTotp {
+    secret: TotpSecret,
+    config: TotpConfig,
+}
+
+impl TryFrom<StoredTotp> for Totp {
+    type Error = SharedError;
+
+    fn try_from(stored: StoredTotp) -> SharedResult<Self> {
+        Totp::new(stored.secret, stored.config)
+    }
+}
+
+impl Totp {
+    pub fn new(secret: TotpSecret, config: TotpConfig) -> SharedResult<Self> {
+        config.validate()?;
+        Ok(Self { secret, config })
+    }
+
+    /// Fresh secret with default parameters
+    pub fn generate() -> Self {
+        Self { secret: TotpSecret::generate(), config: TotpConfig::default() }
+    }
+
+    /// RFC 4226 HOTP value for one time step
+    pub fn code_for_step(&self, step: u64) -> String {
+        let hash = self.config.algorithm.mac(&self.secret.0, &step.to_be_bytes());
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([hash[offset] & 0x7f, hash[offset + 1], hash[offset + 2], hash[offset + 3]]);
+        let code = binary % 10_u32.pow(self.config.digits);
+        format!("{:0width$}", code, width = self.config.digits as usize)
+    }
+
+    pub fn code_at(&self, at: DateTime<Utc>) -> String {
+        self.code_for_step(self.config.step_at(at))
+    }
+
+    /// Check `code` against the steps around `at`. Returns the matching step,
+    /// which callers store and pass back as `last_used_step` so a code can't
+    /// be replayed.
+    pub fn verify(&self, code: &str, at: DateTime<Utc>, last_used_step: Option<u64>) -> Option<u64> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() != self.config.digits as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let current = self.config.step_at(at);
+        let drift = u64::from(self.config.drift_steps);
+        (current.saturating_sub(drift)..=current.saturating_add(drift))
+            .filter(|step| last_used_step.is_none_or(|last| *step > last))
+            .find(|step| constant_time_eq(self.code_for_step(*step).as_bytes(), code.as_bytes()))
+    }
+
+    /// Key URI for authenticator apps, e.g.
+    /// `otpauth://totp/MyDR24:dr.rao%40example.com?secret=...&issuer=MyDR24`
+    pub fn provisioning_uri(&self, issuer: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm={}&digits={}&period={}",
+            percent_encode(issuer),
+            percent_encode(account),
+            self.secret.to_base32(),
+            percent_encode(issuer),
+            self.config.algorithm.as_str(),
+            self.config.digits,
+            self.config.period_seconds
+        )
+    }
+
+    pub fn provisioning(&self, issuer: &str, account: &str) -> TotpProvisioning {
+        TotpProvisioning {
+            uri: self.provisioning_uri(issuer, account),
+            secret: self.secret.to_base32(),
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Single-use codes for when the authenticator device is lost. Only salted
+/// hashes are stored; the plain codes are shown to the user once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecoveryCodes {
+    salt: String,
+    hashes: Vec<String>,
+}
+
+impl RecoveryCodes {
+    /// New codes to display, e.g. "k7mq2-x9rtb", and their stored form
+    pub fn generate(count: usize) -> (Vec<String>, Self) {
+        let mut rng = OsRng;
+        let mut salt = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        let mut stored = Self { salt: base32::encode(base32::Alphabet::Rfc4648Lower { padding: false }, &salt), hashes: Vec::new() };
+        let codes: Vec<String> = (0..count)
+            .map(|_| {
+                let raw: String = (0..RECOVERY_CODE_LENGTH)
+                    .map(|_| RECOVERY_ALPHABET[rng.gen_range(0..RECOVERY_ALPHABET.len())] as char)
+                    .collect();
+                format!("{}-{}", &raw[..RECOVERY_CODE_LENGTH / 2], &raw[RECOVERY_CODE_LENGTH / 2..])
+            })
+            .collect();
+        stored.hashes = codes.iter().map(|code| stored.hash(code)).collect();
+        (codes, stored)
+    }
+
+    fn hash(&self, code: &str) -> String {
+        let normalized: String = code
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let digest = Sha256::digest(format!("{}:{}", self.salt, normalized));
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Use up a code; false if it is wrong or was already used
+    pub fn redeem(&mut self, code: &str) -> bool {
+        let hash = self.hash(code);
+        match self.hashes.iter().position(|h| constant_time_eq(h.as_bytes(), hash.as_bytes())) {
+            Some(index) => {
+                self.hashes.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.hashes.len()
+    }
+}
+
+/// How a second factor was satisfied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MfaMethod {
+    Totp,
+    RecoveryCode,
+}
+
+/// A user's MFA setup, persisted with their account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MfaEnrollment {
+    pub totp: Totp,
+    pub recovery_codes: RecoveryCodes,
+    /// Set once the user has proved their app works by entering a code
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub last_used_step: Option<u64>,
+}
+
+impl MfaEnrollment {
+    /// Start enrollment; returns the recovery codes to show the user once
+    pub fn begin() -> (Self, Vec<String>) {
+        let (codes, recovery_codes) = RecoveryCodes::generate(RECOVERY_CODE_COUNT);
+        let enrollment = Self { totp: Totp::generate(), recovery_codes, confirmed_at: None, last_used_step: None };
+        (enrollment, codes)
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+
+    /// Finish enrollment with the first code from the user's app
+    pub fn confirm(&mut self, code: &str, now: DateTime<Utc>) -> SharedResult<()> {
+        let step = self
+            .totp
+            .verify(code, now, self.last_used_step)
+            .ok_or_else(|| SharedError::AuthenticationError("Invalid verification code".to_string()))?;
+        self.last_used_step = Some(step);
+        self.confirmed_at = Some(now);
+        Ok(())
+    }
+
+    /// Check a sign-in code, falling back to recovery codes
+    pub fn verify(&mut self, code: &str, now: DateTime<Utc>) -> SharedResult<MfaMethod> {
+        if !self.is_confirmed() {
+            return Err(SharedError::AuthenticationError("MFA enrollment not confirmed".to_string()));
+        }
+        if let Some(step) = self.totp.verify(code, now, self.last_used_step) {
+            self.last_used_step = Some(step);
+            return Ok(MfaMethod::Totp);
+        }
+        if self.recovery_codes.redeem(code) {
+            return Ok(MfaMethod::RecoveryCode);
+        }
+        Err(SharedError::AuthenticationError("Invalid verification code".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // RFC 6238 appendix B, 8 digits; each algorithm uses a key of its hash length
+        let seed = b"12345678901234567890";
+        let vectors: [(TotpAlgorithm, Vec<u8>, i64, &str); 4] = [
+            (TotpAlgorithm::Sha1, seed.to_vec(), 59, "94287082"),
+            (TotpAlgorithm::Sha1, seed.to_vec(), 1_111_111_109, "07081804"),
+            (TotpAlgorithm::Sha256, seed.repeat(2)[..32].to_vec(), 59, "46119246"),
+            (TotpAlgorithm::Sha512, seed.repeat(4)[..64].to_vec(), 20_000_000_000, "47863826"),
+        ];
+        for (algorithm, key, time, expected) in vectors {
+            let config = TotpConfig { algorithm, digits: 8, ..TotpConfig::default() };
+            let totp = Totp::new(TotpSecret(key), config).unwrap();
+            assert_eq!(totp.code_at(Utc.timestamp_opt(time, 0).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn test_verification_drift_replay_and_provisioning() {
+        let totp = Totp::generate();
+        let now = Utc::now();
+        let code = totp.code_at(now);
+        let step = totp.verify(&code, now, None).unwrap();
+        assert_eq!(totp.verify(&code, now + Duration::seconds(30), None), Some(step));
+        assert_eq!(totp.verify(&code, now + Duration::seconds(90), None), None);
+        assert_eq!(totp.verify(&code, now, Some(step)), None);
+        assert_eq!(totp.verify("12345", now, None), None);
+
+        let uri = totp.provisioning_uri("MyDR24", "dr.rao@example.com");
+        assert!(uri.starts_with("otpauth://totp/MyDR24:dr.rao%40example.com?secret="));
+        assert!(uri.ends_with("&issuer=MyDR24&algorithm=SHA1&digits=6&period=30"));
+        let secret = totp.provisioning("MyDR24", "dr.rao").secret;
+        assert_eq!(TotpSecret::from_base32(&secret.to_lowercase()).unwrap(), totp.secret);
+        assert_eq!(format!("{:?}", totp.secret), "TotpSecret([REDACTED])");
+    }
+
+    #[test]
+    fn test_enrollment_and_recovery_codes() {
+        let now = Utc::now();
+        let (mut enrollment, codes) = MfaEnrollment::begin();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert!(enrollment.verify(&enrollment.totp.code_at(now), now).is_err());
+        enrollment.confirm(&enrollment.totp.code_at(now), now).unwrap();
+
+        // The confirmation code can't be reused to sign in
+        let later = now + Duration::seconds(60);
+        assert!(enrollment.verify(&enrollment.totp.code_at(now), later).is_err());
+        assert_eq!(enrollment.verify(&enrollment.totp.code_at(later), later).unwrap(), MfaMethod::Totp);
+
+        assert_eq!(enrollment.verify(&codes[0].to_uppercase(), later).unwrap(), MfaMethod::RecoveryCode);
+        assert!(enrollment.verify(&codes[0], later).is_err());
+        assert_eq!(enrollment.recovery_codes.remaining(), RECOVERY_CODE_COUNT - 1);
+
+        // Stored form round-trips and holds no plain codes
+        let json = serde_json::to_string(&enrollment).unwrap();
+        assert!(!json.contains(&codes[1]));
+        let restored: MfaEnrollment = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, enrollment);
+        assert!(serde_json::from_str::<MfaEnrollment>(&json.replace(r#""digits":6"#, r#""digits":10"#)).is_err());
+    }
+}
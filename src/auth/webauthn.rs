@@ -0,0 +1,334 @@
+//! WebAuthn ceremony data for fingerprint and face unlock: server-issued options, browser-returned credentials and their client-side checks
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::session::StepUpMethod;
+use crate::errors::{SharedError, SharedResult};
+
+/// `type` recorded in the client data of a registration
+pub const WEBAUTHN_CREATE: &str = "webauthn.create";
+/// `type` recorded in the client data of an assertion
+pub const WEBAUTHN_GET: &str = "webauthn.get";
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const AUTHENTICATOR_DATA_MIN_LEN: usize = 37;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserVerification {
+    /// Fingerprint, face or device PIN must be checked; used for step-up
+    #[default]
+    Required,
+    Preferred,
+    Discouraged,
+}
+
+impl UserVerification {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserVerification::Required => "required",
+            UserVerification::Preferred => "preferred",
+            UserVerification::Discouraged => "discouraged",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuthenticatorAttachment {
+    /// Built into the device: Touch ID, Windows Hello, Android biometrics
+    Platform,
+    /// A separate key, e.g. USB or NFC
+    CrossPlatform,
+}
+
+impl AuthenticatorAttachment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthenticatorAttachment::Platform => "platform",
+            AuthenticatorAttachment::CrossPlatform => "cross-platform",
+        }
+    }
+
+    pub fn step_up_method(&self) -> StepUpMethod {
+        match self {
+            AuthenticatorAttachment::Platform => StepUpMethod::Biometric,
+            AuthenticatorAttachment::CrossPlatform => StepUpMethod::SecurityKey,
+        }
+    }
+}
+
+/// Options for registering a new credential, issued by the server. Binary
+/// values are base64url without padding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebAuthnCreationOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    pub rp_name: String,
+    pub user_id: String,
+    pub user_name: String,
+    pub user_display_name: String,
+    /// Credentials the user already has, so the same authenticator isn't registered twice
+    #[serde(default)]
+    pub exclude_credentials: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authenticator_attachment: Option<AuthenticatorAttachment>,
+    #[serde(default)]
+    pub user_verification: UserVerification,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u32>,
+}
+
+/// Options for proving possession of a registered credential, issued by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebAuthnRequestOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    #[serde(default)]
+    pub allow_credentials: Vec<String>,
+    #[serde(default)]
+    pub user_verification: UserVerification,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u32>,
+}
+
+/// A new credential as returned by `navigator.credentials.create`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebAuthnAttestation {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub attestation_object: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authenticator_attachment: Option<AuthenticatorAttachment>,
+}
+
+/// A signed challenge as returned by `navigator.credentials.get`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebAuthnAssertion {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_handle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authenticator_attachment: Option<AuthenticatorAttachment>,
+}
+
+/// A credential registered to a user, as listed on the security settings screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebAuthnCredential {
+    pub credential_id: String,
+    pub user_id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authenticator_attachment: Option<AuthenticatorAttachment>,
+    #[serde(default)]
+    pub sign_count: u32,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// The browser's record of what it signed (`clientDataJSON`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollectedClientData {
+    #[serde(rename = "type")]
+    pub ceremony: String,
+    pub challenge: String,
+    pub origin: String,
+    #[serde(default, rename = "crossOrigin")]
+    pub cross_origin: bool,
+}
+
+fn decode(field: &str, value: &str) -> SharedResult<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| SharedError::ValidationError(format!("WebAuthn {} is not base64url", field)))
+}
+
+impl CollectedClientData {
+    pub fn parse(client_data_json: &str) -> SharedResult<Self> {
+        Ok(serde_json::from_slice(&decode("client data", client_data_json)?)?)
+    }
+
+    /// Check the ceremony type, that the browser signed the server's challenge,
+    /// and that it did so for our origin rather than a look-alike site
+    pub fn verify(&self, ceremony: &str, challenge: &str, origin: &str) -> SharedResult<()> {
+        if self.ceremony != ceremony {
+            return Err(SharedError::AuthenticationError(format!("Expected a {} response", ceremony)));
+        }
+        if self.challenge.trim_end_matches('=') != challenge.trim_end_matches('=') {
+            return Err(SharedError::AuthenticationError("WebAuthn challenge mismatch".to_string()));
+        }
+        if self.origin != origin.trim_end_matches('/') || self.cross_origin {
+            return Err(SharedError::AuthenticationError(format!("WebAuthn response from unexpected origin {}", self.origin)));
+        }
+        Ok(())
+    }
+}
+
+/// The fixed-size prefix of an authenticator's data (WebAuthn §6.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatorData {
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+}
+
+impl AuthenticatorData {
+    pub fn parse(bytes: &[u8]) -> SharedResult<Self> {
+        if bytes.len() < AUTHENTICATOR_DATA_MIN_LEN {
+            return Err(SharedError::ValidationError("Authenticator data is too short".to_string()));
+        }
+        let mut rp_id_hash = [0u8; 32];
+        rp_id_hash.copy_from_slice(&bytes[..32]);
+        let sign_count = u32::from_be_bytes([bytes[33], bytes[34], bytes[35], bytes[36]]);
+        Ok(Self { rp_id_hash, flags: bytes[32], sign_count })
+    }
+
+    pub fn user_present(&self) -> bool {
+        self.flags & FLAG_USER_PRESENT != 0
+    }
+
+    /// The authenticator checked a fingerprint, face or PIN, not just a tap
+    pub fn user_verified(&self) -> bool {
+        self.flags & FLAG_USER_VERIFIED != 0
+    }
+
+    pub fn matches_rp_id(&self, rp_id: &str) -> bool {
+        self.rp_id_hash[..] == Sha256::digest(rp_id.as_bytes())[..]
+    }
+}
+
+impl WebAuthnAttestation {
+    pub fn client_data(&self) -> SharedResult<CollectedClientData> {
+        CollectedClientData::parse(&self.client_data_json)
+    }
+
+    /// Sanity-check a registration before sending it; the server verifies the
+    /// attestation itself
+    pub fn check(&self, options: &WebAuthnCreationOptions, origin: &str) -> SharedResult<()> {
+        self.client_data()?.verify(WEBAUTHN_CREATE, &options.challenge, origin)
+    }
+}
+
+impl WebAuthnAssertion {
+    pub fn client_data(&self) -> SharedResult<CollectedClientData> {
+        CollectedClientData::parse(&self.client_data_json)
+    }
+
+    pub fn authenticator_data(&self) -> SharedResult<AuthenticatorData> {
+        AuthenticatorData::parse(&decode("authenticator data", &self.authenticator_data)?)
+    }
+
+    /// Check everything that can be checked without the credential's public
+    /// key: challenge, origin, relying party and user presence/verification.
+    /// The server still verifies the signature before trusting the assertion.
+    pub fn check(&self, options: &WebAuthnRequestOptions, origin: &str) -> SharedResult<AuthenticatorData> {
+        self.client_data()?.verify(WEBAUTHN_GET, &options.challenge, origin)?;
+        if !options.allow_credentials.is_empty() && !options.allow_credentials.contains(&self.credential_id) {
+            return Err(SharedError::AuthenticationError("Credential was not offered for this sign-in".to_string()));
+        }
+        let data = self.authenticator_data()?;
+        if !data.matches_rp_id(&options.rp_id) {
+            return Err(SharedError::AuthenticationError("Assertion is for a different relying party".to_string()));
+        }
+        if !data.user_present() {
+            return Err(SharedError::AuthenticationError("Authenticator did not confirm user presence".to_string()));
+        }
+        if options.user_verification == UserVerification::Required && !data.user_verified() {
+            return Err(SharedError::AuthenticationError("Authenticator did not verify the user".to_string()));
+        }
+        Ok(data)
+    }
+
+    /// How this assertion should be recorded as a session step-up
+    pub fn step_up_method(&self) -> StepUpMethod {
+        self.authenticator_attachment
+            .map(|attachment| attachment.step_up_method())
+            .unwrap_or(StepUpMethod::SecurityKey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ORIGIN: &str = "https://app.mydr24.com";
+
+    fn b64(bytes: impl AsRef<[u8]>) -> String {
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    fn assertion(ceremony: &str, challenge: &str, rp_id: &str, flags: u8) -> WebAuthnAssertion {
+        let client_data = json!({ "type": ceremony, "challenge": challenge, "origin": ORIGIN });
+        let mut authenticator_data = Sha256::digest(rp_id.as_bytes()).to_vec();
+        authenticator_data.push(flags);
+        authenticator_data.extend_from_slice(&7u32.to_be_bytes());
+        WebAuthnAssertion {
+            credential_id: "cred-1".to_string(),
+            client_data_json: b64(client_data.to_string()),
+            authenticator_data: b64(authenticator_data),
+            signature: b64("sig"),
+            user_handle: None,
+            authenticator_attachment: Some(AuthenticatorAttachment::Platform),
+        }
+    }
+
+    #[test]
+    fn test_assertion_checks() {
+        let options: WebAuthnRequestOptions = serde_json::from_value(json!({
+            "challenge": "c2VydmVyLWNoYWxsZW5nZQ", "rp_id": "mydr24.com", "allow_credentials": ["cred-1"],
+        }))
+        .unwrap();
+        assert_eq!(options.user_verification, UserVerification::Required);
+
+        let good = assertion(WEBAUTHN_GET, &options.challenge, "mydr24.com", FLAG_USER_PRESENT | FLAG_USER_VERIFIED);
+        let data = good.check(&options, ORIGIN).unwrap();
+        assert_eq!(data.sign_count, 7);
+        assert_eq!(good.step_up_method(), StepUpMethod::Biometric);
+
+        // Tap without fingerprint/face, another site, a replayed challenge or a registration response
+        let tap_only = assertion(WEBAUTHN_GET, &options.challenge, "mydr24.com", FLAG_USER_PRESENT);
+        assert!(tap_only.check(&options, ORIGIN).is_err());
+        let preferred = WebAuthnRequestOptions { user_verification: UserVerification::Preferred, ..options.clone() };
+        assert!(tap_only.check(&preferred, ORIGIN).is_ok());
+        assert!(good.check(&options, "https://mydr24.co").is_err());
+        let phishing = assertion(WEBAUTHN_GET, &options.challenge, "evil.example", 0x05);
+        assert!(phishing.check(&options, ORIGIN).is_err());
+        assert!(assertion(WEBAUTHN_GET, "b2xk", "mydr24.com", 0x05).check(&options, ORIGIN).is_err());
+        assert!(assertion(WEBAUTHN_CREATE, &options.challenge, "mydr24.com", 0x05).check(&options, ORIGIN).is_err());
+
+        let unlisted = WebAuthnRequestOptions { allow_credentials: vec!["cred-2".to_string()], ..options };
+        assert!(good.check(&unlisted, ORIGIN).is_err());
+        assert!(AuthenticatorData::parse(&[0u8; 36]).is_err());
+    }
+
+    #[test]
+    fn test_registration_check() {
+        let options: WebAuthnCreationOptions = serde_json::from_value(json!({
+            "challenge": "cmVnLWNoYWxsZW5nZQ", "rp_id": "mydr24.com", "rp_name": "MyDR24",
+            "user_id": "dXNlci0x", "user_name": "asha@example.com", "user_display_name": "Asha",
+            "authenticator_attachment": "platform",
+        }))
+        .unwrap();
+        let client_data = json!({ "type": WEBAUTHN_CREATE, "challenge": options.challenge, "origin": ORIGIN });
+        let attestation = WebAuthnAttestation {
+            credential_id: "cred-1".to_string(),
+            client_data_json: b64(client_data.to_string()),
+            attestation_object: b64("cbor"),
+            authenticator_attachment: Some(AuthenticatorAttachment::Platform),
+        };
+        assert!(attestation.check(&options, ORIGIN).is_ok());
+        assert!(attestation.check(&options, "https://attacker.example").is_err());
+        let other = WebAuthnCreationOptions { challenge: "b3RoZXI".to_string(), ..options };
+        assert!(attestation.check(&other, ORIGIN).is_err());
+        assert_eq!(serde_json::to_value(AuthenticatorAttachment::CrossPlatform).unwrap(), "cross-platform");
+    }
+}
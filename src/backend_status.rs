@@ -0,0 +1,167 @@
+//! Backend reachability tracking for health-check polling and status indicators
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Coarse backend health, as shown by the layout's status indicator
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum BackendHealth {
+    /// No check has completed yet
+    #[default]
+    Unknown,
+    Healthy,
+    Degraded,
+    Down,
+}
+
+impl BackendHealth {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackendHealth::Unknown => "unknown",
+            BackendHealth::Healthy => "healthy",
+            BackendHealth::Degraded => "degraded",
+            BackendHealth::Down => "down",
+        }
+    }
+}
+
+/// Thresholds and cadence for the health poller
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthPollConfig {
+    pub interval_ms: u32,
+    /// Successful checks slower than this count as degraded
+    pub degraded_latency_ms: f64,
+    /// Consecutive failed checks before reporting the backend as down
+    pub down_after_failures: u32,
+}
+
+impl Default for HealthPollConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 30_000,
+            degraded_latency_ms: 1_500.0,
+            down_after_failures: 2,
+        }
+    }
+}
+
+/// Result of a single health check
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthCheckOutcome {
+    Reachable {
+        server_status: String,
+        version: Option<String>,
+        latency_ms: f64,
+    },
+    Unreachable {
+        error: String,
+        latency_ms: f64,
+    },
+}
+
+/// Latest known backend status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct BackendStatus {
+    pub health: BackendHealth,
+    pub latency_ms: Option<f64>,
+    pub server_version: Option<String>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+impl BackendStatus {
+    /// Fold a new check into the status; a single failure only degrades until the threshold is hit
+    pub fn next(&self, outcome: HealthCheckOutcome, config: &HealthPollConfig) -> Self {
+        match outcome {
+            HealthCheckOutcome::Reachable { server_status, version, latency_ms } => {
+                let reported_healthy = matches!(server_status.to_lowercase().as_str(), "ok" | "healthy" | "up");
+                let health = if reported_healthy && latency_ms <= config.degraded_latency_ms {
+                    BackendHealth::Healthy
+                } else {
+                    BackendHealth::Degraded
+                };
+
+                Self {
+                    health,
+                    latency_ms: Some(latency_ms),
+                    server_version: version.or_else(|| self.server_version.clone()),
+                    consecutive_failures: 0,
+                    last_error: None,
+                    checked_at: Some(Utc::now()),
+                }
+            }
+            HealthCheckOutcome::Unreachable { error, latency_ms } => {
+                let consecutive_failures = self.consecutive_failures + 1;
+                let health = if consecutive_failures >= config.down_after_failures {
+                    BackendHealth::Down
+                } else {
+                    BackendHealth::Degraded
+                };
+
+                Self {
+                    health,
+                    latency_ms: Some(latency_ms),
+                    server_version: self.server_version.clone(),
+                    consecutive_failures,
+                    last_error: Some(error),
+                    checked_at: Some(Utc::now()),
+                }
+            }
+        }
+    }
+
+    /// Short human-readable summary, e.g. for a tooltip
+    pub fn summary(&self) -> String {
+        match (self.health, self.latency_ms) {
+            (BackendHealth::Unknown, _) => "Checking backend status...".to_string(),
+            (BackendHealth::Down, _) => format!(
+                "Backend unreachable{}",
+                self.last_error.as_ref().map(|e| format!(": {}", e)).unwrap_or_default()
+            ),
+            (health, Some(latency)) => format!("Backend {} ({:.0} ms)", health.as_str(), latency),
+            (health, None) => format!("Backend {}", health.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reachable(latency_ms: f64) -> HealthCheckOutcome {
+        HealthCheckOutcome::Reachable {
+            server_status: "ok".to_string(),
+            version: Some("1.4.0".to_string()),
+            latency_ms,
+        }
+    }
+
+    fn unreachable() -> HealthCheckOutcome {
+        HealthCheckOutcome::Unreachable { error: "timeout".to_string(), latency_ms: 10_000.0 }
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        let config = HealthPollConfig::default();
+        let status = BackendStatus::default().next(reachable(120.0), &config);
+        assert_eq!(status.health, BackendHealth::Healthy);
+
+        let status = status.next(unreachable(), &config);
+        assert_eq!(status.health, BackendHealth::Degraded);
+        let status = status.next(unreachable(), &config);
+        assert_eq!(status.health, BackendHealth::Down);
+        assert_eq!(status.server_version.as_deref(), Some("1.4.0"));
+
+        let status = status.next(reachable(120.0), &config);
+        assert_eq!(status.health, BackendHealth::Healthy);
+        assert_eq!(status.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_slow_response_is_degraded() {
+        let status = BackendStatus::default().next(reachable(4_000.0), &HealthPollConfig::default());
+        assert_eq!(status.health, BackendHealth::Degraded);
+        assert_eq!(status.summary(), "Backend degraded (4000 ms)");
+    }
+}
@@ -0,0 +1,378 @@
+//! Care plans for home care coordination (SC-005): goals, the tasks that serve them, and progress roll-ups
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{SharedError, SharedResult};
+use crate::healthcare_service_engine::ServiceCategory;
+use crate::ids::{PatientId, ProviderId};
+
+/// Who is responsible for a care task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CareAssignee {
+    Patient,
+    Provider { provider_id: ProviderId },
+    /// Family member or other informal caregiver
+    Caregiver { name: String, relationship: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CareTaskStatus {
+    Pending,
+    InProgress,
+    Completed,
+    /// Deliberately not done this time, e.g. the patient was in hospital
+    Skipped,
+    Cancelled,
+}
+
+impl CareTaskStatus {
+    pub fn is_open(&self) -> bool {
+        matches!(self, CareTaskStatus::Pending | CareTaskStatus::InProgress)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CareTask {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub assignee: CareAssignee,
+    pub due_at: Option<DateTime<Utc>>,
+    pub status: CareTaskStatus,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Name of whoever marked the task done
+    pub completed_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl CareTask {
+    pub fn new(id: impl Into<String>, title: impl Into<String>, assignee: CareAssignee) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            description: None,
+            assignee,
+            due_at: None,
+            status: CareTaskStatus::Pending,
+            completed_at: None,
+            completed_by: None,
+            notes: None,
+        }
+    }
+
+    pub fn due(mut self, due_at: DateTime<Utc>) -> Self {
+        self.due_at = Some(due_at);
+        self
+    }
+
+    pub fn complete(&mut self, at: DateTime<Utc>, by: &str) {
+        self.status = CareTaskStatus::Completed;
+        self.completed_at = Some(at);
+        self.completed_by = Some(by.to_string());
+    }
+
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.status.is_open() && self.due_at.is_some_and(|due| due < now)
+    }
+
+    /// Cancelled tasks no longer count toward progress; skipped ones count as not done
+    fn counts_toward_progress(&self) -> bool {
+        self.status != CareTaskStatus::Cancelled
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CareGoalStatus {
+    Active,
+    Achieved,
+    Abandoned,
+}
+
+/// Measurable target for a goal, e.g. walk 500 m unaided
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoalMeasure {
+    pub unit: String,
+    pub baseline: f64,
+    pub target: f64,
+    pub current: f64,
+}
+
+impl GoalMeasure {
+    /// Share of the way from baseline to target, 0.0 to 1.0; works for decreasing targets too
+    pub fn progress(&self) -> f64 {
+        let span = self.target - self.baseline;
+        if span == 0.0 {
+            return if self.current == self.target { 1.0 } else { 0.0 };
+        }
+        ((self.current - self.baseline) / span).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CareGoal {
+    pub id: String,
+    pub description: String,
+    pub target_date: Option<NaiveDate>,
+    pub status: CareGoalStatus,
+    pub measure: Option<GoalMeasure>,
+    #[serde(default)]
+    pub tasks: Vec<CareTask>,
+}
+
+impl CareGoal {
+    pub fn new(id: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            description: description.into(),
+            target_date: None,
+            status: CareGoalStatus::Active,
+            measure: None,
+            tasks: Vec::new(),
+        }
+    }
+
+    pub fn with_measure(mut self, measure: GoalMeasure) -> Self {
+        self.measure = Some(measure);
+        self
+    }
+
+    pub fn with_task(mut self, task: CareTask) -> Self {
+        self.tasks.push(task);
+        self
+    }
+
+    /// 0.0 to 1.0: from the measure when there is one, otherwise the share of tasks completed
+    pub fn progress(&self) -> f64 {
+        match (self.status, &self.measure) {
+            (CareGoalStatus::Achieved, _) => 1.0,
+            (_, Some(measure)) => measure.progress(),
+            _ => task_progress(&self.tasks).fraction(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CarePlanStatus {
+    Draft,
+    Active,
+    OnHold,
+    Completed,
+    Cancelled,
+}
+
+/// Task counts for progress bars and summaries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CareProgress {
+    /// Tasks that count toward progress (not cancelled)
+    pub total: usize,
+    pub completed: usize,
+    pub open: usize,
+    pub overdue: usize,
+}
+
+impl CareProgress {
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+
+    /// Whole percent, for progress bars
+    pub fn percent(&self) -> u8 {
+        (self.fraction() * 100.0).round() as u8
+    }
+}
+
+fn task_progress<'a>(tasks: impl IntoIterator<Item = &'a CareTask>) -> CareProgress {
+    task_progress_at(tasks, None)
+}
+
+fn task_progress_at<'a>(tasks: impl IntoIterator<Item = &'a CareTask>, now: Option<DateTime<Utc>>) -> CareProgress {
+    let mut progress = CareProgress::default();
+    for task in tasks.into_iter().filter(|t| t.counts_toward_progress()) {
+        progress.total += 1;
+        if task.status == CareTaskStatus::Completed {
+            progress.completed += 1;
+        }
+        if task.status.is_open() {
+            progress.open += 1;
+        }
+        if now.is_some_and(|now| task.is_overdue(now)) {
+            progress.overdue += 1;
+        }
+    }
+    progress
+}
+
+/// A patient's home care plan, owned by a coordinating provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CarePlan {
+    pub id: String,
+    pub patient_id: PatientId,
+    pub title: String,
+    pub service_category: ServiceCategory,
+    pub coordinator_id: Option<ProviderId>,
+    pub status: CarePlanStatus,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub goals: Vec<CareGoal>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CarePlan {
+    /// New draft home care plan starting on `start_date`
+    pub fn new(id: impl Into<String>, patient_id: PatientId, title: impl Into<String>, start_date: NaiveDate) -> Self {
+        let now = Utc::now();
+        Self {
+            id: id.into(),
+            patient_id,
+            title: title.into(),
+            service_category: ServiceCategory::HomeCareServices,
+            coordinator_id: None,
+            status: CarePlanStatus::Draft,
+            start_date,
+            end_date: None,
+            goals: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn with_goal(mut self, goal: CareGoal) -> Self {
+        self.goals.push(goal);
+        self
+    }
+
+    pub fn tasks(&self) -> impl Iterator<Item = &CareTask> {
+        self.goals.iter().flat_map(|goal| goal.tasks.iter())
+    }
+
+    pub fn tasks_for<'a>(&'a self, assignee: &'a CareAssignee) -> impl Iterator<Item = &'a CareTask> {
+        self.tasks().filter(move |task| &task.assignee == assignee)
+    }
+
+    pub fn overdue_tasks(&self, now: DateTime<Utc>) -> Vec<&CareTask> {
+        self.tasks().filter(|task| task.is_overdue(now)).collect()
+    }
+
+    /// Open tasks with a due time, soonest first
+    pub fn upcoming_tasks(&self) -> Vec<&CareTask> {
+        let mut tasks: Vec<_> = self.tasks().filter(|t| t.status.is_open() && t.due_at.is_some()).collect();
+        tasks.sort_by_key(|t| t.due_at);
+        tasks
+    }
+
+    /// Task counts across all goals as of `now`
+    pub fn task_progress(&self, now: DateTime<Utc>) -> CareProgress {
+        task_progress_at(self.tasks(), Some(now))
+    }
+
+    /// Mean progress of the goals still being pursued, 0.0 to 1.0
+    pub fn goal_progress(&self) -> f64 {
+        let goals: Vec<f64> = self
+            .goals
+            .iter()
+            .filter(|goal| goal.status != CareGoalStatus::Abandoned)
+            .map(CareGoal::progress)
+            .collect();
+        if goals.is_empty() {
+            0.0
+        } else {
+            goals.iter().sum::<f64>() / goals.len() as f64
+        }
+    }
+
+    pub fn task_mut(&mut self, task_id: &str) -> Option<&mut CareTask> {
+        self.goals
+            .iter_mut()
+            .flat_map(|goal| goal.tasks.iter_mut())
+            .find(|task| task.id == task_id)
+    }
+
+    /// Mark a task done and bump `updated_at`
+    pub fn complete_task(&mut self, task_id: &str, at: DateTime<Utc>, by: &str) -> SharedResult<()> {
+        let task = self
+            .task_mut(task_id)
+            .ok_or_else(|| SharedError::NotFoundError(format!("Care task {} not in plan", task_id)))?;
+        if !task.status.is_open() {
+            return Err(SharedError::ValidationError(format!("Care task {} is already closed", task_id)));
+        }
+        task.complete(at, by);
+        self.updated_at = at;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn plan(now: DateTime<Utc>) -> CarePlan {
+        let daughter = CareAssignee::Caregiver { name: "Asha".to_string(), relationship: "daughter".to_string() };
+        let nurse = CareAssignee::Provider { provider_id: ProviderId::new("nurse-1") };
+        CarePlan::new("cp-1", PatientId::new("pat-1"), "Post-surgery recovery", now.date_naive())
+            .with_goal(
+                CareGoal::new("g-1", "Wound heals without infection")
+                    .with_task(CareTask::new("t-1", "Dressing change", nurse.clone()).due(now - Duration::hours(2)))
+                    .with_task(CareTask::new("t-2", "Dressing change", nurse).due(now + Duration::days(2)))
+                    .with_task(CareTask::new("t-3", "Check temperature", daughter.clone()).due(now - Duration::hours(1))),
+            )
+            .with_goal(
+                CareGoal::new("g-2", "Walk 500 m unaided").with_measure(GoalMeasure {
+                    unit: "m".to_string(),
+                    baseline: 100.0,
+                    target: 500.0,
+                    current: 300.0,
+                }),
+            )
+    }
+
+    #[test]
+    fn test_progress_aggregation() {
+        let now = Utc.with_ymd_and_hms(2026, 5, 4, 10, 0, 0).unwrap();
+        let mut plan = plan(now);
+
+        let progress = plan.task_progress(now);
+        assert_eq!((progress.total, progress.completed, progress.overdue), (3, 0, 2));
+        assert_eq!(plan.goal_progress(), 0.25);
+
+        plan.complete_task("t-1", now, "Nurse Rekha").unwrap();
+        plan.task_mut("t-3").unwrap().status = CareTaskStatus::Cancelled;
+        let progress = plan.task_progress(now);
+        assert_eq!((progress.total, progress.completed, progress.overdue), (2, 1, 0));
+        assert_eq!(progress.percent(), 50);
+        assert_eq!(plan.goals[0].progress(), 0.5);
+        assert!(plan.complete_task("t-1", now, "Nurse Rekha").is_err());
+        assert!(plan.complete_task("t-9", now, "Nurse Rekha").is_err());
+
+        let upcoming = plan.upcoming_tasks();
+        assert_eq!(upcoming.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), vec!["t-2"]);
+
+        // Decreasing targets, e.g. pain score from 8 down to 2
+        let pain = GoalMeasure { unit: "score".to_string(), baseline: 8.0, target: 2.0, current: 5.0 };
+        assert_eq!(pain.progress(), 0.5);
+    }
+
+    #[test]
+    fn test_care_plan_serialization() {
+        let plan = plan(Utc::now());
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["goals"][0]["tasks"][2]["assignee"]["kind"], "caregiver");
+        assert_eq!(json["goals"][0]["tasks"][0]["status"], "pending");
+        assert_eq!(serde_json::from_value::<CarePlan>(json).unwrap(), plan);
+
+        let daughter = CareAssignee::Caregiver { name: "Asha".to_string(), relationship: "daughter".to_string() };
+        assert_eq!(plan.tasks_for(&daughter).count(), 1);
+    }
+}
@@ -3,6 +3,7 @@
 
 use leptos::prelude::*;
 use crate::{WebSocketClient, ChatMessage};
+use crate::ui::NotificationIndicator;
 use uuid::Uuid;
 use chrono::Utc;
 
@@ -302,22 +303,8 @@ pub fn ChatNotificationBadge(
     };
     
     view! {
-        {move || {
-            let count = unread_count();
-            if count > 0 {
-                view! {
-                    <div class="relative inline-flex">
-                        <span class="absolute -top-2 -right-2 bg-red-500 text-white text-xs rounded-full h-5 w-5 flex items-center justify-center">
-                            {if count > 9 { "9+" } else { &count.to_string() }}
-                        </span>
-                        <span class="text-2xl">💬</span>
-                    </div>
-                }.into_view()
-            } else {
-                view! {
-                    <span class="text-2xl opacity-50">💬</span>
-                }.into_view()
-            }
-        }}
+        <NotificationIndicator count=Signal::derive(move || unread_count() as u32) max=9 label="unread messages">
+            <span class="text-2xl">💬</span>
+        </NotificationIndicator>
     }
 }
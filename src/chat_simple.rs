@@ -3,36 +3,67 @@
 
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
-use uuid::Uuid;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use web_sys::{console, window};
+use crate::auth::{ChatKeyRing, Role};
+#[cfg(feature = "post-quantum")]
+use crate::auth::{ChatKeyEnvelope, HybridKeyPair, HybridPublicKeyBundle, PQAuthentication};
+use crate::ids::{BookingId, PatientId, ProviderId};
 use crate::websocket_simple::{SimpleWebSocketClient, ChatMessage, MessageType, create_chat_message};
-use base64::{Engine as _, engine::general_purpose};
 
+// One chat per booking, under the chat_id the server assigned it
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatSession {
     pub chat_id: String,
-    pub patient_id: String,
-    pub provider_id: String,
+    pub booking_id: BookingId,
+    pub patient_id: PatientId,
+    pub provider_id: ProviderId,
     pub messages: Vec<ChatMessage>,
     pub is_active: bool,
+    // False in builds without key exchange; show `security_label_key` so users know
+    #[serde(default)]
+    pub end_to_end_encrypted: bool,
     pub created_at: DateTime<Utc>,
     pub last_activity: DateTime<Utc>,
 }
 
+impl ChatSession {
+    // Translation key for the chat header, e.g. for `ChatSecurityBadge`
+    pub fn security_label_key(&self) -> &'static str {
+        if self.end_to_end_encrypted {
+            "chat.security.end_to_end"
+        } else {
+            "chat.security.transport_only"
+        }
+    }
+}
+
+// Chat keys are exchanged over the post-quantum hybrid KEM; without it chats
+// fall back to TLS to the relay only, and are labelled as such
+pub const CHAT_END_TO_END_AVAILABLE: bool = cfg!(feature = "post-quantum");
+
+// End-to-end chat keys by booking
+pub type ChatKeyStore = Arc<Mutex<HashMap<BookingId, ChatKeyRing>>>;
+
+// Chats by chat_id, shared with the WebSocket callback so incoming messages land in them
+pub type ChatSessionStore = Arc<Mutex<HashMap<String, ChatSession>>>;
+
 pub struct SimpleChatManager {
     pub current_user_id: String,
-    pub current_user_role: String, // "patient" or "provider"
-    pub active_sessions: HashMap<String, ChatSession>,
+    pub current_user_role: Role,
+    pub active_sessions: ChatSessionStore,
+    pub chat_keys: ChatKeyStore,
     pub websocket_client: Option<SimpleWebSocketClient>,
 }
 
 impl SimpleChatManager {
-    pub fn new(user_id: String, user_role: String) -> Self {
+    pub fn new(user_id: String, user_role: Role) -> Self {
         Self {
             current_user_id: user_id,
             current_user_role: user_role,
-            active_sessions: HashMap::new(),
+            active_sessions: Arc::new(Mutex::new(HashMap::new())),
+            chat_keys: Arc::new(Mutex::new(HashMap::new())),
             websocket_client: None,
         }
     }
@@ -40,14 +71,15 @@ impl SimpleChatManager {
     pub fn set_websocket_client(&mut self, client: SimpleWebSocketClient) {
         // Register for chat message callbacks
         client.on_message(MessageType::ChatMessage, {
-            let user_id = self.current_user_id.clone();
+            let sessions = Arc::clone(&self.active_sessions);
+            let chat_keys = Arc::clone(&self.chat_keys);
             move |message| {
-                console::log_1(&format!("Received chat message: {:?}", message).into());
-                // Handle incoming chat message
                 if let Ok(chat_msg) = serde_json::from_value::<ChatMessage>(message.payload) {
-                    // Update UI or store message
-                    console::log_1(&format!("Chat message from {}: {}", 
-                        chat_msg.sender_id, chat_msg.content).into());
+                    let message_id = chat_msg.message_id.clone();
+                    // Never log the decrypted content
+                    if let Err(e) = store_incoming(&sessions, &chat_keys, chat_msg) {
+                        console::log_1(&format!("Dropping chat message {}: {}", message_id, e).into());
+                    }
                 }
             }
         });
@@ -55,67 +87,213 @@ impl SimpleChatManager {
         self.websocket_client = Some(client);
     }
     
-    pub fn create_chat_session(&mut self, other_user_id: String) -> String {
-        let chat_id = Uuid::new_v4().to_string();
-        
-        let (patient_id, provider_id) = if self.current_user_role == "patient" {
-            (self.current_user_id.clone(), other_user_id)
+    // Start the chat for a booking under the chat_id the server assigned. The
+    // starting side generates the chat key; share it with `distribute_chat_key`.
+    pub fn create_chat_session(&mut self, chat_id: String, booking_id: BookingId, other_user_id: String) -> String {
+        if CHAT_END_TO_END_AVAILABLE {
+            let participants = vec![self.current_user_id.clone(), other_user_id.clone()];
+            self.chat_keys
+                .lock()
+                .unwrap()
+                .entry(booking_id.clone())
+                .or_insert_with(|| ChatKeyRing::new(booking_id.clone(), &participants));
+        }
+        self.insert_session(chat_id, booking_id, other_user_id)
+    }
+    
+    // Join a chat another participant started. Messages can be sent and read
+    // once their key arrives through `accept_chat_key`.
+    pub fn join_chat_session(&mut self, chat_id: String, booking_id: BookingId, other_user_id: String) -> String {
+        self.insert_session(chat_id, booking_id, other_user_id)
+    }
+    
+    fn insert_session(&mut self, chat_id: String, booking_id: BookingId, other_user_id: String) -> String {
+        let (patient_id, provider_id) = if self.current_user_role == Role::Patient {
+            (PatientId::new(self.current_user_id.clone()), ProviderId::new(other_user_id))
         } else {
-            (other_user_id, self.current_user_id.clone())
+            (PatientId::new(other_user_id), ProviderId::new(self.current_user_id.clone()))
         };
         
         let session = ChatSession {
             chat_id: chat_id.clone(),
+            booking_id,
             patient_id,
             provider_id,
             messages: Vec::new(),
             is_active: true,
+            end_to_end_encrypted: CHAT_END_TO_END_AVAILABLE,
             created_at: Utc::now(),
             last_activity: Utc::now(),
         };
         
-        self.active_sessions.insert(chat_id.clone(), session);
+        self.active_sessions.lock().unwrap().insert(chat_id.clone(), session);
         chat_id
     }
     
-    pub async fn send_message(&mut self, chat_id: String, content: String) -> Result<(), String> {
-        let chat_message = create_chat_message(
-            chat_id.clone(),
-            self.current_user_id.clone(),
-            "provider-001".to_string(), // TODO: Get actual receiver_id
-            content.clone(),
-            "text".to_string(),
-        );
+    fn booking_of(&self, chat_id: &str) -> Result<BookingId, String> {
+        self.active_sessions
+            .lock()
+            .unwrap()
+            .get(chat_id)
+            .map(|session| session.booking_id.clone())
+            .ok_or_else(|| format!("Unknown chat {}", chat_id))
+    }
+    
+    // Call whenever someone joins or leaves the booking's chat. Returns the new
+    // key epoch if the key rotated; distribute it before sending more messages.
+    pub fn update_participants(&mut self, chat_id: &str, participants: &[String]) -> Result<Option<u32>, String> {
+        let booking_id = self.booking_of(chat_id)?;
+        let mut keys = self.chat_keys.lock().unwrap();
+        let ring = keys.get_mut(&booking_id).ok_or_else(|| format!("No chat key for booking {}", booking_id))?;
+        Ok(ring.update_participants(participants))
+    }
+    
+    // Encrypt the current chat key to every other participant's published key
+    #[cfg(feature = "post-quantum")]
+    pub fn seal_chat_keys(
+        &self,
+        chat_id: &str,
+        bundles: &HashMap<String, HybridPublicKeyBundle>,
+    ) -> Result<Vec<ChatKeyEnvelope>, String> {
+        let booking_id = self.booking_of(chat_id)?;
+        let keys = self.chat_keys.lock().unwrap();
+        let ring = keys.get(&booking_id).ok_or_else(|| format!("No chat key for booking {}", booking_id))?;
+        let auth = PQAuthentication::new();
+        ring.participants()
+            .iter()
+            .filter(|participant| **participant != self.current_user_id)
+            .map(|participant| {
+                let bundle = bundles.get(participant)
+                    .ok_or_else(|| format!("No public key for participant {}", participant))?;
+                auth.seal_chat_key(ring.current_key(), participant, bundle).map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+    
+    #[cfg(feature = "post-quantum")]
+    pub async fn distribute_chat_key(
+        &self,
+        chat_id: &str,
+        bundles: &HashMap<String, HybridPublicKeyBundle>,
+    ) -> Result<(), String> {
+        let envelopes = self.seal_chat_keys(chat_id, bundles)?;
+        let client = self.websocket_client.as_ref().ok_or("WebSocket not connected")?;
+        for envelope in envelopes {
+            client.send_chat_key(envelope).await?;
+        }
+        Ok(())
+    }
+    
+    // Install a chat key another participant sent us: the first key of a
+    // joined chat, or a later epoch after a rotation
+    #[cfg(feature = "post-quantum")]
+    pub fn accept_chat_key(&mut self, envelope: &ChatKeyEnvelope, keypair: &HybridKeyPair) -> Result<(), String> {
+        let key = PQAuthentication::new().open_chat_key(envelope, keypair).map_err(|e| e.to_string())?;
+        let participants = self.active_sessions
+            .lock()
+            .unwrap()
+            .values()
+            .find(|session| session.booking_id == envelope.booking_id)
+            .map(|session| vec![session.patient_id.to_string(), session.provider_id.to_string()])
+            .ok_or_else(|| format!("No chat for booking {}", envelope.booking_id))?;
         
-        // Add to local session
-        if let Some(session) = self.active_sessions.get_mut(&chat_id) {
-            session.messages.push(chat_message.clone());
-            session.last_activity = Utc::now();
+        let mut keys = self.chat_keys.lock().unwrap();
+        match keys.get_mut(&envelope.booking_id) {
+            Some(ring) => ring.install(key).map_err(|e| e.to_string()),
+            None => {
+                keys.insert(envelope.booking_id.clone(), ChatKeyRing::from_key(key, &participants));
+                Ok(())
+            }
         }
+    }
+    
+    pub async fn send_message(&mut self, chat_id: String, content: String) -> Result<(), String> {
+        let outgoing = {
+            let mut sessions = self.active_sessions.lock().unwrap();
+            let session = sessions.get_mut(&chat_id).ok_or_else(|| format!("Unknown chat {}", chat_id))?;
+            let receiver_id = if self.current_user_role == Role::Patient {
+                session.provider_id.to_string()
+            } else {
+                session.patient_id.to_string()
+            };
+            let chat_message = create_chat_message(
+                chat_id.clone(),
+                self.current_user_id.clone(),
+                receiver_id,
+                content,
+                "text".to_string(),
+            );
+            
+            // Only ciphertext leaves the device in end-to-end chats
+            let mut outgoing = chat_message.clone();
+            if session.end_to_end_encrypted {
+                let keys = self.chat_keys.lock().unwrap();
+                let ring = keys.get(&session.booking_id).ok_or_else(|| {
+                    format!("No chat key for booking {} yet; wait for it to be shared", session.booking_id)
+                })?;
+                encrypt_message(&mut outgoing, ring)?;
+            }
+            
+            session.messages.push(chat_message);
+            session.last_activity = Utc::now();
+            outgoing
+        };
         
         // Send via WebSocket
         if let Some(client) = &self.websocket_client {
-            client.send_chat_message(chat_message).await?;
+            client.send_chat_message(outgoing).await?;
         }
         
         Ok(())
     }
     
+    // Decrypt a message received from the server and add it to its chat
+    pub fn receive_message(&mut self, message: ChatMessage) -> Result<ChatMessage, String> {
+        store_incoming(&self.active_sessions, &self.chat_keys, message)
+    }
+    
     pub fn get_chat_messages(&self, chat_id: &str) -> Vec<ChatMessage> {
         self.active_sessions
+            .lock()
+            .unwrap()
             .get(chat_id)
             .map(|session| session.messages.clone())
             .unwrap_or_default()
     }
     
-    pub fn get_active_chats(&self) -> Vec<&ChatSession> {
+    pub fn get_active_chats(&self) -> Vec<ChatSession> {
         self.active_sessions
+            .lock()
+            .unwrap()
             .values()
             .filter(|session| session.is_active)
+            .cloned()
             .collect()
     }
 }
 
+// Decrypt an incoming message and append it to its chat. End-to-end chats
+// refuse plaintext, which could only have been injected by the relay.
+fn store_incoming(sessions: &ChatSessionStore, keys: &ChatKeyStore, mut message: ChatMessage) -> Result<ChatMessage, String> {
+    let mut sessions = sessions.lock().unwrap();
+    let session = sessions
+        .get_mut(&message.chat_id)
+        .ok_or_else(|| format!("Unknown chat {}", message.chat_id))?;
+    if session.end_to_end_encrypted {
+        let keys = keys.lock().unwrap();
+        let ring = keys
+            .get(&session.booking_id)
+            .ok_or_else(|| format!("No chat key for booking {} yet", session.booking_id))?;
+        decrypt_message(&mut message, ring)?;
+    } else if message.is_encrypted {
+        return Err(format!("Chat message {} is end-to-end encrypted, which this build can't read", message.message_id));
+    }
+    
+    session.messages.push(message.clone());
+    session.last_activity = Utc::now();
+    Ok(message)
+}
+
 // Quick response templates for providers
 pub fn get_provider_quick_responses() -> Vec<(&'static str, &'static str)> {
     vec![
@@ -128,18 +306,31 @@ pub fn get_provider_quick_responses() -> Vec<(&'static str, &'static str)> {
     ]
 }
 
-// Message encryption helpers (simplified)
-pub fn encrypt_message(content: &str, _key: &str) -> String {
-    // Simplified encryption - in production use proper encryption
-    general_purpose::STANDARD.encode(content)
+// End-to-end message encryption; the ciphertext is bound to the message id and sender
+pub fn encrypt_message(message: &mut ChatMessage, keys: &ChatKeyRing) -> Result<(), String> {
+    if message.is_encrypted {
+        return Ok(());
+    }
+    message.content = keys.encrypt(&message.message_id, &message.sender_id, &message.content)
+        .map_err(|e| e.to_string())?;
+    message.is_encrypted = true;
+    Ok(())
 }
 
-pub fn decrypt_message(encrypted: &str, _key: &str) -> Result<String, String> {
-    // Simplified decryption
-    general_purpose::STANDARD.decode(encrypted)
-        .map_err(|e| format!("Decryption error: {}", e))
-        .and_then(|bytes| String::from_utf8(bytes)
-            .map_err(|e| format!("UTF-8 error: {}", e)))
+// The decrypted text is zeroized inside the key ring, but the copy put back
+// into `content` is a plain String: chat history is rendered straight from
+// `ChatMessage`, so plaintext in memory is not covered past this point.
+pub fn decrypt_message(message: &mut ChatMessage, keys: &ChatKeyRing) -> Result<(), String> {
+    // Plaintext could only have been injected by the relay
+    if !message.is_encrypted {
+        return Err(format!("Chat message {} is not end-to-end encrypted", message.message_id));
+    }
+    message.content = keys.decrypt(&message.message_id, &message.sender_id, &message.content)
+        .map_err(|e| e.to_string())?
+        .expose()
+        .to_string();
+    message.is_encrypted = false;
+    Ok(())
 }
 
 // HIPAA compliance helpers
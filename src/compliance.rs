@@ -1,5 +1,6 @@
 //! Healthcare compliance utilities and HIPAA/GDPR support
 
+use crate::auth::Role;
 use crate::errors::{SharedError, SharedResult};
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,9 @@ pub mod hipaa {
     use super::*;
     use chrono::{DateTime, Utc};
 
+    /// Resource type of sign-in and sign-out audit entries
+    pub const AUTHENTICATION_RESOURCE: &str = "authentication";
+
     /// HIPAA audit log entry
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct HipaaAuditEntry {
@@ -21,11 +25,18 @@ pub mod hipaa {
         pub ip_address: Option<String>,
         pub user_agent: Option<String>,
         pub outcome: AuditOutcome,
+        #[serde(default)]
+        pub purpose: Option<String>,
+        #[serde(default)]
+        pub severity: AuditSeverity,
+        /// Set for entries a privacy officer must look at, e.g. break-glass access
+        #[serde(default)]
+        pub requires_review: bool,
         pub details: serde_json::Value,
     }
 
     /// HIPAA auditable actions
-    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
     pub enum HipaaAction {
         Create,
         Read,
@@ -34,6 +45,10 @@ pub mod hipaa {
         Access,
         Login,
         Logout,
+        /// Rejected sign-in: wrong password, failed second factor or unknown device
+        FailedLogin,
+        /// Break-glass: emergency access granted outside normal permissions
+        EmergencyAccess,
         Export,
         Print,
         Share,
@@ -43,13 +58,22 @@ pub mod hipaa {
     }
 
     /// Audit outcome status
-    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
     pub enum AuditOutcome {
         Success,
         Failure,
         Warning,
     }
 
+    /// How urgently an entry needs attention when the log is monitored
+    #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum AuditSeverity {
+        #[default]
+        Info,
+        Warning,
+        Critical,
+    }
+
     /// Protected Health Information (PHI) classifier
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct PhiClassification {
@@ -103,10 +127,21 @@ pub mod hipaa {
                 ip_address: None,
                 user_agent: None,
                 outcome,
+                purpose: None,
+                severity: AuditSeverity::Info,
+                requires_review: false,
                 details: serde_json::Value::Null,
             }
         }
 
+        /// Sign-in or sign-out event. `user_id` may be an email or other login
+        /// name for failed attempts, so it is kept as the resource id as well.
+        pub fn authentication(action: HipaaAction, user_id: &str, outcome: AuditOutcome) -> Self {
+            let mut entry = Self::new(action, AUTHENTICATION_RESOURCE, user_id, outcome);
+            entry.user_id = uuid::Uuid::parse_str(user_id).ok();
+            entry
+        }
+
         pub fn with_user(mut self, user_id: uuid::Uuid) -> Self {
             self.user_id = Some(user_id);
             self
@@ -123,12 +158,200 @@ pub mod hipaa {
             self
         }
 
+        pub fn with_purpose(mut self, purpose: &str) -> Self {
+            self.purpose = Some(purpose.to_string());
+            self
+        }
+
+        pub fn with_severity(mut self, severity: AuditSeverity) -> Self {
+            self.severity = severity;
+            self
+        }
+
+        /// Mark the entry for post-hoc review
+        pub fn flag_for_review(mut self) -> Self {
+            self.requires_review = true;
+            self
+        }
+
         pub fn with_details<T: Serialize>(mut self, details: &T) -> Self {
             self.details = serde_json::to_value(details).unwrap_or(serde_json::Value::Null);
             self
         }
     }
 
+    /// Destination for HIPAA audit entries emitted by shared components
+    pub trait AuditSink: Send + Sync {
+        fn record(&self, entry: HipaaAuditEntry);
+    }
+
+    /// Audit sink that forwards entries to the `log` facade as JSON
+    #[derive(Debug, Clone, Default)]
+    pub struct LogAuditSink;
+
+    impl AuditSink for LogAuditSink {
+        fn record(&self, entry: HipaaAuditEntry) {
+            match serde_json::to_string(&entry) {
+                Ok(json) => log::info!(target: "hipaa_audit", "{}", json),
+                Err(e) => log::error!(target: "hipaa_audit", "Failed to serialize audit entry {}: {}", entry.entry_id, e),
+            }
+        }
+    }
+
+    /// In-memory audit sink for tests and client-side buffering
+    #[derive(Debug, Clone, Default)]
+    pub struct MemoryAuditSink {
+        entries: std::sync::Arc<std::sync::Mutex<Vec<HipaaAuditEntry>>>,
+    }
+
+    impl MemoryAuditSink {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Snapshot of all entries recorded so far
+        pub fn entries(&self) -> Vec<HipaaAuditEntry> {
+            self.entries.lock().map(|e| e.clone()).unwrap_or_default()
+        }
+
+        /// Remove and return all recorded entries
+        pub fn drain(&self) -> Vec<HipaaAuditEntry> {
+            self.entries.lock().map(|mut e| std::mem::take(&mut *e)).unwrap_or_default()
+        }
+
+        /// Entries matching `query`, ignoring its pagination
+        pub fn query(&self, query: &AuditQuery) -> Vec<HipaaAuditEntry> {
+            self.entries()
+                .into_iter()
+                .filter(|entry| query.matches(entry))
+                .collect()
+        }
+    }
+
+    impl AuditSink for MemoryAuditSink {
+        fn record(&self, entry: HipaaAuditEntry) {
+            if let Ok(mut entries) = self.entries.lock() {
+                entries.push(entry);
+            }
+        }
+    }
+
+    /// Filters for searching audit logs, locally or via the admin API
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct AuditQuery {
+        pub user_id: Option<uuid::Uuid>,
+        pub patient_id: Option<uuid::Uuid>,
+        pub action: Option<HipaaAction>,
+        pub resource_type: Option<String>,
+        pub outcome: Option<AuditOutcome>,
+        pub requires_review: Option<bool>,
+        pub from: Option<DateTime<Utc>>,
+        pub until: Option<DateTime<Utc>>,
+        pub page: Option<u32>,
+        pub page_size: Option<u32>,
+    }
+
+    impl AuditQuery {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn user(mut self, user_id: uuid::Uuid) -> Self {
+            self.user_id = Some(user_id);
+            self
+        }
+
+        pub fn patient(mut self, patient_id: uuid::Uuid) -> Self {
+            self.patient_id = Some(patient_id);
+            self
+        }
+
+        pub fn action(mut self, action: HipaaAction) -> Self {
+            self.action = Some(action);
+            self
+        }
+
+        pub fn resource_type(mut self, resource_type: &str) -> Self {
+            self.resource_type = Some(resource_type.to_string());
+            self
+        }
+
+        pub fn outcome(mut self, outcome: AuditOutcome) -> Self {
+            self.outcome = Some(outcome);
+            self
+        }
+
+        /// Only entries flagged (or not flagged) for review
+        pub fn requires_review(mut self, requires_review: bool) -> Self {
+            self.requires_review = Some(requires_review);
+            self
+        }
+
+        /// Inclusive start, exclusive end
+        pub fn between(mut self, from: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+            self.from = Some(from);
+            self.until = Some(until);
+            self
+        }
+
+        pub fn page(mut self, page: u32, page_size: u32) -> Self {
+            self.page = Some(page);
+            self.page_size = Some(page_size);
+            self
+        }
+
+        pub fn matches(&self, entry: &HipaaAuditEntry) -> bool {
+            self.user_id.is_none_or(|id| entry.user_id == Some(id))
+                && self.patient_id.is_none_or(|id| entry.patient_id == Some(id))
+                && self.action.as_ref().is_none_or(|action| &entry.action == action)
+                && self.resource_type.as_ref().is_none_or(|kind| &entry.resource_type == kind)
+                && self.outcome.as_ref().is_none_or(|outcome| &entry.outcome == outcome)
+                && self.requires_review.is_none_or(|flag| entry.requires_review == flag)
+                && self.from.is_none_or(|from| entry.timestamp >= from)
+                && self.until.is_none_or(|until| entry.timestamp < until)
+        }
+
+        pub fn to_query_params(&self) -> Vec<(&'static str, String)> {
+            let variant_name = |value: serde_json::Result<serde_json::Value>| match value {
+                Ok(serde_json::Value::String(name)) => Some(name),
+                _ => None,
+            };
+
+            let mut params = Vec::new();
+            if let Some(user_id) = self.user_id {
+                params.push(("user_id", user_id.to_string()));
+            }
+            if let Some(patient_id) = self.patient_id {
+                params.push(("patient_id", patient_id.to_string()));
+            }
+            if let Some(action) = self.action.as_ref().and_then(|a| variant_name(serde_json::to_value(a))) {
+                params.push(("action", action));
+            }
+            if let Some(resource_type) = &self.resource_type {
+                params.push(("resource_type", resource_type.clone()));
+            }
+            if let Some(outcome) = self.outcome.as_ref().and_then(|o| variant_name(serde_json::to_value(o))) {
+                params.push(("outcome", outcome));
+            }
+            if let Some(requires_review) = self.requires_review {
+                params.push(("requires_review", requires_review.to_string()));
+            }
+            if let Some(from) = self.from {
+                params.push(("from", from.to_rfc3339()));
+            }
+            if let Some(until) = self.until {
+                params.push(("until", until.to_rfc3339()));
+            }
+            if let Some(page) = self.page {
+                params.push(("page", page.to_string()));
+            }
+            if let Some(page_size) = self.page_size {
+                params.push(("page_size", page_size.to_string()));
+            }
+            params
+        }
+    }
+
     /// Classify text for PHI content
     pub fn classify_phi(text: &str) -> PhiClassification {
         let mut phi_types = Vec::new();
@@ -143,10 +366,14 @@ pub mod hipaa {
             contains_phi = true;
         }
 
-        // Phone number detection
+        // Phone number detection: US-style groups, or anything that normalizes to a valid E.164 number
+        let phone_candidates = regex::Regex::new(r"\+?\d[\d\s().-]{6,}\d").unwrap();
         if regex::Regex::new(r"\b\d{3}[-.]?\d{3}[-.]?\d{4}\b")
             .unwrap()
             .is_match(text)
+            || phone_candidates
+                .find_iter(text)
+                .any(|candidate| crate::utils::contact::PhoneNumber::parse(candidate.as_str()).is_ok())
         {
             phi_types.push(PhiType::PhoneNumber);
             contains_phi = true;
@@ -170,10 +397,14 @@ pub mod hipaa {
             contains_phi = true;
         }
 
-        // Medical record number patterns
+        // Medical record number patterns, plus checksummed MRNs under any facility prefix
+        let mrn_candidates = regex::Regex::new(r"\b[A-Za-z]{1,6}-[A-Za-z0-9]{4,32}-[A-Za-z0-9]\b").unwrap();
         if regex::Regex::new(r"\bMRN[-:]?\s*\d+\b")
             .unwrap()
             .is_match(text)
+            || mrn_candidates
+                .find_iter(text)
+                .any(|candidate| crate::ids::MedicalRecordNumber::parse(candidate.as_str()).is_ok())
         {
             phi_types.push(PhiType::MedicalRecordNumber);
             contains_phi = true;
@@ -225,30 +456,30 @@ pub mod hipaa {
 
     /// Generate minimum necessary access justification
     pub fn validate_minimum_necessary_access(
-        user_role: &str,
+        user_role: Role,
         requested_data: &[&str],
         purpose: &str,
     ) -> SharedResult<bool> {
         let allowed_data = match user_role {
-            "physician" => vec![
+            Role::Physician => vec![
                 "medical_history", "current_medications", "lab_results", 
                 "imaging", "vital_signs", "treatment_notes", "patient_demographics"
             ],
-            "nurse" => vec![
+            Role::Nurse => vec![
                 "vital_signs", "current_medications", "treatment_notes", 
                 "care_plans", "patient_demographics"
             ],
-            "technician" => vec![
+            Role::Technician => vec![
                 "lab_results", "imaging", "vital_signs"
             ],
-            "admin" => vec![
+            Role::Admin | Role::OrgAdmin => vec![
                 "patient_demographics", "insurance_info", "billing_info"
             ],
-            "patient" => vec![
+            Role::Patient => vec![
                 "own_medical_history", "own_medications", "own_lab_results",
                 "own_imaging", "own_vital_signs", "own_treatment_notes"
             ],
-            _ => vec![],
+            Role::Provider => vec![],
         };
 
         let unauthorized_access: Vec<&str> = requested_data
@@ -273,7 +504,14 @@ pub mod hipaa {
 /// GDPR compliance utilities
 pub mod gdpr {
     use super::*;
+    use super::hipaa::{AuditOutcome, AuditSeverity, AuditSink, HipaaAction, HipaaAuditEntry};
     use chrono::{DateTime, Utc};
+    use crate::schema::Versioned;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+
+    /// Resource type of consent-denial audit entries
+    pub const CONSENT_RESOURCE: &str = "consent";
 
     /// GDPR consent record
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -289,10 +527,13 @@ pub mod gdpr {
         pub consent_version: String,
         pub ip_address: Option<String>,
         pub user_agent: Option<String>,
+        /// Handwritten signature as an image data URL, for consent captured on a signature pad
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub signature: Option<String>,
     }
 
     /// Data processing purposes under GDPR
-    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
     pub enum DataProcessingPurpose {
         Healthcare,
         Research,
@@ -303,6 +544,26 @@ pub mod gdpr {
         Emergency,
     }
 
+    impl DataProcessingPurpose {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                DataProcessingPurpose::Healthcare => "healthcare",
+                DataProcessingPurpose::Research => "research",
+                DataProcessingPurpose::Marketing => "marketing",
+                DataProcessingPurpose::Analytics => "analytics",
+                DataProcessingPurpose::Communication => "communication",
+                DataProcessingPurpose::Legal => "legal",
+                DataProcessingPurpose::Emergency => "emergency",
+            }
+        }
+    }
+
+    impl fmt::Display for DataProcessingPurpose {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.as_str())
+        }
+    }
+
     /// Legal basis for processing under GDPR Article 6
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub enum LegalBasis {
@@ -356,6 +617,7 @@ pub mod gdpr {
                 consent_version,
                 ip_address: None,
                 user_agent: None,
+                signature: None,
             }
         }
 
@@ -370,9 +632,23 @@ pub mod gdpr {
             self
         }
 
+        pub fn with_signature(mut self, signature: String) -> Self {
+            self.signature = Some(signature);
+            self
+        }
+
+        pub fn is_signed(&self) -> bool {
+            self.signature.as_deref().is_some_and(|s| !s.trim().is_empty())
+        }
+
         pub fn is_valid(&self) -> bool {
-            self.withdrawn_at.is_none() &&
-            self.expires_at.map_or(true, |exp| exp > Utc::now())
+            self.is_valid_at(Utc::now())
+        }
+
+        pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+            self.withdrawn_at.is_none_or(|withdrawn_at| withdrawn_at > now) &&
+            self.granted_at <= now &&
+            self.expires_at.is_none_or(|exp| exp > now)
         }
 
         pub fn withdraw(&mut self) {
@@ -380,6 +656,178 @@ pub mod gdpr {
         }
     }
 
+    impl Versioned for ConsentRecord {
+        const SCHEMA_NAME: &'static str = "consent_record";
+        const SCHEMA_VERSION: u32 = 2;
+
+        fn migrate(from_version: u32, mut data: serde_json::Value) -> SharedResult<serde_json::Value> {
+            match (from_version, data.as_object_mut()) {
+                // v2 added handwritten signatures
+                (1, Some(record)) => {
+                    record.entry("signature").or_insert(serde_json::Value::Null);
+                    Ok(data)
+                }
+                _ => Err(SharedError::SerializationError(format!(
+                    "No migration for consent_record v{}",
+                    from_version
+                ))),
+            }
+        }
+    }
+
+    /// Where a data subject's consent records are kept
+    pub trait ConsentStore: Send + Sync {
+        fn consents(&self, subject_id: uuid::Uuid) -> Vec<ConsentRecord>;
+
+        /// True if the subject has a consent for `purpose` that is valid at `now`
+        fn has_consent(&self, subject_id: uuid::Uuid, purpose: &DataProcessingPurpose, now: DateTime<Utc>) -> bool {
+            self.consents(subject_id)
+                .iter()
+                .any(|consent| &consent.purpose == purpose && consent.is_valid_at(now))
+        }
+    }
+
+    /// In-memory consent store for tests and client-side caching of a
+    /// patient's own consents
+    #[derive(Debug, Clone, Default)]
+    pub struct MemoryConsentStore {
+        consents: Arc<Mutex<Vec<ConsentRecord>>>,
+    }
+
+    impl MemoryConsentStore {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn record(&self, consent: ConsentRecord) {
+            if let Ok(mut consents) = self.consents.lock() {
+                consents.retain(|existing| existing.consent_id != consent.consent_id);
+                consents.push(consent);
+            }
+        }
+
+        /// Withdraw every active consent the subject gave for `purpose`; returns how many
+        pub fn withdraw(&self, subject_id: uuid::Uuid, purpose: &DataProcessingPurpose, now: DateTime<Utc>) -> usize {
+            let Ok(mut consents) = self.consents.lock() else {
+                return 0;
+            };
+            let mut withdrawn = 0;
+            for consent in consents.iter_mut() {
+                if consent.user_id == subject_id && &consent.purpose == purpose && consent.withdrawn_at.is_none() {
+                    consent.withdrawn_at = Some(now);
+                    withdrawn += 1;
+                }
+            }
+            withdrawn
+        }
+    }
+
+    impl ConsentStore for MemoryConsentStore {
+        fn consents(&self, subject_id: uuid::Uuid) -> Vec<ConsentRecord> {
+            self.consents
+                .lock()
+                .map(|consents| consents.iter().filter(|c| c.user_id == subject_id).cloned().collect())
+                .unwrap_or_default()
+        }
+    }
+
+    /// A data subject's data, sealed until a [`ConsentGate`] confirms they
+    /// consented to `purpose`. The value can't be read, printed or serialized
+    /// any other way, so analytics code handed guards can't reach
+    /// non-consented data.
+    pub struct ConsentGuard<T> {
+        subject_id: uuid::Uuid,
+        purpose: DataProcessingPurpose,
+        data: T,
+    }
+
+    impl<T> ConsentGuard<T> {
+        pub fn new(subject_id: uuid::Uuid, purpose: DataProcessingPurpose, data: T) -> Self {
+            Self { subject_id, purpose, data }
+        }
+
+        pub fn subject_id(&self) -> uuid::Uuid {
+            self.subject_id
+        }
+
+        pub fn purpose(&self) -> &DataProcessingPurpose {
+            &self.purpose
+        }
+    }
+
+    impl<T> fmt::Debug for ConsentGuard<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ConsentGuard")
+                .field("subject_id", &self.subject_id)
+                .field("purpose", &self.purpose)
+                .finish_non_exhaustive()
+        }
+    }
+
+    /// Checks the consent store before releasing [`ConsentGuard`]ed data, and
+    /// audits every denial
+    #[derive(Clone)]
+    pub struct ConsentGate {
+        store: Arc<dyn ConsentStore>,
+        audit_sink: Option<Arc<dyn AuditSink>>,
+    }
+
+    impl fmt::Debug for ConsentGate {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ConsentGate")
+                .field("audit_sink", &self.audit_sink.is_some())
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl ConsentGate {
+        pub fn new(store: Arc<dyn ConsentStore>) -> Self {
+            Self { store, audit_sink: None }
+        }
+
+        pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+            self.audit_sink = Some(sink);
+            self
+        }
+
+        /// Ok if the subject consented to `purpose`; otherwise the denial is audited
+        pub fn check(&self, subject_id: uuid::Uuid, purpose: &DataProcessingPurpose, now: DateTime<Utc>) -> SharedResult<()> {
+            if self.store.has_consent(subject_id, purpose, now) {
+                return Ok(());
+            }
+            if let Some(sink) = &self.audit_sink {
+                let mut entry = HipaaAuditEntry::new(HipaaAction::Access, CONSENT_RESOURCE, &subject_id.to_string(), AuditOutcome::Failure)
+                    .with_patient(subject_id)
+                    .with_purpose(purpose.as_str())
+                    .with_severity(AuditSeverity::Warning)
+                    .with_details(&serde_json::json!({ "reason": "no_valid_consent" }));
+                entry.timestamp = now;
+                sink.record(entry);
+            }
+            Err(SharedError::AuthorizationError(format!(
+                "Data subject {} has not consented to {} processing",
+                subject_id, purpose
+            )))
+        }
+
+        /// Unwrap the data if its subject consented to the guard's purpose
+        pub fn open<T>(&self, guard: ConsentGuard<T>, now: DateTime<Utc>) -> SharedResult<T> {
+            self.check(guard.subject_id, &guard.purpose, now)?;
+            Ok(guard.data)
+        }
+
+        pub fn open_ref<'a, T>(&self, guard: &'a ConsentGuard<T>, now: DateTime<Utc>) -> SharedResult<&'a T> {
+            self.check(guard.subject_id, &guard.purpose, now)?;
+            Ok(&guard.data)
+        }
+
+        /// The consented subset of a batch, e.g. rows for an analytics job;
+        /// each excluded subject is audited
+        pub fn filter<T>(&self, guards: impl IntoIterator<Item = ConsentGuard<T>>, now: DateTime<Utc>) -> Vec<T> {
+            guards.into_iter().filter_map(|guard| self.open(guard, now).ok()).collect()
+        }
+    }
+
     /// Check GDPR compliance for data processing
     pub fn check_compliance(
         user_consents: &[ConsentRecord],
@@ -569,25 +1017,78 @@ mod tests {
         assert!(classification.phi_types.contains(&hipaa::PhiType::Email));
         assert!(classification.phi_types.contains(&hipaa::PhiType::PhoneNumber));
         assert!(classification.phi_types.contains(&hipaa::PhiType::DateOfBirth));
+
+        let indian = hipaa::classify_phi("Call the patient on +91 98765 43210 after rounds");
+        assert!(indian.phi_types.contains(&hipaa::PhiType::PhoneNumber));
+        assert!(!hipaa::classify_phi("Follow up on 2024-01-15").contains_phi);
+
+        let mrn = crate::ids::MrnGenerator::new("AIIMS").unwrap().sequential(42).unwrap();
+        let record = hipaa::classify_phi(&format!("Chart {} reviewed", mrn));
+        assert!(record.phi_types.contains(&hipaa::PhiType::MedicalRecordNumber));
+    }
+
+    #[test]
+    fn test_audit_query_filters() {
+        use hipaa::AuditSink;
+
+        let patient = uuid::Uuid::new_v4();
+        let sink = hipaa::MemoryAuditSink::new();
+        sink.record(
+            hipaa::HipaaAuditEntry::new(hipaa::HipaaAction::Read, "lab_results", "r1", hipaa::AuditOutcome::Success)
+                .with_patient(patient),
+        );
+        sink.record(hipaa::HipaaAuditEntry::new(hipaa::HipaaAction::Export, "gdpr_export", "e1", hipaa::AuditOutcome::Failure));
+
+        let query = hipaa::AuditQuery::new().patient(patient).action(hipaa::HipaaAction::Read);
+        assert_eq!(sink.query(&query).len(), 1);
+        assert_eq!(sink.query(&hipaa::AuditQuery::new().outcome(hipaa::AuditOutcome::Failure))[0].resource_id, "e1");
+
+        let params = query.page(2, 50).to_query_params();
+        assert!(params.contains(&("action", "Read".to_string())));
+        assert!(params.contains(&("page_size", "50".to_string())));
     }
 
     #[test]
     fn test_minimum_necessary_access() {
         let result = hipaa::validate_minimum_necessary_access(
-            "physician",
+            Role::Physician,
             &["medical_history", "lab_results"],
             "diagnosis"
         );
         assert!(result.is_ok());
 
         let result = hipaa::validate_minimum_necessary_access(
-            "technician",
+            Role::Technician,
             &["medical_history", "lab_results"],
             "lab_work"
         );
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_memory_audit_sink() {
+        use hipaa::AuditSink;
+
+        let sink = hipaa::MemoryAuditSink::new();
+        let patient_id = uuid::Uuid::new_v4();
+        sink.record(
+            hipaa::HipaaAuditEntry::new(
+                hipaa::HipaaAction::Read,
+                "medical_record",
+                &patient_id.to_string(),
+                hipaa::AuditOutcome::Success,
+            )
+            .with_patient(patient_id)
+            .with_purpose("treatment"),
+        );
+
+        let entries = sink.drain();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].patient_id, Some(patient_id));
+        assert_eq!(entries[0].purpose.as_deref(), Some("treatment"));
+        assert!(sink.entries().is_empty());
+    }
+
     #[test]
     fn test_consent_record() {
         let mut consent = gdpr::ConsentRecord::new(
@@ -599,7 +1100,11 @@ mod tests {
         );
 
         assert!(consent.is_valid());
-        
+        assert!(!consent.is_signed());
+
+        consent = consent.with_signature("data:image/png;base64,iVBORw0KGgo=".to_string());
+        assert!(consent.is_signed());
+
         consent.withdraw();
         assert!(!consent.is_valid());
     }
@@ -626,4 +1131,44 @@ mod tests {
         assert!(!status.is_compliant);
         assert_eq!(status.missing_consents.len(), 1);
     }
+
+    #[test]
+    fn test_consent_gate() {
+        use gdpr::{ConsentGate, ConsentGuard, DataProcessingPurpose, MemoryConsentStore};
+        use std::sync::Arc;
+
+        let now = chrono::Utc::now();
+        let (consented, declined) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let store = MemoryConsentStore::new();
+        for (subject, purpose) in [(consented, DataProcessingPurpose::Analytics), (declined, DataProcessingPurpose::Healthcare)] {
+            let mut consent = gdpr::ConsentRecord::new(subject, purpose, gdpr::LegalBasis::Consent, "Consent".to_string(), "v1.0".to_string());
+            consent.granted_at = now - chrono::Duration::days(1);
+            store.record(consent);
+        }
+        let sink = hipaa::MemoryAuditSink::new();
+        let gate = ConsentGate::new(Arc::new(store.clone())).with_audit_sink(Arc::new(sink.clone()));
+
+        let rows = vec![
+            ConsentGuard::new(consented, DataProcessingPurpose::Analytics, 72),
+            ConsentGuard::new(declined, DataProcessingPurpose::Analytics, 95),
+        ];
+        // The guarded value never reaches Debug output
+        assert_eq!(
+            format!("{:?}", rows[1]),
+            format!("ConsentGuard {{ subject_id: {:?}, purpose: Analytics, .. }}", declined)
+        );
+        assert_eq!(gate.filter(rows, now), vec![72]);
+
+        let denials = sink.drain();
+        assert_eq!(denials.len(), 1);
+        assert_eq!(denials[0].patient_id, Some(declined));
+        assert_eq!(denials[0].purpose.as_deref(), Some("analytics"));
+
+        // Withdrawal takes effect immediately
+        let guard = ConsentGuard::new(consented, DataProcessingPurpose::Analytics, "bp-series");
+        assert_eq!(gate.open_ref(&guard, now).unwrap(), &"bp-series");
+        assert_eq!(store.withdraw(consented, &DataProcessingPurpose::Analytics, now), 1);
+        assert!(gate.open(guard, now + chrono::Duration::seconds(1)).is_err());
+        assert_eq!(sink.entries().len(), 1);
+    }
 }
@@ -0,0 +1,308 @@
+//! Drug-interaction checking for prescriptions. Apps plug in their interaction
+//! database through [`InteractionChecker`]; [`PrescriptionBuilder`] runs it as
+//! each medication is added so prescribers see warnings before signing.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::errors::{SharedError, SharedResult};
+use crate::models::{Medication, Prescription};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InteractionSeverity {
+    Minor,
+    Moderate,
+    Major,
+    /// Must not be co-prescribed
+    Contraindicated,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InteractionWarning {
+    /// Generic name of the medication being added
+    pub drug: String,
+    /// Generic name of the medication it interacts with
+    pub interacts_with: String,
+    pub severity: InteractionSeverity,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub management: Option<String>,
+}
+
+impl InteractionWarning {
+    /// Whether the pair is the same regardless of which drug was added first
+    pub fn involves(&self, a: &str, b: &str) -> bool {
+        let (drug, other) = (normalize(&self.drug), normalize(&self.interacts_with));
+        let (a, b) = (normalize(a), normalize(b));
+        (drug == a && other == b) || (drug == b && other == a)
+    }
+}
+
+/// Source of drug-interaction knowledge, e.g. a licensed database or a
+/// formulary service. Implementations compare by generic name.
+pub trait InteractionChecker: Send + Sync {
+    /// Interactions between `candidate` and any of `others`
+    fn check(&self, candidate: &Medication, others: &[Medication]) -> SharedResult<Vec<InteractionWarning>>;
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+#[derive(Debug, Clone)]
+struct KnownInteraction {
+    severity: InteractionSeverity,
+    description: String,
+    management: Option<String>,
+}
+
+/// In-memory interaction table for tests, demos and small formularies
+#[derive(Debug, Clone, Default)]
+pub struct StaticInteractionTable {
+    pairs: HashMap<(String, String), KnownInteraction>,
+}
+
+impl StaticInteractionTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an interaction; the pair is symmetric
+    pub fn with_interaction(
+        mut self,
+        a: &str,
+        b: &str,
+        severity: InteractionSeverity,
+        description: impl Into<String>,
+        management: Option<&str>,
+    ) -> Self {
+        self.pairs.insert(
+            Self::key(a, b),
+            KnownInteraction {
+                severity,
+                description: description.into(),
+                management: management.map(str::to_string),
+            },
+        );
+        self
+    }
+
+    fn key(a: &str, b: &str) -> (String, String) {
+        let (a, b) = (normalize(a), normalize(b));
+        if a <= b { (a, b) } else { (b, a) }
+    }
+}
+
+impl InteractionChecker for StaticInteractionTable {
+    fn check(&self, candidate: &Medication, others: &[Medication]) -> SharedResult<Vec<InteractionWarning>> {
+        Ok(others
+            .iter()
+            .filter_map(|other| {
+                let known = self.pairs.get(&Self::key(&candidate.generic_name, &other.generic_name))?;
+                Some(InteractionWarning {
+                    drug: candidate.generic_name.clone(),
+                    interacts_with: other.generic_name.clone(),
+                    severity: known.severity,
+                    description: known.description.clone(),
+                    management: known.management.clone(),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Collects medications for a prescription and checks each one against the
+/// patient's current medications and the ones already added.
+pub struct PrescriptionBuilder {
+    checker: Arc<dyn InteractionChecker>,
+    current_medications: Vec<Medication>,
+    medications: Vec<Medication>,
+    warnings: Vec<InteractionWarning>,
+}
+
+impl std::fmt::Debug for PrescriptionBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrescriptionBuilder")
+            .field("medications", &self.medications.len())
+            .field("warnings", &self.warnings.len())
+            .finish()
+    }
+}
+
+impl PrescriptionBuilder {
+    pub fn new(checker: Arc<dyn InteractionChecker>) -> Self {
+        Self {
+            checker,
+            current_medications: Vec::new(),
+            medications: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Medications the patient already takes, from their record
+    pub fn with_current_medications(mut self, medications: Vec<Medication>) -> Self {
+        self.current_medications = medications;
+        self
+    }
+
+    /// Add a medication and return the warnings it raised. The medication is
+    /// added even when it interacts; [`build`](Self::build) refuses
+    /// contraindicated combinations unless they are acknowledged.
+    pub fn add_medication(&mut self, medication: Medication) -> SharedResult<Vec<InteractionWarning>> {
+        if medication.generic_name.trim().is_empty() {
+            return Err(SharedError::ValidationError("Medication generic name is required".to_string()));
+        }
+        if self
+            .medications
+            .iter()
+            .any(|existing| normalize(&existing.generic_name) == normalize(&medication.generic_name))
+        {
+            return Err(SharedError::ValidationError(format!(
+                "{} is already on this prescription",
+                medication.generic_name
+            )));
+        }
+
+        let others: Vec<Medication> = self
+            .current_medications
+            .iter()
+            .chain(self.medications.iter())
+            .cloned()
+            .collect();
+        let mut raised = self.checker.check(&medication, &others)?;
+        raised.retain(|warning| {
+            !self
+                .warnings
+                .iter()
+                .any(|existing| existing.involves(&warning.drug, &warning.interacts_with))
+        });
+        self.warnings.extend(raised.iter().cloned());
+        self.medications.push(medication);
+        Ok(raised)
+    }
+
+    pub fn remove_medication(&mut self, generic_name: &str) {
+        let name = normalize(generic_name);
+        self.medications.retain(|m| normalize(&m.generic_name) != name);
+        self.warnings
+            .retain(|w| normalize(&w.drug) != name && normalize(&w.interacts_with) != name);
+    }
+
+    pub fn medications(&self) -> &[Medication] {
+        &self.medications
+    }
+
+    /// All warnings so far, most severe first
+    pub fn warnings(&self) -> Vec<InteractionWarning> {
+        let mut warnings = self.warnings.clone();
+        warnings.sort_by_key(|w| std::cmp::Reverse(w.severity));
+        warnings
+    }
+
+    pub fn has_contraindications(&self) -> bool {
+        self.warnings
+            .iter()
+            .any(|w| w.severity == InteractionSeverity::Contraindicated)
+    }
+
+    /// Finish the prescription. Contraindicated combinations are rejected
+    /// unless `override_contraindications` records a deliberate decision.
+    pub fn build(
+        self,
+        instructions: impl Into<String>,
+        follow_up_date: Option<chrono::NaiveDate>,
+        override_contraindications: bool,
+    ) -> SharedResult<Prescription> {
+        if self.medications.is_empty() {
+            return Err(SharedError::ValidationError("Prescription has no medications".to_string()));
+        }
+        if self.has_contraindications() && !override_contraindications {
+            return Err(SharedError::ValidationError(
+                "Prescription contains contraindicated combinations".to_string(),
+            ));
+        }
+        Ok(Prescription {
+            medications: self.medications,
+            instructions: instructions.into(),
+            follow_up_date,
+            digital_signature: String::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DoseFrequency, MedicationRoute};
+
+    fn oral(name: &str) -> Medication {
+        Medication::new(name, "", MedicationRoute::Oral, DoseFrequency::OnceDaily)
+    }
+
+    fn checker() -> Arc<dyn InteractionChecker> {
+        Arc::new(
+            StaticInteractionTable::new()
+                .with_interaction(
+                    "Warfarin",
+                    "Aspirin",
+                    InteractionSeverity::Major,
+                    "Increased bleeding risk",
+                    Some("Monitor INR"),
+                )
+                .with_interaction(
+                    "sildenafil",
+                    "nitroglycerin",
+                    InteractionSeverity::Contraindicated,
+                    "Severe hypotension",
+                    None,
+                ),
+        )
+    }
+
+    #[test]
+    fn test_builder_warns_against_current_and_new_medications() {
+        let mut builder = PrescriptionBuilder::new(checker()).with_current_medications(vec![oral("warfarin")]);
+
+        let warnings = builder.add_medication(oral("Aspirin")).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].severity, InteractionSeverity::Major);
+        assert!(warnings[0].involves("warfarin", "aspirin"));
+
+        assert!(builder.add_medication(oral("Paracetamol")).unwrap().is_empty());
+        assert!(builder.add_medication(oral("aspirin")).is_err());
+
+        let prescription = builder.build("After food", None, false).unwrap();
+        assert_eq!(prescription.medications.len(), 2);
+    }
+
+    #[test]
+    fn test_contraindications_block_build_unless_overridden() {
+        let contraindicated = || {
+            let mut builder = PrescriptionBuilder::new(checker());
+            builder.add_medication(oral("Nitroglycerin")).unwrap();
+            builder.add_medication(oral("Sildenafil")).unwrap();
+            builder
+        };
+        assert!(contraindicated().build("", None, false).is_err());
+        assert!(contraindicated().build("", None, true).is_ok());
+
+        let mut builder = contraindicated();
+        builder.remove_medication("sildenafil");
+        assert!(!builder.has_contraindications());
+        assert!(builder.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_medication_coding() {
+        let mut medication = Medication::new("Paracetamol", "500 mg", MedicationRoute::Oral, DoseFrequency::TwiceDaily);
+        medication.brand_name = Some("Crocin".to_string());
+        medication.dosage = "1 tablet".to_string();
+        medication.duration = "5 days".to_string();
+        assert_eq!(medication.display_name(), "Paracetamol 500 mg (Crocin)");
+        assert_eq!(medication.sig(), "1 tablet PO BD for 5 days");
+        assert_eq!(DoseFrequency::EveryHours(6).doses_per_day(), Some(4.0));
+        assert_eq!(serde_json::to_string(&DoseFrequency::ThreeTimesDaily).unwrap(), "\"TDS\"");
+    }
+}
@@ -9,6 +9,9 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use js_sys::Promise;
 use crate::websocket_simple::{SimpleWebSocketClient, EmergencyAlert, create_emergency_alert};
+use crate::geo::GeoPoint;
+use crate::ids::PatientId;
+use crate::models::BloodType;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmergencyContact {
@@ -22,19 +25,19 @@ pub struct MedicalInfo {
     pub condition: String,
     pub medications: Vec<String>,
     pub allergies: Vec<String>,
-    pub blood_type: Option<String>,
+    pub blood_type: Option<BloodType>,
 }
 
 pub struct SimpleEmergencySystem {
-    pub patient_id: String,
+    pub patient_id: PatientId,
     pub emergency_contacts: Vec<EmergencyContact>,
     pub medical_info: Option<MedicalInfo>,
     pub websocket_client: Option<SimpleWebSocketClient>,
-    pub last_location: Option<(f64, f64)>,
+    pub last_location: Option<GeoPoint>,
 }
 
 impl SimpleEmergencySystem {
-    pub fn new(patient_id: String) -> Self {
+    pub fn new(patient_id: PatientId) -> Self {
         Self {
             patient_id,
             emergency_contacts: Vec::new(),
@@ -57,7 +60,7 @@ impl SimpleEmergencySystem {
     }
     
     // Get current location using Web Geolocation API
-    pub async fn get_current_location(&mut self) -> Result<(f64, f64), String> {
+    pub async fn get_current_location(&mut self) -> Result<GeoPoint, String> {
         let window = web_sys::window().ok_or("No window object")?;
         let geolocation = window.navigator().geolocation()
             .map_err(|_| "Geolocation not supported")?;
@@ -98,8 +101,9 @@ impl SimpleEmergencySystem {
         let lat = js_result.as_f64().unwrap_or(0.0);
         let lng = js_result.as_f64().unwrap_or(0.0);
         
-        self.last_location = Some((lat, lng));
-        Ok((lat, lng))
+        let location = GeoPoint::new(lat, lng).map_err(|e| e.to_string())?;
+        self.last_location = Some(location);
+        Ok(location)
     }
     
     // Trigger emergency alert
@@ -119,7 +123,7 @@ impl SimpleEmergencySystem {
         let medical_condition = self.medical_info.as_ref()
             .map(|info| format!("Condition: {}, Blood Type: {}, Allergies: {}", 
                 info.condition,
-                info.blood_type.as_deref().unwrap_or("Unknown"),
+                info.blood_type.map_or("Unknown", |blood_type| blood_type.as_str()),
                 info.allergies.join(", ")
             ));
         
@@ -192,7 +196,7 @@ impl SimpleEmergencySystem {
     
     // Validate emergency system setup
     pub fn validate_setup(&self) -> Result<(), String> {
-        if self.patient_id.is_empty() {
+        if self.patient_id.as_str().is_empty() {
             return Err("Patient ID not set".to_string());
         }
         
@@ -217,7 +221,7 @@ pub fn create_medical_info(
     condition: String,
     medications: Vec<String>,
     allergies: Vec<String>,
-    blood_type: Option<String>,
+    blood_type: Option<BloodType>,
 ) -> MedicalInfo {
     MedicalInfo {
         condition,
@@ -0,0 +1,283 @@
+//! Envelope encryption for PHI stored on the client: a fresh data key per value, wrapped by a master key
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::{engine::general_purpose, Engine as _};
+use rand::{rngs::OsRng, RngCore};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::errors::{SharedError, SharedResult};
+use crate::secrets::SecretBytes;
+
+/// Cipher used for both key wrapping and field data
+pub const ENVELOPE_ALGORITHM: &str = "AES-256-GCM";
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// A 256-bit AES key, zeroed on drop
+#[derive(Clone)]
+pub struct DataKey([u8; KEY_LEN]);
+
+impl DataKey {
+    pub fn generate() -> Self {
+        // Fill in place so no unwiped copy is left on the stack
+        let mut key = Self([0u8; KEY_LEN]);
+        OsRng.fill_bytes(&mut key.0);
+        key
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> SharedResult<Self> {
+        let key: [u8; KEY_LEN] = bytes.try_into().map_err(|_| {
+            SharedError::CryptographicError(format!("Key must be {} bytes, got {}", KEY_LEN, bytes.len()))
+        })?;
+        Ok(Self(key))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; KEY_LEN] {
+        &self.0
+    }
+
+    /// Encrypt with a random nonce; returns `(nonce, ciphertext)`
+    pub(crate) fn seal(&self, plaintext: &[u8], aad: &[u8]) -> SharedResult<(Vec<u8>, Vec<u8>)> {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new(&self.0.into())
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+            .map_err(|_| SharedError::CryptographicError("Encryption failed".to_string()))?;
+        Ok((nonce.to_vec(), ciphertext))
+    }
+
+    pub(crate) fn open(&self, nonce: &[u8], ciphertext: &[u8], aad: &[u8]) -> SharedResult<SecretBytes> {
+        if nonce.len() != NONCE_LEN {
+            return Err(SharedError::CryptographicError("Invalid nonce length".to_string()));
+        }
+        Aes256Gcm::new(&self.0.into())
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad })
+            .map(SecretBytes::new)
+            .map_err(|_| SharedError::CryptographicError("Decryption failed: wrong key or tampered data".to_string()))
+    }
+}
+
+impl Drop for DataKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for DataKey {}
+
+impl fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DataKey([REDACTED])")
+    }
+}
+
+/// Key-encryption key. Only ever wraps data keys; never encrypts data directly.
+#[derive(Debug, Clone)]
+pub struct MasterKey {
+    pub key_id: String,
+    key: DataKey,
+}
+
+impl MasterKey {
+    pub fn new(key_id: impl Into<String>, key: DataKey) -> Self {
+        Self { key_id: key_id.into(), key }
+    }
+
+    pub fn generate(key_id: impl Into<String>) -> Self {
+        Self::new(key_id, DataKey::generate())
+    }
+
+    /// Key material as delivered by the key service, base64 encoded
+    pub fn from_base64(key_id: impl Into<String>, key_b64: &str) -> SharedResult<Self> {
+        let bytes = Zeroizing::new(general_purpose::STANDARD.decode(key_b64)?);
+        Ok(Self::new(key_id, DataKey::from_bytes(&bytes)?))
+    }
+
+    /// Encrypt a data key for storage next to the data it protects
+    pub fn wrap(&self, data_key: &DataKey) -> SharedResult<WrappedKey> {
+        let (nonce, ciphertext) = self.key.seal(&data_key.0, self.key_id.as_bytes())?;
+        Ok(WrappedKey {
+            master_key_id: self.key_id.clone(),
+            nonce: general_purpose::STANDARD.encode(nonce),
+            ciphertext: general_purpose::STANDARD.encode(ciphertext),
+        })
+    }
+
+    pub fn unwrap(&self, wrapped: &WrappedKey) -> SharedResult<DataKey> {
+        if wrapped.master_key_id != self.key_id {
+            return Err(SharedError::CryptographicError(format!(
+                "Data key was wrapped by master key '{}', not '{}'",
+                wrapped.master_key_id, self.key_id
+            )));
+        }
+        let nonce = general_purpose::STANDARD.decode(&wrapped.nonce)?;
+        let ciphertext = general_purpose::STANDARD.decode(&wrapped.ciphertext)?;
+        DataKey::from_bytes(self.key.open(&nonce, &ciphertext, self.key_id.as_bytes())?.expose())
+    }
+}
+
+/// A data key encrypted under a master key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WrappedKey {
+    pub master_key_id: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Stored form of an encrypted value. The value is bound to a context (the
+/// record and field), so a ciphertext copied elsewhere won't decrypt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncryptedField {
+    pub enc: String,
+    pub key: WrappedKey,
+    pub nonce: String,
+    pub data: String,
+}
+
+impl EncryptedField {
+    pub fn seal(master_key: &MasterKey, context: &str, plaintext: &[u8]) -> SharedResult<Self> {
+        let data_key = DataKey::generate();
+        let (nonce, ciphertext) = data_key.seal(plaintext, context.as_bytes())?;
+        Ok(Self {
+            enc: ENVELOPE_ALGORITHM.to_string(),
+            key: master_key.wrap(&data_key)?,
+            nonce: general_purpose::STANDARD.encode(nonce),
+            data: general_purpose::STANDARD.encode(ciphertext),
+        })
+    }
+
+    /// Decrypt to a buffer that is wiped when dropped
+    pub fn open(&self, master_key: &MasterKey, context: &str) -> SharedResult<SecretBytes> {
+        if self.enc != ENVELOPE_ALGORITHM {
+            return Err(SharedError::CryptographicError(format!("Unsupported algorithm: {}", self.enc)));
+        }
+        let data_key = master_key.unwrap(&self.key)?;
+        let nonce = general_purpose::STANDARD.decode(&self.nonce)?;
+        let ciphertext = general_purpose::STANDARD.decode(&self.data)?;
+        data_key.open(&nonce, &ciphertext, context.as_bytes())
+    }
+
+    /// Parse a stored value, or `None` if it is not an encrypted field
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let is_envelope = value.get("enc").and_then(Value::as_str) == Some(ENVELOPE_ALGORITHM);
+        is_envelope.then(|| serde_json::from_value(value.clone()).ok()).flatten()
+    }
+}
+
+fn field_mut<'a>(record: &'a mut Value, field: &str) -> SharedResult<&'a mut Value> {
+    record
+        .as_object_mut()
+        .ok_or_else(|| SharedError::ValidationError("Expected a JSON object".to_string()))?
+        .get_mut(field)
+        .ok_or_else(|| SharedError::NotFoundError(format!("Field '{}'", field)))
+}
+
+fn field_context(context_id: &str, field: &str) -> String {
+    format!("{}|{}", context_id, field)
+}
+
+/// Replace `record[field]` with its encrypted form, e.g. before caching a
+/// patient record. `context_id` names the record or patient it belongs to,
+/// so the ciphertext can't be moved to another record's field. Fields that
+/// are already encrypted are left alone.
+pub fn encrypt_json_field(record: &mut Value, field: &str, context_id: &str, master_key: &MasterKey) -> SharedResult<()> {
+    let value = field_mut(record, field)?;
+    if EncryptedField::from_value(value).is_some() {
+        return Ok(());
+    }
+    let context = field_context(context_id, field);
+    let sealed = EncryptedField::seal(master_key, &context, &Zeroizing::new(serde_json::to_vec(value)?))?;
+    *value = serde_json::to_value(sealed)?;
+    Ok(())
+}
+
+/// Restore `record[field]` encrypted by [`encrypt_json_field`] under the same `context_id`
+pub fn decrypt_json_field(record: &mut Value, field: &str, context_id: &str, master_key: &MasterKey) -> SharedResult<()> {
+    let value = field_mut(record, field)?;
+    let sealed = EncryptedField::from_value(value)
+        .ok_or_else(|| SharedError::ValidationError(format!("Field '{}' is not encrypted", field)))?;
+    *value = serde_json::from_slice(sealed.open(master_key, &field_context(context_id, field))?.expose())?;
+    Ok(())
+}
+
+/// Encrypt a whole value, e.g. a typed struct, under `context`
+pub fn encrypt_json<T: Serialize>(value: &T, context: &str, master_key: &MasterKey) -> SharedResult<EncryptedField> {
+    EncryptedField::seal(master_key, context, &Zeroizing::new(serde_json::to_vec(value)?))
+}
+
+pub fn decrypt_json<T: DeserializeOwned>(sealed: &EncryptedField, context: &str, master_key: &MasterKey) -> SharedResult<T> {
+    Ok(serde_json::from_slice(sealed.open(master_key, context)?.expose())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_field_round_trip_and_binding() {
+        let master = MasterKey::generate("device-key-1");
+        let original = json!({
+            "patient_id": "p-1",
+            "diagnosis": { "code": "E11.9", "text": "Type 2 diabetes" },
+            "notes": "Reports dizziness",
+        });
+        let mut record = original.clone();
+        encrypt_json_field(&mut record, "diagnosis", "rec-1", &master).unwrap();
+        encrypt_json_field(&mut record, "notes", "rec-1", &master).unwrap();
+        assert_eq!(record["patient_id"], "p-1");
+        assert!(!record.to_string().contains("diabetes"));
+        assert_eq!(record["notes"]["key"]["master_key_id"], "device-key-1");
+
+        // Encrypting twice is a no-op; each field gets its own data key
+        let before = record.clone();
+        encrypt_json_field(&mut record, "notes", "rec-1", &master).unwrap();
+        assert_eq!(record, before);
+        assert_ne!(record["notes"]["key"], record["diagnosis"]["key"]);
+
+        // A ciphertext moved to another field doesn't decrypt
+        let mut swapped = record.clone();
+        swapped["notes"] = record["diagnosis"].clone();
+        assert!(decrypt_json_field(&mut swapped, "notes", "rec-1", &master).is_err());
+        // ...nor does the same field copied into another patient's record
+        let mut other_record = record.clone();
+        assert!(decrypt_json_field(&mut other_record, "notes", "rec-2", &master).is_err());
+
+        decrypt_json_field(&mut record, "diagnosis", "rec-1", &master).unwrap();
+        decrypt_json_field(&mut record, "notes", "rec-1", &master).unwrap();
+        assert_eq!(record, original);
+        assert!(decrypt_json_field(&mut record, "notes", "rec-1", &master).is_err());
+        assert!(encrypt_json_field(&mut record, "missing", "rec-1", &master).is_err());
+    }
+
+    #[test]
+    fn test_wrong_key_and_tampering() {
+        let master = MasterKey::generate("k1");
+        let sealed = encrypt_json(&vec!["metformin", "lisinopril"], "medications", &master).unwrap();
+        let medications: Vec<String> = decrypt_json(&sealed, "medications", &master).unwrap();
+        assert_eq!(medications, vec!["metformin", "lisinopril"]);
+
+        assert!(decrypt_json::<Vec<String>>(&sealed, "medications", &MasterKey::generate("k1")).is_err());
+        assert!(decrypt_json::<Vec<String>>(&sealed, "medications", &MasterKey::generate("k2")).is_err());
+
+        let mut tampered = sealed.clone();
+        let mut data = general_purpose::STANDARD.decode(&tampered.data).unwrap();
+        data[0] ^= 1;
+        tampered.data = general_purpose::STANDARD.encode(data);
+        assert!(decrypt_json::<Vec<String>>(&tampered, "medications", &master).is_err());
+
+        // Master keys load from base64; wrapped keys survive storage
+        let raw = general_purpose::STANDARD.encode([7u8; 32]);
+        let loaded = MasterKey::from_base64("k3", &raw).unwrap();
+        let wrapped: WrappedKey = serde_json::from_str(&serde_json::to_string(&loaded.wrap(&DataKey::generate()).unwrap()).unwrap()).unwrap();
+        assert!(loaded.unwrap(&wrapped).is_ok());
+        assert!(MasterKey::from_base64("k4", "c2hvcnQ=").is_err());
+        assert_eq!(format!("{:?}", DataKey::generate()), "DataKey([REDACTED])");
+    }
+}
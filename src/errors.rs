@@ -1,8 +1,165 @@
 //! Error types and handling for MyDR24 shared library
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-/// Shared error types for MyDR24 platform
+/// Stable, machine-readable error code. The serialized strings are part of the
+/// API contract: clients branch on them, so never renumber or reuse one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorCode {
+    #[serde(rename = "AUTH_001")]
+    Authentication,
+    #[serde(rename = "AUTH_002")]
+    Authorization,
+    #[serde(rename = "VAL_001")]
+    Validation,
+    #[serde(rename = "DB_001")]
+    Database,
+    #[serde(rename = "NET_001")]
+    Network,
+    #[serde(rename = "CRYPTO_001")]
+    Cryptographic,
+    #[serde(rename = "CONFIG_001")]
+    Configuration,
+    #[serde(rename = "COMP_001")]
+    Compliance,
+    #[serde(rename = "HIPAA_001")]
+    HipaaViolation,
+    #[serde(rename = "GDPR_001")]
+    GdprViolation,
+    #[serde(rename = "AUDIT_001")]
+    Audit,
+    #[serde(rename = "RATE_001")]
+    RateLimited,
+    #[serde(rename = "NOT_FOUND_001")]
+    NotFound,
+    #[serde(rename = "SVC_001")]
+    ServiceUnavailable,
+    #[serde(rename = "TIMEOUT_001")]
+    Timeout,
+    #[serde(rename = "SER_001")]
+    Serialization,
+    #[serde(rename = "MED_001")]
+    MedicalRecord,
+    #[serde(rename = "SCHED_001")]
+    Scheduling,
+    #[serde(rename = "PAY_001")]
+    Payment,
+    #[serde(rename = "WS_001")]
+    WebSocket,
+    #[serde(rename = "EMRG_001")]
+    Emergency,
+    #[serde(rename = "INT_001")]
+    Integration,
+    #[serde(rename = "INT_500")]
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Authentication => "AUTH_001",
+            ErrorCode::Authorization => "AUTH_002",
+            ErrorCode::Validation => "VAL_001",
+            ErrorCode::Database => "DB_001",
+            ErrorCode::Network => "NET_001",
+            ErrorCode::Cryptographic => "CRYPTO_001",
+            ErrorCode::Configuration => "CONFIG_001",
+            ErrorCode::Compliance => "COMP_001",
+            ErrorCode::HipaaViolation => "HIPAA_001",
+            ErrorCode::GdprViolation => "GDPR_001",
+            ErrorCode::Audit => "AUDIT_001",
+            ErrorCode::RateLimited => "RATE_001",
+            ErrorCode::NotFound => "NOT_FOUND_001",
+            ErrorCode::ServiceUnavailable => "SVC_001",
+            ErrorCode::Timeout => "TIMEOUT_001",
+            ErrorCode::Serialization => "SER_001",
+            ErrorCode::MedicalRecord => "MED_001",
+            ErrorCode::Scheduling => "SCHED_001",
+            ErrorCode::Payment => "PAY_001",
+            ErrorCode::WebSocket => "WS_001",
+            ErrorCode::Emergency => "EMRG_001",
+            ErrorCode::Integration => "INT_001",
+            ErrorCode::Internal => "INT_500",
+        }
+    }
+
+    /// Whether the same request may succeed if repeated later. Rate limits are
+    /// left out: callers that retry these would hammer a throttled endpoint, so
+    /// 429s go through `RetryPolicy`, which waits for `Retry-After`.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::Network
+                | ErrorCode::ServiceUnavailable
+                | ErrorCode::Timeout
+                | ErrorCode::Database
+        )
+    }
+
+    /// HTTP status equivalent
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ErrorCode::Authentication => 401,
+            ErrorCode::Authorization => 403,
+            ErrorCode::Validation => 400,
+            ErrorCode::NotFound => 404,
+            ErrorCode::RateLimited => 429,
+            ErrorCode::ServiceUnavailable => 503,
+            ErrorCode::Timeout => 408,
+            ErrorCode::HipaaViolation | ErrorCode::GdprViolation => 451,
+            _ => 500,
+        }
+    }
+
+    /// Closest code for an HTTP error status, for errors reported by the backend
+    pub fn from_http_status(status: u16) -> Self {
+        match status {
+            401 => ErrorCode::Authentication,
+            403 => ErrorCode::Authorization,
+            404 | 410 => ErrorCode::NotFound,
+            408 | 504 => ErrorCode::Timeout,
+            429 => ErrorCode::RateLimited,
+            451 => ErrorCode::Compliance,
+            502 | 503 => ErrorCode::ServiceUnavailable,
+            400..=499 => ErrorCode::Validation,
+            _ => ErrorCode::Internal,
+        }
+    }
+
+    /// Text safe to show to patients and providers; never includes internal details
+    pub fn user_message(&self) -> &'static str {
+        match self {
+            ErrorCode::Authentication => "Please sign in again to continue.",
+            ErrorCode::Authorization => "You don't have permission to do that.",
+            ErrorCode::Validation => "Some of the details entered are not valid.",
+            ErrorCode::NotFound => "We couldn't find what you were looking for.",
+            ErrorCode::RateLimited => "Too many attempts. Please wait a moment and try again.",
+            ErrorCode::Network => "You appear to be offline. Check your connection and try again.",
+            ErrorCode::ServiceUnavailable | ErrorCode::Timeout | ErrorCode::Database => {
+                "The service is busy right now. Please try again shortly."
+            }
+            ErrorCode::Scheduling => "That appointment time is no longer available.",
+            ErrorCode::Payment => "The payment could not be processed.",
+            ErrorCode::Emergency => {
+                "We couldn't send the emergency alert. If this is an emergency, call 112."
+            }
+            ErrorCode::HipaaViolation | ErrorCode::GdprViolation | ErrorCode::Compliance => {
+                "This action isn't allowed under our data protection rules."
+            }
+            _ => "Something went wrong. Please try again later.",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Shared error types for MyDR24 platform. `Display` gives the diagnostic
+/// message for logs; show [`user_message`](Self::user_message) to end users.
 #[derive(Error, Debug, Clone)]
 pub enum SharedError {
     #[error("Authentication error: {0}")]
@@ -79,11 +236,15 @@ pub enum SharedError {
 pub type SharedResult<T> = Result<T, SharedError>;
 
 /// Error context for debugging and audit trails
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorContext {
     pub error_id: uuid::Uuid,
     pub error_code: String,
     pub error_message: String,
+    #[serde(default)]
+    pub user_message: String,
+    #[serde(default)]
+    pub retryable: bool,
     pub user_id: Option<uuid::Uuid>,
     pub request_id: Option<uuid::Uuid>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
@@ -98,6 +259,8 @@ impl ErrorContext {
             error_id: uuid::Uuid::new_v4(),
             error_code: error.error_code(),
             error_message: error.to_string(),
+            user_message: error.user_message(),
+            retryable: error.is_retryable(),
             user_id: None,
             request_id: None,
             timestamp: chrono::Utc::now(),
@@ -124,44 +287,109 @@ impl ErrorContext {
 }
 
 impl SharedError {
+    /// Build an error from a code, e.g. one received from another service
+    pub fn new(code: ErrorCode, details: impl Into<String>) -> Self {
+        let details = details.into();
+        match code {
+            ErrorCode::Authentication => SharedError::AuthenticationError(details),
+            ErrorCode::Authorization => SharedError::AuthorizationError(details),
+            ErrorCode::Validation => SharedError::ValidationError(details),
+            ErrorCode::Database => SharedError::DatabaseError(details),
+            ErrorCode::Network => SharedError::NetworkError(details),
+            ErrorCode::Cryptographic => SharedError::CryptographicError(details),
+            ErrorCode::Configuration => SharedError::ConfigurationError(details),
+            ErrorCode::Compliance => SharedError::ComplianceError(details),
+            ErrorCode::HipaaViolation => SharedError::HipaaViolation(details),
+            ErrorCode::GdprViolation => SharedError::GdprViolation(details),
+            ErrorCode::Audit => SharedError::AuditError(details),
+            ErrorCode::RateLimited => SharedError::RateLimitError(details),
+            ErrorCode::NotFound => SharedError::NotFoundError(details),
+            ErrorCode::ServiceUnavailable => SharedError::ServiceUnavailableError(details),
+            ErrorCode::Timeout => SharedError::TimeoutError(details),
+            ErrorCode::Serialization => SharedError::SerializationError(details),
+            ErrorCode::MedicalRecord => SharedError::MedicalRecordError(details),
+            ErrorCode::Scheduling => SharedError::SchedulingError(details),
+            ErrorCode::Payment => SharedError::PaymentError(details),
+            ErrorCode::WebSocket => SharedError::WebSocketError(details),
+            ErrorCode::Emergency => SharedError::EmergencyError(details),
+            ErrorCode::Integration => SharedError::IntegrationError(details),
+            ErrorCode::Internal => SharedError::InternalError(details),
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SharedError::AuthenticationError(_) => ErrorCode::Authentication,
+            SharedError::AuthorizationError(_) => ErrorCode::Authorization,
+            SharedError::ValidationError(_) => ErrorCode::Validation,
+            SharedError::DatabaseError(_) => ErrorCode::Database,
+            SharedError::NetworkError(_) => ErrorCode::Network,
+            SharedError::CryptographicError(_) => ErrorCode::Cryptographic,
+            SharedError::ConfigurationError(_) => ErrorCode::Configuration,
+            SharedError::ComplianceError(_) => ErrorCode::Compliance,
+            SharedError::HipaaViolation(_) => ErrorCode::HipaaViolation,
+            SharedError::GdprViolation(_) => ErrorCode::GdprViolation,
+            SharedError::AuditError(_) => ErrorCode::Audit,
+            SharedError::RateLimitError(_) => ErrorCode::RateLimited,
+            SharedError::NotFoundError(_) => ErrorCode::NotFound,
+            SharedError::ServiceUnavailableError(_) => ErrorCode::ServiceUnavailable,
+            SharedError::TimeoutError(_) => ErrorCode::Timeout,
+            SharedError::SerializationError(_) => ErrorCode::Serialization,
+            SharedError::MedicalRecordError(_) => ErrorCode::MedicalRecord,
+            SharedError::SchedulingError(_) => ErrorCode::Scheduling,
+            SharedError::PaymentError(_) => ErrorCode::Payment,
+            SharedError::WebSocketError(_) => ErrorCode::WebSocket,
+            SharedError::EmergencyError(_) => ErrorCode::Emergency,
+            SharedError::IntegrationError(_) => ErrorCode::Integration,
+            SharedError::InternalError(_) => ErrorCode::Internal,
+        }
+    }
+
     /// Get error code for classification and monitoring
     pub fn error_code(&self) -> String {
+        self.code().as_str().to_string()
+    }
+
+    /// Diagnostic detail without the category prefix; may contain internal
+    /// information, so log it rather than showing it
+    pub fn details(&self) -> &str {
         match self {
-            SharedError::AuthenticationError(_) => "AUTH_001".to_string(),
-            SharedError::AuthorizationError(_) => "AUTH_002".to_string(),
-            SharedError::ValidationError(_) => "VAL_001".to_string(),
-            SharedError::DatabaseError(_) => "DB_001".to_string(),
-            SharedError::NetworkError(_) => "NET_001".to_string(),
-            SharedError::CryptographicError(_) => "CRYPTO_001".to_string(),
-            SharedError::ConfigurationError(_) => "CONFIG_001".to_string(),
-            SharedError::ComplianceError(_) => "COMP_001".to_string(),
-            SharedError::HipaaViolation(_) => "HIPAA_001".to_string(),
-            SharedError::GdprViolation(_) => "GDPR_001".to_string(),
-            SharedError::AuditError(_) => "AUDIT_001".to_string(),
-            SharedError::RateLimitError(_) => "RATE_001".to_string(),
-            SharedError::NotFoundError(_) => "NOT_FOUND_001".to_string(),
-            SharedError::ServiceUnavailableError(_) => "SVC_001".to_string(),
-            SharedError::TimeoutError(_) => "TIMEOUT_001".to_string(),
-            SharedError::SerializationError(_) => "SER_001".to_string(),
-            SharedError::MedicalRecordError(_) => "MED_001".to_string(),
-            SharedError::SchedulingError(_) => "SCHED_001".to_string(),
-            SharedError::PaymentError(_) => "PAY_001".to_string(),
-            SharedError::WebSocketError(_) => "WS_001".to_string(),
-            SharedError::EmergencyError(_) => "EMRG_001".to_string(),
-            SharedError::IntegrationError(_) => "INT_001".to_string(),
-            SharedError::InternalError(_) => "INT_500".to_string(),
+            SharedError::AuthenticationError(details)
+            | SharedError::AuthorizationError(details)
+            | SharedError::ValidationError(details)
+            | SharedError::DatabaseError(details)
+            | SharedError::NetworkError(details)
+            | SharedError::CryptographicError(details)
+            | SharedError::ConfigurationError(details)
+            | SharedError::ComplianceError(details)
+            | SharedError::HipaaViolation(details)
+            | SharedError::GdprViolation(details)
+            | SharedError::AuditError(details)
+            | SharedError::RateLimitError(details)
+            | SharedError::NotFoundError(details)
+            | SharedError::ServiceUnavailableError(details)
+            | SharedError::TimeoutError(details)
+            | SharedError::SerializationError(details)
+            | SharedError::MedicalRecordError(details)
+            | SharedError::SchedulingError(details)
+            | SharedError::PaymentError(details)
+            | SharedError::WebSocketError(details)
+            | SharedError::EmergencyError(details)
+            | SharedError::IntegrationError(details)
+            | SharedError::InternalError(details) => details,
         }
     }
 
+    /// Message for end users: generic text for the error's code. Details,
+    /// validation ones included, can echo stored values or internal names, so
+    /// they stay in logs; forms show their own per-field text.
+    pub fn user_message(&self) -> String {
+        self.code().user_message().to_string()
+    }
+
     /// Check if error is retryable
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            SharedError::NetworkError(_)
-                | SharedError::ServiceUnavailableError(_)
-                | SharedError::TimeoutError(_)
-                | SharedError::DatabaseError(_)
-        )
+        self.code().is_retryable()
     }
 
     /// Check if error requires immediate attention
@@ -191,17 +419,7 @@ impl SharedError {
 
     /// Get HTTP status code equivalent
     pub fn http_status_code(&self) -> u16 {
-        match self {
-            SharedError::AuthenticationError(_) => 401,
-            SharedError::AuthorizationError(_) => 403,
-            SharedError::ValidationError(_) => 400,
-            SharedError::NotFoundError(_) => 404,
-            SharedError::RateLimitError(_) => 429,
-            SharedError::ServiceUnavailableError(_) => 503,
-            SharedError::TimeoutError(_) => 408,
-            SharedError::HipaaViolation(_) | SharedError::GdprViolation(_) => 451,
-            _ => 500,
-        }
+        self.code().http_status()
     }
 }
 
@@ -271,6 +489,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_codes_are_stable_and_round_trip() {
+        assert_eq!(serde_json::to_string(&ErrorCode::NotFound).unwrap(), "\"NOT_FOUND_001\"");
+        assert_eq!(serde_json::from_str::<ErrorCode>("\"RATE_001\"").unwrap(), ErrorCode::RateLimited);
+
+        let error = SharedError::new(ErrorCode::Timeout, "upstream took 30s");
+        assert!(matches!(error, SharedError::TimeoutError(_)));
+        assert_eq!(error.code(), ErrorCode::Timeout);
+        assert_eq!(error.details(), "upstream took 30s");
+        assert!(error.is_retryable());
+        assert!(!SharedError::RateLimitError("login".to_string()).is_retryable());
+        assert_eq!(ErrorCode::from_http_status(503), ErrorCode::ServiceUnavailable);
+        assert_eq!(ErrorCode::from_http_status(422), ErrorCode::Validation);
+    }
+
+    #[test]
+    fn test_application_errors_convert() {
+        use crate::ApplicationError;
+
+        let shared = SharedError::from(ApplicationError::ValidationError("fee must be positive".to_string()));
+        assert_eq!(shared.code(), ErrorCode::Validation);
+        assert_eq!(shared.details(), "fee must be positive");
+
+        let upstream = ApplicationError::from(SharedError::TimeoutError("maps API".to_string()));
+        assert!(matches!(upstream, ApplicationError::ExternalServiceError(_)));
+        assert!(upstream.is_retryable());
+        assert_eq!(SharedError::from(upstream).code(), ErrorCode::ServiceUnavailable);
+    }
+
+    #[test]
+    fn test_user_messages_hide_diagnostics() {
+        let database = SharedError::DatabaseError("connection to 10.0.3.7:5432 refused".to_string());
+        assert!(!database.user_message().contains("10.0.3.7"));
+        assert!(database.to_string().contains("10.0.3.7"));
+
+        let validation = SharedError::ValidationError("Implausible heart rate reading: 720 bpm".to_string());
+        assert_eq!(validation.user_message(), "Some of the details entered are not valid.");
+    }
+
     #[test]
     fn test_error_context() {
         let error = SharedError::ValidationError("test error".to_string());
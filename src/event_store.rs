@@ -0,0 +1,401 @@
+//! Durable, append-only record of domain events for replay, debugging and audit
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+use crate::errors::{SharedError, SharedResult};
+
+/// An event type that can be recorded in an [`EventStore`]. `EVENT_TYPE` is
+/// stored with each record, so it must stay stable once events are written.
+pub trait DomainEvent: Serialize + DeserializeOwned + Send + Sync + 'static {
+    const EVENT_TYPE: &'static str;
+    /// Bump when the payload shape changes incompatibly
+    const SCHEMA_VERSION: u32 = 1;
+
+    /// Entity the event is about, e.g. an appointment or referral id
+    fn aggregate_id(&self) -> Option<String> {
+        None
+    }
+}
+
+/// An event waiting to be appended; the store assigns its sequence number
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewEvent {
+    pub event_type: String,
+    pub schema_version: u32,
+    pub aggregate_id: Option<String>,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl NewEvent {
+    pub fn from_domain<T: DomainEvent>(event: &T) -> SharedResult<Self> {
+        Ok(Self {
+            event_type: T::EVENT_TYPE.to_string(),
+            schema_version: T::SCHEMA_VERSION,
+            aggregate_id: event.aggregate_id(),
+            payload: serde_json::to_value(event)?,
+            occurred_at: Utc::now(),
+        })
+    }
+}
+
+/// An event as recorded, with its position in the store
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredEvent {
+    /// Starts at 1 and increases by one per append
+    pub sequence: u64,
+    pub event_type: String,
+    pub schema_version: u32,
+    pub aggregate_id: Option<String>,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl StoredEvent {
+    fn new(sequence: u64, event: NewEvent) -> Self {
+        Self {
+            sequence,
+            event_type: event.event_type,
+            schema_version: event.schema_version,
+            aggregate_id: event.aggregate_id,
+            payload: event.payload,
+            occurred_at: event.occurred_at,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    pub fn is<T: DomainEvent>(&self) -> bool {
+        self.event_type == T::EVENT_TYPE
+    }
+
+    /// The payload as `T`, or `None` if this record holds a different event type
+    pub fn decode<T: DomainEvent>(&self) -> SharedResult<Option<T>> {
+        if !self.is::<T>() {
+            return Ok(None);
+        }
+        if self.schema_version > T::SCHEMA_VERSION {
+            return Err(SharedError::SerializationError(format!(
+                "{} v{} is newer than supported v{}",
+                self.event_type,
+                self.schema_version,
+                T::SCHEMA_VERSION
+            )));
+        }
+        Ok(Some(serde_json::from_value(self.payload.clone())?))
+    }
+}
+
+/// Derived state saved at a sequence number, so replay can resume from there
+/// instead of from the first event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventSnapshot {
+    /// Which projection the state belongs to, e.g. "appointment_stats"
+    pub name: String,
+    /// Last event sequence folded into `state`
+    pub sequence: u64,
+    pub state: serde_json::Value,
+    pub taken_at: DateTime<Utc>,
+}
+
+impl EventSnapshot {
+    pub fn new<S: Serialize>(name: &str, sequence: u64, state: &S) -> SharedResult<Self> {
+        Ok(Self {
+            name: name.to_string(),
+            sequence,
+            state: serde_json::to_value(state)?,
+            taken_at: Utc::now(),
+        })
+    }
+
+    pub fn decode<S: DeserializeOwned>(&self) -> SharedResult<S> {
+        Ok(serde_json::from_value(self.state.clone())?)
+    }
+}
+
+/// Append-only event log
+pub trait EventStore: Send + Sync {
+    fn append(&self, event: NewEvent) -> SharedResult<StoredEvent>;
+
+    /// Events with a sequence greater than `after`, oldest first, at most `limit`
+    fn read_since(&self, after: u64, limit: Option<usize>) -> SharedResult<Vec<StoredEvent>>;
+
+    /// Save `snapshot`, replacing any earlier snapshot with the same name
+    fn snapshot(&self, snapshot: EventSnapshot) -> SharedResult<()>;
+
+    fn latest_snapshot(&self, name: &str) -> SharedResult<Option<EventSnapshot>>;
+
+    /// Sequence of the newest event, 0 when empty
+    fn last_sequence(&self) -> SharedResult<u64>;
+}
+
+/// Record a typed domain event
+pub fn append_event<T: DomainEvent>(store: &dyn EventStore, event: &T) -> SharedResult<StoredEvent> {
+    store.append(NewEvent::from_domain(event)?)
+}
+
+/// Replay stored events after `after` through `apply`, oldest first, in pages.
+/// Returns the sequence of the last event replayed.
+pub fn replay<F>(store: &dyn EventStore, after: u64, mut apply: F) -> SharedResult<u64>
+where
+    F: FnMut(&StoredEvent) -> SharedResult<()>,
+{
+    const PAGE: usize = 500;
+    let mut last = after;
+    loop {
+        let page = store.read_since(last, Some(PAGE))?;
+        for event in &page {
+            apply(event)?;
+            last = event.sequence;
+        }
+        if page.len() < PAGE {
+            return Ok(last);
+        }
+    }
+}
+
+fn poisoned(e: impl std::fmt::Display) -> SharedError {
+    SharedError::InternalError(format!("Event store poisoned: {}", e))
+}
+
+#[derive(Debug, Default)]
+struct MemoryLog {
+    events: Vec<StoredEvent>,
+    snapshots: Vec<EventSnapshot>,
+}
+
+/// Non-persistent store for tests and short-lived sessions
+#[derive(Debug, Clone, Default)]
+pub struct MemoryEventStore {
+    log: Arc<Mutex<MemoryLog>>,
+}
+
+impl MemoryEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EventStore for MemoryEventStore {
+    fn append(&self, event: NewEvent) -> SharedResult<StoredEvent> {
+        let mut log = self.log.lock().map_err(poisoned)?;
+        let stored = StoredEvent::new(log.events.len() as u64 + 1, event);
+        log.events.push(stored.clone());
+        Ok(stored)
+    }
+
+    fn read_since(&self, after: u64, limit: Option<usize>) -> SharedResult<Vec<StoredEvent>> {
+        let log = self.log.lock().map_err(poisoned)?;
+        // Sequences are dense from 1, so `after` is also the index of the next event
+        let start = (after as usize).min(log.events.len());
+        let end = limit.map_or(log.events.len(), |limit| (start + limit).min(log.events.len()));
+        Ok(log.events[start..end].to_vec())
+    }
+
+    fn snapshot(&self, snapshot: EventSnapshot) -> SharedResult<()> {
+        let mut log = self.log.lock().map_err(poisoned)?;
+        log.snapshots.retain(|s| s.name != snapshot.name);
+        log.snapshots.push(snapshot);
+        Ok(())
+    }
+
+    fn latest_snapshot(&self, name: &str) -> SharedResult<Option<EventSnapshot>> {
+        let log = self.log.lock().map_err(poisoned)?;
+        Ok(log.snapshots.iter().find(|s| s.name == name).cloned())
+    }
+
+    fn last_sequence(&self) -> SharedResult<u64> {
+        Ok(self.log.lock().map_err(poisoned)?.events.len() as u64)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use file::FileEventStore;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod file {
+    use super::*;
+    use std::fs::{File, OpenOptions};
+    use std::io::{BufRead, BufReader, Write};
+    use std::path::{Path, PathBuf};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "record", rename_all = "snake_case")]
+    enum Record {
+        Event(StoredEvent),
+        Snapshot(EventSnapshot),
+    }
+
+    /// Store backed by a JSON-lines file, one record per line. Appends are
+    /// flushed before returning; snapshots are appended to the same file and
+    /// the newest one per name wins.
+    #[derive(Debug, Clone)]
+    pub struct FileEventStore {
+        path: PathBuf,
+        last_sequence: Arc<Mutex<u64>>,
+    }
+
+    impl FileEventStore {
+        /// Open `path`, creating it if needed
+        pub fn open(path: impl AsRef<Path>) -> SharedResult<Self> {
+            let path = path.as_ref().to_path_buf();
+            OpenOptions::new().create(true).append(true).open(&path).map_err(|e| io_error(&path, e))?;
+            let mut last_sequence = 0;
+            for record in read_records(&path)? {
+                if let Record::Event(event) = record {
+                    last_sequence = event.sequence;
+                }
+            }
+            Ok(Self { path, last_sequence: Arc::new(Mutex::new(last_sequence)) })
+        }
+
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+
+        fn write(&self, record: &Record) -> SharedResult<()> {
+            let mut line = serde_json::to_string(record)?;
+            line.push('\n');
+            let mut file = OpenOptions::new()
+                .append(true)
+                .open(&self.path)
+                .map_err(|e| io_error(&self.path, e))?;
+            file.write_all(line.as_bytes())
+                .and_then(|_| file.sync_data())
+                .map_err(|e| io_error(&self.path, e))
+        }
+    }
+
+    fn io_error(path: &Path, error: std::io::Error) -> SharedError {
+        SharedError::DatabaseError(format!("Event log {}: {}", path.display(), error))
+    }
+
+    fn read_records(path: &Path) -> SharedResult<Vec<Record>> {
+        let file = File::open(path).map_err(|e| io_error(path, e))?;
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| io_error(path, e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(record) => records.push(record),
+                // A crash mid-write can leave a partial last line; skip it
+                Err(e) => log::warn!("Skipping unreadable event log line in {}: {}", path.display(), e),
+            }
+        }
+        Ok(records)
+    }
+
+    impl EventStore for FileEventStore {
+        fn append(&self, event: NewEvent) -> SharedResult<StoredEvent> {
+            // Held across the write so sequences stay in file order
+            let mut last_sequence = self.last_sequence.lock().map_err(poisoned)?;
+            let stored = StoredEvent::new(*last_sequence + 1, event);
+            self.write(&Record::Event(stored.clone()))?;
+            *last_sequence = stored.sequence;
+            Ok(stored)
+        }
+
+        fn read_since(&self, after: u64, limit: Option<usize>) -> SharedResult<Vec<StoredEvent>> {
+            let events = read_records(&self.path)?.into_iter().filter_map(|record| match record {
+                Record::Event(event) if event.sequence > after => Some(event),
+                _ => None,
+            });
+            Ok(match limit {
+                Some(limit) => events.take(limit).collect(),
+                None => events.collect(),
+            })
+        }
+
+        fn snapshot(&self, snapshot: EventSnapshot) -> SharedResult<()> {
+            let _guard = self.last_sequence.lock().map_err(poisoned)?;
+            self.write(&Record::Snapshot(snapshot))
+        }
+
+        fn latest_snapshot(&self, name: &str) -> SharedResult<Option<EventSnapshot>> {
+            Ok(read_records(&self.path)?.into_iter().rev().find_map(|record| match record {
+                Record::Snapshot(snapshot) if snapshot.name == name => Some(snapshot),
+                _ => None,
+            }))
+        }
+
+        fn last_sequence(&self) -> SharedResult<u64> {
+            Ok(*self.last_sequence.lock().map_err(poisoned)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct ReferralCompleted {
+        referral_id: String,
+        points: u32,
+    }
+
+    impl DomainEvent for ReferralCompleted {
+        const EVENT_TYPE: &'static str = "referral_completed";
+
+        fn aggregate_id(&self) -> Option<String> {
+            Some(self.referral_id.clone())
+        }
+    }
+
+    fn exercise(store: &dyn EventStore) {
+        for points in [10, 20, 30] {
+            let event = ReferralCompleted { referral_id: format!("ref-{}", points), points };
+            append_event(store, &event).unwrap();
+        }
+        assert_eq!(store.last_sequence().unwrap(), 3);
+
+        let since = store.read_since(1, None).unwrap();
+        assert_eq!(since.iter().map(|e| e.sequence).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(since[0].aggregate_id.as_deref(), Some("ref-20"));
+        assert_eq!(store.read_since(0, Some(1)).unwrap().len(), 1);
+
+        let mut total = 0;
+        let last = replay(store, 0, |event| {
+            total += event.decode::<ReferralCompleted>()?.map_or(0, |e| e.points);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!((last, total), (3, 60));
+
+        store.snapshot(EventSnapshot::new("points", 2, &30u32).unwrap()).unwrap();
+        store.snapshot(EventSnapshot::new("points", 3, &60u32).unwrap()).unwrap();
+        let snapshot = store.latest_snapshot("points").unwrap().unwrap();
+        assert_eq!((snapshot.sequence, snapshot.decode::<u32>().unwrap()), (3, 60));
+        assert!(store.latest_snapshot("other").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_memory_event_store() {
+        exercise(&MemoryEventStore::new());
+    }
+
+    #[test]
+    fn test_file_event_store_survives_reopen() {
+        let path = std::env::temp_dir().join(format!("mydr24-events-{}.jsonl", uuid::Uuid::new_v4()));
+        exercise(&FileEventStore::open(&path).unwrap());
+
+        let reopened = FileEventStore::open(&path).unwrap();
+        assert_eq!(reopened.last_sequence().unwrap(), 3);
+        let next = append_event(&reopened, &ReferralCompleted { referral_id: "ref-40".to_string(), points: 40 }).unwrap();
+        assert_eq!(next.sequence, 4);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_decode_rejects_newer_schema() {
+        let mut event = NewEvent::from_domain(&ReferralCompleted { referral_id: "r".to_string(), points: 1 }).unwrap();
+        event.schema_version = 2;
+        let stored = MemoryEventStore::new().append(event).unwrap();
+        assert!(stored.decode::<ReferralCompleted>().is_err());
+    }
+}
@@ -1,9 +1,17 @@
-//! WebSocket events and real-time messaging structures
+//! WebSocket events, real-time messaging structures and the in-process event bus
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+use crate::geo::GeoPoint;
+use crate::ids::{AppointmentId, BookingId, ProviderId};
 use crate::models::Appointment;
 
 /// WebSocket event types for real-time communication
@@ -17,8 +25,8 @@ pub enum WebSocketEvent {
     // Appointment events
     AppointmentScheduled { appointment: Appointment },
     AppointmentUpdated { appointment: Appointment },
-    AppointmentCancelled { appointment_id: Uuid, reason: String },
-    AppointmentReminder { appointment_id: Uuid, minutes_until: u32 },
+    AppointmentCancelled { appointment_id: AppointmentId, reason: String },
+    AppointmentReminder { appointment_id: AppointmentId, minutes_until: u32 },
     
     // Chat and communication
     ChatMessage { message: ChatMessage },
@@ -29,11 +37,11 @@ pub enum WebSocketEvent {
     SystemNotification { notification: SystemNotification },
     
     // Provider location and availability
-    ProviderLocationUpdate { provider_id: Uuid, location: GeoLocation },
-    ProviderAvailabilityUpdate { provider_id: Uuid, is_available: bool },
+    ProviderLocationUpdate { provider_id: ProviderId, location: GeoLocation },
+    ProviderAvailabilityUpdate { provider_id: ProviderId, is_available: bool },
     
     // Booking and status updates
-    BookingStatusUpdate { booking_id: Uuid, status: String },
+    BookingStatusUpdate { booking_id: BookingId, status: String },
     PaymentStatusUpdate { payment_id: Uuid, status: String },
     
     // System events
@@ -50,7 +58,7 @@ pub enum WebSocketEvent {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ChatMessage {
     pub id: Uuid,
-    pub appointment_id: Uuid,
+    pub appointment_id: AppointmentId,
     pub sender_id: Uuid,
     pub sender_role: String, // "patient", "provider", "system"
     pub message: String,
@@ -166,6 +174,12 @@ pub struct GeoLocation {
     pub country: Option<String>,
 }
 
+impl GeoLocation {
+    pub fn point(&self) -> GeoPoint {
+        GeoPoint { latitude: self.latitude, longitude: self.longitude }
+    }
+}
+
 /// Server status information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ServerStatus {
@@ -228,9 +242,251 @@ pub struct ConnectionInfo {
     pub user_agent: Option<String>,
 }
 
+/// Events raised locally by the shared library for the host app (never sent over the socket)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "data")]
+pub enum ClientEvent {
+    ApiRequestCompleted(ApiRequestCompleted),
+}
+
+/// Outcome and timing of a single API request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiRequestCompleted {
+    pub correlation_id: String,
+    pub method: String,
+    pub path: String,
+    pub status: Option<u16>, // None for transport failures
+    pub elapsed_ms: f64,
+    pub error: Option<String>,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl ApiRequestCompleted {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none() && matches!(self.status, Some(200..=399))
+    }
+}
+
+pub type ClientEventListener = Arc<dyn Fn(&ClientEvent) + Send + Sync + 'static>;
+
+/// Fan-out of client events to registered listeners
+#[derive(Clone, Default)]
+pub struct ClientEventEmitter {
+    listeners: Arc<Mutex<Vec<ClientEventListener>>>,
+}
+
+impl std::fmt::Debug for ClientEventEmitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientEventEmitter")
+            .field("listeners", &self.listener_count())
+            .finish()
+    }
+}
+
+impl ClientEventEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe<F>(&self, listener: F)
+    where
+        F: Fn(&ClientEvent) + Send + Sync + 'static,
+    {
+        if let Ok(mut listeners) = self.listeners.lock() {
+            listeners.push(Arc::new(listener));
+        }
+    }
+
+    pub fn emit(&self, event: &ClientEvent) {
+        // Clone the list so listeners may subscribe without deadlocking
+        let listeners = self.listeners.lock().map(|l| l.clone()).unwrap_or_default();
+        for listener in listeners {
+            listener(event);
+        }
+    }
+
+    pub fn listener_count(&self) -> usize {
+        self.listeners.lock().map(|l| l.len()).unwrap_or(0)
+    }
+}
+
+/// Future returned by async [`EventBus`] subscribers; not `Send` so handlers can await browser APIs
+pub type EventFuture = Pin<Box<dyn Future<Output = ()> + 'static>>;
+
+/// Runs async subscriber futures when events are published with [`EventBus::publish`],
+/// e.g. `wasm_bindgen_futures::spawn_local` in the browser
+pub type EventSpawner = Arc<dyn Fn(EventFuture) + Send + Sync + 'static>;
+
+type SyncHandler = Arc<dyn Fn(&(dyn Any + Send + Sync)) + Send + Sync + 'static>;
+type AsyncHandler = Arc<dyn Fn(Arc<dyn Any + Send + Sync>) -> EventFuture + Send + Sync + 'static>;
+
+#[derive(Clone)]
+enum EventHandler {
+    Sync(SyncHandler),
+    Async(AsyncHandler),
+}
+
+type HandlerRegistry = HashMap<TypeId, Vec<(u64, EventHandler)>>;
+
+/// Handle returned by [`EventBus::subscribe`], used to unsubscribe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Subscription {
+    id: u64,
+    event_type: TypeId,
+}
+
+/// In-process publish/subscribe keyed by event type, so the UI, WebSocket
+/// client and service engine can react to each other's events without
+/// holding references to one another. Any `Send + Sync + 'static` type can be
+/// an event; subscribers only see events of the type they subscribed to.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    handlers: Arc<Mutex<HandlerRegistry>>,
+    next_id: Arc<AtomicU64>,
+    spawner: Option<EventSpawner>,
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let subscribers: usize = self.handlers.lock().map(|h| h.values().map(Vec::len).sum()).unwrap_or(0);
+        f.debug_struct("EventBus")
+            .field("subscribers", &subscribers)
+            .field("spawner", &self.spawner.is_some())
+            .finish()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run async subscribers on `spawner` when [`publish`](Self::publish) is used
+    pub fn with_spawner<F>(mut self, spawner: F) -> Self
+    where
+        F: Fn(EventFuture) + Send + Sync + 'static,
+    {
+        self.spawner = Some(Arc::new(spawner));
+        self
+    }
+
+    /// Call `handler` synchronously for every published `T`
+    pub fn subscribe<T, F>(&self, handler: F) -> Subscription
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        self.register::<T>(EventHandler::Sync(Arc::new(move |event| {
+            if let Some(event) = event.downcast_ref::<T>() {
+                handler(event);
+            }
+        })))
+    }
+
+    /// Run `handler` as a future for every published `T`
+    pub fn subscribe_async<T, F, Fut>(&self, handler: F) -> Subscription
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<T>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.register::<T>(EventHandler::Async(Arc::new(move |event| {
+            let handler = Arc::clone(&handler);
+            Box::pin(async move {
+                if let Ok(event) = event.downcast::<T>() {
+                    handler(event).await;
+                }
+            })
+        })))
+    }
+
+    /// Returns false if the subscription was already removed
+    pub fn unsubscribe(&self, subscription: Subscription) -> bool {
+        let Ok(mut handlers) = self.handlers.lock() else {
+            return false;
+        };
+        let Some(list) = handlers.get_mut(&subscription.event_type) else {
+            return false;
+        };
+        let before = list.len();
+        list.retain(|(id, _)| *id != subscription.id);
+        before != list.len()
+    }
+
+    pub fn subscriber_count<T: 'static>(&self) -> usize {
+        self.handlers
+            .lock()
+            .map(|h| h.get(&TypeId::of::<T>()).map_or(0, Vec::len))
+            .unwrap_or(0)
+    }
+
+    /// Deliver `event` to sync subscribers now and hand async ones to the
+    /// spawner. Without a spawner, async subscribers are skipped with a warning;
+    /// use [`publish_async`](Self::publish_async) to await them instead.
+    /// Returns the number of subscribers notified.
+    pub fn publish<T: Send + Sync + 'static>(&self, event: T) -> usize {
+        let event: Arc<dyn Any + Send + Sync> = Arc::new(event);
+        let mut notified = 0;
+        for handler in self.handlers_for::<T>() {
+            match handler {
+                EventHandler::Sync(handler) => {
+                    handler(event.as_ref());
+                    notified += 1;
+                }
+                EventHandler::Async(handler) => match &self.spawner {
+                    Some(spawner) => {
+                        spawner(handler(Arc::clone(&event)));
+                        notified += 1;
+                    }
+                    None => log::warn!(
+                        "EventBus has no spawner; skipped async subscriber for {}",
+                        std::any::type_name::<T>()
+                    ),
+                },
+            }
+        }
+        notified
+    }
+
+    /// Deliver `event` to every subscriber in subscription order, awaiting
+    /// async subscribers one after another
+    pub async fn publish_async<T: Send + Sync + 'static>(&self, event: T) -> usize {
+        let event: Arc<dyn Any + Send + Sync> = Arc::new(event);
+        let handlers = self.handlers_for::<T>();
+        for handler in &handlers {
+            match handler {
+                EventHandler::Sync(handler) => handler(event.as_ref()),
+                EventHandler::Async(handler) => handler(Arc::clone(&event)).await,
+            }
+        }
+        handlers.len()
+    }
+
+    fn register<T: 'static>(&self, handler: EventHandler) -> Subscription {
+        let subscription = Subscription {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            event_type: TypeId::of::<T>(),
+        };
+        if let Ok(mut handlers) = self.handlers.lock() {
+            handlers.entry(subscription.event_type).or_default().push((subscription.id, handler));
+        }
+        subscription
+    }
+
+    // Cloned so subscribers may subscribe or publish without deadlocking
+    fn handlers_for<T: 'static>(&self) -> Vec<EventHandler> {
+        self.handlers
+            .lock()
+            .ok()
+            .and_then(|h| h.get(&TypeId::of::<T>()).map(|list| list.iter().map(|(_, h)| h.clone()).collect()))
+            .unwrap_or_default()
+    }
+}
+
 impl ChatMessage {
     pub fn new_text_message(
-        appointment_id: Uuid,
+        appointment_id: AppointmentId,
         sender_id: Uuid,
         sender_role: &str,
         message: &str,
@@ -273,8 +529,8 @@ impl EmergencyAlert {
 
 impl SystemNotification {
     pub fn new_appointment_reminder(
-        appointment_id: Uuid,
-        patient_id: Uuid,
+        appointment_id: &AppointmentId,
+        patient_user_id: Uuid,
         message: &str,
     ) -> Self {
         Self {
@@ -282,7 +538,7 @@ impl SystemNotification {
             title: "Appointment Reminder".to_string(),
             message: message.to_string(),
             notification_type: NotificationType::AppointmentReminder,
-            target_users: vec![patient_id],
+            target_users: vec![patient_user_id],
             target_roles: vec!["patient".to_string()],
             created_at: Utc::now(),
             expires_at: Some(Utc::now() + chrono::Duration::days(1)),
@@ -291,3 +547,70 @@ impl SystemNotification {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[derive(Debug)]
+    struct AppointmentBooked {
+        appointment_id: AppointmentId,
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(output) => output,
+            std::task::Poll::Pending => panic!("event handlers should resolve immediately"),
+        }
+    }
+
+    #[test]
+    fn test_event_bus_routes_by_type() {
+        let bus = EventBus::new();
+        let booked = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&booked);
+        let subscription = bus.subscribe(move |event: &AppointmentBooked| {
+            seen.lock().unwrap().push(event.appointment_id.clone());
+        });
+        let other = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&other);
+        bus.subscribe(move |_: &ClientEvent| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(bus.publish(AppointmentBooked { appointment_id: AppointmentId::new("apt-1") }), 1);
+        assert_eq!(*booked.lock().unwrap(), vec![AppointmentId::new("apt-1")]);
+        assert_eq!(other.load(Ordering::SeqCst), 0);
+
+        assert!(bus.unsubscribe(subscription));
+        assert!(!bus.unsubscribe(subscription));
+        assert_eq!(bus.publish(AppointmentBooked { appointment_id: AppointmentId::new("apt-2") }), 0);
+        assert_eq!(booked.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_async_subscribers() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let bus = EventBus::new();
+        bus.subscribe_async(move |event: Arc<AppointmentBooked>| {
+            let counter = Arc::clone(&counter);
+            async move {
+                assert_eq!(event.appointment_id.as_str(), "apt-1");
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        // Skipped without a spawner, awaited by publish_async
+        assert_eq!(bus.publish(AppointmentBooked { appointment_id: AppointmentId::new("apt-1") }), 0);
+        assert_eq!(block_on(bus.publish_async(AppointmentBooked { appointment_id: AppointmentId::new("apt-1") })), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let spawned = bus.with_spawner(block_on);
+        assert_eq!(spawned.publish(AppointmentBooked { appointment_id: AppointmentId::new("apt-1") }), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,220 @@
+//! Geographic primitives for provider search, tracking and emergency dispatch
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{SharedError, SharedResult};
+
+/// Mean Earth radius used for great-circle distances
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// WGS84 coordinate pair in decimal degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Earlier name of [`GeoPoint`]
+pub type LatLng = GeoPoint;
+
+impl GeoPoint {
+    pub fn new(latitude: f64, longitude: f64) -> SharedResult<Self> {
+        let point = Self { latitude, longitude };
+        if point.is_valid() {
+            Ok(point)
+        } else {
+            Err(SharedError::ValidationError(format!(
+                "Invalid coordinates: ({}, {})",
+                latitude, longitude
+            )))
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.latitude.is_finite()
+            && self.longitude.is_finite()
+            && (-90.0..=90.0).contains(&self.latitude)
+            && (-180.0..=180.0).contains(&self.longitude)
+    }
+
+    /// Great-circle distance using the haversine formula
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let lat1 = self.latitude.to_radians();
+        let lat2 = other.latitude.to_radians();
+        let d_lat = (other.latitude - self.latitude).to_radians();
+        let d_lng = (other.longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+
+    pub fn distance_m(&self, other: &GeoPoint) -> f64 {
+        self.distance_km(other) * 1000.0
+    }
+
+    /// True if the point lies within `radius_km` of `center`, edge included
+    pub fn within_radius_km(&self, center: &GeoPoint, radius_km: f64) -> bool {
+        // Cheap box test first; the haversine only runs for nearby points
+        BoundingBox::around(center, radius_km).contains(self) && self.distance_km(center) <= radius_km
+    }
+}
+
+impl TryFrom<(f64, f64)> for GeoPoint {
+    type Error = SharedError;
+
+    /// `(latitude, longitude)`, the order browser geolocation reports
+    fn try_from((latitude, longitude): (f64, f64)) -> SharedResult<Self> {
+        Self::new(latitude, longitude)
+    }
+}
+
+impl From<GeoPoint> for (f64, f64) {
+    fn from(point: GeoPoint) -> Self {
+        (point.latitude, point.longitude)
+    }
+}
+
+/// Latitude/longitude rectangle, e.g. for a coarse "nearby" prefilter.
+/// `min_longitude > max_longitude` when the box crosses the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub min_latitude: f64,
+    pub min_longitude: f64,
+    pub max_latitude: f64,
+    pub max_longitude: f64,
+}
+
+impl BoundingBox {
+    /// Smallest box containing every point within `radius_km` of `center`
+    pub fn around(center: &GeoPoint, radius_km: f64) -> Self {
+        let radius_km = radius_km.max(0.0);
+        let d_lat = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        let min_latitude = center.latitude - d_lat;
+        let max_latitude = center.latitude + d_lat;
+
+        // A circle reaching over a pole covers every longitude
+        if min_latitude <= -90.0 || max_latitude >= 90.0 {
+            return Self {
+                min_latitude: min_latitude.max(-90.0),
+                min_longitude: -180.0,
+                max_latitude: max_latitude.min(90.0),
+                max_longitude: 180.0,
+            };
+        }
+
+        let ratio = (radius_km / EARTH_RADIUS_KM).sin() / center.latitude.to_radians().cos();
+        if ratio >= 1.0 {
+            return Self { min_latitude, min_longitude: -180.0, max_latitude, max_longitude: 180.0 };
+        }
+        let d_lng = ratio.asin().to_degrees();
+        Self {
+            min_latitude,
+            min_longitude: wrap_longitude(center.longitude - d_lng),
+            max_latitude,
+            max_longitude: wrap_longitude(center.longitude + d_lng),
+        }
+    }
+
+    /// Box around `points`; `None` when there are none. Never crosses the antimeridian.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a GeoPoint>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, point| {
+            let bounds = bounds.unwrap_or(Self {
+                min_latitude: point.latitude,
+                min_longitude: point.longitude,
+                max_latitude: point.latitude,
+                max_longitude: point.longitude,
+            });
+            Some(Self {
+                min_latitude: bounds.min_latitude.min(point.latitude),
+                min_longitude: bounds.min_longitude.min(point.longitude),
+                max_latitude: bounds.max_latitude.max(point.latitude),
+                max_longitude: bounds.max_longitude.max(point.longitude),
+            })
+        })
+    }
+
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_longitude > self.max_longitude
+    }
+
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        let latitude_ok = (self.min_latitude..=self.max_latitude).contains(&point.latitude);
+        let longitude_ok = if self.crosses_antimeridian() {
+            point.longitude >= self.min_longitude || point.longitude <= self.max_longitude
+        } else {
+            (self.min_longitude..=self.max_longitude).contains(&point.longitude)
+        };
+        latitude_ok && longitude_ok
+    }
+
+    pub fn center(&self) -> GeoPoint {
+        let mut longitude = (self.min_longitude + self.max_longitude) / 2.0;
+        if self.crosses_antimeridian() {
+            longitude = wrap_longitude(longitude + 180.0);
+        }
+        GeoPoint {
+            latitude: (self.min_latitude + self.max_latitude) / 2.0,
+            longitude,
+        }
+    }
+}
+
+/// Bring a longitude back into -180..=180
+fn wrap_longitude(longitude: f64) -> f64 {
+    if (-180.0..=180.0).contains(&longitude) {
+        longitude
+    } else {
+        (longitude + 180.0).rem_euclid(360.0) - 180.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_between_cities() {
+        let mumbai = GeoPoint::new(19.0760, 72.8777).unwrap();
+        let pune = GeoPoint::new(18.5204, 73.8567).unwrap();
+        let distance = mumbai.distance_km(&pune);
+        assert!((distance - 120.0).abs() < 5.0, "got {}", distance);
+        assert_eq!(mumbai.distance_km(&mumbai), 0.0);
+    }
+
+    #[test]
+    fn test_invalid_coordinates() {
+        assert!(GeoPoint::new(91.0, 0.0).is_err());
+        assert!(GeoPoint::new(0.0, -181.0).is_err());
+        assert!(GeoPoint::new(f64::NAN, 0.0).is_err());
+        assert!(GeoPoint::try_from((12.97, 77.59)).is_ok());
+    }
+
+    #[test]
+    fn test_within_radius_and_bounding_box() {
+        let mumbai = GeoPoint::new(19.0760, 72.8777).unwrap();
+        let pune = GeoPoint::new(18.5204, 73.8567).unwrap();
+        assert!(pune.within_radius_km(&mumbai, 150.0));
+        assert!(!pune.within_radius_km(&mumbai, 100.0));
+
+        let bounds = BoundingBox::around(&mumbai, 150.0);
+        assert!(bounds.contains(&pune));
+        assert!(!bounds.crosses_antimeridian());
+        assert!((bounds.center().latitude - mumbai.latitude).abs() < 1e-9);
+
+        // Fiji straddles the antimeridian
+        let suva = GeoPoint::new(-18.1416, 178.4419).unwrap();
+        let taveuni = GeoPoint::new(-16.85, -179.95).unwrap();
+        let bounds = BoundingBox::around(&suva, 300.0);
+        assert!(bounds.crosses_antimeridian());
+        assert!(bounds.contains(&taveuni));
+        assert!(taveuni.within_radius_km(&suva, 300.0));
+
+        let polar = BoundingBox::around(&GeoPoint::new(89.5, 0.0).unwrap(), 100.0);
+        assert_eq!((polar.min_longitude, polar.max_latitude), (-180.0, 90.0));
+
+        let bounds = BoundingBox::from_points(&[mumbai, pune]).unwrap();
+        assert_eq!(bounds.min_latitude, pune.latitude);
+        assert_eq!(bounds.max_longitude, pune.longitude);
+        assert!(BoundingBox::from_points(&[]).is_none());
+    }
+}
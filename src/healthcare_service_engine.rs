@@ -11,6 +11,20 @@ use chrono::{DateTime, Utc};
 /// Implements configuration-driven architecture with 8 healthcare service categories
 pub mod healthcare_service_engine {
     use super::*;
+    use crate::appointment_events::{
+        AppointmentCancelled, AppointmentCompleted, AppointmentRequested, AppointmentStarted, CancelledBy,
+        ProviderAssigned,
+    };
+    use crate::auth::{AccessPolicySet, Environment, ResourceAttributes, SubjectAttributes};
+    use crate::compliance::hipaa::HipaaAction;
+    use crate::errors::{ErrorCode, SharedError, SharedResult};
+    use crate::events::EventBus;
+    use crate::geo::GeoPoint;
+    use crate::models::EligibilityCheckResult;
+    use crate::ids::{AppointmentId, ProviderId};
+    use crate::money::{Currency, Money};
+    use crate::recurrence::Recurrence;
+    use crate::schema::Versioned;
 
     // Supporting types for healthcare configurations
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,12 +85,12 @@ pub mod healthcare_service_engine {
     // Additional service-related structures
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct ServicePricing {
-        pub base_cost: f64,
+        pub base_cost: Money,
         pub surge_multiplier: f64,
-        pub total_cost: f64,
-        pub provider_share: f64,
-        pub platform_fee: f64,
-        pub estimated_insurance_coverage: Option<f64>,
+        pub total_cost: Money,
+        pub provider_share: Money,
+        pub platform_fee: Money,
+        pub estimated_insurance_coverage: Option<Money>,
     }
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -338,6 +352,11 @@ pub mod healthcare_service_engine {
         pub steps_completed: Vec<String>,
     }
 
+    impl Versioned for WorkflowInstance {
+        const SCHEMA_NAME: &'static str = "workflow_instance";
+        const SCHEMA_VERSION: u32 = 1;
+    }
+
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct StepExecutorConfig {
         pub executor_type: String,
@@ -365,8 +384,7 @@ pub mod healthcare_service_engine {
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct BasePricingModel {
-        pub base_rate: f64,
-        pub currency: String,
+        pub base_rate: Money,
     }
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -472,6 +490,12 @@ pub mod healthcare_service_engine {
         pub address: String,
     }
 
+    impl LocationInfo {
+        pub fn point(&self) -> GeoPoint {
+            GeoPoint { latitude: self.latitude, longitude: self.longitude }
+        }
+    }
+
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct NetworkInfluenceConfig {
         pub network_multiplier: f64,
@@ -498,15 +522,53 @@ pub mod healthcare_service_engine {
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct PricingQuote {
-        pub base_price: f64,
-        pub total_price: f64,
-        pub breakdown: HashMap<String, f64>,
+        pub base_price: Money,
+        pub total_price: Money,
+        pub breakdown: HashMap<String, Money>,
     }
 
+    /// A quote. Stored quotes without `insurance_coverage` predate insurance
+    /// support and load with zero coverage in the quote's currency.
     #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(from = "StoredPricingResult")]
     pub struct PricingResult {
-        pub final_price: f64,
+        pub final_price: Money,
         pub applied_discounts: Vec<String>,
+        /// Part of `final_price` the patient's insurer is expected to pay
+        pub insurance_coverage: Money,
+    }
+
+    #[derive(Deserialize)]
+    struct StoredPricingResult {
+        final_price: Money,
+        applied_discounts: Vec<String>,
+        #[serde(default)]
+        insurance_coverage: Option<Money>,
+    }
+
+    impl From<StoredPricingResult> for PricingResult {
+        fn from(stored: StoredPricingResult) -> Self {
+            let mut pricing = PricingResult::new(stored.final_price, stored.applied_discounts);
+            if let Some(coverage) = stored.insurance_coverage {
+                pricing.insurance_coverage = coverage;
+            }
+            pricing
+        }
+    }
+
+    impl PricingResult {
+        /// Quote with no insurance applied yet
+        pub fn new(final_price: Money, applied_discounts: Vec<String>) -> Self {
+            Self {
+                final_price,
+                applied_discounts,
+                insurance_coverage: Money::zero(final_price.currency),
+            }
+        }
+
+        pub fn patient_payable(&self) -> SharedResult<Money> {
+            self.final_price.saturating_sub(&self.insurance_coverage)
+        }
     }
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -549,6 +611,9 @@ pub mod healthcare_service_engine {
     pub struct SchedulingPreferences {
         pub preferred_time_slots: Vec<String>,
         pub buffer_time_minutes: u32,
+        /// Repeat the visit, e.g. a nursing series booked as one request
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub recurrence: Option<Recurrence>,
     }
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -611,6 +676,9 @@ pub mod healthcare_service_engine {
         pub referral_engine: ReferralEngine,
         pub pricing_engine: PricingEngine,
         pub compliance_engine: ComplianceEngine,
+        /// Appointment lifecycle events are published here
+        #[serde(skip)]
+        pub events: EventBus,
     }
 
     /// Dynamic Service Configuration
@@ -632,8 +700,7 @@ pub mod healthcare_service_engine {
     /// Healthcare Pricing Model with Dynamic Rules
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct PricingModel {
-        pub base_price: f64,
-        pub currency: String,
+        pub base_price: Money,
         pub dynamic_factors: Vec<PricingFactor>,
         pub discount_rules: Vec<DiscountRule>,
         pub surge_pricing: SurgePricingConfig,
@@ -813,7 +880,7 @@ pub mod healthcare_service_engine {
         pub timeout_minutes: Option<u32>,
     }
 
-    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
     pub enum WorkflowStepType {
         FormSubmission,
         DocumentUpload,
@@ -900,6 +967,9 @@ pub mod healthcare_service_engine {
         pub consent_management: ConsentManagementConfig,
         pub breach_detection: BreachDetectionConfig,
         pub regulatory_reporting: RegulatoryReportingConfig,
+        /// Attribute-based rules for PHI access, on top of the role matrix
+        #[serde(default)]
+        pub access_policies: AccessPolicySet,
     }
 
     /// Multi-tenant Healthcare Organization Support
@@ -982,9 +1052,16 @@ pub mod healthcare_service_engine {
                 referral_engine: ReferralEngine::new(),
                 pricing_engine: PricingEngine::new(),
                 compliance_engine: ComplianceEngine::new(),
+                events: EventBus::new(),
             }
         }
 
+        /// Publish lifecycle events on `events` instead of a private bus
+        pub fn with_event_bus(mut self, events: EventBus) -> Self {
+            self.events = events;
+            self
+        }
+
         /// Load configuration from external configuration management system
         pub async fn load_configuration(&mut self, config_source: &str) -> Result<(), ApplicationError> {
             // Implementation for loading configuration from external system
@@ -1001,10 +1078,10 @@ pub mod healthcare_service_engine {
             // self.validate_service_request(&request).await?;
 
             // 2. Apply pricing rules (placeholder implementation)
-            let pricing = PricingResult {
-                final_price: 500.0,
-                applied_discounts: vec!["No discounts applied".to_string()],
-            };
+            let pricing = PricingResult::new(
+                Money::from_major(500, Currency::Inr),
+                vec!["No discounts applied".to_string()],
+            );
 
             // 3. Find suitable providers (simplified implementation)
             let providers = vec![];
@@ -1018,6 +1095,17 @@ pub mod healthcare_service_engine {
             // 6. Log for compliance and audit (placeholder)
             // self.compliance_engine.log_service_request(&request).await?;
 
+            // 7. Announce the new appointment to analytics and notifications
+            self.events.publish(AppointmentRequested {
+                appointment_id: AppointmentId::from(request.request_id),
+                request_id: request.request_id,
+                patient_id: request.patient_id.into(),
+                is_emergency: request.emergency_info.is_some()
+                    || request.service_category == ServiceCategory::EmergencyServices,
+                service_category: request.service_category,
+                requested_at: Utc::now(),
+            });
+
             Ok(ServiceProcessingResult {
                 request_id: request.request_id,
                 pricing,
@@ -1027,6 +1115,49 @@ pub mod healthcare_service_engine {
             })
         }
 
+        /// Record that `provider_id` accepted the appointment
+        pub fn assign_provider(
+            &self,
+            appointment_id: AppointmentId,
+            provider_id: ProviderId,
+            previous_provider_id: Option<ProviderId>,
+        ) -> ProviderAssigned {
+            let event = ProviderAssigned { appointment_id, provider_id, previous_provider_id, assigned_at: Utc::now() };
+            self.events.publish(event.clone());
+            event
+        }
+
+        pub fn start_appointment(&self, appointment_id: AppointmentId, provider_id: ProviderId) -> AppointmentStarted {
+            let event = AppointmentStarted { appointment_id, provider_id, started_at: Utc::now() };
+            self.events.publish(event.clone());
+            event
+        }
+
+        /// Record completion; the duration is measured from `started.started_at`
+        pub fn complete_appointment(&self, started: &AppointmentStarted, follow_up_needed: bool) -> AppointmentCompleted {
+            let completed_at = Utc::now();
+            let event = AppointmentCompleted {
+                appointment_id: started.appointment_id.clone(),
+                provider_id: started.provider_id.clone(),
+                completed_at,
+                duration_minutes: (completed_at - started.started_at).num_minutes().max(0) as u32,
+                follow_up_needed,
+            };
+            self.events.publish(event.clone());
+            event
+        }
+
+        pub fn cancel_appointment(
+            &self,
+            appointment_id: AppointmentId,
+            cancelled_by: CancelledBy,
+            reason: impl Into<String>,
+        ) -> AppointmentCancelled {
+            let event = AppointmentCancelled { appointment_id, cancelled_by, reason: reason.into(), cancelled_at: Utc::now() };
+            self.events.publish(event.clone());
+            event
+        }
+
         /// Update referral points and priority scoring
         pub async fn process_referral_completion(
             &mut self,
@@ -1066,7 +1197,7 @@ pub mod healthcare_service_engine {
             Ok(ReferralProcessingResult {
                 points_awarded: points,
                 new_priority_level: "1".to_string(), // Placeholder priority level
-                credit_balance: 100.0,   // Placeholder credit balance
+                credit_balance: Money::from_major(100, Currency::Inr), // Placeholder credit balance
             })
         }
 
@@ -1080,19 +1211,89 @@ pub mod healthcare_service_engine {
     }
 
     /// Application Layer Error Types
-    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
     pub enum ApplicationError {
+        #[error("Configuration error: {0}")]
         ConfigurationError(String),
+        #[error("Validation error: {0}")]
         ValidationError(String),
+        #[error("Provider matching error: {0}")]
         ProviderMatchingError(String),
+        #[error("Pricing calculation error: {0}")]
         PricingCalculationError(String),
+        #[error("Workflow execution error: {0}")]
         WorkflowExecutionError(String),
+        #[error("Compliance violation: {0}")]
         ComplianceViolation(String),
+        #[error("Referral processing error: {0}")]
         ReferralProcessingError(String),
+        #[error("Database error: {0}")]
         DatabaseError(String),
+        #[error("External service error: {0}")]
         ExternalServiceError(String),
     }
 
+    impl ApplicationError {
+        pub fn code(&self) -> ErrorCode {
+            match self {
+                ApplicationError::ConfigurationError(_) => ErrorCode::Configuration,
+                ApplicationError::ValidationError(_) => ErrorCode::Validation,
+                ApplicationError::ProviderMatchingError(_) => ErrorCode::Scheduling,
+                ApplicationError::PricingCalculationError(_) => ErrorCode::Payment,
+                ApplicationError::ComplianceViolation(_) => ErrorCode::Compliance,
+                ApplicationError::DatabaseError(_) => ErrorCode::Database,
+                ApplicationError::ExternalServiceError(_) => ErrorCode::ServiceUnavailable,
+                ApplicationError::WorkflowExecutionError(_) | ApplicationError::ReferralProcessingError(_) => {
+                    ErrorCode::Internal
+                }
+            }
+        }
+
+        pub fn is_retryable(&self) -> bool {
+            self.code().is_retryable()
+        }
+    }
+
+    impl From<ApplicationError> for SharedError {
+        fn from(error: ApplicationError) -> Self {
+            let code = error.code();
+            match error {
+                // Keep the engine step that failed; the shared variant doesn't say
+                ApplicationError::ProviderMatchingError(_)
+                | ApplicationError::PricingCalculationError(_)
+                | ApplicationError::WorkflowExecutionError(_)
+                | ApplicationError::ReferralProcessingError(_)
+                | ApplicationError::ExternalServiceError(_) => SharedError::new(code, error.to_string()),
+                ApplicationError::ConfigurationError(details)
+                | ApplicationError::ValidationError(details)
+                | ApplicationError::ComplianceViolation(details)
+                | ApplicationError::DatabaseError(details) => SharedError::new(code, details),
+            }
+        }
+    }
+
+    /// Shared-layer failures surfacing in the engine, e.g. from validation helpers
+    impl From<SharedError> for ApplicationError {
+        fn from(error: SharedError) -> Self {
+            match error {
+                SharedError::ValidationError(details) => ApplicationError::ValidationError(details),
+                SharedError::ConfigurationError(details) => ApplicationError::ConfigurationError(details),
+                SharedError::DatabaseError(details) => ApplicationError::DatabaseError(details),
+                SharedError::ComplianceError(details)
+                | SharedError::HipaaViolation(details)
+                | SharedError::GdprViolation(details) => ApplicationError::ComplianceViolation(details),
+                SharedError::SchedulingError(details) => ApplicationError::ProviderMatchingError(details),
+                SharedError::PaymentError(details) => ApplicationError::PricingCalculationError(details),
+                SharedError::NetworkError(_)
+                | SharedError::ServiceUnavailableError(_)
+                | SharedError::TimeoutError(_)
+                | SharedError::IntegrationError(_)
+                | SharedError::RateLimitError(_) => ApplicationError::ExternalServiceError(error.to_string()),
+                other => ApplicationError::WorkflowExecutionError(other.to_string()),
+            }
+        }
+    }
+
     /// Result Types
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct ProviderMatch {
@@ -1116,7 +1317,7 @@ pub mod healthcare_service_engine {
     pub struct ReferralProcessingResult {
         pub points_awarded: u32,
         pub new_priority_level: String,
-        pub credit_balance: f64,
+        pub credit_balance: Money,
     }
 
     // Additional supporting types and implementations...
@@ -1214,10 +1415,40 @@ pub mod healthcare_service_engine {
             providers: &[ProviderMatch],
         ) -> Result<PricingResult, ApplicationError> {
             // Calculate pricing for consultation
-            Ok(PricingResult {
-                final_price: 500.0,
-                applied_discounts: vec!["No discounts applied".to_string()],
-            })
+            Ok(PricingResult::new(
+                Money::from_major(500, Currency::Inr),
+                vec!["No discounts applied".to_string()],
+            ))
+        }
+
+        /// Apply an eligibility check to a quote. Coverage is only counted when
+        /// insurance integration is enabled and the payer is supported (an empty
+        /// list accepts any payer), and never exceeds the quoted price.
+        pub fn apply_eligibility(&self, mut pricing: PricingResult, eligibility: &EligibilityCheckResult) -> PricingResult {
+            let config = &self.insurance_integration_config;
+            let payer_supported = config.supported_providers.is_empty()
+                || config
+                    .supported_providers
+                    .iter()
+                    .any(|p| p.eq_ignore_ascii_case(&eligibility.payer_name));
+            let same_currency = Currency::parse(&eligibility.currency) == Some(pricing.final_price.currency);
+            if !config.integration_enabled || !payer_supported || !same_currency || !eligibility.status.is_covered() {
+                return pricing;
+            }
+
+            // The payer may have checked a different amount than this quote
+            let percent = if eligibility.billed_amount > 0.0 {
+                (eligibility.covered_amount / eligibility.billed_amount * 100.0).clamp(0.0, 100.0)
+            } else {
+                0.0
+            };
+            pricing.insurance_coverage = pricing.final_price.percent(percent);
+            pricing
+        }
+
+        /// Split a total into the provider's share and the platform fee
+        pub fn revenue_split(&self, total: Money) -> (Money, Money) {
+            total.split(self.revenue_sharing_config.provider_share_percentage)
         }
     }
 
@@ -1245,8 +1476,20 @@ pub mod healthcare_service_engine {
                     reporting_frequency: "monthly".to_string(),
                     required_fields: Vec::new(),
                 },
+                access_policies: AccessPolicySet::default(),
             }
         }
+
+        /// Check a PHI request against the configured access policies
+        pub fn authorize_phi_access(
+            &self,
+            subject: &SubjectAttributes,
+            action: &HipaaAction,
+            resource: &ResourceAttributes,
+            environment: &Environment,
+        ) -> SharedResult<()> {
+            self.access_policies.evaluate(subject, action, resource, environment).into_result()
+        }
     }
 }
 
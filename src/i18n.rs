@@ -0,0 +1,437 @@
+// Translation lookup for shared UI strings
+// Keys are dotted paths ("pagination.next"); plural forms add ".one"/".other"
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Hi,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Hi];
+
+    /// BCP 47 language code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Hi => "hi",
+        }
+    }
+
+    /// Name of the language in itself, for language pickers
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Hi => "हिन्दी",
+        }
+    }
+
+    /// Parse a language tag such as "hi" or "hi-IN", ignoring the region
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|locale| locale.code() == language)
+    }
+
+    fn dictionary(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Hi => HI,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralCategory {
+    One,
+    Other,
+}
+
+impl PluralCategory {
+    fn suffix(&self) -> &'static str {
+        match self {
+            PluralCategory::One => "one",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// CLDR cardinal plural rules for whole numbers
+pub fn plural_category(locale: Locale, count: u64) -> PluralCategory {
+    let one = match locale {
+        Locale::En => count == 1,
+        // Hindi treats zero as singular
+        Locale::Hi => count <= 1,
+    };
+    if one { PluralCategory::One } else { PluralCategory::Other }
+}
+
+/// Translation of `key` in `locale`, without falling back
+pub fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    locale
+        .dictionary()
+        .iter()
+        .find(|(candidate, _)| *candidate == key)
+        .map(|(_, text)| *text)
+}
+
+/// Translation of `key`, falling back to English and then to the key itself
+/// so a missing entry shows up in the UI instead of an empty string
+pub fn translate(locale: Locale, key: &str) -> &str {
+    lookup(locale, key)
+        .or_else(|| lookup(Locale::En, key))
+        .unwrap_or(key)
+}
+
+/// [`translate`] with `{name}` placeholders replaced from `args`
+pub fn translate_with(locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+    interpolate(translate(locale, key), args)
+}
+
+/// Plural form of `key` for `count`, with `{count}` and any `args` filled in
+pub fn translate_plural(locale: Locale, key: &str, count: u64, args: &[(&str, &str)]) -> String {
+    let plural_key = format!("{}.{}", key, plural_category(locale, count).suffix());
+    let count_text = count.to_string();
+    let mut all_args = vec![("count", count_text.as_str())];
+    all_args.extend_from_slice(args);
+    interpolate(translate(locale, &plural_key), &all_args)
+}
+
+fn interpolate(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+static EN: &[(&str, &str)] = &[
+    ("common.loading", "Loading…"),
+    ("common.dismiss", "Dismiss"),
+    ("common.clear", "Clear"),
+    ("common.try_again", "Try again"),
+    ("common.get_started", "Get started"),
+    ("common.notifications.one", "{count} notification"),
+    ("common.notifications.other", "{count} notifications"),
+    ("a11y.skip_to_content", "Skip to main content"),
+    ("a11y.open_navigation", "Open navigation"),
+    ("a11y.close_navigation", "Close navigation"),
+    ("alert.emergency", "Emergency Alert"),
+    ("alert.medical_warning", "Medical Warning"),
+    ("alert.success", "Success"),
+    ("alert.information", "Information"),
+    ("alert.appointment_reminder", "Appointment Reminder"),
+    ("alert.medication_reminder", "Medication Reminder"),
+    ("alert.next_dose", "Next dose: {time}"),
+    ("alert.maintenance", "Scheduled System Maintenance"),
+    ("alert.expected_duration", "Expected duration: {duration}"),
+    ("alert.privacy", "Privacy & Security Notice"),
+    ("alert.privacy_body", "This platform complies with HIPAA, GDPR, and healthcare data protection standards. Your medical information is encrypted and secure."),
+    ("table.no_records", "No records found"),
+    ("table.select_all", "Select all rows on this page"),
+    ("table.select_row", "Select row"),
+    ("table.selected.one", "{count} selected"),
+    ("table.selected.other", "{count} selected"),
+    ("pagination.showing", "Showing {first}–{last} of {total}"),
+    ("pagination.no_results", "No results"),
+    ("pagination.rows_per_page", "Rows per page"),
+    ("pagination.previous", "Previous page"),
+    ("pagination.next", "Next page"),
+    ("pagination.page", "Page {page}"),
+    ("priority.emergency", "Emergency"),
+    ("priority.urgent", "Urgent"),
+    ("priority.normal", "Normal"),
+    ("priority.low", "Low"),
+    ("chat.security.end_to_end", "End-to-end encrypted"),
+    ("chat.security.transport_only", "Not end-to-end encrypted"),
+    ("status.active", "Active"),
+    ("status.inactive", "Inactive"),
+    ("status.pending", "Pending"),
+    ("status.verified", "Verified"),
+    ("status.suspended", "Suspended"),
+    ("status.emergency", "Emergency"),
+    ("status.stable", "Stable"),
+    ("status.needs-attention", "Needs Attention"),
+    ("status.critical", "Critical"),
+    ("timeline.kind.visit", "Visit"),
+    ("timeline.kind.prescription", "Prescription"),
+    ("timeline.kind.lab_result", "Lab result"),
+    ("timeline.kind.emergency", "Emergency"),
+    ("timeline.kind.other", "Event"),
+    ("timeline.today", "Today"),
+    ("timeline.yesterday", "Yesterday"),
+    ("timeline.empty", "No history yet"),
+    ("time.just_now", "Just now"),
+    ("time.tomorrow", "Tomorrow"),
+    ("time.minutes_ago.one", "{count} min ago"),
+    ("time.minutes_ago.other", "{count} min ago"),
+    ("time.minutes_from_now.one", "in {count} min"),
+    ("time.minutes_from_now.other", "in {count} min"),
+    ("time.hours_ago.one", "{count} hour ago"),
+    ("time.hours_ago.other", "{count} hours ago"),
+    ("time.hours_from_now.one", "in {count} hour"),
+    ("time.hours_from_now.other", "in {count} hours"),
+    ("time.days_ago.one", "{count} day ago"),
+    ("time.days_ago.other", "{count} days ago"),
+    ("time.days_from_now.one", "in {count} day"),
+    ("time.days_from_now.other", "in {count} days"),
+    ("empty.default", "Nothing here yet"),
+    ("empty.no_appointments_today.title", "No appointments today"),
+    ("empty.no_appointments_today.description", "Your schedule is clear. New bookings will appear here."),
+    ("empty.no_patients_found.title", "No patients found"),
+    ("empty.no_patients_found.description", "Try a different name, MRN or date of birth."),
+    ("empty.no_alerts.title", "No alerts"),
+    ("empty.no_alerts.description", "Everything looks good. You're all caught up."),
+    ("empty.no_results.title", "No results"),
+    ("empty.no_results.description", "Try adjusting your search or filters."),
+    ("empty.no_messages.title", "No messages yet"),
+    ("empty.no_messages.description", "Messages from your care team will appear here."),
+    ("error.load_failed", "{component} couldn't be loaded"),
+    ("error.reference", "Reference: {id}"),
+    ("emergency.alerts.title", "Emergency Alerts"),
+    ("emergency.alerts.active", "{count} Active"),
+    ("emergency.none.title", "No Active Emergencies"),
+    ("emergency.none.description", "All emergency situations are currently resolved."),
+    ("emergency.eta", "ETA: {time}"),
+    ("emergency.patient_info", "Patient Information"),
+    ("emergency.field.name", "Name:"),
+    ("emergency.field.age", "Age:"),
+    ("emergency.age_years.one", "{count} year"),
+    ("emergency.age_years.other", "{count} years"),
+    ("emergency.field.medical_id", "Medical ID:"),
+    ("emergency.field.blood_type", "Blood Type:"),
+    ("emergency.field.allergies", "Allergies:"),
+    ("emergency.field.emergency_contact", "Emergency Contact:"),
+    ("emergency.action.accept", "Accept Emergency"),
+    ("emergency.action.decline", "Cannot Respond"),
+    ("emergency.in_progress", "Emergency response in progress..."),
+    ("emergency.trigger", "Emergency"),
+    ("emergency.trigger.mobile", "EMERGENCY ALERT"),
+    ("emergency.trigger.hint", "Tap for immediate help"),
+    ("emergency.status.pending", "Pending"),
+    ("emergency.status.accepted", "Accepted"),
+    ("emergency.status.en_route", "En route"),
+    ("emergency.status.on_scene", "On scene"),
+    ("emergency.status.resolved", "Resolved"),
+    ("emergency.status.cancelled", "Cancelled"),
+    ("emergency.announce.new", "New emergency: {title}"),
+    ("emergency.announce.updated", "{title}: {status}"),
+    ("auth.lockout.delayed", "Too many failed attempts. Try again in {time}."),
+    ("auth.lockout.locked", "Account temporarily locked. Try again in {time}."),
+    ("print.print", "Print"),
+    ("print.printed_on", "Printed {date}"),
+    ("print.confidential", "Confidential: contains patient health information"),
+    ("print.reference", "Ref. {id}"),
+    ("print.document.prescription", "Prescription"),
+    ("print.document.discharge_summary", "Discharge summary"),
+    ("print.document.appointment_confirmation", "Appointment confirmation"),
+    ("print.field.patient", "Patient"),
+    ("print.field.patient_id", "Patient ID"),
+    ("print.field.admitted", "Admitted"),
+    ("print.field.discharged", "Discharged"),
+    ("print.field.attending", "Attending physician"),
+    ("print.field.diagnosis", "Diagnosis"),
+    ("print.field.hospital_course", "Hospital course"),
+    ("print.field.medications", "Discharge medications"),
+    ("print.field.instructions", "Instructions"),
+    ("print.field.follow_up", "Follow-up"),
+    ("print.field.provider", "Provider"),
+    ("print.field.visit_type", "Visit type"),
+    ("print.field.date_time", "Date and time"),
+    ("print.field.location", "Location"),
+    ("print.field.signature", "Signature"),
+    ("print.arrive_early", "Please arrive {minutes} minutes before your appointment."),
+    ("filter.search", "Search…"),
+    ("filter.clear_all", "Clear all"),
+    ("filter.from", "From"),
+    ("filter.until", "To"),
+    ("filter.date", "Date"),
+    ("filter.remove", "Remove filter {label}"),
+];
+
+static HI: &[(&str, &str)] = &[
+    ("common.loading", "लोड हो रहा है…"),
+    ("common.dismiss", "बंद करें"),
+    ("common.clear", "साफ़ करें"),
+    ("common.try_again", "फिर से प्रयास करें"),
+    ("common.get_started", "शुरू करें"),
+    ("common.notifications.one", "{count} सूचना"),
+    ("common.notifications.other", "{count} सूचनाएँ"),
+    ("a11y.skip_to_content", "मुख्य सामग्री पर जाएँ"),
+    ("a11y.open_navigation", "नेविगेशन खोलें"),
+    ("a11y.close_navigation", "नेविगेशन बंद करें"),
+    ("alert.emergency", "आपातकालीन चेतावनी"),
+    ("alert.medical_warning", "चिकित्सा चेतावनी"),
+    ("alert.success", "सफल"),
+    ("alert.information", "जानकारी"),
+    ("alert.appointment_reminder", "अपॉइंटमेंट अनुस्मारक"),
+    ("alert.medication_reminder", "दवा अनुस्मारक"),
+    ("alert.next_dose", "अगली खुराक: {time}"),
+    ("alert.maintenance", "निर्धारित सिस्टम रखरखाव"),
+    ("alert.expected_duration", "अनुमानित अवधि: {duration}"),
+    ("alert.privacy", "गोपनीयता और सुरक्षा सूचना"),
+    ("alert.privacy_body", "यह प्लेटफ़ॉर्म HIPAA, GDPR और स्वास्थ्य डेटा सुरक्षा मानकों का पालन करता है। आपकी चिकित्सा जानकारी एन्क्रिप्टेड और सुरक्षित है।"),
+    ("table.no_records", "कोई रिकॉर्ड नहीं मिला"),
+    ("table.select_all", "इस पृष्ठ की सभी पंक्तियाँ चुनें"),
+    ("table.select_row", "पंक्ति चुनें"),
+    ("table.selected.one", "{count} चयनित"),
+    ("table.selected.other", "{count} चयनित"),
+    ("pagination.showing", "{total} में से {first}–{last} दिखाए जा रहे हैं"),
+    ("pagination.no_results", "कोई परिणाम नहीं"),
+    ("pagination.rows_per_page", "प्रति पृष्ठ पंक्तियाँ"),
+    ("pagination.previous", "पिछला पृष्ठ"),
+    ("pagination.next", "अगला पृष्ठ"),
+    ("pagination.page", "पृष्ठ {page}"),
+    ("priority.emergency", "आपातकाल"),
+    ("priority.urgent", "अत्यावश्यक"),
+    ("priority.normal", "सामान्य"),
+    ("priority.low", "कम"),
+    ("chat.security.end_to_end", "एंड-टू-एंड एन्क्रिप्टेड"),
+    ("chat.security.transport_only", "एंड-टू-एंड एन्क्रिप्टेड नहीं"),
+    ("status.active", "सक्रिय"),
+    ("status.inactive", "निष्क्रिय"),
+    ("status.pending", "लंबित"),
+    ("status.verified", "सत्यापित"),
+    ("status.suspended", "निलंबित"),
+    ("status.emergency", "आपातकाल"),
+    ("status.stable", "स्थिर"),
+    ("status.needs-attention", "ध्यान आवश्यक"),
+    ("status.critical", "गंभीर"),
+    ("timeline.kind.visit", "विज़िट"),
+    ("timeline.kind.prescription", "पर्चा"),
+    ("timeline.kind.lab_result", "लैब परिणाम"),
+    ("timeline.kind.emergency", "आपातकाल"),
+    ("timeline.kind.other", "घटना"),
+    ("timeline.today", "आज"),
+    ("timeline.yesterday", "कल"),
+    ("timeline.empty", "अभी तक कोई इतिहास नहीं"),
+    ("time.just_now", "अभी-अभी"),
+    ("time.tomorrow", "कल"),
+    ("time.minutes_ago.one", "{count} मिनट पहले"),
+    ("time.minutes_ago.other", "{count} मिनट पहले"),
+    ("time.minutes_from_now.one", "{count} मिनट में"),
+    ("time.minutes_from_now.other", "{count} मिनट में"),
+    ("time.hours_ago.one", "{count} घंटा पहले"),
+    ("time.hours_ago.other", "{count} घंटे पहले"),
+    ("time.hours_from_now.one", "{count} घंटे में"),
+    ("time.hours_from_now.other", "{count} घंटे में"),
+    ("time.days_ago.one", "{count} दिन पहले"),
+    ("time.days_ago.other", "{count} दिन पहले"),
+    ("time.days_from_now.one", "{count} दिन में"),
+    ("time.days_from_now.other", "{count} दिन में"),
+    ("empty.default", "यहाँ अभी कुछ नहीं है"),
+    ("empty.no_appointments_today.title", "आज कोई अपॉइंटमेंट नहीं"),
+    ("empty.no_appointments_today.description", "आपका शेड्यूल खाली है। नई बुकिंग यहाँ दिखाई देंगी।"),
+    ("empty.no_patients_found.title", "कोई मरीज़ नहीं मिला"),
+    ("empty.no_patients_found.description", "कोई दूसरा नाम, MRN या जन्म तिथि आज़माएँ।"),
+    ("empty.no_alerts.title", "कोई चेतावनी नहीं"),
+    ("empty.no_alerts.description", "सब ठीक है। कोई लंबित सूचना नहीं।"),
+    ("empty.no_results.title", "कोई परिणाम नहीं"),
+    ("empty.no_results.description", "अपनी खोज या फ़िल्टर बदलकर देखें।"),
+    ("empty.no_messages.title", "अभी तक कोई संदेश नहीं"),
+    ("empty.no_messages.description", "आपकी देखभाल टीम के संदेश यहाँ दिखाई देंगे।"),
+    ("error.load_failed", "{component} लोड नहीं हो सका"),
+    ("error.reference", "संदर्भ: {id}"),
+    ("emergency.alerts.title", "आपातकालीन अलर्ट"),
+    ("emergency.alerts.active", "{count} सक्रिय"),
+    ("emergency.none.title", "कोई सक्रिय आपातकाल नहीं"),
+    ("emergency.none.description", "सभी आपातकालीन स्थितियाँ अभी हल हो चुकी हैं।"),
+    ("emergency.eta", "पहुँचने का अनुमानित समय: {time}"),
+    ("emergency.patient_info", "मरीज़ की जानकारी"),
+    ("emergency.field.name", "नाम:"),
+    ("emergency.field.age", "उम्र:"),
+    ("emergency.age_years.one", "{count} वर्ष"),
+    ("emergency.age_years.other", "{count} वर्ष"),
+    ("emergency.field.medical_id", "मेडिकल आईडी:"),
+    ("emergency.field.blood_type", "ब्लड ग्रुप:"),
+    ("emergency.field.allergies", "एलर्जी:"),
+    ("emergency.field.emergency_contact", "आपातकालीन संपर्क:"),
+    ("emergency.action.accept", "आपातकाल स्वीकार करें"),
+    ("emergency.action.decline", "जवाब नहीं दे सकते"),
+    ("emergency.in_progress", "आपातकालीन सहायता जारी है..."),
+    ("emergency.trigger", "आपातकाल"),
+    ("emergency.trigger.mobile", "आपातकालीन अलर्ट"),
+    ("emergency.trigger.hint", "तुरंत मदद के लिए टैप करें"),
+    ("emergency.status.pending", "लंबित"),
+    ("emergency.status.accepted", "स्वीकृत"),
+    ("emergency.status.en_route", "रास्ते में"),
+    ("emergency.status.on_scene", "मौके पर"),
+    ("emergency.status.resolved", "हल हो गया"),
+    ("emergency.status.cancelled", "रद्द"),
+    ("emergency.announce.new", "नया आपातकाल: {title}"),
+    ("emergency.announce.updated", "{title}: {status}"),
+    ("auth.lockout.delayed", "बहुत अधिक असफल प्रयास। {time} में फिर से प्रयास करें।"),
+    ("auth.lockout.locked", "खाता अस्थायी रूप से लॉक है। {time} में फिर से प्रयास करें।"),
+    ("print.print", "प्रिंट करें"),
+    ("print.printed_on", "मुद्रित {date}"),
+    ("print.confidential", "गोपनीय: इसमें रोगी की स्वास्थ्य जानकारी है"),
+    ("print.reference", "संदर्भ {id}"),
+    ("print.document.prescription", "पर्चा"),
+    ("print.document.discharge_summary", "डिस्चार्ज सारांश"),
+    ("print.document.appointment_confirmation", "अपॉइंटमेंट की पुष्टि"),
+    ("print.field.patient", "रोगी"),
+    ("print.field.patient_id", "रोगी आईडी"),
+    ("print.field.admitted", "भर्ती"),
+    ("print.field.discharged", "छुट्टी"),
+    ("print.field.attending", "उपस्थित चिकित्सक"),
+    ("print.field.diagnosis", "निदान"),
+    ("print.field.hospital_course", "अस्पताल में उपचार"),
+    ("print.field.medications", "छुट्टी के समय की दवाएँ"),
+    ("print.field.instructions", "निर्देश"),
+    ("print.field.follow_up", "फ़ॉलो-अप"),
+    ("print.field.provider", "चिकित्सक"),
+    ("print.field.visit_type", "मुलाक़ात का प्रकार"),
+    ("print.field.date_time", "दिनांक और समय"),
+    ("print.field.location", "स्थान"),
+    ("print.field.signature", "हस्ताक्षर"),
+    ("print.arrive_early", "कृपया अपने अपॉइंटमेंट से {minutes} मिनट पहले पहुँचें।"),
+    ("filter.search", "खोजें…"),
+    ("filter.clear_all", "सभी हटाएँ"),
+    ("filter.from", "से"),
+    ("filter.until", "तक"),
+    ("filter.date", "दिनांक"),
+    ("filter.remove", "फ़िल्टर {label} हटाएँ"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_codes() {
+        assert_eq!(Locale::from_code("hi-IN"), Some(Locale::Hi));
+        assert_eq!(Locale::from_code("EN_gb"), Some(Locale::En));
+        assert_eq!(Locale::from_code("fr"), None);
+        assert_eq!(Locale::default(), Locale::En);
+    }
+
+    #[test]
+    fn test_dictionaries_have_the_same_keys() {
+        for (key, _) in EN {
+            assert!(lookup(Locale::Hi, key).is_some(), "missing Hindi translation for {}", key);
+        }
+        for (key, _) in HI {
+            assert!(lookup(Locale::En, key).is_some(), "Hindi key {} missing from English", key);
+        }
+    }
+
+    #[test]
+    fn test_translate_falls_back() {
+        assert_eq!(translate(Locale::Hi, "timeline.today"), "आज");
+        assert_eq!(translate(Locale::Hi, "no.such.key"), "no.such.key");
+        assert_eq!(
+            translate_with(Locale::En, "pagination.showing", &[("first", "1"), ("last", "10"), ("total", "42")]),
+            "Showing 1–10 of 42"
+        );
+    }
+
+    #[test]
+    fn test_pluralization() {
+        assert_eq!(plural_category(Locale::En, 0), PluralCategory::Other);
+        assert_eq!(plural_category(Locale::Hi, 0), PluralCategory::One);
+        assert_eq!(translate_plural(Locale::En, "common.notifications", 1, &[]), "1 notification");
+        assert_eq!(translate_plural(Locale::En, "common.notifications", 3, &[]), "3 notifications");
+        assert_eq!(translate_plural(Locale::Hi, "common.notifications", 3, &[]), "3 सूचनाएँ");
+    }
+}
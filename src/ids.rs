@@ -0,0 +1,295 @@
+//! Typed identifiers, so a provider id can't be passed where a patient id is expected
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::errors::{SharedError, SharedResult};
+
+/// Opaque string id, serialized as a plain string. Backend ids are usually
+/// UUIDs but older records use other formats, so the inner value is not parsed.
+macro_rules! string_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
+
+            /// Fresh random (v4) id for records created on the client
+            pub fn generate() -> Self {
+                Self(Uuid::new_v4().to_string())
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+
+            pub fn to_uuid(&self) -> Option<Uuid> {
+                Uuid::parse_str(&self.0).ok()
+            }
+        }
+
+        impl From<Uuid> for $name {
+            fn from(id: Uuid) -> Self {
+                Self(id.to_string())
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(id.to_string())
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+string_id!(
+    /// Identifies a patient record
+    PatientId
+);
+string_id!(
+    /// Identifies a healthcare provider
+    ProviderId
+);
+string_id!(
+    /// Identifies a service booking, which may span several appointments
+    BookingId
+);
+string_id!(
+    /// Identifies a single scheduled appointment
+    AppointmentId
+);
+
+const DIGITS: &[u8] = b"0123456789";
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Luhn mod N check character for `segment`. All-digit segments use the
+/// classic mod 10 digit; alphanumeric ones use mod 36 over 0-9A-Z.
+fn check_character(segment: &str) -> Option<char> {
+    let alphabet = if segment.bytes().all(|b| b.is_ascii_digit()) { DIGITS } else { ALPHANUMERIC };
+    let n = alphabet.len();
+    let mut sum = 0;
+    for (i, byte) in segment.bytes().rev().enumerate() {
+        let code = alphabet.iter().position(|&c| c == byte)?;
+        let addend = if i % 2 == 0 { code * 2 } else { code };
+        sum += addend / n + addend % n;
+    }
+    Some(alphabet[(n - sum % n) % n] as char)
+}
+
+/// Medical record number, written `PREFIX-SEGMENT-C` (e.g. "MRN-00001234-6")
+/// where `C` is a check character that catches single-character typos and
+/// most transpositions. Parsing verifies the check character.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct MedicalRecordNumber(String);
+
+impl MedicalRecordNumber {
+    /// Parse and verify an MRN. Case and surrounding whitespace are ignored.
+    pub fn parse(input: &str) -> SharedResult<Self> {
+        let invalid = |reason: &str| {
+            SharedError::ValidationError(format!("Invalid medical record number {}: {}", input, reason))
+        };
+        let normalized = input.trim().to_ascii_uppercase();
+        let mut parts = normalized.rsplitn(3, '-');
+        let (check, segment, prefix) = (parts.next(), parts.next(), parts.next());
+        let (Some(check), Some(segment)) = (check, segment) else {
+            return Err(invalid("missing check character"));
+        };
+        if let Some(prefix) = prefix {
+            validate_prefix(prefix).map_err(|_| invalid("prefix must be 1-6 letters"))?;
+        }
+        if segment.is_empty() || !segment.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(invalid("number must be letters and digits"));
+        }
+        if check.len() != 1 || check_character(segment).map(String::from).as_deref() != Some(check) {
+            return Err(invalid("check character does not match"));
+        }
+        Ok(Self(normalized))
+    }
+
+    /// Append the check character to `segment`
+    pub fn from_parts(prefix: &str, segment: &str) -> SharedResult<Self> {
+        let segment = segment.trim().to_ascii_uppercase();
+        let check = check_character(&segment)
+            .filter(|_| !segment.is_empty())
+            .ok_or_else(|| SharedError::ValidationError("MRN number must be letters and digits".to_string()))?;
+        let prefix = prefix.trim().to_ascii_uppercase();
+        if prefix.is_empty() {
+            return Ok(Self(format!("{}-{}", segment, check)));
+        }
+        validate_prefix(&prefix)?;
+        Ok(Self(format!("{}-{}-{}", prefix, segment, check)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn prefix(&self) -> Option<&str> {
+        self.0.rsplitn(3, '-').nth(2)
+    }
+
+    /// The number without prefix or check character
+    pub fn segment(&self) -> &str {
+        self.0.rsplit('-').nth(1).unwrap_or_default()
+    }
+}
+
+fn validate_prefix(prefix: &str) -> SharedResult<()> {
+    if (1..=6).contains(&prefix.len()) && prefix.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(SharedError::ValidationError(format!("MRN prefix must be 1-6 letters: {}", prefix)))
+    }
+}
+
+impl TryFrom<String> for MedicalRecordNumber {
+    type Error = SharedError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<MedicalRecordNumber> for String {
+    fn from(mrn: MedicalRecordNumber) -> Self {
+        mrn.0
+    }
+}
+
+impl AsRef<str> for MedicalRecordNumber {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for MedicalRecordNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Issues MRNs for one facility, either from a sequence the caller allocates
+/// (e.g. a database counter) or from a random segment.
+#[derive(Debug, Clone)]
+pub struct MrnGenerator {
+    prefix: String,
+    sequence_width: usize,
+    random_length: usize,
+}
+
+impl MrnGenerator {
+    /// Generator for `prefix` ("" for none), with 8-character segments
+    pub fn new(prefix: &str) -> SharedResult<Self> {
+        let prefix = prefix.trim().to_ascii_uppercase();
+        if !prefix.is_empty() {
+            validate_prefix(&prefix)?;
+        }
+        Ok(Self { prefix, sequence_width: 8, random_length: 8 })
+    }
+
+    /// Zero-pad sequence numbers to `width` digits
+    pub fn with_sequence_width(mut self, width: usize) -> Self {
+        self.sequence_width = width.clamp(1, 18);
+        self
+    }
+
+    pub fn with_random_length(mut self, length: usize) -> Self {
+        self.random_length = length.clamp(4, 32);
+        self
+    }
+
+    /// MRN for a caller-allocated sequence number, e.g. "MRN-00001234-6"
+    pub fn sequential(&self, sequence: u64) -> SharedResult<MedicalRecordNumber> {
+        let segment = format!("{:0width$}", sequence, width = self.sequence_width);
+        if segment.len() > self.sequence_width {
+            return Err(SharedError::ValidationError(format!(
+                "Sequence {} does not fit in {} digits",
+                sequence, self.sequence_width
+            )));
+        }
+        MedicalRecordNumber::from_parts(&self.prefix, &segment)
+    }
+
+    /// MRN with a random alphanumeric segment, for systems without a shared counter
+    pub fn random(&self) -> MedicalRecordNumber {
+        let segment = crate::utils::strings::generate_random_string(self.random_length);
+        MedicalRecordNumber::from_parts(&self.prefix, &segment)
+            .expect("generated segments are alphanumeric")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_transparent_strings() {
+        let id = PatientId::new("pat-42");
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"pat-42\"");
+        assert_eq!(serde_json::from_str::<PatientId>("\"pat-42\"").unwrap(), id);
+        assert_eq!(format!("patients/{}", id), "patients/pat-42");
+        assert_eq!(id.to_uuid(), None);
+
+        let uuid = Uuid::new_v4();
+        let provider = ProviderId::from(uuid);
+        assert_eq!(provider.to_uuid(), Some(uuid));
+        assert_ne!(AppointmentId::generate(), AppointmentId::generate());
+    }
+
+    #[test]
+    fn test_medical_record_numbers() {
+        // Classic Luhn example: 7992739871 has check digit 3
+        assert_eq!(check_character("7992739871"), Some('3'));
+
+        let generator = MrnGenerator::new("mrn").unwrap();
+        let mrn = generator.sequential(1234).unwrap();
+        assert_eq!(mrn.segment(), "00001234");
+        assert_eq!(mrn.prefix(), Some("MRN"));
+        assert_eq!(MedicalRecordNumber::parse(&mrn.as_str().to_lowercase()).unwrap(), mrn);
+        assert!(generator.with_sequence_width(3).sequential(1234).is_err());
+
+        let random = MrnGenerator::new("").unwrap().random();
+        assert_eq!(random.prefix(), None);
+        assert_eq!(MedicalRecordNumber::parse(random.as_str()).unwrap(), random);
+
+        // A single changed digit or an adjacent swap fails the check
+        let check = mrn.as_str().chars().last().unwrap();
+        assert!(MedicalRecordNumber::parse(&format!("MRN-00001235-{}", check)).is_err());
+        assert!(MedicalRecordNumber::parse(&format!("MRN-00002134-{}", check)).is_err());
+        assert!(MedicalRecordNumber::parse("MRN-00001234").is_err());
+        assert!(MrnGenerator::new("MRN-1").is_err());
+
+        let json = serde_json::to_string(&mrn).unwrap();
+        assert_eq!(serde_json::from_str::<MedicalRecordNumber>(&json).unwrap(), mrn);
+        assert!(serde_json::from_str::<MedicalRecordNumber>("\"MRN-00001234-X\"").is_err());
+    }
+}
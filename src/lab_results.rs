@@ -0,0 +1,537 @@
+//! Lab results and panels (SC-007) with typed values, reference ranges, abnormal flags and trends
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::errors::{SharedError, SharedResult};
+use crate::ids::PatientId;
+use crate::units::{Analyte, Quantity, Unit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbnormalFlag {
+    Normal,
+    Low,
+    High,
+    CriticalLow,
+    CriticalHigh,
+    Abnormal, // Non-numeric results outside the expected value
+}
+
+impl AbnormalFlag {
+    pub fn is_abnormal(&self) -> bool {
+        !matches!(self, AbnormalFlag::Normal)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        matches!(self, AbnormalFlag::CriticalLow | AbnormalFlag::CriticalHigh)
+    }
+
+    /// 0 for normal, 1 for outside the range, 2 for critical; for picking the worst of several flags
+    pub fn severity(&self) -> u8 {
+        match self {
+            AbnormalFlag::Normal => 0,
+            AbnormalFlag::Low | AbnormalFlag::High | AbnormalFlag::Abnormal => 1,
+            AbnormalFlag::CriticalLow | AbnormalFlag::CriticalHigh => 2,
+        }
+    }
+
+    /// Conventional report code: "L", "H", "LL", "HH", "A", or "" when normal
+    pub fn code(&self) -> &'static str {
+        match self {
+            AbnormalFlag::Normal => "",
+            AbnormalFlag::Low => "L",
+            AbnormalFlag::High => "H",
+            AbnormalFlag::CriticalLow => "LL",
+            AbnormalFlag::CriticalHigh => "HH",
+            AbnormalFlag::Abnormal => "A",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceRange {
+    pub low: Option<f64>,
+    pub high: Option<f64>,
+    pub critical_low: Option<f64>,
+    pub critical_high: Option<f64>,
+    pub text: Option<String>, // e.g. "Negative", for qualitative tests
+}
+
+impl ReferenceRange {
+    pub fn between(low: f64, high: f64) -> Self {
+        Self { low: Some(low), high: Some(high), ..Self::default() }
+    }
+
+    pub fn with_critical(mut self, low: Option<f64>, high: Option<f64>) -> Self {
+        self.critical_low = low;
+        self.critical_high = high;
+        self
+    }
+
+    pub fn classify(&self, value: f64) -> AbnormalFlag {
+        if self.critical_low.is_some_and(|limit| value < limit) {
+            AbnormalFlag::CriticalLow
+        } else if self.critical_high.is_some_and(|limit| value > limit) {
+            AbnormalFlag::CriticalHigh
+        } else if self.low.is_some_and(|limit| value < limit) {
+            AbnormalFlag::Low
+        } else if self.high.is_some_and(|limit| value > limit) {
+            AbnormalFlag::High
+        } else {
+            AbnormalFlag::Normal
+        }
+    }
+
+    /// "70–100", "< 200", "> 40" or the qualitative text
+    pub fn label(&self) -> Option<String> {
+        match (self.low, self.high) {
+            (Some(low), Some(high)) => Some(format!("{}–{}", low, high)),
+            (None, Some(high)) => Some(format!("< {}", high)),
+            (Some(low), None) => Some(format!("> {}", low)),
+            (None, None) => self.text.clone(),
+        }
+    }
+}
+
+/// A result value as reported by the lab
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LabValue {
+    /// Value in a known [`Unit`], so results from different labs can be converted
+    Quantity(Quantity),
+    /// Value in a unit `units` does not model, e.g. "g/dL" or "10^3/µL"
+    Numeric { value: f64, unit: String },
+    /// Qualitative result such as "Positive"
+    Text { value: String },
+}
+
+impl LabValue {
+    /// Typed value from the loose fields labs send; numeric values win over text
+    pub fn from_parts(value: Option<f64>, unit: Option<&str>, text: Option<&str>) -> Option<Self> {
+        match (value, text) {
+            (Some(value), _) => Some(match unit.and_then(Unit::parse) {
+                Some(unit) => LabValue::Quantity(Quantity::new(value, unit)),
+                None => LabValue::Numeric { value, unit: unit.unwrap_or_default().trim().to_string() },
+            }),
+            (None, Some(text)) if !text.trim().is_empty() => Some(LabValue::Text { value: text.trim().to_string() }),
+            _ => None,
+        }
+    }
+
+    pub fn numeric(&self) -> Option<f64> {
+        match self {
+            LabValue::Quantity(quantity) => Some(quantity.value),
+            LabValue::Numeric { value, .. } => Some(*value),
+            LabValue::Text { .. } => None,
+        }
+    }
+
+    pub fn unit_symbol(&self) -> Option<&str> {
+        match self {
+            LabValue::Quantity(quantity) => Some(quantity.unit.symbol()),
+            LabValue::Numeric { unit, .. } if !unit.is_empty() => Some(unit),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LabValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LabValue::Quantity(quantity) => quantity.fmt(f),
+            LabValue::Numeric { value, unit } => write!(f, "{} {}", value, unit),
+            LabValue::Text { value } => f.write_str(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabResultStatus {
+    Preliminary,
+    Final,
+    Corrected,
+    Cancelled,
+}
+
+impl LabResultStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "preliminary" | "partial" | "registered" => Some(LabResultStatus::Preliminary),
+            "final" => Some(LabResultStatus::Final),
+            "corrected" | "amended" => Some(LabResultStatus::Corrected),
+            "cancelled" | "canceled" | "entered-in-error" => Some(LabResultStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Final and corrected results can be acted on
+    pub fn is_reportable(&self) -> bool {
+        matches!(self, LabResultStatus::Final | LabResultStatus::Corrected)
+    }
+}
+
+/// One observation, e.g. fasting glucose
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabResult {
+    pub id: String,
+    pub patient_id: PatientId,
+    pub order_id: Option<String>,
+    /// LOINC code
+    pub test_code: String,
+    pub test_name: String,
+    pub value: LabValue,
+    pub reference_range: Option<ReferenceRange>,
+    /// Flag reported by the lab; wins over one derived from the range
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reported_flag: Option<AbnormalFlag>,
+    pub status: LabResultStatus,
+    pub performing_lab: Option<String>,
+    pub collected_at: DateTime<Utc>,
+    pub reported_at: Option<DateTime<Utc>>,
+}
+
+impl LabResult {
+    pub fn new(
+        id: impl Into<String>,
+        patient_id: PatientId,
+        test_code: impl Into<String>,
+        test_name: impl Into<String>,
+        value: LabValue,
+        collected_at: DateTime<Utc>,
+    ) -> Self {
+        let test_code = test_code.into();
+        let test_name = test_name.into();
+        let analyte = Analyte::from_loinc(&test_code).or_else(|| Analyte::infer(&test_name));
+        let value = match value {
+            LabValue::Quantity(quantity) if quantity.analyte.is_none() => {
+                LabValue::Quantity(quantity.with_analyte(analyte))
+            }
+            value => value,
+        };
+        Self {
+            id: id.into(),
+            patient_id,
+            order_id: None,
+            test_code,
+            test_name,
+            value,
+            reference_range: None,
+            reported_flag: None,
+            status: LabResultStatus::Final,
+            performing_lab: None,
+            collected_at,
+            reported_at: None,
+        }
+    }
+
+    pub fn with_reference_range(mut self, range: ReferenceRange) -> Self {
+        self.reference_range = Some(range);
+        self
+    }
+
+    pub fn with_status(mut self, status: LabResultStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Lab-reported flag if any; otherwise derived from the value and range.
+    /// Qualitative results are abnormal when they differ from the range text.
+    pub fn flag(&self) -> Option<AbnormalFlag> {
+        if self.reported_flag.is_some() {
+            return self.reported_flag;
+        }
+        let range = self.reference_range.as_ref()?;
+        match &self.value {
+            LabValue::Text { value } => {
+                let expected = range.text.as_deref()?;
+                Some(if value.trim().eq_ignore_ascii_case(expected.trim()) {
+                    AbnormalFlag::Normal
+                } else {
+                    AbnormalFlag::Abnormal
+                })
+            }
+            value => value.numeric().map(|v| range.classify(v)),
+        }
+    }
+
+    pub fn is_abnormal(&self) -> bool {
+        self.flag().is_some_and(|flag| flag.is_abnormal())
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.flag().is_some_and(|flag| flag.is_critical())
+    }
+
+    /// Same result reported in `unit`, reference range included, so results from
+    /// labs using different units can be compared and trended together
+    pub fn in_unit(&self, unit: Unit) -> SharedResult<LabResult> {
+        let LabValue::Quantity(quantity) = &self.value else {
+            return Err(SharedError::ValidationError(format!(
+                "Lab result {} has no value in a known unit",
+                self.id
+            )));
+        };
+        let convert = |value: Option<f64>| {
+            value
+                .map(|value| Quantity { value, ..*quantity }.value_in(unit))
+                .transpose()
+        };
+
+        let mut result = self.clone();
+        result.value = LabValue::Quantity(quantity.convert_to(unit)?);
+        if let Some(range) = result.reference_range.as_mut() {
+            range.low = convert(range.low)?;
+            range.high = convert(range.high)?;
+            range.critical_low = convert(range.critical_low)?;
+            range.critical_high = convert(range.critical_high)?;
+        }
+        Ok(result)
+    }
+}
+
+/// Results ordered together, e.g. a lipid profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabPanel {
+    pub id: String,
+    pub patient_id: PatientId,
+    pub order_id: Option<String>,
+    /// LOINC panel code
+    pub panel_code: Option<String>,
+    pub name: String,
+    pub results: Vec<LabResult>,
+    pub collected_at: DateTime<Utc>,
+}
+
+impl LabPanel {
+    pub fn abnormal_results(&self) -> impl Iterator<Item = &LabResult> {
+        self.results.iter().filter(|r| r.is_abnormal())
+    }
+
+    pub fn critical_results(&self) -> impl Iterator<Item = &LabResult> {
+        self.results.iter().filter(|r| r.is_critical())
+    }
+
+    pub fn has_critical(&self) -> bool {
+        self.critical_results().next().is_some()
+    }
+
+    /// Most severe flag across the panel
+    pub fn worst_flag(&self) -> Option<AbnormalFlag> {
+        self.results.iter().filter_map(LabResult::flag).max_by_key(AbnormalFlag::severity)
+    }
+
+    /// Preliminary until every result that wasn't cancelled is final or corrected
+    pub fn status(&self) -> LabResultStatus {
+        let active: Vec<_> = self
+            .results
+            .iter()
+            .map(|r| r.status)
+            .filter(|s| *s != LabResultStatus::Cancelled)
+            .collect();
+        if active.is_empty() {
+            LabResultStatus::Cancelled
+        } else if active.iter().any(|s| !s.is_reportable()) {
+            LabResultStatus::Preliminary
+        } else if active.contains(&LabResultStatus::Corrected) {
+            LabResultStatus::Corrected
+        } else {
+            LabResultStatus::Final
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    Rising,
+    Falling,
+    Stable,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub collected_at: DateTime<Utc>,
+    pub value: f64,
+    pub flag: Option<AbnormalFlag>,
+}
+
+/// Numeric history of one test, oldest first, in a single unit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabTrend {
+    pub test_code: String,
+    pub test_name: String,
+    pub unit: Option<String>,
+    pub points: Vec<TrendPoint>,
+}
+
+impl LabTrend {
+    /// Trend of `test_code` across `results`, in the unit of the latest result.
+    /// Cancelled results and values that can't be brought into that unit are left out.
+    pub fn from_results(results: &[LabResult], test_code: &str) -> Option<Self> {
+        let mut matching: Vec<&LabResult> = results
+            .iter()
+            .filter(|r| r.test_code == test_code && r.status != LabResultStatus::Cancelled && r.value.numeric().is_some())
+            .collect();
+        matching.sort_by_key(|r| r.collected_at);
+        let latest = *matching.last()?;
+        let unit = latest.value.unit_symbol().map(str::to_string);
+
+        let points = matching
+            .into_iter()
+            .filter_map(|result| {
+                let result = match (&latest.value, &result.value) {
+                    (LabValue::Quantity(target), LabValue::Quantity(_)) => result.in_unit(target.unit).ok()?,
+                    (LabValue::Numeric { unit: target, .. }, LabValue::Numeric { unit, .. })
+                        if unit.eq_ignore_ascii_case(target) =>
+                    {
+                        result.clone()
+                    }
+                    _ => return None,
+                };
+                Some(TrendPoint {
+                    collected_at: result.collected_at,
+                    value: result.value.numeric()?,
+                    flag: result.flag(),
+                })
+            })
+            .collect();
+
+        Some(Self {
+            test_code: test_code.to_string(),
+            test_name: latest.test_name.clone(),
+            unit,
+            points,
+        })
+    }
+
+    pub fn latest(&self) -> Option<&TrendPoint> {
+        self.points.last()
+    }
+
+    pub fn previous(&self) -> Option<&TrendPoint> {
+        self.points.iter().rev().nth(1)
+    }
+
+    /// Latest minus previous value
+    pub fn change(&self) -> Option<f64> {
+        Some(self.latest()?.value - self.previous()?.value)
+    }
+
+    pub fn percent_change(&self) -> Option<f64> {
+        let previous = self.previous()?.value;
+        if previous == 0.0 {
+            return None;
+        }
+        Some(self.change()? / previous.abs() * 100.0)
+    }
+
+    /// Direction of the latest change; changes within `tolerance_percent` count as stable
+    pub fn direction(&self, tolerance_percent: f64) -> Option<TrendDirection> {
+        let change = self.change()?;
+        let stable = match self.percent_change() {
+            Some(percent) => percent.abs() <= tolerance_percent,
+            None => change == 0.0,
+        };
+        Some(if stable {
+            TrendDirection::Stable
+        } else if change > 0.0 {
+            TrendDirection::Rising
+        } else {
+            TrendDirection::Falling
+        })
+    }
+
+    /// The latest value is out of range and the previous one was not
+    pub fn newly_abnormal(&self) -> bool {
+        let is_abnormal = |point: Option<&TrendPoint>| point.and_then(|p| p.flag).is_some_and(|f| f.is_abnormal());
+        is_abnormal(self.latest()) && self.previous().is_some() && !is_abnormal(self.previous())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn glucose(id: &str, day: u32, value: f64, unit: Unit) -> LabResult {
+        let range = match unit {
+            Unit::MillimolesPerLiter => ReferenceRange::between(3.9, 5.6),
+            _ => ReferenceRange::between(70.0, 100.0).with_critical(Some(40.0), Some(400.0)),
+        };
+        LabResult::new(
+            id,
+            PatientId::new("pat-1"),
+            "2345-7",
+            "Glucose",
+            LabValue::Quantity(Quantity::new(value, unit)),
+            Utc.with_ymd_and_hms(2026, 3, day, 8, 0, 0).unwrap(),
+        )
+        .with_reference_range(range)
+    }
+
+    #[test]
+    fn test_flags_from_ranges_and_text() {
+        assert_eq!(glucose("g1", 1, 92.0, Unit::MilligramsPerDeciliter).flag(), Some(AbnormalFlag::Normal));
+        assert_eq!(glucose("g2", 1, 130.0, Unit::MilligramsPerDeciliter).flag(), Some(AbnormalFlag::High));
+        assert!(glucose("g3", 1, 35.0, Unit::MilligramsPerDeciliter).is_critical());
+
+        let covid = LabResult::new(
+            "c1",
+            PatientId::new("pat-1"),
+            "94500-6",
+            "SARS-CoV-2 RNA",
+            LabValue::from_parts(None, None, Some("Positive")).unwrap(),
+            Utc::now(),
+        )
+        .with_reference_range(ReferenceRange { text: Some("Negative".to_string()), ..ReferenceRange::default() });
+        assert_eq!(covid.flag(), Some(AbnormalFlag::Abnormal));
+
+        let hemoglobin = LabValue::from_parts(Some(13.5), Some("g/dL"), None).unwrap();
+        assert_eq!(hemoglobin, LabValue::Numeric { value: 13.5, unit: "g/dL".to_string() });
+        assert_eq!(hemoglobin.to_string(), "13.5 g/dL");
+
+        let json = serde_json::to_value(&covid).unwrap();
+        assert_eq!(json["value"]["kind"], "text");
+        assert_eq!(serde_json::from_value::<LabResult>(json).unwrap(), covid);
+    }
+
+    #[test]
+    fn test_panel_summary() {
+        let panel = LabPanel {
+            id: "p1".to_string(),
+            patient_id: PatientId::new("pat-1"),
+            order_id: None,
+            panel_code: None,
+            name: "Glucose series".to_string(),
+            results: vec![
+                glucose("g1", 1, 92.0, Unit::MilligramsPerDeciliter),
+                glucose("g2", 1, 420.0, Unit::MilligramsPerDeciliter).with_status(LabResultStatus::Preliminary),
+                glucose("g3", 1, 130.0, Unit::MilligramsPerDeciliter),
+            ],
+            collected_at: Utc::now(),
+        };
+        assert_eq!(panel.abnormal_results().count(), 2);
+        assert!(panel.has_critical());
+        assert_eq!(panel.worst_flag(), Some(AbnormalFlag::CriticalHigh));
+        assert_eq!(panel.status(), LabResultStatus::Preliminary);
+    }
+
+    #[test]
+    fn test_trend_converts_units() {
+        let results = vec![
+            glucose("g3", 20, 140.0, Unit::MilligramsPerDeciliter),
+            glucose("g1", 1, 5.0, Unit::MillimolesPerLiter),
+            glucose("g2", 10, 95.0, Unit::MilligramsPerDeciliter).with_status(LabResultStatus::Cancelled),
+        ];
+        let trend = LabTrend::from_results(&results, "2345-7").unwrap();
+        assert_eq!(trend.unit.as_deref(), Some("mg/dL"));
+        assert_eq!(trend.points.len(), 2);
+        assert!((trend.points[0].value - 90.08).abs() < 0.01);
+        assert_eq!(trend.direction(5.0), Some(TrendDirection::Rising));
+        assert!(trend.newly_abnormal());
+        assert!(LabTrend::from_results(&results, "2093-3").is_none());
+    }
+}
@@ -3,12 +3,32 @@
 
 // Core modules
 pub mod models;
+pub mod ids;
 pub mod errors;
-#[cfg(feature = "post-quantum")]
 pub mod auth;
+pub mod encryption;
+pub mod secrets;
 pub mod compliance;
 pub mod utils;
 pub mod events;
+pub mod event_store;
+pub mod appointment_events;
+pub mod geo;
+pub mod units;
+pub mod money;
+pub mod vitals;
+pub mod lab_results;
+pub mod care_plan;
+pub mod recurrence;
+pub mod drug_interactions;
+pub mod i18n;
+pub mod api_backend;
+pub mod api_cache;
+pub mod api_metrics;
+pub mod backend_status;
+pub mod schema;
+pub mod offline_queue;
+pub mod outbox;
 pub mod healthcare_service_engine; // Healthcare business logic and service configurations
 
 // UI modules (feature-gated for frontend)
@@ -24,15 +44,37 @@ pub mod emergency_simple;
 pub mod location_simple;
 #[cfg(feature = "ui")]
 pub mod api_client;
+#[cfg(feature = "webauthn")]
+pub mod webauthn_simple;
 
 // Re-exports
 pub use models::*;
+pub use ids::*;
 pub use errors::*;
-#[cfg(feature = "post-quantum")]
 pub use auth::*;
+pub use encryption::*;
+pub use secrets::*;
 pub use compliance::*;
 pub use utils::*;
 pub use events::*;
+pub use event_store::*;
+pub use appointment_events::*;
+pub use geo::*;
+pub use units::*;
+pub use money::*;
+pub use vitals::*;
+pub use lab_results::*;
+pub use care_plan::*;
+pub use recurrence::*;
+pub use drug_interactions::*;
+pub use i18n::*;
+pub use api_backend::*;
+pub use api_cache::*;
+pub use api_metrics::*;
+pub use backend_status::*;
+pub use schema::*;
+pub use offline_queue::*;
+pub use outbox::*;
 pub use healthcare_service_engine::*; // Re-export healthcare service engine components
 
 #[cfg(feature = "ui")]
@@ -47,3 +89,5 @@ pub use emergency_simple::*;
 pub use location_simple::*;
 #[cfg(feature = "ui")]
 pub use api_client::*;
+#[cfg(feature = "webauthn")]
+pub use webauthn_simple::*;
@@ -10,6 +10,8 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use js_sys::Promise;
 use gloo_timers::callback::Interval;
+use crate::geo::GeoPoint;
+use crate::ids::{BookingId, PatientId, ProviderId};
 use crate::websocket_simple::{
     SimpleWebSocketClient, LocationUpdate, EmergencyAlert, MessageType, 
     create_location_update
@@ -47,9 +49,9 @@ impl ProviderStatus {
 }
 
 pub struct SimpleLocationTracker {
-    pub provider_id: String,
+    pub provider_id: ProviderId,
     pub current_status: ProviderStatus,
-    pub current_location: Option<(f64, f64)>,
+    pub current_location: Option<GeoPoint>,
     pub websocket_client: Option<SimpleWebSocketClient>,
     pub tracking_active: bool,
     pub location_interval: Option<Interval>,
@@ -58,7 +60,7 @@ pub struct SimpleLocationTracker {
 }
 
 impl SimpleLocationTracker {
-    pub fn new(provider_id: String) -> Self {
+    pub fn new(provider_id: ProviderId) -> Self {
         Self {
             provider_id,
             current_status: ProviderStatus::Offline,
@@ -161,8 +163,8 @@ impl SimpleLocationTracker {
         if let Some(client) = &self.websocket_client {
             let location_update = create_location_update(
                 self.provider_id.clone(),
-                location.0,
-                location.1,
+                location.latitude,
+                location.longitude,
                 10.0, // accuracy in meters
                 self.current_status.to_string(),
             );
@@ -178,7 +180,7 @@ impl SimpleLocationTracker {
     }
     
     // Get current location using Web Geolocation API
-    async fn get_current_location(&self) -> Result<(f64, f64), String> {
+    async fn get_current_location(&self) -> Result<GeoPoint, String> {
         let window = web_sys::window().ok_or("No window object")?;
         let geolocation = window.navigator().geolocation()
             .map_err(|_| "Geolocation not supported")?;
@@ -219,7 +221,7 @@ impl SimpleLocationTracker {
         let lat = js_result.as_f64().unwrap_or(0.0);
         let lng = js_result.as_f64().unwrap_or(0.0);
         
-        Ok((lat, lng))
+        GeoPoint::new(lat, lng).map_err(|e| e.to_string())
     }
     
     // Respond to emergency alert
@@ -254,22 +256,20 @@ impl SimpleLocationTracker {
         Ok(())
     }
     
-    // Get distance to a location (simplified calculation)
-    pub fn calculate_distance_to(&self, target_lat: f64, target_lng: f64) -> Option<f64> {
-        if let Some((current_lat, current_lng)) = self.current_location {
-            // Simplified distance calculation (not accurate for long distances)
-            let lat_diff = target_lat - current_lat;
-            let lng_diff = target_lng - current_lng;
-            let distance = ((lat_diff * lat_diff) + (lng_diff * lng_diff)).sqrt() * 111000.0; // Rough conversion to meters
-            Some(distance)
-        } else {
-            None
-        }
+    // Great-circle distance to a location in meters
+    pub fn calculate_distance_to(&self, target: &GeoPoint) -> Option<f64> {
+        self.current_location.map(|current| current.distance_m(target))
+    }
+    
+    // Whether the provider is within `radius_km` of a location, e.g. to mark arrival
+    pub fn is_within_km(&self, target: &GeoPoint, radius_km: f64) -> bool {
+        self.current_location
+            .is_some_and(|current| current.within_radius_km(target, radius_km))
     }
     
     // Get estimated time to location (simplified)
-    pub fn estimate_arrival_time(&self, target_lat: f64, target_lng: f64) -> Option<u32> {
-        if let Some(distance) = self.calculate_distance_to(target_lat, target_lng) {
+    pub fn estimate_arrival_time(&self, target: &GeoPoint) -> Option<u32> {
+        if let Some(distance) = self.calculate_distance_to(target) {
             // Assume average speed of 40 km/h in city
             let time_hours = distance / 40000.0;
             let time_minutes = (time_hours * 60.0) as u32;
@@ -306,8 +306,8 @@ impl SimpleLocationTracker {
 // Booking notification handler
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BookingNotification {
-    pub booking_id: String,
-    pub patient_id: String,
+    pub booking_id: BookingId,
+    pub patient_id: PatientId,
     pub patient_location: Option<(f64, f64)>,
     pub service_type: String,
     pub urgency: String,
@@ -317,8 +317,8 @@ pub struct BookingNotification {
 
 impl BookingNotification {
     pub fn new(
-        booking_id: String,
-        patient_id: String,
+        booking_id: BookingId,
+        patient_id: PatientId,
         patient_location: Option<(f64, f64)>,
         service_type: String,
         urgency: String,
@@ -347,13 +347,13 @@ impl BookingNotification {
 
 // Provider notification system
 pub struct ProviderNotificationManager {
-    pub provider_id: String,
+    pub provider_id: ProviderId,
     pub notifications: Vec<BookingNotification>,
     pub emergency_alerts: Vec<EmergencyAlert>,
 }
 
 impl ProviderNotificationManager {
-    pub fn new(provider_id: String) -> Self {
+    pub fn new(provider_id: ProviderId) -> Self {
         Self {
             provider_id,
             notifications: Vec::new(),
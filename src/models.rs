@@ -3,15 +3,21 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use validator::Validate;
 
+use crate::errors::{SharedError, SharedResult};
+use crate::events::NotificationType;
+use crate::healthcare_service_engine::ServiceCategory;
+use crate::ids::{AppointmentId, BookingId, MedicalRecordNumber, PatientId, ProviderId};
+use crate::money::Money;
+
 // Healthcare Service Pricing Structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServicePricing {
-    pub base_cost: f64,
+    pub base_cost: Money,
     pub surge_multiplier: f64,
-    pub total_cost: f64,
-    pub provider_share: f64,
-    pub platform_fee: f64,
-    pub estimated_insurance_coverage: Option<f64>,
+    pub total_cost: Money,
+    pub provider_share: Money,
+    pub platform_fee: Money,
+    pub estimated_insurance_coverage: Option<Money>,
 }
 
 // Referral System Record
@@ -29,7 +35,7 @@ pub struct ReferralRecord {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct Patient {
-    pub id: Uuid,
+    pub id: PatientId,
     #[validate(length(min = 1, max = 100))]
     pub first_name: String,
     #[validate(length(min = 1, max = 100))]
@@ -41,16 +47,23 @@ pub struct Patient {
     pub date_of_birth: chrono::NaiveDate,
     pub gender: Gender,
     pub address: Address,
-    pub medical_record_number: String,
+    pub medical_record_number: MedicalRecordNumber,
     pub emergency_contact: EmergencyContact,
     pub insurance_info: Option<InsuranceInfo>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Patient {
+    /// Age in completed years on `date`
+    pub fn age_on(&self, date: chrono::NaiveDate) -> u32 {
+        crate::utils::datetime::age_in_years(self.date_of_birth, date)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct Provider {
-    pub id: Uuid,
+    pub id: ProviderId,
     #[validate(length(min = 1, max = 100))]
     pub first_name: String,
     #[validate(length(min = 1, max = 100))]
@@ -74,9 +87,9 @@ pub struct Provider {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Appointment {
-    pub id: Uuid,
-    pub patient_id: Uuid,
-    pub provider_id: Uuid,
+    pub id: AppointmentId,
+    pub patient_id: PatientId,
+    pub provider_id: ProviderId,
     pub appointment_type: AppointmentType,
     pub scheduled_time: DateTime<Utc>,
     pub duration_minutes: i32,
@@ -121,6 +134,143 @@ pub struct InsuranceInfo {
     pub expiry_date: chrono::NaiveDate,
 }
 
+/// A patient's insurance policy with per-service coverage, as returned by the payer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InsurancePolicy {
+    pub id: String,
+    pub patient_id: PatientId,
+    pub payer_name: String,
+    pub policy_number: String,
+    pub group_number: Option<String>,
+    pub coverage_type: CoverageType,
+    pub effective_from: chrono::NaiveDate,
+    pub expires_on: chrono::NaiveDate,
+    pub currency: String,
+    #[serde(default)]
+    pub coverages: Vec<CoverageDetail>,
+}
+
+impl InsurancePolicy {
+    pub fn is_active_on(&self, date: chrono::NaiveDate) -> bool {
+        (self.effective_from..=self.expires_on).contains(&date)
+    }
+
+    pub fn coverage_for(&self, category: &ServiceCategory) -> Option<&CoverageDetail> {
+        self.coverages.iter().find(|c| &c.service_category == category)
+    }
+
+    /// Eligibility worked out from the cached policy, for when the payer can't
+    /// be reached. The result has no payer reference and is only an estimate.
+    pub fn estimate_eligibility(
+        &self,
+        booking_id: BookingId,
+        category: &ServiceCategory,
+        billed_amount: f64,
+        on: chrono::NaiveDate,
+    ) -> EligibilityCheckResult {
+        let coverage = self.coverage_for(category);
+        let status = match coverage {
+            _ if !self.is_active_on(on) => EligibilityStatus::PolicyInactive,
+            None => EligibilityStatus::NotCovered,
+            Some(c) if c.requires_preauthorization => EligibilityStatus::PreauthorizationRequired,
+            Some(_) => EligibilityStatus::Eligible,
+        };
+        let covered_amount = match coverage {
+            Some(c) if status.is_covered() => c.covered_amount(billed_amount),
+            _ => 0.0,
+        };
+
+        EligibilityCheckResult {
+            policy_id: self.id.clone(),
+            payer_name: self.payer_name.clone(),
+            booking_id,
+            service_category: category.clone(),
+            status,
+            billed_amount,
+            covered_amount,
+            patient_responsibility: billed_amount - covered_amount,
+            currency: self.currency.clone(),
+            reference: None,
+            messages: vec!["Estimated from saved policy details".to_string()],
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Summary kept on the patient record
+    pub fn info(&self) -> InsuranceInfo {
+        InsuranceInfo {
+            provider_name: self.payer_name.clone(),
+            policy_number: self.policy_number.clone(),
+            group_number: self.group_number.clone(),
+            coverage_type: self.coverage_type,
+            expiry_date: self.expires_on,
+        }
+    }
+}
+
+/// What a policy pays for one service category
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoverageDetail {
+    pub service_category: ServiceCategory,
+    /// Share of the amount after copay that the payer covers, 0-100
+    pub coverage_percentage: f64,
+    /// Fixed amount the patient pays per visit
+    #[serde(default)]
+    pub copay: f64,
+    /// Benefit left for the policy year; `None` when unlimited
+    pub remaining_benefit: Option<f64>,
+    #[serde(default)]
+    pub requires_preauthorization: bool,
+}
+
+impl CoverageDetail {
+    /// Amount the payer covers for a bill of `billed_amount`
+    pub fn covered_amount(&self, billed_amount: f64) -> f64 {
+        let share = (billed_amount - self.copay).max(0.0) * self.coverage_percentage.clamp(0.0, 100.0) / 100.0;
+        match self.remaining_benefit {
+            Some(remaining) => share.min(remaining.max(0.0)),
+            None => share,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EligibilityStatus {
+    Eligible,
+    /// Covered once the payer approves the booking
+    PreauthorizationRequired,
+    NotCovered,
+    PolicyInactive,
+    /// The payer did not give a definite answer
+    Unknown,
+}
+
+impl EligibilityStatus {
+    pub fn is_covered(&self) -> bool {
+        matches!(self, EligibilityStatus::Eligible | EligibilityStatus::PreauthorizationRequired)
+    }
+}
+
+/// Outcome of checking a booking against an insurance policy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EligibilityCheckResult {
+    pub policy_id: String,
+    pub payer_name: String,
+    pub booking_id: BookingId,
+    pub service_category: ServiceCategory,
+    pub status: EligibilityStatus,
+    pub billed_amount: f64,
+    pub covered_amount: f64,
+    pub patient_responsibility: f64,
+    pub currency: String,
+    /// Payer reference to quote on the claim; `None` for local estimates
+    pub reference: Option<String>,
+    #[serde(default)]
+    pub messages: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AvailabilitySchedule {
     pub monday: Option<TimeSlot>,
@@ -132,12 +282,88 @@ pub struct AvailabilitySchedule {
     pub sunday: Option<TimeSlot>,
 }
 
+impl AvailabilitySchedule {
+    pub fn for_weekday(&self, weekday: chrono::Weekday) -> Option<&TimeSlot> {
+        match weekday {
+            chrono::Weekday::Mon => self.monday.as_ref(),
+            chrono::Weekday::Tue => self.tuesday.as_ref(),
+            chrono::Weekday::Wed => self.wednesday.as_ref(),
+            chrono::Weekday::Thu => self.thursday.as_ref(),
+            chrono::Weekday::Fri => self.friday.as_ref(),
+            chrono::Weekday::Sat => self.saturday.as_ref(),
+            chrono::Weekday::Sun => self.sunday.as_ref(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeSlot {
     pub start_time: chrono::NaiveTime,
     pub end_time: chrono::NaiveTime,
 }
 
+/// One-off change to the weekly schedule; `slot: None` marks the whole day unavailable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailabilityException {
+    pub date: chrono::NaiveDate,
+    pub slot: Option<TimeSlot>,
+    pub reason: Option<String>,
+}
+
+/// Weekly availability a provider publishes, in the provider's local timezone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderAvailability {
+    pub provider_id: ProviderId,
+    pub timezone: String,
+    pub weekly_schedule: AvailabilitySchedule,
+    pub exceptions: Vec<AvailabilityException>,
+    pub slot_duration_minutes: i64,
+}
+
+impl ProviderAvailability {
+    /// Working window for `date`, with exceptions taking precedence over the weekly schedule
+    pub fn window_on(&self, date: chrono::NaiveDate) -> Option<&TimeSlot> {
+        use chrono::Datelike;
+
+        match self.exceptions.iter().find(|e| e.date == date) {
+            Some(exception) => exception.slot.as_ref(),
+            None => self.weekly_schedule.for_weekday(date.weekday()),
+        }
+    }
+
+    /// Bookable slots on `date`, before removing already-booked appointments
+    pub fn slots_on(&self, date: chrono::NaiveDate) -> SharedResult<Vec<AppointmentSlot>> {
+        let Some(window) = self.window_on(date) else {
+            return Ok(Vec::new());
+        };
+
+        let starts = crate::utils::datetime::generate_slots(
+            date,
+            window.start_time,
+            window.end_time,
+            self.slot_duration_minutes,
+            &self.timezone,
+        )?;
+
+        Ok(starts
+            .into_iter()
+            .map(|start_time| AppointmentSlot {
+                provider_id: self.provider_id.clone(),
+                start_time,
+                end_time: start_time + chrono::Duration::minutes(self.slot_duration_minutes),
+            })
+            .collect())
+    }
+}
+
+/// A concrete bookable interval in UTC
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AppointmentSlot {
+    pub provider_id: ProviderId,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsultationFee {
     pub base_fee: f64,
@@ -156,15 +382,296 @@ pub struct Prescription {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Medication {
-    pub name: String,
+    /// Generic (INN) name; interaction checks match on this, never the brand
+    #[serde(alias = "name")]
+    pub generic_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brand_name: Option<String>,
+    /// Strength of one unit, e.g. "500 mg"
+    pub strength: String,
+    pub route: MedicationRoute,
+    /// Amount per dose, e.g. "1 tablet"
     pub dosage: String,
-    pub frequency: String,
+    pub frequency: DoseFrequency,
     pub duration: String,
     pub instructions: Option<String>,
 }
 
+impl Medication {
+    pub fn new(
+        generic_name: impl Into<String>,
+        strength: impl Into<String>,
+        route: MedicationRoute,
+        frequency: DoseFrequency,
+    ) -> Self {
+        Self {
+            generic_name: generic_name.into(),
+            brand_name: None,
+            strength: strength.into(),
+            route,
+            dosage: String::new(),
+            frequency,
+            duration: String::new(),
+            instructions: None,
+        }
+    }
+
+    /// "Paracetamol 500 mg (Crocin)"
+    pub fn display_name(&self) -> String {
+        let name = format!("{} {}", self.generic_name, self.strength);
+        match &self.brand_name {
+            Some(brand) => format!("{} ({})", name.trim_end(), brand),
+            None => name.trim_end().to_string(),
+        }
+    }
+
+    /// Directions in prescriber shorthand, e.g. "1 tablet PO BD for 5 days"
+    pub fn sig(&self) -> String {
+        let mut parts = vec![self.dosage.clone(), self.route.abbreviation().to_string(), self.frequency.code()];
+        if !self.duration.is_empty() {
+            parts.push(format!("for {}", self.duration));
+        }
+        parts.retain(|part| !part.is_empty());
+        parts.join(" ")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Allergy {
+    /// Substance as recorded, e.g. "Penicillin" or "Peanuts"
+    pub allergen: String,
+    pub category: AllergenCategory,
+    #[serde(default)]
+    pub reactions: Vec<AllergyReaction>,
+    pub severity: AllergySeverity,
+    pub onset: Option<chrono::NaiveDate>,
+    pub verification: AllergyVerification,
+    pub notes: Option<String>,
+}
+
+impl Allergy {
+    pub fn new(allergen: impl Into<String>, category: AllergenCategory, severity: AllergySeverity) -> Self {
+        Self {
+            allergen: allergen.into(),
+            category,
+            reactions: Vec::new(),
+            severity,
+            onset: None,
+            verification: AllergyVerification::Unconfirmed,
+            notes: None,
+        }
+    }
+
+    /// Refuted and erroneous entries stay on record but are not shown or checked
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.verification,
+            AllergyVerification::Unconfirmed | AllergyVerification::Confirmed
+        )
+    }
+
+    /// "Penicillin (anaphylaxis, hives)"
+    pub fn label(&self) -> String {
+        if self.reactions.is_empty() {
+            return self.allergen.clone();
+        }
+        let reactions: Vec<String> = self.reactions.iter().map(AllergyReaction::label).collect();
+        format!("{} ({})", self.allergen, reactions.join(", "))
+    }
+}
+
 // Enums
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AllergenCategory {
+    Medication,
+    Food,
+    Environmental,
+    /// Vaccines, blood products and other biologics
+    Biologic,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AllergyReaction {
+    Rash,
+    Hives,
+    Itching,
+    Angioedema,
+    Wheezing,
+    Anaphylaxis,
+    Nausea,
+    Diarrhea,
+    Other(String),
+}
+
+impl AllergyReaction {
+    pub fn label(&self) -> String {
+        match self {
+            AllergyReaction::Rash => "rash".to_string(),
+            AllergyReaction::Hives => "hives".to_string(),
+            AllergyReaction::Itching => "itching".to_string(),
+            AllergyReaction::Angioedema => "angioedema".to_string(),
+            AllergyReaction::Wheezing => "wheezing".to_string(),
+            AllergyReaction::Anaphylaxis => "anaphylaxis".to_string(),
+            AllergyReaction::Nausea => "nausea".to_string(),
+            AllergyReaction::Diarrhea => "diarrhoea".to_string(),
+            AllergyReaction::Other(description) => description.to_lowercase(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AllergySeverity {
+    Mild,
+    Moderate,
+    Severe,
+    LifeThreatening,
+}
+
+impl AllergySeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AllergySeverity::Mild => "mild",
+            AllergySeverity::Moderate => "moderate",
+            AllergySeverity::Severe => "severe",
+            AllergySeverity::LifeThreatening => "life-threatening",
+        }
+    }
+}
+
+/// Clinical verification status, following FHIR AllergyIntolerance
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AllergyVerification {
+    /// Reported by the patient, not yet confirmed
+    Unconfirmed,
+    Confirmed,
+    /// Ruled out, e.g. by a challenge test
+    Refuted,
+    EnteredInError,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MedicationRoute {
+    Oral,
+    Sublingual,
+    Topical,
+    Transdermal,
+    Inhaled,
+    Nasal,
+    Ophthalmic,
+    Otic,
+    Rectal,
+    Vaginal,
+    Intravenous,
+    Intramuscular,
+    Subcutaneous,
+}
+
+impl MedicationRoute {
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            MedicationRoute::Oral => "PO",
+            MedicationRoute::Sublingual => "SL",
+            MedicationRoute::Topical => "TOP",
+            MedicationRoute::Transdermal => "TD",
+            MedicationRoute::Inhaled => "INH",
+            MedicationRoute::Nasal => "IN",
+            MedicationRoute::Ophthalmic => "OPH",
+            MedicationRoute::Otic => "OT",
+            MedicationRoute::Rectal => "PR",
+            MedicationRoute::Vaginal => "PV",
+            MedicationRoute::Intravenous => "IV",
+            MedicationRoute::Intramuscular => "IM",
+            MedicationRoute::Subcutaneous => "SC",
+        }
+    }
+
+    pub fn is_parenteral(&self) -> bool {
+        matches!(
+            self,
+            MedicationRoute::Intravenous | MedicationRoute::Intramuscular | MedicationRoute::Subcutaneous
+        )
+    }
+}
+
+/// Coded dosing frequency, serialized as the prescriber abbreviation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DoseFrequency {
+    #[serde(rename = "OD")]
+    OnceDaily,
+    #[serde(rename = "BD")]
+    TwiceDaily,
+    #[serde(rename = "TDS")]
+    ThreeTimesDaily,
+    #[serde(rename = "QDS")]
+    FourTimesDaily,
+    /// Every n hours
+    #[serde(rename = "QH")]
+    EveryHours(u8),
+    #[serde(rename = "HS")]
+    AtBedtime,
+    #[serde(rename = "WEEKLY")]
+    Weekly,
+    /// As needed, no fixed schedule
+    #[serde(rename = "PRN")]
+    AsNeeded,
+    /// Single immediate dose
+    #[serde(rename = "STAT")]
+    Once,
+}
+
+impl DoseFrequency {
+    pub fn code(&self) -> String {
+        match self {
+            DoseFrequency::OnceDaily => "OD".to_string(),
+            DoseFrequency::TwiceDaily => "BD".to_string(),
+            DoseFrequency::ThreeTimesDaily => "TDS".to_string(),
+            DoseFrequency::FourTimesDaily => "QDS".to_string(),
+            DoseFrequency::EveryHours(hours) => format!("Q{}H", hours),
+            DoseFrequency::AtBedtime => "HS".to_string(),
+            DoseFrequency::Weekly => "WEEKLY".to_string(),
+            DoseFrequency::AsNeeded => "PRN".to_string(),
+            DoseFrequency::Once => "STAT".to_string(),
+        }
+    }
+
+    /// Wording for patients, e.g. on printed prescriptions
+    pub fn label(&self) -> String {
+        match self {
+            DoseFrequency::OnceDaily => "Once a day".to_string(),
+            DoseFrequency::TwiceDaily => "Twice a day".to_string(),
+            DoseFrequency::ThreeTimesDaily => "Three times a day".to_string(),
+            DoseFrequency::FourTimesDaily => "Four times a day".to_string(),
+            DoseFrequency::EveryHours(hours) => format!("Every {} hours", hours),
+            DoseFrequency::AtBedtime => "At bedtime".to_string(),
+            DoseFrequency::Weekly => "Once a week".to_string(),
+            DoseFrequency::AsNeeded => "When needed".to_string(),
+            DoseFrequency::Once => "Single dose".to_string(),
+        }
+    }
+
+    /// Scheduled doses per day; `None` for as-needed and one-off doses
+    pub fn doses_per_day(&self) -> Option<f64> {
+        match self {
+            DoseFrequency::OnceDaily | DoseFrequency::AtBedtime => Some(1.0),
+            DoseFrequency::TwiceDaily => Some(2.0),
+            DoseFrequency::ThreeTimesDaily => Some(3.0),
+            DoseFrequency::FourTimesDaily => Some(4.0),
+            DoseFrequency::EveryHours(hours) => (*hours > 0).then(|| 24.0 / f64::from(*hours)),
+            DoseFrequency::Weekly => Some(1.0 / 7.0),
+            DoseFrequency::AsNeeded | DoseFrequency::Once => None,
+        }
+    }
+}
+
+/// Accepts common spellings when parsed or deserialized ("male", "M", "prefer not to say")
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "String")]
 pub enum Gender {
     Male,
     Female,
@@ -172,6 +679,148 @@ pub enum Gender {
     PreferNotToSay,
 }
 
+impl std::str::FromStr for Gender {
+    type Err = SharedError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let normalized: String = value
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match normalized.as_str() {
+            "male" | "m" | "man" => Ok(Gender::Male),
+            "female" | "f" | "woman" => Ok(Gender::Female),
+            "other" | "o" | "nonbinary" | "x" => Ok(Gender::Other),
+            "prefernottosay" | "undisclosed" | "notspecified" => Ok(Gender::PreferNotToSay),
+            _ => Err(SharedError::ValidationError(format!("Unknown gender: {}", value))),
+        }
+    }
+}
+
+impl TryFrom<String> for Gender {
+    type Error = SharedError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl std::fmt::Display for Gender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Gender::Male => "Male",
+            Gender::Female => "Female",
+            Gender::Other => "Other",
+            Gender::PreferNotToSay => "Prefer not to say",
+        })
+    }
+}
+
+/// ABO group with Rh factor, serialized as "A+", "O-", etc. Parsing accepts
+/// the forms seen in practice: "o+", "O +ve", "AB pos", "B negative".
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub enum BloodType {
+    APositive,
+    ANegative,
+    BPositive,
+    BNegative,
+    AbPositive,
+    AbNegative,
+    OPositive,
+    ONegative,
+}
+
+impl BloodType {
+    pub const ALL: [BloodType; 8] = [
+        BloodType::APositive,
+        BloodType::ANegative,
+        BloodType::BPositive,
+        BloodType::BNegative,
+        BloodType::AbPositive,
+        BloodType::AbNegative,
+        BloodType::OPositive,
+        BloodType::ONegative,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BloodType::APositive => "A+",
+            BloodType::ANegative => "A-",
+            BloodType::BPositive => "B+",
+            BloodType::BNegative => "B-",
+            BloodType::AbPositive => "AB+",
+            BloodType::AbNegative => "AB-",
+            BloodType::OPositive => "O+",
+            BloodType::ONegative => "O-",
+        }
+    }
+
+    pub fn is_rh_negative(&self) -> bool {
+        matches!(
+            self,
+            BloodType::ANegative | BloodType::BNegative | BloodType::AbNegative | BloodType::ONegative
+        )
+    }
+}
+
+impl std::str::FromStr for BloodType {
+    type Err = SharedError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let normalized: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase()
+            .replace('−', "-")
+            .replace('0', "O");
+        let split = normalized
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .or_else(|| ["POSITIVE", "NEGATIVE", "POS", "NEG"].iter().filter_map(|rh| normalized.rfind(rh)).min())
+            .unwrap_or(normalized.len());
+        let (group, rh) = normalized.split_at(split);
+        let positive = match rh {
+            "+" | "+VE" | "POS" | "POSITIVE" => true,
+            "-" | "-VE" | "NEG" | "NEGATIVE" => false,
+            _ => return Err(SharedError::ValidationError(format!("Unknown blood type: {}", value))),
+        };
+        let blood_type = match (group, positive) {
+            ("A", true) => BloodType::APositive,
+            ("A", false) => BloodType::ANegative,
+            ("B", true) => BloodType::BPositive,
+            ("B", false) => BloodType::BNegative,
+            ("AB", true) => BloodType::AbPositive,
+            ("AB", false) => BloodType::AbNegative,
+            ("O", true) => BloodType::OPositive,
+            ("O", false) => BloodType::ONegative,
+            _ => return Err(SharedError::ValidationError(format!("Unknown blood type: {}", value))),
+        };
+        Ok(blood_type)
+    }
+}
+
+impl TryFrom<String> for BloodType {
+    type Error = SharedError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<BloodType> for String {
+    fn from(blood_type: BloodType) -> Self {
+        blood_type.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for BloodType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MedicalSpecialization {
     GeneralMedicine,
@@ -220,10 +869,261 @@ pub enum VerificationStatus {
     Suspended,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CoverageType {
     Basic,
     Premium,
     Corporate,
     Government,
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum NotificationChannel {
+    Push,
+    InApp,
+    Sms,
+    Email,
+    WhatsApp,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuietHours {
+    pub start: chrono::NaiveTime,
+    pub end: chrono::NaiveTime,
+    pub timezone: String,
+}
+
+impl QuietHours {
+    /// Whether `at` falls inside the window; windows may wrap past midnight
+    pub fn contains(&self, at: &DateTime<Utc>) -> SharedResult<bool> {
+        let tz: chrono_tz::Tz = self.timezone.parse()
+            .map_err(|e| SharedError::ValidationError(format!("Invalid timezone: {}", e)))?;
+        let local = at.with_timezone(&tz).time();
+
+        Ok(if self.start <= self.end {
+            local >= self.start && local < self.end
+        } else {
+            local >= self.start || local < self.end
+        })
+    }
+}
+
+/// Per-user notification settings shared by the patient and provider apps
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationPreferences {
+    pub user_id: Uuid,
+    pub enabled_channels: Vec<NotificationChannel>,
+    pub muted_types: Vec<NotificationType>,
+    pub quiet_hours: Option<QuietHours>,
+    /// Emergency alerts bypass mutes, quiet hours and channel toggles; not user-configurable
+    #[serde(default = "emergency_override_default")]
+    pub emergency_override: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn emergency_override_default() -> bool {
+    true
+}
+
+impl NotificationPreferences {
+    pub fn default_for(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            enabled_channels: vec![NotificationChannel::Push, NotificationChannel::InApp, NotificationChannel::Email],
+            muted_types: vec![NotificationType::PromotionalOffer],
+            quiet_hours: None,
+            emergency_override: true,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Re-apply invariants after deserializing or editing
+    pub fn normalized(mut self) -> Self {
+        self.emergency_override = true;
+        // Drop repeats anywhere in the list, keeping the user's order
+        let mut seen = std::collections::HashSet::new();
+        self.enabled_channels.retain(|channel| seen.insert(*channel));
+        self
+    }
+
+    /// Decide whether a notification should go out on `channel` at `at`
+    pub fn should_deliver(
+        &self,
+        notification_type: &NotificationType,
+        is_emergency: bool,
+        channel: NotificationChannel,
+        at: &DateTime<Utc>,
+    ) -> bool {
+        if is_emergency {
+            return true;
+        }
+        if !self.enabled_channels.contains(&channel) || self.muted_types.contains(notification_type) {
+            return false;
+        }
+        match &self.quiet_hours {
+            // Fail open on a bad timezone so reminders are not silently dropped
+            Some(quiet) => !quiet.contains(at).unwrap_or(false),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_quiet_hours_wrap_midnight() {
+        let quiet = QuietHours {
+            start: chrono::NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            end: chrono::NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+            timezone: "Asia/Kolkata".to_string(),
+        };
+        // 23:30 IST
+        assert!(quiet.contains(&Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap()).unwrap());
+        // 12:30 IST
+        assert!(!quiet.contains(&Utc.with_ymd_and_hms(2024, 1, 1, 7, 0, 0).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_availability_exceptions_override_schedule() {
+        let nine_to_noon = TimeSlot {
+            start_time: chrono::NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end_time: chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        };
+        let monday = chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let availability = ProviderAvailability {
+            provider_id: ProviderId::generate(),
+            timezone: "Asia/Kolkata".to_string(),
+            weekly_schedule: AvailabilitySchedule {
+                monday: Some(nine_to_noon),
+                tuesday: None,
+                wednesday: None,
+                thursday: None,
+                friday: None,
+                saturday: None,
+                sunday: None,
+            },
+            exceptions: vec![AvailabilityException {
+                date: monday + chrono::Duration::days(7),
+                slot: None,
+                reason: Some("Conference".to_string()),
+            }],
+            slot_duration_minutes: 30,
+        };
+
+        assert_eq!(availability.slots_on(monday).unwrap().len(), 6);
+        assert!(availability.slots_on(monday + chrono::Duration::days(1)).unwrap().is_empty());
+        assert!(availability.slots_on(monday + chrono::Duration::days(7)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_allergy_labels_and_status() {
+        let mut allergy = Allergy::new("Penicillin", AllergenCategory::Medication, AllergySeverity::Severe);
+        assert_eq!(allergy.label(), "Penicillin");
+        allergy.reactions = vec![AllergyReaction::Anaphylaxis, AllergyReaction::Other("Facial swelling".to_string())];
+        assert_eq!(allergy.label(), "Penicillin (anaphylaxis, facial swelling)");
+        assert!(allergy.is_active());
+
+        allergy.verification = AllergyVerification::Refuted;
+        assert!(!allergy.is_active());
+        assert!(AllergySeverity::LifeThreatening > AllergySeverity::Severe);
+    }
+
+    #[test]
+    fn test_blood_type_and_gender_parsing() {
+        for (input, expected) in [
+            ("O+", BloodType::OPositive),
+            ("o+", BloodType::OPositive),
+            ("O +ve", BloodType::OPositive),
+            ("0-", BloodType::ONegative),
+            ("AB pos", BloodType::AbPositive),
+            ("B negative", BloodType::BNegative),
+            ("a-ve", BloodType::ANegative),
+        ] {
+            assert_eq!(input.parse::<BloodType>().unwrap(), expected, "{}", input);
+        }
+        assert!("C+".parse::<BloodType>().is_err());
+        assert!("AB".parse::<BloodType>().is_err());
+        for blood_type in BloodType::ALL {
+            assert_eq!(blood_type.to_string().parse::<BloodType>().unwrap(), blood_type);
+        }
+        assert_eq!(serde_json::to_string(&BloodType::AbNegative).unwrap(), "\"AB-\"");
+        assert_eq!(serde_json::from_str::<BloodType>("\"O +ve\"").unwrap(), BloodType::OPositive);
+
+        assert_eq!("F".parse::<Gender>().unwrap(), Gender::Female);
+        assert_eq!(serde_json::from_str::<Gender>("\"PreferNotToSay\"").unwrap(), Gender::PreferNotToSay);
+        assert_eq!(serde_json::to_string(&Gender::Male).unwrap(), "\"Male\"");
+        assert_eq!(Gender::PreferNotToSay.to_string(), "Prefer not to say");
+        assert!("unknown".parse::<Gender>().is_err());
+    }
+
+    #[test]
+    fn test_emergency_always_delivered() {
+        let mut prefs = NotificationPreferences::default_for(Uuid::new_v4());
+        prefs.enabled_channels.clear();
+        prefs.emergency_override = false;
+        let prefs = prefs.normalized();
+
+        let now = Utc::now();
+        assert!(prefs.emergency_override);
+        assert!(prefs.should_deliver(&NotificationType::SecurityAlert, true, NotificationChannel::Sms, &now));
+        assert!(!prefs.should_deliver(&NotificationType::AppointmentReminder, false, NotificationChannel::Sms, &now));
+
+        let mut prefs = NotificationPreferences::default_for(Uuid::new_v4());
+        prefs.enabled_channels = vec![NotificationChannel::Sms, NotificationChannel::Push, NotificationChannel::Sms];
+        assert_eq!(prefs.normalized().enabled_channels, vec![NotificationChannel::Sms, NotificationChannel::Push]);
+    }
+
+    #[test]
+    fn test_insurance_eligibility_estimate_feeds_pricing() {
+        let date = |d| chrono::NaiveDate::from_ymd_opt(2026, 1, d).unwrap();
+        let policy = InsurancePolicy {
+            id: "pol-1".to_string(),
+            patient_id: PatientId::new("pat-1"),
+            payer_name: "Star Health".to_string(),
+            policy_number: "SH-123".to_string(),
+            group_number: None,
+            coverage_type: CoverageType::Premium,
+            effective_from: date(1),
+            expires_on: date(31),
+            currency: "INR".to_string(),
+            coverages: vec![CoverageDetail {
+                service_category: ServiceCategory::DoctorConsultations,
+                coverage_percentage: 80.0,
+                copay: 100.0,
+                remaining_benefit: Some(1_000.0),
+                requires_preauthorization: false,
+            }],
+        };
+
+        let booking = BookingId::new("bk-1");
+        let result = policy.estimate_eligibility(booking.clone(), &ServiceCategory::DoctorConsultations, 600.0, date(15));
+        assert_eq!(result.status, EligibilityStatus::Eligible);
+        assert_eq!(result.covered_amount, 400.0);
+        assert_eq!(result.patient_responsibility, 200.0);
+        assert_eq!(result.reference, None);
+
+        let capped = policy.estimate_eligibility(booking.clone(), &ServiceCategory::DoctorConsultations, 5_000.0, date(15));
+        assert_eq!(capped.covered_amount, 1_000.0);
+        let uncovered = policy.estimate_eligibility(booking.clone(), &ServiceCategory::NursingServices, 600.0, date(15));
+        assert_eq!((uncovered.status, uncovered.covered_amount), (EligibilityStatus::NotCovered, 0.0));
+        let expired = policy.estimate_eligibility(booking, &ServiceCategory::DoctorConsultations, 600.0, date(31).succ_opt().unwrap());
+        assert_eq!(expired.status, EligibilityStatus::PolicyInactive);
+
+        let mut engine = crate::healthcare_service_engine::PricingEngine::new();
+        let price = crate::money::Money::from_major(600, crate::money::Currency::Inr);
+        let quote = crate::healthcare_service_engine::PricingResult::new(price, Vec::new());
+        assert_eq!(engine.apply_eligibility(quote.clone(), &result).patient_payable().unwrap(), price);
+        // Quotes stored before insurance coverage existed load with none
+        let stored: crate::healthcare_service_engine::PricingResult =
+            serde_json::from_value(serde_json::json!({ "final_price": price, "applied_discounts": [] })).unwrap();
+        assert_eq!(stored.insurance_coverage, crate::money::Money::zero(crate::money::Currency::Inr));
+
+        engine.insurance_integration_config.integration_enabled = true;
+        engine.insurance_integration_config.supported_providers = vec!["star health".to_string()];
+        let payable = engine.apply_eligibility(quote, &result).patient_payable().unwrap();
+        assert_eq!(payable.to_f64(), 200.0);
+    }
+}
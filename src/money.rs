@@ -0,0 +1,251 @@
+//! Monetary amounts in integer minor units, so prices never pick up float rounding errors
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::errors::{SharedError, SharedResult};
+
+/// ISO 4217 currencies the platform bills in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    Inr,
+    Usd,
+    Eur,
+    Gbp,
+    Aed,
+    Jpy,
+}
+
+impl Currency {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Inr => "INR",
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Aed => "AED",
+            Currency::Jpy => "JPY",
+        }
+    }
+
+    /// Case-insensitive ISO code lookup
+    pub fn parse(code: &str) -> Option<Self> {
+        [
+            Currency::Inr,
+            Currency::Usd,
+            Currency::Eur,
+            Currency::Gbp,
+            Currency::Aed,
+            Currency::Jpy,
+        ]
+        .into_iter()
+        .find(|currency| currency.code().eq_ignore_ascii_case(code.trim()))
+    }
+
+    /// Digits after the decimal point, e.g. 2 for paise
+    pub fn minor_units(&self) -> u32 {
+        match self {
+            Currency::Jpy => 0,
+            _ => 2,
+        }
+    }
+
+    pub fn symbol(&self) -> Option<&'static str> {
+        match self {
+            Currency::Inr => Some("₹"),
+            Currency::Usd => Some("$"),
+            Currency::Eur => Some("€"),
+            Currency::Gbp => Some("£"),
+            Currency::Jpy => Some("¥"),
+            Currency::Aed => None,
+        }
+    }
+
+    fn minor_per_major(&self) -> i64 {
+        10_i64.pow(self.minor_units())
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// An amount of one currency. Arithmetic between different currencies is an
+/// error rather than a silent conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Money {
+    /// Amount in the currency's smallest unit, e.g. paise for INR
+    pub amount_minor: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount_minor: i64, currency: Currency) -> Self {
+        Self { amount_minor, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Whole units, e.g. `Money::from_major(500, Currency::Inr)` is ₹500.00
+    pub fn from_major(amount: i64, currency: Currency) -> Self {
+        Self::new(amount.saturating_mul(currency.minor_per_major()), currency)
+    }
+
+    /// Convert a decimal amount from an external system, rounding to the
+    /// nearest minor unit
+    pub fn from_f64(amount: f64, currency: Currency) -> SharedResult<Self> {
+        let minor = (amount * currency.minor_per_major() as f64).round();
+        if !minor.is_finite() || minor.abs() >= i64::MAX as f64 {
+            return Err(SharedError::ValidationError(format!(
+                "Invalid {} amount: {}",
+                currency, amount
+            )));
+        }
+        Ok(Self::new(minor as i64, currency))
+    }
+
+    /// Decimal amount for display maths and legacy APIs; not for further arithmetic
+    pub fn to_f64(&self) -> f64 {
+        self.amount_minor as f64 / self.currency.minor_per_major() as f64
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount_minor == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount_minor < 0
+    }
+
+    fn same_currency(&self, other: &Money) -> SharedResult<()> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(SharedError::ValidationError(format!(
+                "Currency mismatch: {} and {}",
+                self.currency, other.currency
+            )))
+        }
+    }
+
+    fn overflow(&self) -> SharedError {
+        SharedError::ValidationError(format!("{} amount out of range", self.currency))
+    }
+
+    pub fn checked_add(&self, other: &Money) -> SharedResult<Money> {
+        self.same_currency(other)?;
+        self.amount_minor
+            .checked_add(other.amount_minor)
+            .map(|amount| Money::new(amount, self.currency))
+            .ok_or_else(|| self.overflow())
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> SharedResult<Money> {
+        self.same_currency(other)?;
+        self.amount_minor
+            .checked_sub(other.amount_minor)
+            .map(|amount| Money::new(amount, self.currency))
+            .ok_or_else(|| self.overflow())
+    }
+
+    /// Like `checked_sub`, but never goes below zero
+    pub fn saturating_sub(&self, other: &Money) -> SharedResult<Money> {
+        let difference = self.checked_sub(other)?;
+        Ok(if difference.is_negative() { Money::zero(self.currency) } else { difference })
+    }
+
+    /// Total of `amounts`, all of which must be in `currency`
+    pub fn sum<'a>(currency: Currency, amounts: impl IntoIterator<Item = &'a Money>) -> SharedResult<Money> {
+        amounts
+            .into_iter()
+            .try_fold(Money::zero(currency), |total, amount| total.checked_add(amount))
+    }
+
+    /// `percent`% of the amount, rounded half away from zero to the minor unit
+    pub fn percent(&self, percent: f64) -> Money {
+        self.scale(percent / 100.0)
+    }
+
+    /// Amount multiplied by `factor`, e.g. a surge multiplier
+    pub fn scale(&self, factor: f64) -> Money {
+        // `as` saturates, so huge factors clamp instead of wrapping
+        Money::new((self.amount_minor as f64 * factor).round() as i64, self.currency)
+    }
+
+    /// Split into `percent`% and the remainder; the parts always add back up exactly
+    pub fn split(&self, percent: f64) -> (Money, Money) {
+        let share = self.percent(percent);
+        (share, Money::new(self.amount_minor.saturating_sub(share.amount_minor), self.currency))
+    }
+}
+
+impl PartialOrd for Money {
+    /// Amounts in different currencies are not comparable
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.amount_minor.cmp(&other.amount_minor))
+    }
+}
+
+impl fmt::Display for Money {
+    /// "₹1250.50", or "AED 12.00" for currencies without a symbol
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let minor = self.amount_minor.unsigned_abs();
+        let per_major = self.currency.minor_per_major().unsigned_abs();
+        let digits = self.currency.minor_units() as usize;
+        let amount = if digits == 0 {
+            minor.to_string()
+        } else {
+            format!("{}.{:0digits$}", minor / per_major, minor % per_major, digits = digits)
+        };
+        match self.currency.symbol() {
+            Some(symbol) => write!(f, "{}{}{}", sign, symbol, amount),
+            None => write!(f, "{}{} {}", sign, self.currency.code(), amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic_and_rounding() {
+        let fee = Money::from_major(500, Currency::Inr);
+        assert_eq!(fee.amount_minor, 50_000);
+        assert_eq!(Money::from_f64(0.1 + 0.2, Currency::Inr).unwrap(), Money::new(30, Currency::Inr));
+        assert!(Money::from_f64(f64::NAN, Currency::Inr).is_err());
+
+        let total = Money::sum(Currency::Inr, &[fee, Money::new(1_999, Currency::Inr)]).unwrap();
+        assert_eq!(total, Money::new(51_999, Currency::Inr));
+        assert!(fee.checked_add(&Money::from_major(5, Currency::Usd)).is_err());
+        assert_eq!(fee.partial_cmp(&Money::from_major(5, Currency::Usd)), None);
+        assert!(Money::new(i64::MAX, Currency::Inr).checked_add(&Money::new(1, Currency::Inr)).is_err());
+        assert_eq!(fee.saturating_sub(&total).unwrap(), Money::zero(Currency::Inr));
+
+        // 12.5% of ₹0.99 is 12.375 paise
+        assert_eq!(Money::new(99, Currency::Inr).percent(12.5), Money::new(12, Currency::Inr));
+        assert_eq!(Money::new(-101, Currency::Inr).percent(50.0), Money::new(-51, Currency::Inr));
+        let (provider, platform) = Money::new(1_001, Currency::Inr).split(70.0);
+        assert_eq!((provider.amount_minor, platform.amount_minor), (701, 300));
+        assert_eq!(fee.scale(1.5), Money::from_major(750, Currency::Inr));
+    }
+
+    #[test]
+    fn test_display_and_serialization() {
+        assert_eq!(Money::new(125_050, Currency::Inr).to_string(), "₹1250.50");
+        assert_eq!(Money::new(-5, Currency::Usd).to_string(), "-$0.05");
+        assert_eq!(Money::from_major(1_200, Currency::Aed).to_string(), "AED 1200.00");
+        assert_eq!(Money::new(980, Currency::Jpy).to_string(), "¥980");
+        assert_eq!(Currency::parse("inr"), Some(Currency::Inr));
+
+        let json = serde_json::to_value(Money::new(50_000, Currency::Inr)).unwrap();
+        assert_eq!(json, serde_json::json!({ "amount_minor": 50_000, "currency": "INR" }));
+    }
+}
@@ -0,0 +1,313 @@
+//! Offline mutation queue for replaying failed writes when connectivity returns
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::encryption::{decrypt_json, encrypt_json, EncryptedField, MasterKey};
+use crate::errors::{SharedError, SharedResult};
+use crate::schema::{from_versioned_json, to_versioned_json, Versioned};
+
+/// Kind of mutation, used for replay ordering and UI messaging
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MutationKind {
+    EmergencyRequest,
+    StatusUpdate,
+    ChatMessage,
+    Other,
+}
+
+impl MutationKind {
+    /// Lower values are replayed first
+    pub fn replay_priority(&self) -> u8 {
+        match self {
+            MutationKind::EmergencyRequest => 0,
+            MutationKind::StatusUpdate => 1,
+            MutationKind::ChatMessage => 2,
+            MutationKind::Other => 3,
+        }
+    }
+}
+
+/// A write request captured while offline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QueuedMutation {
+    pub id: Uuid,
+    pub idempotency_key: String,
+    pub kind: MutationKind,
+    pub method: String,
+    pub endpoint: String,
+    pub body: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+impl QueuedMutation {
+    pub fn new(kind: MutationKind, method: &str, endpoint: &str, body: serde_json::Value) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            id,
+            idempotency_key: id.to_string(),
+            kind,
+            method: method.to_string(),
+            endpoint: endpoint.to_string(),
+            body,
+            created_at: Utc::now(),
+            attempts: 0,
+            last_error: None,
+        }
+    }
+
+    pub fn with_idempotency_key(mut self, key: &str) -> Self {
+        self.idempotency_key = key.to_string();
+        self
+    }
+}
+
+impl Versioned for QueuedMutation {
+    const SCHEMA_NAME: &'static str = "queued_mutation";
+    const SCHEMA_VERSION: u32 = 1;
+}
+
+/// Result of replaying a single queued mutation
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayOutcome {
+    Succeeded,
+    /// Server rejected the mutation because state changed while offline
+    Conflict(String),
+    /// Transient failure; keep the mutation queued
+    RetryLater(String),
+}
+
+/// Summary of a sync pass
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncReport {
+    pub succeeded: usize,
+    pub conflicts: usize,
+    pub remaining: usize,
+}
+
+/// Persistence backend for the queue (e.g. browser localStorage)
+pub trait QueueStorage: Send + Sync {
+    fn load(&self) -> Option<String>;
+    fn save(&self, data: &str) -> SharedResult<()>;
+}
+
+/// Non-persistent storage for tests and server-side use
+#[derive(Debug, Clone, Default)]
+pub struct MemoryQueueStorage {
+    data: Arc<Mutex<Option<String>>>,
+}
+
+impl QueueStorage for MemoryQueueStorage {
+    fn load(&self) -> Option<String> {
+        self.data.lock().ok().and_then(|d| d.clone())
+    }
+
+    fn save(&self, data: &str) -> SharedResult<()> {
+        let mut stored = self
+            .data
+            .lock()
+            .map_err(|e| SharedError::InternalError(format!("Queue storage poisoned: {}", e)))?;
+        *stored = Some(data.to_string());
+        Ok(())
+    }
+}
+
+/// Envelope context for a sealed queue, so the ciphertext can't be passed off as another record
+const QUEUE_CONTEXT: &str = "offline_queue";
+
+/// Seals the queue under a master key before handing it to another backend.
+/// Queued bodies are often PHI (chat messages, emergency requests), so
+/// anything persisted on a device should go through this.
+#[derive(Debug, Clone)]
+pub struct EncryptedQueueStorage<S> {
+    inner: S,
+    master_key: MasterKey,
+}
+
+impl<S: QueueStorage> EncryptedQueueStorage<S> {
+    pub fn new(inner: S, master_key: MasterKey) -> Self {
+        Self { inner, master_key }
+    }
+}
+
+impl<S: QueueStorage> QueueStorage for EncryptedQueueStorage<S> {
+    fn load(&self) -> Option<String> {
+        let data = self.inner.load()?;
+        serde_json::from_str::<EncryptedField>(&data)
+            .map_err(SharedError::from)
+            .and_then(|sealed| decrypt_json(&sealed, QUEUE_CONTEXT, &self.master_key))
+            .map_err(|e| log::warn!("Discarding offline queue that can't be decrypted: {}", e))
+            .ok()
+    }
+
+    fn save(&self, data: &str) -> SharedResult<()> {
+        let sealed = encrypt_json(&data, QUEUE_CONTEXT, &self.master_key)?;
+        self.inner.save(&serde_json::to_string(&sealed)?)
+    }
+}
+
+/// Persisted FIFO of pending mutations, replayed emergency-first
+#[derive(Clone)]
+pub struct OfflineQueue {
+    storage: Arc<dyn QueueStorage>,
+    items: Arc<Mutex<Vec<QueuedMutation>>>,
+}
+
+impl std::fmt::Debug for OfflineQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OfflineQueue")
+            .field("pending", &self.len())
+            .finish()
+    }
+}
+
+impl OfflineQueue {
+    /// Create a queue, restoring any mutations previously saved to `storage`
+    pub fn new(storage: Arc<dyn QueueStorage>) -> Self {
+        let items = storage
+            .load()
+            .and_then(|data| {
+                from_versioned_json::<Vec<QueuedMutation>>(&data)
+                    .map_err(|e| log::warn!("Discarding unreadable offline queue: {}", e))
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        Self {
+            storage,
+            items: Arc::new(Mutex::new(items)),
+        }
+    }
+
+    pub fn enqueue(&self, mutation: QueuedMutation) -> SharedResult<Uuid> {
+        let id = mutation.id;
+        self.update(|items| items.push(mutation))?;
+        Ok(id)
+    }
+
+    /// Pending mutations in replay order
+    pub fn pending(&self) -> Vec<QueuedMutation> {
+        let mut items = self.items.lock().map(|i| i.clone()).unwrap_or_default();
+        items.sort_by_key(|m| (m.kind.replay_priority(), m.created_at));
+        items
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.lock().map(|i| i.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn remove(&self, id: Uuid) -> SharedResult<()> {
+        self.update(|items| items.retain(|m| m.id != id))
+    }
+
+    pub fn record_failure(&self, id: Uuid, error: &str) -> SharedResult<()> {
+        self.update(|items| {
+            if let Some(mutation) = items.iter_mut().find(|m| m.id == id) {
+                mutation.attempts += 1;
+                mutation.last_error = Some(error.to_string());
+            }
+        })
+    }
+
+    /// Drop or keep a mutation depending on how its replay went
+    pub fn apply_outcome(&self, mutation: &QueuedMutation, outcome: &ReplayOutcome) -> SharedResult<()> {
+        match outcome {
+            ReplayOutcome::Succeeded | ReplayOutcome::Conflict(_) => self.remove(mutation.id),
+            ReplayOutcome::RetryLater(error) => self.record_failure(mutation.id, error),
+        }
+    }
+
+    fn update<F: FnOnce(&mut Vec<QueuedMutation>)>(&self, f: F) -> SharedResult<()> {
+        let mut items = self
+            .items
+            .lock()
+            .map_err(|e| SharedError::InternalError(format!("Offline queue poisoned: {}", e)))?;
+        f(&mut items);
+        let data = to_versioned_json(&*items)?;
+        self.storage.save(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_persists_and_restores() {
+        let storage = Arc::new(MemoryQueueStorage::default());
+        let queue = OfflineQueue::new(storage.clone());
+        queue
+            .enqueue(QueuedMutation::new(
+                MutationKind::StatusUpdate,
+                "PUT",
+                "admin/providers/p1/status",
+                serde_json::json!({ "status": "active" }),
+            ))
+            .unwrap();
+
+        let restored = OfflineQueue::new(storage);
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored.pending()[0].endpoint, "admin/providers/p1/status");
+    }
+
+    #[test]
+    fn test_encrypted_storage() {
+        let master = MasterKey::generate("device-key-1");
+        let inner = MemoryQueueStorage::default();
+        let queue = OfflineQueue::new(Arc::new(EncryptedQueueStorage::new(inner.clone(), master.clone())));
+        queue
+            .enqueue(QueuedMutation::new(
+                MutationKind::ChatMessage,
+                "POST",
+                "chat/messages",
+                serde_json::json!({ "content": "Chest pain since morning" }),
+            ))
+            .unwrap();
+        assert!(!inner.load().unwrap().contains("Chest pain"));
+
+        let restored = OfflineQueue::new(Arc::new(EncryptedQueueStorage::new(inner.clone(), master)));
+        assert_eq!(restored.pending()[0].body["content"], "Chest pain since morning");
+        let other_key = EncryptedQueueStorage::new(inner, MasterKey::generate("device-key-1"));
+        assert!(OfflineQueue::new(Arc::new(other_key)).is_empty());
+    }
+
+    #[test]
+    fn test_emergency_replayed_first() {
+        let queue = OfflineQueue::new(Arc::new(MemoryQueueStorage::default()));
+        queue
+            .enqueue(QueuedMutation::new(MutationKind::ChatMessage, "POST", "chat/messages", serde_json::Value::Null))
+            .unwrap();
+        queue
+            .enqueue(QueuedMutation::new(MutationKind::EmergencyRequest, "POST", "emergency/request", serde_json::Value::Null))
+            .unwrap();
+
+        let pending = queue.pending();
+        assert_eq!(pending[0].kind, MutationKind::EmergencyRequest);
+        assert_eq!(pending[1].kind, MutationKind::ChatMessage);
+    }
+
+    #[test]
+    fn test_apply_outcome() {
+        let queue = OfflineQueue::new(Arc::new(MemoryQueueStorage::default()));
+        let mutation = QueuedMutation::new(MutationKind::Other, "POST", "x", serde_json::Value::Null);
+        queue.enqueue(mutation.clone()).unwrap();
+
+        queue
+            .apply_outcome(&mutation, &ReplayOutcome::RetryLater("503".to_string()))
+            .unwrap();
+        assert_eq!(queue.pending()[0].attempts, 1);
+
+        queue
+            .apply_outcome(&mutation, &ReplayOutcome::Conflict("409".to_string()))
+            .unwrap();
+        assert!(queue.is_empty());
+    }
+}
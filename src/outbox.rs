@@ -0,0 +1,388 @@
+//! Transactional outbox: events saved locally with the state change that caused them, then delivered with retries
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::api_backend::{ApiBackend, ApiRequestBuilder, IDEMPOTENCY_KEY_HEADER};
+use crate::errors::{SharedError, SharedResult};
+use crate::event_store::{DomainEvent, NewEvent};
+use crate::offline_queue::{QueueStorage, ReplayOutcome, SyncReport};
+use crate::schema::{from_versioned_json, to_versioned_json, Versioned};
+
+/// An event waiting to be delivered. `id` doubles as the idempotency key, so
+/// a delivery that succeeded but was not acknowledged is deduplicated by the
+/// receiver when it is retried.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub id: Uuid,
+    pub event: NewEvent,
+    pub created_at: DateTime<Utc>,
+    pub attempts: u32,
+    /// Not retried before this time; `None` when never attempted
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl OutboxEntry {
+    fn new(event: NewEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event,
+            created_at: Utc::now(),
+            attempts: 0,
+            next_attempt_at: None,
+            last_error: None,
+        }
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt_at.is_none_or(|at| at <= now)
+    }
+}
+
+/// Events staged inside [`Outbox::transaction`]
+#[derive(Debug, Default)]
+pub struct OutboxTransaction {
+    events: Vec<NewEvent>,
+}
+
+impl OutboxTransaction {
+    pub fn push<T: DomainEvent>(&mut self, event: &T) -> SharedResult<()> {
+        self.events.push(NewEvent::from_domain(event)?);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Future returned by [`OutboxSink::deliver`]; not `Send` so it can wrap browser fetch
+pub type OutboxFuture<'a> = Pin<Box<dyn Future<Output = ReplayOutcome> + 'a>>;
+
+/// Where outbox entries are delivered (backend, WebSocket, ...)
+pub trait OutboxSink {
+    /// `Conflict` means the receiver rejected the event for good; it is dead-lettered
+    fn deliver<'a>(&'a self, entry: &'a OutboxEntry) -> OutboxFuture<'a>;
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct OutboxState {
+    entries: Vec<OutboxEntry>,
+    dead_letters: Vec<OutboxEntry>,
+}
+
+impl Versioned for OutboxState {
+    const SCHEMA_NAME: &'static str = "outbox";
+    const SCHEMA_VERSION: u32 = 1;
+}
+
+/// Persisted queue of domain events awaiting delivery. Entries are delivered
+/// in the order they were written; a transient failure stops the pass so a
+/// later event never overtakes an earlier one.
+#[derive(Clone)]
+pub struct Outbox {
+    storage: Arc<dyn QueueStorage>,
+    state: Arc<Mutex<OutboxState>>,
+    dispatching: Arc<AtomicBool>,
+    retry_base_ms: u64,
+    retry_max_ms: u64,
+}
+
+impl std::fmt::Debug for Outbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Outbox")
+            .field("pending", &self.len())
+            .field("dead_letters", &self.dead_letters().len())
+            .finish()
+    }
+}
+
+impl Outbox {
+    /// Create an outbox, restoring entries previously saved to `storage`
+    pub fn new(storage: Arc<dyn QueueStorage>) -> Self {
+        let state = storage
+            .load()
+            .and_then(|data| {
+                from_versioned_json::<OutboxState>(&data)
+                    .map_err(|e| log::warn!("Discarding unreadable outbox: {}", e))
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        Self {
+            storage,
+            state: Arc::new(Mutex::new(state)),
+            dispatching: Arc::new(AtomicBool::new(false)),
+            retry_base_ms: 1_000,
+            retry_max_ms: 5 * 60_000,
+        }
+    }
+
+    /// Retry delay starts at `base_ms` and doubles per failed attempt up to `max_ms`.
+    /// Transient failures are retried indefinitely so offline events are never dropped.
+    pub fn with_backoff(mut self, base_ms: u64, max_ms: u64) -> Self {
+        self.retry_base_ms = base_ms;
+        self.retry_max_ms = max_ms.max(base_ms);
+        self
+    }
+
+    /// Run a state change and stage the events it produces. The events are
+    /// saved only if `change` succeeds, so state and events can't diverge.
+    pub fn transaction<R, F>(&self, change: F) -> SharedResult<R>
+    where
+        F: FnOnce(&mut OutboxTransaction) -> SharedResult<R>,
+    {
+        let mut tx = OutboxTransaction::default();
+        let result = change(&mut tx)?;
+        if !tx.is_empty() {
+            self.update(|state| state.entries.extend(tx.events.into_iter().map(OutboxEntry::new)))?;
+        }
+        Ok(result)
+    }
+
+    /// Stage a single event outside of a state change
+    pub fn stage<T: DomainEvent>(&self, event: &T) -> SharedResult<Uuid> {
+        let entry = OutboxEntry::new(NewEvent::from_domain(event)?);
+        let id = entry.id;
+        self.update(|state| state.entries.push(entry))?;
+        Ok(id)
+    }
+
+    /// Entries awaiting delivery, oldest first
+    pub fn pending(&self) -> Vec<OutboxEntry> {
+        self.state.lock().map(|s| s.entries.clone()).unwrap_or_default()
+    }
+
+    /// Entries the receiver rejected; kept so they can be inspected or requeued
+    pub fn dead_letters(&self) -> Vec<OutboxEntry> {
+        self.state.lock().map(|s| s.dead_letters.clone()).unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().map(|s| s.entries.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Move dead letters back into the queue, e.g. after a backend fix
+    pub fn requeue_dead_letters(&self) -> SharedResult<usize> {
+        let mut count = 0;
+        self.update(|state| {
+            count = state.dead_letters.len();
+            for mut entry in state.dead_letters.drain(..) {
+                entry.next_attempt_at = None;
+                state.entries.push(entry);
+            }
+            state.entries.sort_by_key(|e| e.created_at);
+        })?;
+        Ok(count)
+    }
+
+    pub async fn dispatch(&self, sink: &dyn OutboxSink) -> SharedResult<SyncReport> {
+        self.dispatch_at(sink, Utc::now()).await
+    }
+
+    /// Deliver due entries in order as of `now`. Returns an empty report if
+    /// another dispatch is already running, so no entry is sent twice at once.
+    pub async fn dispatch_at(&self, sink: &dyn OutboxSink, now: DateTime<Utc>) -> SharedResult<SyncReport> {
+        let mut report = SyncReport::default();
+        if self.dispatching.swap(true, Ordering::AcqRel) {
+            report.remaining = self.len();
+            return Ok(report);
+        }
+        let result = self.deliver_due(sink, now, &mut report).await;
+        self.dispatching.store(false, Ordering::Release);
+        result?;
+
+        report.remaining = self.len();
+        Ok(report)
+    }
+
+    async fn deliver_due(&self, sink: &dyn OutboxSink, now: DateTime<Utc>, report: &mut SyncReport) -> SharedResult<()> {
+        for entry in self.pending() {
+            if !entry.is_due(now) {
+                break;
+            }
+            match sink.deliver(&entry).await {
+                ReplayOutcome::Succeeded => {
+                    self.update(|state| state.entries.retain(|e| e.id != entry.id))?;
+                    report.succeeded += 1;
+                }
+                ReplayOutcome::Conflict(reason) => {
+                    log::warn!("Outbox event {} rejected: {}", entry.id, reason);
+                    self.update(|state| {
+                        if let Some(index) = state.entries.iter().position(|e| e.id == entry.id) {
+                            let mut rejected = state.entries.remove(index);
+                            rejected.last_error = Some(reason);
+                            state.dead_letters.push(rejected);
+                        }
+                    })?;
+                    report.conflicts += 1;
+                }
+                ReplayOutcome::RetryLater(error) => {
+                    let delay = self.backoff_ms(entry.attempts);
+                    self.update(|state| {
+                        if let Some(pending) = state.entries.iter_mut().find(|e| e.id == entry.id) {
+                            pending.attempts += 1;
+                            pending.next_attempt_at = Some(now + Duration::milliseconds(delay as i64));
+                            pending.last_error = Some(error);
+                        }
+                    })?;
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn backoff_ms(&self, attempts: u32) -> u64 {
+        self.retry_base_ms
+            .saturating_mul(1u64 << attempts.min(16))
+            .min(self.retry_max_ms)
+    }
+
+    fn update<F: FnOnce(&mut OutboxState)>(&self, f: F) -> SharedResult<()> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|e| SharedError::InternalError(format!("Outbox poisoned: {}", e)))?;
+        f(&mut state);
+        let data = to_versioned_json(&*state)?;
+        self.storage.save(&data)
+    }
+}
+
+/// Delivers entries by POSTing them to an events endpoint, keyed by entry id
+pub struct BackendOutboxSink {
+    backend: Arc<dyn ApiBackend>,
+    url: String,
+}
+
+impl BackendOutboxSink {
+    pub fn new(backend: Arc<dyn ApiBackend>, url: &str) -> Self {
+        Self { backend, url: url.to_string() }
+    }
+}
+
+impl OutboxSink for BackendOutboxSink {
+    fn deliver<'a>(&'a self, entry: &'a OutboxEntry) -> OutboxFuture<'a> {
+        Box::pin(async move {
+            let request = match ApiRequestBuilder::new("POST", &self.url)
+                .header(IDEMPOTENCY_KEY_HEADER, &entry.id.to_string())
+                .json(entry)
+            {
+                Ok(request) => request,
+                Err(e) => return ReplayOutcome::Conflict(format!("Failed to serialize outbox event: {}", e)),
+            };
+
+            match self.backend.execute(request).await {
+                Ok(response) if response.ok() => ReplayOutcome::Succeeded,
+                Ok(response) if matches!(response.status(), 400 | 409 | 412 | 422) => {
+                    ReplayOutcome::Conflict(response.error_detail())
+                }
+                Ok(response) => ReplayOutcome::RetryLater(format!("HTTP {}", response.status())),
+                Err(e) => ReplayOutcome::RetryLater(format!("Network error: {}", e)),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_backend::{HttpResponse, MockApiClient, MockReply};
+    use crate::offline_queue::MemoryQueueStorage;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct ReferralCompleted {
+        referral_id: String,
+    }
+
+    impl DomainEvent for ReferralCompleted {
+        const EVENT_TYPE: &'static str = "referral_completed";
+    }
+
+    fn referral(id: &str) -> ReferralCompleted {
+        ReferralCompleted { referral_id: id.to_string() }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(output) => output,
+            std::task::Poll::Pending => panic!("outbox sink should resolve immediately"),
+        }
+    }
+
+    #[test]
+    fn test_transaction_stages_events_only_on_success() {
+        let storage = Arc::new(MemoryQueueStorage::default());
+        let outbox = Outbox::new(storage.clone());
+
+        let points = outbox
+            .transaction(|tx| {
+                tx.push(&referral("ref-1"))?;
+                tx.push(&referral("ref-2"))?;
+                Ok(20)
+            })
+            .unwrap();
+        assert_eq!(points, 20);
+
+        let failed: SharedResult<()> = outbox.transaction(|tx| {
+            tx.push(&referral("ref-3"))?;
+            Err(SharedError::ValidationError("insufficient credits".to_string()))
+        });
+        assert!(failed.is_err());
+
+        let restored = Outbox::new(storage);
+        let pending = restored.pending();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].event.payload["referral_id"], "ref-1");
+    }
+
+    #[test]
+    fn test_dispatch_retries_in_order_and_dead_letters_rejections() {
+        let backend = Arc::new(MockApiClient::new());
+        let sink = BackendOutboxSink::new(backend.clone(), "http://localhost:8080/api/v1/events");
+        let outbox = Outbox::new(Arc::new(MemoryQueueStorage::default())).with_backoff(1_000, 60_000);
+        let first = outbox.stage(&referral("ref-1")).unwrap();
+        outbox.stage(&referral("ref-2")).unwrap();
+        let now = Utc::now();
+
+        // Offline: the first entry backs off and the second is not attempted
+        backend.push_reply("POST", "events", MockReply::NetworkError("offline".to_string()));
+        let report = block_on(outbox.dispatch_at(&sink, now)).unwrap();
+        assert_eq!((report.succeeded, report.remaining), (0, 2));
+        assert_eq!(outbox.pending()[0].attempts, 1);
+        assert_eq!(backend.requests().len(), 1);
+
+        // Not due yet
+        block_on(outbox.dispatch_at(&sink, now)).unwrap();
+        assert_eq!(backend.requests().len(), 1);
+
+        backend.push_reply("POST", "events", MockReply::Respond(HttpResponse::json(202, &serde_json::json!({}))));
+        backend.push_reply("POST", "events", MockReply::Respond(HttpResponse::json(422, &serde_json::json!({ "detail": "unknown referral" }))));
+        let report = block_on(outbox.dispatch_at(&sink, now + Duration::seconds(2))).unwrap();
+        assert_eq!((report.succeeded, report.conflicts, report.remaining), (1, 1, 0));
+
+        let requests = backend.requests();
+        assert_eq!(requests[1].header(IDEMPOTENCY_KEY_HEADER), Some(first.to_string().as_str()));
+        assert_eq!(outbox.dead_letters()[0].event.payload["referral_id"], "ref-2");
+
+        assert_eq!(outbox.requeue_dead_letters().unwrap(), 1);
+        assert_eq!(outbox.len(), 1);
+    }
+}
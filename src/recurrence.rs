@@ -0,0 +1,279 @@
+//! Recurring appointment rules, modelled on iCalendar RRULE, and their expansion into concrete slots
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{SharedError, SharedResult};
+use crate::ids::ProviderId;
+use crate::models::AppointmentSlot;
+
+/// Upper bound on occurrences a single rule may expand to, so an open-ended
+/// series can't flood the schedule
+pub const MAX_OCCURRENCES: usize = 366;
+
+/// Periods searched before giving up on a rule that never matches, e.g. a
+/// 7-day interval filtered to a different weekday
+const MAX_PERIODS: u32 = 5_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// How often a booking repeats. Times of day come from the first occurrence
+/// and are kept in local time, so a 10:00 visit stays at 10:00 across DST.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Repeat every `interval` days, weeks or months
+    #[serde(default = "default_interval")]
+    pub interval: u32,
+    /// Weekly: the days of each week to book. Daily: only these days.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub by_day: Vec<Weekday>,
+    /// Last moment an occurrence may start, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+    /// Total number of occurrences, including the first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+fn default_interval() -> u32 {
+    1
+}
+
+impl Recurrence {
+    pub fn new(frequency: Frequency) -> Self {
+        Self { frequency, interval: 1, by_day: Vec::new(), until: None, count: None }
+    }
+
+    pub fn daily() -> Self {
+        Self::new(Frequency::Daily)
+    }
+
+    pub fn weekly() -> Self {
+        Self::new(Frequency::Weekly)
+    }
+
+    pub fn monthly() -> Self {
+        Self::new(Frequency::Monthly)
+    }
+
+    pub fn every(mut self, interval: u32) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn on(mut self, days: &[Weekday]) -> Self {
+        self.by_day = days.to_vec();
+        self
+    }
+
+    pub fn until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn count(mut self, count: u32) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    pub fn validate(&self) -> SharedResult<()> {
+        if self.interval == 0 {
+            return Err(SharedError::ValidationError("Recurrence interval must be at least 1".to_string()));
+        }
+        if self.until.is_some() && self.count.is_some() {
+            return Err(SharedError::ValidationError(
+                "Recurrence may end by date or by count, not both".to_string(),
+            ));
+        }
+        if self.count == Some(0) {
+            return Err(SharedError::ValidationError("Recurrence count must be at least 1".to_string()));
+        }
+        if self.frequency == Frequency::Monthly && !self.by_day.is_empty() {
+            return Err(SharedError::ValidationError(
+                "Monthly recurrence repeats on the start date; weekdays are not supported".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// True if the series ends on its own rather than running until cancelled
+    pub fn is_bounded(&self) -> bool {
+        self.until.is_some() || self.count.is_some()
+    }
+
+    /// Local dates of period `index`, in order
+    fn period_dates(&self, start: NaiveDate, index: u32) -> Vec<NaiveDate> {
+        let step = i64::from(index) * i64::from(self.interval);
+        match self.frequency {
+            Frequency::Daily => vec![start + Duration::days(step)],
+            Frequency::Weekly => {
+                let week_start = start - Duration::days(i64::from(start.weekday().num_days_from_monday()))
+                    + Duration::weeks(step);
+                let mut days: Vec<u32> = if self.by_day.is_empty() {
+                    vec![start.weekday().num_days_from_monday()]
+                } else {
+                    self.by_day.iter().map(Weekday::num_days_from_monday).collect()
+                };
+                days.sort_unstable();
+                days.dedup();
+                days.into_iter().map(|day| week_start + Duration::days(i64::from(day))).collect()
+            }
+            Frequency::Monthly => {
+                let month0 = i64::from(start.month0()) + step;
+                let year = i64::from(start.year()) + month0.div_euclid(12);
+                // Months without the start day (e.g. the 31st) are skipped, as in RRULE
+                i32::try_from(year)
+                    .ok()
+                    .and_then(|year| NaiveDate::from_ymd_opt(year, month0.rem_euclid(12) as u32 + 1, start.day()))
+                    .into_iter()
+                    .collect()
+            }
+        }
+    }
+
+    /// Start times of every occurrence, beginning with `start` if it matches
+    /// the rule. Open-ended rules yield until `MAX_OCCURRENCES`.
+    pub fn occurrences(&self, start: DateTime<Utc>, tz: &Tz) -> SharedResult<Vec<DateTime<Utc>>> {
+        self.validate()?;
+        let local_start = start.with_timezone(tz).naive_local();
+        let start_date = local_start.date();
+        let time = local_start.time();
+        let limit = self
+            .count
+            .map_or(MAX_OCCURRENCES, |count| (count as usize).min(MAX_OCCURRENCES));
+
+        let mut occurrences = Vec::new();
+        for index in 0..MAX_PERIODS {
+            for date in self.period_dates(start_date, index) {
+                if date < start_date || (self.frequency == Frequency::Daily && !self.matches_day(date)) {
+                    continue;
+                }
+                let Some(at) = resolve_local(tz, date.and_time(time)) else { continue };
+                if self.until.is_some_and(|until| at > until) {
+                    return Ok(occurrences);
+                }
+                occurrences.push(at);
+                if occurrences.len() >= limit {
+                    return Ok(occurrences);
+                }
+            }
+        }
+        Ok(occurrences)
+    }
+
+    /// Occurrences starting inside `[from, to)`, e.g. for a calendar page
+    pub fn occurrences_between(
+        &self,
+        start: DateTime<Utc>,
+        tz: &Tz,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> SharedResult<Vec<DateTime<Utc>>> {
+        Ok(self
+            .occurrences(start, tz)?
+            .into_iter()
+            .skip_while(|at| *at < from)
+            .take_while(|at| *at < to)
+            .collect())
+    }
+
+    /// Bookable slots for the whole series with one provider
+    pub fn slots(
+        &self,
+        provider_id: &ProviderId,
+        start: DateTime<Utc>,
+        duration_minutes: u32,
+        tz: &Tz,
+    ) -> SharedResult<Vec<AppointmentSlot>> {
+        let duration = Duration::minutes(i64::from(duration_minutes));
+        Ok(self
+            .occurrences(start, tz)?
+            .into_iter()
+            .map(|start_time| AppointmentSlot {
+                provider_id: provider_id.clone(),
+                start_time,
+                end_time: start_time + duration,
+            })
+            .collect())
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        self.by_day.is_empty() || self.by_day.contains(&date.weekday())
+    }
+}
+
+/// Map a local time to UTC. Ambiguous times take the earlier instant; times in
+/// a DST gap move forward by an hour, as RFC 5545 specifies.
+fn resolve_local(tz: &Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(local + Duration::hours(1))).earliest())
+        .map(|at| at.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::datetime::DEFAULT_TIMEZONE;
+
+    fn ist(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        DEFAULT_TIMEZONE.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_weekly_physiotherapy_series() {
+        // Mon/Wed/Fri at 10:00 IST for ten sessions, starting on a Wednesday
+        let rule = Recurrence::weekly().on(&[Weekday::Fri, Weekday::Mon, Weekday::Wed]).count(10);
+        let sessions = rule.occurrences(ist(2024, 1, 3, 10), &DEFAULT_TIMEZONE).unwrap();
+        assert_eq!(sessions.len(), 10);
+        assert_eq!(sessions[0], ist(2024, 1, 3, 10));
+        assert_eq!(sessions[1], ist(2024, 1, 5, 10));
+        assert_eq!(sessions[2], ist(2024, 1, 8, 10));
+        assert_eq!(sessions[9], ist(2024, 1, 24, 10));
+
+        let fortnightly = Recurrence::weekly().every(2).until(ist(2024, 2, 1, 0));
+        let visits = fortnightly.occurrences(ist(2024, 1, 3, 10), &DEFAULT_TIMEZONE).unwrap();
+        assert_eq!(visits, vec![ist(2024, 1, 3, 10), ist(2024, 1, 17, 10), ist(2024, 1, 31, 10)]);
+
+        let slots = rule.slots(&ProviderId::new("physio-1"), ist(2024, 1, 3, 10), 45, &DEFAULT_TIMEZONE).unwrap();
+        assert_eq!(slots[0].end_time - slots[0].start_time, Duration::minutes(45));
+    }
+
+    #[test]
+    fn test_daily_monthly_and_dst() {
+        // Weekday-only nursing visits skip the weekend
+        let nursing = Recurrence::daily().on(&[Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]);
+        let visits = nursing
+            .occurrences_between(ist(2024, 1, 5, 9), &DEFAULT_TIMEZONE, ist(2024, 1, 5, 0), ist(2024, 1, 9, 0))
+            .unwrap();
+        assert_eq!(visits, vec![ist(2024, 1, 5, 9), ist(2024, 1, 8, 9)]);
+        assert_eq!(nursing.occurrences(ist(2024, 1, 5, 9), &DEFAULT_TIMEZONE).unwrap().len(), MAX_OCCURRENCES);
+
+        // The 31st is skipped in shorter months
+        let monthly = Recurrence::monthly().count(3);
+        let reviews = monthly.occurrences(ist(2024, 1, 31, 11), &DEFAULT_TIMEZONE).unwrap();
+        assert_eq!(reviews, vec![ist(2024, 1, 31, 11), ist(2024, 3, 31, 11), ist(2024, 5, 31, 11)]);
+
+        // Local time is kept across a DST change
+        let london: Tz = "Europe/London".parse().unwrap();
+        let start = london.with_ymd_and_hms(2024, 3, 29, 10, 0, 0).unwrap().with_timezone(&Utc);
+        let days = Recurrence::daily().count(3).occurrences(start, &london).unwrap();
+        assert_eq!(days[2].with_timezone(&london).time(), chrono::NaiveTime::from_hms_opt(10, 0, 0).unwrap());
+        assert_eq!(days[2] - days[0], Duration::hours(47));
+
+        assert!(Recurrence::daily().every(0).validate().is_err());
+        assert!(Recurrence::daily().count(2).until(start).validate().is_err());
+        assert!(Recurrence::monthly().on(&[Weekday::Mon]).validate().is_err());
+        // A filter that can never match gives up instead of looping
+        let never = Recurrence::daily().every(7).on(&[Weekday::Sun]);
+        assert!(never.occurrences(ist(2024, 1, 3, 10), &DEFAULT_TIMEZONE).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+//! Schema versions for locally persisted models, with step-by-step migrations so cached data survives upgrades
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::{SharedError, SharedResult};
+
+/// Version assumed for data saved before it was wrapped in a [`VersionedData`] envelope
+pub const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+/// A model that is persisted locally (localStorage, IndexedDB, files) and may
+/// be read back by a newer build. Bump `SCHEMA_VERSION` whenever the stored
+/// shape changes and teach `migrate` how to step from the previous version.
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Name used in error messages, e.g. "consent_record"
+    const SCHEMA_NAME: &'static str;
+    const SCHEMA_VERSION: u32;
+
+    /// Upgrade raw data from `from_version` to `from_version + 1`
+    fn migrate(from_version: u32, data: Value) -> SharedResult<Value> {
+        let _ = data;
+        Err(SharedError::SerializationError(format!(
+            "No migration for {} v{}",
+            Self::SCHEMA_NAME,
+            from_version
+        )))
+    }
+}
+
+/// A collection is stored at its elements' version and migrated element by element
+impl<T: Versioned> Versioned for Vec<T> {
+    const SCHEMA_NAME: &'static str = T::SCHEMA_NAME;
+    const SCHEMA_VERSION: u32 = T::SCHEMA_VERSION;
+
+    fn migrate(from_version: u32, data: Value) -> SharedResult<Value> {
+        match data {
+            Value::Array(items) => items
+                .into_iter()
+                .map(|item| T::migrate(from_version, item))
+                .collect::<SharedResult<Vec<_>>>()
+                .map(Value::Array),
+            _ => Err(SharedError::SerializationError(format!(
+                "Expected a list of {}",
+                Self::SCHEMA_NAME
+            ))),
+        }
+    }
+}
+
+/// Stored form of a [`Versioned`] value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionedData {
+    pub schema_version: u32,
+    pub data: Value,
+}
+
+impl VersionedData {
+    pub fn new<T: Versioned>(value: &T) -> SharedResult<Self> {
+        Ok(Self {
+            schema_version: T::SCHEMA_VERSION,
+            data: serde_json::to_value(value)?,
+        })
+    }
+
+    /// Read stored JSON, treating anything without an envelope as
+    /// `UNVERSIONED_SCHEMA_VERSION` data
+    pub fn parse(json: &str) -> SharedResult<Self> {
+        let value: Value = serde_json::from_str(json)?;
+        let enveloped = value.as_object().is_some_and(|object| {
+            object.len() == 2 && object.get("schema_version").is_some_and(Value::is_u64) && object.contains_key("data")
+        });
+        if enveloped {
+            Ok(serde_json::from_value(value)?)
+        } else {
+            Ok(Self {
+                schema_version: UNVERSIONED_SCHEMA_VERSION,
+                data: value,
+            })
+        }
+    }
+
+    /// Migrate to `T`'s current version and deserialize. Data written by a
+    /// newer build is refused rather than misread.
+    pub fn upgrade<T: Versioned>(self) -> SharedResult<T> {
+        if self.schema_version > T::SCHEMA_VERSION {
+            return Err(SharedError::SerializationError(format!(
+                "{} v{} is newer than supported v{}",
+                T::SCHEMA_NAME,
+                self.schema_version,
+                T::SCHEMA_VERSION
+            )));
+        }
+        let mut data = self.data;
+        for version in self.schema_version..T::SCHEMA_VERSION {
+            data = T::migrate(version, data)?;
+        }
+        Ok(serde_json::from_value(data)?)
+    }
+}
+
+/// Serialize `value` with its schema version for local storage
+pub fn to_versioned_json<T: Versioned>(value: &T) -> SharedResult<String> {
+    Ok(serde_json::to_string(&VersionedData::new(value)?)?)
+}
+
+/// Read a value saved by `to_versioned_json`, or by a build that predates versioning
+pub fn from_versioned_json<T: Versioned>(json: &str) -> SharedResult<T> {
+    VersionedData::parse(json)?.upgrade()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::gdpr::{ConsentRecord, DataProcessingPurpose, LegalBasis};
+    use crate::offline_queue::{MutationKind, QueuedMutation};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reminder {
+        title: String,
+        minutes_before: u32,
+    }
+
+    impl Versioned for Reminder {
+        const SCHEMA_NAME: &'static str = "reminder";
+        const SCHEMA_VERSION: u32 = 3;
+
+        fn migrate(from_version: u32, mut data: Value) -> SharedResult<Value> {
+            match from_version {
+                // v1 called the title "text"
+                1 => {
+                    let text = data["text"].take();
+                    data["title"] = text;
+                }
+                // v2 stored hours
+                2 => {
+                    let hours = data["hours_before"].take().as_u64().unwrap_or(0);
+                    data["minutes_before"] = (hours * 60).into();
+                }
+                _ => unreachable!(),
+            }
+            Ok(data)
+        }
+    }
+
+    #[test]
+    fn test_migrates_through_every_version() {
+        let v1 = r#"{"text":"Take insulin","hours_before":2}"#;
+        let reminder: Reminder = from_versioned_json(v1).unwrap();
+        assert_eq!(reminder, Reminder { title: "Take insulin".to_string(), minutes_before: 120 });
+
+        let json = to_versioned_json(&vec![reminder]).unwrap();
+        assert!(json.starts_with(r#"{"schema_version":3,"#));
+        assert_eq!(from_versioned_json::<Vec<Reminder>>(&json).unwrap()[0].minutes_before, 120);
+
+        let newer = r#"{"schema_version":4,"data":{}}"#;
+        assert!(from_versioned_json::<Reminder>(newer).is_err());
+    }
+
+    #[test]
+    fn test_persisted_models_read_legacy_data() {
+        // Consents captured before signatures existed
+        let consent = ConsentRecord::new(
+            uuid::Uuid::new_v4(),
+            DataProcessingPurpose::Healthcare,
+            LegalBasis::Consent,
+            "I consent".to_string(),
+            "v1.0".to_string(),
+        );
+        let mut legacy = serde_json::to_value(&consent).unwrap();
+        legacy.as_object_mut().unwrap().remove("signature");
+        let restored: ConsentRecord = from_versioned_json(&legacy.to_string()).unwrap();
+        assert_eq!(restored.consent_id, consent.consent_id);
+        assert!(!restored.is_signed());
+
+        // Offline queues saved as a bare array
+        let queued = vec![QueuedMutation::new(MutationKind::ChatMessage, "POST", "chat", serde_json::json!({}))];
+        let legacy = serde_json::to_string(&queued).unwrap();
+        assert_eq!(from_versioned_json::<Vec<QueuedMutation>>(&legacy).unwrap(), queued);
+    }
+}
@@ -0,0 +1,125 @@
+//! Holders for keys, tokens and decrypted PHI that wipe their memory on drop and never print their contents
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::errors::{SharedError, SharedResult};
+
+/// A token, password or other text secret. The buffer is zeroed when the
+/// value is dropped; copies made with `expose().to_string()` are not, so keep
+/// those short-lived.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(Zeroizing::new(value.into()))
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
+
+/// Serialized as a plain string, for request and response bodies
+impl Serialize for SecretString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.expose())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// Raw key material or a decrypted PHI buffer, zeroed on drop
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretBytes(Zeroizing<Vec<u8>>);
+
+impl SecretBytes {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(Zeroizing::new(bytes))
+    }
+
+    pub fn expose(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Reuse the buffer as text without copying it; on failure the bytes are wiped
+    pub fn into_utf8(mut self) -> SharedResult<SecretString> {
+        let bytes = std::mem::take(&mut *self.0);
+        String::from_utf8(bytes).map(SecretString::new).map_err(|e| {
+            e.into_bytes().zeroize();
+            SharedError::ValidationError("Secret is not valid UTF-8".to_string())
+        })
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes([REDACTED; {}])", self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted_debug_and_serde() {
+        let token = SecretString::new("eyJhbGciOi.payload.sig");
+        assert_eq!(format!("{:?}", token), "SecretString([REDACTED])");
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(json, r#""eyJhbGciOi.payload.sig""#);
+        let back: SecretString = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, token);
+
+        let buffer = SecretBytes::new(b"BP 120/80".to_vec());
+        assert_eq!(format!("{:?}", buffer), "SecretBytes([REDACTED; 9])");
+        assert_eq!(buffer.into_utf8().unwrap().expose(), "BP 120/80");
+        assert!(SecretBytes::new(vec![0xff, 0xfe]).into_utf8().is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use leptos::prelude::*;
+use crate::ui::use_i18n;
+use crate::utils::a11y::is_activation_key;
+
+/// Focus outline for custom interactive elements, matching `Button`
+pub const FOCUS_RING: &str = "focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2";
+
+/// Whether a keydown on a `role="button"` element should activate it.
+/// Prevents the page from scrolling when Space is the activating key.
+pub fn activate_on_key(ev: &leptos::ev::KeyboardEvent) -> bool {
+    if is_activation_key(&ev.key()) {
+        ev.prevent_default();
+        true
+    } else {
+        false
+    }
+}
+
+/// Content announced to screen readers but not shown
+#[component]
+pub fn VisuallyHidden(children: Children) -> impl IntoView {
+    view! { <span class="sr-only">{children()}</span> }
+}
+
+/// Off-screen region that announces `message` whenever it changes. Use
+/// `assertive` only for emergencies; it interrupts whatever is being read.
+#[component]
+pub fn LiveRegion(
+    #[prop(into)] message: Signal<String>,
+    #[prop(optional)] assertive: Option<bool>,
+) -> impl IntoView {
+    let assertive = assertive.unwrap_or(false);
+    view! {
+        <div
+            class="sr-only"
+            role=if assertive { "alert" } else { "status" }
+            aria-live=if assertive { "assertive" } else { "polite" }
+            aria-atomic="true"
+        >
+            {move || message.get()}
+        </div>
+    }
+}
+
+/// First focusable element on the page; jumps keyboard users past the navigation
+#[component]
+pub fn SkipLink(
+    /// Id of the main content element, which should have `tabindex="-1"`
+    #[prop(into)] target_id: String,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    view! {
+        <a
+            href=format!("#{}", target_id)
+            class="sr-only focus:not-sr-only focus:fixed focus:left-4 focus:top-4 focus:z-[100] focus:rounded-md focus:bg-background focus:px-4 focus:py-2 focus:text-sm focus:font-medium focus:shadow-lg focus:ring-2 focus:ring-ring"
+        >
+            {move || i18n.t("a11y.skip_to_content")}
+        </a>
+    }
+}
@@ -1,5 +1,5 @@
 use leptos::prelude::*;
-use crate::ui::cn;
+use crate::ui::{cn, use_i18n, Button, ButtonSize, ButtonVariant};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AlertVariant {
@@ -8,6 +8,8 @@ pub enum AlertVariant {
     Warning,
     Success,
     Info,
+    /// Solid red for emergencies; banners with it cannot be dismissed
+    Emergency,
 }
 
 #[component]
@@ -26,6 +28,7 @@ pub fn Alert(
         AlertVariant::Warning => "border-yellow-500/50 bg-yellow-50 text-yellow-900 dark:border-yellow-500 [&>svg]:text-yellow-600",
         AlertVariant::Success => "border-green-500/50 bg-green-50 text-green-900 dark:border-green-500 [&>svg]:text-green-600",
         AlertVariant::Info => "border-blue-500/50 bg-blue-50 text-blue-900 dark:border-blue-500 [&>svg]:text-blue-600",
+        AlertVariant::Emergency => "border-red-700 bg-red-600 text-white [&>svg]:text-white",
     };
     
     let alert_classes = cn(&[
@@ -34,8 +37,16 @@ pub fn Alert(
         class.unwrap_or(""),
     ]);
     
+    // Only urgent alerts interrupt screen readers; the rest wait their turn
+    let urgent = matches!(variant, AlertVariant::Destructive | AlertVariant::Emergency);
+
     view! {
-        <div class=alert_classes role="alert">
+        <div
+            class=alert_classes
+            role=if urgent { "alert" } else { "status" }
+            aria-live=if urgent { "assertive" } else { "polite" }
+            aria-atomic="true"
+        >
             {children()}
         </div>
     }
@@ -82,7 +93,8 @@ pub fn EmergencyAlert(
     #[prop(optional)] _class: Option<&'static str>,
     children: Children,
 ) -> impl IntoView {
-    let title_text = title.unwrap_or_else(|| "Emergency Alert".to_string());
+    let i18n = use_i18n();
+    let title_text = move || title.clone().unwrap_or_else(|| i18n.t("alert.emergency"));
     
     view! {
         <Alert variant=AlertVariant::Destructive class="border-red-600 bg-red-50 animate-pulse">
@@ -105,7 +117,8 @@ pub fn MedicalWarningAlert(
     #[prop(optional)] class: Option<&'static str>,
     children: Children,
 ) -> impl IntoView {
-    let title_text = title.unwrap_or_else(|| "Medical Warning".to_string());
+    let i18n = use_i18n();
+    let title_text = move || title.clone().unwrap_or_else(|| i18n.t("alert.medical_warning"));
     
     view! {
         <Alert variant=AlertVariant::Warning class=class.unwrap_or("")>
@@ -128,7 +141,8 @@ pub fn SuccessAlert(
     #[prop(optional)] class: Option<&'static str>,
     children: Children,
 ) -> impl IntoView {
-    let title_text = title.unwrap_or_else(|| "Success".to_string());
+    let i18n = use_i18n();
+    let title_text = move || title.clone().unwrap_or_else(|| i18n.t("alert.success"));
     
     view! {
         <Alert variant=AlertVariant::Success class=class.unwrap_or("")>
@@ -151,7 +165,8 @@ pub fn InfoAlert(
     #[prop(optional)] class: Option<&'static str>,
     children: Children,
 ) -> impl IntoView {
-    let title_text = title.unwrap_or_else(|| "Information".to_string());
+    let i18n = use_i18n();
+    let title_text = move || title.clone().unwrap_or_else(|| i18n.t("alert.information"));
     
     view! {
         <Alert variant=AlertVariant::Info class=class.unwrap_or("")>
@@ -174,12 +189,13 @@ pub fn AppointmentReminderAlert(
     appointment_time: String,
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
+    let i18n = use_i18n();
     view! {
         <Alert variant=AlertVariant::Info class=class.unwrap_or("")>
             <div class="flex items-center">
                 <span class="text-blue-600 mr-2 text-lg">"📅"</span>
                 <AlertTitle>
-                    "Appointment Reminder"
+                    {move || i18n.t("alert.appointment_reminder")}
                 </AlertTitle>
             </div>
             <AlertDescription class="mt-2">
@@ -196,18 +212,19 @@ pub fn MedicationReminderAlert(
     #[prop(optional)] next_dose_time: Option<String>,
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
+    let i18n = use_i18n();
     view! {
         <Alert variant=AlertVariant::Warning class=class.unwrap_or("")>
             <div class="flex items-center">
                 <span class="text-orange-600 mr-2 text-lg">"💊"</span>
                 <AlertTitle>
-                    "Medication Reminder"
+                    {move || i18n.t("alert.medication_reminder")}
                 </AlertTitle>
             </div>
             <AlertDescription class="mt-2">
                 <p>"Time to take your " <strong>{medication_name}</strong> " (" {dosage} ")"</p>
                 {move || next_dose_time.as_ref().map(|time| view! {
-                    <p class="text-sm text-muted-foreground mt-1">{format!("Next dose: {}", time)}</p>
+                    <p class="text-sm text-muted-foreground mt-1">{i18n.t_with("alert.next_dose", &[("time", time.as_str())])}</p>
                 })}
             </AlertDescription>
         </Alert>
@@ -220,19 +237,20 @@ pub fn SystemMaintenanceAlert(
     #[prop(optional)] duration: Option<String>,
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
+    let i18n = use_i18n();
     view! {
         <Alert variant=AlertVariant::Warning class=class.unwrap_or("")>
             <div class="flex items-center">
                 <span class="text-orange-600 mr-2 text-lg">"🔧"</span>
                 <AlertTitle>
-                    "Scheduled System Maintenance"
+                    {move || i18n.t("alert.maintenance")}
                 </AlertTitle>
             </div>
             <AlertDescription class="mt-2">
                 <p>"The system will be under maintenance on " <strong>{maintenance_time}</strong></p>
                 {move || {
                     duration.as_ref().map(|d| view! {
-                        <p class="text-sm text-muted-foreground mt-1">"Expected duration: " {d.clone()}</p>
+                        <p class="text-sm text-muted-foreground mt-1">{i18n.t_with("alert.expected_duration", &[("duration", d.as_str())])}</p>
                     })
                 }}
             </AlertDescription>
@@ -244,17 +262,167 @@ pub fn SystemMaintenanceAlert(
 pub fn PrivacyComplianceAlert(
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
+    let i18n = use_i18n();
     view! {
         <Alert variant=AlertVariant::Info class=class.unwrap_or("")>
             <div class="flex items-center">
                 <span class="text-blue-600 mr-2 text-lg">"🔒"</span>
                 <AlertTitle>
-                    "Privacy & Security Notice"
+                    {move || i18n.t("alert.privacy")}
                 </AlertTitle>
             </div>
             <AlertDescription class="mt-2">
-                <p>"This platform complies with HIPAA, GDPR, and healthcare data protection standards. Your medical information is encrypted and secure."</p>
+                <p>{move || i18n.t("alert.privacy_body")}</p>
             </AlertDescription>
         </Alert>
     }
 }
+
+const DISMISSAL_STORAGE_PREFIX: &str = "alert-banner-dismissed:";
+
+/// Button shown in an [`AlertBanner`], e.g. "View status page"
+#[derive(Clone)]
+pub struct AlertBannerAction {
+    pub label: String,
+    pub variant: ButtonVariant,
+    /// Hide the banner once the action has run
+    pub dismisses: bool,
+    pub on_action: Callback<()>,
+}
+
+impl AlertBannerAction {
+    pub fn new(label: impl Into<String>, on_action: Callback<()>) -> Self {
+        Self {
+            label: label.into(),
+            variant: ButtonVariant::Outline,
+            dismisses: false,
+            on_action,
+        }
+    }
+
+    pub fn primary(mut self) -> Self {
+        self.variant = ButtonVariant::Default;
+        self
+    }
+
+    pub fn dismissing(mut self) -> Self {
+        self.dismisses = true;
+        self
+    }
+}
+
+/// Whether a stored dismissal (expiry in ms since the epoch) still hides the banner
+pub fn dismissal_active(stored: Option<&str>, now_ms: f64) -> bool {
+    stored
+        .and_then(|until| until.parse::<f64>().ok())
+        .is_some_and(|until| until > now_ms)
+}
+
+fn load_dismissal(id: &str) -> bool {
+    let stored = window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(&format!("{}{}", DISMISSAL_STORAGE_PREFIX, id)).ok().flatten());
+    dismissal_active(stored.as_deref(), js_sys::Date::now())
+}
+
+fn save_dismissal(id: &str, hours: u32) {
+    if let Ok(Some(storage)) = window().local_storage() {
+        let until = js_sys::Date::now() + hours as f64 * 3_600_000.0;
+        let _ = storage.set_item(&format!("{}{}", DISMISSAL_STORAGE_PREFIX, id), &until.to_string());
+    }
+}
+
+/// Full-width announcement such as scheduled downtime. Dismissal can be
+/// remembered for `dismiss_hours`; the emergency variant cannot be dismissed.
+#[component]
+pub fn AlertBanner(
+    /// Identifies the announcement for remembered dismissals; use a new id for a new announcement
+    #[prop(into)] id: String,
+    #[prop(into)] title: String,
+    #[prop(optional)] variant: Option<AlertVariant>,
+    /// Keep the banner hidden for this many hours after dismissal; otherwise it returns on reload
+    #[prop(optional)] dismiss_hours: Option<u32>,
+    #[prop(optional)] actions: Vec<AlertBannerAction>,
+    #[prop(optional)] on_dismiss: Option<Callback<()>>,
+    #[prop(optional)] class: Option<&'static str>,
+    children: Children,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let variant = variant.unwrap_or(AlertVariant::Info);
+    let dismissible = variant != AlertVariant::Emergency;
+    let hidden = RwSignal::new(dismissible && dismiss_hours.is_some() && load_dismissal(&id));
+
+    let dismiss = move || {
+        hidden.set(true);
+        if let Some(hours) = dismiss_hours {
+            save_dismissal(&id, hours);
+        }
+        if let Some(callback) = on_dismiss {
+            callback.run(());
+        }
+    };
+
+    let (variant_classes, icon) = match variant {
+        AlertVariant::Default => ("bg-background text-foreground", "📢"),
+        AlertVariant::Info => ("border-blue-500/50 bg-blue-50 text-blue-900", "ℹ️"),
+        AlertVariant::Warning => ("border-yellow-500/50 bg-yellow-50 text-yellow-900", "⚠️"),
+        AlertVariant::Success => ("border-green-500/50 bg-green-50 text-green-900", "✅"),
+        AlertVariant::Destructive => ("border-destructive/50 bg-red-50 text-destructive", "⛔"),
+        AlertVariant::Emergency => ("border-red-700 bg-red-600 text-white", "🚨"),
+    };
+    let banner_classes = cn(&["flex w-full flex-wrap items-start gap-3 border-b px-4 py-3 text-sm", variant_classes, class.unwrap_or("")]);
+
+    let actions = actions
+        .into_iter()
+        .map(|action| {
+            let dismiss = dismiss.clone();
+            let on_action = action.on_action;
+            let dismisses = action.dismisses && dismissible;
+            view! {
+                <Button
+                    variant=action.variant
+                    size=ButtonSize::Sm
+                    on_click=Callback::new(move |_| {
+                        on_action.run(());
+                        if dismisses {
+                            dismiss();
+                        }
+                    })
+                >
+                    {action.label.clone()}
+                </Button>
+            }
+        })
+        .collect_view();
+
+    let dismiss_button = dismissible.then(|| view! {
+        <button
+            type="button"
+            class="rounded-md p-1 opacity-70 transition-opacity hover:opacity-100 focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring"
+            aria-label=move || i18n.t("common.dismiss")
+            on:click=move |_| dismiss()
+        >
+            "✕"
+        </button>
+    });
+
+    view! {
+        <div
+            class=move || cn(&[banner_classes.as_str(), if hidden.get() { "hidden" } else { "" }])
+            role=if dismissible { "status" } else { "alert" }
+            aria-live=if dismissible { "polite" } else { "assertive" }
+        >
+            <span class="text-lg leading-none" aria-hidden="true">{icon}</span>
+            <div class="min-w-0 flex-1 space-y-1">
+                <p class="font-semibold">{title}</p>
+                <div>{children()}</div>
+            </div>
+            <div class="flex items-center gap-2">
+                {actions}
+                {dismiss_button}
+            </div>
+        </div>
+    }
+}
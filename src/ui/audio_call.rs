@@ -0,0 +1,182 @@
+use leptos::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+use crate::ui::{cn, CallState, ConfirmDialog, ConnectionQuality, ConnectionQualityIndicator, MediaEngine, MediaEvent};
+use crate::utils::datetime::format_elapsed;
+
+fn toggle_classes(active: bool) -> String {
+    cn(&[
+        "flex h-16 w-16 flex-col items-center justify-center gap-0.5 rounded-full text-xs font-medium transition-colors focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-white",
+        if active { "bg-white text-gray-900 hover:bg-white/90" } else { "bg-white/15 text-white hover:bg-white/25" },
+    ])
+}
+
+/// Controls for `audio_only_enabled` consultations: call timer, mute, speaker,
+/// connection quality and an optional switch to video.
+///
+/// Uses the same [`MediaEngine`] as [`VideoConsultation`](crate::ui::VideoConsultation),
+/// so escalating can hand the engine straight to the video UI.
+#[component]
+pub fn AudioCallPanel(
+    engine: Rc<dyn MediaEngine>,
+    #[prop(into)] remote_name: String,
+    /// Called after the engine has ended the call
+    on_end: Callback<()>,
+    /// Shows a video button; video is enabled on the engine before this runs
+    #[prop(optional)] on_escalate: Option<Callback<()>>,
+    /// Subtitle under the name, e.g. the provider's specialty
+    #[prop(optional, into)] subtitle: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let engine = StoredValue::new_local(engine);
+
+    let state = RwSignal::new(CallState::Connecting);
+    let quality = RwSignal::new(ConnectionQuality::Good);
+    let remote_audio = RwSignal::new(true);
+    let muted = RwSignal::new(false);
+    let speaker = RwSignal::new(false);
+    let confirm_end = RwSignal::new(false);
+    let connected_at = RwSignal::new(None::<f64>);
+    let now = RwSignal::new(js_sys::Date::now());
+
+    let audio_ref = NodeRef::<leptos::html::Audio>::new();
+
+    engine.with_value(|engine| {
+        engine.set_video_enabled(false);
+        engine.subscribe(Box::new(move |event| match event {
+            MediaEvent::StateChanged(value) => state.set(value),
+            MediaEvent::QualityChanged(value) => quality.set(value),
+            MediaEvent::RemoteAudioChanged(value) => remote_audio.set(value),
+            MediaEvent::RemoteVideoChanged(_) => {}
+        }));
+    });
+
+    Effect::new(move |_| {
+        if let Some(audio) = audio_ref.get() {
+            engine.with_value(|engine| engine.attach_remote_audio(&audio));
+        }
+    });
+
+    // The timer starts on first connect and keeps running through reconnects
+    Effect::new(move |_| {
+        if state.get() == CallState::Connected && connected_at.get_untracked().is_none() {
+            connected_at.set(Some(js_sys::Date::now()));
+        }
+    });
+    if let Ok(handle) = set_interval_with_handle(
+        move || {
+            if state.get_untracked() != CallState::Ended {
+                now.set(js_sys::Date::now());
+            }
+        },
+        Duration::from_secs(1),
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let elapsed = move || {
+        connected_at
+            .get()
+            .map(|started| format_elapsed(((now.get() - started).max(0.0) / 1000.0) as u64))
+    };
+
+    let toggle_mute = move |_| {
+        let muted_now = !muted.get_untracked();
+        engine.with_value(|engine| engine.set_audio_enabled(!muted_now));
+        muted.set(muted_now);
+    };
+    let toggle_speaker = move |_| {
+        let enabled = !speaker.get_untracked();
+        engine.with_value(|engine| engine.set_speaker_enabled(enabled));
+        speaker.set(enabled);
+    };
+    let end_call = Callback::new(move |_| {
+        confirm_end.set(false);
+        engine.with_value(|engine| engine.end_call());
+        state.set(CallState::Ended);
+        on_end.run(());
+    });
+
+    let initials: String = remote_name
+        .split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .collect::<String>()
+        .to_uppercase();
+    let muted_notice = format!("{} is muted", remote_name);
+    let container_classes = cn(&["flex w-full flex-col items-center gap-6 rounded-lg bg-gray-950 p-8 text-white", class.unwrap_or("")]);
+
+    view! {
+        <section class=container_classes aria-label=format!("Audio call with {}", remote_name)>
+            <audio node_ref=audio_ref autoplay=true class="hidden"></audio>
+            <div class="flex items-center gap-2 rounded-full bg-white/10 px-3 py-1 text-xs">
+                <ConnectionQualityIndicator quality=quality />
+                <span aria-live="polite">{move || state.get().label()}</span>
+            </div>
+            <div class="flex flex-col items-center gap-2 text-center">
+                <span class=move || cn(&[
+                    "flex h-24 w-24 items-center justify-center rounded-full bg-gray-700 text-3xl font-semibold",
+                    if state.get() == CallState::Connected && remote_audio.get() { "ring-4 ring-green-500/40" } else { "" },
+                ])>
+                    {initials}
+                </span>
+                <h2 class="text-xl font-semibold">{remote_name.clone()}</h2>
+                {subtitle.map(|subtitle| view! { <p class="text-sm text-white/60">{subtitle}</p> })}
+                <p class="font-mono text-2xl tabular-nums" role="timer" aria-label="Call duration">
+                    {move || elapsed().unwrap_or_else(|| "--:--".to_string())}
+                </p>
+                <Show when=move || !remote_audio.get()>
+                    <p class="text-xs text-yellow-300">{muted_notice.clone()}</p>
+                </Show>
+            </div>
+            <div class="flex items-center gap-4" role="toolbar" aria-label="Call controls">
+                <button
+                    type="button"
+                    class=move || toggle_classes(muted.get())
+                    aria-pressed=move || muted.get().to_string()
+                    on:click=toggle_mute
+                >
+                    {move || if muted.get() { "Unmute" } else { "Mute" }}
+                </button>
+                <button
+                    type="button"
+                    class=move || toggle_classes(speaker.get())
+                    aria-pressed=move || speaker.get().to_string()
+                    on:click=toggle_speaker
+                >
+                    "Speaker"
+                </button>
+                {on_escalate.map(|on_escalate| view! {
+                    <button
+                        type="button"
+                        class=toggle_classes(false)
+                        aria-label="Switch to video"
+                        disabled=move || state.get() != CallState::Connected
+                        on:click=move |_| {
+                            engine.with_value(|engine| engine.set_video_enabled(true));
+                            on_escalate.run(());
+                        }
+                    >
+                        "Video"
+                    </button>
+                })}
+                <button
+                    type="button"
+                    class="flex h-16 w-16 items-center justify-center rounded-full bg-red-600 text-xs font-medium text-white transition-colors hover:bg-red-700 focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-white disabled:opacity-50"
+                    disabled=move || state.get() == CallState::Ended
+                    on:click=move |_| confirm_end.set(true)
+                >
+                    "End"
+                </button>
+            </div>
+            <ConfirmDialog
+                open=confirm_end
+                on_confirm=end_call
+                on_cancel=Callback::new(move |_| confirm_end.set(false))
+                title="End call?"
+                message=format!("This will disconnect you from {}.", remote_name)
+                confirm_label="End call"
+            />
+        </section>
+    }
+}
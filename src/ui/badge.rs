@@ -1,5 +1,5 @@
 use leptos::prelude::*;
-use crate::ui::{cn, Priority, HealthcareStatus};
+use crate::ui::{cn, use_i18n, I18n, Priority, HealthcareStatus};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BadgeVariant {
@@ -52,16 +52,39 @@ pub fn PriorityBadge(
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
     let (variant, icon, text) = match priority {
-        Priority::Emergency => (BadgeVariant::Destructive, "🚨", "Emergency"),
-        Priority::Urgent => (BadgeVariant::Warning, "⚠️", "Urgent"),
-        Priority::Normal => (BadgeVariant::Info, "ℹ️", "Normal"),
-        Priority::Low => (BadgeVariant::Success, "✅", "Low"),
+        Priority::Emergency => (BadgeVariant::Destructive, "🚨", "priority.emergency"),
+        Priority::Urgent => (BadgeVariant::Warning, "⚠️", "priority.urgent"),
+        Priority::Normal => (BadgeVariant::Info, "ℹ️", "priority.normal"),
+        Priority::Low => (BadgeVariant::Success, "✅", "priority.low"),
     };
+    let i18n = use_i18n();
     
     view! {
         <Badge variant=variant class=class.unwrap_or("")>
             <span class="mr-1">{icon}</span>
-            {text}
+            {move || i18n.t(text)}
+        </Badge>
+    }
+}
+
+/// Chat header badge; pass `ChatSession::end_to_end_encrypted` so chats that
+/// fell back to transport encryption never look end-to-end encrypted
+#[component]
+pub fn ChatSecurityBadge(
+    end_to_end: bool,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let (variant, icon, text) = if end_to_end {
+        (BadgeVariant::Success, "🔒", "chat.security.end_to_end")
+    } else {
+        (BadgeVariant::Warning, "⚠️", "chat.security.transport_only")
+    };
+    let i18n = use_i18n();
+
+    view! {
+        <Badge variant=variant class=class.unwrap_or("")>
+            <span class="mr-1" aria-hidden="true">{icon}</span>
+            {move || i18n.t(text)}
         </Badge>
     }
 }
@@ -72,21 +95,22 @@ pub fn StatusBadge(
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
     let (variant, icon, text) = match status {
-        HealthcareStatus::Active => (BadgeVariant::Success, "✅", "Active"),
-        HealthcareStatus::Inactive => (BadgeVariant::Secondary, "⭕", "Inactive"),
-        HealthcareStatus::Pending => (BadgeVariant::Warning, "⏳", "Pending"),
-        HealthcareStatus::Verified => (BadgeVariant::Info, "✔️", "Verified"),
-        HealthcareStatus::Suspended => (BadgeVariant::Destructive, "🚫", "Suspended"),
-        HealthcareStatus::Emergency => (BadgeVariant::Destructive, "🚨", "Emergency"),
-        HealthcareStatus::Stable => (BadgeVariant::Success, "✅", "Stable"),
-        HealthcareStatus::NeedsAttention => (BadgeVariant::Warning, "⚠️", "Needs Attention"),
-        HealthcareStatus::Critical => (BadgeVariant::Destructive, "🔴", "Critical"),
+        HealthcareStatus::Active => (BadgeVariant::Success, "✅", "status.active"),
+        HealthcareStatus::Inactive => (BadgeVariant::Secondary, "⭕", "status.inactive"),
+        HealthcareStatus::Pending => (BadgeVariant::Warning, "⏳", "status.pending"),
+        HealthcareStatus::Verified => (BadgeVariant::Info, "✔️", "status.verified"),
+        HealthcareStatus::Suspended => (BadgeVariant::Destructive, "🚫", "status.suspended"),
+        HealthcareStatus::Emergency => (BadgeVariant::Destructive, "🚨", "status.emergency"),
+        HealthcareStatus::Stable => (BadgeVariant::Success, "✅", "status.stable"),
+        HealthcareStatus::NeedsAttention => (BadgeVariant::Warning, "⚠️", "status.needs-attention"),
+        HealthcareStatus::Critical => (BadgeVariant::Destructive, "🔴", "status.critical"),
     };
+    let i18n = use_i18n();
     
     view! {
         <Badge variant=variant class=class.unwrap_or("")>
             <span class="mr-1">{icon}</span>
-            {text}
+            {move || i18n.t(text)}
         </Badge>
     }
 }
@@ -232,3 +256,96 @@ pub fn RatingBadge(
         </Badge>
     }
 }
+
+/// Count text for notification badges, capped as "99+" past `max`
+pub fn notification_count_label(count: u32, max: u32) -> String {
+    if count > max {
+        format!("{}+", max)
+    } else {
+        count.to_string()
+    }
+}
+
+fn notification_priority_classes(priority: Option<Priority>) -> &'static str {
+    match priority {
+        None | Some(Priority::Emergency) => "bg-red-500 text-white",
+        Some(Priority::Urgent) => "bg-orange-500 text-white",
+        Some(Priority::Normal) => "bg-primary text-primary-foreground",
+        Some(Priority::Low) => "bg-muted text-muted-foreground",
+    }
+}
+
+fn notification_aria_label(i18n: I18n, count: u32, label: Option<&'static str>) -> String {
+    match label {
+        Some(label) => format!("{} {}", count, label),
+        None => i18n.t_plural("common.notifications", count as u64),
+    }
+}
+
+/// Unread/pending count pill. Hidden while the count is zero; `dot` shows a
+/// plain marker instead of the number.
+#[component]
+pub fn NotificationBadge(
+    #[prop(into)] count: Signal<u32>,
+    #[prop(optional)] dot: Option<bool>,
+    /// Counts above this show as "{max}+" (default: 99)
+    #[prop(optional)] max: Option<u32>,
+    /// Colouring; red when not set
+    #[prop(optional)] priority: Option<Priority>,
+    /// What is being counted, for screen readers, e.g. "unread messages"
+    #[prop(optional)] label: Option<&'static str>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let dot = dot.unwrap_or(false);
+    let max = max.unwrap_or(99);
+    let i18n = use_i18n();
+
+    let shape = if dot {
+        "h-2 w-2"
+    } else {
+        "h-5 min-w-[1.25rem] px-1.5 text-xs font-semibold leading-none"
+    };
+    let badge_classes = cn(&[
+        "inline-flex items-center justify-center rounded-full",
+        shape,
+        notification_priority_classes(priority),
+        class.unwrap_or(""),
+    ]);
+
+    move || {
+        let count = count.get();
+        (count > 0).then(|| view! {
+            <span class=badge_classes.clone() role="status" aria-label=notification_aria_label(i18n, count, label)>
+                {(!dot).then(|| notification_count_label(count, max))}
+            </span>
+        })
+    }
+}
+
+/// Positions a [`NotificationBadge`] over the corner of an icon or button
+#[component]
+pub fn NotificationIndicator(
+    #[prop(into)] count: Signal<u32>,
+    #[prop(optional)] dot: Option<bool>,
+    #[prop(optional)] max: Option<u32>,
+    #[prop(optional)] priority: Option<Priority>,
+    #[prop(optional)] label: Option<&'static str>,
+    #[prop(optional)] class: Option<&'static str>,
+    children: Children,
+) -> impl IntoView {
+    let dot = dot.unwrap_or(false);
+    let offset = if dot { "absolute top-0 right-0" } else { "absolute -top-1.5 -right-1.5" };
+    let max = max.unwrap_or(99);
+    let priority = priority.unwrap_or(Priority::Emergency);
+    let badge = match label {
+        Some(label) => view! { <NotificationBadge count=count dot=dot max=max priority=priority label=label class=offset /> }.into_any(),
+        None => view! { <NotificationBadge count=count dot=dot max=max priority=priority class=offset /> }.into_any(),
+    };
+
+    view! {
+        <span class=cn(&["relative inline-flex", class.unwrap_or("")])>
+            {children()}
+            {badge}
+        </span>
+    }
+}
@@ -0,0 +1,233 @@
+use leptos::prelude::*;
+use crate::ui::cn;
+
+/// Prefix used when selected regions are stored in a service request's `special_requirements`
+pub const BODY_REGION_REQUIREMENT_PREFIX: &str = "body-region:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodySide {
+    Front,
+    Back,
+}
+
+impl BodySide {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BodySide::Front => "Front",
+            BodySide::Back => "Back",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Rect { x: f64, y: f64, width: f64, height: f64 },
+    Ellipse { cx: f64, cy: f64, rx: f64, ry: f64 },
+}
+
+/// Selectable area of the silhouette; `code` is stable and safe to persist
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodyRegion {
+    pub code: &'static str,
+    pub label: &'static str,
+    pub side: BodySide,
+    shape: Shape,
+}
+
+const fn rect(x: f64, y: f64, width: f64, height: f64) -> Shape {
+    Shape::Rect { x, y, width, height }
+}
+
+const fn ellipse(cx: f64, cy: f64, rx: f64, ry: f64) -> Shape {
+    Shape::Ellipse { cx, cy, rx, ry }
+}
+
+const fn region(code: &'static str, label: &'static str, side: BodySide, shape: Shape) -> BodyRegion {
+    BodyRegion { code, label, side, shape }
+}
+
+// 200×440 viewBox. On the front view the patient's right is on the viewer's left.
+const REGIONS: &[BodyRegion] = &[
+    region("front-head", "Head / face", BodySide::Front, ellipse(100.0, 40.0, 28.0, 34.0)),
+    region("front-neck", "Throat", BodySide::Front, rect(88.0, 72.0, 24.0, 18.0)),
+    region("front-chest", "Chest", BodySide::Front, rect(62.0, 90.0, 76.0, 60.0)),
+    region("front-abdomen", "Abdomen", BodySide::Front, rect(66.0, 150.0, 68.0, 55.0)),
+    region("front-pelvis", "Pelvis / groin", BodySide::Front, rect(66.0, 205.0, 68.0, 35.0)),
+    region("front-right-upper-arm", "Right upper arm", BodySide::Front, rect(36.0, 92.0, 24.0, 80.0)),
+    region("front-left-upper-arm", "Left upper arm", BodySide::Front, rect(140.0, 92.0, 24.0, 80.0)),
+    region("front-right-forearm", "Right forearm", BodySide::Front, rect(28.0, 172.0, 22.0, 70.0)),
+    region("front-left-forearm", "Left forearm", BodySide::Front, rect(150.0, 172.0, 22.0, 70.0)),
+    region("front-right-hand", "Right hand", BodySide::Front, ellipse(38.0, 256.0, 12.0, 16.0)),
+    region("front-left-hand", "Left hand", BodySide::Front, ellipse(162.0, 256.0, 12.0, 16.0)),
+    region("front-right-thigh", "Right thigh", BodySide::Front, rect(68.0, 240.0, 30.0, 86.0)),
+    region("front-left-thigh", "Left thigh", BodySide::Front, rect(102.0, 240.0, 30.0, 86.0)),
+    region("front-right-knee", "Right knee", BodySide::Front, ellipse(83.0, 338.0, 14.0, 12.0)),
+    region("front-left-knee", "Left knee", BodySide::Front, ellipse(117.0, 338.0, 14.0, 12.0)),
+    region("front-right-shin", "Right shin", BodySide::Front, rect(70.0, 350.0, 26.0, 66.0)),
+    region("front-left-shin", "Left shin", BodySide::Front, rect(104.0, 350.0, 26.0, 66.0)),
+    region("front-right-foot", "Right foot", BodySide::Front, ellipse(82.0, 426.0, 16.0, 9.0)),
+    region("front-left-foot", "Left foot", BodySide::Front, ellipse(118.0, 426.0, 16.0, 9.0)),
+    region("back-head", "Back of head", BodySide::Back, ellipse(100.0, 40.0, 28.0, 34.0)),
+    region("back-neck", "Back of neck", BodySide::Back, rect(88.0, 72.0, 24.0, 18.0)),
+    region("back-upper", "Upper back", BodySide::Back, rect(62.0, 90.0, 76.0, 60.0)),
+    region("back-lower", "Lower back", BodySide::Back, rect(66.0, 150.0, 68.0, 55.0)),
+    region("back-buttocks", "Buttocks", BodySide::Back, rect(66.0, 205.0, 68.0, 35.0)),
+    region("back-left-upper-arm", "Left upper arm (back)", BodySide::Back, rect(36.0, 92.0, 24.0, 80.0)),
+    region("back-right-upper-arm", "Right upper arm (back)", BodySide::Back, rect(140.0, 92.0, 24.0, 80.0)),
+    region("back-left-forearm", "Left forearm (back)", BodySide::Back, rect(28.0, 172.0, 22.0, 70.0)),
+    region("back-right-forearm", "Right forearm (back)", BodySide::Back, rect(150.0, 172.0, 22.0, 70.0)),
+    region("back-left-hand", "Back of left hand", BodySide::Back, ellipse(38.0, 256.0, 12.0, 16.0)),
+    region("back-right-hand", "Back of right hand", BodySide::Back, ellipse(162.0, 256.0, 12.0, 16.0)),
+    region("back-left-hamstring", "Left hamstring", BodySide::Back, rect(68.0, 240.0, 30.0, 86.0)),
+    region("back-right-hamstring", "Right hamstring", BodySide::Back, rect(102.0, 240.0, 30.0, 86.0)),
+    region("back-left-knee", "Back of left knee", BodySide::Back, ellipse(83.0, 338.0, 14.0, 12.0)),
+    region("back-right-knee", "Back of right knee", BodySide::Back, ellipse(117.0, 338.0, 14.0, 12.0)),
+    region("back-left-calf", "Left calf", BodySide::Back, rect(70.0, 350.0, 26.0, 66.0)),
+    region("back-right-calf", "Right calf", BodySide::Back, rect(104.0, 350.0, 26.0, 66.0)),
+    region("back-left-heel", "Left heel", BodySide::Back, ellipse(82.0, 426.0, 16.0, 9.0)),
+    region("back-right-heel", "Right heel", BodySide::Back, ellipse(118.0, 426.0, 16.0, 9.0)),
+];
+
+pub fn body_regions(side: BodySide) -> impl Iterator<Item = &'static BodyRegion> {
+    REGIONS.iter().filter(move |r| r.side == side)
+}
+
+pub fn body_region(code: &str) -> Option<&'static BodyRegion> {
+    REGIONS.iter().find(|r| r.code == code)
+}
+
+/// Region codes as `special_requirements` entries, e.g. "body-region:front-chest"
+pub fn body_region_requirements(codes: &[String]) -> Vec<String> {
+    codes
+        .iter()
+        .map(|code| format!("{}{}", BODY_REGION_REQUIREMENT_PREFIX, code))
+        .collect()
+}
+
+/// Front/back silhouette where patients mark where it hurts.
+///
+/// `selected` holds region codes; see [`body_region_requirements`] for
+/// attaching them to a service request.
+#[component]
+pub fn BodyMap(
+    selected: RwSignal<Vec<String>>,
+    /// Allow more than one region (default: true)
+    #[prop(optional)] multiple: Option<bool>,
+    #[prop(optional)] on_change: Option<Callback<Vec<String>>>,
+    #[prop(optional, into)] label: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let multiple = multiple.unwrap_or(true);
+    let label = label.unwrap_or_else(|| "Where is the pain or injury?".to_string());
+    let side = RwSignal::new(BodySide::Front);
+
+    let toggle = move |code: &'static str| {
+        selected.update(|codes| {
+            if let Some(position) = codes.iter().position(|c| c == code) {
+                codes.remove(position);
+            } else if multiple {
+                codes.push(code.to_string());
+            } else {
+                *codes = vec![code.to_string()];
+            }
+        });
+        if let Some(callback) = on_change {
+            callback.run(selected.get_untracked());
+        }
+    };
+
+    let side_button = move |value: BodySide| {
+        view! {
+            <button
+                type="button"
+                role="tab"
+                aria-selected=move || (side.get() == value).to_string()
+                class=move || cn(&[
+                    "rounded-sm px-3 py-1 text-sm font-medium transition-colors",
+                    if side.get() == value { "bg-background text-foreground shadow-sm" } else { "text-muted-foreground hover:text-foreground" },
+                ])
+                on:click=move |_| side.set(value)
+            >
+                {value.label()}
+            </button>
+        }
+    };
+
+    let regions = move || {
+        body_regions(side.get())
+            .map(|region| {
+                let code = region.code;
+                let is_selected = move || selected.with(|codes| codes.iter().any(|c| c == code));
+                let fill = move || if is_selected() { "hsl(var(--destructive))" } else { "hsl(var(--muted))" };
+                let shape = match region.shape {
+                    Shape::Rect { x, y, width, height } => view! {
+                        <rect x=x y=y width=width height=height rx="8" fill=fill stroke="hsl(var(--border))" />
+                    }
+                    .into_any(),
+                    Shape::Ellipse { cx, cy, rx, ry } => view! {
+                        <ellipse cx=cx cy=cy rx=rx ry=ry fill=fill stroke="hsl(var(--border))" />
+                    }
+                    .into_any(),
+                };
+                view! {
+                    <g
+                        role="checkbox"
+                        tabindex="0"
+                        aria-label=region.label
+                        aria-checked=move || is_selected().to_string()
+                        class="cursor-pointer outline-none transition-opacity hover:opacity-80 focus-visible:opacity-70"
+                        on:click=move |_| toggle(code)
+                        on:keydown=move |ev| {
+                            if ev.key() == "Enter" || ev.key() == " " {
+                                ev.prevent_default();
+                                toggle(code);
+                            }
+                        }
+                    >
+                        {shape}
+                    </g>
+                }
+            })
+            .collect_view()
+    };
+
+    let chips = move || {
+        selected
+            .get()
+            .into_iter()
+            .filter_map(|code| body_region(&code))
+            .map(|region| {
+                let code = region.code;
+                view! {
+                    <li class="inline-flex items-center gap-1 rounded-full bg-destructive/10 px-2.5 py-0.5 text-xs font-medium text-destructive">
+                        {region.label}
+                        <button
+                            type="button"
+                            class="ml-0.5 rounded-full hover:text-foreground"
+                            aria-label=format!("Remove {}", region.label)
+                            on:click=move |_| toggle(code)
+                        >
+                            "✕"
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    let container_classes = cn(&["w-full space-y-3", class.unwrap_or("")]);
+
+    view! {
+        <fieldset class=container_classes>
+            <legend class="text-sm font-medium">{label}</legend>
+            <div role="tablist" aria-label="Body view" class="inline-flex rounded-md bg-muted p-1">
+                {side_button(BodySide::Front)}
+                {side_button(BodySide::Back)}
+            </div>
+            <svg viewBox="0 0 200 440" class="mx-auto h-96 w-auto" aria-label=move || format!("{} of body", side.get().label())>
+                {regions}
+            </svg>
+            <ul class="flex flex-wrap gap-2" aria-live="polite">{chips}</ul>
+        </fieldset>
+    }
+}
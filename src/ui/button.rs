@@ -30,7 +30,7 @@ pub fn Button(
     #[prop(optional)] class: Option<&'static str>,
     #[prop(optional)] disabled: Option<bool>,
     #[prop(optional)] loading: Option<bool>,
-    #[prop(optional)] on_click: Option<Box<dyn Fn() + 'static + Send>>,
+    #[prop(optional)] on_click: Option<Callback<()>>,
     children: Children,
 ) -> impl IntoView {
     let variant = variant.unwrap_or(ButtonVariant::Default);
@@ -73,13 +73,12 @@ pub fn Button(
             class=button_classes
             disabled=move || disabled || loading
             on:click=move |_| {
-                if let Some(handler) = &on_click {
+                if let Some(handler) = on_click {
                     if !disabled && !loading {
-                        handler();
+                        handler.run(());
                     }
                 }
             }
-        >
         >
             {move || {
                 if loading {
@@ -103,7 +102,7 @@ pub fn Button(
 pub fn EmergencyButton(
     #[prop(optional)] class: Option<&'static str>,
     #[prop(optional)] disabled: Option<bool>,
-    #[prop(optional)] on_click: Option<Box<dyn Fn() + 'static + Send>>,
+    #[prop(optional)] on_click: Option<Callback<()>>,
     children: Children,
 ) -> impl IntoView {
     let emergency_classes = "bg-red-600 text-white hover:bg-red-700 border-2 border-red-800 shadow-lg animate-pulse";
@@ -113,7 +112,7 @@ pub fn EmergencyButton(
             variant=ButtonVariant::Destructive
             class=emergency_classes
             disabled=disabled.unwrap_or(false)
-            on_click=on_click.unwrap_or_else(|| Box::new(|| {}))
+            on_click=on_click.unwrap_or_else(|| Callback::new(|_| {}))
         >
             <span class="mr-2">"🚨"</span>
             {children()}
@@ -125,7 +124,7 @@ pub fn EmergencyButton(
 pub fn CallButton(
     #[prop(optional)] class: Option<&'static str>,
     #[prop(optional)] disabled: Option<bool>,
-    #[prop(optional)] on_click: Option<Box<dyn Fn() + 'static + Send>>,
+    #[prop(optional)] on_click: Option<Callback<()>>,
     children: Children,
 ) -> impl IntoView {
     let call_classes = "bg-green-600 text-white hover:bg-green-700 shadow-md";
@@ -135,7 +134,7 @@ pub fn CallButton(
             variant=ButtonVariant::Default
             class=call_classes
             disabled=disabled.unwrap_or(false)
-            on_click=on_click.unwrap_or_else(|| Box::new(|| {}))
+            on_click=on_click.unwrap_or_else(|| Callback::new(|_| {}))
         >
             <span class="mr-2">"📞"</span>
             {children()}
@@ -147,7 +146,7 @@ pub fn CallButton(
 pub fn VideoCallButton(
     #[prop(optional)] class: Option<&'static str>,
     #[prop(optional)] disabled: Option<bool>,
-    #[prop(optional)] on_click: Option<Box<dyn Fn() + 'static + Send>>,
+    #[prop(optional)] on_click: Option<Callback<()>>,
     children: Children,
 ) -> impl IntoView {
     let video_classes = "bg-blue-600 text-white hover:bg-blue-700 shadow-md";
@@ -157,7 +156,7 @@ pub fn VideoCallButton(
             variant=ButtonVariant::Default
             class=video_classes
             disabled=disabled.unwrap_or(false)
-            on_click=on_click.unwrap_or_else(|| Box::new(|| {}))
+            on_click=on_click.unwrap_or_else(|| Callback::new(|_| {}))
         >
             <span class="mr-2">"📹"</span>
             {children()}
@@ -170,7 +169,7 @@ pub fn BookingButton(
     #[prop(optional)] class: Option<&'static str>,
     #[prop(optional)] disabled: Option<bool>,
     #[prop(optional)] loading: Option<bool>,
-    #[prop(optional)] on_click: Option<Box<dyn Fn() + 'static + Send>>,
+    #[prop(optional)] on_click: Option<Callback<()>>,
     children: Children,
 ) -> impl IntoView {
     let booking_classes = "bg-indigo-600 text-white hover:bg-indigo-700 shadow-md";
@@ -181,7 +180,7 @@ pub fn BookingButton(
             class=booking_classes
             disabled=disabled.unwrap_or(false)
             loading=loading.unwrap_or(false)
-            on_click=on_click.unwrap_or_else(|| Box::new(|| {}))
+            on_click=on_click.unwrap_or_else(|| Callback::new(|_| {}))
         >
             <span class="mr-2">"📅"</span>
             {children()}
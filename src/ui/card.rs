@@ -169,7 +169,7 @@ pub fn ProviderCard(
     #[prop(optional)] rating: Option<f32>,
     #[prop(optional)] availability: Option<String>,
     #[prop(optional)] class: Option<&'static str>,
-    #[prop(optional)] on_book: Option<Box<dyn Fn() + 'static + Send>>,
+    #[prop(optional)] on_book: Option<Callback<()>>,
 ) -> impl IntoView {
     view! {
         <Card class="hover:shadow-md transition-shadow">
@@ -211,17 +211,13 @@ pub fn ProviderCard(
                     view! {}.into_any()
                 }
             }}
-            {if on_book.is_some() {
-                view! {
-                    <CardFooter>
-                        <crate::ui::button::BookingButton>
-                            "Book Appointment"
-                        </crate::ui::button::BookingButton>
-                    </CardFooter>
-                }.into_any()
-            } else {
-                view! {}.into_any()
-            }}
+            {on_book.map(|on_book| view! {
+                <CardFooter>
+                    <crate::ui::button::BookingButton on_click=on_book>
+                        "Book Appointment"
+                    </crate::ui::button::BookingButton>
+                </CardFooter>
+            })}
         </Card>
     }
 }
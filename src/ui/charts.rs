@@ -0,0 +1,823 @@
+use leptos::prelude::*;
+use std::collections::BTreeMap;
+use crate::api_client::VitalSigns;
+use crate::ui::cn;
+use crate::ui::healthcare::{AppointmentInfo, HealthMetric};
+use crate::ui::DesignSystem;
+use crate::units::Unit;
+use crate::utils::datetime::parse_timestamp;
+
+/// A single labelled value, e.g. one bar or one donut segment
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartPoint {
+    pub label: String,
+    pub value: f64,
+}
+
+impl ChartPoint {
+    pub fn new(label: impl Into<String>, value: f64) -> Self {
+        Self {
+            label: label.into(),
+            value,
+        }
+    }
+}
+
+/// A named line in a [`LineChart`]; points share x positions across series
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSeries {
+    pub name: String,
+    pub points: Vec<ChartPoint>,
+    /// Overrides the palette colour for this series
+    pub color: Option<&'static str>,
+}
+
+impl ChartSeries {
+    pub fn new(name: impl Into<String>, points: Vec<ChartPoint>) -> Self {
+        Self {
+            name: name.into(),
+            points,
+            color: None,
+        }
+    }
+
+    pub fn with_color(mut self, color: &'static str) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Palette colour for the `index`th series, cycling through `chart_1..chart_5`
+pub fn chart_color(index: usize) -> &'static str {
+    DesignSystem::default().colors.chart_colors()[index % 5]
+}
+
+/// Plot area inside an SVG viewBox, mapping data values to coordinates
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ChartFrame {
+    pub width: f64,
+    pub height: f64,
+    pub left: f64,
+    pub right: f64,
+    pub top: f64,
+    pub bottom: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ChartFrame {
+    pub fn new(width: f64, height: f64, (min, max): (f64, f64)) -> Self {
+        Self {
+            width,
+            height,
+            left: 40.0,
+            right: 12.0,
+            top: 12.0,
+            bottom: 28.0,
+            min,
+            max,
+        }
+    }
+
+    pub fn plot_width(&self) -> f64 {
+        self.width - self.left - self.right
+    }
+
+    pub fn plot_height(&self) -> f64 {
+        self.height - self.top - self.bottom
+    }
+
+    /// Centre of the `index`th of `count` evenly spaced positions
+    pub fn x(&self, index: usize, count: usize) -> f64 {
+        if count <= 1 {
+            return self.left + self.plot_width() / 2.0;
+        }
+        self.left + self.plot_width() * index as f64 / (count - 1) as f64
+    }
+
+    /// Position `ratio` (0.0–1.0) of the way across the plot area
+    pub fn x_ratio(&self, ratio: f64) -> f64 {
+        self.left + self.plot_width() * ratio.clamp(0.0, 1.0)
+    }
+
+    pub fn y(&self, value: f64) -> f64 {
+        let span = (self.max - self.min).max(f64::EPSILON);
+        self.top + self.plot_height() * (1.0 - (value - self.min) / span)
+    }
+
+    pub fn baseline(&self) -> f64 {
+        self.y(self.min.max(0.0).min(self.max))
+    }
+}
+
+/// Range covering `values` with some headroom; `None` when there is no data
+pub(crate) fn value_range(values: impl IntoIterator<Item = f64>) -> Option<(f64, f64)> {
+    let (min, max) = values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold(None, |range: Option<(f64, f64)>, v| match range {
+            Some((min, max)) => Some((min.min(v), max.max(v))),
+            None => Some((v, v)),
+        })?;
+
+    if (max - min).abs() < f64::EPSILON {
+        return Some((min - 1.0, max + 1.0));
+    }
+    let padding = (max - min) * 0.1;
+    Some((min - padding, max + padding))
+}
+
+/// Evenly spaced axis ticks rounded to 1, 2 or 5 times a power of ten
+pub(crate) fn nice_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    if max.is_nan() || min.is_nan() || max <= min || count < 2 {
+        return vec![min];
+    }
+    let raw_step = (max - min) / (count - 1) as f64;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(raw_step);
+
+    let mut ticks = Vec::new();
+    let mut tick = (min / step).ceil() * step;
+    while tick <= max + step * 1e-9 {
+        ticks.push(tick);
+        tick += step;
+    }
+    ticks
+}
+
+pub(crate) fn format_tick(value: f64) -> String {
+    if value.fract().abs() < 1e-9 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+pub(crate) fn line_path(points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, (x, y))| format!("{}{:.1} {:.1}", if i == 0 { "M" } else { " L" }, x, y))
+        .collect()
+}
+
+/// Horizontal grid lines and y-axis labels
+pub(crate) fn y_axis(frame: ChartFrame) -> AnyView {
+    nice_ticks(frame.min, frame.max, 5)
+        .into_iter()
+        .map(|tick| {
+            let y = frame.y(tick);
+            view! {
+                <g>
+                    <line x1=frame.left x2=frame.width - frame.right y1=y y2=y stroke="currentColor" stroke-opacity="0.1" />
+                    <text x=frame.left - 6.0 y=y + 3.0 text-anchor="end" font-size="10" fill="currentColor" fill-opacity="0.6">
+                        {format_tick(tick)}
+                    </text>
+                </g>
+            }
+        })
+        .collect_view()
+        .into_any()
+}
+
+/// X-axis labels, thinned so at most `max_labels` are drawn
+pub(crate) fn x_axis(frame: ChartFrame, labels: &[String], max_labels: usize) -> AnyView {
+    let count = labels.len();
+    let every = count.div_ceil(max_labels.max(1)).max(1);
+    labels
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i % every == 0)
+        .map(|(i, label)| {
+            view! {
+                <text x=frame.x(i, count) y=frame.height - 8.0 text-anchor="middle" font-size="10" fill="currentColor" fill-opacity="0.6">
+                    {label.clone()}
+                </text>
+            }
+        })
+        .collect_view()
+        .into_any()
+}
+
+#[component]
+pub fn ChartLegend(items: Vec<(String, &'static str)>) -> impl IntoView {
+    view! {
+        <ul class="flex flex-wrap gap-4 text-xs text-muted-foreground">
+            {items
+                .into_iter()
+                .map(|(name, color)| view! {
+                    <li class="flex items-center gap-1.5">
+                        <span class="inline-block h-2.5 w-2.5 rounded-full" style=format!("background-color: {}", color)></span>
+                        {name}
+                    </li>
+                })
+                .collect_view()}
+        </ul>
+    }
+}
+
+fn empty_chart(height: u32) -> AnyView {
+    view! {
+        <div class="flex items-center justify-center text-sm text-muted-foreground" style=format!("height: {}px", height)>
+            "No data to display"
+        </div>
+    }
+    .into_any()
+}
+
+/// Multi-series line chart
+#[component]
+pub fn LineChart(
+    #[prop(into)] series: Signal<Vec<ChartSeries>>,
+    /// Accessible name for the chart
+    #[prop(into)] title: String,
+    #[prop(optional)] height: Option<u32>,
+    #[prop(optional)] y_min: Option<f64>,
+    #[prop(optional)] y_max: Option<f64>,
+    #[prop(optional, into)] unit: Option<String>,
+    #[prop(optional)] show_points: Option<bool>,
+    #[prop(optional)] show_legend: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let height = height.unwrap_or(240);
+    let show_points = show_points.unwrap_or(true);
+    let show_legend = show_legend.unwrap_or(true);
+    let unit = unit.unwrap_or_default();
+    let container_classes = cn(&["w-full space-y-2", class.unwrap_or("")]);
+
+    let chart = move || {
+        let series = series.get();
+        let Some((auto_min, auto_max)) = value_range(series.iter().flat_map(|s| s.points.iter().map(|p| p.value))) else {
+            return empty_chart(height);
+        };
+        let frame = ChartFrame::new(600.0, height as f64, (y_min.unwrap_or(auto_min), y_max.unwrap_or(auto_max)));
+        let count = series.iter().map(|s| s.points.len()).max().unwrap_or(0);
+        let labels: Vec<String> = series
+            .iter()
+            .max_by_key(|s| s.points.len())
+            .map(|s| s.points.iter().map(|p| p.label.clone()).collect())
+            .unwrap_or_default();
+
+        let lines = series
+            .iter()
+            .enumerate()
+            .map(|(index, s)| {
+                let color = s.color.unwrap_or_else(|| chart_color(index));
+                let coords: Vec<(f64, f64)> = s
+                    .points
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| (frame.x(i, count), frame.y(p.value)))
+                    .collect();
+                let dots = show_points.then(|| {
+                    s.points
+                        .iter()
+                        .zip(coords.iter())
+                        .map(|(point, (x, y))| {
+                            let tooltip = format!("{} – {}: {} {}", s.name, point.label, format_tick(point.value), unit);
+                            view! {
+                                <circle cx=*x cy=*y r="3" fill=color>
+                                    <title>{tooltip}</title>
+                                </circle>
+                            }
+                        })
+                        .collect_view()
+                });
+                view! {
+                    <g>
+                        <path d=line_path(&coords) fill="none" stroke=color stroke-width="2" stroke-linejoin="round" stroke-linecap="round" />
+                        {dots}
+                    </g>
+                }
+            })
+            .collect_view();
+
+        let legend = (show_legend && series.len() > 1).then(|| {
+            let items = series
+                .iter()
+                .enumerate()
+                .map(|(index, s)| (s.name.clone(), s.color.unwrap_or_else(|| chart_color(index))))
+                .collect::<Vec<_>>();
+            view! { <ChartLegend items=items /> }
+        });
+
+        view! {
+            <svg viewBox=format!("0 0 {} {}", frame.width, frame.height) class="w-full text-foreground" role="img" aria-label=title.clone()>
+                {y_axis(frame)}
+                {x_axis(frame, &labels, 8)}
+                {lines}
+            </svg>
+            {legend}
+        }
+        .into_any()
+    };
+
+    view! { <figure class=container_classes>{chart}</figure> }
+}
+
+/// Vertical bar chart
+#[component]
+pub fn BarChart(
+    #[prop(into)] data: Signal<Vec<ChartPoint>>,
+    #[prop(into)] title: String,
+    #[prop(optional)] height: Option<u32>,
+    #[prop(optional)] color: Option<&'static str>,
+    #[prop(optional, into)] unit: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let height = height.unwrap_or(240);
+    let color = color.unwrap_or_else(|| chart_color(0));
+    let unit = unit.unwrap_or_default();
+    let container_classes = cn(&["w-full", class.unwrap_or("")]);
+
+    let chart = move || {
+        let data = data.get();
+        let Some((_, max)) = value_range(data.iter().map(|p| p.value).chain(std::iter::once(0.0))) else {
+            return empty_chart(height);
+        };
+        if data.is_empty() {
+            return empty_chart(height);
+        }
+
+        let frame = ChartFrame::new(600.0, height as f64, (0.0, max));
+        let slot = frame.plot_width() / data.len() as f64;
+        let bar_width = (slot * 0.7).max(1.0);
+        let labels: Vec<String> = data.iter().map(|p| p.label.clone()).collect();
+
+        let bars = data
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let x = frame.left + slot * i as f64 + (slot - bar_width) / 2.0;
+                let y = frame.y(point.value.max(0.0));
+                let tooltip = format!("{}: {} {}", point.label, format_tick(point.value), unit);
+                view! {
+                    <rect x=x y=y width=bar_width height=(frame.baseline() - y).max(0.0) rx="2" fill=color>
+                        <title>{tooltip}</title>
+                    </rect>
+                }
+            })
+            .collect_view();
+
+        // Bar centres sit in the middle of each slot rather than on the line-chart grid
+        let label_frame = ChartFrame {
+            left: frame.left + slot / 2.0,
+            right: frame.right + slot / 2.0,
+            ..frame
+        };
+
+        view! {
+            <svg viewBox=format!("0 0 {} {}", frame.width, frame.height) class="w-full text-foreground" role="img" aria-label=title.clone()>
+                {y_axis(frame)}
+                {x_axis(label_frame, &labels, 12)}
+                {bars}
+            </svg>
+        }
+        .into_any()
+    };
+
+    view! { <figure class=container_classes>{chart}</figure> }
+}
+
+/// Compact inline trend line without axes
+#[component]
+pub fn Sparkline(
+    #[prop(into)] values: Signal<Vec<f64>>,
+    #[prop(into)] label: String,
+    #[prop(optional)] color: Option<&'static str>,
+    #[prop(optional)] width: Option<u32>,
+    #[prop(optional)] height: Option<u32>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let width = width.unwrap_or(100) as f64;
+    let height = height.unwrap_or(24) as f64;
+    let color = color.unwrap_or_else(|| chart_color(0));
+    let classes = cn(&["inline-block align-middle", class.unwrap_or("")]);
+
+    let path = move || {
+        let values = values.get();
+        let Some(range) = value_range(values.iter().copied()) else {
+            return String::new();
+        };
+        let frame = ChartFrame {
+            left: 1.0,
+            right: 1.0,
+            top: 2.0,
+            bottom: 2.0,
+            ..ChartFrame::new(width, height, range)
+        };
+        let coords: Vec<(f64, f64)> = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (frame.x(i, values.len()), frame.y(*v)))
+            .collect();
+        line_path(&coords)
+    };
+
+    view! {
+        <svg
+            viewBox=format!("0 0 {} {}", width, height)
+            width=width
+            height=height
+            class=classes
+            role="img"
+            aria-label=label
+        >
+            <path d=path fill="none" stroke=color stroke-width="1.5" stroke-linejoin="round" stroke-linecap="round" />
+        </svg>
+    }
+}
+
+/// Donut chart with an optional centre label, e.g. appointment status breakdown
+#[component]
+pub fn DonutChart(
+    #[prop(into)] segments: Signal<Vec<ChartPoint>>,
+    #[prop(into)] title: String,
+    #[prop(optional, into)] center_label: Option<String>,
+    #[prop(optional)] size: Option<u32>,
+    #[prop(optional)] show_legend: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    const RADIUS: f64 = 40.0;
+    const STROKE: f64 = 14.0;
+    let size = size.unwrap_or(160);
+    let show_legend = show_legend.unwrap_or(true);
+    let container_classes = cn(&["flex flex-col items-center gap-3", class.unwrap_or("")]);
+
+    let chart = move || {
+        let segments: Vec<ChartPoint> = segments.get().into_iter().filter(|s| s.value > 0.0).collect();
+        let total: f64 = segments.iter().map(|s| s.value).sum();
+        if total <= 0.0 {
+            return empty_chart(size);
+        }
+
+        let circumference = 2.0 * std::f64::consts::PI * RADIUS;
+        let mut offset = 0.0;
+        let arcs = segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let length = circumference * segment.value / total;
+                let dash_offset = -offset;
+                offset += length;
+                let tooltip = format!("{}: {} ({:.0}%)", segment.label, format_tick(segment.value), segment.value / total * 100.0);
+                view! {
+                    <circle
+                        cx="50" cy="50" r=RADIUS
+                        fill="none"
+                        stroke=chart_color(index)
+                        stroke-width=STROKE
+                        stroke-dasharray=format!("{:.2} {:.2}", length, circumference - length)
+                        stroke-dashoffset=format!("{:.2}", dash_offset)
+                        transform="rotate(-90 50 50)"
+                    >
+                        <title>{tooltip}</title>
+                    </circle>
+                }
+            })
+            .collect_view();
+
+        let legend = show_legend.then(|| {
+            let items = segments
+                .iter()
+                .enumerate()
+                .map(|(index, s)| (format!("{} ({})", s.label, format_tick(s.value)), chart_color(index)))
+                .collect::<Vec<_>>();
+            view! { <ChartLegend items=items /> }
+        });
+
+        view! {
+            <svg viewBox="0 0 100 100" width=size height=size role="img" aria-label=title.clone() class="text-foreground">
+                <circle cx="50" cy="50" r=RADIUS fill="none" stroke="currentColor" stroke-opacity="0.08" stroke-width=STROKE />
+                {arcs}
+                {center_label.clone().map(|label| view! {
+                    <text x="50" y="54" text-anchor="middle" font-size="12" font-weight="600" fill="currentColor">{label}</text>
+                })}
+            </svg>
+            {legend}
+        }
+        .into_any()
+    };
+
+    view! { <figure class=container_classes>{chart}</figure> }
+}
+
+/// Blood pressure and heart rate over time from successive vitals readings
+#[component]
+pub fn VitalsOverTimeChart(
+    #[prop(into)] readings: Signal<Vec<VitalSigns>>,
+    #[prop(optional)] height: Option<u32>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let palette = DesignSystem::default().colors;
+    let series = Signal::derive(move || {
+        let readings = readings.get();
+        let label = |v: &VitalSigns| v.last_measured.chars().take(10).collect::<String>();
+        let series_for = |name: &str, value: &dyn Fn(&VitalSigns) -> Option<f64>| {
+            let points = readings
+                .iter()
+                .filter_map(|v| value(v).map(|value| ChartPoint::new(label(v), value)))
+                .collect::<Vec<_>>();
+            (!points.is_empty()).then(|| ChartSeries::new(name, points))
+        };
+
+        [
+            series_for("Systolic (mmHg)", &|v| v.blood_pressure.as_ref().map(|bp| bp.systolic as f64))
+                .map(|s| s.with_color(palette.emergency)),
+            series_for("Diastolic (mmHg)", &|v| v.blood_pressure.as_ref().map(|bp| bp.diastolic as f64))
+                .map(|s| s.with_color(palette.urgent)),
+            series_for("Heart rate (bpm)", &|v| v.heart_rate.map(|hr| hr as f64))
+                .map(|s| s.with_color(palette.primary)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+    });
+
+    view! {
+        <LineChart
+            series=series
+            title="Vitals over time"
+            height=height.unwrap_or(260)
+            class=class.unwrap_or("")
+        />
+    }
+}
+
+/// Number of appointments per day
+#[component]
+pub fn AppointmentVolumeChart(
+    #[prop(into)] appointments: Signal<Vec<AppointmentInfo>>,
+    #[prop(optional)] height: Option<u32>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let data = Signal::derive(move || {
+        let mut per_day: BTreeMap<String, f64> = BTreeMap::new();
+        appointments.with(|appointments| {
+            for appointment in appointments {
+                *per_day.entry(appointment.date.clone()).or_default() += 1.0;
+            }
+        });
+        per_day
+            .into_iter()
+            .map(|(date, count)| ChartPoint::new(date, count))
+            .collect::<Vec<_>>()
+    });
+
+    view! {
+        <BarChart
+            data=data
+            title="Appointment volume"
+            unit="appointments"
+            height=height.unwrap_or(240)
+            class=class.unwrap_or("")
+        />
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartEventKind {
+    Medication,
+    Appointment,
+    Other,
+}
+
+impl ChartEventKind {
+    fn marker(&self) -> &'static str {
+        match self {
+            ChartEventKind::Medication => "💊",
+            ChartEventKind::Appointment => "📅",
+            ChartEventKind::Other => "•",
+        }
+    }
+}
+
+/// Something that happened at a point in time, drawn as a vertical annotation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartEvent {
+    /// Timestamp in any format accepted by `parse_timestamp`
+    pub at: String,
+    pub label: String,
+    pub kind: ChartEventKind,
+}
+
+impl ChartEvent {
+    pub fn new(at: impl Into<String>, label: impl Into<String>, kind: ChartEventKind) -> Self {
+        Self {
+            at: at.into(),
+            label: label.into(),
+            kind,
+        }
+    }
+
+    pub fn medication(at: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(at, label, ChartEventKind::Medication)
+    }
+
+    pub fn appointment(at: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(at, label, ChartEventKind::Appointment)
+    }
+}
+
+/// Leading number of a metric value, e.g. 120 from "120/80" or 98.6 from "98.6°F"
+fn leading_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let end = value
+        .char_indices()
+        .find(|(i, c)| !(c.is_ascii_digit() || *c == '.' || (*i == 0 && *c == '-')))
+        .map(|(i, _)| i)
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+/// Bounds of a reference range such as "60-100", "< 120", "> 95%" or "36.1 – 37.2 °C"
+fn parse_normal_range(range: &str) -> Option<(Option<f64>, Option<f64>)> {
+    let range = range.trim();
+    if let Some(rest) = range.strip_prefix('<').or_else(|| range.strip_prefix('≤')) {
+        return Some((None, Some(leading_number(rest.trim_start_matches('=').trim())?)));
+    }
+    if let Some(rest) = range.strip_prefix('>').or_else(|| range.strip_prefix('≥')) {
+        return Some((Some(leading_number(rest.trim_start_matches('=').trim())?), None));
+    }
+    let (low, high) = range.split_once(['-', '–'])?;
+    Some((Some(leading_number(low)?), Some(leading_number(high)?)))
+}
+
+/// Reading value in the chart's unit. Readings in another unit are converted,
+/// or dropped when they cannot be, so mg/dL and mmol/L never share an axis.
+fn reading_value(reading: &HealthMetric, unit: &str, target: Option<Unit>) -> Option<f64> {
+    if let (Some(quantity), Some(target)) = (reading.quantity(), target) {
+        return quantity.value_in(target).ok();
+    }
+    (reading.unit() == unit).then(|| leading_number(&reading.value_text())).flatten()
+}
+
+/// Time series of one [`HealthMetric`] with its normal range shaded.
+///
+/// Readings outside the range are marked, and medication or appointment
+/// events are annotated at their time. Readings are placed by their
+/// `last_updated` timestamp; if any cannot be parsed they are spaced evenly
+/// and events are omitted.
+#[component]
+pub fn VitalTrendChart(
+    #[prop(into)] readings: Signal<Vec<HealthMetric>>,
+    #[prop(optional, into)] events: Option<Signal<Vec<ChartEvent>>>,
+    /// Overrides the range parsed from the readings' `normal_range`
+    #[prop(optional)] normal_range: Option<(Option<f64>, Option<f64>)>,
+    #[prop(optional, into)] title: Option<String>,
+    #[prop(optional)] height: Option<u32>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let height = height.unwrap_or(260);
+    let palette = DesignSystem::default().colors;
+    let container_classes = cn(&["w-full space-y-2", class.unwrap_or("")]);
+
+    let chart = move || {
+        let readings = readings.get();
+        let events = events.map(|events| events.get()).unwrap_or_default();
+        let Some(first) = readings.first() else {
+            return empty_chart(height);
+        };
+        let name = title.clone().unwrap_or_else(|| first.name().to_string());
+        let unit = first.unit().to_string();
+        let target_unit = first.quantity().map(|quantity| quantity.unit);
+        let (low, high) = normal_range
+            .or_else(|| first.normal_range().as_deref().and_then(parse_normal_range))
+            .unwrap_or((None, None));
+
+        let points: Vec<(String, f64)> = readings
+            .iter()
+            .filter_map(|r| reading_value(r, &unit, target_unit).map(|value| (r.last_updated.clone(), value)))
+            .collect();
+        let Some((auto_min, auto_max)) = value_range(points.iter().map(|(_, v)| *v).chain(low).chain(high)) else {
+            return empty_chart(height);
+        };
+        let frame = ChartFrame::new(600.0, height as f64, (auto_min, auto_max));
+
+        // Time scale when every reading has a parseable timestamp, otherwise evenly spaced
+        let times: Option<Vec<i64>> = points
+            .iter()
+            .map(|(at, _)| parse_timestamp(at).map(|t| t.and_utc().timestamp()))
+            .collect();
+        let span = times.as_ref().and_then(|times| Some((*times.iter().min()?, *times.iter().max()?)));
+        let x_at = |index: usize, time: Option<i64>| match (time, span) {
+            (Some(t), Some((start, end))) if end > start => frame.x_ratio((t - start) as f64 / (end - start) as f64),
+            _ => frame.x(index, points.len()),
+        };
+        let coords: Vec<(f64, f64)> = points
+            .iter()
+            .enumerate()
+            .map(|(i, (_, value))| (x_at(i, times.as_ref().map(|t| t[i])), frame.y(*value)))
+            .collect();
+
+        let band = (low.is_some() || high.is_some()).then(|| {
+            let top = frame.y(high.unwrap_or(frame.max).min(frame.max));
+            let bottom = frame.y(low.unwrap_or(frame.min).max(frame.min));
+            let bounds = [low, high]
+                .into_iter()
+                .flatten()
+                .map(|bound| {
+                    let y = frame.y(bound);
+                    view! {
+                        <line x1=frame.left x2=frame.width - frame.right y1=y y2=y stroke=palette.success stroke-opacity="0.6" stroke-dasharray="4 4" />
+                    }
+                })
+                .collect_view();
+            view! {
+                <g aria-hidden="true">
+                    <rect x=frame.left y=top width=frame.plot_width() height=(bottom - top).max(0.0) fill=palette.success fill-opacity="0.1" />
+                    {bounds}
+                </g>
+            }
+        });
+
+        let annotations = span.map(|(start, end)| {
+            events
+                .iter()
+                .filter_map(|event| {
+                    let t = parse_timestamp(&event.at)?.and_utc().timestamp();
+                    if t < start || t > end {
+                        return None;
+                    }
+                    let x = if end > start { frame.x_ratio((t - start) as f64 / (end - start) as f64) } else { frame.x(0, 1) };
+                    let tooltip = format!("{} – {}", event.at, event.label);
+                    Some(view! {
+                        <g>
+                            <line x1=x x2=x y1=frame.top y2=frame.height - frame.bottom stroke="currentColor" stroke-opacity="0.3" stroke-dasharray="2 3" />
+                            <text x=x y=frame.top + 10.0 text-anchor="middle" font-size="11">{event.kind.marker()}</text>
+                            <title>{tooltip}</title>
+                        </g>
+                    })
+                })
+                .collect_view()
+        });
+
+        let is_abnormal = |value: f64| low.is_some_and(|low| value < low) || high.is_some_and(|high| value > high);
+        let markers = points
+            .iter()
+            .zip(coords.iter())
+            .map(|((at, value), (x, y))| {
+                let abnormal = is_abnormal(*value);
+                let tooltip = format!(
+                    "{}: {} {}{}",
+                    at,
+                    format_tick(*value),
+                    unit,
+                    if abnormal { " (outside normal range)" } else { "" }
+                );
+                view! {
+                    <circle
+                        cx=*x
+                        cy=*y
+                        r=if abnormal { "5" } else { "3" }
+                        fill=if abnormal { palette.emergency } else { palette.primary }
+                        stroke=if abnormal { "white" } else { "none" }
+                        stroke-width="1.5"
+                        data-abnormal=abnormal.to_string()
+                    >
+                        <title>{tooltip}</title>
+                    </circle>
+                }
+            })
+            .collect_view();
+
+        let labels: Vec<String> = points.iter().map(|(at, _)| at.chars().take(10).collect()).collect();
+        let abnormal_count = points.iter().filter(|(_, v)| is_abnormal(*v)).count();
+        let summary = format!(
+            "{} trend, {} readings{}",
+            name,
+            points.len(),
+            if abnormal_count > 0 { format!(", {} outside normal range", abnormal_count) } else { String::new() }
+        );
+
+        view! {
+            <svg viewBox=format!("0 0 {} {}", frame.width, frame.height) class="w-full text-foreground" role="img" aria-label=summary>
+                {band}
+                {y_axis(frame)}
+                {x_axis(frame, &labels, 6)}
+                {annotations}
+                <path d=line_path(&coords) fill="none" stroke=palette.primary stroke-width="2" stroke-linejoin="round" stroke-linecap="round" />
+                {markers}
+            </svg>
+            <figcaption class="flex items-center justify-between text-xs text-muted-foreground">
+                <span>{format!("{} ({})", name, unit)}</span>
+                {(low.is_some() || high.is_some()).then(|| {
+                    let range = match (low, high) {
+                        (Some(low), Some(high)) => format!("Normal {}–{}", format_tick(low), format_tick(high)),
+                        (Some(low), None) => format!("Normal > {}", format_tick(low)),
+                        (None, Some(high)) => format!("Normal < {}", format_tick(high)),
+                        (None, None) => String::new(),
+                    };
+                    view! { <span>{range}</span> }
+                })}
+            </figcaption>
+        }
+        .into_any()
+    };
+
+    view! { <figure class=container_classes>{chart}</figure> }
+}
@@ -0,0 +1,308 @@
+use leptos::prelude::*;
+use crate::errors::{SharedError, SharedResult};
+use crate::ui::cn;
+use crate::utils::clinical::{self, BmiCategory, CkdStage, Sex};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+impl UnitSystem {
+    fn weight_unit(&self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "kg",
+            UnitSystem::Imperial => "lb",
+        }
+    }
+
+    fn height_unit(&self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "cm",
+            UnitSystem::Imperial => "in",
+        }
+    }
+
+    fn weight_kg(&self, value: f64) -> f64 {
+        match self {
+            UnitSystem::Metric => value,
+            UnitSystem::Imperial => clinical::lb_to_kg(value),
+        }
+    }
+
+    fn height_cm(&self, value: f64) -> f64 {
+        match self {
+            UnitSystem::Metric => value,
+            UnitSystem::Imperial => clinical::inches_to_cm(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CreatinineUnit {
+    MgPerDl,
+    UmolPerL,
+}
+
+impl CreatinineUnit {
+    fn label(&self) -> &'static str {
+        match self {
+            CreatinineUnit::MgPerDl => "mg/dL",
+            CreatinineUnit::UmolPerL => "µmol/L",
+        }
+    }
+}
+
+/// `None` until every field has a value; validation errors carry the message to show
+type CalculatorResult = Option<Result<f64, String>>;
+
+fn parse(input: RwSignal<String>) -> Option<f64> {
+    input.with(|value| value.trim().parse().ok())
+}
+
+fn to_result(result: SharedResult<f64>) -> Result<f64, String> {
+    result.map_err(|err| match err {
+        SharedError::ValidationError(message) => message,
+        other => other.to_string(),
+    })
+}
+
+fn number_field(label: &'static str, value: RwSignal<String>, unit: impl Fn() -> &'static str + Send + Sync + 'static) -> impl IntoView {
+    let id = format!("calc-{}", uuid::Uuid::new_v4().simple());
+    view! {
+        <div class="space-y-1">
+            <label for=id.clone() class="text-sm font-medium">{label}</label>
+            <div class="flex items-center rounded-md border border-input bg-background focus-within:ring-2 focus-within:ring-ring">
+                <input
+                    id=id
+                    type="number"
+                    inputmode="decimal"
+                    min="0"
+                    step="any"
+                    class="h-9 w-full rounded-md bg-transparent px-3 text-sm focus:outline-none"
+                    prop:value=move || value.get()
+                    on:input=move |ev| value.set(event_target_value(&ev))
+                />
+                <span class="shrink-0 pr-3 text-xs text-muted-foreground">{unit}</span>
+            </div>
+        </div>
+    }
+}
+
+fn segmented<T: Copy + PartialEq + Send + Sync + 'static>(
+    label: &'static str,
+    selected: RwSignal<T>,
+    options: Vec<(T, &'static str)>,
+) -> impl IntoView {
+    let buttons = options
+        .into_iter()
+        .map(|(value, text)| {
+            view! {
+                <button
+                    type="button"
+                    role="radio"
+                    aria-checked=move || (selected.get() == value).to_string()
+                    class=move || cn(&[
+                        "rounded-sm px-2.5 py-1 text-xs font-medium transition-colors",
+                        if selected.get() == value { "bg-background text-foreground shadow-sm" } else { "text-muted-foreground hover:text-foreground" },
+                    ])
+                    on:click=move |_| selected.set(value)
+                >
+                    {text}
+                </button>
+            }
+        })
+        .collect_view();
+    view! {
+        <div role="radiogroup" aria-label=label class="inline-flex rounded-md bg-muted p-0.5">{buttons}</div>
+    }
+}
+
+fn unit_toggle(units: RwSignal<UnitSystem>) -> impl IntoView {
+    segmented("Units", units, vec![(UnitSystem::Metric, "Metric"), (UnitSystem::Imperial, "Imperial")])
+}
+
+fn result_panel(
+    result: Memo<CalculatorResult>,
+    unit: &'static str,
+    decimals: usize,
+    interpret: impl Fn(f64) -> String + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <div class="min-h-[3.5rem] rounded-md bg-muted/50 p-3" aria-live="polite">
+            {move || match result.get() {
+                None => view! { <p class="text-sm text-muted-foreground">"Enter values to calculate"</p> }.into_any(),
+                Some(Err(message)) => view! { <p class="text-sm text-destructive" role="alert">{message}</p> }.into_any(),
+                Some(Ok(value)) => view! {
+                    <p class="text-2xl font-bold">
+                        {format!("{:.*}", decimals, value)}
+                        <span class="ml-1 text-sm font-normal text-muted-foreground">{unit}</span>
+                    </p>
+                    <p class="text-sm text-muted-foreground">{interpret(value)}</p>
+                }
+                .into_any(),
+            }}
+        </div>
+    }
+}
+
+fn card(title: &'static str, class: Option<&'static str>, body: impl IntoView) -> impl IntoView {
+    view! {
+        <section class=cn(&["w-full space-y-3 rounded-lg border bg-card p-4 text-card-foreground shadow-sm", class.unwrap_or("")]) aria-label=title>
+            {body}
+        </section>
+    }
+}
+
+fn notify(result: Memo<CalculatorResult>, on_result: Option<Callback<f64>>) {
+    if let Some(callback) = on_result {
+        Effect::new(move |_| {
+            if let Some(Ok(value)) = result.get() {
+                callback.run(value);
+            }
+        });
+    }
+}
+
+/// Body mass index with WHO category
+#[component]
+pub fn BmiCalculator(
+    #[prop(optional)] units: Option<UnitSystem>,
+    #[prop(optional)] on_result: Option<Callback<f64>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let units = RwSignal::new(units.unwrap_or(UnitSystem::Metric));
+    let weight = RwSignal::new(String::new());
+    let height = RwSignal::new(String::new());
+
+    let result = Memo::new(move |_| {
+        let (weight, height) = (parse(weight)?, parse(height)?);
+        let units = units.get();
+        Some(to_result(clinical::bmi(units.weight_kg(weight), units.height_cm(height))))
+    });
+    notify(result, on_result);
+
+    card("BMI calculator", class, view! {
+        <div class="flex items-center justify-between">
+            <h3 class="font-semibold">"BMI"</h3>
+            {unit_toggle(units)}
+        </div>
+        <div class="grid grid-cols-2 gap-3">
+            {number_field("Weight", weight, move || units.get().weight_unit())}
+            {number_field("Height", height, move || units.get().height_unit())}
+        </div>
+        {result_panel(result, "kg/m²", 1, |bmi| BmiCategory::from_bmi(bmi).label().to_string())}
+    })
+}
+
+/// Body surface area (Mosteller), e.g. for chemotherapy dosing
+#[component]
+pub fn BsaCalculator(
+    #[prop(optional)] units: Option<UnitSystem>,
+    #[prop(optional)] on_result: Option<Callback<f64>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let units = RwSignal::new(units.unwrap_or(UnitSystem::Metric));
+    let weight = RwSignal::new(String::new());
+    let height = RwSignal::new(String::new());
+
+    let result = Memo::new(move |_| {
+        let (weight, height) = (parse(weight)?, parse(height)?);
+        let units = units.get();
+        Some(to_result(clinical::bsa(units.weight_kg(weight), units.height_cm(height))))
+    });
+    notify(result, on_result);
+
+    card("BSA calculator", class, view! {
+        <div class="flex items-center justify-between">
+            <h3 class="font-semibold">"Body surface area"</h3>
+            {unit_toggle(units)}
+        </div>
+        <div class="grid grid-cols-2 gap-3">
+            {number_field("Weight", weight, move || units.get().weight_unit())}
+            {number_field("Height", height, move || units.get().height_unit())}
+        </div>
+        {result_panel(result, "m²", 2, |_| "Mosteller formula".to_string())}
+    })
+}
+
+/// Weight-based (pediatric) dose, optionally capped at a maximum dose
+#[component]
+pub fn DosageCalculator(
+    #[prop(optional)] units: Option<UnitSystem>,
+    /// Prefill mg/kg, e.g. from a medication's dosing guideline
+    #[prop(optional)] mg_per_kg: Option<f64>,
+    #[prop(optional)] max_dose_mg: Option<f64>,
+    #[prop(optional)] on_result: Option<Callback<f64>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let units = RwSignal::new(units.unwrap_or(UnitSystem::Metric));
+    let weight = RwSignal::new(String::new());
+    let dose = RwSignal::new(mg_per_kg.map(|d| d.to_string()).unwrap_or_default());
+    let max_dose = RwSignal::new(max_dose_mg.map(|d| d.to_string()).unwrap_or_default());
+
+    let result = Memo::new(move |_| {
+        let (weight, dose) = (parse(weight)?, parse(dose)?);
+        Some(to_result(clinical::weight_based_dose(units.get().weight_kg(weight), dose, parse(max_dose))))
+    });
+    notify(result, on_result);
+
+    let capped = move |value: f64| match parse(max_dose) {
+        Some(max) if max > 0.0 && value >= max => "Capped at maximum dose".to_string(),
+        _ => "Per dose".to_string(),
+    };
+
+    card("Dosage calculator", class, view! {
+        <div class="flex items-center justify-between">
+            <h3 class="font-semibold">"Dose by weight"</h3>
+            {unit_toggle(units)}
+        </div>
+        <div class="grid grid-cols-3 gap-3">
+            {number_field("Weight", weight, move || units.get().weight_unit())}
+            {number_field("Dose", dose, || "mg/kg")}
+            {number_field("Max dose", max_dose, || "mg")}
+        </div>
+        {result_panel(result, "mg", 1, capped)}
+        <p class="text-xs text-muted-foreground">"Always check against the product's dosing guidance."</p>
+    })
+}
+
+/// Estimated GFR (CKD-EPI 2021) with KDIGO stage
+#[component]
+pub fn GfrCalculator(
+    #[prop(optional)] creatinine_unit: Option<CreatinineUnit>,
+    #[prop(optional)] on_result: Option<Callback<f64>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let creatinine_unit = RwSignal::new(creatinine_unit.unwrap_or(CreatinineUnit::MgPerDl));
+    let creatinine = RwSignal::new(String::new());
+    let age = RwSignal::new(String::new());
+    let sex = RwSignal::new(Sex::Female);
+
+    let result = Memo::new(move |_| {
+        let (creatinine, age) = (parse(creatinine)?, parse(age)?);
+        let creatinine = match creatinine_unit.get() {
+            CreatinineUnit::MgPerDl => creatinine,
+            CreatinineUnit::UmolPerL => clinical::creatinine_umol_to_mg_dl(creatinine),
+        };
+        Some(to_result(clinical::egfr(creatinine, age.max(0.0) as u32, sex.get())))
+    });
+    notify(result, on_result);
+
+    card("eGFR calculator", class, view! {
+        <div class="flex items-center justify-between">
+            <h3 class="font-semibold">"eGFR"</h3>
+            {segmented("Creatinine unit", creatinine_unit, vec![(CreatinineUnit::MgPerDl, "mg/dL"), (CreatinineUnit::UmolPerL, "µmol/L")])}
+        </div>
+        <div class="grid grid-cols-2 gap-3">
+            {number_field("Serum creatinine", creatinine, move || creatinine_unit.get().label())}
+            {number_field("Age", age, || "years")}
+        </div>
+        <div class="flex items-center gap-3">
+            <span class="text-sm font-medium">"Sex"</span>
+            {segmented("Sex", sex, vec![(Sex::Female, "Female"), (Sex::Male, "Male")])}
+        </div>
+        {result_panel(result, "mL/min/1.73m²", 0, |egfr| CkdStage::from_egfr(egfr).label().to_string())}
+    })
+}
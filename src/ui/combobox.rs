@@ -0,0 +1,301 @@
+use leptos::prelude::*;
+use futures::future::{FutureExt, LocalBoxFuture};
+use std::sync::Arc;
+use std::time::Duration;
+use wasm_bindgen_futures::spawn_local;
+use crate::api_client::{ApiClient, SearchQuery};
+use crate::ui::{cn, SelectOption};
+
+/// Async source of suggestions for a query string
+pub type SuggestionLoader = Arc<dyn Fn(String) -> LocalBoxFuture<'static, Result<Vec<SelectOption>, String>>>;
+
+/// ICD-10 codes, shown as "J45.909 — Unspecified asthma"
+pub fn icd10_suggestions(client: ApiClient) -> SuggestionLoader {
+    Arc::new(move |query| {
+        let client = client.clone();
+        async move {
+            let codes = client.search_icd10_codes(&query).await?;
+            Ok(codes
+                .into_iter()
+                .map(|c| SelectOption::new(c.code.clone(), c.code).with_description(c.description))
+                .collect())
+        }
+        .boxed_local()
+    })
+}
+
+/// Medication names with strength and dose form as the description
+pub fn medication_suggestions(client: ApiClient) -> SuggestionLoader {
+    Arc::new(move |query| {
+        let client = client.clone();
+        async move {
+            let medications = client.search_medications(&query).await?;
+            Ok(medications
+                .into_iter()
+                .map(|m| {
+                    let detail = [m.strength, m.dose_form].into_iter().flatten().collect::<Vec<_>>().join(" ");
+                    let option = SelectOption::new(m.id, m.name);
+                    if detail.is_empty() { option } else { option.with_description(detail) }
+                })
+                .collect())
+        }
+        .boxed_local()
+    })
+}
+
+/// Providers matching the query by name or specialty, best matches first
+pub fn provider_suggestions(client: ApiClient) -> SuggestionLoader {
+    Arc::new(move |query| {
+        let client = client.clone();
+        async move {
+            let results = client.search_providers(SearchQuery::new().text(&query).page(1, 20)).await?;
+            Ok(results
+                .into_iter()
+                .map(|r| {
+                    SelectOption::new(r.provider.id.into_inner(), r.provider.name)
+                        .with_description(r.provider.specialization.join(", "))
+                })
+                .collect())
+        }
+        .boxed_local()
+    })
+}
+
+/// Text input with suggestions loaded as the user types.
+///
+/// Requests are debounced and responses to superseded queries are dropped, so
+/// slow lookups never overwrite newer results.
+#[component]
+pub fn Combobox(
+    loader: SuggestionLoader,
+    value: RwSignal<Option<SelectOption>>,
+    /// Wait after the last keystroke before loading, in milliseconds (default: 250)
+    #[prop(optional)] debounce_ms: Option<u64>,
+    /// Minimum query length before loading (default: 2)
+    #[prop(optional)] min_chars: Option<usize>,
+    #[prop(optional, into)] placeholder: Option<String>,
+    /// Accessible name when there is no visible `<label>`
+    #[prop(optional, into)] label: Option<String>,
+    #[prop(optional)] on_select: Option<Callback<SelectOption>>,
+    #[prop(optional)] id: Option<&'static str>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let debounce = Duration::from_millis(debounce_ms.unwrap_or(250));
+    let min_chars = min_chars.unwrap_or(2);
+    let listbox_id = format!("combobox-{}", uuid::Uuid::new_v4().simple());
+
+    let query = RwSignal::new(value.get_untracked().map(|v| v.label).unwrap_or_default());
+    let suggestions = RwSignal::new(Vec::<SelectOption>::new());
+    let open = RwSignal::new(false);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let highlighted = RwSignal::new(None::<usize>);
+    // Incremented per keystroke; timers and responses for older values are ignored
+    let generation = RwSignal::new(0u32);
+
+    let search = move |text: String| {
+        let token = generation.get_untracked().wrapping_add(1);
+        generation.set(token);
+        highlighted.set(None);
+        error.set(None);
+
+        if text.trim().chars().count() < min_chars {
+            suggestions.set(Vec::new());
+            loading.set(false);
+            open.set(false);
+            return;
+        }
+
+        let loader = loader.clone();
+        set_timeout(
+            move || {
+                if generation.get_untracked() != token {
+                    return;
+                }
+                loading.set(true);
+                open.set(true);
+                spawn_local(async move {
+                    let result = loader(text.trim().to_string()).await;
+                    if generation.get_untracked() != token {
+                        return;
+                    }
+                    loading.set(false);
+                    match result {
+                        Ok(options) => suggestions.set(options),
+                        Err(message) => {
+                            suggestions.set(Vec::new());
+                            error.set(Some(message));
+                        }
+                    }
+                });
+            },
+            debounce,
+        );
+    };
+
+    let close = move || {
+        open.set(false);
+        highlighted.set(None);
+    };
+
+    let choose = move |option: SelectOption| {
+        // Invalidate in-flight lookups so they cannot reopen the list
+        generation.update(|token| *token = token.wrapping_add(1));
+        query.set(option.label.clone());
+        value.set(Some(option.clone()));
+        loading.set(false);
+        close();
+        if let Some(callback) = on_select {
+            callback.run(option);
+        }
+    };
+
+    let step = move |delta: isize| {
+        let options = suggestions.get_untracked();
+        let len = options.len() as isize;
+        if len == 0 {
+            return;
+        }
+        let mut index = highlighted.get_untracked().map(|i| i as isize).unwrap_or(if delta > 0 { -1 } else { len });
+        for _ in 0..len {
+            index = (index + delta).rem_euclid(len);
+            if !options[index as usize].disabled {
+                highlighted.set(Some(index as usize));
+                return;
+            }
+        }
+    };
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| match ev.key().as_str() {
+        "ArrowDown" => {
+            ev.prevent_default();
+            open.set(!suggestions.with_untracked(Vec::is_empty));
+            step(1);
+        }
+        "ArrowUp" => {
+            ev.prevent_default();
+            step(-1);
+        }
+        "Enter" if open.get_untracked() => {
+            ev.prevent_default();
+            let option = highlighted
+                .get_untracked()
+                .and_then(|index| suggestions.with_untracked(|options| options.get(index).cloned()));
+            if let Some(option) = option {
+                choose(option);
+            }
+        }
+        "Escape" => {
+            if open.get_untracked() {
+                ev.prevent_default();
+                close();
+            } else if !query.get_untracked().is_empty() {
+                ev.prevent_default();
+                query.set(String::new());
+                value.set(None);
+            }
+        }
+        "Tab" => close(),
+        _ => {}
+    };
+
+    let option_id = {
+        let listbox_id = listbox_id.clone();
+        move |index: usize| format!("{}-option-{}", listbox_id, index)
+    };
+    let active_descendant = {
+        let option_id = option_id.clone();
+        move || highlighted.get().filter(|_| open.get()).map(&option_id)
+    };
+
+    let option_list = move || {
+        if loading.get() {
+            return view! { <li class="px-2 py-1.5 text-sm text-muted-foreground" role="status">"Searching…"</li> }.into_any();
+        }
+        if let Some(message) = error.get() {
+            return view! { <li class="px-2 py-1.5 text-sm text-destructive" role="alert">{message}</li> }.into_any();
+        }
+        let options = suggestions.get();
+        if options.is_empty() {
+            return view! { <li class="px-2 py-1.5 text-sm text-muted-foreground">"No matches"</li> }.into_any();
+        }
+        options
+            .into_iter()
+            .enumerate()
+            .map(|(index, option)| {
+                let disabled = option.disabled;
+                let option_classes = move || {
+                    cn(&[
+                        "flex w-full cursor-default select-none flex-col rounded-sm px-2 py-1.5 text-sm outline-none",
+                        if highlighted.get() == Some(index) { "bg-accent text-accent-foreground" } else { "" },
+                        if disabled { "pointer-events-none opacity-50" } else { "cursor-pointer" },
+                    ])
+                };
+                let label = option.label.clone();
+                let description = option.description.clone();
+                view! {
+                    <li
+                        role="option"
+                        id=option_id(index)
+                        aria-selected=move || (highlighted.get() == Some(index)).to_string()
+                        aria-disabled=disabled.to_string()
+                        class=option_classes
+                        on:mouseenter=move |_| highlighted.set(Some(index))
+                        on:mousedown=|ev| ev.prevent_default()
+                        on:click=move |_| if !disabled { choose(option.clone()) }
+                    >
+                        <span>{label}</span>
+                        {description.map(|description| view! {
+                            <span class="text-xs text-muted-foreground">{description}</span>
+                        })}
+                    </li>
+                }
+            })
+            .collect_view()
+            .into_any()
+    };
+
+    let input_classes = cn(&[
+        "flex h-10 w-full rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2 disabled:cursor-not-allowed disabled:opacity-50",
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <div class="relative w-full">
+            <input
+                type="text"
+                id=id
+                class=input_classes
+                role="combobox"
+                autocomplete="off"
+                aria-autocomplete="list"
+                aria-expanded=move || open.get().to_string()
+                aria-controls=listbox_id.clone()
+                aria-activedescendant=active_descendant
+                aria-busy=move || loading.get().to_string()
+                aria-label=label
+                placeholder=placeholder
+                prop:value=move || query.get()
+                on:input=move |ev| {
+                    let text = event_target_value(&ev);
+                    query.set(text.clone());
+                    if value.get_untracked().is_some() {
+                        value.set(None);
+                    }
+                    search(text);
+                }
+                on:keydown=on_keydown
+                on:blur=move |_| close()
+            />
+            <Show when=move || open.get()>
+                <ul
+                    id=listbox_id.clone()
+                    role="listbox"
+                    class="absolute z-50 mt-1 max-h-60 w-full overflow-y-auto rounded-md border bg-popover p-1 text-popover-foreground shadow-md"
+                >
+                    {option_list.clone()}
+                </ul>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,538 @@
+use leptos::prelude::*;
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::sync::Arc;
+use crate::ui::{activate_on_key, cn, page_count, use_i18n, FOCUS_RING, Button, ButtonSize, ButtonVariant, EmptyState, EmptyStatePreset, FilterState, Pagination};
+
+/// Comparable value extracted from a row for sorting
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum SortValue {
+    Empty,
+    Number(f64),
+    Text(String),
+    DateTime(DateTime<Utc>),
+}
+
+impl SortValue {
+    /// Case-insensitive text value
+    pub fn text(value: impl AsRef<str>) -> Self {
+        SortValue::Text(value.as_ref().to_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub fn toggled(&self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+
+    pub fn aria_sort(&self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "ascending",
+            SortDirection::Descending => "descending",
+        }
+    }
+
+    fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    }
+}
+
+type CellRenderer<T> = Arc<dyn Fn(&T) -> AnyView + Send + Sync>;
+type SortKey<T> = Arc<dyn Fn(&T) -> SortValue + Send + Sync>;
+
+/// Column definition for [`DataTable`]
+pub struct TableColumn<T> {
+    pub id: &'static str,
+    pub header: String,
+    pub cell: CellRenderer<T>,
+    pub sort_by: Option<SortKey<T>>,
+    pub class: &'static str,
+}
+
+impl<T> Clone for TableColumn<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            header: self.header.clone(),
+            cell: self.cell.clone(),
+            sort_by: self.sort_by.clone(),
+            class: self.class,
+        }
+    }
+}
+
+impl<T: 'static> TableColumn<T> {
+    pub fn new(
+        id: &'static str,
+        header: impl Into<String>,
+        cell: impl Fn(&T) -> AnyView + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            id,
+            header: header.into(),
+            cell: Arc::new(cell),
+            sort_by: None,
+            class: "",
+        }
+    }
+
+    /// Plain text column, sortable by its text
+    pub fn text(
+        id: &'static str,
+        header: impl Into<String>,
+        value: impl Fn(&T) -> String + Send + Sync + 'static,
+    ) -> Self {
+        let value = Arc::new(value);
+        let sort_value = value.clone();
+        Self::new(id, header, move |row: &T| value(row).into_any())
+            .sortable(move |row: &T| SortValue::text(sort_value(row)))
+    }
+
+    pub fn sortable(mut self, sort_by: impl Fn(&T) -> SortValue + Send + Sync + 'static) -> Self {
+        self.sort_by = Some(Arc::new(sort_by));
+        self
+    }
+
+    pub fn with_class(mut self, class: &'static str) -> Self {
+        self.class = class;
+        self
+    }
+}
+
+/// Default row filter: the free text against the columns' text sort values
+fn matches_text_columns<T>(columns: &[TableColumn<T>], row: &T, filters: &FilterState) -> bool {
+    let texts: Vec<String> = columns
+        .iter()
+        .filter_map(|column| column.sort_by.as_ref())
+        .filter_map(|sort_by| match sort_by(row) {
+            SortValue::Text(text) => Some(text),
+            _ => None,
+        })
+        .collect();
+    filters.matches_text(&texts.iter().map(String::as_str).collect::<Vec<_>>())
+}
+
+/// Action applied to every selected row, e.g. "Deactivate providers"
+pub struct BulkAction<T: 'static> {
+    pub label: String,
+    pub variant: ButtonVariant,
+    pub on_action: Callback<Vec<T>>,
+}
+
+impl<T: 'static> Clone for BulkAction<T> {
+    fn clone(&self) -> Self {
+        Self {
+            label: self.label.clone(),
+            variant: self.variant,
+            on_action: self.on_action,
+        }
+    }
+}
+
+impl<T: 'static> BulkAction<T> {
+    pub fn new(label: impl Into<String>, on_action: Callback<Vec<T>>) -> Self {
+        Self {
+            label: label.into(),
+            variant: ButtonVariant::Outline,
+            on_action,
+        }
+    }
+
+    pub fn destructive(mut self) -> Self {
+        self.variant = ButtonVariant::Destructive;
+        self
+    }
+}
+
+/// Sortable, filterable, paginated table with optional row selection and bulk actions
+#[component]
+pub fn DataTable<T>(
+    #[prop(into)] rows: Signal<Vec<T>>,
+    columns: Vec<TableColumn<T>>,
+    /// Stable identifier used for selection and keyed rendering
+    row_key: fn(&T) -> String,
+    #[prop(optional)] on_row_click: Option<Callback<T>>,
+    #[prop(optional, into)] loading: Option<Signal<bool>>,
+    #[prop(optional, into)] empty_message: Option<String>,
+    /// Filters from a `FilterBar`. Without `filter_row` only the free text is
+    /// applied, against the text of sortable columns.
+    #[prop(optional, into)] filters: Option<Signal<FilterState>>,
+    #[prop(optional)] filter_row: Option<fn(&T, &FilterState) -> bool>,
+    /// Icon and description for the empty table; `empty_message` still sets the title
+    #[prop(optional)] empty_preset: Option<EmptyStatePreset>,
+    /// Rows per page; 0 disables pagination (default: 10)
+    #[prop(optional)] page_size: Option<usize>,
+    /// Choices for the rows-per-page selector; hidden when not set
+    #[prop(optional)] page_size_options: Option<Vec<u32>>,
+    #[prop(optional)] selectable: Option<bool>,
+    #[prop(optional)] bulk_actions: Vec<BulkAction<T>>,
+    #[prop(optional)] on_selection_change: Option<Callback<Vec<T>>>,
+    #[prop(optional)] sticky_header: Option<bool>,
+    #[prop(optional)] caption: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let columns = Arc::new(columns);
+    let bulk_actions = Arc::new(bulk_actions);
+    let page_size = RwSignal::new(page_size.unwrap_or(10) as u32);
+    let has_size_options = page_size_options.as_ref().is_some_and(|options| !options.is_empty());
+    let selectable = selectable.unwrap_or(false);
+    let sticky_header = sticky_header.unwrap_or(true);
+    let i18n = use_i18n();
+    let empty_title = move || {
+        empty_message.clone().unwrap_or_else(|| match empty_preset {
+            Some(preset) => i18n.t(preset.title_key()),
+            None => i18n.t("table.no_records"),
+        })
+    };
+    let empty_preset = empty_preset.unwrap_or(EmptyStatePreset::NoResults);
+    let is_loading = move || loading.map(|l| l.get()).unwrap_or(false);
+    let column_count = columns.len() + usize::from(selectable);
+
+    let sort = RwSignal::new(None::<(usize, SortDirection)>);
+    // 1-based, as in `Pagination`
+    let page = RwSignal::new(1u32);
+    let selected = RwSignal::new(HashSet::<String>::new());
+
+    let sort_columns = columns.clone();
+    let sorted_rows = Signal::derive(move || {
+        let mut rows = rows.get();
+        if let Some(filters) = filters {
+            filters.with(|filters| {
+                if !filters.is_empty() {
+                    rows.retain(|row| match filter_row {
+                        Some(filter_row) => filter_row(row, filters),
+                        None => matches_text_columns(&sort_columns, row, filters),
+                    });
+                }
+            });
+        }
+        if let Some((index, direction)) = sort.get() {
+            if let Some(sort_by) = sort_columns.get(index).and_then(|c| c.sort_by.as_ref()) {
+                rows.sort_by(|a, b| {
+                    direction.apply(sort_by(a).partial_cmp(&sort_by(b)).unwrap_or(Ordering::Equal))
+                });
+            }
+        }
+        rows
+    });
+
+    // New filters start over from the first page
+    Effect::new(move |_| {
+        if let Some(filters) = filters {
+            filters.track();
+            page.set(1);
+        }
+    });
+
+    let total = Signal::derive(move || sorted_rows.with(|rows| rows.len() as u64));
+    let pages = Signal::derive(move || page_count(total.get(), page_size.get()));
+
+    let page_rows = Signal::derive(move || {
+        let rows = sorted_rows.get();
+        let size = page_size.get() as usize;
+        if size == 0 {
+            return rows;
+        }
+        let current = page.get().clamp(1, pages.get()) as usize - 1;
+        rows.into_iter()
+            .skip(current * size)
+            .take(size)
+            .collect::<Vec<_>>()
+    });
+
+    let selected_rows = move || {
+        selected.with(|keys| {
+            rows.get()
+                .into_iter()
+                .filter(|row| keys.contains(&row_key(row)))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let notify_selection = move || {
+        if let Some(callback) = on_selection_change {
+            callback.run(selected_rows());
+        }
+    };
+
+    let toggle_row = move |key: String| {
+        selected.update(|keys| {
+            if !keys.remove(&key) {
+                keys.insert(key);
+            }
+        });
+        notify_selection();
+    };
+
+    let all_page_selected = move || {
+        let keys: Vec<String> = page_rows.with(|rows| rows.iter().map(row_key).collect());
+        !keys.is_empty() && selected.with(|selected| keys.iter().all(|k| selected.contains(k)))
+    };
+
+    let toggle_page = move || {
+        let keys: Vec<String> = page_rows.with(|rows| rows.iter().map(row_key).collect());
+        let select = !all_page_selected();
+        selected.update(|selected| {
+            for key in keys {
+                if select {
+                    selected.insert(key);
+                } else {
+                    selected.remove(&key);
+                }
+            }
+        });
+        notify_selection();
+    };
+
+    let on_sort = move |index: usize| {
+        sort.update(|sort| {
+            *sort = match *sort {
+                Some((current, direction)) if current == index => Some((index, direction.toggled())),
+                _ => Some((index, SortDirection::Ascending)),
+            };
+        });
+        page.set(1);
+    };
+
+    let header_cells = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let header = column.header.clone();
+            let th_classes = cn(&[
+                "h-12 px-4 text-left align-middle font-medium text-muted-foreground",
+                column.class,
+            ]);
+
+            if column.sort_by.is_some() {
+                let aria_sort = move || match sort.get() {
+                    Some((current, direction)) if current == index => direction.aria_sort(),
+                    _ => "none",
+                };
+                let indicator = move || match sort.get() {
+                    Some((current, SortDirection::Ascending)) if current == index => "▲",
+                    Some((current, SortDirection::Descending)) if current == index => "▼",
+                    _ => "↕",
+                };
+                view! {
+                    <th scope="col" class=th_classes aria-sort=aria_sort>
+                        <button
+                            type="button"
+                            class="inline-flex items-center gap-1 hover:text-foreground focus:outline-none focus-visible:ring-2 focus-visible:ring-ring rounded-sm"
+                            on:click=move |_| on_sort(index)
+                        >
+                            {header}
+                            <span class="text-xs" aria-hidden="true">{indicator}</span>
+                        </button>
+                    </th>
+                }
+                .into_any()
+            } else {
+                view! { <th scope="col" class=th_classes>{header}</th> }.into_any()
+            }
+        })
+        .collect_view();
+
+    let thead_classes = cn(&[
+        "[&_tr]:border-b bg-background",
+        if sticky_header { "sticky top-0 z-10 shadow-sm" } else { "" },
+    ]);
+
+    let body_columns = columns.clone();
+    let body = move || {
+        if is_loading() {
+            return view! {
+                <tr>
+                    <td colspan=column_count class="h-24 text-center text-muted-foreground">
+                        <span class="inline-flex items-center gap-2">
+                            <span class="h-4 w-4 animate-spin rounded-full border-2 border-primary border-t-transparent"></span>
+                            {i18n.t("common.loading")}
+                        </span>
+                    </td>
+                </tr>
+            }
+            .into_any();
+        }
+
+        let rows = page_rows.get();
+        if rows.is_empty() {
+            return view! {
+                <tr>
+                    <td colspan=column_count>
+                        <EmptyState preset=empty_preset title=empty_title() compact=true />
+                    </td>
+                </tr>
+            }
+            .into_any();
+        }
+
+        rows.into_iter()
+            .map(|row| {
+                let key = row_key(&row);
+                let cells = body_columns
+                    .iter()
+                    .map(|column| {
+                        let td_classes = cn(&["p-4 align-middle", column.class]);
+                        view! { <td class=td_classes>{(column.cell)(&row)}</td> }
+                    })
+                    .collect_view();
+
+                let checkbox = selectable.then(|| {
+                    let checked_key = key.clone();
+                    let toggle_key = key.clone();
+                    view! {
+                        <td class="w-10 px-4 align-middle" on:click=|ev| ev.stop_propagation()>
+                            <input
+                                type="checkbox"
+                                class="h-4 w-4 rounded border-input"
+                                aria-label=move || i18n.t("table.select_row")
+                                prop:checked=move || selected.with(|keys| keys.contains(&checked_key))
+                                on:change=move |_| toggle_row(toggle_key.clone())
+                            />
+                        </td>
+                    }
+                });
+
+                let row_selected_key = key.clone();
+                let row_classes = move || {
+                    cn(&[
+                        "border-b transition-colors hover:bg-muted/50",
+                        if on_row_click.is_some() { "cursor-pointer" } else { "" },
+                        if on_row_click.is_some() { FOCUS_RING } else { "" },
+                        if selected.with(|keys| keys.contains(&row_selected_key)) { "bg-muted" } else { "" },
+                    ])
+                };
+                let click_row = row.clone();
+
+                view! {
+                    <tr
+                        class=row_classes
+                        tabindex=on_row_click.map(|_| "0")
+                        on:click=move |_| {
+                            if let Some(callback) = on_row_click {
+                                callback.run(click_row.clone());
+                            }
+                        }
+                        on:keydown=move |ev| {
+                            if let Some(callback) = on_row_click {
+                                if activate_on_key(&ev) {
+                                    callback.run(row.clone());
+                                }
+                            }
+                        }
+                    >
+                        {checkbox}
+                        {cells}
+                    </tr>
+                }
+                .into_any()
+            })
+            .collect_view()
+            .into_any()
+    };
+
+    let bulk_bar = move || {
+        let count = selected_rows().len();
+        (selectable && count > 0).then(|| {
+            let actions = bulk_actions
+                .iter()
+                .cloned()
+                .map(|action| {
+                    let on_action = action.on_action;
+                    view! {
+                        <Button
+                            variant=action.variant
+                            size=ButtonSize::Sm
+                            on_click=Callback::new(move |_| on_action.run(selected_rows()))
+                        >
+                            {action.label.clone()}
+                        </Button>
+                    }
+                })
+                .collect_view();
+
+            view! {
+                <div class="flex items-center justify-between gap-4 rounded-md border bg-muted/50 px-4 py-2" role="region" aria-label="Bulk actions">
+                    <span class="text-sm font-medium">{i18n.t_plural("table.selected", count as u64)}</span>
+                    <div class="flex items-center gap-2">
+                        {actions}
+                        <Button
+                            variant=ButtonVariant::Ghost
+                            size=ButtonSize::Sm
+                            on_click=Callback::new(move |_| {
+                                selected.set(HashSet::new());
+                                notify_selection();
+                            })
+                        >
+                            {i18n.t("common.clear")}
+                        </Button>
+                    </div>
+                </div>
+            }
+        })
+    };
+
+    let page_size_options = page_size_options.unwrap_or_default();
+    let pager = move || {
+        (page_size.get() > 0 && (pages.get() > 1 || has_size_options)).then(|| {
+            view! {
+                <Pagination
+                    page=page
+                    page_size=page_size
+                    total=total
+                    page_size_options=page_size_options.clone()
+                    class="px-2 py-3"
+                />
+            }
+        })
+    };
+
+    let container_classes = cn(&["w-full space-y-2", class.unwrap_or("")]);
+
+    view! {
+        <div class=container_classes>
+            {bulk_bar}
+            <div class="relative w-full overflow-auto rounded-md border max-h-[70vh]">
+                <table class="w-full caption-bottom text-sm" aria-busy=move || is_loading().to_string()>
+                    {caption.map(|caption| view! { <caption class="sr-only">{caption}</caption> })}
+                    <thead class=thead_classes>
+                        <tr>
+                            {selectable.then(|| view! {
+                                <th scope="col" class="w-10 px-4">
+                                    <input
+                                        type="checkbox"
+                                        class="h-4 w-4 rounded border-input"
+                                        aria-label=move || i18n.t("table.select_all")
+                                        prop:checked=all_page_selected
+                                        on:change=move |_| toggle_page()
+                                    />
+                                </th>
+                            })}
+                            {header_cells}
+                        </tr>
+                    </thead>
+                    <tbody class="[&_tr:last-child]:border-0">
+                        {body}
+                    </tbody>
+                </table>
+            </div>
+            {pager}
+        </div>
+    }
+}
@@ -0,0 +1,245 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use crate::ui::{cn, Button, ButtonVariant};
+
+/// Elements that can receive keyboard focus inside a dialog panel
+const FOCUSABLE_SELECTOR: &str = "a[href], button:not([disabled]), input:not([disabled]), select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex='-1'])";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DialogSize {
+    Sm,
+    Default,
+    Lg,
+    Xl,
+    Full,
+}
+
+impl DialogSize {
+    pub fn max_width(&self) -> &'static str {
+        match self {
+            DialogSize::Sm => "max-w-sm",
+            DialogSize::Default => "max-w-lg",
+            DialogSize::Lg => "max-w-2xl",
+            DialogSize::Xl => "max-w-4xl",
+            DialogSize::Full => "max-w-[calc(100vw-2rem)] h-[calc(100vh-2rem)]",
+        }
+    }
+}
+
+fn focusable_elements(container: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+    let Ok(nodes) = container.query_selector_all(FOCUSABLE_SELECTOR) else {
+        return Vec::new();
+    };
+
+    (0..nodes.length())
+        .filter_map(|i| nodes.get(i))
+        .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect()
+}
+
+/// Modal dialog with focus trapping, Escape-to-close and backdrop dismissal.
+///
+/// Focus moves into the panel when it opens and returns to the previously
+/// focused element when it closes.
+#[component]
+pub fn Dialog(
+    #[prop(into)] open: Signal<bool>,
+    on_close: Callback<()>,
+    #[prop(into)] title: String,
+    #[prop(optional, into)] description: Option<String>,
+    #[prop(optional)] size: Option<DialogSize>,
+    /// Close when the backdrop is clicked (default: true)
+    #[prop(optional)] close_on_backdrop: Option<bool>,
+    /// Use `role="alertdialog"` for dialogs that interrupt the user
+    #[prop(optional)] alert: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+    children: ChildrenFn,
+) -> impl IntoView {
+    let size = size.unwrap_or(DialogSize::Default);
+    let close_on_backdrop = close_on_backdrop.unwrap_or(true);
+    let role = if alert.unwrap_or(false) { "alertdialog" } else { "dialog" };
+
+    let dialog_id = format!("dialog-{}", uuid::Uuid::new_v4().simple());
+    let title_id = format!("{}-title", dialog_id);
+    let description_id = description.as_ref().map(|_| format!("{}-description", dialog_id));
+
+    let panel_ref = NodeRef::<leptos::html::Div>::new();
+    let previous_focus = StoredValue::new_local(None::<web_sys::Element>);
+
+    // Remember what had focus so it can be restored on close
+    Effect::new(move |was_open: Option<bool>| {
+        let is_open = open.get();
+        if is_open && was_open != Some(true) {
+            previous_focus.set_value(document().active_element());
+        } else if !is_open && was_open == Some(true) {
+            if let Some(element) = previous_focus.get_value() {
+                if let Ok(element) = element.dyn_into::<web_sys::HtmlElement>() {
+                    let _ = element.focus();
+                }
+            }
+            previous_focus.set_value(None);
+        }
+        is_open
+    });
+
+    // Move focus into the panel once it is mounted
+    Effect::new(move |_| {
+        if let Some(panel) = panel_ref.get() {
+            match focusable_elements(&panel).first() {
+                Some(first) => {
+                    let _ = first.focus();
+                }
+                None => {
+                    let _ = panel.focus();
+                }
+            }
+        }
+    });
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| match ev.key().as_str() {
+        "Escape" => {
+            ev.prevent_default();
+            ev.stop_propagation();
+            on_close.run(());
+        }
+        "Tab" => {
+            let Some(panel) = panel_ref.get() else {
+                return;
+            };
+            let focusable = focusable_elements(&panel);
+            let (Some(first), Some(last)) = (focusable.first(), focusable.last()) else {
+                ev.prevent_default();
+                return;
+            };
+
+            let active = document().active_element();
+            let is_active = |element: &web_sys::HtmlElement| {
+                active.as_ref() == Some(element.unchecked_ref::<web_sys::Element>())
+            };
+
+            if ev.shift_key() && is_active(first) {
+                ev.prevent_default();
+                let _ = last.focus();
+            } else if !ev.shift_key() && is_active(last) {
+                ev.prevent_default();
+                let _ = first.focus();
+            }
+        }
+        _ => {}
+    };
+
+    let panel_classes = cn(&[
+        "relative z-50 grid w-full gap-4 rounded-lg border bg-background p-6 shadow-lg focus:outline-none",
+        size.max_width(),
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="fixed inset-0 z-50 flex items-center justify-center p-4" on:keydown=on_keydown>
+                <div
+                    class="fixed inset-0 bg-black/50 backdrop-blur-sm"
+                    aria-hidden="true"
+                    on:click=move |_| {
+                        if close_on_backdrop {
+                            on_close.run(());
+                        }
+                    }
+                ></div>
+                <div
+                    node_ref=panel_ref
+                    class=panel_classes.clone()
+                    role=role
+                    aria-modal="true"
+                    aria-labelledby=title_id.clone()
+                    aria-describedby=description_id.clone()
+                    tabindex="-1"
+                >
+                    <div class="flex items-start justify-between gap-4">
+                        <h2 id=title_id.clone() class="text-lg font-semibold leading-none tracking-tight">
+                            {title.clone()}
+                        </h2>
+                        <button
+                            type="button"
+                            class="rounded-sm opacity-70 ring-offset-background transition-opacity hover:opacity-100 focus:outline-none focus:ring-2 focus:ring-ring focus:ring-offset-2"
+                            aria-label="Close"
+                            on:click=move |_| on_close.run(())
+                        >
+                            <span aria-hidden="true">"✕"</span>
+                        </button>
+                    </div>
+                    {description.clone().map(|text| view! {
+                        <p id=description_id.clone() class="text-sm text-muted-foreground">{text}</p>
+                    })}
+                    {children()}
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+pub fn DialogFooter(
+    #[prop(optional)] class: Option<&'static str>,
+    children: Children,
+) -> impl IntoView {
+    let footer_classes = cn(&[
+        "flex flex-col-reverse gap-2 sm:flex-row sm:justify-end",
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <div class=footer_classes>
+            {children()}
+        </div>
+    }
+}
+
+/// Confirmation prompt for destructive actions such as cancelling an
+/// appointment or discharging a patient. Escape and backdrop clicks cancel.
+#[component]
+pub fn ConfirmDialog(
+    #[prop(into)] open: Signal<bool>,
+    on_confirm: Callback<()>,
+    on_cancel: Callback<()>,
+    #[prop(into)] title: String,
+    #[prop(into)] message: String,
+    #[prop(optional, into)] confirm_label: Option<String>,
+    #[prop(optional, into)] cancel_label: Option<String>,
+    /// Style the confirm button as destructive (default: true)
+    #[prop(optional)] destructive: Option<bool>,
+) -> impl IntoView {
+    let confirm_label = confirm_label.unwrap_or_else(|| "Confirm".to_string());
+    let cancel_label = cancel_label.unwrap_or_else(|| "Cancel".to_string());
+    let confirm_variant = if destructive.unwrap_or(true) {
+        ButtonVariant::Destructive
+    } else {
+        ButtonVariant::Default
+    };
+
+    view! {
+        <Dialog
+            open=open
+            on_close=on_cancel
+            title=title
+            description=message
+            size=DialogSize::Sm
+            alert=true
+        >
+            <DialogFooter>
+                <Button
+                    variant=ButtonVariant::Outline
+                    on_click=on_cancel
+                >
+                    {cancel_label.clone()}
+                </Button>
+                <Button
+                    variant=confirm_variant
+                    on_click=on_confirm
+                >
+                    {confirm_label.clone()}
+                </Button>
+            </DialogFooter>
+        </Dialog>
+    }
+}
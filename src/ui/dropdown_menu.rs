@@ -0,0 +1,300 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use crate::ui::{cn, Icon, IconSize};
+
+/// Entry in a [`DropdownMenu`]
+#[derive(Clone)]
+pub enum MenuItem {
+    Action {
+        label: String,
+        icon: Option<String>,
+        shortcut: Option<String>,
+        destructive: bool,
+        disabled: bool,
+        on_select: Callback<()>,
+    },
+    Label(String),
+    Separator,
+}
+
+impl std::fmt::Debug for MenuItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MenuItem::Action { label, .. } => f.debug_tuple("Action").field(label).finish(),
+            MenuItem::Label(label) => f.debug_tuple("Label").field(label).finish(),
+            MenuItem::Separator => f.write_str("Separator"),
+        }
+    }
+}
+
+impl MenuItem {
+    pub fn action(label: impl Into<String>, on_select: Callback<()>) -> Self {
+        MenuItem::Action {
+            label: label.into(),
+            icon: None,
+            shortcut: None,
+            destructive: false,
+            disabled: false,
+            on_select,
+        }
+    }
+
+    /// Action styled as dangerous, e.g. "Cancel appointment"
+    pub fn destructive(label: impl Into<String>, on_select: Callback<()>) -> Self {
+        let mut item = Self::action(label, on_select);
+        if let MenuItem::Action { destructive, .. } = &mut item {
+            *destructive = true;
+        }
+        item
+    }
+
+    pub fn label(label: impl Into<String>) -> Self {
+        MenuItem::Label(label.into())
+    }
+
+    pub fn separator() -> Self {
+        MenuItem::Separator
+    }
+
+    /// Icon name from the icon registry; ignored for labels and separators
+    pub fn with_icon(mut self, name: impl Into<String>) -> Self {
+        if let MenuItem::Action { icon, .. } = &mut self {
+            *icon = Some(name.into());
+        }
+        self
+    }
+
+    pub fn with_shortcut(mut self, keys: impl Into<String>) -> Self {
+        if let MenuItem::Action { shortcut, .. } = &mut self {
+            *shortcut = Some(keys.into());
+        }
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        if let MenuItem::Action { disabled, .. } = &mut self {
+            *disabled = true;
+        }
+        self
+    }
+
+    fn is_focusable(&self) -> bool {
+        matches!(self, MenuItem::Action { disabled: false, .. })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MenuAlign {
+    Start,
+    End,
+}
+
+fn focus_element(id: &str) {
+    if let Some(element) = document()
+        .get_element_by_id(id)
+        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        let _ = element.focus();
+    }
+}
+
+/// Actions menu opened from a trigger button, following the WAI-ARIA menu button pattern
+#[component]
+pub fn DropdownMenu(
+    items: Vec<MenuItem>,
+    /// Accessible name of the trigger, e.g. "Appointment actions"
+    #[prop(into)] label: String,
+    #[prop(optional)] align: Option<MenuAlign>,
+    #[prop(optional)] trigger_class: Option<&'static str>,
+    #[prop(optional)] class: Option<&'static str>,
+    /// Trigger content; defaults to a "more" glyph
+    #[prop(optional)] children: Option<Children>,
+) -> impl IntoView {
+    let align = align.unwrap_or(MenuAlign::End);
+    let menu_id = format!("menu-{}", uuid::Uuid::new_v4().simple());
+    let trigger_id = format!("{}-trigger", menu_id);
+    let focusable: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.is_focusable())
+        .map(|(index, _)| index)
+        .collect();
+
+    let open = RwSignal::new(false);
+    let focused = RwSignal::new(None::<usize>);
+    let item_id = {
+        let menu_id = menu_id.clone();
+        move |index: usize| format!("{}-item-{}", menu_id, index)
+    };
+
+    let open_menu = {
+        let focusable = focusable.clone();
+        let item_id = item_id.clone();
+        move |last: bool| {
+            open.set(true);
+            let target = if last { focusable.last() } else { focusable.first() }.copied();
+            focused.set(target);
+            if let Some(index) = target {
+                let id = item_id(index);
+                request_animation_frame(move || focus_element(&id));
+            }
+        }
+    };
+
+    let close_menu = {
+        let trigger_id = trigger_id.clone();
+        move |restore_focus: bool| {
+            open.set(false);
+            focused.set(None);
+            if restore_focus {
+                focus_element(&trigger_id);
+            }
+        }
+    };
+
+    let on_trigger_keydown = {
+        let open_menu = open_menu.clone();
+        move |ev: leptos::ev::KeyboardEvent| match ev.key().as_str() {
+            "ArrowDown" | "Enter" | " " => {
+                ev.prevent_default();
+                open_menu(false);
+            }
+            "ArrowUp" => {
+                ev.prevent_default();
+                open_menu(true);
+            }
+            _ => {}
+        }
+    };
+
+    let on_menu_keydown = {
+        let item_id = item_id.clone();
+        let close_menu = close_menu.clone();
+        let focusable = focusable.clone();
+        move |ev: leptos::ev::KeyboardEvent| {
+            let position = focused.get_untracked().and_then(|f| focusable.iter().position(|&i| i == f));
+            let next = match ev.key().as_str() {
+                "ArrowDown" => position.map(|p| (p + 1) % focusable.len()).or(Some(0)),
+                "ArrowUp" => position.map(|p| (p + focusable.len() - 1) % focusable.len()).or(Some(0)),
+                "Home" => Some(0),
+                "End" => Some(focusable.len().saturating_sub(1)),
+                "Escape" => {
+                    ev.prevent_default();
+                    close_menu(true);
+                    return;
+                }
+                "Tab" => {
+                    close_menu(false);
+                    return;
+                }
+                _ => return,
+            };
+            ev.prevent_default();
+            if let Some(index) = next.and_then(|p| focusable.get(p)).copied() {
+                focused.set(Some(index));
+                focus_element(&item_id(index));
+            }
+        }
+    };
+
+    let rendered_items = items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| match item {
+            MenuItem::Separator => view! {
+                <div role="separator" class="-mx-1 my-1 h-px bg-muted"></div>
+            }
+            .into_any(),
+            MenuItem::Label(text) => view! {
+                <div role="presentation" class="px-2 py-1.5 text-xs font-semibold text-muted-foreground">{text}</div>
+            }
+            .into_any(),
+            MenuItem::Action { label, icon, shortcut, destructive, disabled, on_select } => {
+                let close_menu = close_menu.clone();
+                let hover_id = item_id(index);
+                let item_classes = cn(&[
+                    "relative flex w-full cursor-default select-none items-center gap-2 rounded-sm px-2 py-1.5 text-left text-sm outline-none transition-colors focus:bg-accent focus:text-accent-foreground",
+                    if destructive { "text-destructive focus:bg-destructive focus:text-destructive-foreground" } else { "" },
+                    if disabled { "pointer-events-none opacity-50" } else { "cursor-pointer" },
+                ]);
+                view! {
+                    <button
+                        type="button"
+                        role="menuitem"
+                        id=item_id(index)
+                        tabindex="-1"
+                        class=item_classes
+                        aria-disabled=disabled.to_string()
+                        on:mouseenter=move |_| {
+                            if !disabled {
+                                focused.set(Some(index));
+                                focus_element(&hover_id);
+                            }
+                        }
+                        on:click=move |_| {
+                            if !disabled {
+                                close_menu(true);
+                                on_select.run(());
+                            }
+                        }
+                    >
+                        {icon.map(|name| view! { <Icon name=name size=IconSize::Sm /> })}
+                        <span class="flex-1">{label}</span>
+                        {shortcut.map(|keys| view! {
+                            <kbd class="ml-auto text-xs tracking-widest opacity-60">{keys}</kbd>
+                        })}
+                    </button>
+                }
+                .into_any()
+            }
+        })
+        .collect_view();
+
+    let content_classes = cn(&[
+        "absolute z-50 mt-2 min-w-[10rem] overflow-hidden rounded-md border bg-popover p-1 text-popover-foreground shadow-md",
+        match align {
+            MenuAlign::Start => "left-0",
+            MenuAlign::End => "right-0",
+        },
+        class.unwrap_or(""),
+    ]);
+    let trigger_classes = cn(&[
+        "inline-flex h-10 items-center justify-center rounded-md px-3 text-sm font-medium hover:bg-accent hover:text-accent-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2",
+        trigger_class.unwrap_or(""),
+    ]);
+
+    let backdrop_close = close_menu.clone();
+
+    view! {
+        <div class="relative inline-block text-left">
+            <button
+                type="button"
+                id=trigger_id.clone()
+                class=trigger_classes
+                aria-haspopup="menu"
+                aria-expanded=move || open.get().to_string()
+                aria-controls=menu_id.clone()
+                aria-label=label
+                on:click=move |_| if open.get_untracked() { close_menu(false) } else { open_menu(false) }
+                on:keydown=on_trigger_keydown
+            >
+                {match children {
+                    Some(children) => children(),
+                    None => view! { <span aria-hidden="true">"⋯"</span> }.into_any(),
+                }}
+            </button>
+            <div class="fixed inset-0 z-40" aria-hidden="true" hidden=move || !open.get() on:click=move |_| backdrop_close(false)></div>
+            <div
+                id=menu_id
+                role="menu"
+                aria-labelledby=trigger_id
+                aria-orientation="vertical"
+                class=content_classes
+                hidden=move || !open.get()
+                on:keydown=on_menu_keydown
+            >
+                {rendered_items}
+            </div>
+        </div>
+    }
+}
@@ -1,22 +1,10 @@
 use leptos::prelude::*;
-use crate::ui::{Icon, IconSize, Button, Variant, Priority, HealthcareStatus};
+use std::collections::HashMap;
+use crate::ui::{use_i18n, Icon, IconSize, LiveRegion, Priority};
+use crate::ids::MedicalRecordNumber;
+use crate::models::BloodType;
 
-#[                            {alerts.into_iter().map(|alert| view! {
-                                <EmergencyAlertCard 
-                                    alert=alert.clone()
-                                    on_accept=Box::new(move |id| {
-                                        if let Some(callback) = &on_accept {
-                                            callback(id);
-                                        }
-                                    })
-                                    on_decline=Box::new(move |id| {
-                                        if let Some(callback) = &on_decline {
-                                            callback(id);
-                                        }
-                                    })
-                                    show_patient_info=show_patient_info
-                                />
-                            }).collect_view()}one)]
+#[derive(Debug, Clone)]
 pub struct EmergencyAlert {
     pub id: String,
     pub title: String,
@@ -33,8 +21,8 @@ pub struct EmergencyAlert {
 pub struct PatientInfo {
     pub name: String,
     pub age: u8,
-    pub medical_id: String,
-    pub blood_type: Option<String>,
+    pub medical_id: MedicalRecordNumber,
+    pub blood_type: Option<BloodType>,
     pub allergies: Vec<String>,
     pub emergency_contact: Option<String>,
 }
@@ -61,6 +49,18 @@ impl EmergencyStatus {
         }
     }
     
+    /// Translation key of the status label
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            EmergencyStatus::Pending => "emergency.status.pending",
+            EmergencyStatus::Accepted => "emergency.status.accepted",
+            EmergencyStatus::EnRoute => "emergency.status.en_route",
+            EmergencyStatus::OnScene => "emergency.status.on_scene",
+            EmergencyStatus::Resolved => "emergency.status.resolved",
+            EmergencyStatus::Cancelled => "emergency.status.cancelled",
+        }
+    }
+    
     pub fn color(&self) -> &'static str {
         match self {
             EmergencyStatus::Pending => "hsl(48 96% 53%)",     // Yellow
@@ -86,33 +86,82 @@ impl EmergencyStatus {
 
 #[component]
 pub fn EmergencyAlertPanel(
-    #[prop()] alerts: Vec<EmergencyAlert>,
-    #[prop(optional)] on_accept: Option<Box<dyn Fn(String)>>,
-    #[prop(optional)] on_decline: Option<Box<dyn Fn(String)>>,
+    #[prop(into)] alerts: Signal<Vec<EmergencyAlert>>,
+    #[prop(optional)] on_accept: Option<Callback<String>>,
+    #[prop(optional)] on_decline: Option<Callback<String>>,
     #[prop(optional)] show_patient_info: Option<bool>,
 ) -> impl IntoView {
     let show_patient_info = show_patient_info.unwrap_or(true);
-    
+    // Cards always offer both actions; forward them to whichever callbacks were given
+    let forward = |callback: Option<Callback<String>>| {
+        Callback::new(move |id| {
+            if let Some(callback) = callback {
+                callback.run(id);
+            }
+        })
+    };
+    let on_accept = forward(on_accept);
+    let on_decline = forward(on_decline);
+    let i18n = use_i18n();
+
+    // Alerts that arrive or change status are read out straight away; the
+    // last status seen for each alert tells the two apart
+    let announcement = RwSignal::new(String::new());
+    let last_seen = StoredValue::new(HashMap::<String, EmergencyStatus>::new());
+    Effect::new(move |_| {
+        let alerts = alerts.get();
+        let messages: Vec<String> = last_seen.with_value(|last_seen| {
+            alerts
+                .iter()
+                .filter_map(|alert| match last_seen.get(&alert.id) {
+                    None => Some(i18n.t_with(
+                        "emergency.announce.new",
+                        &[("title", alert.title.as_str())],
+                    )),
+                    Some(status) if *status != alert.status => Some(i18n.t_with(
+                        "emergency.announce.updated",
+                        &[
+                            ("title", alert.title.as_str()),
+                            ("status", i18n.t(alert.status.label_key()).as_str()),
+                        ],
+                    )),
+                    Some(_) => None,
+                })
+                .collect()
+        });
+        last_seen.set_value(alerts.iter().map(|alert| (alert.id.clone(), alert.status)).collect());
+        if !messages.is_empty() {
+            announcement.set(messages.join(". "));
+        }
+    });
+
     view! {
         <div class="space-y-4">
+            <LiveRegion message=announcement assertive=true />
             <div class="flex items-center justify-between">
                 <h2 class="text-xl font-bold text-slate-900 flex items-center">
                     <Icon name="emergency-alert".to_string() size=IconSize::Lg class="text-red-600 mr-3 animate-pulse".to_string() />
-                    Emergency Alerts
+                    {move || i18n.t("emergency.alerts.title")}
                 </h2>
                 <div class="flex items-center space-x-2">
                     <div class="w-3 h-3 bg-red-500 rounded-full animate-pulse"></div>
-                    <span class="text-sm font-medium text-slate-600">{alerts.len()} Active</span>
+                    <span class="text-sm font-medium text-slate-600">
+                        {move || {
+                            let count = alerts.with(|alerts| alerts.len()).to_string();
+                            i18n.t_with("emergency.alerts.active", &[("count", count.as_str())])
+                        }}
+                    </span>
                 </div>
             </div>
             
-            {
+            {move || {
+                let alerts = alerts.get();
                 if alerts.is_empty() {
                     view! {
                         <div class="bg-green-50 border border-green-200 rounded-xl p-6 text-center">
                             <Icon name="interface-check-circle".to_string() size=IconSize::Xl class="text-green-600 mx-auto mb-3".to_string() />
-                            <h3 class="text-lg font-semibold text-green-800 mb-2">No Active Emergencies</h3>
-                            <p class="text-green-600">All emergency situations are currently resolved.</p>
+                            <h3 class="text-lg font-semibold text-green-800 mb-2">{move || i18n.t("emergency.none.title")}</h3>
+                            <p class="text-green-600">{move || i18n.t("emergency.none.description")}</p>
                         </div>
                     }.into_any()
                 } else {
@@ -120,24 +169,16 @@ pub fn EmergencyAlertPanel(
                         <div class="space-y-4">
                             {alerts.into_iter().map(|alert| view! {
                                 <EmergencyAlertCard 
-                                    alert=alert.clone()
-                                    on_accept=move |id| {
-                                        if let Some(callback) = &on_accept {
-                                            callback(id);
-                                        }
-                                    }
-                                    on_decline=move |id| {
-                                        if let Some(callback) = &on_decline {
-                                            callback(id);
-                                        }
-                                    }
+                                    alert=alert
+                                    on_accept=on_accept
+                                    on_decline=on_decline
                                     show_patient_info=show_patient_info
                                 />
                             }).collect_view()}
                         </div>
                     }.into_any()
                 }
-            }
+            }}
         </div>
     }
 }
@@ -145,14 +186,16 @@ pub fn EmergencyAlertPanel(
 #[component]
 pub fn EmergencyAlertCard(
     #[prop()] alert: EmergencyAlert,
-    #[prop(optional)] on_accept: Option<Box<dyn Fn(String)>>,
-    #[prop(optional)] on_decline: Option<Box<dyn Fn(String)>>,
+    #[prop(optional)] on_accept: Option<Callback<String>>,
+    #[prop(optional)] on_decline: Option<Callback<String>>,
     #[prop(optional)] show_patient_info: Option<bool>,
 ) -> impl IntoView {
     let show_patient_info = show_patient_info.unwrap_or(true);
-    let alert_id = alert.id.clone();
     let alert_id_accept = alert.id.clone();
     let alert_id_decline = alert.id.clone();
+    let i18n = use_i18n();
+    let priority_key = format!("priority.{}", alert.priority.as_str());
+    let status_key = alert.status.label_key();
     
     let priority_bg = match alert.priority {
         Priority::Emergency => "bg-gradient-to-r from-red-50 to-red-100 border-red-300",
@@ -195,7 +238,7 @@ pub fn EmergencyAlertCard(
                                     Priority::Low => "bg-green-600 text-white",
                                 }
                             )>
-                                {alert.priority.as_str().to_uppercase()}
+                                <span class="uppercase">{move || i18n.t(&priority_key)}</span>
                             </span>
                         </div>
                         <p class=format!("text-sm {}", priority_text)>{alert.description.clone()}</p>
@@ -209,7 +252,7 @@ pub fn EmergencyAlertCard(
                         style=format!("background-color: {}; color: white;", alert.status.color())
                     >
                         <Icon name=alert.status.icon().to_string() size=IconSize::Xs class="text-white".to_string() />
-                        <span>{alert.status.as_str().replace("-", " ").to_uppercase()}</span>
+                        <span class="uppercase">{move || i18n.t(status_key)}</span>
                     </div>
                     <div class=format!("text-xs {}", priority_text)>
                         {alert.created_at.clone()}
@@ -237,7 +280,9 @@ pub fn EmergencyAlertCard(
                         view! {
                             <div class="flex items-center space-x-2">
                                 <Icon name="interface-clock".to_string() size=IconSize::Sm class="text-slate-600".to_string() />
-                                <span class="text-sm font-medium text-slate-700">ETA: {response_time.clone()}</span>
+                                <span class="text-sm font-medium text-slate-700">
+                                    {i18n.t_with("emergency.eta", &[("time", response_time.as_str())])}
+                                </span>
                             </div>
                         }.into_any()
                     } else {
@@ -254,28 +299,28 @@ pub fn EmergencyAlertCard(
                             <div class="bg-white bg-opacity-70 rounded-lg p-4 mb-4 border border-white border-opacity-50">
                                 <h4 class="font-semibold text-slate-800 mb-3 flex items-center">
                                     <Icon name="user-patient".to_string() size=IconSize::Sm class="mr-2".to_string() />
-                                    Patient Information
+                                    {i18n.t("emergency.patient_info")}
                                 </h4>
                                 
                                 <div class="grid grid-cols-1 md:grid-cols-2 gap-3 text-sm">
                                     <div>
-                                        <span class="font-medium text-slate-600">Name:</span>
+                                        <span class="font-medium text-slate-600">{i18n.t("emergency.field.name")}</span>
                                         <span class="ml-2 text-slate-800">{patient.name.clone()}</span>
                                     </div>
                                     <div>
-                                        <span class="font-medium text-slate-600">Age:</span>
-                                        <span class="ml-2 text-slate-800">{patient.age} years</span>
+                                        <span class="font-medium text-slate-600">{i18n.t("emergency.field.age")}</span>
+                                        <span class="ml-2 text-slate-800">{i18n.t_plural("emergency.age_years", patient.age.into())}</span>
                                     </div>
                                     <div>
-                                        <span class="font-medium text-slate-600">Medical ID:</span>
-                                        <span class="ml-2 text-slate-800 font-mono">{patient.medical_id.clone()}</span>
+                                        <span class="font-medium text-slate-600">{i18n.t("emergency.field.medical_id")}</span>
+                                        <span class="ml-2 text-slate-800 font-mono">{patient.medical_id.to_string()}</span>
                                     </div>
                                     {
                                         if let Some(blood_type) = &patient.blood_type {
                                             view! {
                                                 <div>
-                                                    <span class="font-medium text-slate-600">Blood Type:</span>
-                                                    <span class="ml-2 text-slate-800 font-semibold">{blood_type.clone()}</span>
+                                                    <span class="font-medium text-slate-600">{i18n.t("emergency.field.blood_type")}</span>
+                                                    <span class="ml-2 text-slate-800 font-semibold">{blood_type.to_string()}</span>
                                                 </div>
                                             }.into_any()
                                         } else {
@@ -290,7 +335,7 @@ pub fn EmergencyAlertCard(
                                             <div class="mt-3 p-3 bg-red-50 border border-red-200 rounded-lg">
                                                 <div class="flex items-center space-x-2 mb-2">
                                                     <Icon name="emergency-alert".to_string() size=IconSize::Sm class="text-red-600".to_string() />
-                                                    <span class="font-semibold text-red-800">Allergies:</span>
+                                                    <span class="font-semibold text-red-800">{i18n.t("emergency.field.allergies")}</span>
                                                 </div>
                                                 <div class="text-sm text-red-700">
                                                     {patient.allergies.join(", ")}
@@ -306,7 +351,7 @@ pub fn EmergencyAlertCard(
                                     if let Some(contact) = &patient.emergency_contact {
                                         view! {
                                             <div class="mt-3">
-                                                <span class="font-medium text-slate-600">Emergency Contact:</span>
+                                                <span class="font-medium text-slate-600">{i18n.t("emergency.field.emergency_contact")}</span>
                                                 <a href=format!("tel:{}", contact) class="ml-2 text-blue-600 hover:text-blue-800 underline font-medium">
                                                     {contact.clone()}
                                                 </a>
@@ -334,25 +379,25 @@ pub fn EmergencyAlertCard(
                             <button 
                                 class="flex-1 bg-gradient-to-r from-green-600 to-green-700 text-white px-6 py-3 rounded-lg font-semibold hover:from-green-700 hover:to-green-800 transition-all duration-200 shadow-md hover:shadow-lg transform hover:scale-105 active:scale-95 flex items-center justify-center"
                                 on:click=move |_| {
-                                    if let Some(callback) = &on_accept {
-                                        callback(alert_id_accept.clone());
+                                    if let Some(callback) = on_accept {
+                                        callback.run(alert_id_accept.clone());
                                     }
                                 }
                             >
                                 <Icon name="interface-check".to_string() size=IconSize::Md class="text-white mr-2".to_string() />
-                                Accept Emergency
+                                {move || i18n.t("emergency.action.accept")}
                             </button>
                             
                             <button 
                                 class="flex-1 bg-gradient-to-r from-gray-500 to-gray-600 text-white px-6 py-3 rounded-lg font-semibold hover:from-gray-600 hover:to-gray-700 transition-all duration-200 shadow-md hover:shadow-lg transform hover:scale-105 active:scale-95 flex items-center justify-center"
                                 on:click=move |_| {
-                                    if let Some(callback) = &on_decline {
-                                        callback(alert_id_decline.clone());
+                                    if let Some(callback) = on_decline {
+                                        callback.run(alert_id_decline.clone());
                                     }
                                 }
                             >
                                 <Icon name="interface-close".to_string() size=IconSize::Md class="text-white mr-2".to_string() />
-                                Cannot Respond
+                                {move || i18n.t("emergency.action.decline")}
                             </button>
                         </div>
                     }.into_any()
@@ -360,7 +405,7 @@ pub fn EmergencyAlertCard(
                     view! {
                         <div class="text-center py-2">
                             <span class="text-sm font-medium text-slate-600">
-                                Emergency response in progress...
+                                {move || i18n.t("emergency.in_progress")}
                             </span>
                         </div>
                     }.into_any()
@@ -372,12 +417,13 @@ pub fn EmergencyAlertCard(
 
 #[component]
 pub fn EmergencyTriggerButton(
-    #[prop(optional)] on_emergency: Option<Box<dyn Fn()>>,
+    #[prop(optional)] on_emergency: Option<Callback<()>>,
     #[prop(optional)] size: Option<String>,
     #[prop(optional)] mobile: Option<bool>,
 ) -> impl IntoView {
     let size = size.unwrap_or_else(|| "large".to_string());
     let mobile = mobile.unwrap_or(false);
+    let i18n = use_i18n();
     
     let button_class = if mobile {
         "w-full bg-gradient-to-r from-red-600 to-red-700 text-white px-8 py-6 rounded-2xl font-bold text-lg shadow-xl hover:from-red-700 hover:to-red-800 transition-all duration-200 transform hover:scale-105 active:scale-95 flex items-center justify-center"
@@ -393,8 +439,8 @@ pub fn EmergencyTriggerButton(
         <button 
             class=button_class
             on:click=move |_| {
-                if let Some(callback) = &on_emergency {
-                    callback();
+                if let Some(callback) = on_emergency {
+                    callback.run(());
                 }
             }
         >
@@ -405,12 +451,12 @@ pub fn EmergencyTriggerButton(
             />
             <div class="text-left">
                 <div class="font-bold">
-                    {if mobile { "EMERGENCY ALERT" } else { "Emergency" }}
+                    {move || i18n.t(if mobile { "emergency.trigger.mobile" } else { "emergency.trigger" })}
                 </div>
                 {
                     if mobile {
                         view! {
-                            <div class="text-sm text-red-100">Tap for immediate help</div>
+                            <div class="text-sm text-red-100">{move || i18n.t("emergency.trigger.hint")}</div>
                         }.into_any()
                     } else {
                         view! {}.into_any()
@@ -427,6 +473,7 @@ pub fn EmergencyStatusIndicator(
     #[prop(optional)] show_text: Option<bool>,
 ) -> impl IntoView {
     let show_text = show_text.unwrap_or(true);
+    let i18n = use_i18n();
     
     view! {
         <div class="flex items-center space-x-2">
@@ -437,8 +484,8 @@ pub fn EmergencyStatusIndicator(
             {
                 if show_text {
                     view! {
-                        <span class="text-sm font-medium text-slate-700">
-                            {status.as_str().replace("-", " ").to_uppercase()}
+                        <span class="text-sm font-medium text-slate-700 uppercase">
+                            {move || i18n.t(status.label_key())}
                         </span>
                     }.into_any()
                 } else {
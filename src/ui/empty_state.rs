@@ -0,0 +1,103 @@
+use leptos::prelude::*;
+use crate::ui::{cn, use_i18n, Button, ButtonSize, Icon, IconSize};
+
+/// Ready-made copy and icons for common empty lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyStatePreset {
+    NoAppointmentsToday,
+    NoPatientsFound,
+    NoAlerts,
+    NoResults,
+    NoMessages,
+}
+
+impl EmptyStatePreset {
+    /// Registry name of the preset's icon
+    pub fn icon(&self) -> &'static str {
+        match self {
+            EmptyStatePreset::NoAppointmentsToday => "interface-calendar",
+            EmptyStatePreset::NoPatientsFound => "user-patient",
+            EmptyStatePreset::NoAlerts => "status-completed",
+            EmptyStatePreset::NoResults => "interface-search",
+            EmptyStatePreset::NoMessages => "interface-chat",
+        }
+    }
+
+    /// Translation key of the title
+    pub fn title_key(&self) -> &'static str {
+        match self {
+            EmptyStatePreset::NoAppointmentsToday => "empty.no_appointments_today.title",
+            EmptyStatePreset::NoPatientsFound => "empty.no_patients_found.title",
+            EmptyStatePreset::NoAlerts => "empty.no_alerts.title",
+            EmptyStatePreset::NoResults => "empty.no_results.title",
+            EmptyStatePreset::NoMessages => "empty.no_messages.title",
+        }
+    }
+
+    /// Translation key of the description
+    pub fn description_key(&self) -> &'static str {
+        match self {
+            EmptyStatePreset::NoAppointmentsToday => "empty.no_appointments_today.description",
+            EmptyStatePreset::NoPatientsFound => "empty.no_patients_found.description",
+            EmptyStatePreset::NoAlerts => "empty.no_alerts.description",
+            EmptyStatePreset::NoResults => "empty.no_results.description",
+            EmptyStatePreset::NoMessages => "empty.no_messages.description",
+        }
+    }
+}
+
+/// Placeholder for lists and panels with nothing to show. Start from a
+/// `preset` and override any of its text, or set everything explicitly.
+#[component]
+pub fn EmptyState(
+    #[prop(optional)] preset: Option<EmptyStatePreset>,
+    /// Registry icon name; overrides the preset's icon
+    #[prop(optional, into)] icon: Option<String>,
+    #[prop(optional, into)] title: Option<String>,
+    #[prop(optional, into)] description: Option<String>,
+    #[prop(optional, into)] action_label: Option<String>,
+    #[prop(optional)] on_action: Option<Callback<()>>,
+    /// Smaller padding and icon for table cells and side panels
+    #[prop(optional)] compact: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let compact = compact.unwrap_or(false);
+    let i18n = use_i18n();
+    let icon = icon.or_else(|| preset.map(|p| p.icon().to_string()));
+    let title = move || {
+        title.clone().unwrap_or_else(|| i18n.t(preset.map_or("empty.default", |p| p.title_key())))
+    };
+    let description = description.or_else(|| preset.map(|p| i18n.t(p.description_key())));
+
+    let action = on_action.map(|on_action| {
+        view! {
+            <Button size=ButtonSize::Sm on_click=on_action class="mt-4">
+                {action_label.unwrap_or_else(|| i18n.t("common.get_started"))}
+            </Button>
+        }
+    });
+
+    let container_classes = cn(&[
+        "flex flex-col items-center justify-center text-center",
+        if compact { "py-6" } else { "py-12" },
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <div class=container_classes role="status">
+            {icon.map(|icon| view! {
+                <span class=cn(&[
+                    "mb-3 flex items-center justify-center rounded-full bg-muted text-muted-foreground",
+                    if compact { "h-10 w-10" } else { "h-14 w-14" },
+                ])>
+                    <Icon name=icon size=if compact { IconSize::Md } else { IconSize::Lg } />
+                </span>
+            })}
+            <p class="text-sm font-medium text-foreground">{title}</p>
+            {description.map(|description| view! {
+                <p class="mt-1 max-w-sm text-sm text-muted-foreground">{description}</p>
+            })}
+            {action}
+        </div>
+    }
+}
@@ -0,0 +1,159 @@
+use leptos::prelude::*;
+use chrono::{DateTime, Utc};
+use std::fmt::Display;
+use crate::ui::{cn, use_i18n, Button, ButtonSize, ButtonVariant, Icon, IconSize};
+use crate::utils::strings::sanitize_for_logging;
+
+/// Failure record that is safe to log or send to telemetry: the message has
+/// emails, phone numbers and SSNs redacted, and only the short `id` is shown
+/// to the user so support can find the log line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorReport {
+    pub id: String,
+    pub component: String,
+    pub message: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl ErrorReport {
+    pub fn new(component: impl Into<String>, error: impl Display) -> Self {
+        let mut id = uuid::Uuid::new_v4().simple().to_string();
+        id.truncate(8);
+        Self {
+            id,
+            component: component.into(),
+            message: sanitize_for_logging(&error.to_string()),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    fn log(&self) {
+        log::error!("{} failed [{}]: {}", self.component, self.id, self.message);
+    }
+}
+
+/// Reports data errors to the nearest [`ErrorBoundaryCard`], which then shows
+/// its fallback. Outside a card errors are only logged.
+#[derive(Clone, Copy)]
+pub struct ErrorHandler {
+    component: StoredValue<String>,
+    error: RwSignal<Option<ErrorReport>>,
+    on_error: Option<Callback<ErrorReport>>,
+}
+
+impl ErrorHandler {
+    fn new(component: String, on_error: Option<Callback<ErrorReport>>) -> Self {
+        Self {
+            component: StoredValue::new(component),
+            error: RwSignal::new(None),
+            on_error,
+        }
+    }
+
+    /// Log a PHI-safe report without changing what is shown
+    fn capture(&self, error: impl Display) -> ErrorReport {
+        let report = ErrorReport::new(self.component.get_value(), error);
+        report.log();
+        if let Some(callback) = self.on_error {
+            callback.run(report.clone());
+        }
+        report
+    }
+
+    pub fn report(&self, error: impl Display) -> ErrorReport {
+        let report = self.capture(error);
+        self.error.set(Some(report.clone()));
+        report
+    }
+
+    /// The `Ok` value, or `None` after reporting the error
+    pub fn handle<T, E: Display>(&self, result: Result<T, E>) -> Option<T> {
+        result.map_err(|error| self.report(error)).ok()
+    }
+
+    pub fn error(&self) -> Option<ErrorReport> {
+        self.error.get()
+    }
+
+    pub fn clear(&self) {
+        self.error.set(None);
+    }
+}
+
+/// Handler of the enclosing [`ErrorBoundaryCard`], or a log-only one
+pub fn use_error_handler() -> ErrorHandler {
+    use_context::<ErrorHandler>().unwrap_or_else(|| ErrorHandler::new("app".to_string(), None))
+}
+
+/// Card that isolates failures in its content: errors rendered as `Err(_)`
+/// views or reported through [`use_error_handler`] replace only this card
+/// with a fallback and a retry button, instead of blanking the dashboard.
+#[component]
+pub fn ErrorBoundaryCard(
+    /// Names the card in the fallback and in logs, e.g. "Upcoming appointments"
+    #[prop(into)] title: String,
+    /// Called on retry, e.g. to refetch the card's data
+    #[prop(optional)] on_retry: Option<Callback<()>>,
+    /// Receives each PHI-safe report, e.g. for telemetry
+    #[prop(optional)] on_error: Option<Callback<ErrorReport>>,
+    #[prop(optional)] class: Option<&'static str>,
+    children: ChildrenFn,
+) -> impl IntoView {
+    let handler = ErrorHandler::new(title.clone(), on_error);
+    let i18n = use_i18n();
+    provide_context(handler);
+    // Bumped on retry to remount the children
+    let attempt = RwSignal::new(0u32);
+
+    let retry = move || {
+        handler.clear();
+        attempt.update(|n| *n += 1);
+        if let Some(callback) = on_retry {
+            callback.run(());
+        }
+    };
+
+    let fallback = move |report: ErrorReport| {
+        view! {
+            <div class="flex flex-col items-center justify-center gap-2 py-8 text-center" role="alert">
+                <span class="flex h-10 w-10 items-center justify-center rounded-full bg-red-100 text-red-700">
+                    <Icon name="status-error" size=IconSize::Md />
+                </span>
+                <p class="text-sm font-medium">{i18n.t_with("error.load_failed", &[("component", handler.component.get_value().as_str())])}</p>
+                <p class="text-xs text-muted-foreground">{i18n.t_with("error.reference", &[("id", report.id.as_str())])}</p>
+                <Button variant=ButtonVariant::Outline size=ButtonSize::Sm on_click=Callback::new(move |_| retry()) class="mt-2">
+                    {i18n.t("common.try_again")}
+                </Button>
+            </div>
+        }
+        .into_any()
+    };
+
+    let body = move || {
+        if let Some(report) = handler.error.get() {
+            return fallback(report);
+        }
+        attempt.track();
+        let children = children.clone();
+        view! {
+            <ErrorBoundary fallback=move |errors: ArcRwSignal<Errors>| {
+                let message = errors.with_untracked(|errors| {
+                    errors.iter().map(|(_, error)| error.to_string()).collect::<Vec<_>>().join("; ")
+                });
+                fallback(handler.capture(message))
+            }>
+                {children()}
+            </ErrorBoundary>
+        }
+        .into_any()
+    };
+
+    view! {
+        <div class=cn(&["rounded-lg border bg-card text-card-foreground shadow-sm", class.unwrap_or("")])>
+            <div class="p-6 pb-2">
+                <h3 class="text-lg font-semibold leading-none tracking-tight">{title}</h3>
+            </div>
+            <div class="p-6 pt-0">{body}</div>
+        </div>
+    }
+}
@@ -0,0 +1,367 @@
+use leptos::prelude::*;
+use std::sync::Arc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use crate::api_client::{ApiClient, DocumentCategory, UploadFile, UploadProgress, UploadedDocument};
+use crate::ids::PatientId;
+use crate::ui::cn;
+use crate::utils::strings::format_file_size;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadStatus {
+    /// Read and validated, waiting to be sent
+    Ready,
+    Uploading(f32),
+    Uploaded,
+    /// Upload failed; the file can be retried
+    Failed(String),
+    /// Failed client-side validation and will never be sent
+    Rejected(String),
+}
+
+/// File tracked by a [`FileDropzone`]
+#[derive(Debug, Clone)]
+pub struct DropzoneFile {
+    pub id: u64,
+    pub name: String,
+    pub size: u64,
+    /// Object URL for image thumbnails; revoked when the file is removed
+    pub preview_url: Option<String>,
+    pub status: UploadStatus,
+    pub document: Option<UploadedDocument>,
+    upload: Option<Arc<UploadFile>>,
+}
+
+async fn read_file(file: &web_sys::File) -> Result<Vec<u8>, String> {
+    let buffer = JsFuture::from(file.array_buffer())
+        .await
+        .map_err(|e| format!("Could not read file: {:?}", e))?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
+
+fn file_list_to_vec(list: Option<web_sys::FileList>) -> Vec<web_sys::File> {
+    list.map(|list| (0..list.length()).filter_map(|i| list.get(i)).collect())
+        .unwrap_or_default()
+}
+
+/// Drag-and-drop upload area for KYC documents and medical reports.
+///
+/// Files are checked against the category's size and type limits before they
+/// are read, then uploaded through [`ApiClient::upload_document`] with live
+/// progress. Failed uploads keep their bytes so they can be retried.
+#[component]
+pub fn FileDropzone(
+    client: ApiClient,
+    #[prop(into)] patient_id: PatientId,
+    category: DocumentCategory,
+    #[prop(optional)] multiple: Option<bool>,
+    /// Upload as soon as files are added (default: true)
+    #[prop(optional)] auto_upload: Option<bool>,
+    #[prop(optional)] on_uploaded: Option<Callback<UploadedDocument>>,
+    #[prop(optional, into)] label: Option<String>,
+    #[prop(optional)] disabled: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let multiple = multiple.unwrap_or(false);
+    let auto_upload = auto_upload.unwrap_or(true);
+    let disabled = disabled.unwrap_or(false);
+    let label = label.unwrap_or_else(|| "Drag files here or click to browse".to_string());
+    let input_id = format!("dropzone-{}", uuid::Uuid::new_v4().simple());
+    let accept = category.allowed_content_types().join(",");
+
+    let files = RwSignal::new(Vec::<DropzoneFile>::new());
+    let dragging = RwSignal::new(false);
+    let next_id = StoredValue::new(0u64);
+
+    let set_status = move |id: u64, status: UploadStatus| {
+        files.update(|files| {
+            if let Some(entry) = files.iter_mut().find(|f| f.id == id) {
+                entry.status = status;
+            }
+        });
+    };
+
+    let upload = {
+        let client = client.clone();
+        let patient_id = patient_id.clone();
+        move |id: u64| {
+            let Some(file) = files.with_untracked(|files| {
+                files.iter().find(|f| f.id == id).and_then(|f| f.upload.clone())
+            }) else {
+                return;
+            };
+            set_status(id, UploadStatus::Uploading(0.0));
+
+            let client = client.clone();
+            let patient_id = patient_id.clone();
+            let on_progress = Arc::new(move |progress: UploadProgress| {
+                set_status(id, UploadStatus::Uploading(progress.percent()));
+            });
+            spawn_local(async move {
+                match client.upload_document(&patient_id, (*file).clone(), category, Some(on_progress)).await {
+                    Ok(document) => {
+                        files.update(|files| {
+                            if let Some(entry) = files.iter_mut().find(|f| f.id == id) {
+                                entry.status = UploadStatus::Uploaded;
+                                entry.document = Some(document.clone());
+                                // Bytes are no longer needed once the server has them
+                                entry.upload = None;
+                            }
+                        });
+                        if let Some(callback) = on_uploaded {
+                            callback.run(document);
+                        }
+                    }
+                    Err(error) => set_status(id, UploadStatus::Failed(error)),
+                }
+            });
+        }
+    };
+
+    let add_files = {
+        let upload = upload.clone();
+        move |selected: Vec<web_sys::File>| {
+            let selected = if multiple { selected } else { selected.into_iter().take(1).collect() };
+            if !multiple {
+                remove_all(files);
+            }
+
+            for file in selected {
+                let id = next_id.get_value();
+                next_id.set_value(id + 1);
+
+                let name = file.name();
+                let content_type = file.type_();
+                let size = file.size() as u64;
+                let preview_url = content_type
+                    .starts_with("image/")
+                    .then(|| web_sys::Url::create_object_url_with_blob(&file).ok())
+                    .flatten();
+                let status = match category.check(&content_type, size) {
+                    Ok(()) => UploadStatus::Ready,
+                    Err(reason) => UploadStatus::Rejected(reason),
+                };
+                let accepted = status == UploadStatus::Ready;
+
+                files.update(|files| {
+                    files.push(DropzoneFile {
+                        id,
+                        name: name.clone(),
+                        size,
+                        preview_url,
+                        status,
+                        document: None,
+                        upload: None,
+                    })
+                });
+
+                if accepted {
+                    let upload = upload.clone();
+                    spawn_local(async move {
+                        match read_file(&file).await {
+                            Ok(bytes) => {
+                                files.update(|files| {
+                                    if let Some(entry) = files.iter_mut().find(|f| f.id == id) {
+                                        entry.upload = Some(Arc::new(UploadFile::new(name, content_type, bytes)));
+                                    }
+                                });
+                                if auto_upload {
+                                    upload(id);
+                                }
+                            }
+                            Err(error) => set_status(id, UploadStatus::Rejected(error)),
+                        }
+                    });
+                }
+            }
+        }
+    };
+
+    let remove = move |id: u64| {
+        files.update(|files| {
+            if let Some(position) = files.iter().position(|f| f.id == id) {
+                let entry = files.remove(position);
+                if let Some(url) = entry.preview_url {
+                    let _ = web_sys::Url::revoke_object_url(&url);
+                }
+            }
+        });
+    };
+
+    on_cleanup(move || remove_all(files));
+
+    let on_drop = {
+        let add_files = add_files.clone();
+        move |ev: leptos::ev::DragEvent| {
+            ev.prevent_default();
+            dragging.set(false);
+            if !disabled {
+                add_files(file_list_to_vec(ev.data_transfer().and_then(|dt| dt.files())));
+            }
+        }
+    };
+
+    let on_input_change = move |ev: leptos::ev::Event| {
+        if let Some(input) = ev.target().and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok()) {
+            add_files(file_list_to_vec(input.files()));
+            // Allow picking the same file again after removing it
+            input.set_value("");
+        }
+    };
+
+    let upload_pending = upload.clone();
+    let has_pending = move || files.with(|files| files.iter().any(|f| f.status == UploadStatus::Ready && f.upload.is_some()));
+
+    let file_rows = move || {
+        files
+            .get()
+            .into_iter()
+            .map(|entry| {
+                let id = entry.id;
+                let retry = upload.clone();
+                let (status_text, status_class) = match &entry.status {
+                    UploadStatus::Ready => ("Ready".to_string(), "text-muted-foreground"),
+                    UploadStatus::Uploading(percent) => (format!("Uploading {:.0}%", percent), "text-muted-foreground"),
+                    UploadStatus::Uploaded => ("Uploaded".to_string(), "text-green-600"),
+                    UploadStatus::Failed(error) | UploadStatus::Rejected(error) => (error.clone(), "text-destructive"),
+                };
+                let progress = match entry.status {
+                    UploadStatus::Uploading(percent) => Some(percent),
+                    _ => None,
+                };
+                let failed = matches!(entry.status, UploadStatus::Failed(_));
+                let uploading = progress.is_some();
+
+                view! {
+                    <li class="flex items-center gap-3 rounded-md border p-2">
+                        {match entry.preview_url.clone() {
+                            Some(url) => view! {
+                                <img src=url alt="" class="h-10 w-10 shrink-0 rounded object-cover" />
+                            }.into_any(),
+                            None => view! {
+                                <span class="flex h-10 w-10 shrink-0 items-center justify-center rounded bg-muted text-xs font-medium uppercase text-muted-foreground" aria-hidden="true">
+                                    {entry.name.rsplit('.').next().unwrap_or("").chars().take(4).collect::<String>()}
+                                </span>
+                            }.into_any(),
+                        }}
+                        <div class="min-w-0 flex-1">
+                            <p class="truncate text-sm font-medium">{entry.name.clone()}</p>
+                            <p class=cn(&["text-xs", status_class]) role=failed.then_some("alert")>
+                                {format!("{} · {}", format_file_size(entry.size), status_text)}
+                            </p>
+                            {progress.map(|percent| view! {
+                                <div
+                                    class="mt-1 h-1.5 w-full overflow-hidden rounded-full bg-secondary"
+                                    role="progressbar"
+                                    aria-valuemin="0"
+                                    aria-valuemax="100"
+                                    aria-valuenow=format!("{:.0}", percent)
+                                >
+                                    <div class="h-full bg-primary transition-all" style=format!("width: {:.0}%", percent)></div>
+                                </div>
+                            })}
+                        </div>
+                        {failed.then(|| view! {
+                            <button
+                                type="button"
+                                class="text-sm font-medium text-primary hover:underline"
+                                on:click=move |_| retry(id)
+                            >
+                                "Retry"
+                            </button>
+                        })}
+                        <button
+                            type="button"
+                            class="rounded-sm px-1 text-muted-foreground hover:text-foreground disabled:opacity-50"
+                            aria-label=format!("Remove {}", entry.name)
+                            disabled=uploading
+                            on:click=move |_| remove(id)
+                        >
+                            "✕"
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    let zone_classes = move || {
+        cn(&[
+            "flex w-full cursor-pointer flex-col items-center justify-center gap-1 rounded-lg border-2 border-dashed p-6 text-center text-sm transition-colors focus-within:ring-2 focus-within:ring-ring focus-within:ring-offset-2",
+            if dragging.get() { "border-primary bg-primary/5" } else { "border-input hover:bg-accent/50" },
+            if disabled { "pointer-events-none opacity-50" } else { "" },
+            class.unwrap_or(""),
+        ])
+    };
+    let limits = format!(
+        "{} up to {}",
+        category
+            .allowed_content_types()
+            .iter()
+            .map(|t| t.rsplit('/').next().unwrap_or(t).to_uppercase())
+            .collect::<Vec<_>>()
+            .join(", "),
+        format_file_size(category.max_size_bytes())
+    );
+
+    view! {
+        <div class="w-full space-y-3">
+            <label
+                for=input_id.clone()
+                class=zone_classes
+                on:dragover=move |ev: leptos::ev::DragEvent| {
+                    ev.prevent_default();
+                    dragging.set(true);
+                }
+                on:dragleave=move |_| dragging.set(false)
+                on:drop=on_drop
+            >
+                <span class="font-medium">{label}</span>
+                <span class="text-xs text-muted-foreground">{limits}</span>
+                <input
+                    id=input_id
+                    type="file"
+                    class="sr-only"
+                    accept=accept
+                    multiple=multiple
+                    disabled=disabled
+                    on:change=on_input_change
+                />
+            </label>
+            <ul class="space-y-2" aria-live="polite">{file_rows}</ul>
+            <Show when=move || !auto_upload && has_pending()>
+                <button
+                    type="button"
+                    class="inline-flex h-9 items-center rounded-md bg-primary px-3 text-sm font-medium text-primary-foreground hover:bg-primary/90"
+                    on:click={
+                        let upload_pending = upload_pending.clone();
+                        move |_| {
+                            let pending: Vec<u64> = files.with_untracked(|files| {
+                                files
+                                    .iter()
+                                    .filter(|f| f.status == UploadStatus::Ready && f.upload.is_some())
+                                    .map(|f| f.id)
+                                    .collect()
+                            });
+                            for id in pending {
+                                upload_pending(id);
+                            }
+                        }
+                    }
+                >
+                    "Upload"
+                </button>
+            </Show>
+        </div>
+    }
+}
+
+/// Clear the list and release thumbnail object URLs
+fn remove_all(files: RwSignal<Vec<DropzoneFile>>) {
+    // `try_update` because this also runs from `on_cleanup`, when the signal may be gone
+    files.try_update(|files| {
+        for url in files.drain(..).filter_map(|f| f.preview_url) {
+            let _ = web_sys::Url::revoke_object_url(&url);
+        }
+    });
+}
@@ -0,0 +1,338 @@
+use leptos::prelude::*;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use crate::ui::{cn, use_i18n, Select, SelectOption};
+
+/// Dropdown facet of a [`FilterBar`], e.g. "Specialty". The `id` doubles as
+/// the query parameter name when filters are sent to a search endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Facet {
+    pub id: &'static str,
+    pub label: String,
+    pub options: Vec<SelectOption>,
+    /// Allow several values at once; otherwise a new choice replaces the old one
+    pub multiple: bool,
+}
+
+impl Facet {
+    pub fn new(id: &'static str, label: impl Into<String>, options: Vec<SelectOption>) -> Self {
+        Self {
+            id,
+            label: label.into(),
+            options,
+            multiple: false,
+        }
+    }
+
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    fn option_label(&self, value: &str) -> String {
+        self.options
+            .iter()
+            .find(|option| option.value == value)
+            .map_or_else(|| value.to_string(), |option| option.label.clone())
+    }
+}
+
+/// Inclusive date range; either end may be open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+}
+
+impl DateRange {
+    pub fn is_empty(&self) -> bool {
+        self.from.is_none() && self.until.is_none()
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.from.is_none_or(|from| date >= from) && self.until.is_none_or(|until| date <= until)
+    }
+}
+
+/// Filters chosen in a [`FilterBar`]. Search endpoints take it through
+/// `SearchQuery::from_filters` and `PatientSearchQuery::from_filters`;
+/// [`DataTable`](crate::ui::DataTable) applies it to rows with `filter_row`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterState {
+    /// Free-text search, trimmed; `None` when blank
+    pub text: Option<String>,
+    /// Selected values per facet id
+    pub facets: BTreeMap<&'static str, Vec<String>>,
+    pub date_range: DateRange,
+}
+
+impl FilterState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none() && self.facets.values().all(Vec::is_empty) && self.date_range.is_empty()
+    }
+
+    /// Number of chips the filter bar shows
+    pub fn active_count(&self) -> usize {
+        usize::from(self.text.is_some())
+            + self.facets.values().map(Vec::len).sum::<usize>()
+            + usize::from(!self.date_range.is_empty())
+    }
+
+    pub fn values(&self, facet: &str) -> &[String] {
+        self.facets.get(facet).map_or(&[], Vec::as_slice)
+    }
+
+    /// First selected value, for single-choice facets
+    pub fn value(&self, facet: &str) -> Option<&str> {
+        self.values(facet).first().map(String::as_str)
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        let text = text.trim();
+        self.text = (!text.is_empty()).then(|| text.to_string());
+    }
+
+    pub fn set_values(&mut self, facet: &'static str, values: Vec<String>) {
+        if values.is_empty() {
+            self.facets.remove(facet);
+        } else {
+            self.facets.insert(facet, values);
+        }
+    }
+
+    pub fn remove_value(&mut self, facet: &str, value: &str) {
+        if let Some(values) = self.facets.get_mut(facet) {
+            values.retain(|v| v != value);
+            if values.is_empty() {
+                self.facets.remove(facet);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Case-insensitive match of the free text against any of `fields`
+    pub fn matches_text(&self, fields: &[&str]) -> bool {
+        self.text.as_ref().is_none_or(|text| {
+            let text = text.to_lowercase();
+            fields.iter().any(|field| field.to_lowercase().contains(&text))
+        })
+    }
+
+    /// Whether `value` is allowed by the facet; facets with nothing selected allow everything
+    pub fn matches_facet(&self, facet: &str, value: &str) -> bool {
+        let values = self.values(facet);
+        values.is_empty() || values.iter().any(|v| v == value)
+    }
+
+    /// Free text as `q`, each facet under its id (comma-separated), dates as `from`/`until`
+    pub fn to_query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(text) = &self.text {
+            params.push(("q", text.clone()));
+        }
+        for (facet, values) in &self.facets {
+            if !values.is_empty() {
+                params.push((*facet, values.join(",")));
+            }
+        }
+        if let Some(from) = self.date_range.from {
+            params.push(("from", from.to_string()));
+        }
+        if let Some(until) = self.date_range.until {
+            params.push(("until", until.to_string()));
+        }
+        params
+    }
+}
+
+/// Bound `Select` for one facet, kept in step with chip removal and clear-all
+#[component]
+fn FacetSelect(facet: Facet, filters: RwSignal<FilterState>, on_change: Callback<()>) -> impl IntoView {
+    let id = facet.id;
+    let value = RwSignal::new(filters.with_untracked(|f| f.values(id).to_vec()));
+    Effect::new(move |_| {
+        let current = filters.with(|f| f.values(id).to_vec());
+        if value.with_untracked(|v| *v != current) {
+            value.set(current);
+        }
+    });
+
+    view! {
+        <div class="w-44">
+            <Select
+                options=facet.options
+                value=value
+                multiple=facet.multiple
+                placeholder=facet.label.clone()
+                label=facet.label
+                on_change=Callback::new(move |values: Vec<String>| {
+                    filters.update(|f| f.set_values(id, values));
+                    on_change.run(());
+                })
+            />
+        </div>
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Search box, dropdown facets and an optional date range above a list or
+/// table. Active filters show as removable chips with a clear-all action.
+/// Every change is written to `filters` and reported through `on_change`.
+#[component]
+pub fn FilterBar(
+    filters: RwSignal<FilterState>,
+    #[prop(optional)] facets: Vec<Facet>,
+    /// Show the free-text search box (default: true)
+    #[prop(optional)] search: Option<bool>,
+    #[prop(optional, into)] search_placeholder: Option<String>,
+    /// Label of the date-range facet; the range inputs are hidden when not set
+    #[prop(optional, into)] date_label: Option<String>,
+    #[prop(optional)] on_change: Option<Callback<FilterState>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let search = search.unwrap_or(true);
+    let i18n = use_i18n();
+    let notify = Callback::new(move |_| {
+        if let Some(callback) = on_change {
+            callback.run(filters.get_untracked());
+        }
+    });
+    let update = move |change: &dyn Fn(&mut FilterState)| {
+        filters.update(|f| change(f));
+        notify.run(());
+    };
+
+    let search_box = search.then(|| {
+        view! {
+            <input
+                type="search"
+                class="h-10 w-full min-w-48 flex-1 rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background placeholder:text-muted-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2 sm:w-auto"
+                placeholder=move || search_placeholder.clone().unwrap_or_else(|| i18n.t("filter.search"))
+                aria-label=move || i18n.t("filter.search")
+                prop:value=move || filters.with(|f| f.text.clone().unwrap_or_default())
+                on:input=move |ev| {
+                    let text = event_target_value(&ev);
+                    update(&|f| f.set_text(&text));
+                }
+            />
+        }
+    });
+
+    let facet_labels: Vec<(&'static str, Facet)> = facets.iter().map(|facet| (facet.id, facet.clone())).collect();
+    let facet_selects = facets
+        .into_iter()
+        .map(|facet| view! { <FacetSelect facet=facet filters=filters on_change=notify /> })
+        .collect_view();
+
+    let date_inputs = date_label.clone().map(|label| {
+        let date_value = move |pick: fn(&DateRange) -> Option<NaiveDate>| {
+            move || filters.with(|f| pick(&f.date_range).map(|d| d.to_string()).unwrap_or_default())
+        };
+        view! {
+            <fieldset class="flex items-center gap-2 text-sm">
+                <legend class="sr-only">{label}</legend>
+                <input
+                    type="date"
+                    class="h-10 rounded-md border border-input bg-background px-2 text-sm"
+                    aria-label=move || i18n.t("filter.from")
+                    prop:value=date_value(|range| range.from)
+                    on:change=move |ev| {
+                        let from = parse_date(&event_target_value(&ev));
+                        update(&|f| f.date_range.from = from);
+                    }
+                />
+                <span class="text-muted-foreground" aria-hidden="true">"–"</span>
+                <input
+                    type="date"
+                    class="h-10 rounded-md border border-input bg-background px-2 text-sm"
+                    aria-label=move || i18n.t("filter.until")
+                    prop:value=date_value(|range| range.until)
+                    on:change=move |ev| {
+                        let until = parse_date(&event_target_value(&ev));
+                        update(&|f| f.date_range.until = until);
+                    }
+                />
+            </fieldset>
+        }
+    });
+
+    let chip = move |label: String, remove: Box<dyn Fn(&mut FilterState) + Send + Sync>| {
+        let remove_label = label.clone();
+        view! {
+            <span class="inline-flex items-center gap-1 rounded-full border bg-secondary px-2.5 py-0.5 text-xs font-medium text-secondary-foreground">
+                {label}
+                <button
+                    type="button"
+                    class="rounded-full px-1 leading-none hover:bg-secondary-foreground/10 focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring"
+                    aria-label=move || i18n.t_with("filter.remove", &[("label", remove_label.as_str())])
+                    on:click=move |_| update(&*remove)
+                >
+                    "×"
+                </button>
+            </span>
+        }
+    };
+
+    let chips = move || {
+        let state = filters.get();
+        if state.is_empty() {
+            return None;
+        }
+        let mut views = Vec::new();
+        if let Some(text) = state.text.clone() {
+            views.push(chip(format!("“{}”", text), Box::new(|f| f.text = None)));
+        }
+        for (id, facet) in &facet_labels {
+            let id = *id;
+            for value in state.values(id) {
+                let label = format!("{}: {}", facet.label, facet.option_label(value));
+                let value = value.clone();
+                views.push(chip(label, Box::new(move |f| f.remove_value(id, &value))));
+            }
+        }
+        if !state.date_range.is_empty() {
+            let format = |date: Option<NaiveDate>| date.map_or_else(|| "…".to_string(), |d| d.format("%d %b %Y").to_string());
+            let label = format!(
+                "{}: {} – {}",
+                date_label.clone().unwrap_or_else(|| i18n.t("filter.date")),
+                format(state.date_range.from),
+                format(state.date_range.until),
+            );
+            views.push(chip(label, Box::new(|f| f.date_range = DateRange::default())));
+        }
+
+        Some(view! {
+            <div class="flex flex-wrap items-center gap-2" aria-live="polite">
+                {views}
+                <button
+                    type="button"
+                    class="text-xs font-medium text-muted-foreground underline-offset-4 hover:text-foreground hover:underline"
+                    on:click=move |_| update(&|f| f.clear())
+                >
+                    {move || i18n.t("filter.clear_all")}
+                </button>
+            </div>
+        })
+    };
+
+    view! {
+        <div class=cn(&["space-y-2", class.unwrap_or("")]) role="search">
+            <div class="flex flex-wrap items-center gap-2">
+                {search_box}
+                {facet_selects}
+                {date_inputs}
+            </div>
+            {chips}
+        </div>
+    }
+}
@@ -1,36 +1,180 @@
 use leptos::prelude::*;
-use crate::ui::{Icon, IconSize, Priority, HealthcareStatus, StatsTrend, CardVariant};
+use chrono::{DateTime, Utc};
+use crate::ui::{activate_on_key, cn, use_i18n, Icon, IconSize, Priority, HealthcareStatus, StatsTrend, CardVariant, FOCUS_RING};
+use crate::errors::{SharedError, SharedResult};
+use crate::ids::MedicalRecordNumber;
+use crate::models::{Allergy, AllergySeverity, AllergyVerification, BloodType, Gender};
+use crate::units::{Analyte, Quantity, Unit};
+use crate::utils::datetime::{format_date, format_relative, format_time, parse_timezone, DEFAULT_TIMEZONE};
+use crate::vitals::{AgeGroup, VitalSeverity, VitalSign};
 
 #[derive(Debug, Clone)]
 pub struct PatientInfo {
     pub id: String,
     pub name: String,
     pub age: u8,
-    pub gender: String,
-    pub blood_type: Option<String>,
-    pub medical_id: String,
+    pub gender: Gender,
+    pub blood_type: Option<BloodType>,
+    pub medical_id: MedicalRecordNumber,
     pub avatar_url: Option<String>,
     pub status: HealthcareStatus,
     pub last_visit: Option<String>,
     pub next_appointment: Option<String>,
     pub primary_doctor: Option<String>,
     pub emergency_contact: Option<String>,
-    pub allergies: Vec<String>,
+    pub allergies: Vec<Allergy>,
     pub chronic_conditions: Vec<String>,
 }
 
+/// Display data for one allergy chip on a patient card
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllergyChip {
+    pub label: String,
+    /// Tooltip with severity and verification, e.g. "severe, unconfirmed"
+    pub title: String,
+    pub class: &'static str,
+}
+
+impl From<&Allergy> for AllergyChip {
+    fn from(allergy: &Allergy) -> Self {
+        let class = match allergy.severity {
+            AllergySeverity::LifeThreatening => "bg-red-600 text-white",
+            AllergySeverity::Severe => "bg-red-100 text-red-800 ring-1 ring-red-300",
+            AllergySeverity::Moderate => "bg-red-100 text-red-800",
+            AllergySeverity::Mild => "bg-orange-50 text-orange-800",
+        };
+        let mut title = allergy.severity.as_str().to_string();
+        if allergy.verification == AllergyVerification::Unconfirmed {
+            title.push_str(", unconfirmed");
+        }
+        Self {
+            label: allergy.label(),
+            title,
+            class,
+        }
+    }
+}
+
+impl PatientInfo {
+    /// Chips for active allergies, most severe first
+    pub fn allergy_chips(&self) -> Vec<AllergyChip> {
+        let mut active: Vec<&Allergy> = self.allergies.iter().filter(|allergy| allergy.is_active()).collect();
+        active.sort_by_key(|allergy| std::cmp::Reverse(allergy.severity));
+        active.into_iter().map(AllergyChip::from).collect()
+    }
+}
+
+/// What a metric card shows. Vital signs keep their typed reading so text,
+/// normal range and status are all derived from it; anything else (lab
+/// results, scores) is a legacy free-form reading with a hand-set status.
+#[derive(Debug, Clone)]
+pub enum MetricReading {
+    Vital {
+        vital: VitalSign,
+        age: AgeGroup,
+    },
+    Other {
+        name: String,
+        value: String,
+        unit: String,
+        normal_range: Option<String>,
+        status: HealthcareStatus,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct HealthMetric {
-    pub name: String,
-    pub value: String,
-    pub unit: String,
-    pub normal_range: Option<String>,
-    pub status: HealthcareStatus,
+    pub reading: MetricReading,
     pub trend: StatsTrend,
     pub last_updated: String,
     pub icon: String,
 }
 
+impl HealthMetric {
+    /// Card data for a typed reading; status and normal range follow from
+    /// the patient's age instead of being set by hand
+    pub fn from_vital(vital: VitalSign, age: AgeGroup, last_updated: impl Into<String>) -> Self {
+        Self {
+            icon: vital_icon(&vital).to_string(),
+            reading: MetricReading::Vital { vital, age },
+            trend: StatsTrend::Stable,
+            last_updated: last_updated.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match &self.reading {
+            MetricReading::Vital { vital, .. } => vital.name(),
+            MetricReading::Other { name, .. } => name,
+        }
+    }
+
+    /// Value without its unit, e.g. "120/80"
+    pub fn value_text(&self) -> String {
+        match &self.reading {
+            MetricReading::Vital { vital, .. } => vital.value_text(),
+            MetricReading::Other { value, .. } => value.clone(),
+        }
+    }
+
+    pub fn unit(&self) -> &str {
+        match &self.reading {
+            MetricReading::Vital { vital, .. } => vital.unit_symbol(),
+            MetricReading::Other { unit, .. } => unit,
+        }
+    }
+
+    pub fn normal_range(&self) -> Option<String> {
+        match &self.reading {
+            MetricReading::Vital { vital, age } => Some(vital.normal_range_text(*age)),
+            MetricReading::Other { normal_range, .. } => normal_range.clone(),
+        }
+    }
+
+    pub fn status(&self) -> HealthcareStatus {
+        match &self.reading {
+            MetricReading::Vital { vital, age } => vital.evaluate(*age),
+            MetricReading::Other { status, .. } => *status,
+        }
+    }
+
+    /// Typed value when the reading is a single number in a known unit; blood
+    /// pressure ("120/80") and unitless scores give `None`
+    pub fn quantity(&self) -> Option<Quantity> {
+        match &self.reading {
+            MetricReading::Vital { vital: VitalSign::Temperature(quantity), .. } => Some(*quantity),
+            _ => Quantity::parse(&self.value_text(), self.unit())
+                .map(|quantity| quantity.with_analyte(Analyte::infer(self.name()))),
+        }
+    }
+
+    /// Numeric value in `unit`; errors rather than mixing units
+    pub fn value_in(&self, unit: Unit) -> SharedResult<f64> {
+        self.quantity()
+            .ok_or_else(|| SharedError::ValidationError(format!("{} has no value in a known unit", self.name())))?
+            .value_in(unit)
+    }
+}
+
+impl VitalSign {
+    /// Card status for the reading against age-adjusted ranges
+    pub fn evaluate(&self, age: AgeGroup) -> HealthcareStatus {
+        match self.severity(age) {
+            VitalSeverity::Normal => HealthcareStatus::Stable,
+            VitalSeverity::Abnormal => HealthcareStatus::NeedsAttention,
+            VitalSeverity::Critical => HealthcareStatus::Critical,
+        }
+    }
+}
+
+fn vital_icon(vital: &VitalSign) -> &'static str {
+    match vital {
+        VitalSign::HeartRate(_) | VitalSign::SpO2(_) => "medical-heart",
+        VitalSign::Temperature(_) => "medical-thermometer",
+        VitalSign::BloodPressure { .. } | VitalSign::RespiratoryRate(_) => "medical-stethoscope",
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AppointmentInfo {
     pub id: String,
@@ -40,6 +184,8 @@ pub struct AppointmentInfo {
     pub appointment_type: String,
     pub date: String,
     pub time: String,
+    /// Exact start; when set, cards show it in the viewer's timezone instead of `date`/`time`
+    pub starts_at: Option<DateTime<Utc>>,
     pub duration: String,
     pub location: String,
     pub status: AppointmentStatus,
@@ -97,11 +243,12 @@ pub fn PatientCard(
     #[prop()] patient: PatientInfo,
     #[prop(optional)] variant: Option<CardVariant>,
     #[prop(optional)] compact: Option<bool>,
-    #[prop(optional)] on_click: Option<Box<dyn Fn(String)>>,
+    #[prop(optional)] on_click: Option<Callback<String>>,
 ) -> impl IntoView {
     let variant = variant.unwrap_or(CardVariant::Patient);
     let compact = compact.unwrap_or(false);
     let patient_id = patient.id.clone();
+    let allergy_chips = patient.allergy_chips();
     
     let card_class = match variant {
         CardVariant::Emergency => "bg-gradient-to-br from-red-50 to-red-100 border-red-200 shadow-lg",
@@ -116,12 +263,30 @@ pub fn PatientCard(
         _ => "bg-gray-500",
     };
     
+    // Clickable cards behave as buttons: focusable and activated by Enter/Space
+    let clickable = on_click.is_some();
+    let open = move || {
+        if let Some(callback) = on_click {
+            callback.run(patient_id.clone());
+        }
+    };
+    let open_on_key = open.clone();
+
     view! {
         <div 
-            class=format!("rounded-xl border-2 p-6 hover:shadow-lg transition-all duration-200 cursor-pointer {}", card_class)
-            on:click=move |_| {
-                if let Some(callback) = &on_click {
-                    callback(patient_id.clone());
+            class=cn(&[
+                "rounded-xl border-2 p-6 hover:shadow-lg transition-all duration-200",
+                card_class,
+                if clickable { "cursor-pointer" } else { "" },
+                if clickable { FOCUS_RING } else { "" },
+            ])
+            role=clickable.then_some("button")
+            tabindex=clickable.then_some("0")
+            aria-label=clickable.then(|| format!("{}, {}", patient.name, patient.medical_id))
+            on:click=move |_| open()
+            on:keydown=move |ev| {
+                if activate_on_key(&ev) {
+                    open_on_key();
                 }
             }
         >
@@ -151,9 +316,9 @@ pub fn PatientCard(
                             <div class="flex-1 min-w-0">
                                 <h3 class="font-semibold text-slate-900 truncate">{patient.name.clone()}</h3>
                                 <p class="text-sm text-slate-600">
-                                    {patient.age} years • {patient.gender.clone()}
+                                    {patient.age} years • {patient.gender.to_string()}
                                 </p>
-                                <p class="text-xs text-slate-500 font-mono">{patient.medical_id.clone()}</p>
+                                <p class="text-xs text-slate-500 font-mono">{patient.medical_id.to_string()}</p>
                             </div>
                             
                             {
@@ -161,7 +326,7 @@ pub fn PatientCard(
                                     view! {
                                         <div class="text-right">
                                             <div class="bg-red-100 text-red-800 px-3 py-1 rounded-full text-sm font-semibold">
-                                                {blood_type.clone()}
+                                                {blood_type.to_string()}
                                             </div>
                                         </div>
                                     }.into_any()
@@ -200,14 +365,14 @@ pub fn PatientCard(
                                     <div class="flex items-center space-x-4 text-sm text-slate-600 mb-2">
                                         <span>{patient.age} years old</span>
                                         <span>•</span>
-                                        <span>{patient.gender.clone()}</span>
+                                        <span>{patient.gender.to_string()}</span>
                                         {
                                             if let Some(blood_type) = &patient.blood_type {
                                                 view! {
                                                     <>
                                                         <span>"•"</span>
                                                         <span class="bg-red-100 text-red-800 px-2 py-1 rounded-full text-xs font-semibold">
-                                                            {blood_type.clone()}
+                                                            {blood_type.to_string()}
                                                         </span>
                                                     </>
                                                 }.into_any()
@@ -216,7 +381,7 @@ pub fn PatientCard(
                                             }
                                         }
                                     </div>
-                                    <p class="text-sm text-slate-500 font-mono">ID: {patient.medical_id.clone()}</p>
+                                    <p class="text-sm text-slate-500 font-mono">ID: {patient.medical_id.to_string()}</p>
                                 </div>
                             </div>
                             
@@ -289,11 +454,11 @@ pub fn PatientCard(
                             
                             // Allergies and conditions
                             {
-                                if !patient.allergies.is_empty() || !patient.chronic_conditions.is_empty() {
+                                if !allergy_chips.is_empty() || !patient.chronic_conditions.is_empty() {
                                     view! {
                                         <div class="pt-4 border-t border-slate-200 space-y-3">
                                             {
-                                                if !patient.allergies.is_empty() {
+                                                if !allergy_chips.is_empty() {
                                                     view! {
                                                         <div class="bg-red-50 border border-red-200 rounded-lg p-3">
                                                             <div class="flex items-center space-x-2 mb-2">
@@ -301,9 +466,9 @@ pub fn PatientCard(
                                                                 <span class="font-semibold text-red-800">Allergies</span>
                                                             </div>
                                                             <div class="flex flex-wrap gap-2">
-                                                                {patient.allergies.iter().map(|allergy| view! {
-                                                                    <span class="bg-red-100 text-red-800 px-2 py-1 rounded-full text-xs font-medium">
-                                                                        {allergy.clone()}
+                                                                {allergy_chips.iter().map(|chip| view! {
+                                                                    <span class=format!("px-2 py-1 rounded-full text-xs font-medium {}", chip.class) title=chip.title.clone()>
+                                                                        {chip.label.clone()}
                                                                     </span>
                                                                 }).collect_view()}
                                                             </div>
@@ -356,7 +521,7 @@ pub fn HealthMetricCard(
 ) -> impl IntoView {
     let compact = compact.unwrap_or(false);
     
-    let status_colors = match metric.status {
+    let status_colors = match metric.status() {
         HealthcareStatus::Critical => ("bg-red-50 border-red-200", "text-red-800", "text-red-600"),
         HealthcareStatus::NeedsAttention => ("bg-yellow-50 border-yellow-200", "text-yellow-800", "text-yellow-600"),
         HealthcareStatus::Stable => ("bg-green-50 border-green-200", "text-green-800", "text-green-600"),
@@ -368,7 +533,7 @@ pub fn HealthMetricCard(
             <div class="flex items-center justify-between mb-3">
                 <div class="flex items-center space-x-2">
                     <Icon name=metric.icon.clone() size=IconSize::Md class=status_colors.2.to_string() />
-                    <h3 class=format!("font-semibold {}", status_colors.1)>{metric.name.clone()}</h3>
+                    <h3 class=format!("font-semibold {}", status_colors.1)>{metric.name().to_string()}</h3>
                 </div>
                 
                 <div class="flex items-center space-x-1">
@@ -381,18 +546,18 @@ pub fn HealthMetricCard(
             <div class="space-y-2">
                 <div class="flex items-baseline space-x-2">
                     <span class=format!("text-2xl font-bold {}", status_colors.1)>
-                        {metric.value.clone()}
+                        {metric.value_text()}
                     </span>
                     <span class=format!("text-sm {}", status_colors.2)>
-                        {metric.unit.clone()}
+                        {metric.unit().to_string()}
                     </span>
                 </div>
                 
                 {
-                    if let Some(normal_range) = &metric.normal_range {
+                    if let Some(normal_range) = metric.normal_range() {
                         view! {
                             <p class=format!("text-xs {}", status_colors.2)>
-                                Normal: {normal_range.clone()}
+                                Normal: {normal_range}
                             </p>
                         }.into_any()
                     } else {
@@ -420,12 +585,27 @@ pub fn HealthMetricCard(
 pub fn AppointmentCard(
     #[prop()] appointment: AppointmentInfo,
     #[prop(optional)] compact: Option<bool>,
-    #[prop(optional)] on_click: Option<Box<dyn Fn(String)>>,
+    #[prop(optional)] on_click: Option<Callback<String>>,
     #[prop(optional)] show_patient: Option<bool>,
+    /// IANA timezone for `starts_at` (default: Asia/Kolkata)
+    #[prop(optional, into)] timezone: Option<String>,
 ) -> impl IntoView {
     let compact = compact.unwrap_or(false);
     let show_patient = show_patient.unwrap_or(true);
     let appointment_id = appointment.id.clone();
+    let locale = use_i18n().locale();
+    let tz = timezone
+        .as_deref()
+        .and_then(|tz| parse_timezone(tz).ok())
+        .unwrap_or(DEFAULT_TIMEZONE);
+    let (date, time, relative) = match appointment.starts_at {
+        Some(starts_at) => (
+            format_date(&starts_at, &tz, locale),
+            format_time(&starts_at, &tz, locale),
+            Some(format_relative(&starts_at, &Utc::now(), &tz, locale)),
+        ),
+        None => (appointment.date.clone(), appointment.time.clone(), None),
+    };
     
     let priority_colors = match appointment.priority {
         Priority::Emergency => ("bg-red-50 border-red-300", "text-red-800", "text-red-600"),
@@ -433,12 +613,29 @@ pub fn AppointmentCard(
         _ => ("bg-blue-50 border-blue-200", "text-blue-800", "text-blue-600"),
     };
     
+    let clickable = on_click.is_some();
+    let open = move || {
+        if let Some(callback) = on_click {
+            callback.run(appointment_id.clone());
+        }
+    };
+    let open_on_key = open.clone();
+
     view! {
         <div 
-            class=format!("rounded-lg border-2 p-4 hover:shadow-lg transition-all duration-200 cursor-pointer {}", priority_colors.0)
-            on:click=move |_| {
-                if let Some(callback) = &on_click {
-                    callback(appointment_id.clone());
+            class=cn(&[
+                "rounded-lg border-2 p-4 hover:shadow-lg transition-all duration-200",
+                priority_colors.0,
+                if clickable { "cursor-pointer" } else { "" },
+                if clickable { FOCUS_RING } else { "" },
+            ])
+            role=clickable.then_some("button")
+            tabindex=clickable.then_some("0")
+            aria-label=clickable.then(|| format!("{} with {}, {} {}", appointment.appointment_type, appointment.doctor_name, date, time))
+            on:click=move |_| open()
+            on:keydown=move |ev| {
+                if activate_on_key(&ev) {
+                    open_on_key();
                 }
             }
         >
@@ -497,11 +694,14 @@ pub fn AppointmentCard(
             <div class="grid grid-cols-2 gap-4 text-sm">
                 <div class="flex items-center space-x-2">
                     <Icon name="interface-calendar".to_string() size=IconSize::Sm class="text-slate-500".to_string() />
-                    <span class="text-slate-700">{appointment.date.clone()}</span>
+                    <span class="text-slate-700">{date.clone()}</span>
                 </div>
                 <div class="flex items-center space-x-2">
                     <Icon name="interface-clock".to_string() size=IconSize::Sm class="text-slate-500".to_string() />
-                    <span class="text-slate-700">{appointment.time.clone()}</span>
+                    <span class="text-slate-700">
+                        {time.clone()}
+                        {relative.map(|relative| view! { <span class="text-slate-500">" · " {relative}</span> })}
+                    </span>
                 </div>
                 <div class="flex items-center space-x-2">
                     <Icon name="location-pin".to_string() size=IconSize::Sm class="text-slate-500".to_string() />
@@ -0,0 +1,19 @@
+// Emergency icons: alerts, calls and ambulance dispatch
+
+pub(super) const ICONS: &[(&str, &str)] = &[
+    ("emergency-alert", EMERGENCY_ALERT),
+    ("emergency-call", EMERGENCY_CALL),
+    ("emergency-ambulance", EMERGENCY_AMBULANCE),
+    ("emergency-siren", EMERGENCY_SIREN),
+    ("emergency-shield", EMERGENCY_SHIELD),
+];
+
+const EMERGENCY_ALERT: &str = r#"<path d="M12 2L1 21h22L12 2zm0 3.99L19.53 19H4.47L12 5.99zM11 16h2v2h-2zm0-6h2v4h-2z"/>"#;
+
+const EMERGENCY_CALL: &str = r#"<path d="M20.01 15.38c-1.23 0-2.42-.2-3.53-.56-.35-.12-.74-.03-1.01.24l-1.57 1.97c-2.83-1.35-5.48-3.9-6.89-6.83l1.95-1.66c.27-.28.35-.67.24-1.02-.37-1.11-.56-2.3-.56-3.53 0-.54-.45-.99-.99-.99H4.19C3.65 3 3 3.24 3 3.99 3 13.28 10.73 21 20.01 21c.71 0 .99-.63.99-1.18v-3.45c0-.54-.45-.99-.99-.99z"/>"#;
+
+const EMERGENCY_AMBULANCE: &str = r#"<path d="M18.92 6.01C18.72 5.42 18.16 5 17.5 5h-11C5.84 5 5.28 5.42 5.08 6.01L3 12v8c0 .55.45 1 1 1h1c.55 0 1-.45 1-1v-1h12v1c0 .55.45 1 1 1h1c.55 0 1-.45 1-1v-8l-2.08-5.99zM6.5 16c-.83 0-1.5-.67-1.5-1.5S5.67 13 6.5 13s1.5.67 1.5 1.5S7.33 16 6.5 16zm11 0c-.83 0-1.5-.67-1.5-1.5s.67-1.5 1.5-1.5 1.5.67 1.5 1.5-.67 1.5-1.5 1.5zM5 11l1.5-4.5h11L19 11H5z"/>"#;
+
+const EMERGENCY_SIREN: &str = r#"<path d="M18 10c0-4.42-3.58-8-8-8s-8 3.58-8 8c0 1.95.7 3.73 1.86 5.12L5 16.24C3.74 14.78 3 12.48 3 10c0-4.97 4.03-9 9-9s9 4.03 9 9c0 2.48-.74 4.78-2 6.24l1.14 1.12C21.3 13.73 22 11.95 22 10zM12 6c-2.21 0-4 1.79-4 4s1.79 4 4 4 4-1.79 4-4-1.79-4-4-4z"/>"#;
+
+const EMERGENCY_SHIELD: &str = r#"<path d="M12 1L3 5v6c0 5.55 3.84 10.74 9 12 5.16-1.26 9-6.45 9-12V5l-9-4zm0 10.99h7c-.53 4.12-3.28 7.79-7 8.94V12H5V6.3l7-3.11v8.8z"/>"#;
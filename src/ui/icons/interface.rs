@@ -0,0 +1,78 @@
+// Interface icons: navigation, actions and people
+
+pub(super) const ICONS: &[(&str, &str)] = &[
+    ("user-doctor", USER_DOCTOR),
+    ("user-patient", USER_PATIENT),
+    ("user-nurse", USER_NURSE),
+    ("user-admin", USER_ADMIN),
+    ("user-organization", USER_ORGANIZATION),
+    ("user-group", USER_GROUP),
+    ("interface-dashboard", INTERFACE_DASHBOARD),
+    ("interface-calendar", INTERFACE_CALENDAR),
+    ("interface-chat", INTERFACE_CHAT),
+    ("interface-settings", INTERFACE_SETTINGS),
+    ("interface-search", INTERFACE_SEARCH),
+    ("interface-location", INTERFACE_LOCATION),
+    ("interface-clock", INTERFACE_CLOCK),
+    ("interface-phone", INTERFACE_PHONE),
+    ("interface-mail", INTERFACE_MAIL),
+    ("interface-logout", INTERFACE_LOGOUT),
+    ("interface-menu", INTERFACE_MENU),
+    ("interface-close", INTERFACE_CLOSE),
+    ("interface-plus", INTERFACE_PLUS),
+    ("interface-minus", INTERFACE_MINUS),
+    ("interface-chevron-right", INTERFACE_CHEVRON_RIGHT),
+    ("interface-chevron-left", INTERFACE_CHEVRON_LEFT),
+    ("interface-chevron-up", INTERFACE_CHEVRON_UP),
+    ("interface-chevron-down", INTERFACE_CHEVRON_DOWN),
+];
+
+// User Icons
+const USER_DOCTOR: &str = r#"<path d="M14 6V4h-4v2c0 1.1.9 2 2 2s2-.9 2-2zm-2 4c-1.1 0-2-.9-2-2V6H8v2c0 2.21 1.79 4 4 4s4-1.79 4-4V6h-2v2c0 1.1-.9 2-2 2zm0 2c-2.67 0-8 1.34-8 4v2h16v-2c0-2.66-5.33-4-8-4z"/>"#;
+
+const USER_PATIENT: &str = r#"<path d="M12 12c2.21 0 4-1.79 4-4s-1.79-4-4-4-4 1.79-4 4 1.79 4 4 4zm0 2c-2.67 0-8 1.34-8 4v2h16v-2c0-2.66-5.33-4-8-4z"/>"#;
+
+const USER_NURSE: &str = r#"<path d="M12 6c1.1 0 2-.9 2-2 0-.38-.1-.73-.29-1.03L12 1.27l-1.71 1.7C10.1 3.27 10 3.62 10 4c0 1.1.9 2 2 2zm0 2c-2.67 0-8 1.34-8 4v1h16v-1c0-2.66-5.33-4-8-4zm0 6c-1.1 0-2 .9-2 2s.9 2 2 2 2-.9 2-2-.9-2-2-2z"/>"#;
+
+const USER_ADMIN: &str = r#"<path d="M12 1L3 5v6c0 5.55 3.84 10.74 9 12 5.16-1.26 9-6.45 9-12V5l-9-4zm-2 16l-4-4 1.41-1.41L10 14.17l6.59-6.59L18 9l-8 8z"/>"#;
+
+const USER_ORGANIZATION: &str = r#"<path d="M12 7V3H2v18h20V7H12zM6 19H4v-2h2v2zm0-4H4v-2h2v2zm0-4H4V9h2v2zm0-4H4V5h2v2zm4 12H8v-2h2v2zm0-4H8v-2h2v2zm0-4H8V9h2v2zm0-4H8V5h2v2zm10 12h-8v-2h2v-2h-2v-2h2v-2h-2V9h8v10z"/>"#;
+
+const USER_GROUP: &str = r#"<path d="M16 4c0-1.11.89-2 2-2s2 .89 2 2-.89 2-2 2-2-.89-2-2zM4 4c0-1.11.89-2 2-2s2 .89 2 2-.89 2-2 2-2-.89-2-2zm5 4c0-1.11.89-2 2-2s2 .89 2 2-.89 2-2 2-2-.89-2-2zm3 7v-2c0-1.1-.9-2-2-2H8c-1.1 0-2 .9-2 2v2H4v4h16v-4h-2z"/>"#;
+
+// Interface Icons
+const INTERFACE_DASHBOARD: &str = r#"<path d="M3 13h8V3H3v10zm0 8h8v-6H3v6zm10 0h8V11h-8v10zm0-18v6h8V3h-8z"/>"#;
+
+const INTERFACE_CALENDAR: &str = r#"<path d="M19 3h-1V1h-2v2H8V1H6v2H5c-1.11 0-1.99.9-1.99 2L3 19c0 1.1.89 2 2 2h14c1.1 0 2-.9 2-2V5c0-1.1-.9-2-2-2zm0 16H5V8h14v11zM7 10h5v5H7z"/>"#;
+
+const INTERFACE_CHAT: &str = r#"<path d="M20 2H4c-1.1 0-2 .9-2 2v12c0 1.1.9 2 2 2h14l4 4V4c0-1.1-.9-2-2-2zm-2 12H6v-2h12v2zm0-3H6V9h12v2zm0-3H6V6h12v2z"/>"#;
+
+const INTERFACE_SETTINGS: &str = r#"<path d="M19.14,12.94c0.04-0.3,0.06-0.61,0.06-0.94c0-0.32-0.02-0.64-0.07-0.94l2.03-1.58c0.18-0.14,0.23-0.41,0.12-0.61 l-1.92-3.32c-0.12-0.22-0.37-0.29-0.59-0.22l-2.39,0.96c-0.5-0.38-1.03-0.7-1.62-0.94L14.4,2.81c-0.04-0.24-0.24-0.41-0.48-0.41 h-3.84c-0.24,0-0.43,0.17-0.47,0.41L9.25,5.35C8.66,5.59,8.12,5.92,7.63,6.29L5.24,5.33c-0.22-0.08-0.47,0-0.59,0.22L2.74,8.87 C2.62,9.08,2.66,9.34,2.86,9.48l2.03,1.58C4.84,11.36,4.82,11.68,4.82,12s0.02,0.64,0.07,0.94l-2.03,1.58 c-0.18,0.14-0.23,0.41-0.12,0.61l1.92,3.32c0.12,0.22,0.37,0.29,0.59,0.22l2.39-0.96c0.5,0.38,1.03,0.7,1.62,0.94l0.36,2.54 c0.05,0.24,0.24,0.41,0.48,0.41h3.84c0.24,0,0.44-0.17,0.47-0.41l0.36-2.54c0.59-0.24,1.13-0.56,1.62-0.94l2.39,0.96 c0.22,0.08,0.47,0,0.59-0.22l1.92-3.32c0.12-0.22,0.07-0.47-0.12-0.61L19.14,12.94z M12,15.6c-1.98,0-3.6-1.62-3.6-3.6 s1.62-3.6,3.6-3.6s3.6,1.62,3.6,3.6S13.98,15.6,12,15.6z"/>"#;
+
+const INTERFACE_SEARCH: &str = r#"<path d="M15.5 14h-.79l-.28-.27C15.41 12.59 16 11.11 16 9.5 16 5.91 13.09 3 9.5 3S3 5.91 3 9.5 5.91 16 9.5 16c1.61 0 3.09-.59 4.23-1.57l.27.28v.79l5 4.99L20.49 19l-4.99-5zm-6 0C7.01 14 5 11.99 5 9.5S7.01 5 9.5 5 14 7.01 14 9.5 11.99 14 9.5 14z"/>"#;
+
+const INTERFACE_LOCATION: &str = r#"<path d="M12 2C8.13 2 5 5.13 5 9c0 5.25 7 13 7 13s7-7.75 7-13c0-3.87-3.13-7-7-7zm0 9.5c-1.38 0-2.5-1.12-2.5-2.5s1.12-2.5 2.5-2.5 2.5 1.12 2.5 2.5-1.12 2.5-2.5 2.5z"/>"#;
+
+const INTERFACE_CLOCK: &str = r#"<path d="M11.99 2C6.47 2 2 6.48 2 12s4.47 10 9.99 10C17.52 22 22 17.52 22 12S17.52 2 11.99 2zM12 20c-4.42 0-8-3.58-8-8s3.58-8 8-8 8 3.58 8 8-3.58 8-8 8zm.5-13H11v6l5.25 3.15.75-1.23-4.5-2.67z"/>"#;
+
+const INTERFACE_PHONE: &str = r#"<path d="M6.62 10.79c1.44 2.83 3.76 5.14 6.59 6.59l2.2-2.2c.27-.27.67-.36 1.02-.24 1.12.37 2.33.57 3.57.57.55 0 1 .45 1 1V20c0 .55-.45 1-1 1-9.39 0-17-7.61-17-17 0-.55.45-1 1-1h3.5c.55 0 1 .45 1 1 0 1.25.2 2.45.57 3.57.11.35.03.74-.25 1.02l-2.2 2.2z"/>"#;
+
+const INTERFACE_MAIL: &str = r#"<path d="M20 4H4c-1.1 0-1.99.9-1.99 2L2 18c0 1.1.9 2 2 2h16c1.1 0 2-.9 2-2V6c0-1.1-.9-2-2-2zm0 4l-8 5-8-5V6l8 5 8-5v2z"/>"#;
+
+const INTERFACE_LOGOUT: &str = r#"<path d="M17 7l-1.41 1.41L18.17 11H8v2h10.17l-2.58 2.58L17 17l5-5zM4 5h8V3H4c-1.1 0-2 .9-2 2v14c0 1.1.9 2 2 2h8v-2H4V5z"/>"#;
+
+const INTERFACE_MENU: &str = r#"<path d="M3 18h18v-2H3v2zm0-5h18v-2H3v2zm0-7v2h18V6H3z"/>"#;
+
+const INTERFACE_CLOSE: &str = r#"<path d="M19 6.41L17.59 5 12 10.59 6.41 5 5 6.41 10.59 12 5 17.59 6.41 19 12 13.41 17.59 19 19 17.59 13.41 12z"/>"#;
+
+const INTERFACE_PLUS: &str = r#"<path d="M19 13h-6v6h-2v-6H5v-2h6V5h2v6h6v2z"/>"#;
+
+const INTERFACE_MINUS: &str = r#"<path d="M19 13H5v-2h14v2z"/>"#;
+
+const INTERFACE_CHEVRON_RIGHT: &str = r#"<path d="M10 6L8.59 7.41 13.17 12l-4.58 4.59L10 18l6-6z"/>"#;
+
+const INTERFACE_CHEVRON_LEFT: &str = r#"<path d="M15.41 7.41L14 6l-6 6 6 6 1.41-1.41L10.83 12z"/>"#;
+
+const INTERFACE_CHEVRON_UP: &str = r#"<path d="M7.41 15.41L12 10.83l4.59 4.58L18 14l-6-6-6 6z"/>"#;
+
+const INTERFACE_CHEVRON_DOWN: &str = r#"<path d="M7.41 8.59L12 13.17l4.59-4.58L18 10l-6 6-6-6 1.41-1.41z"/>"#;
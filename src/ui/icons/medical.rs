@@ -0,0 +1,34 @@
+// Medical icons: clinical equipment, medication and care
+
+pub(super) const ICONS: &[(&str, &str)] = &[
+    ("medical-heart", MEDICAL_HEART),
+    ("medical-stethoscope", MEDICAL_STETHOSCOPE),
+    ("medical-hospital", MEDICAL_HOSPITAL),
+    ("medical-prescription", MEDICAL_PRESCRIPTION),
+    ("medical-syringe", MEDICAL_SYRINGE),
+    ("medical-bag", MEDICAL_BAG),
+    ("medical-cross", MEDICAL_CROSS),
+    ("medical-thermometer", MEDICAL_THERMOMETER),
+    ("medical-bandage", MEDICAL_BANDAGE),
+    ("medical-pill", MEDICAL_PILL),
+];
+
+const MEDICAL_HEART: &str = r#"<path d="M12 21.35l-1.45-1.32C5.4 15.36 2 12.28 2 8.5 2 5.42 4.42 3 7.5 3c1.74 0 3.41.81 4.5 2.09C13.09 3.81 14.76 3 16.5 3 19.58 3 22 5.42 22 8.5c0 3.78-3.4 6.86-8.55 11.54L12 21.35z"/>"#;
+
+const MEDICAL_STETHOSCOPE: &str = r#"<path d="M19.48 12.35c-1.57-4.08-7.16-4.3-5.81-10.23.1-.44.02-.81-.06-.94C13.46 1 13.21 1 12.81 1c-.4 0-.65 0-.8.18-.08.13-.16.5-.06.94 1.35 5.93-4.24 6.15-5.81 10.23C3.97 14.63 2 17.41 2 20.5 2 21.88 3.12 23 4.5 23c1.38 0 2.5-1.12 2.5-2.5 0-1.74-.73-3.26-1.87-4.36C6.27 14.93 8.12 13.5 9.5 13.5c1.38 0 3.23 1.43 4.37 2.64C12.73 17.24 12 18.76 12 20.5c0 1.38 1.12 2.5 2.5 2.5s2.5-1.12 2.5-2.5c0-3.09-1.97-5.87-4.52-8.15z"/>"#;
+
+const MEDICAL_HOSPITAL: &str = r#"<path d="M19 8h-2V6c0-1.1-.9-2-2-2H9c-1.1 0-2 .9-2 2v2H5c-1.1 0-2 .9-2 2v8c0 1.1.9 2 2 2h14c1.1 0 2-.9 2-2v-8c0-1.1-.9-2-2-2zM9 6h6v2H9V6zm6 10h-2v2h-2v-2H9v-2h2v-2h2v2h2v2z"/>"#;
+
+const MEDICAL_PRESCRIPTION: &str = r#"<path d="M14 2H6c-1.1 0-2 .9-2 2v16c0 1.1.89 2 2 2h12c1.1 0 2-.9 2-2V8l-6-6zm4 18H6V4h7v5h5v11zm-7-9h-1v1h1v1h-1v1h-1v-1H8v-1h1v-1H8v-1h1V9h1v1h1v1z"/>"#;
+
+const MEDICAL_SYRINGE: &str = r#"<path d="M19.5 3.5L18 2l-1.5 1.5L15 2l-1.5 1.5L12 2l-1.5 1.5L9 2l-1.5 1.5-1.5-1.5-1.5 1.5 1.5 1.5-1.5 1.5 1.5 1.5 1.5-1.5 1.5 1.5 1.5-1.5 1.5 1.5 1.5-1.5 1.5 1.5 1.5-1.5-1.5-1.5 1.5-1.5z"/>"#;
+
+const MEDICAL_BAG: &str = r#"<path d="M20 6h-2.18c.11-.31.18-.65.18-1a2.996 2.996 0 0 0-5.5-1.65l-.5.67-.5-.68C10.96 2.54 10.05 2 9 2 7.34 2 6 3.34 6 5c0 .35.07.69.18 1H4c-1.11 0-2 .89-2 2v11c0 1.11.89 2 2 2h16c1.11 0 2-.89 2-2V8c0-1.11-.89-2-2-2zM9 4c.55 0 1 .45 1 1s-.45 1-1 1-1-.45-1-1 .45-1 1-1zm6 0c.55 0 1 .45 1 1s-.45 1-1 1-1-.45-1-1 .45-1 1-1zM4 19V8h16v11H4z"/>"#;
+
+const MEDICAL_CROSS: &str = r#"<path d="M17 7h-4V3c0-.55-.45-1-1-1s-1 .45-1 1v4H7c-.55 0-1 .45-1 1s.45 1 1 1h4v4c0 .55.45 1 1 1s1-.45 1-1V9h4c.55 0 1-.45 1-1s-.45-1-1-1z"/>"#;
+
+const MEDICAL_THERMOMETER: &str = r#"<path d="M15 13V5c0-1.66-1.34-3-3-3S9 3.34 9 5v8c-1.21.91-2 2.37-2 4 0 2.76 2.24 5 5 5s5-2.24 5-5c0-1.63-.79-3.09-2-4zM11 5c0-.55.45-1 1-1s1 .45 1 1h-2z"/>"#;
+
+const MEDICAL_BANDAGE: &str = r#"<path d="M20.73 7.31l-3.04-3.04a.996.996 0 0 0-1.41 0l-8.49 8.49c-.78.78-.78 2.05 0 2.83l3.04 3.04c.78.78 2.05.78 2.83 0l8.49-8.49c.39-.39.39-1.02 0-1.41l-1.42-1.42zm-8.5 8.5l-1.41-1.41 8.49-8.49 1.41 1.41-8.49 8.49z"/>"#;
+
+const MEDICAL_PILL: &str = r#"<path d="M21.5 9h-19C1.67 9 1 9.67 1 10.5v3C1 14.33 1.67 15 2.5 15h19c.83 0 1.5-.67 1.5-1.5v-3c0-.83-.67-1.5-1.5-1.5zm-10 4h-9v-2h9v2z"/>"#;
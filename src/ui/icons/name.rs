@@ -0,0 +1,116 @@
+// Compile-time icon names for the built-in packs
+// Variants only exist when their pack's feature is enabled, so referencing an
+// icon from a disabled pack is a build error rather than a blank glyph
+
+macro_rules! icon_names {
+    ($($feature:literal { $($variant:ident => $name:literal,)* })*) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum IconName {
+            $($(
+                #[cfg(feature = $feature)]
+                $variant,
+            )*)*
+            /// Any other registered icon, e.g. one added with `register_icon`
+            Custom(String),
+        }
+
+        impl IconName {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($(
+                        #[cfg(feature = $feature)]
+                        IconName::$variant => $name,
+                    )*)*
+                    IconName::Custom(name) => name,
+                }
+            }
+
+            /// Built-in variant for `name`, or `Custom` for anything else
+            pub fn parse(name: &str) -> Self {
+                match name {
+                    $($(
+                        #[cfg(feature = $feature)]
+                        $name => IconName::$variant,
+                    )*)*
+                    other => IconName::Custom(other.to_string()),
+                }
+            }
+        }
+    };
+}
+
+icon_names! {
+    "icons-medical" {
+        MedicalHeart => "medical-heart",
+        MedicalStethoscope => "medical-stethoscope",
+        MedicalHospital => "medical-hospital",
+        MedicalPrescription => "medical-prescription",
+        MedicalSyringe => "medical-syringe",
+        MedicalBag => "medical-bag",
+        MedicalCross => "medical-cross",
+        MedicalThermometer => "medical-thermometer",
+        MedicalBandage => "medical-bandage",
+        MedicalPill => "medical-pill",
+    }
+    "icons-emergency" {
+        EmergencyAlert => "emergency-alert",
+        EmergencyCall => "emergency-call",
+        EmergencyAmbulance => "emergency-ambulance",
+        EmergencySiren => "emergency-siren",
+        EmergencyShield => "emergency-shield",
+    }
+    "icons-interface" {
+        UserDoctor => "user-doctor",
+        UserPatient => "user-patient",
+        UserNurse => "user-nurse",
+        UserAdmin => "user-admin",
+        UserOrganization => "user-organization",
+        UserGroup => "user-group",
+        InterfaceDashboard => "interface-dashboard",
+        InterfaceCalendar => "interface-calendar",
+        InterfaceChat => "interface-chat",
+        InterfaceSettings => "interface-settings",
+        InterfaceSearch => "interface-search",
+        InterfaceLocation => "interface-location",
+        InterfaceClock => "interface-clock",
+        InterfacePhone => "interface-phone",
+        InterfaceMail => "interface-mail",
+        InterfaceLogout => "interface-logout",
+        InterfaceMenu => "interface-menu",
+        InterfaceClose => "interface-close",
+        InterfacePlus => "interface-plus",
+        InterfaceMinus => "interface-minus",
+        InterfaceChevronRight => "interface-chevron-right",
+        InterfaceChevronLeft => "interface-chevron-left",
+        InterfaceChevronUp => "interface-chevron-up",
+        InterfaceChevronDown => "interface-chevron-down",
+    }
+    "icons-status" {
+        StatusAvailable => "status-available",
+        StatusBusy => "status-busy",
+        StatusOffline => "status-offline",
+        StatusCompleted => "status-completed",
+        StatusPending => "status-pending",
+        StatusWarning => "status-warning",
+        StatusError => "status-error",
+        StatusSuccess => "status-success",
+    }
+}
+
+impl std::fmt::Display for IconName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for IconName {
+    fn from(name: &str) -> Self {
+        IconName::parse(name)
+    }
+}
+
+impl From<String> for IconName {
+    fn from(name: String) -> Self {
+        IconName::parse(&name)
+    }
+}
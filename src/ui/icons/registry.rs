@@ -0,0 +1,77 @@
+// Process-wide icon registry
+// Icons from enabled packs are seeded on first use; apps add their own at startup
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+type IconMap = HashMap<String, Cow<'static, str>>;
+
+static REGISTRY: OnceLock<RwLock<IconMap>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<IconMap> {
+    REGISTRY.get_or_init(|| {
+        let icons = super::builtin_packs()
+            .iter()
+            .flat_map(|pack| pack.iter())
+            .map(|(name, svg)| (name.to_string(), Cow::Borrowed(*svg)))
+            .collect();
+        RwLock::new(icons)
+    })
+}
+
+/// Lookup and registration of icons rendered by [`Icon`](super::Icon).
+///
+/// Icon markup is the inner content of a `0 0 24 24` SVG viewBox, typically one
+/// or more `<path>` elements. Registering an existing name replaces it, so apps
+/// can also restyle built-in icons.
+pub struct IconRegistry;
+
+impl IconRegistry {
+    pub fn register(name: impl Into<String>, svg: impl Into<Cow<'static, str>>) {
+        if let Ok(mut icons) = registry().write() {
+            icons.insert(name.into(), svg.into());
+        }
+    }
+
+    /// Register several icons at once, e.g. a specialty set like dental or ophthalmology
+    pub fn register_pack<N, S>(icons: impl IntoIterator<Item = (N, S)>)
+    where
+        N: Into<String>,
+        S: Into<Cow<'static, str>>,
+    {
+        if let Ok(mut registry) = registry().write() {
+            registry.extend(icons.into_iter().map(|(name, svg)| (name.into(), svg.into())));
+        }
+    }
+
+    pub fn get(name: &str) -> Option<Cow<'static, str>> {
+        registry().read().ok()?.get(name).cloned()
+    }
+
+    pub fn contains(name: &str) -> bool {
+        registry().read().map(|icons| icons.contains_key(name)).unwrap_or(false)
+    }
+
+    /// Registered icon names in alphabetical order
+    pub fn names() -> Vec<String> {
+        let mut names: Vec<String> = registry()
+            .read()
+            .map(|icons| icons.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+}
+
+pub fn register_icon(name: impl Into<String>, svg: impl Into<Cow<'static, str>>) {
+    IconRegistry::register(name, svg);
+}
+
+pub fn register_pack<N, S>(icons: impl IntoIterator<Item = (N, S)>)
+where
+    N: Into<String>,
+    S: Into<Cow<'static, str>>,
+{
+    IconRegistry::register_pack(icons);
+}
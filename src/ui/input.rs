@@ -9,8 +9,8 @@ pub fn Input(
     #[prop(optional)] value: Option<String>,
     #[prop(optional)] disabled: Option<bool>,
     #[prop(optional)] required: Option<bool>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
-    #[prop(optional)] on_change: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
+    #[prop(optional)] on_change: Option<Callback<String>>,
     #[prop(optional)] id: Option<&'static str>,
     #[prop(optional)] name: Option<&'static str>,
 ) -> impl IntoView {
@@ -34,15 +34,13 @@ pub fn Input(
             id=id.unwrap_or("")
             name=name.unwrap_or("")
             on:input=move |ev| {
-                if let Some(handler) = &on_input {
-                    let value = event_target_value(&ev);
-                    handler(value);
+                if let Some(handler) = on_input {
+                    handler.run(event_target_value(&ev));
                 }
             }
             on:change=move |ev| {
-                if let Some(handler) = &on_change {
-                    let value = event_target_value(&ev);
-                    handler(value);
+                if let Some(handler) = on_change {
+                    handler.run(event_target_value(&ev));
                 }
             }
         />
@@ -74,7 +72,7 @@ pub fn Label(
 #[component]
 pub fn PatientIdInput(
     #[prop(optional)] value: Option<String>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
     #[prop(optional)] required: Option<bool>,
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
@@ -86,7 +84,7 @@ pub fn PatientIdInput(
                 name="patient_id"
                 placeholder="Enter patient ID"
                 value=value.unwrap_or_default()
-                on_input=on_input.unwrap_or_else(|| Box::new(|_| {}))
+                on_input=on_input.unwrap_or_else(|| Callback::new(|_| {}))
                 required=required.unwrap_or(false)
                 class=class.unwrap_or("")
             />
@@ -97,7 +95,7 @@ pub fn PatientIdInput(
 #[component]
 pub fn ProviderLicenseInput(
     #[prop(optional)] value: Option<String>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
     #[prop(optional)] required: Option<bool>,
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
@@ -109,7 +107,7 @@ pub fn ProviderLicenseInput(
                 name="license_number"
                 placeholder="Enter medical license number"
                 value=value.unwrap_or_default()
-                on_input=on_input.unwrap_or_else(|| Box::new(|_| {}))
+                on_input=on_input.unwrap_or_else(|| Callback::new(|_| {}))
                 required=required.unwrap_or(false)
                 class=class.unwrap_or("")
             />
@@ -121,7 +119,7 @@ pub fn ProviderLicenseInput(
 pub fn SearchInput(
     #[prop(optional)] value: Option<String>,
     #[prop(optional)] placeholder: Option<&'static str>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
     view! {
@@ -135,7 +133,7 @@ pub fn SearchInput(
                 input_type="search"
                 placeholder=placeholder.unwrap_or("Search...")
                 value=value.unwrap_or_default()
-                on_input=on_input.unwrap_or_else(|| Box::new(|_| {}))
+                on_input=on_input.unwrap_or_else(|| Callback::new(|_| {}))
                 class="pl-10"
             />
         </div>
@@ -145,7 +143,7 @@ pub fn SearchInput(
 #[component]
 pub fn EmailInput(
     #[prop(optional)] value: Option<String>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
     #[prop(optional)] required: Option<bool>,
     #[prop(optional)] disabled: Option<bool>,
     #[prop(optional)] class: Option<&'static str>,
@@ -159,7 +157,7 @@ pub fn EmailInput(
                 name="email"
                 placeholder="Enter your email address"
                 value=value.unwrap_or_default()
-                on_input=on_input.unwrap_or_else(|| Box::new(|_| {}))
+                on_input=on_input.unwrap_or_else(|| Callback::new(|_| {}))
                 required=required.unwrap_or(false)
                 disabled=disabled.unwrap_or(false)
                 class=class.unwrap_or("")
@@ -171,7 +169,7 @@ pub fn EmailInput(
 #[component]
 pub fn PasswordInput(
     #[prop(optional)] value: Option<String>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
     #[prop(optional)] required: Option<bool>,
     #[prop(optional)] placeholder: Option<&'static str>,
     #[prop(optional)] class: Option<&'static str>,
@@ -185,7 +183,7 @@ pub fn PasswordInput(
                 name="password"
                 placeholder=placeholder.unwrap_or("Enter your password")
                 value=value.unwrap_or_default()
-                on_input=on_input.unwrap_or_else(|| Box::new(|_| {}))
+                on_input=on_input.unwrap_or_else(|| Callback::new(|_| {}))
                 required=required.unwrap_or(false)
                 class=class.unwrap_or("")
             />
@@ -196,7 +194,7 @@ pub fn PasswordInput(
 #[component]
 pub fn PhoneInput(
     #[prop(optional)] value: Option<String>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
     #[prop(optional)] required: Option<bool>,
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
@@ -209,7 +207,7 @@ pub fn PhoneInput(
                 name="phone"
                 placeholder="+91 9876543210"
                 value=value.unwrap_or_default()
-                on_input=on_input.unwrap_or_else(|| Box::new(|_| {}))
+                on_input=on_input.unwrap_or_else(|| Callback::new(|_| {}))
                 required=required.unwrap_or(false)
                 class=class.unwrap_or("")
             />
@@ -220,7 +218,7 @@ pub fn PhoneInput(
 #[component]
 pub fn DateInput(
     #[prop(optional)] value: Option<String>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
     #[prop(optional)] required: Option<bool>,
     #[prop(optional)] min: Option<&'static str>,
     #[prop(optional)] max: Option<&'static str>,
@@ -237,7 +235,7 @@ pub fn DateInput(
                 id="date"
                 name="date"
                 value=value.unwrap_or_default()
-                on_input=on_input.unwrap_or_else(|| Box::new(|_| {}))
+                on_input=on_input.unwrap_or_else(|| Callback::new(|_| {}))
                 required=required.unwrap_or(false)
                 class=class.unwrap_or("")
             />
@@ -248,7 +246,7 @@ pub fn DateInput(
 #[component]
 pub fn TimeInput(
     #[prop(optional)] value: Option<String>,
-    #[prop(optional)] on_input: Option<Box<dyn Fn(String) + 'static + Send>>,
+    #[prop(optional)] on_input: Option<Callback<String>>,
     #[prop(optional)] required: Option<bool>,
     #[prop(optional)] class: Option<&'static str>,
     #[prop(optional)] label: Option<&'static str>,
@@ -263,7 +261,7 @@ pub fn TimeInput(
                 id="time"
                 name="time"
                 value=value.unwrap_or_default()
-                on_input=on_input.unwrap_or_else(|| Box::new(|_| {}))
+                on_input=on_input.unwrap_or_else(|| Callback::new(|_| {}))
                 required=required.unwrap_or(false)
                 class=class.unwrap_or("")
             />
@@ -0,0 +1,216 @@
+use leptos::prelude::*;
+use crate::lab_results::{AbnormalFlag, LabPanel, LabResult, LabResultStatus, LabTrend, TrendDirection};
+use crate::ui::{cn, use_i18n, Badge, BadgeVariant};
+use crate::utils::datetime::{format_date, format_datetime, parse_timezone, DEFAULT_TIMEZONE};
+
+/// Changes smaller than this share of the previous value show as stable
+const TREND_TOLERANCE_PERCENT: f64 = 5.0;
+
+fn flag_badge(flag: AbnormalFlag) -> (BadgeVariant, &'static str) {
+    match flag {
+        AbnormalFlag::Normal => (BadgeVariant::Success, "Normal"),
+        AbnormalFlag::Low => (BadgeVariant::Warning, "Low"),
+        AbnormalFlag::High => (BadgeVariant::Warning, "High"),
+        AbnormalFlag::Abnormal => (BadgeVariant::Warning, "Abnormal"),
+        AbnormalFlag::CriticalLow => (BadgeVariant::Destructive, "Critically low"),
+        AbnormalFlag::CriticalHigh => (BadgeVariant::Destructive, "Critically high"),
+    }
+}
+
+fn status_label(status: LabResultStatus) -> Option<&'static str> {
+    match status {
+        LabResultStatus::Final => None,
+        LabResultStatus::Preliminary => Some("Preliminary"),
+        LabResultStatus::Corrected => Some("Corrected"),
+        LabResultStatus::Cancelled => Some("Cancelled"),
+    }
+}
+
+fn value_class(flag: Option<AbnormalFlag>) -> &'static str {
+    match flag {
+        Some(flag) if flag.is_critical() => "text-destructive",
+        Some(flag) if flag.is_abnormal() => "text-amber-700",
+        _ => "",
+    }
+}
+
+/// Reference range with the result's unit, e.g. "70–100 mg/dL"
+fn range_text(result: &LabResult) -> Option<String> {
+    let range = result.reference_range.as_ref()?;
+    let label = range.label()?;
+    let numeric = range.low.is_some() || range.high.is_some();
+    Some(match result.value.unit_symbol() {
+        Some(unit) if numeric => format!("{} {}", label, unit),
+        _ => label,
+    })
+}
+
+/// "↑ +12.5 (+9.8%)" for the latest change in `trend`, plus a sentence for screen readers
+fn trend_summary(trend: &LabTrend) -> Option<(String, String)> {
+    let change = trend.change()?;
+    let arrow = match trend.direction(TREND_TOLERANCE_PERCENT)? {
+        TrendDirection::Rising => "↑",
+        TrendDirection::Falling => "↓",
+        TrendDirection::Stable => "→",
+    };
+    let percent = trend
+        .percent_change()
+        .map(|p| format!(" ({:+.1}%)", p))
+        .unwrap_or_default();
+    let label = format!("{} {:+.1}{}", arrow, change, percent);
+    let unit = trend.unit.as_deref().map(|unit| format!(" {}", unit)).unwrap_or_default();
+    let description = format!("Changed by {:+.1}{} since the previous result", change, unit);
+    Some((label, description))
+}
+
+/// One lab result with its reference range, flag and, when a trend is given, the latest change
+#[component]
+pub fn LabResultCard(
+    result: LabResult,
+    /// History of the same test, e.g. from `LabTrend::from_results`
+    #[prop(optional)] trend: Option<LabTrend>,
+    /// IANA timezone for the collection time (default: Asia/Kolkata)
+    #[prop(optional, into)] timezone: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let locale = use_i18n().locale();
+    let tz = timezone
+        .as_deref()
+        .and_then(|tz| parse_timezone(tz).ok())
+        .unwrap_or(DEFAULT_TIMEZONE);
+    let flag = result.flag();
+    let critical = result.is_critical();
+    let range = range_text(&result);
+    let trend = trend.as_ref().filter(|t| t.points.len() > 1).and_then(trend_summary);
+    let card_classes = cn(&[
+        "rounded-lg border bg-card p-4 text-card-foreground shadow-sm",
+        if critical { "border-destructive" } else { "" },
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <article class=card_classes role=critical.then_some("alert")>
+            <div class="flex items-start justify-between gap-2">
+                <div class="min-w-0">
+                    <h3 class="truncate font-semibold">{result.test_name.clone()}</h3>
+                    <p class="text-xs text-muted-foreground">
+                        <time
+                            datetime=result.collected_at.to_rfc3339()
+                            title=format_datetime(&result.collected_at, &tz, locale)
+                        >
+                            {format_date(&result.collected_at, &tz, locale)}
+                        </time>
+                        {result.performing_lab.clone().map(|lab| format!(" · {}", lab))}
+                    </p>
+                </div>
+                <div class="flex shrink-0 flex-col items-end gap-1">
+                    {flag.map(|flag| {
+                        let (variant, label) = flag_badge(flag);
+                        view! { <Badge variant=variant>{label}</Badge> }
+                    })}
+                    {status_label(result.status).map(|label| view! { <Badge variant=BadgeVariant::Outline>{label}</Badge> })}
+                </div>
+            </div>
+            <div class="mt-3 flex items-baseline justify-between gap-2">
+                <p class=cn(&["text-2xl font-bold", value_class(flag)])>
+                    {result.value.to_string()}
+                    {flag.filter(|f| f.is_abnormal()).map(|f| view! {
+                        <span class="ml-1 text-sm font-semibold">{f.code()}</span>
+                    })}
+                </p>
+                {trend.map(|(label, description)| view! {
+                    <span class="text-sm text-muted-foreground" title=description.clone() aria-label=description>
+                        {label}
+                    </span>
+                })}
+            </div>
+            {range.map(|range| view! {
+                <p class="mt-1 text-xs text-muted-foreground">{format!("Reference: {}", range)}</p>
+            })}
+        </article>
+    }
+}
+
+fn panel_row(result: LabResult) -> impl IntoView {
+    let flag = result.flag();
+    let range = range_text(&result).unwrap_or_default();
+    view! {
+        <tr class="border-b align-top last:border-0">
+            <th scope="row" class="py-2 pr-4 text-left font-medium">{result.test_name.clone()}</th>
+            <td class=cn(&["py-2 pr-4 font-semibold", value_class(flag)])>
+                {result.value.to_string()}
+                {flag.filter(|f| f.is_abnormal()).map(|f| format!(" {}", f.code()))}
+            </td>
+            <td class="py-2 pr-4 text-muted-foreground">{range}</td>
+            <td class="py-2">
+                {status_label(result.status).map(|label| view! { <Badge variant=BadgeVariant::Outline>{label}</Badge> })}
+            </td>
+        </tr>
+    }
+}
+
+/// All results of a panel in a table, with critical values called out above it
+#[component]
+pub fn LabPanelCard(
+    panel: LabPanel,
+    /// IANA timezone for the collection time (default: Asia/Kolkata)
+    #[prop(optional, into)] timezone: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let locale = use_i18n().locale();
+    let tz = timezone
+        .as_deref()
+        .and_then(|tz| parse_timezone(tz).ok())
+        .unwrap_or(DEFAULT_TIMEZONE);
+    let critical: Vec<String> = panel.critical_results().map(|r| format!("{} {}", r.test_name, r.value)).collect();
+    let abnormal_count = panel.abnormal_results().count();
+    let status = status_label(panel.status());
+    let card_classes = cn(&[
+        "rounded-lg border bg-card p-4 text-card-foreground shadow-sm",
+        if critical.is_empty() { "" } else { "border-destructive" },
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <section class=card_classes>
+            <header class="flex items-start justify-between gap-2">
+                <div class="min-w-0">
+                    <h3 class="truncate font-semibold">{panel.name.clone()}</h3>
+                    <p class="text-xs text-muted-foreground">
+                        <time datetime=panel.collected_at.to_rfc3339()>
+                            {format_datetime(&panel.collected_at, &tz, locale)}
+                        </time>
+                    </p>
+                </div>
+                <div class="flex shrink-0 flex-col items-end gap-1">
+                    {(abnormal_count > 0).then(|| view! {
+                        <Badge variant=BadgeVariant::Warning>
+                            {format!("{} abnormal", abnormal_count)}
+                        </Badge>
+                    })}
+                    {status.map(|label| view! { <Badge variant=BadgeVariant::Outline>{label}</Badge> })}
+                </div>
+            </header>
+            {(!critical.is_empty()).then(|| view! {
+                <div role="alert" class="mt-3 rounded-md border border-destructive bg-destructive/10 p-2 text-sm text-destructive">
+                    {format!("Critical: {}", critical.join(", "))}
+                </div>
+            })}
+            <div class="mt-3 overflow-x-auto">
+                <table class="w-full text-sm">
+                    <thead>
+                        <tr class="border-b text-left text-muted-foreground">
+                            <th scope="col" class="py-2 pr-4 font-medium">"Test"</th>
+                            <th scope="col" class="py-2 pr-4 font-medium">"Result"</th>
+                            <th scope="col" class="py-2 pr-4 font-medium">"Reference"</th>
+                            <th scope="col" class="py-2 font-medium"><span class="sr-only">"Status"</span></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {panel.results.into_iter().map(panel_row).collect_view()}
+                    </tbody>
+                </table>
+            </div>
+        </section>
+    }
+}
@@ -1,5 +1,8 @@
 use leptos::prelude::*;
-use crate::ui::{Icon, IconSize, Priority, HealthcareStatus, Button, Variant};
+use crate::ui::{Icon, IconSize, Priority, HealthcareStatus, Button, Variant, NotificationBadge, NotificationIndicator};
+use crate::auth::Role;
+use crate::backend_status::{BackendHealth, BackendStatus};
+use super::navigation::{Breadcrumbs, PageTitle};
 
 #[derive(Debug, Clone)]
 pub struct HealthcareNavigationItem {
@@ -84,7 +87,7 @@ impl UserInfo {
     pub fn provider(name: &str, location: &str) -> Self {
         Self {
             name: name.to_string(),
-            role: "Healthcare Provider".to_string(),
+            role: Role::Provider.label().to_string(),
             avatar_url: None,
             status: HealthcareStatus::Active,
             location: Some(location.to_string()),
@@ -95,7 +98,7 @@ impl UserInfo {
     pub fn patient(name: &str) -> Self {
         Self {
             name: name.to_string(),
-            role: "Patient".to_string(),
+            role: Role::Patient.label().to_string(),
             avatar_url: None,
             status: HealthcareStatus::Active,
             location: None,
@@ -106,7 +109,7 @@ impl UserInfo {
     pub fn admin(name: &str) -> Self {
         Self {
             name: name.to_string(),
-            role: "System Administrator".to_string(),
+            role: Role::Admin.label().to_string(),
             avatar_url: None,
             status: HealthcareStatus::Active,
             location: None,
@@ -123,16 +126,21 @@ pub fn HealthcareAppLayout(
     #[prop()] user_info: UserInfo,
     #[prop(optional)] emergency_count: Option<u32>,
     #[prop(optional)] system_status: Option<HealthcareStatus>,
-    #[prop(optional)] on_emergency_click: Option<Box<dyn Fn()>>,
+    // Live status from `ApiClient::start_health_polling`; takes precedence over `system_status`
+    #[prop(optional, into)] backend_status: Option<Signal<BackendStatus>>,
+    #[prop(optional)] on_emergency_click: Option<Callback<()>>,
+    /// Unread notifications; the bell shows a dot while non-zero
+    #[prop(optional, into)] notification_count: Option<Signal<u32>>,
     children: Children,
 ) -> impl IntoView {
+    let notification_count = notification_count.unwrap_or_else(|| Signal::derive(|| 0));
     let (is_mobile_sidebar_open, set_is_mobile_sidebar_open) = signal(false);
     let (show_user_menu, set_show_user_menu) = signal(false);
     
     // Emergency button handler
     let emergency_handler = move |_| {
-        if let Some(callback) = &on_emergency_click {
-            callback();
+        if let Some(callback) = on_emergency_click {
+            callback.run(());
         }
     };
     
@@ -153,16 +161,36 @@ pub fn HealthcareAppLayout(
                         
                         // System Status Indicator
                         {
-                            let status_color = match system_status.unwrap_or(HealthcareStatus::Active) {
-                                HealthcareStatus::Active => "bg-green-400",
-                                HealthcareStatus::Emergency => "bg-red-400 animate-pulse",
-                                HealthcareStatus::Pending => "bg-yellow-400",
-                                _ => "bg-gray-400",
-                            };
-                            view! {
-                                <div class="flex items-center">
-                                    <div class=format!("w-3 h-3 rounded-full {} shadow-sm", status_color)></div>
-                                </div>
+                            if let Some(backend_status) = backend_status {
+                                view! {
+                                    <div class="flex items-center" role="status" aria-live="polite">
+                                        <div
+                                            class=move || {
+                                                let status_color = match backend_status.get().health {
+                                                    BackendHealth::Healthy => "bg-green-400",
+                                                    BackendHealth::Degraded => "bg-yellow-400",
+                                                    BackendHealth::Down => "bg-red-400 animate-pulse",
+                                                    BackendHealth::Unknown => "bg-gray-400",
+                                                };
+                                                format!("w-3 h-3 rounded-full {} shadow-sm", status_color)
+                                            }
+                                            title=move || backend_status.get().summary()
+                                        ></div>
+                                        <span class="sr-only">{move || backend_status.get().summary()}</span>
+                                    </div>
+                                }.into_any()
+                            } else {
+                                let status_color = match system_status.unwrap_or(HealthcareStatus::Active) {
+                                    HealthcareStatus::Active => "bg-green-400",
+                                    HealthcareStatus::Emergency => "bg-red-400 animate-pulse",
+                                    HealthcareStatus::Pending => "bg-yellow-400",
+                                    _ => "bg-gray-400",
+                                };
+                                view! {
+                                    <div class="flex items-center">
+                                        <div class=format!("w-3 h-3 rounded-full {} shadow-sm", status_color)></div>
+                                    </div>
+                                }.into_any()
                             }
                         }
                     </div>
@@ -262,9 +290,9 @@ pub fn HealthcareAppLayout(
                             <Icon name="interface-menu".to_string() size=IconSize::Lg class="text-slate-600".to_string() />
                         </button>
                         
-                        // Breadcrumb or page title could go here
                         <div class="hidden lg:block">
-                            <h2 class="text-lg font-semibold text-slate-900">Dashboard</h2>
+                            <PageTitle class="text-slate-900" />
+                            <Breadcrumbs class="text-xs" />
                         </div>
                         
                         // Top bar actions
@@ -278,9 +306,10 @@ pub fn HealthcareAppLayout(
                             </button>
                             
                             // Notifications
-                            <button class="relative p-2 rounded-lg text-slate-600 hover:text-slate-900 hover:bg-slate-100 transition-colors duration-200">
-                                <Icon name="interface-bell".to_string() size=IconSize::Md class="text-slate-600".to_string() />
-                                <span class="absolute top-1 right-1 w-2 h-2 bg-red-500 rounded-full"></span>
+                            <button class="relative p-2 rounded-lg text-slate-600 hover:text-slate-900 hover:bg-slate-100 transition-colors duration-200" aria-label="Notifications">
+                                <NotificationIndicator count=notification_count dot=true>
+                                    <Icon name="interface-bell".to_string() size=IconSize::Md class="text-slate-600".to_string() />
+                                </NotificationIndicator>
                             </button>
                             
                             // User menu (mobile)
@@ -429,11 +458,7 @@ fn HealthcareNavigationItemComponent(
                 // Notification badge
                 {
                     if let Some(count) = item.notification_count {
-                        view! {
-                            <span class="bg-red-500 text-white text-xs px-2 py-1 rounded-full min-w-[1.25rem] h-5 flex items-center justify-center font-semibold shadow-sm">
-                                {if count > 99 { "99+".to_string() } else { count.to_string() }}
-                            </span>
-                        }.into_any()
+                        view! { <NotificationBadge count=count class="shadow-sm" /> }.into_any()
                     } else if let Some(badge) = &item.badge {
                         view! {
                             <span class="bg-red-500 text-white text-xs px-2 py-1 rounded-full font-bold animate-pulse shadow-sm">
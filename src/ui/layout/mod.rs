@@ -2,9 +2,14 @@
 // Professional layout system with sidebar navigation and responsive design
 
 mod simple;
+mod navigation;
 // mod enhanced; // Temporarily disabled due to compilation issues
 
 pub use simple::{SimpleAppLayout, SimpleNavigationItem};
+pub use navigation::{
+    path_matches, provide_navigation, provide_navigation_for_path, use_navigation, Breadcrumb,
+    Breadcrumbs, NavigationState, NavigationTree, PageTitle,
+};
 // pub use enhanced::{HealthcareAppLayout, HealthcareNavigationItem, UserInfo}; // Temporarily disabled
 
 #[derive(Debug, Clone)]
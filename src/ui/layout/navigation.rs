@@ -0,0 +1,227 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_location;
+use crate::ui::cn;
+use super::{NavigationItem, SimpleNavigationItem};
+
+/// Navigation menus that can drive breadcrumbs and active-link highlighting
+pub trait NavigationTree {
+    fn nav_title(&self) -> &str;
+    fn nav_href(&self) -> Option<&str>;
+    fn nav_children(&self) -> &[Self]
+    where
+        Self: Sized;
+}
+
+impl NavigationTree for SimpleNavigationItem {
+    fn nav_title(&self) -> &str {
+        &self.title
+    }
+
+    fn nav_href(&self) -> Option<&str> {
+        self.href.as_deref()
+    }
+
+    fn nav_children(&self) -> &[Self] {
+        &self.children
+    }
+}
+
+impl NavigationTree for NavigationItem {
+    fn nav_title(&self) -> &str {
+        &self.title
+    }
+
+    fn nav_href(&self) -> Option<&str> {
+        self.href.as_deref()
+    }
+
+    fn nav_children(&self) -> &[Self] {
+        &self.children
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breadcrumb {
+    pub title: String,
+    /// `None` for groups without a page of their own
+    pub href: Option<String>,
+}
+
+impl Breadcrumb {
+    pub fn new(title: impl Into<String>, href: impl Into<String>) -> Self {
+        Self { title: title.into(), href: Some(href.into()) }
+    }
+
+    pub fn label(title: impl Into<String>) -> Self {
+        Self { title: title.into(), href: None }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NavNode {
+    crumb: Breadcrumb,
+    children: Vec<NavNode>,
+}
+
+fn to_nodes<T: NavigationTree>(items: &[T]) -> Vec<NavNode> {
+    items
+        .iter()
+        .map(|item| NavNode {
+            crumb: Breadcrumb {
+                title: item.nav_title().to_string(),
+                href: item.nav_href().map(str::to_string),
+            },
+            children: to_nodes(item.nav_children()),
+        })
+        .collect()
+}
+
+/// Whether `href` covers `path`: an exact match or a parent segment, so
+/// "/patient/records" matches "/patient/records/42" but not "/patient/records-archive"
+pub fn path_matches(href: &str, path: &str) -> bool {
+    let href = href.trim_end_matches('/');
+    let path = path.trim_end_matches('/');
+    path == href || (path.starts_with(href) && path[href.len()..].starts_with('/')) || href.is_empty()
+}
+
+/// Trail to the most specific item matching `path`, from the top-level item down
+fn resolve_trail(nodes: &[NavNode], path: &str) -> Vec<Breadcrumb> {
+    fn walk(nodes: &[NavNode], path: &str, trail: &mut Vec<Breadcrumb>, best: &mut (usize, Vec<Breadcrumb>)) {
+        for node in nodes {
+            trail.push(node.crumb.clone());
+            if let Some(href) = node.crumb.href.as_deref() {
+                let specificity = href.trim_end_matches('/').len() + 1;
+                if path_matches(href, path) && specificity > best.0 {
+                    *best = (specificity, trail.clone());
+                }
+            }
+            walk(&node.children, path, trail, best);
+            trail.pop();
+        }
+    }
+
+    let mut best = (0, Vec::new());
+    walk(nodes, path, &mut Vec::new(), &mut best);
+    best.1
+}
+
+/// Route-derived navigation state shared through context: the current
+/// path, the matching breadcrumb trail, the active link and the page title.
+#[derive(Debug, Clone, Copy)]
+pub struct NavigationState {
+    pub path: Signal<String>,
+    trail: Memo<Vec<Breadcrumb>>,
+}
+
+impl NavigationState {
+    pub fn new<T: NavigationTree>(items: &[T], path: Signal<String>) -> Self {
+        let nodes = to_nodes(items);
+        let trail = Memo::new(move |_| path.with(|path| resolve_trail(&nodes, path)));
+        Self { path, trail }
+    }
+
+    pub fn trail(&self) -> Vec<Breadcrumb> {
+        self.trail.get()
+    }
+
+    /// Href of the most specific menu item matching the current route
+    pub fn active_href(&self) -> Option<String> {
+        self.trail.with(|trail| trail.last().and_then(|crumb| crumb.href.clone()))
+    }
+
+    pub fn is_active(&self, href: &str) -> bool {
+        self.trail.with(|trail| trail.last().and_then(|crumb| crumb.href.as_deref()) == Some(href))
+    }
+
+    /// Whether a menu group contains the active item
+    pub fn contains_active(&self, title: &str) -> bool {
+        self.trail.with(|trail| trail.iter().any(|crumb| crumb.href.is_none() && crumb.title == title))
+    }
+
+    /// Title of the active item, or `None` when the route is outside the menu
+    pub fn page_title(&self) -> Option<String> {
+        self.trail.with(|trail| trail.last().map(|crumb| crumb.title.clone()))
+    }
+}
+
+/// Provide navigation state following the router's current location.
+/// Must be called inside a `<Router>`.
+pub fn provide_navigation<T: NavigationTree>(items: &[T]) -> NavigationState {
+    let pathname = use_location().pathname;
+    provide_navigation_for_path(items, pathname.into())
+}
+
+/// Provide navigation state for an explicit path, for apps without `leptos_router`
+pub fn provide_navigation_for_path<T: NavigationTree>(items: &[T], path: Signal<String>) -> NavigationState {
+    let state = NavigationState::new(items, path);
+    provide_context(state);
+    state
+}
+
+pub fn use_navigation() -> Option<NavigationState> {
+    use_context::<NavigationState>()
+}
+
+/// Breadcrumb trail for the current route. Pass `items` to override the
+/// trail derived from [`NavigationState`], e.g. to append a record name.
+#[component]
+pub fn Breadcrumbs(
+    #[prop(optional, into)] items: Option<Signal<Vec<Breadcrumb>>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let navigation = use_navigation();
+    let crumbs = move || match items {
+        Some(items) => items.get(),
+        None => navigation.map(|navigation| navigation.trail()).unwrap_or_default(),
+    };
+
+    view! {
+        <nav aria-label="Breadcrumb" class=cn(&["text-sm", class.unwrap_or("")])>
+            <ol class="flex flex-wrap items-center gap-1.5 text-muted-foreground">
+                {move || {
+                    let crumbs = crumbs();
+                    let last = crumbs.len().saturating_sub(1);
+                    crumbs
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, crumb)| {
+                            let current = index == last;
+                            view! {
+                                <li class="inline-flex items-center gap-1.5">
+                                    {(index > 0).then(|| view! { <span aria-hidden="true">"/"</span> })}
+                                    {match (crumb.href, current) {
+                                        (Some(href), false) => view! {
+                                            <a href=href class="transition-colors hover:text-foreground">{crumb.title}</a>
+                                        }
+                                        .into_any(),
+                                        (_, true) => view! {
+                                            <span class="font-medium text-foreground" aria-current="page">{crumb.title}</span>
+                                        }
+                                        .into_any(),
+                                        (None, false) => view! { <span>{crumb.title}</span> }.into_any(),
+                                    }}
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ol>
+        </nav>
+    }
+}
+
+/// Heading with the active page's title from [`NavigationState`]
+#[component]
+pub fn PageTitle(
+    /// Shown when the route is outside the navigation menu
+    #[prop(optional, into)] fallback: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let navigation = use_navigation();
+    let fallback = fallback.unwrap_or_default();
+    view! {
+        <h2 class=cn(&["text-lg font-semibold", class.unwrap_or("")])>
+            {move || navigation.and_then(|navigation| navigation.page_title()).unwrap_or_else(|| fallback.clone())}
+        </h2>
+    }
+}
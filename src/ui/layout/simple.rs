@@ -1,5 +1,6 @@
 use leptos::prelude::*;
-use crate::ui::{Icon, IconSize};
+use crate::ui::{use_i18n, Icon, IconSize, NotificationBadge, NotificationIndicator, SkipLink};
+use super::navigation::{provide_navigation_for_path, use_navigation, Breadcrumbs, PageTitle};
 
 // Simple navigation structure without complex callbacks
 #[derive(Debug, Clone)]
@@ -8,6 +9,8 @@ pub struct SimpleNavigationItem {
     pub icon: String,
     pub href: Option<String>,
     pub badge: Option<String>,
+    /// Live unread/pending count, e.g. new messages
+    pub notification_count: Option<Signal<u32>>,
     pub is_emergency: bool,
     pub children: Vec<SimpleNavigationItem>,
 }
@@ -19,6 +22,7 @@ impl SimpleNavigationItem {
             icon: icon.to_string(),
             href: Some(href.to_string()),
             badge: None,
+            notification_count: None,
             is_emergency: title == "Emergency",
             children: vec![],
         }
@@ -30,11 +34,17 @@ impl SimpleNavigationItem {
             icon: icon.to_string(),
             href: None,
             badge: None,
+            notification_count: None,
             is_emergency: false,
             children,
         }
     }
     
+    pub fn with_notification_count(mut self, count: impl Into<Signal<u32>>) -> Self {
+        self.notification_count = Some(count.into());
+        self
+    }
+
     pub fn provider_nav() -> Vec<Self> {
         vec![
             Self::link("Dashboard", "interface-dashboard", "/provider/dashboard"),
@@ -62,6 +72,11 @@ impl SimpleNavigationItem {
     }
 }
 
+/// App shell with sidebar navigation.
+///
+/// The active link, page title and breadcrumbs follow the route when a
+/// parent has called `provide_navigation` inside the `<Router>`; pass
+/// `current_path` instead when the app has no router.
 #[component]
 pub fn SimpleAppLayout(
     #[prop()] navigation_items: Vec<SimpleNavigationItem>,
@@ -69,9 +84,17 @@ pub fn SimpleAppLayout(
     #[prop()] brand_icon: String,
     #[prop()] user_name: String,
     #[prop()] user_role: String,
+    #[prop(optional, into)] current_path: Option<Signal<String>>,
+    /// Unread notifications shown on the header bell
+    #[prop(optional, into)] notification_count: Option<Signal<u32>>,
     children: Children,
 ) -> impl IntoView {
     let (is_mobile_sidebar_open, set_is_mobile_sidebar_open) = signal(false);
+    let i18n = use_i18n();
+    let navigation = match current_path {
+        Some(path) => Some(provide_navigation_for_path(&navigation_items, path)),
+        None => use_navigation(),
+    };
     
     // Clone the brand values for use in the mobile overlay closure
     let brand_icon_cloned = brand_icon.clone();
@@ -81,6 +104,7 @@ pub fn SimpleAppLayout(
     
     view! {
         <div class="min-h-screen bg-gray-50 flex">
+            <SkipLink target_id="main-content" />
             // Sidebar for desktop
             <aside class="hidden lg:flex lg:flex-col lg:w-64 lg:fixed lg:inset-y-0 bg-white border-r border-gray-200">
                 <div class="flex-1 flex flex-col min-h-0">
@@ -111,20 +135,31 @@ pub fn SimpleAppLayout(
             </aside>
             
             // Main content area
-            <main class="flex-1 lg:pl-64">
+            <main id="main-content" tabindex="-1" class="flex-1 lg:pl-64 focus:outline-none">
                 // Top bar
                 <header class="bg-white shadow-sm border-b border-gray-200 px-4 py-3 lg:px-6">
                     <div class="flex items-center justify-between">
                         <button 
                             class="lg:hidden p-2 rounded-md text-gray-600 hover:text-gray-900 hover:bg-gray-100"
+                            aria-label=move || i18n.t("a11y.open_navigation")
+                            aria-expanded=move || is_mobile_sidebar_open.get().to_string()
                             on:click=move |_| set_is_mobile_sidebar_open.update(|open| *open = !*open)
                         >
                             <Icon name="interface-menu".to_string() size=IconSize::Lg class="text-gray-600".to_string() />
                         </button>
+
+                        {navigation.map(|_| view! {
+                            <div class="hidden lg:block space-y-0.5">
+                                <PageTitle class="text-gray-900" />
+                                <Breadcrumbs class="text-xs" />
+                            </div>
+                        })}
                         
                         <div class="flex items-center space-x-4">
-                            <button class="p-2 rounded-md text-gray-600 hover:text-gray-900 hover:bg-gray-100">
-                                <Icon name="interface-bell".to_string() size=IconSize::Md class="text-gray-600".to_string() />
+                            <button class="p-2 rounded-md text-gray-600 hover:text-gray-900 hover:bg-gray-100" aria-label="Notifications">
+                                <NotificationIndicator count=notification_count.unwrap_or_else(|| Signal::derive(|| 0))>
+                                    <Icon name="interface-bell".to_string() size=IconSize::Md class="text-gray-600".to_string() />
+                                </NotificationIndicator>
                             </button>
                             
                             {
@@ -160,7 +195,7 @@ pub fn SimpleAppLayout(
                                 class="fixed inset-0 bg-black bg-opacity-50"
                                 on:click=move |_| set_is_mobile_sidebar_open.set(false)
                             ></div>
-                            <aside class="fixed inset-y-0 left-0 w-64 bg-white border-r border-gray-200 z-50">
+                            <aside class="fixed inset-y-0 left-0 w-64 bg-white border-r border-gray-200 z-50" role="dialog" aria-modal="true">
                                 <div class="flex-1 flex flex-col min-h-0">
                                     // Brand header
                                     <div class="flex items-center justify-between h-16 flex-shrink-0 px-4 bg-blue-600 text-white">
@@ -170,6 +205,7 @@ pub fn SimpleAppLayout(
                                         </div>
                                         <button 
                                             class="text-white"
+                                            aria-label=move || i18n.t("a11y.close_navigation")
                                             on:click=move |_| set_is_mobile_sidebar_open.set(false)
                                         >
                                             <Icon name="interface-close".to_string() size=IconSize::Md class="text-white".to_string() />
@@ -198,31 +234,50 @@ pub fn SimpleAppLayout(
 fn SimpleNavigationItemComponent(
     #[prop()] item: SimpleNavigationItem,
 ) -> impl IntoView {
-    let (is_expanded, set_is_expanded) = signal(false);
+    let navigation = use_navigation();
+    // Open groups that contain the current page
+    let (is_expanded, set_is_expanded) = signal(
+        navigation.is_some_and(|navigation| untrack(|| navigation.contains_active(&item.title))),
+    );
     let has_children = !item.children.is_empty();
     
     if let Some(href) = &item.href {
         // Navigation link
-        let item_class = if item.is_emergency {
-            "bg-red-50 text-red-700 border-red-200"
-        } else {
-            "text-gray-700 hover:text-gray-900 hover:bg-gray-100"
+        let is_emergency = item.is_emergency;
+        let is_active = {
+            let href = href.clone();
+            move || navigation.is_some_and(|navigation| navigation.is_active(&href))
+        };
+        let item_class = {
+            let is_active = is_active.clone();
+            move || {
+                let state = if is_emergency {
+                    "bg-red-50 text-red-700 border-red-200"
+                } else if is_active() {
+                    "bg-blue-50 text-blue-700 border-blue-200"
+                } else {
+                    "text-gray-700 hover:text-gray-900 hover:bg-gray-100 border-transparent"
+                };
+                format!("group flex items-center px-3 py-2 rounded-lg text-sm font-medium transition-colors duration-200 border {}", state)
+            }
         };
         
         view! {
             <a 
                 href=href
-                class=format!("group flex items-center px-3 py-2 rounded-lg text-sm font-medium transition-colors duration-200 border {}", item_class)
+                class=item_class
+                aria-current=move || is_active().then_some("page")
             >
                 <Icon name=item.icon.clone() size=IconSize::Md class="mr-3".to_string() />
                 <span class="flex-1">{item.title.clone()}</span>
-                {item.badge.as_ref().map(|badge| {
-                    view! {
-                        <span class="bg-red-100 text-red-800 text-xs px-2 py-1 rounded-full">
-                            {badge.clone()}
-                        </span>
+                {match (item.notification_count, item.badge.clone()) {
+                    (Some(count), _) => view! { <NotificationBadge count=count /> }.into_any(),
+                    (None, Some(badge)) => view! {
+                        <span class="bg-red-100 text-red-800 text-xs px-2 py-1 rounded-full">{badge}</span>
                     }
-                })}
+                    .into_any(),
+                    (None, None) => view! {}.into_any(),
+                }}
             </a>
         }.into_any()
     } else if has_children {
@@ -231,6 +286,7 @@ fn SimpleNavigationItemComponent(
             <div>
                 <button 
                     class="w-full group flex items-center px-3 py-2 rounded-lg text-sm font-medium text-gray-700 hover:text-gray-900 hover:bg-gray-100 transition-colors duration-200"
+                    aria-expanded=move || is_expanded.get().to_string()
                     on:click=move |_| set_is_expanded.update(|expanded| *expanded = !*expanded)
                 >
                     <Icon name=item.icon.clone() size=IconSize::Md class="mr-3".to_string() />
@@ -0,0 +1,83 @@
+use leptos::prelude::*;
+use crate::i18n::{translate, translate_plural, translate_with, Locale};
+use crate::ui::cn;
+
+/// Active locale shared through context. Lookups read the locale signal, so
+/// text rendered from inside a reactive closure follows locale changes.
+#[derive(Debug, Clone, Copy)]
+pub struct I18n {
+    locale: RwSignal<Locale>,
+}
+
+impl I18n {
+    pub fn locale(&self) -> Locale {
+        self.locale.get()
+    }
+
+    pub fn set_locale(&self, locale: Locale) {
+        self.locale.set(locale);
+    }
+
+    pub fn t(&self, key: &str) -> String {
+        translate(self.locale.get(), key).to_string()
+    }
+
+    pub fn t_with(&self, key: &str, args: &[(&str, &str)]) -> String {
+        translate_with(self.locale.get(), key, args)
+    }
+
+    pub fn t_plural(&self, key: &str, count: u64) -> String {
+        translate_plural(self.locale.get(), key, count, &[])
+    }
+}
+
+/// Make `initial` the locale for this subtree, usually at the app root.
+/// Keeps the document's `lang` attribute in step for screen readers.
+pub fn provide_i18n(initial: Locale) -> I18n {
+    let i18n = I18n { locale: RwSignal::new(initial) };
+    provide_context(i18n);
+
+    Effect::new(move |_| {
+        let code = i18n.locale().code();
+        if let Some(root) = document().document_element() {
+            let _ = root.set_attribute("lang", code);
+        }
+    });
+
+    i18n
+}
+
+/// Context locale, or English when no provider is mounted
+pub fn use_i18n() -> I18n {
+    use_context::<I18n>().unwrap_or_else(|| I18n { locale: RwSignal::new(Locale::En) })
+}
+
+/// Language picker bound to the context locale
+#[component]
+pub fn LocaleSelect(
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let options = Locale::ALL
+        .into_iter()
+        .map(|locale| view! {
+            <option value=locale.code() lang=locale.code() selected=move || i18n.locale() == locale>
+                {locale.native_name()}
+            </option>
+        })
+        .collect_view();
+
+    view! {
+        <select
+            aria-label="Language"
+            class=cn(&["h-8 rounded-md border border-input bg-background px-2 text-sm", class.unwrap_or("")])
+            on:change=move |ev| {
+                if let Some(locale) = Locale::from_code(&event_target_value(&ev)) {
+                    i18n.set_locale(locale);
+                }
+            }
+        >
+            {options}
+        </select>
+    }
+}
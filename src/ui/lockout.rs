@@ -0,0 +1,53 @@
+use leptos::prelude::*;
+use chrono::Utc;
+use std::time::Duration;
+use crate::auth::LockoutStatus;
+use crate::ui::{use_i18n, Alert, AlertDescription, AlertVariant};
+
+/// Seconds until the next sign-in attempt is allowed, ticking down once a
+/// second; `None` while the form may be submitted. Feed it
+/// `ApiClient::lockout_status` after a failed login.
+pub fn use_lockout_countdown(status: Signal<Option<LockoutStatus>>) -> Signal<Option<u64>> {
+    let now = RwSignal::new(Utc::now());
+    if let Ok(handle) = set_interval_with_handle(move || now.set(Utc::now()), Duration::from_secs(1)) {
+        on_cleanup(move || handle.clear());
+    }
+    Signal::derive(move || {
+        let now = now.get();
+        status.with(|status| status.as_ref().and_then(|s| s.retry_in_seconds(now)))
+    })
+}
+
+/// `m:ss` for the countdown text
+pub fn format_countdown(seconds: u64) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Notice for sign-in forms while attempts are throttled or the account is
+/// locked. Hides itself when the countdown ends; disable the submit button
+/// with the same `use_lockout_countdown` signal.
+#[component]
+pub fn LockoutNotice(
+    #[prop(into)] status: Signal<Option<LockoutStatus>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let seconds_left = use_lockout_countdown(status);
+    let locked = move || status.with(|status| status.as_ref().is_some_and(|s| s.locked));
+
+    move || {
+        seconds_left.get().map(|seconds| {
+            let (variant, key) = if locked() {
+                (AlertVariant::Destructive, "auth.lockout.locked")
+            } else {
+                (AlertVariant::Warning, "auth.lockout.delayed")
+            };
+            let text = i18n.t_with(key, &[("time", &format_countdown(seconds))]);
+            view! {
+                <Alert variant=variant class=class.unwrap_or("")>
+                    <AlertDescription>{text}</AlertDescription>
+                </Alert>
+            }
+        })
+    }
+}
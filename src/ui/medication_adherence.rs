@@ -0,0 +1,290 @@
+use leptos::prelude::*;
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+use crate::ui::{cn, EmptyState};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DoseStatus {
+    Taken,
+    Missed,
+    Skipped,
+    /// Not yet recorded; overdue once `scheduled_at` has passed
+    Scheduled,
+}
+
+/// One scheduled dose of a medication, in the patient's local time
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoseRecord {
+    pub id: String,
+    pub medication: String,
+    pub dosage: String,
+    pub scheduled_at: NaiveDateTime,
+    pub status: DoseStatus,
+    pub taken_at: Option<NaiveDateTime>,
+}
+
+impl DoseRecord {
+    pub fn new(
+        id: impl Into<String>,
+        medication: impl Into<String>,
+        dosage: impl Into<String>,
+        scheduled_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            medication: medication.into(),
+            dosage: dosage.into(),
+            scheduled_at,
+            status: DoseStatus::Scheduled,
+            taken_at: None,
+        }
+    }
+
+    pub fn with_status(mut self, status: DoseStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    fn is_due(&self, now: NaiveDateTime) -> bool {
+        self.status != DoseStatus::Scheduled || self.scheduled_at <= now
+    }
+}
+
+/// Taken and due dose counts for one calendar day
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DaySummary {
+    pub taken: u32,
+    pub due: u32,
+}
+
+impl DaySummary {
+    pub fn is_complete(&self) -> bool {
+        self.due > 0 && self.taken == self.due
+    }
+}
+
+/// Per-day totals of doses that were due by `now`
+pub fn daily_summary(doses: &[DoseRecord], now: NaiveDateTime) -> BTreeMap<NaiveDate, DaySummary> {
+    let mut days: BTreeMap<NaiveDate, DaySummary> = BTreeMap::new();
+    for dose in doses.iter().filter(|d| d.is_due(now)) {
+        let day = days.entry(dose.scheduled_at.date()).or_default();
+        day.due += 1;
+        if dose.status == DoseStatus::Taken {
+            day.taken += 1;
+        }
+    }
+    days
+}
+
+/// Share of due doses that were taken, from 0.0 to 1.0; `None` before any dose is due
+pub fn adherence_rate(doses: &[DoseRecord], now: NaiveDateTime) -> Option<f64> {
+    let (taken, due) = daily_summary(doses, now)
+        .values()
+        .fold((0, 0), |(taken, due), day| (taken + day.taken, due + day.due));
+    (due > 0).then(|| taken as f64 / due as f64)
+}
+
+/// Consecutive fully-adherent days ending today, or yesterday while today is still in progress
+pub fn current_streak(doses: &[DoseRecord], now: NaiveDateTime) -> u32 {
+    let days = daily_summary(doses, now);
+    let mut day = now.date();
+    if !days.get(&day).is_some_and(DaySummary::is_complete) {
+        day -= Duration::days(1);
+    }
+    let mut streak = 0;
+    while days.get(&day).is_some_and(DaySummary::is_complete) {
+        streak += 1;
+        day -= Duration::days(1);
+    }
+    streak
+}
+
+/// Longest run of consecutive fully-adherent days
+pub fn longest_streak(doses: &[DoseRecord], now: NaiveDateTime) -> u32 {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for (date, day) in daily_summary(doses, now) {
+        let consecutive = previous.is_some_and(|p| p + Duration::days(1) == date);
+        run = match (day.is_complete(), consecutive) {
+            (true, true) => run + 1,
+            (true, false) => 1,
+            (false, _) => 0,
+        };
+        longest = longest.max(run);
+        previous = Some(date);
+    }
+    longest
+}
+
+fn heat_class(summary: Option<&DaySummary>) -> &'static str {
+    match summary {
+        None => "bg-muted",
+        Some(day) if day.is_complete() => "bg-green-500",
+        Some(day) if day.taken == 0 => "bg-red-500",
+        Some(_) => "bg-yellow-400",
+    }
+}
+
+/// Adherence overview for chronic-care patients: a calendar heatmap of
+/// taken and missed doses, streaks, and the next doses to take.
+#[component]
+pub fn MedicationAdherence(
+    #[prop(into)] doses: Signal<Vec<DoseRecord>>,
+    /// Number of days shown in the heatmap, ending today (default: 28)
+    #[prop(optional)] days: Option<u32>,
+    /// Upcoming and overdue doses listed (default: 5)
+    #[prop(optional)] upcoming_limit: Option<usize>,
+    /// Called with the dose id
+    #[prop(optional)] on_mark_taken: Option<Callback<String>>,
+    /// Called with the dose id
+    #[prop(optional)] on_skip: Option<Callback<String>>,
+    /// Reference time; defaults to the device clock
+    #[prop(optional)] now: Option<NaiveDateTime>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let days = days.unwrap_or(28).max(1);
+    let upcoming_limit = upcoming_limit.unwrap_or(5);
+    let now = move || now.unwrap_or_else(|| Local::now().naive_local());
+
+    let stats = move || {
+        let doses = doses.get();
+        let now = now();
+        (adherence_rate(&doses, now), current_streak(&doses, now), longest_streak(&doses, now))
+    };
+
+    let heatmap = move || {
+        let now = now();
+        let summary = doses.with(|doses| daily_summary(doses, now));
+        let today = now.date();
+        let start = today - Duration::days(days as i64 - 1);
+        // Pad so columns line up with weekdays, Monday first
+        let padding = start.weekday().num_days_from_monday();
+
+        let cells = (0..days)
+            .map(|offset| {
+                let date = start + Duration::days(offset as i64);
+                let day = summary.get(&date).copied();
+                let label = match day {
+                    Some(day) => format!("{}: {} of {} doses taken", date.format("%b %-d"), day.taken, day.due),
+                    None => format!("{}: no doses due", date.format("%b %-d")),
+                };
+                view! {
+                    <div
+                        class=cn(&["aspect-square rounded-sm", heat_class(day.as_ref()), if date == today { "ring-2 ring-ring ring-offset-1" } else { "" }])
+                        role="gridcell"
+                        aria-label=label.clone()
+                        title=label
+                    ></div>
+                }
+            })
+            .collect_view();
+
+        view! {
+            <div class="grid grid-cols-7 gap-1" role="grid" aria-label="Dose history">
+                {(0..padding).map(|_| view! { <div aria-hidden="true"></div> }).collect_view()}
+                {cells}
+            </div>
+        }
+    };
+
+    let upcoming = move || {
+        let now = now();
+        let mut pending: Vec<DoseRecord> = doses.with(|doses| {
+            doses.iter().filter(|d| d.status == DoseStatus::Scheduled).cloned().collect()
+        });
+        pending.sort_by_key(|d| d.scheduled_at);
+        pending.truncate(upcoming_limit);
+
+        if pending.is_empty() {
+            return view! { <EmptyState icon="medical-pill" title="No upcoming doses" compact=true /> }.into_any();
+        }
+
+        pending
+            .into_iter()
+            .map(|dose| {
+                let overdue = dose.scheduled_at <= now;
+                let taken_id = dose.id.clone();
+                let skip_id = dose.id.clone();
+                let when = if dose.scheduled_at.date() == now.date() {
+                    format!("Today {}", dose.scheduled_at.format("%H:%M"))
+                } else {
+                    dose.scheduled_at.format("%a %b %-d, %H:%M").to_string()
+                };
+                view! {
+                    <li class=cn(&["flex items-center justify-between gap-3 rounded-md border p-3", if overdue { "border-red-200 bg-red-50" } else { "" }])>
+                        <div class="min-w-0">
+                            <p class="truncate text-sm font-medium">{dose.medication.clone()} " " <span class="text-muted-foreground">{dose.dosage.clone()}</span></p>
+                            <p class=cn(&["text-xs", if overdue { "font-medium text-red-700" } else { "text-muted-foreground" }])>
+                                {if overdue { format!("Overdue · {}", when) } else { when }}
+                            </p>
+                        </div>
+                        <div class="flex shrink-0 gap-2">
+                            {on_skip.map(|callback| view! {
+                                <button
+                                    type="button"
+                                    class="rounded-md px-2 py-1 text-xs font-medium text-muted-foreground hover:bg-accent"
+                                    on:click=move |_| callback.run(skip_id.clone())
+                                >
+                                    "Skip"
+                                </button>
+                            })}
+                            {on_mark_taken.map(|callback| view! {
+                                <button
+                                    type="button"
+                                    class="rounded-md bg-primary px-2 py-1 text-xs font-medium text-primary-foreground hover:bg-primary/90"
+                                    aria-label=format!("Mark {} taken", dose.medication)
+                                    on:click=move |_| callback.run(taken_id.clone())
+                                >
+                                    "Taken"
+                                </button>
+                            })}
+                        </div>
+                    </li>
+                }
+            })
+            .collect_view()
+            .into_any()
+    };
+
+    let container_classes = cn(&["w-full space-y-4 rounded-lg border bg-card p-4 text-card-foreground", class.unwrap_or("")]);
+
+    view! {
+        <section class=container_classes aria-label="Medication adherence">
+            <div class="grid grid-cols-3 gap-4 text-center">
+                {move || {
+                    let (rate, current, longest) = stats();
+                    view! {
+                        <div>
+                            <p class="text-2xl font-bold">{rate.map(|r| format!("{:.0}%", r * 100.0)).unwrap_or_else(|| "--".to_string())}</p>
+                            <p class="text-xs text-muted-foreground">"Adherence"</p>
+                        </div>
+                        <div>
+                            <p class="text-2xl font-bold">{current}</p>
+                            <p class="text-xs text-muted-foreground">"Day streak"</p>
+                        </div>
+                        <div>
+                            <p class="text-2xl font-bold">{longest}</p>
+                            <p class="text-xs text-muted-foreground">"Best streak"</p>
+                        </div>
+                    }
+                }}
+            </div>
+            <div class="space-y-2">
+                <div class="grid grid-cols-7 gap-1 text-center text-[10px] text-muted-foreground" aria-hidden="true">
+                    {["M", "T", "W", "T", "F", "S", "S"].into_iter().map(|d| view! { <span>{d}</span> }).collect_view()}
+                </div>
+                {heatmap}
+                <div class="flex items-center gap-3 text-xs text-muted-foreground">
+                    <span class="flex items-center gap-1"><span class="h-2.5 w-2.5 rounded-sm bg-green-500"></span>"All taken"</span>
+                    <span class="flex items-center gap-1"><span class="h-2.5 w-2.5 rounded-sm bg-yellow-400"></span>"Partial"</span>
+                    <span class="flex items-center gap-1"><span class="h-2.5 w-2.5 rounded-sm bg-red-500"></span>"Missed"</span>
+                </div>
+            </div>
+            <div class="space-y-2">
+                <h4 class="text-sm font-semibold">"Upcoming doses"</h4>
+                <ul class="space-y-2">{upcoming}</ul>
+            </div>
+        </section>
+    }
+}
@@ -1,5 +1,5 @@
 use leptos::prelude::*;
-use crate::ui::cn;
+use crate::ui::{cn, use_i18n};
 
 // Separator component
 #[component]
@@ -278,9 +278,10 @@ pub fn LoadingSpinner(
         class.unwrap_or(""),
     ]);
     
+    let i18n = use_i18n();
     view! {
-        <div class=spinner_classes role="status" aria-label="Loading">
-            <span class="sr-only">"Loading..."</span>
+        <div class=spinner_classes role="status" aria-label=move || i18n.t("common.loading")>
+            <span class="sr-only">{move || i18n.t("common.loading")}</span>
         </div>
     }
 }
@@ -319,6 +320,7 @@ pub fn HealthStatusIndicator(
     }
 }
 
+/// Latest readings only; use `VitalTrendChart` to show how a vital has changed over time
 #[component]
 pub fn VitalSignsDisplay(
     heart_rate: Option<i32>,
@@ -362,7 +364,7 @@ pub fn AppointmentTimeSlot(
     time: String,
     available: bool,
     #[prop(optional)] selected: Option<bool>,
-    #[prop(optional)] on_click: Option<Box<dyn Fn()>>,
+    #[prop(optional)] on_click: Option<Callback<()>>,
     #[prop(optional)] class: Option<&'static str>,
 ) -> impl IntoView {
     let selected = selected.unwrap_or(false);
@@ -386,8 +388,8 @@ pub fn AppointmentTimeSlot(
             class=slot_classes
             disabled=!available
             on:click=move |_| {
-                if let Some(callback) = on_click.as_ref() {
-                    callback();
+                if let Some(callback) = on_click {
+                    callback.run(());
                 }
             }
         >
@@ -6,6 +6,41 @@ pub mod card;
 pub mod input;
 pub mod badge;
 pub mod alert;
+pub mod dialog;
+pub mod toast;
+pub mod data_table;
+pub mod scheduler;
+pub mod charts;
+pub mod theme;
+pub mod tabs;
+pub mod select;
+pub mod dropdown_menu;
+pub mod popover;
+pub mod stepper;
+pub mod file_dropzone;
+pub mod combobox;
+pub mod medication_adherence;
+pub mod prescription;
+pub mod lab_result_card;
+pub mod print;
+pub mod triage;
+pub mod body_map;
+pub mod signature_pad;
+pub mod video_consultation;
+pub mod audio_call;
+pub mod clinical_calculators;
+pub mod timeline;
+pub mod pagination;
+pub mod filter_bar;
+pub mod empty_state;
+pub mod lockout;
+pub mod error_boundary;
+pub mod locale;
+pub mod a11y;
+// Not glob re-exported: its card components overlap names in `card`
+pub mod healthcare;
+// Not glob re-exported: `EmergencyAlert` and `PatientInfo` clash with the event and healthcare models
+pub mod emergency;
 pub mod misc;
 pub mod icons;
 pub mod layout;
@@ -17,6 +52,37 @@ pub use card::*;
 pub use input::*;
 pub use badge::*;
 pub use alert::*;
+pub use dialog::*;
+pub use toast::*;
+pub use data_table::*;
+pub use scheduler::*;
+pub use charts::*;
+pub use theme::*;
+pub use tabs::*;
+pub use select::*;
+pub use dropdown_menu::*;
+pub use popover::*;
+pub use stepper::*;
+pub use file_dropzone::*;
+pub use combobox::*;
+pub use medication_adherence::*;
+pub use prescription::*;
+pub use lab_result_card::*;
+pub use print::*;
+pub use triage::*;
+pub use body_map::*;
+pub use signature_pad::*;
+pub use video_consultation::*;
+pub use audio_call::*;
+pub use clinical_calculators::*;
+pub use timeline::*;
+pub use pagination::*;
+pub use filter_bar::*;
+pub use empty_state::*;
+pub use lockout::*;
+pub use error_boundary::*;
+pub use locale::*;
+pub use a11y::*;
 pub use misc::*;
 pub use icons::*;
 pub use layout::*;
@@ -112,6 +178,13 @@ pub struct Animations {
     pub duration_slow: &'static str,
 }
 
+impl ColorPalette {
+    /// Series colours in the order charts assign them
+    pub fn chart_colors(&self) -> [&'static str; 5] {
+        [self.chart_1, self.chart_2, self.chart_3, self.chart_4, self.chart_5]
+    }
+}
+
 impl Default for DesignSystem {
     fn default() -> Self {
         Self {
@@ -0,0 +1,208 @@
+use leptos::prelude::*;
+use crate::i18n::{translate, translate_with, Locale};
+use crate::ui::{cn, use_i18n};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageItem {
+    Page(u32),
+    Ellipsis,
+}
+
+/// Page buttons to show: first and last pages, `siblings` pages either side of
+/// `current`, and ellipses for the gaps. Pages are 1-based.
+pub fn page_items(current: u32, page_count: u32, siblings: u32) -> Vec<PageItem> {
+    let page_count = page_count.max(1);
+    let current = current.clamp(1, page_count);
+    // first + last + current + siblings + two ellipses
+    let slots = 2 * siblings + 5;
+    if page_count <= slots {
+        return (1..=page_count).map(PageItem::Page).collect();
+    }
+
+    let left = current.saturating_sub(siblings).max(1);
+    let right = (current + siblings).min(page_count);
+    let left_gap = left > 3;
+    let right_gap = right < page_count - 2;
+    let edge = 3 + 2 * siblings;
+
+    let mut items = Vec::with_capacity(slots as usize);
+    match (left_gap, right_gap) {
+        (false, _) => {
+            items.extend((1..=edge).map(PageItem::Page));
+            items.push(PageItem::Ellipsis);
+            items.push(PageItem::Page(page_count));
+        }
+        (true, false) => {
+            items.push(PageItem::Page(1));
+            items.push(PageItem::Ellipsis);
+            items.extend((page_count - edge + 1..=page_count).map(PageItem::Page));
+        }
+        (true, true) => {
+            items.push(PageItem::Page(1));
+            items.push(PageItem::Ellipsis);
+            items.extend((left..=right).map(PageItem::Page));
+            items.push(PageItem::Ellipsis);
+            items.push(PageItem::Page(page_count));
+        }
+    }
+    items
+}
+
+pub fn page_count(total: u64, page_size: u32) -> u32 {
+    if page_size == 0 {
+        return 1;
+    }
+    total.div_ceil(page_size as u64).max(1) as u32
+}
+
+/// "Showing X–Y of Z" for a 1-based page
+pub fn showing_label(locale: Locale, page: u32, page_size: u32, total: u64) -> String {
+    if total == 0 {
+        return translate(locale, "pagination.no_results").to_string();
+    }
+    let first = (page.max(1) as u64 - 1) * page_size as u64 + 1;
+    let last = (first + page_size as u64 - 1).min(total);
+    translate_with(locale, "pagination.showing", &[
+        ("first", first.min(total).to_string().as_str()),
+        ("last", last.to_string().as_str()),
+        ("total", total.to_string().as_str()),
+    ])
+}
+
+const DEFAULT_PAGE_SIZES: [u32; 4] = [10, 25, 50, 100];
+
+/// Page navigation for lists and tables. Pages are 1-based to match
+/// [`Paginated`](crate::api_client::Paginated) responses: feed its `total`
+/// in and refetch when `on_change` reports a new page or page size.
+#[component]
+pub fn Pagination(
+    page: RwSignal<u32>,
+    page_size: RwSignal<u32>,
+    #[prop(into)] total: Signal<u64>,
+    /// Choices for the page-size selector; an empty list hides it (default: 10, 25, 50, 100)
+    #[prop(optional)] page_size_options: Option<Vec<u32>>,
+    /// Pages shown either side of the current one (default: 1)
+    #[prop(optional)] siblings: Option<u32>,
+    /// Called with `(page, page_size)` after either changes
+    #[prop(optional)] on_change: Option<Callback<(u32, u32)>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let page_size_options = page_size_options.unwrap_or_else(|| DEFAULT_PAGE_SIZES.to_vec());
+    let siblings = siblings.unwrap_or(1);
+    let i18n = use_i18n();
+    let pages = move || page_count(total.get(), page_size.get());
+    let current = move || page.get().clamp(1, pages());
+
+    let go_to = move |target: u32| {
+        let target = target.clamp(1, page_count(total.get_untracked(), page_size.get_untracked()));
+        if target != page.get_untracked() {
+            page.set(target);
+            if let Some(callback) = on_change {
+                callback.run((target, page_size.get_untracked()));
+            }
+        }
+    };
+    let set_page_size = move |size: u32| {
+        page_size.set(size);
+        page.set(1);
+        if let Some(callback) = on_change {
+            callback.run((1, size));
+        }
+    };
+
+    let nav_button = "inline-flex h-8 min-w-8 items-center justify-center rounded-md border px-2 text-sm transition-colors hover:bg-accent disabled:pointer-events-none disabled:opacity-50";
+
+    let buttons = move || {
+        page_items(current(), pages(), siblings)
+            .into_iter()
+            .map(|item| match item {
+                PageItem::Ellipsis => view! {
+                    <li><span class="inline-flex h-8 w-8 items-center justify-center text-muted-foreground" aria-hidden="true">"…"</span></li>
+                }
+                .into_any(),
+                PageItem::Page(number) => {
+                    let is_current = number == current();
+                    view! {
+                        <li>
+                            <button
+                                type="button"
+                                class=cn(&[
+                                    nav_button,
+                                    if is_current { "border-primary bg-primary text-primary-foreground hover:bg-primary/90" } else { "" },
+                                ])
+                                aria-label=i18n.t_with("pagination.page", &[("page", number.to_string().as_str())])
+                                aria-current=is_current.then_some("page")
+                                on:click=move |_| go_to(number)
+                            >
+                                {number}
+                            </button>
+                        </li>
+                    }
+                    .into_any()
+                }
+            })
+            .collect_view()
+    };
+
+    let size_selector = (!page_size_options.is_empty()).then(|| {
+        let id = format!("page-size-{}", uuid::Uuid::new_v4().simple());
+        let options = page_size_options
+            .into_iter()
+            .map(|size| view! { <option value=size.to_string() selected=move || page_size.get() == size>{size}</option> })
+            .collect_view();
+        view! {
+            <div class="flex items-center gap-2">
+                <label for=id.clone() class="text-muted-foreground">{move || i18n.t("pagination.rows_per_page")}</label>
+                <select
+                    id=id
+                    class="h-8 rounded-md border border-input bg-background px-2 text-sm"
+                    on:change=move |ev| {
+                        if let Ok(size) = event_target_value(&ev).parse() {
+                            set_page_size(size);
+                        }
+                    }
+                >
+                    {options}
+                </select>
+            </div>
+        }
+    });
+
+    let container_classes = cn(&["flex flex-wrap items-center justify-between gap-3 text-sm", class.unwrap_or("")]);
+
+    view! {
+        <nav class=container_classes aria-label="Pagination">
+            <span class="text-muted-foreground" aria-live="polite">
+                {move || showing_label(i18n.locale(), current(), page_size.get(), total.get())}
+            </span>
+            <div class="flex flex-wrap items-center gap-4">
+                {size_selector}
+                <ul class="flex items-center gap-1">
+                    <li>
+                        <button
+                            type="button"
+                            class=nav_button
+                            aria-label=move || i18n.t("pagination.previous")
+                            disabled=move || current() <= 1
+                            on:click=move |_| go_to(current().saturating_sub(1))
+                        >
+                            "‹"
+                        </button>
+                    </li>
+                    {buttons}
+                    <li>
+                        <button
+                            type="button"
+                            class=nav_button
+                            aria-label=move || i18n.t("pagination.next")
+                            disabled=move || current() >= pages()
+                            on:click=move |_| go_to(current() + 1)
+                        >
+                            "›"
+                        </button>
+                    </li>
+                </ul>
+            </div>
+        </nav>
+    }
+}
@@ -0,0 +1,331 @@
+use leptos::prelude::*;
+use std::time::Duration;
+use wasm_bindgen::JsCast;
+use crate::ui::cn;
+
+/// Gap between the trigger and floating content, in pixels
+const FLOATING_OFFSET: f64 = 8.0;
+/// Minimum distance kept from the viewport edge
+const VIEWPORT_MARGIN: f64 = 8.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Placement {
+    pub fn opposite(&self) -> Self {
+        match self {
+            Placement::Top => Placement::Bottom,
+            Placement::Bottom => Placement::Top,
+            Placement::Left => Placement::Right,
+            Placement::Right => Placement::Left,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Placement::Top => "top",
+            Placement::Bottom => "bottom",
+            Placement::Left => "left",
+            Placement::Right => "right",
+        }
+    }
+}
+
+/// Axis-aligned box in viewport coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    fn from_element(element: &web_sys::Element) -> Self {
+        let rect = element.get_bounding_client_rect();
+        Self {
+            x: rect.x(),
+            y: rect.y(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
+
+/// Resolved placement and top-left corner for floating content.
+///
+/// Flips to the opposite side when `preferred` lacks room and the opposite
+/// side has it, then clamps the result inside the viewport.
+pub fn compute_position(
+    trigger: Rect,
+    content: (f64, f64),
+    viewport: (f64, f64),
+    preferred: Placement,
+) -> (Placement, f64, f64) {
+    let (width, height) = content;
+    let (viewport_width, viewport_height) = viewport;
+    let room = |placement: Placement| match placement {
+        Placement::Top => trigger.y - FLOATING_OFFSET - VIEWPORT_MARGIN >= height,
+        Placement::Bottom => viewport_height - (trigger.y + trigger.height) - FLOATING_OFFSET - VIEWPORT_MARGIN >= height,
+        Placement::Left => trigger.x - FLOATING_OFFSET - VIEWPORT_MARGIN >= width,
+        Placement::Right => viewport_width - (trigger.x + trigger.width) - FLOATING_OFFSET - VIEWPORT_MARGIN >= width,
+    };
+
+    let placement = if !room(preferred) && room(preferred.opposite()) {
+        preferred.opposite()
+    } else {
+        preferred
+    };
+
+    let center_x = trigger.x + (trigger.width - width) / 2.0;
+    let center_y = trigger.y + (trigger.height - height) / 2.0;
+    let (left, top) = match placement {
+        Placement::Top => (center_x, trigger.y - height - FLOATING_OFFSET),
+        Placement::Bottom => (center_x, trigger.y + trigger.height + FLOATING_OFFSET),
+        Placement::Left => (trigger.x - width - FLOATING_OFFSET, center_y),
+        Placement::Right => (trigger.x + trigger.width + FLOATING_OFFSET, center_y),
+    };
+
+    let clamp = |value: f64, size: f64, limit: f64| {
+        value.min(limit - size - VIEWPORT_MARGIN).max(VIEWPORT_MARGIN)
+    };
+    (placement, clamp(left, width, viewport_width), clamp(top, height, viewport_height))
+}
+
+fn viewport_size() -> (f64, f64) {
+    let window = window();
+    let dimension = |value: Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue>| {
+        value.ok().and_then(|v| v.as_f64()).unwrap_or(0.0)
+    };
+    (dimension(window.inner_width()), dimension(window.inner_height()))
+}
+
+/// Position `content` next to `anchor`, returning the inline style and resolved side
+fn place(anchor: &web_sys::Element, content: &web_sys::Element, preferred: Placement) -> (String, Placement) {
+    let content_rect = Rect::from_element(content);
+    let (placement, left, top) = compute_position(
+        Rect::from_element(anchor),
+        (content_rect.width, content_rect.height),
+        viewport_size(),
+        preferred,
+    );
+    (format!("position: fixed; left: {:.0}px; top: {:.0}px;", left, top), placement)
+}
+
+/// Hidden until measured so the first frame does not flash in the wrong place
+const UNPLACED_STYLE: &str = "position: fixed; left: 0; top: 0; visibility: hidden;";
+
+/// Short hint shown on hover or keyboard focus, e.g. to expand "BP" or label an icon-only button.
+///
+/// The first element inside the trigger is linked to the tooltip with `aria-describedby`.
+#[component]
+pub fn Tooltip(
+    #[prop(into)] content: String,
+    #[prop(optional)] placement: Option<Placement>,
+    /// Delay before showing, in milliseconds (default: 300)
+    #[prop(optional)] delay_ms: Option<u64>,
+    #[prop(optional)] class: Option<&'static str>,
+    children: Children,
+) -> impl IntoView {
+    let preferred = placement.unwrap_or(Placement::Top);
+    let delay = Duration::from_millis(delay_ms.unwrap_or(300));
+    let tooltip_id = format!("tooltip-{}", uuid::Uuid::new_v4().simple());
+
+    let open = RwSignal::new(false);
+    let pending = RwSignal::new(0u32);
+    let style = RwSignal::new(UNPLACED_STYLE.to_string());
+    let side = RwSignal::new(preferred);
+    let anchor_ref = NodeRef::<leptos::html::Span>::new();
+    let content_ref = NodeRef::<leptos::html::Div>::new();
+
+    let show = move || {
+        // Ignore timers from earlier hovers that have since been cancelled
+        let token = pending.get_untracked().wrapping_add(1);
+        pending.set(token);
+        set_timeout(
+            move || {
+                if pending.get_untracked() == token {
+                    open.set(true);
+                }
+            },
+            delay,
+        );
+    };
+    let hide = move || {
+        pending.update(|token| *token = token.wrapping_add(1));
+        open.set(false);
+        style.set(UNPLACED_STYLE.to_string());
+    };
+
+    let describedby_id = tooltip_id.clone();
+    Effect::new(move |_| {
+        if let Some(trigger) = anchor_ref.get().and_then(|anchor| anchor.first_element_child()) {
+            let _ = trigger.set_attribute("aria-describedby", &describedby_id);
+        }
+    });
+
+    Effect::new(move |_| {
+        if let (true, Some(anchor), Some(content)) = (open.get(), anchor_ref.get(), content_ref.get()) {
+            let (placed, resolved) = place(&anchor, &content, preferred);
+            style.set(placed);
+            side.set(resolved);
+        }
+    });
+
+    let tooltip_classes = cn(&[
+        "z-50 max-w-xs rounded-md bg-foreground px-3 py-1.5 text-xs text-background shadow-md pointer-events-none",
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <span
+            node_ref=anchor_ref
+            class="inline-flex"
+            on:mouseenter=move |_| show()
+            on:mouseleave=move |_| hide()
+            on:focusin=move |_| show()
+            on:focusout=move |_| hide()
+            on:keydown=move |ev| {
+                if ev.key() == "Escape" {
+                    hide();
+                }
+            }
+        >
+            {children()}
+            <Show when=move || open.get()>
+                <div
+                    node_ref=content_ref
+                    id=tooltip_id.clone()
+                    role="tooltip"
+                    class=tooltip_classes.clone()
+                    style=move || style.get()
+                    data-side=move || side.get().as_str()
+                >
+                    {content.clone()}
+                </div>
+            </Show>
+        </span>
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PopoverTrigger {
+    Click,
+    Hover,
+    Focus,
+}
+
+/// Floating panel anchored to a trigger, e.g. the reference range behind a vital-sign value.
+///
+/// Closes on Escape and, for click triggers, on clicks outside the trigger and panel.
+#[component]
+pub fn Popover(
+    /// Content of the trigger element
+    trigger: ChildrenFn,
+    #[prop(optional)] trigger_on: Option<PopoverTrigger>,
+    #[prop(optional)] placement: Option<Placement>,
+    /// Controlled open state; internal state is used when omitted
+    #[prop(optional)] open: Option<RwSignal<bool>>,
+    /// Accessible name of the panel
+    #[prop(optional, into)] label: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+    children: ChildrenFn,
+) -> impl IntoView {
+    let trigger_on = trigger_on.unwrap_or(PopoverTrigger::Click);
+    let preferred = placement.unwrap_or(Placement::Bottom);
+    let open = open.unwrap_or_else(|| RwSignal::new(false));
+    let panel_id = format!("popover-{}", uuid::Uuid::new_v4().simple());
+
+    let style = RwSignal::new(UNPLACED_STYLE.to_string());
+    let side = RwSignal::new(preferred);
+    let root_ref = NodeRef::<leptos::html::Span>::new();
+    let anchor_ref = NodeRef::<leptos::html::Button>::new();
+    let content_ref = NodeRef::<leptos::html::Div>::new();
+
+    let set_open = move |value: bool| {
+        if !value {
+            style.set(UNPLACED_STYLE.to_string());
+        }
+        open.set(value);
+    };
+
+    Effect::new(move |_| {
+        if let (true, Some(anchor), Some(content)) = (open.get(), anchor_ref.get(), content_ref.get()) {
+            let (placed, resolved) = place(&anchor, &content, preferred);
+            style.set(placed);
+            side.set(resolved);
+        }
+    });
+
+    if trigger_on == PopoverTrigger::Click {
+        let handle = window_event_listener(leptos::ev::pointerdown, move |ev| {
+            if !open.get_untracked() {
+                return;
+            }
+            let inside = match (root_ref.get_untracked(), ev.target().and_then(|t| t.dyn_into::<web_sys::Node>().ok())) {
+                (Some(root), Some(target)) => root.contains(Some(&target)),
+                _ => false,
+            };
+            if !inside {
+                set_open(false);
+            }
+        });
+        on_cleanup(move || handle.remove());
+    }
+
+    let panel_classes = cn(&[
+        "z-50 w-72 rounded-md border bg-popover p-4 text-popover-foreground shadow-md outline-none",
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <span
+            node_ref=root_ref
+            class="relative inline-flex"
+            on:keydown=move |ev| {
+                if ev.key() == "Escape" && open.get_untracked() {
+                    ev.stop_propagation();
+                    set_open(false);
+                    if let Some(anchor) = anchor_ref.get_untracked() {
+                        let _ = anchor.focus();
+                    }
+                }
+            }
+            on:mouseenter=move |_| if trigger_on == PopoverTrigger::Hover { set_open(true) }
+            on:mouseleave=move |_| if trigger_on == PopoverTrigger::Hover { set_open(false) }
+            on:focusin=move |_| if trigger_on == PopoverTrigger::Focus { set_open(true) }
+            on:focusout=move |_| if trigger_on == PopoverTrigger::Focus { set_open(false) }
+        >
+            <button
+                type="button"
+                node_ref=anchor_ref
+                class="inline-flex items-center"
+                aria-haspopup="dialog"
+                aria-expanded=move || open.get().to_string()
+                aria-controls=panel_id.clone()
+                on:click=move |_| if trigger_on == PopoverTrigger::Click { set_open(!open.get_untracked()) }
+            >
+                {trigger()}
+            </button>
+            <Show when=move || open.get()>
+                <div
+                    node_ref=content_ref
+                    id=panel_id.clone()
+                    role="dialog"
+                    aria-label=label.clone()
+                    class=panel_classes.clone()
+                    style=move || style.get()
+                    data-side=move || side.get().as_str()
+                    tabindex="-1"
+                >
+                    {children()}
+                </div>
+            </Show>
+        </span>
+    }
+}
@@ -0,0 +1,237 @@
+use leptos::prelude::*;
+use chrono::{Local, NaiveDate};
+use crate::models::{Medication, Prescription};
+use crate::ui::{cn, use_print, Badge, BadgeVariant, Button, ButtonVariant, PrintDocument};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureStatus {
+    Signed,
+    Unsigned,
+    /// Signature present but failed verification
+    Invalid,
+}
+
+impl SignatureStatus {
+    /// Presence check only; pass a verified status to the viewer when one is available
+    pub fn from_prescription(prescription: &Prescription) -> Self {
+        if prescription.digital_signature.trim().is_empty() {
+            SignatureStatus::Unsigned
+        } else {
+            SignatureStatus::Signed
+        }
+    }
+
+    fn badge(&self) -> (BadgeVariant, &'static str) {
+        match self {
+            SignatureStatus::Signed => (BadgeVariant::Success, "Digitally signed"),
+            SignatureStatus::Unsigned => (BadgeVariant::Warning, "Unsigned"),
+            SignatureStatus::Invalid => (BadgeVariant::Destructive, "Signature invalid"),
+        }
+    }
+}
+
+/// Who issued a prescription, for whom, and how long it is valid
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrescriptionDetails {
+    pub id: String,
+    pub patient_name: String,
+    pub prescriber_name: String,
+    pub prescriber_license: Option<String>,
+    pub issued_on: NaiveDate,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl PrescriptionDetails {
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires_on.is_some_and(|expiry| expiry < today)
+    }
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%d %b %Y").to_string()
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Compact summary for prescription lists
+#[component]
+pub fn PrescriptionCard(
+    prescription: Prescription,
+    details: PrescriptionDetails,
+    #[prop(optional)] signature: Option<SignatureStatus>,
+    #[prop(optional)] on_open: Option<Callback<String>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let signature = signature.unwrap_or_else(|| SignatureStatus::from_prescription(&prescription));
+    let (signature_variant, signature_label) = signature.badge();
+    let expired = details.is_expired(today());
+    let names = prescription
+        .medications
+        .iter()
+        .map(|m| m.generic_name.clone())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let card_classes = cn(&[
+        "w-full rounded-lg border bg-card p-4 text-left text-card-foreground shadow-sm",
+        if on_open.is_some() { "cursor-pointer transition-colors hover:bg-accent/50" } else { "" },
+        class.unwrap_or(""),
+    ]);
+    let id = details.id.clone();
+
+    view! {
+        <article
+            class=card_classes
+            tabindex=on_open.map(|_| "0")
+            on:click=move |_| if let Some(callback) = on_open { callback.run(id.clone()) }
+        >
+            <div class="flex items-start justify-between gap-2">
+                <div class="min-w-0">
+                    <h3 class="truncate font-semibold">{names}</h3>
+                    <p class="text-sm text-muted-foreground">
+                        {format!("Dr. {} · {}", details.prescriber_name, format_date(details.issued_on))}
+                    </p>
+                </div>
+                <div class="flex shrink-0 flex-col items-end gap-1">
+                    <Badge variant=signature_variant>{signature_label}</Badge>
+                    {expired.then(|| view! { <Badge variant=BadgeVariant::Destructive>"Expired"</Badge> })}
+                </div>
+            </div>
+            <p class="mt-2 text-xs text-muted-foreground">
+                {format!("{} medication{}", prescription.medications.len(), if prescription.medications.len() == 1 { "" } else { "s" })}
+            </p>
+        </article>
+    }
+}
+
+fn medication_row(medication: Medication) -> impl IntoView {
+    view! {
+        <tr class="border-b align-top last:border-0 print:break-inside-avoid">
+            <td class="py-2 pr-4 font-medium">{medication.display_name()}</td>
+            <td class="py-2 pr-4">{format!("{} {}", medication.dosage, medication.route.abbreviation()).trim().to_string()}</td>
+            <td class="py-2 pr-4">{medication.frequency.label()}</td>
+            <td class="py-2 pr-4">{medication.duration}</td>
+            <td class="py-2 text-muted-foreground">{medication.instructions.unwrap_or_default()}</td>
+        </tr>
+    }
+}
+
+/// Medication table shared by prescriptions and discharge summaries
+pub(crate) fn medication_table(medications: Vec<Medication>) -> impl IntoView {
+    view! {
+        <div class="overflow-x-auto">
+            <table class="w-full text-sm">
+                <thead>
+                    <tr class="border-b text-left text-muted-foreground">
+                        <th scope="col" class="py-2 pr-4 font-medium">"Medication"</th>
+                        <th scope="col" class="py-2 pr-4 font-medium">"Dosage"</th>
+                        <th scope="col" class="py-2 pr-4 font-medium">"Frequency"</th>
+                        <th scope="col" class="py-2 pr-4 font-medium">"Duration"</th>
+                        <th scope="col" class="py-2 font-medium">"Instructions"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {medications.into_iter().map(medication_row).collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Full prescription with a print-ready layout.
+///
+/// Print uses the browser dialog; the action bar is hidden on paper. Download
+/// is left to the app (e.g. fetching the signed PDF) through `on_download`.
+#[component]
+pub fn PrescriptionViewer(
+    prescription: Prescription,
+    details: PrescriptionDetails,
+    #[prop(optional)] signature: Option<SignatureStatus>,
+    /// Called with the prescription id
+    #[prop(optional)] on_download: Option<Callback<String>>,
+    #[prop(optional)] show_print: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let show_print = show_print.unwrap_or(true);
+    let signature = signature.unwrap_or_else(|| SignatureStatus::from_prescription(&prescription));
+    let (signature_variant, signature_label) = signature.badge();
+    let expired = details.is_expired(today());
+    let download_id = details.id.clone();
+    let print = use_print();
+
+    let container_classes = cn(&[
+        "print-document",
+        PrintDocument::Prescription.css_class(),
+        "w-full space-y-6 rounded-lg border bg-card p-6 text-card-foreground shadow-sm print:border-0 print:p-0 print:shadow-none",
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <article class=container_classes aria-label="Prescription">
+            <header class="flex flex-wrap items-start justify-between gap-4 border-b pb-4">
+                <div>
+                    <h2 class="text-xl font-semibold">"Prescription"</h2>
+                    <p class="text-sm text-muted-foreground">{format!("Ref. {}", details.id)}</p>
+                </div>
+                <div class="flex flex-wrap items-center gap-2">
+                    <Badge variant=signature_variant>{signature_label}</Badge>
+                    {expired.then(|| view! { <Badge variant=BadgeVariant::Destructive>"Expired"</Badge> })}
+                </div>
+            </header>
+
+            <dl class="grid grid-cols-2 gap-4 text-sm sm:grid-cols-4">
+                <div>
+                    <dt class="text-muted-foreground">"Patient"</dt>
+                    <dd class="font-medium">{details.patient_name.clone()}</dd>
+                </div>
+                <div>
+                    <dt class="text-muted-foreground">"Prescriber"</dt>
+                    <dd class="font-medium">{format!("Dr. {}", details.prescriber_name)}</dd>
+                    {details.prescriber_license.clone().map(|license| view! {
+                        <dd class="text-xs text-muted-foreground">{format!("Licence {}", license)}</dd>
+                    })}
+                </div>
+                <div>
+                    <dt class="text-muted-foreground">"Issued"</dt>
+                    <dd class="font-medium">{format_date(details.issued_on)}</dd>
+                </div>
+                <div>
+                    <dt class="text-muted-foreground">"Valid until"</dt>
+                    <dd class=cn(&["font-medium", if expired { "text-destructive" } else { "" }])>
+                        {details.expires_on.map(format_date).unwrap_or_else(|| "—".to_string())}
+                    </dd>
+                </div>
+            </dl>
+
+            {medication_table(prescription.medications)}
+
+            {(!prescription.instructions.trim().is_empty()).then(|| view! {
+                <section class="space-y-1">
+                    <h3 class="text-sm font-semibold">"Instructions"</h3>
+                    <p class="whitespace-pre-line text-sm">{prescription.instructions.clone()}</p>
+                </section>
+            })}
+
+            {prescription.follow_up_date.map(|date| view! {
+                <p class="text-sm">
+                    <span class="font-medium">"Follow-up: "</span>
+                    {format_date(date)}
+                </p>
+            })}
+
+            <footer class="no-print flex flex-wrap items-center justify-end gap-2 border-t pt-4">
+                {show_print.then(|| view! {
+                    <Button variant=ButtonVariant::Outline on_click=Callback::new(move |_| print.print())>
+                        "Print"
+                    </Button>
+                })}
+                {on_download.map(|callback| view! {
+                    <Button on_click=Callback::new(move |_| callback.run(download_id.clone()))>
+                        "Download"
+                    </Button>
+                })}
+            </footer>
+        </article>
+    }
+}
@@ -0,0 +1,276 @@
+use leptos::prelude::*;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use crate::ids::PatientId;
+use crate::models::Medication;
+use crate::ui::{cn, use_i18n, Button, ButtonVariant};
+use crate::ui::prescription::medication_table;
+
+/// Documents with a dedicated print stylesheet in `styles.css`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrintDocument {
+    Prescription,
+    DischargeSummary,
+    AppointmentConfirmation,
+}
+
+impl PrintDocument {
+    pub fn title_key(&self) -> &'static str {
+        match self {
+            PrintDocument::Prescription => "print.document.prescription",
+            PrintDocument::DischargeSummary => "print.document.discharge_summary",
+            PrintDocument::AppointmentConfirmation => "print.document.appointment_confirmation",
+        }
+    }
+
+    /// Class that selects the document's `@media print` rules
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            PrintDocument::Prescription => "print-prescription",
+            PrintDocument::DischargeSummary => "print-discharge-summary",
+            PrintDocument::AppointmentConfirmation => "print-appointment-confirmation",
+        }
+    }
+}
+
+/// Print trigger and state from [`use_print`]
+#[derive(Debug, Clone, Copy)]
+pub struct PrintHandle {
+    printing: RwSignal<bool>,
+}
+
+impl PrintHandle {
+    /// Open the browser print dialog
+    pub fn print(&self) {
+        let _ = window().print();
+    }
+
+    /// True while the page is rendered for paper, e.g. to expand collapsed sections
+    pub fn is_printing(&self) -> bool {
+        self.printing.get()
+    }
+}
+
+/// Print the current page and follow the browser's print lifecycle
+pub fn use_print() -> PrintHandle {
+    let printing = RwSignal::new(false);
+    let before = window_event_listener_untyped("beforeprint", move |_| printing.set(true));
+    let after = window_event_listener_untyped("afterprint", move |_| printing.set(false));
+    on_cleanup(move || {
+        before.remove();
+        after.remove();
+    });
+    PrintHandle { printing }
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%d %b %Y").to_string()
+}
+
+/// Letterhead, confidentiality footer and print button around a clinical
+/// document. On paper only the document itself is printed: app chrome marked
+/// `no-print` is hidden, margins come from `@page`, and the footer repeats
+/// the print time so stale copies can be spotted.
+#[component]
+pub fn PrintLayout(
+    document: PrintDocument,
+    /// Clinic or hospital named in the letterhead
+    #[prop(into)] facility: String,
+    /// Address and phone line under the facility name
+    #[prop(optional, into)] facility_details: Option<String>,
+    #[prop(optional, into)] reference: Option<String>,
+    #[prop(optional)] show_print: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+    children: Children,
+) -> impl IntoView {
+    let show_print = show_print.unwrap_or(true);
+    let i18n = use_i18n();
+    let print = use_print();
+    let printed_on = Local::now().format("%d %b %Y %H:%M").to_string();
+
+    let container_classes = cn(&[
+        "print-document mx-auto w-full max-w-3xl space-y-6 rounded-lg border bg-card p-8 text-card-foreground shadow-sm",
+        document.css_class(),
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <article class=container_classes aria-label=move || i18n.t(document.title_key())>
+            {show_print.then(|| view! {
+                <div class="no-print flex justify-end">
+                    <Button variant=ButtonVariant::Outline on_click=Callback::new(move |_| print.print())>
+                        {move || i18n.t("print.print")}
+                    </Button>
+                </div>
+            })}
+            <header class="print-letterhead flex items-start justify-between gap-4 border-b pb-4">
+                <div>
+                    <p class="text-lg font-semibold">{facility}</p>
+                    {facility_details.filter(|details| !details.is_empty()).map(|details| view! {
+                        <p class="text-sm text-muted-foreground">{details}</p>
+                    })}
+                </div>
+                <div class="text-right">
+                    <h1 class="text-xl font-semibold">{move || i18n.t(document.title_key())}</h1>
+                    {reference.map(|reference| view! {
+                        <p class="text-sm text-muted-foreground">
+                            {move || i18n.t_with("print.reference", &[("id", reference.as_str())])}
+                        </p>
+                    })}
+                </div>
+            </header>
+            <div class="print-body space-y-6">{children()}</div>
+            <footer class="print-footer flex justify-between gap-4 border-t pt-2 text-xs text-muted-foreground">
+                <span>{move || i18n.t("print.confidential")}</span>
+                <span>{move || i18n.t_with("print.printed_on", &[("date", printed_on.as_str())])}</span>
+            </footer>
+        </article>
+    }
+}
+
+fn field(label: String, value: String) -> impl IntoView {
+    view! {
+        <div>
+            <dt class="text-muted-foreground">{label}</dt>
+            <dd class="font-medium">{value}</dd>
+        </div>
+    }
+}
+
+fn signature_line(label: String) -> impl IntoView {
+    view! {
+        <div class="print-signature mt-12 w-64 border-t pt-1 text-xs text-muted-foreground">{label}</div>
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DischargeSummary {
+    pub id: String,
+    pub patient_name: String,
+    pub patient_id: PatientId,
+    pub attending_physician: String,
+    pub admitted_on: NaiveDate,
+    pub discharged_on: NaiveDate,
+    pub diagnosis: String,
+    pub hospital_course: String,
+    pub medications: Vec<Medication>,
+    pub instructions: String,
+    pub follow_up: Option<String>,
+}
+
+/// Discharge summary in a [`PrintLayout`], signed by the attending physician
+#[component]
+pub fn DischargeSummaryView(
+    summary: DischargeSummary,
+    #[prop(into)] facility: String,
+    #[prop(optional, into)] facility_details: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let t = move |key: &str| i18n.t(key);
+    let facility_details = facility_details.unwrap_or_default();
+
+    view! {
+        <PrintLayout
+            document=PrintDocument::DischargeSummary
+            facility=facility
+            facility_details=facility_details
+            reference=summary.id.clone()
+            class=class.unwrap_or("")
+        >
+            <dl class="grid grid-cols-2 gap-4 text-sm sm:grid-cols-3">
+                {field(t("print.field.patient"), summary.patient_name.clone())}
+                {field(t("print.field.patient_id"), summary.patient_id.to_string())}
+                {field(t("print.field.attending"), format!("Dr. {}", summary.attending_physician))}
+                {field(t("print.field.admitted"), format_date(summary.admitted_on))}
+                {field(t("print.field.discharged"), format_date(summary.discharged_on))}
+            </dl>
+            <section class="space-y-1 print:break-inside-avoid">
+                <h2 class="text-sm font-semibold">{t("print.field.diagnosis")}</h2>
+                <p class="text-sm">{summary.diagnosis.clone()}</p>
+            </section>
+            <section class="space-y-1">
+                <h2 class="text-sm font-semibold">{t("print.field.hospital_course")}</h2>
+                <p class="whitespace-pre-line text-sm">{summary.hospital_course.clone()}</p>
+            </section>
+            {(!summary.medications.is_empty()).then(|| view! {
+                <section class="space-y-1">
+                    <h2 class="text-sm font-semibold">{t("print.field.medications")}</h2>
+                    {medication_table(summary.medications.clone())}
+                </section>
+            })}
+            {(!summary.instructions.trim().is_empty()).then(|| view! {
+                <section class="space-y-1 print:break-inside-avoid">
+                    <h2 class="text-sm font-semibold">{t("print.field.instructions")}</h2>
+                    <p class="whitespace-pre-line text-sm">{summary.instructions.clone()}</p>
+                </section>
+            })}
+            {summary.follow_up.clone().map(|follow_up| view! {
+                <p class="text-sm">
+                    <span class="font-medium">{format!("{}: ", t("print.field.follow_up"))}</span>
+                    {follow_up}
+                </p>
+            })}
+            {signature_line(format!("{} · Dr. {}", t("print.field.signature"), summary.attending_physician))}
+        </PrintLayout>
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppointmentConfirmation {
+    pub id: String,
+    pub patient_name: String,
+    pub provider_name: String,
+    pub appointment_type: String,
+    pub scheduled_time: DateTime<Utc>,
+    pub location: Option<String>,
+    /// Minutes before the appointment the patient should check in
+    pub arrive_early_minutes: Option<u32>,
+    pub instructions: Option<String>,
+}
+
+/// Appointment slip for the patient, printed at the front desk
+#[component]
+pub fn AppointmentConfirmationView(
+    confirmation: AppointmentConfirmation,
+    #[prop(into)] facility: String,
+    #[prop(optional, into)] facility_details: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let t = move |key: &str| i18n.t(key);
+    let facility_details = facility_details.unwrap_or_default();
+    let scheduled = confirmation
+        .scheduled_time
+        .with_timezone(&Local)
+        .format("%a %d %b %Y, %H:%M")
+        .to_string();
+
+    view! {
+        <PrintLayout
+            document=PrintDocument::AppointmentConfirmation
+            facility=facility
+            facility_details=facility_details
+            reference=confirmation.id.clone()
+            class=class.unwrap_or("")
+        >
+            <dl class="grid grid-cols-2 gap-4 text-sm">
+                {field(t("print.field.patient"), confirmation.patient_name.clone())}
+                {field(t("print.field.provider"), format!("Dr. {}", confirmation.provider_name))}
+                {field(t("print.field.visit_type"), confirmation.appointment_type.clone())}
+                {field(t("print.field.date_time"), scheduled)}
+                {confirmation.location.clone().map(|location| field(t("print.field.location"), location))}
+            </dl>
+            {confirmation.arrive_early_minutes.map(|minutes| view! {
+                <p class="text-sm font-medium">
+                    {i18n.t_with("print.arrive_early", &[("minutes", minutes.to_string().as_str())])}
+                </p>
+            })}
+            {confirmation.instructions.clone().map(|instructions| view! {
+                <section class="space-y-1">
+                    <h2 class="text-sm font-semibold">{t("print.field.instructions")}</h2>
+                    <p class="whitespace-pre-line text-sm">{instructions}</p>
+                </section>
+            })}
+        </PrintLayout>
+    }
+}
@@ -0,0 +1,454 @@
+use leptos::prelude::*;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::sync::Arc;
+use crate::healthcare_service_engine::TimeWindow;
+use crate::models::AppointmentSlot;
+use crate::ui::cn;
+use crate::ui::healthcare::{AppointmentInfo, AppointmentStatus};
+use crate::utils::datetime::{intervals_overlap, parse_clock_time, parse_duration_minutes};
+
+/// Appointment length assumed when `AppointmentInfo::duration` cannot be parsed
+const DEFAULT_APPOINTMENT_MINUTES: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchedulerView {
+    Day,
+    Week,
+    Month,
+}
+
+impl SchedulerView {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchedulerView::Day => "day",
+            SchedulerView::Week => "week",
+            SchedulerView::Month => "month",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SchedulerView::Day => "Day",
+            SchedulerView::Week => "Week",
+            SchedulerView::Month => "Month",
+        }
+    }
+}
+
+/// An appointment placed on the calendar, with parsed local times
+#[derive(Debug, Clone)]
+pub struct ScheduledAppointment {
+    pub appointment: AppointmentInfo,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    /// Overlaps another active appointment with the same doctor
+    pub conflict: bool,
+}
+
+impl ScheduledAppointment {
+    /// Place an appointment using its `date` (YYYY-MM-DD), `time` and `duration` strings
+    pub fn from_info(appointment: AppointmentInfo) -> Option<Self> {
+        let date = NaiveDate::parse_from_str(appointment.date.trim(), "%Y-%m-%d").ok()?;
+        let time = parse_clock_time(&appointment.time)?;
+        let minutes = parse_duration_minutes(&appointment.duration).unwrap_or(DEFAULT_APPOINTMENT_MINUTES);
+        let start = date.and_time(time);
+
+        Some(Self {
+            appointment,
+            start,
+            end: start + Duration::minutes(minutes),
+            conflict: false,
+        })
+    }
+
+    fn is_active(&self) -> bool {
+        !matches!(
+            self.appointment.status,
+            AppointmentStatus::Cancelled | AppointmentStatus::NoShow
+        )
+    }
+}
+
+/// Parse appointments and flag double-bookings for the same doctor
+pub fn schedule_appointments(appointments: Vec<AppointmentInfo>) -> Vec<ScheduledAppointment> {
+    let mut scheduled: Vec<_> = appointments
+        .into_iter()
+        .filter_map(ScheduledAppointment::from_info)
+        .collect();
+    scheduled.sort_by_key(|a| a.start);
+
+    for i in 0..scheduled.len() {
+        for j in (i + 1)..scheduled.len() {
+            if scheduled[j].start >= scheduled[i].end {
+                break;
+            }
+            let clash = scheduled[i].is_active()
+                && scheduled[j].is_active()
+                && scheduled[i].appointment.doctor_name == scheduled[j].appointment.doctor_name;
+            if clash {
+                scheduled[i].conflict = true;
+                scheduled[j].conflict = true;
+            }
+        }
+    }
+    scheduled
+}
+
+fn start_of_week(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn shift_date(date: NaiveDate, view: SchedulerView, forward: bool) -> NaiveDate {
+    let sign = if forward { 1 } else { -1 };
+    match view {
+        SchedulerView::Day => date + Duration::days(sign),
+        SchedulerView::Week => date + Duration::days(7 * sign),
+        SchedulerView::Month if forward => date.checked_add_months(chrono::Months::new(1)).unwrap_or(date),
+        SchedulerView::Month => date.checked_sub_months(chrono::Months::new(1)).unwrap_or(date),
+    }
+}
+
+fn period_title(date: NaiveDate, view: SchedulerView) -> String {
+    match view {
+        SchedulerView::Day => date.format("%A, %-d %B %Y").to_string(),
+        SchedulerView::Week => {
+            let start = start_of_week(date);
+            let end = start + Duration::days(6);
+            format!("{} – {}", start.format("%-d %b"), end.format("%-d %b %Y"))
+        }
+        SchedulerView::Month => date.format("%B %Y").to_string(),
+    }
+}
+
+fn appointment_classes(item: &ScheduledAppointment) -> String {
+    cn(&[
+        "w-full rounded-md border-l-4 px-2 py-1 text-left text-xs shadow-sm transition-colors hover:brightness-95 focus:outline-none focus-visible:ring-2 focus-visible:ring-ring",
+        if item.conflict { "border-red-600 bg-red-50 ring-1 ring-red-400" } else { "bg-blue-50" },
+        if item.appointment.status == AppointmentStatus::Cancelled { "line-through opacity-60" } else { "" },
+    ])
+}
+
+/// Calendar for provider and admin apps with day, week and month views.
+///
+/// Open availability slots that do not clash with an existing appointment are
+/// rendered as buttons; clicking one calls `on_slot_click` to book it.
+#[component]
+pub fn Scheduler(
+    #[prop(into)] appointments: Signal<Vec<AppointmentInfo>>,
+    /// Bookable slots, typically from `ProviderAvailability::slots_on`
+    #[prop(optional, into)] slots: Option<Signal<Vec<AppointmentSlot>>>,
+    /// IANA timezone used to display slots (default: UTC)
+    #[prop(optional, into)] timezone: Option<String>,
+    /// Visible hours in day and week views (default: 08:00–18:00)
+    #[prop(optional)] working_hours: Option<TimeWindow>,
+    #[prop(optional)] initial_date: Option<NaiveDate>,
+    #[prop(optional)] initial_view: Option<SchedulerView>,
+    /// Grid row height in minutes (default: 30)
+    #[prop(optional)] interval_minutes: Option<i64>,
+    #[prop(optional)] on_slot_click: Option<Callback<AppointmentSlot>>,
+    #[prop(optional)] on_appointment_click: Option<Callback<AppointmentInfo>>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let tz: chrono_tz::Tz = timezone
+        .as_deref()
+        .and_then(|tz| tz.parse().ok())
+        .unwrap_or(chrono_tz::UTC);
+    let interval = Duration::minutes(interval_minutes.unwrap_or(30).max(5));
+    let (day_start, day_end) = working_hours
+        .as_ref()
+        .and_then(|w| Some((parse_clock_time(&w.start_time)?, parse_clock_time(&w.end_time)?)))
+        .filter(|(start, end)| start < end)
+        .unwrap_or((
+            NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        ));
+
+    let today = Utc::now().with_timezone(&tz).date_naive();
+    let current_date = RwSignal::new(initial_date.unwrap_or(today));
+    let view_mode = RwSignal::new(initial_view.unwrap_or(SchedulerView::Week));
+
+    let scheduled = Signal::derive(move || Arc::new(schedule_appointments(appointments.get())));
+    let local_slots = Signal::derive(move || {
+        slots
+            .map(|slots| slots.get())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|slot| {
+                let start = slot.start_time.with_timezone(&tz).naive_local();
+                let end = slot.end_time.with_timezone(&tz).naive_local();
+                (slot, start, end)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let appointment_button = move |item: ScheduledAppointment| {
+        let label = format!(
+            "{} {} with {}{}",
+            item.start.format("%H:%M"),
+            item.appointment.appointment_type,
+            item.appointment.patient_name,
+            if item.conflict { " (conflict)" } else { "" },
+        );
+        let classes = appointment_classes(&item);
+        let time = item.start.format("%H:%M").to_string();
+        let conflict = item.conflict;
+        let appointment = item.appointment;
+        let title = appointment.patient_name.clone();
+        let kind = appointment.appointment_type.clone();
+        view! {
+            <button
+                type="button"
+                class=classes
+                aria-label=label
+                on:click=move |_| {
+                    if let Some(callback) = on_appointment_click {
+                        callback.run(appointment.clone());
+                    }
+                }
+            >
+                <span class="font-semibold">{time}" "{title}</span>
+                <span class="block truncate text-muted-foreground">{kind}</span>
+                {conflict.then(|| view! { <span class="block font-semibold text-red-700">"⚠ Conflict"</span> })}
+            </button>
+        }
+    };
+
+    let slot_button = move |slot: AppointmentSlot, start: NaiveDateTime| {
+        let label = format!("Book {}", start.format("%H:%M"));
+        view! {
+            <button
+                type="button"
+                class="w-full rounded-md border border-dashed border-green-500 bg-green-50 px-2 py-1 text-left text-xs text-green-800 hover:bg-green-100 focus:outline-none focus-visible:ring-2 focus-visible:ring-ring disabled:cursor-default"
+                aria-label=format!("Book slot at {}", start.format("%-d %B %H:%M"))
+                disabled=on_slot_click.is_none()
+                on:click=move |_| {
+                    if let Some(callback) = on_slot_click {
+                        callback.run(slot.clone());
+                    }
+                }
+            >
+                {label}
+            </button>
+        }
+    };
+
+    // Day and week views: rows of `interval` between the working hours
+    let time_grid = move |days: Vec<NaiveDate>| {
+        let scheduled = scheduled.get();
+        let slots = local_slots.get();
+        let mut rows = Vec::new();
+        let mut row_start = day_start;
+        while row_start < day_end {
+            rows.push(row_start);
+            let (next, wrapped) = row_start.overflowing_add_signed(interval);
+            if wrapped != 0 {
+                break;
+            }
+            row_start = next;
+        }
+
+        let header = days
+            .iter()
+            .map(|day| {
+                let is_today = *day == today;
+                view! {
+                    <th scope="col" class=cn(&["border-b px-2 py-2 text-left text-xs font-medium", if is_today { "text-primary" } else { "text-muted-foreground" }])>
+                        {day.format("%a %-d").to_string()}
+                    </th>
+                }
+            })
+            .collect_view();
+
+        let body = rows
+            .into_iter()
+            .map(|row_time| {
+                let cells = days
+                    .iter()
+                    .map(|day| {
+                        let cell_start = day.and_time(row_time);
+                        let cell_end = cell_start + interval;
+                        let in_cell = |start: NaiveDateTime| start >= cell_start && start < cell_end;
+
+                        let appointments = scheduled
+                            .iter()
+                            .filter(|item| in_cell(item.start))
+                            .cloned()
+                            .map(appointment_button)
+                            .collect_view();
+                        let open_slots = slots
+                            .iter()
+                            .filter(|(_, start, end)| {
+                                in_cell(*start)
+                                    && !scheduled.iter().any(|item| {
+                                        item.is_active() && intervals_overlap(*start, *end, item.start, item.end)
+                                    })
+                            })
+                            .map(|(slot, start, _)| slot_button(slot.clone(), *start))
+                            .collect_view();
+
+                        view! {
+                            <td class="h-12 min-w-[8rem] border-b border-l p-1 align-top">
+                                <div class="space-y-1">{appointments}{open_slots}</div>
+                            </td>
+                        }
+                    })
+                    .collect_view();
+
+                view! {
+                    <tr>
+                        <th scope="row" class="w-16 border-b px-2 py-1 text-right align-top text-xs font-normal text-muted-foreground">
+                            {row_time.format("%H:%M").to_string()}
+                        </th>
+                        {cells}
+                    </tr>
+                }
+            })
+            .collect_view();
+
+        view! {
+            <table class="w-full border-collapse text-sm">
+                <thead class="sticky top-0 z-10 bg-background">
+                    <tr>
+                        <th scope="col" class="w-16 border-b"><span class="sr-only">"Time"</span></th>
+                        {header}
+                    </tr>
+                </thead>
+                <tbody>{body}</tbody>
+            </table>
+        }
+        .into_any()
+    };
+
+    let month_grid = move |date: NaiveDate| {
+        let scheduled = scheduled.get();
+        let first = date.with_day(1).unwrap_or(date);
+        let grid_start = start_of_week(first);
+
+        let weeks = (0..6)
+            .map(|week| {
+                let days = (0..7)
+                    .map(|offset| {
+                        let day = grid_start + Duration::days(week * 7 + offset);
+                        let in_month = day.month() == first.month();
+                        let items: Vec<_> = scheduled.iter().filter(|item| item.start.date() == day).cloned().collect();
+                        let has_conflict = items.iter().any(|item| item.conflict);
+                        let overflow = items.len().saturating_sub(3);
+                        let preview = items.into_iter().take(3).map(appointment_button).collect_view();
+
+                        view! {
+                            <td class=cn(&[
+                                "h-28 w-[14.28%] border p-1 align-top",
+                                if in_month { "" } else { "bg-muted/40 text-muted-foreground" },
+                                if has_conflict { "bg-red-50/60" } else { "" },
+                            ])>
+                                <button
+                                    type="button"
+                                    class=cn(&["mb-1 rounded-full px-2 text-xs font-medium hover:bg-muted", if day == today { "bg-primary text-primary-foreground" } else { "" }])
+                                    aria-label=format!("Show {}", day.format("%-d %B %Y"))
+                                    on:click=move |_| {
+                                        current_date.set(day);
+                                        view_mode.set(SchedulerView::Day);
+                                    }
+                                >
+                                    {day.day()}
+                                </button>
+                                <div class="space-y-1">
+                                    {preview}
+                                    {(overflow > 0).then(|| view! { <p class="px-1 text-xs text-muted-foreground">{format!("+{} more", overflow)}</p> })}
+                                </div>
+                            </td>
+                        }
+                    })
+                    .collect_view();
+                view! { <tr>{days}</tr> }
+            })
+            .collect_view();
+
+        let weekday_headers = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+            .into_iter()
+            .map(|name| view! { <th scope="col" class="border-b px-2 py-2 text-left text-xs font-medium text-muted-foreground">{name}</th> })
+            .collect_view();
+
+        view! {
+            <table class="w-full table-fixed border-collapse text-sm">
+                <thead><tr>{weekday_headers}</tr></thead>
+                <tbody>{weeks}</tbody>
+            </table>
+        }
+        .into_any()
+    };
+
+    let grid = move || {
+        let date = current_date.get();
+        match view_mode.get() {
+            SchedulerView::Day => time_grid(vec![date]),
+            SchedulerView::Week => {
+                let start = start_of_week(date);
+                time_grid((0..7).map(|offset| start + Duration::days(offset)).collect())
+            }
+            SchedulerView::Month => month_grid(date),
+        }
+    };
+
+    let view_buttons = [SchedulerView::Day, SchedulerView::Week, SchedulerView::Month]
+        .into_iter()
+        .map(|mode| {
+            view! {
+                <button
+                    type="button"
+                    class=move || cn(&[
+                        "h-8 px-3 text-sm font-medium first:rounded-l-md last:rounded-r-md border",
+                        if view_mode.get() == mode { "bg-primary text-primary-foreground" } else { "bg-background hover:bg-muted" },
+                    ])
+                    aria-pressed=move || (view_mode.get() == mode).to_string()
+                    on:click=move |_| view_mode.set(mode)
+                >
+                    {mode.label()}
+                </button>
+            }
+        })
+        .collect_view();
+
+    let container_classes = cn(&["w-full space-y-3", class.unwrap_or("")]);
+
+    view! {
+        <div class=container_classes>
+            <div class="flex flex-wrap items-center justify-between gap-2">
+                <div class="flex items-center gap-2">
+                    <button
+                        type="button"
+                        class="h-8 rounded-md border px-2 hover:bg-muted"
+                        aria-label="Previous period"
+                        on:click=move |_| current_date.update(|d| *d = shift_date(*d, view_mode.get_untracked(), false))
+                    >
+                        "‹"
+                    </button>
+                    <button
+                        type="button"
+                        class="h-8 rounded-md border px-3 text-sm hover:bg-muted"
+                        on:click=move |_| current_date.set(today)
+                    >
+                        "Today"
+                    </button>
+                    <button
+                        type="button"
+                        class="h-8 rounded-md border px-2 hover:bg-muted"
+                        aria-label="Next period"
+                        on:click=move |_| current_date.update(|d| *d = shift_date(*d, view_mode.get_untracked(), true))
+                    >
+                        "›"
+                    </button>
+                    <h2 class="ml-2 text-lg font-semibold" aria-live="polite">
+                        {move || period_title(current_date.get(), view_mode.get())}
+                    </h2>
+                </div>
+                <div class="inline-flex" role="group" aria-label="Calendar view">
+                    {view_buttons}
+                </div>
+            </div>
+            <div class="max-h-[70vh] overflow-auto rounded-md border" data-view=move || view_mode.get().as_str()>
+                {grid}
+            </div>
+        </div>
+    }
+}
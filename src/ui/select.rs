@@ -0,0 +1,304 @@
+use leptos::prelude::*;
+use crate::ui::cn;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectOption {
+    pub value: String,
+    pub label: String,
+    /// Options sharing a group are listed under a common heading
+    pub group: Option<String>,
+    pub description: Option<String>,
+    pub disabled: bool,
+}
+
+impl SelectOption {
+    pub fn new(value: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+            group: None,
+            description: None,
+            disabled: false,
+        }
+    }
+
+    pub fn in_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.disabled = true;
+        self
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        query.is_empty() || self.label.to_lowercase().contains(&query.to_lowercase())
+    }
+}
+
+/// Next enabled option index when moving by `step`, without wrapping
+fn step_option(options: &[SelectOption], from: Option<usize>, step: isize) -> Option<usize> {
+    let len = options.len() as isize;
+    let mut index = match from {
+        Some(index) => index as isize + step,
+        None if step > 0 => 0,
+        None => len - 1,
+    };
+    while (0..len).contains(&index) {
+        if !options[index as usize].disabled {
+            return Some(index as usize);
+        }
+        index += step;
+    }
+    from
+}
+
+/// Styled replacement for `<select>` supporting multi-select, search and option groups.
+///
+/// `value` always holds a list of selected values; single selects keep at most one.
+#[component]
+pub fn Select(
+    #[prop(into)] options: Signal<Vec<SelectOption>>,
+    value: RwSignal<Vec<String>>,
+    #[prop(optional)] multiple: Option<bool>,
+    #[prop(optional)] searchable: Option<bool>,
+    #[prop(optional, into)] placeholder: Option<String>,
+    /// Accessible name when there is no visible `<label>`
+    #[prop(optional, into)] label: Option<String>,
+    #[prop(optional, into)] disabled: Option<Signal<bool>>,
+    #[prop(optional)] on_change: Option<Callback<Vec<String>>>,
+    #[prop(optional)] id: Option<&'static str>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let multiple = multiple.unwrap_or(false);
+    let searchable = searchable.unwrap_or(false);
+    let placeholder = placeholder.unwrap_or_else(|| "Select…".to_string());
+    let is_disabled = move || disabled.map(|d| d.get()).unwrap_or(false);
+    let listbox_id = format!("select-{}", uuid::Uuid::new_v4().simple());
+
+    let open = RwSignal::new(false);
+    let query = RwSignal::new(String::new());
+    let highlighted = RwSignal::new(None::<usize>);
+
+    let visible_options = Signal::derive(move || {
+        let query = query.get();
+        options.get().into_iter().filter(|o| o.matches(&query)).collect::<Vec<_>>()
+    });
+
+    let close = move || {
+        open.set(false);
+        query.set(String::new());
+        highlighted.set(None);
+    };
+
+    let toggle_value = move |option_value: String| {
+        value.update(|selected| {
+            if multiple {
+                if let Some(position) = selected.iter().position(|v| *v == option_value) {
+                    selected.remove(position);
+                } else {
+                    selected.push(option_value);
+                }
+            } else {
+                *selected = vec![option_value];
+            }
+        });
+        if let Some(callback) = on_change {
+            callback.run(value.get_untracked());
+        }
+        if !multiple {
+            close();
+        }
+    };
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        let options = visible_options.get_untracked();
+        match ev.key().as_str() {
+            "ArrowDown" | "ArrowUp" => {
+                ev.prevent_default();
+                if !open.get_untracked() {
+                    open.set(true);
+                }
+                let step = if ev.key() == "ArrowDown" { 1 } else { -1 };
+                highlighted.set(step_option(&options, highlighted.get_untracked(), step));
+            }
+            "Home" if open.get_untracked() => {
+                ev.prevent_default();
+                highlighted.set(step_option(&options, None, 1));
+            }
+            "End" if open.get_untracked() => {
+                ev.prevent_default();
+                highlighted.set(step_option(&options, None, -1));
+            }
+            "Enter" | " " if !(searchable && ev.key() == " " && open.get_untracked()) => {
+                ev.prevent_default();
+                if !open.get_untracked() {
+                    open.set(true);
+                } else if let Some(option) = highlighted.get_untracked().and_then(|i| options.get(i)) {
+                    if !option.disabled {
+                        toggle_value(option.value.clone());
+                    }
+                }
+            }
+            "Escape" if open.get_untracked() => {
+                ev.prevent_default();
+                close();
+            }
+            "Tab" => close(),
+            _ => {}
+        }
+    };
+
+    let summary = move || {
+        let selected = value.get();
+        let options = options.get();
+        let labels: Vec<String> = selected
+            .iter()
+            .filter_map(|v| options.iter().find(|o| &o.value == v).map(|o| o.label.clone()))
+            .collect();
+        match labels.len() {
+            0 => None,
+            1 => labels.into_iter().next(),
+            n if n <= 2 => Some(labels.join(", ")),
+            n => Some(format!("{} selected", n)),
+        }
+    };
+
+    let option_list_id = listbox_id.clone();
+    let option_list = move || {
+        let options = visible_options.get();
+        if options.is_empty() {
+            return view! { <li class="px-2 py-1.5 text-sm text-muted-foreground">"No options"</li> }.into_any();
+        }
+
+        let mut current_group: Option<String> = None;
+        options
+            .into_iter()
+            .enumerate()
+            .map(|(index, option)| {
+                let heading = (option.group != current_group).then(|| {
+                    current_group = option.group.clone();
+                    option.group.clone().map(|group| view! {
+                        <li role="presentation" class="px-2 pb-1 pt-2 text-xs font-semibold uppercase tracking-wide text-muted-foreground">
+                            {group}
+                        </li>
+                    })
+                });
+
+                let option_value = option.value.clone();
+                let selected_value = option.value.clone();
+                let is_selected = move || value.with(|v| v.contains(&selected_value));
+                let option_classes = move || {
+                    cn(&[
+                        "relative flex w-full cursor-default select-none items-start gap-2 rounded-sm py-1.5 pl-8 pr-2 text-sm outline-none",
+                        if highlighted.get() == Some(index) { "bg-accent text-accent-foreground" } else { "" },
+                        if option.disabled { "pointer-events-none opacity-50" } else { "cursor-pointer" },
+                    ])
+                };
+                let check_selected = is_selected.clone();
+
+                view! {
+                    {heading}
+                    <li
+                        role="option"
+                        id=format!("{}-option-{}", option_list_id, index)
+                        aria-selected=move || is_selected().to_string()
+                        aria-disabled=option.disabled.to_string()
+                        class=option_classes
+                        on:mouseenter=move |_| highlighted.set(Some(index))
+                        on:mousedown=|ev| ev.prevent_default()
+                        on:click=move |_| {
+                            if !option.disabled {
+                                toggle_value(option_value.clone());
+                            }
+                        }
+                    >
+                        <span class="absolute left-2 flex h-3.5 w-3.5 items-center justify-center" aria-hidden="true">
+                            {move || check_selected().then_some("✓")}
+                        </span>
+                        <span class="flex flex-col">
+                            <span>{option.label.clone()}</span>
+                            {option.description.clone().map(|description| view! {
+                                <span class="text-xs text-muted-foreground">{description}</span>
+                            })}
+                        </span>
+                    </li>
+                }
+            })
+            .collect_view()
+            .into_any()
+    };
+
+    let active_descendant_id = listbox_id.clone();
+    let active_descendant = move || {
+        highlighted
+            .get()
+            .filter(|_| open.get())
+            .map(|index| format!("{}-option-{}", active_descendant_id, index))
+    };
+
+    let trigger_classes = cn(&[
+        "flex h-10 w-full items-center justify-between rounded-md border border-input bg-background px-3 py-2 text-sm ring-offset-background focus:outline-none focus:ring-2 focus:ring-ring focus:ring-offset-2 disabled:cursor-not-allowed disabled:opacity-50",
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <div class="relative w-full" on:keydown=on_keydown>
+            <button
+                type="button"
+                id=id
+                class=trigger_classes
+                role="combobox"
+                aria-haspopup="listbox"
+                aria-expanded=move || open.get().to_string()
+                aria-controls=listbox_id.clone()
+                aria-activedescendant=active_descendant.clone()
+                aria-label=label
+                disabled=is_disabled
+                on:click=move |_| if open.get_untracked() { close() } else { open.set(true) }
+            >
+                <span class=move || if summary().is_some() { "truncate" } else { "truncate text-muted-foreground" }>
+                    {move || summary().unwrap_or_else(|| placeholder.clone())}
+                </span>
+                <span class="ml-2 opacity-50" aria-hidden="true">"▾"</span>
+            </button>
+            <Show when=move || open.get()>
+                <div class="fixed inset-0 z-40" aria-hidden="true" on:click=move |_| close()></div>
+                <div class="absolute z-50 mt-1 w-full min-w-[8rem] overflow-hidden rounded-md border bg-popover text-popover-foreground shadow-md">
+                    {searchable.then(|| view! {
+                        <div class="border-b p-1">
+                            <input
+                                type="text"
+                                class="h-8 w-full rounded-sm bg-transparent px-2 text-sm outline-none placeholder:text-muted-foreground"
+                                placeholder="Search…"
+                                aria-label="Filter options"
+                                aria-controls=listbox_id.clone()
+                                aria-activedescendant=active_descendant.clone()
+                                autofocus=true
+                                prop:value=move || query.get()
+                                on:input=move |ev| {
+                                    query.set(event_target_value(&ev));
+                                    highlighted.set(None);
+                                }
+                            />
+                        </div>
+                    })}
+                    <ul
+                        id=listbox_id.clone()
+                        role="listbox"
+                        aria-multiselectable=multiple.to_string()
+                        class="max-h-60 overflow-y-auto p-1"
+                    >
+                        {option_list.clone()}
+                    </ul>
+                </div>
+            </Show>
+        </div>
+    }
+}
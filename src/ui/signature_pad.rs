@@ -0,0 +1,292 @@
+use leptos::prelude::*;
+use base64::{Engine as _, engine::general_purpose};
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, PointerEvent};
+use crate::compliance::gdpr::{ConsentRecord, DataProcessingPurpose, LegalBasis};
+use crate::ui::cn;
+
+/// One continuous pen stroke, in canvas pixels
+pub type Stroke = Vec<(f64, f64)>;
+
+const INK: &str = "#111827";
+const LINE_WIDTH: f64 = 2.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureFormat {
+    Png,
+    /// Vector strokes; smaller and scales cleanly when printed
+    Svg,
+}
+
+/// Standalone SVG document for the given strokes
+pub fn signature_svg(strokes: &[Stroke], width: u32, height: u32) -> String {
+    let paths: String = strokes
+        .iter()
+        .filter_map(|stroke| {
+            let (first, rest) = stroke.split_first()?;
+            let mut d = format!("M{:.1} {:.1}", first.0, first.1);
+            if rest.is_empty() {
+                // A single tap still leaves a dot thanks to the round line cap
+                d.push_str(" l0.1 0");
+            }
+            for (x, y) in rest {
+                d.push_str(&format!(" L{:.1} {:.1}", x, y));
+            }
+            Some(format!(r#"<path d="{}"/>"#, d))
+        })
+        .collect();
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {h}" width="{w}" height="{h}"><g fill="none" stroke="{ink}" stroke-width="{lw}" stroke-linecap="round" stroke-linejoin="round">{paths}</g></svg>"#,
+        w = width,
+        h = height,
+        ink = INK,
+        lw = LINE_WIDTH,
+        paths = paths,
+    )
+}
+
+pub fn signature_svg_data_url(strokes: &[Stroke], width: u32, height: u32) -> String {
+    format!(
+        "data:image/svg+xml;base64,{}",
+        general_purpose::STANDARD.encode(signature_svg(strokes, width, height))
+    )
+}
+
+fn context_2d(canvas: &HtmlCanvasElement) -> Option<CanvasRenderingContext2d> {
+    canvas.get_context("2d").ok()??.dyn_into().ok()
+}
+
+fn redraw(canvas: &HtmlCanvasElement, strokes: &[Stroke]) {
+    let Some(context) = context_2d(canvas) else { return };
+    context.clear_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
+    context.set_stroke_style_str(INK);
+    context.set_line_width(LINE_WIDTH);
+    context.set_line_cap("round");
+    context.set_line_join("round");
+    for stroke in strokes {
+        let Some((first, rest)) = stroke.split_first() else { continue };
+        context.begin_path();
+        context.move_to(first.0, first.1);
+        if rest.is_empty() {
+            context.line_to(first.0 + 0.1, first.1);
+        }
+        for (x, y) in rest {
+            context.line_to(*x, *y);
+        }
+        context.stroke();
+    }
+}
+
+/// Pointer position in canvas pixels, which differ from CSS pixels when the canvas is scaled
+fn canvas_point(canvas: &HtmlCanvasElement, ev: &PointerEvent) -> (f64, f64) {
+    let scale_x = canvas.width() as f64 / canvas.client_width().max(1) as f64;
+    let scale_y = canvas.height() as f64 / canvas.client_height().max(1) as f64;
+    (ev.offset_x() as f64 * scale_x, ev.offset_y() as f64 * scale_y)
+}
+
+/// Canvas for handwritten signatures with mouse, touch or stylus.
+///
+/// `value` holds the exported data URL after each stroke, undo or clear, and
+/// is `None` while the pad is empty.
+#[component]
+pub fn SignaturePad(
+    value: RwSignal<Option<String>>,
+    /// Export format for `value` (default: PNG)
+    #[prop(optional)] format: Option<SignatureFormat>,
+    /// Canvas size in pixels (default: 500×200)
+    #[prop(optional)] width: Option<u32>,
+    #[prop(optional)] height: Option<u32>,
+    #[prop(optional, into)] label: Option<String>,
+    #[prop(optional)] disabled: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let format = format.unwrap_or(SignatureFormat::Png);
+    let width = width.unwrap_or(500);
+    let height = height.unwrap_or(200);
+    let label = label.unwrap_or_else(|| "Signature".to_string());
+    let disabled = disabled.unwrap_or(false);
+
+    let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
+    let strokes = RwSignal::new(Vec::<Stroke>::new());
+    let drawing = RwSignal::new(false);
+
+    Effect::new(move |_| {
+        let strokes = strokes.get();
+        if let Some(canvas) = canvas_ref.get() {
+            redraw(&canvas, &strokes);
+        }
+    });
+
+    let export = move || {
+        let data_url = strokes.with_untracked(|strokes| {
+            if strokes.is_empty() {
+                return None;
+            }
+            match format {
+                SignatureFormat::Png => {
+                    // Redraw now rather than waiting for the effect, so undo exports the updated canvas
+                    let canvas = canvas_ref.get_untracked()?;
+                    redraw(&canvas, strokes);
+                    canvas.to_data_url().ok()
+                }
+                SignatureFormat::Svg => Some(signature_svg_data_url(strokes, width, height)),
+            }
+        });
+        value.set(data_url);
+    };
+
+    let on_pointerdown = move |ev: PointerEvent| {
+        if disabled || ev.button() != 0 {
+            return;
+        }
+        let Some(canvas) = canvas_ref.get_untracked() else { return };
+        ev.prevent_default();
+        let _ = canvas.set_pointer_capture(ev.pointer_id());
+        let point = canvas_point(&canvas, &ev);
+        strokes.update(|strokes| strokes.push(vec![point]));
+        drawing.set(true);
+    };
+    let on_pointermove = move |ev: PointerEvent| {
+        if !drawing.get_untracked() {
+            return;
+        }
+        let Some(canvas) = canvas_ref.get_untracked() else { return };
+        let point = canvas_point(&canvas, &ev);
+        strokes.update(|strokes| {
+            if let Some(stroke) = strokes.last_mut() {
+                stroke.push(point);
+            }
+        });
+    };
+    let finish_stroke = move |_: PointerEvent| {
+        if drawing.get_untracked() {
+            drawing.set(false);
+            export();
+        }
+    };
+
+    let undo = move |_| {
+        strokes.update(|strokes| {
+            strokes.pop();
+        });
+        export();
+    };
+    let clear = move |_| {
+        strokes.set(Vec::new());
+        export();
+    };
+
+    let is_empty = move || strokes.with(|strokes| strokes.is_empty());
+    let canvas_label = label.clone();
+    let action_classes = "rounded-md px-3 py-1.5 text-sm font-medium text-muted-foreground transition-colors hover:bg-accent hover:text-accent-foreground disabled:pointer-events-none disabled:opacity-50";
+    let container_classes = cn(&["w-full space-y-2", class.unwrap_or("")]);
+
+    view! {
+        <div class=container_classes>
+            <div class="flex items-center justify-between">
+                <span class="text-sm font-medium">{label}</span>
+                <div class="flex gap-1">
+                    <button type="button" class=action_classes disabled=move || disabled || is_empty() on:click=undo>
+                        "Undo"
+                    </button>
+                    <button type="button" class=action_classes disabled=move || disabled || is_empty() on:click=clear>
+                        "Clear"
+                    </button>
+                </div>
+            </div>
+            <div class=cn(&["relative rounded-md border-2 border-dashed bg-white", if disabled { "opacity-50" } else { "" }])>
+                <canvas
+                    node_ref=canvas_ref
+                    width=width
+                    height=height
+                    role="img"
+                    aria-label=move || format!("{}: {}", canvas_label, if is_empty() { "empty" } else { "signed" })
+                    class=cn(&["block h-auto w-full touch-none", if disabled { "cursor-not-allowed" } else { "cursor-crosshair" }])
+                    on:pointerdown=on_pointerdown
+                    on:pointermove=on_pointermove
+                    on:pointerup=finish_stroke
+                    on:pointercancel=finish_stroke
+                ></canvas>
+                <Show when=is_empty>
+                    <span class="pointer-events-none absolute inset-x-0 bottom-6 mx-8 border-b text-center text-xs text-muted-foreground">
+                        "Sign here"
+                    </span>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+/// Consent text, agreement checkbox and signature pad in one form.
+///
+/// Submitting builds a [`ConsentRecord`] carrying the signature, for
+/// in-person consent and telemedicine agreements.
+#[component]
+pub fn ConsentCapture(
+    user_id: uuid::Uuid,
+    purpose: DataProcessingPurpose,
+    legal_basis: LegalBasis,
+    #[prop(into)] consent_text: String,
+    #[prop(into)] consent_version: String,
+    on_submit: Callback<ConsentRecord>,
+    #[prop(optional)] format: Option<SignatureFormat>,
+    #[prop(optional, into)] title: Option<String>,
+    #[prop(optional, into)] submit_label: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let title = title.unwrap_or_else(|| "Consent".to_string());
+    let submit_label = submit_label.unwrap_or_else(|| "Sign and submit".to_string());
+    let agreed = RwSignal::new(false);
+    let signature = RwSignal::new(None::<String>);
+    let checkbox_id = format!("consent-agree-{}", uuid::Uuid::new_v4().simple());
+
+    let ready = move || agreed.get() && signature.with(Option::is_some);
+    let text = consent_text.clone();
+    let submit = move |_| {
+        let Some(data_url) = signature.get_untracked() else { return };
+        if !agreed.get_untracked() {
+            return;
+        }
+        let record = ConsentRecord::new(
+            user_id,
+            purpose.clone(),
+            legal_basis.clone(),
+            text.clone(),
+            consent_version.clone(),
+        )
+        .with_signature(data_url);
+        on_submit.run(record);
+    };
+
+    let container_classes = cn(&["w-full space-y-4 rounded-lg border bg-card p-6 text-card-foreground shadow-sm", class.unwrap_or("")]);
+
+    view! {
+        <section class=container_classes aria-label=title.clone()>
+            <h3 class="text-lg font-semibold">{title}</h3>
+            <div class="max-h-64 overflow-y-auto whitespace-pre-line rounded-md border bg-muted/30 p-4 text-sm" tabindex="0">
+                {consent_text}
+            </div>
+            <div class="flex items-start gap-2">
+                <input
+                    type="checkbox"
+                    id=checkbox_id.clone()
+                    class="mt-0.5 h-4 w-4 rounded border-input accent-primary"
+                    prop:checked=move || agreed.get()
+                    on:change=move |ev| agreed.set(event_target_checked(&ev))
+                />
+                <label for=checkbox_id class="text-sm">"I have read and agree to the terms above"</label>
+            </div>
+            <SignaturePad value=signature format=format.unwrap_or(SignatureFormat::Png) />
+            <div class="flex justify-end">
+                <button
+                    type="button"
+                    class="inline-flex h-10 items-center justify-center rounded-md bg-primary px-4 py-2 text-sm font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:pointer-events-none disabled:opacity-50"
+                    disabled=move || !ready()
+                    on:click=submit
+                >
+                    {submit_label}
+                </button>
+            </div>
+        </section>
+    }
+}
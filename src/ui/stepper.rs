@@ -0,0 +1,323 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use crate::healthcare_service_engine::{WorkflowStep, WorkflowStepType};
+use crate::ui::{cn, Button, ButtonVariant};
+
+type StepContent = Arc<dyn Fn() -> AnyView + Send + Sync>;
+type StepValidator = Arc<dyn Fn() -> Result<(), String> + Send + Sync>;
+
+/// One step of a [`Stepper`]
+#[derive(Clone)]
+pub struct StepDefinition {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// Optional steps can be skipped without passing validation
+    pub optional: bool,
+    pub content: StepContent,
+    /// Runs before advancing; an `Err` message blocks navigation and is shown to the user
+    pub validate: Option<StepValidator>,
+}
+
+impl std::fmt::Debug for StepDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StepDefinition")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .field("optional", &self.optional)
+            .finish()
+    }
+}
+
+impl StepDefinition {
+    pub fn new<V: IntoView + 'static>(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        content: impl Fn() -> V + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            description: None,
+            optional: false,
+            content: Arc::new(move || content().into_any()),
+            validate: None,
+        }
+    }
+
+    /// Step backed by a workflow definition step, keeping its id and name
+    pub fn from_workflow_step<V: IntoView + 'static>(
+        step: &WorkflowStep,
+        content: impl Fn() -> V + Send + Sync + 'static,
+    ) -> Self {
+        Self::new(step.step_id.clone(), step.name.clone(), content)
+            .with_description(step_type_label(&step.step_type))
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    pub fn with_validation(mut self, validate: impl Fn() -> Result<(), String> + Send + Sync + 'static) -> Self {
+        self.validate = Some(Arc::new(validate));
+        self
+    }
+}
+
+fn step_type_label(step_type: &WorkflowStepType) -> &'static str {
+    match step_type {
+        WorkflowStepType::FormSubmission => "Fill in details",
+        WorkflowStepType::DocumentUpload => "Upload documents",
+        WorkflowStepType::OTPVerification => "Verify code",
+        WorkflowStepType::BackgroundCheck => "Background check",
+        WorkflowStepType::LicenseVerification => "License verification",
+        WorkflowStepType::PaymentProcessing => "Payment",
+        WorkflowStepType::NotificationSend => "Notification",
+        WorkflowStepType::ManualReview => "Review",
+        WorkflowStepType::SystemValidation => "Validation",
+    }
+}
+
+/// Progress saved between visits so long flows can be resumed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StepperProgress {
+    pub current: Option<String>,
+    pub completed: Vec<String>,
+}
+
+impl StepperProgress {
+    fn load(key: &str) -> Option<Self> {
+        window()
+            .local_storage()
+            .ok()
+            .flatten()?
+            .get_item(key)
+            .ok()
+            .flatten()
+            .and_then(|data| serde_json::from_str(&data).ok())
+    }
+
+    fn save(&self, key: &str) {
+        if let (Ok(Some(storage)), Ok(data)) = (window().local_storage(), serde_json::to_string(self)) {
+            let _ = storage.set_item(key, &data);
+        }
+    }
+
+    fn clear(key: &str) {
+        if let Ok(Some(storage)) = window().local_storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+}
+
+/// Multi-step wizard for intake, onboarding and triage flows.
+///
+/// Steps must be completed in order; completed steps can be revisited from the
+/// step list. With `persist_key` set, progress survives page reloads until the
+/// flow finishes.
+#[component]
+pub fn Stepper(
+    steps: Vec<StepDefinition>,
+    on_complete: Callback<()>,
+    #[prop(optional)] on_step_change: Option<Callback<String>>,
+    /// localStorage key for resuming progress
+    #[prop(optional, into)] persist_key: Option<String>,
+    #[prop(optional, into)] finish_label: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let steps = Arc::new(steps);
+    let finish_label = finish_label.unwrap_or_else(|| "Finish".to_string());
+    let total = steps.len();
+
+    let restored = persist_key
+        .as_deref()
+        .and_then(StepperProgress::load)
+        .unwrap_or_default();
+    let restored_index = restored
+        .current
+        .as_ref()
+        .and_then(|id| steps.iter().position(|s| &s.id == id))
+        .unwrap_or(0);
+
+    let current = RwSignal::new(restored_index);
+    let completed = RwSignal::new(restored.completed);
+    let error = RwSignal::new(None::<String>);
+
+    let save_steps = steps.clone();
+    let save_key = persist_key.clone();
+    Effect::new(move |_| {
+        let progress = StepperProgress {
+            current: save_steps.get(current.get()).map(|s| s.id.clone()),
+            completed: completed.get(),
+        };
+        if let Some(key) = &save_key {
+            progress.save(key);
+        }
+    });
+
+    let go_to = {
+        let steps = steps.clone();
+        move |index: usize| {
+            error.set(None);
+            current.set(index);
+            if let (Some(callback), Some(step)) = (on_step_change, steps.get(index)) {
+                callback.run(step.id.clone());
+            }
+        }
+    };
+
+    let advance = {
+        let steps = steps.clone();
+        let go_to = go_to.clone();
+        move |skip: bool| {
+            let index = current.get_untracked();
+            let Some(step) = steps.get(index) else {
+                return;
+            };
+            if !skip {
+                if let Some(validate) = &step.validate {
+                    if let Err(message) = validate() {
+                        error.set(Some(message));
+                        return;
+                    }
+                }
+                completed.update(|done| {
+                    if !done.contains(&step.id) {
+                        done.push(step.id.clone());
+                    }
+                });
+            }
+
+            if index + 1 < total {
+                go_to(index + 1);
+            } else {
+                if let Some(key) = &persist_key {
+                    StepperProgress::clear(key);
+                }
+                on_complete.run(());
+            }
+        }
+    };
+
+    let step_list = steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| {
+            let step_id = step.id.clone();
+            let done_id = step.id.clone();
+            let is_done = move || completed.with(|done| done.contains(&done_id));
+            let reachable = {
+                let is_done = is_done.clone();
+                move || index <= current.get() || is_done()
+            };
+            let go_to = go_to.clone();
+            let indicator_done = is_done.clone();
+            let indicator_classes = {
+                let is_done = is_done.clone();
+                move || {
+                    cn(&[
+                        "flex h-8 w-8 shrink-0 items-center justify-center rounded-full border-2 text-sm font-semibold",
+                        if current.get() == index {
+                            "border-primary bg-primary text-primary-foreground"
+                        } else if is_done() {
+                            "border-primary text-primary"
+                        } else {
+                            "border-muted-foreground/40 text-muted-foreground"
+                        },
+                    ])
+                }
+            };
+            let can_visit = reachable.clone();
+
+            view! {
+                <li class="flex flex-1 items-center gap-2" data-step=step_id>
+                    <button
+                        type="button"
+                        class="flex items-center gap-2 text-left disabled:cursor-not-allowed"
+                        aria-current=move || (current.get() == index).then_some("step")
+                        disabled=move || !reachable()
+                        on:click=move |_| if can_visit() { go_to(index) }
+                    >
+                        <span class=indicator_classes>
+                            {move || if indicator_done() && current.get() != index { "✓".to_string() } else { (index + 1).to_string() }}
+                        </span>
+                        <span class="hidden flex-col sm:flex">
+                            <span class="text-sm font-medium">{step.title.clone()}</span>
+                            {step.description.clone().map(|description| view! {
+                                <span class="text-xs text-muted-foreground">{description}</span>
+                            })}
+                            {step.optional.then(|| view! { <span class="text-xs italic text-muted-foreground">"Optional"</span> })}
+                        </span>
+                    </button>
+                    {(index + 1 < total).then(|| view! { <span class="h-px flex-1 bg-border" aria-hidden="true"></span> })}
+                </li>
+            }
+        })
+        .collect_view();
+
+    let content_steps = steps.clone();
+    let content = move || {
+        content_steps
+            .get(current.get())
+            .map(|step| (step.content)())
+    };
+
+    let optional_steps = steps.clone();
+    let is_optional = move || optional_steps.get(current.get()).map(|s| s.optional).unwrap_or(false);
+    let back = go_to.clone();
+    let next = advance.clone();
+    let skip = advance;
+    let next_label = move || {
+        if current.get() + 1 >= total { finish_label.clone() } else { "Next".to_string() }
+    };
+
+    let container_classes = cn(&["w-full space-y-6", class.unwrap_or("")]);
+
+    view! {
+        <div class=container_classes>
+            <nav aria-label="Progress">
+                <ol class="flex items-center gap-2">{step_list}</ol>
+            </nav>
+            <p class="sr-only" aria-live="polite">
+                {move || format!("Step {} of {}", current.get() + 1, total)}
+            </p>
+            <section>{content}</section>
+            {move || error.get().map(|message| view! {
+                <p class="text-sm font-medium text-destructive" role="alert">{message}</p>
+            })}
+            <div class="flex items-center justify-between gap-2">
+                <Show when=move || current.get() > 0 fallback=|| view! { <span></span> }>
+                    <Button
+                        variant=ButtonVariant::Outline
+                        on_click=Callback::new({
+                            let back = back.clone();
+                            move |_| back(current.get_untracked().saturating_sub(1))
+                        })
+                    >
+                        "Back"
+                    </Button>
+                </Show>
+                <div class="flex items-center gap-2">
+                    <Show when=is_optional.clone()>
+                        <Button variant=ButtonVariant::Ghost on_click=Callback::new({
+                            let skip = skip.clone();
+                            move |_| skip(true)
+                        })>
+                            "Skip"
+                        </Button>
+                    </Show>
+                    <Button on_click=Callback::new(move |_| next(false))>
+                        {next_label.clone()}
+                    </Button>
+                </div>
+            </div>
+        </div>
+    }
+}
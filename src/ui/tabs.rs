@@ -0,0 +1,248 @@
+use leptos::prelude::*;
+use std::sync::Arc;
+use wasm_bindgen::JsCast;
+use crate::ui::{cn, NotificationBadge, Priority};
+
+type PanelRenderer = Arc<dyn Fn() -> AnyView + Send + Sync>;
+
+/// One tab and its lazily rendered panel
+#[derive(Clone)]
+pub struct TabItem {
+    pub id: String,
+    pub label: String,
+    /// Count shown next to the label, e.g. unread documents
+    pub badge: Option<Signal<Option<u32>>>,
+    pub disabled: bool,
+    pub content: PanelRenderer,
+}
+
+impl std::fmt::Debug for TabItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TabItem")
+            .field("id", &self.id)
+            .field("label", &self.label)
+            .field("disabled", &self.disabled)
+            .finish()
+    }
+}
+
+impl TabItem {
+    pub fn new<V: IntoView + 'static>(
+        id: impl Into<String>,
+        label: impl Into<String>,
+        content: impl Fn() -> V + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            badge: None,
+            disabled: false,
+            content: Arc::new(move || content().into_any()),
+        }
+    }
+
+    pub fn with_badge(mut self, badge: impl Into<Signal<Option<u32>>>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.disabled = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TabsVariant {
+    Default,
+    Underline,
+}
+
+/// Next enabled tab index when moving by `step` (wrapping), used for arrow keys
+fn step_enabled(tabs: &[TabItem], from: usize, step: isize) -> Option<usize> {
+    let len = tabs.len() as isize;
+    (1..=len)
+        .map(|offset| (from as isize + step * offset).rem_euclid(len) as usize)
+        .find(|&index| !tabs[index].disabled)
+}
+
+/// Accessible tab set following the WAI-ARIA tabs pattern.
+///
+/// Pass `selected` to control the active tab from outside; otherwise the first
+/// enabled tab (or `default_tab`) is active. Panels are only rendered once they
+/// have been opened, and stay mounted afterwards unless `unmount_inactive` is set.
+#[component]
+pub fn Tabs(
+    tabs: Vec<TabItem>,
+    #[prop(optional, into)] selected: Option<RwSignal<String>>,
+    #[prop(optional, into)] default_tab: Option<String>,
+    #[prop(optional)] on_change: Option<Callback<String>>,
+    #[prop(optional)] variant: Option<TabsVariant>,
+    /// Drop a panel's DOM when switching away instead of hiding it
+    #[prop(optional)] unmount_inactive: Option<bool>,
+    /// Accessible name for the tab list
+    #[prop(optional, into)] label: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let variant = variant.unwrap_or(TabsVariant::Default);
+    let unmount_inactive = unmount_inactive.unwrap_or(false);
+    let tabs = Arc::new(tabs);
+    let base_id = format!("tabs-{}", uuid::Uuid::new_v4().simple());
+
+    let initial = default_tab
+        .filter(|id| tabs.iter().any(|t| &t.id == id && !t.disabled))
+        .or_else(|| tabs.iter().find(|t| !t.disabled).map(|t| t.id.clone()))
+        .unwrap_or_default();
+    let active = selected.unwrap_or_else(|| RwSignal::new(initial));
+    let visited = RwSignal::new(vec![active.get_untracked()]);
+
+    let select = move |id: String| {
+        if active.get_untracked() == id {
+            return;
+        }
+        visited.update(|visited| {
+            if !visited.contains(&id) {
+                visited.push(id.clone());
+            }
+        });
+        active.set(id.clone());
+        if let Some(callback) = on_change {
+            callback.run(id);
+        }
+    };
+
+    // Track tabs selected from outside so their panels render too
+    Effect::new(move |_| {
+        let id = active.get();
+        visited.update(|visited| {
+            if !visited.contains(&id) {
+                visited.push(id);
+            }
+        });
+    });
+
+    let list_classes = match variant {
+        TabsVariant::Default => "inline-flex h-10 items-center justify-center rounded-md bg-muted p-1 text-muted-foreground",
+        TabsVariant::Underline => "flex items-center gap-4 border-b",
+    };
+
+    let key_tabs = tabs.clone();
+    let key_base_id = base_id.clone();
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        let Some(current) = key_tabs.iter().position(|t| t.id == active.get_untracked()) else {
+            return;
+        };
+        let target = match ev.key().as_str() {
+            "ArrowRight" => step_enabled(&key_tabs, current, 1),
+            "ArrowLeft" => step_enabled(&key_tabs, current, -1),
+            "Home" => key_tabs.iter().position(|t| !t.disabled),
+            "End" => key_tabs.iter().rposition(|t| !t.disabled),
+            _ => return,
+        };
+        ev.prevent_default();
+
+        if let Some(index) = target {
+            let id = key_tabs[index].id.clone();
+            select(id.clone());
+            if let Some(tab) = document()
+                .get_element_by_id(&format!("{}-tab-{}", key_base_id, id))
+                .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+            {
+                let _ = tab.focus();
+            }
+        }
+    };
+
+    let triggers = tabs
+        .iter()
+        .map(|tab| {
+            let id = tab.id.clone();
+            let click_id = tab.id.clone();
+            let is_active = {
+                let id = id.clone();
+                move || active.get() == id
+            };
+            let trigger_classes = {
+                let is_active = is_active.clone();
+                move || {
+                    let state = match (variant, is_active()) {
+                        (TabsVariant::Default, true) => "bg-background text-foreground shadow-sm",
+                        (TabsVariant::Default, false) => "hover:text-foreground",
+                        (TabsVariant::Underline, true) => "border-b-2 border-primary text-foreground",
+                        (TabsVariant::Underline, false) => "border-b-2 border-transparent text-muted-foreground hover:text-foreground",
+                    };
+                    cn(&[
+                        "inline-flex items-center justify-center gap-2 whitespace-nowrap px-3 py-1.5 text-sm font-medium ring-offset-background transition-all focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2 disabled:pointer-events-none disabled:opacity-50",
+                        if variant == TabsVariant::Default { "rounded-sm" } else { "pb-2" },
+                        state,
+                    ])
+                }
+            };
+            let selected_attr = is_active.clone();
+            let tabindex = is_active.clone();
+            let badge = tab.badge.map(|badge| view! {
+                <NotificationBadge count=Signal::derive(move || badge.get().unwrap_or(0)) priority=Priority::Normal />
+            });
+
+            view! {
+                <button
+                    type="button"
+                    role="tab"
+                    id=format!("{}-tab-{}", base_id, id)
+                    aria-controls=format!("{}-panel-{}", base_id, id)
+                    aria-selected=move || selected_attr().to_string()
+                    tabindex=move || if tabindex() { "0" } else { "-1" }
+                    disabled=tab.disabled
+                    class=trigger_classes
+                    on:click=move |_| select(click_id.clone())
+                >
+                    {tab.label.clone()}
+                    {badge}
+                </button>
+            }
+        })
+        .collect_view();
+
+    let panels = tabs
+        .iter()
+        .map(|tab| {
+            let id = tab.id.clone();
+            let content = tab.content.clone();
+            let show_id = id.clone();
+            let hidden_id = id.clone();
+            let should_render = move || {
+                if unmount_inactive {
+                    active.get() == show_id
+                } else {
+                    visited.with(|visited| visited.contains(&show_id))
+                }
+            };
+
+            view! {
+                <div
+                    role="tabpanel"
+                    id=format!("{}-panel-{}", base_id, id)
+                    aria-labelledby=format!("{}-tab-{}", base_id, id)
+                    tabindex="0"
+                    hidden=move || active.get() != hidden_id
+                    class="mt-2 ring-offset-background focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring focus-visible:ring-offset-2"
+                >
+                    <Show when=should_render>
+                        {content()}
+                    </Show>
+                </div>
+            }
+        })
+        .collect_view();
+
+    let container_classes = cn(&["w-full", class.unwrap_or("")]);
+
+    view! {
+        <div class=container_classes>
+            <div role="tablist" aria-label=label class=list_classes on:keydown=on_keydown>
+                {triggers}
+            </div>
+            {panels}
+        </div>
+    }
+}
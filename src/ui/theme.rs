@@ -0,0 +1,306 @@
+use leptos::prelude::*;
+use std::collections::BTreeMap;
+use crate::healthcare_service_engine::BrandCustomizationConfig;
+use crate::ui::{cn, ColorPalette, DesignSystem};
+use crate::utils::color::to_hsl_components;
+
+/// localStorage key used to remember the user's theme choice
+pub const THEME_STORAGE_KEY: &str = "mydr24-theme";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    /// Follow the operating system's `prefers-color-scheme`
+    System,
+}
+
+impl ThemeMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeMode::Light => "light",
+            ThemeMode::Dark => "dark",
+            ThemeMode::System => "system",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "light" => Some(ThemeMode::Light),
+            "dark" => Some(ThemeMode::Dark),
+            "system" => Some(ThemeMode::System),
+            _ => None,
+        }
+    }
+}
+
+impl ColorPalette {
+    /// Dark counterpart of the default palette; status and chart colours are shared
+    pub fn dark() -> Self {
+        let light = DesignSystem::default().colors;
+        Self {
+            primary: "hsl(210 40% 98%)",
+            primary_foreground: "hsl(222.2 84% 4.9%)",
+            secondary: "hsl(217.2 32.6% 17.5%)",
+            secondary_foreground: "hsl(210 40% 98%)",
+            destructive: "hsl(0 62.8% 30.6%)",
+            destructive_foreground: "hsl(210 40% 98%)",
+            muted: "hsl(217.2 32.6% 17.5%)",
+            muted_foreground: "hsl(215 20.2% 65.1%)",
+            accent: "hsl(217.2 32.6% 17.5%)",
+            accent_foreground: "hsl(210 40% 98%)",
+            background: "hsl(222.2 84% 4.9%)",
+            foreground: "hsl(210 40% 98%)",
+            card: "hsl(222.2 84% 4.9%)",
+            card_foreground: "hsl(210 40% 98%)",
+            border: "hsl(217.2 32.6% 17.5%)",
+            input: "hsl(217.2 32.6% 17.5%)",
+            ring: "hsl(212.7 26.8% 83.9%)",
+            ..light
+        }
+    }
+
+    /// CSS custom properties in the `--name: h s% l%` form `styles.css` expects
+    pub fn css_variables(&self) -> Vec<(&'static str, String)> {
+        [
+            ("--primary", self.primary),
+            ("--primary-foreground", self.primary_foreground),
+            ("--secondary", self.secondary),
+            ("--secondary-foreground", self.secondary_foreground),
+            ("--destructive", self.destructive),
+            ("--destructive-foreground", self.destructive_foreground),
+            ("--muted", self.muted),
+            ("--muted-foreground", self.muted_foreground),
+            ("--accent", self.accent),
+            ("--accent-foreground", self.accent_foreground),
+            ("--background", self.background),
+            ("--foreground", self.foreground),
+            ("--card", self.card),
+            ("--card-foreground", self.card_foreground),
+            ("--popover", self.card),
+            ("--popover-foreground", self.card_foreground),
+            ("--border", self.border),
+            ("--input", self.input),
+            ("--ring", self.ring),
+            ("--chart-1", self.chart_1),
+            ("--chart-2", self.chart_2),
+            ("--chart-3", self.chart_3),
+            ("--chart-4", self.chart_4),
+            ("--chart-5", self.chart_5),
+            ("--emergency", self.emergency),
+            ("--urgent", self.urgent),
+            ("--normal", self.normal),
+            ("--low-priority", self.low_priority),
+            ("--success", self.success),
+            ("--warning", self.warning),
+            ("--info", self.info),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| to_hsl_components(value).map(|hsl| (name, hsl)))
+        .collect()
+    }
+}
+
+impl DesignSystem {
+    pub fn dark() -> Self {
+        Self {
+            colors: ColorPalette::dark(),
+            ..Self::default()
+        }
+    }
+
+    /// Inline style declaring every theme variable, with `overrides` applied last
+    pub fn css_variables(&self, overrides: &BrandOverrides) -> String {
+        let mut declarations: Vec<String> = self
+            .colors
+            .css_variables()
+            .into_iter()
+            .filter(|(name, _)| !overrides.colors.contains_key(*name))
+            .map(|(name, value)| format!("{}: {};", name, value))
+            .collect();
+
+        declarations.extend(overrides.colors.iter().map(|(name, value)| format!("{}: {};", name, value)));
+        declarations.push(format!("--radius: {};", self.borders.radius));
+        declarations.push(format!("--font-sans: {};", self.typography.font_sans));
+        declarations.push(format!("--font-mono: {};", self.typography.font_mono));
+        declarations.join(" ")
+    }
+}
+
+/// Organisation branding applied on top of the light/dark palette
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BrandOverrides {
+    /// CSS variable name (e.g. `--primary`) to bare HSL components
+    pub colors: BTreeMap<String, String>,
+    pub logo_url: Option<String>,
+}
+
+impl BrandOverrides {
+    /// Accepts keys like `primary`, `primary_foreground` or `--chart-1`; colours
+    /// that cannot be parsed are ignored rather than breaking the theme
+    pub fn from_config(config: &BrandCustomizationConfig) -> Self {
+        let colors = config
+            .theme_colors
+            .iter()
+            .filter_map(|(key, value)| {
+                let name = format!("--{}", key.trim().trim_start_matches("--").replace('_', "-"));
+                to_hsl_components(value).map(|hsl| (name, hsl))
+            })
+            .collect();
+
+        Self {
+            colors,
+            logo_url: Some(config.logo_url.clone()).filter(|url| !url.is_empty()),
+        }
+    }
+}
+
+/// Theme state shared through [`ThemeProvider`]
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeContext {
+    mode: RwSignal<ThemeMode>,
+    system_dark: RwSignal<bool>,
+    brand: RwSignal<BrandOverrides>,
+}
+
+impl ThemeContext {
+    pub fn mode(&self) -> ThemeMode {
+        self.mode.get()
+    }
+
+    pub fn set_mode(&self, mode: ThemeMode) {
+        self.mode.set(mode);
+    }
+
+    /// Resolved dark/light state, taking the OS preference into account
+    pub fn is_dark(&self) -> bool {
+        match self.mode.get() {
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+            ThemeMode::System => self.system_dark.get(),
+        }
+    }
+
+    /// Switch between light and dark, leaving `System` behind
+    pub fn toggle(&self) {
+        let dark = self.is_dark();
+        self.mode.set(if dark { ThemeMode::Light } else { ThemeMode::Dark });
+    }
+
+    pub fn brand(&self) -> BrandOverrides {
+        self.brand.get()
+    }
+
+    pub fn set_brand(&self, brand: BrandOverrides) {
+        self.brand.set(brand);
+    }
+
+    pub fn apply_brand_config(&self, config: &BrandCustomizationConfig) {
+        self.brand.set(BrandOverrides::from_config(config));
+    }
+
+    pub fn logo_url(&self) -> Option<String> {
+        self.brand.with(|brand| brand.logo_url.clone())
+    }
+
+    /// Design tokens for the active mode
+    pub fn design_system(&self) -> DesignSystem {
+        if self.is_dark() {
+            DesignSystem::dark()
+        } else {
+            DesignSystem::default()
+        }
+    }
+}
+
+/// Access the theme provided by the nearest [`ThemeProvider`]
+pub fn use_theme() -> ThemeContext {
+    expect_context::<ThemeContext>()
+}
+
+fn system_prefers_dark() -> bool {
+    window()
+        .match_media("(prefers-color-scheme: dark)")
+        .ok()
+        .flatten()
+        .map(|query| query.matches())
+        .unwrap_or(false)
+}
+
+fn stored_mode() -> Option<ThemeMode> {
+    window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(THEME_STORAGE_KEY).ok().flatten())
+        .and_then(|value| ThemeMode::parse(&value))
+}
+
+/// Applies the `DesignSystem` as CSS variables on `<html>`, toggles the
+/// `dark` class and exposes [`use_theme`] to descendants
+#[component]
+pub fn ThemeProvider(
+    /// Mode used when nothing is stored (default: System)
+    #[prop(optional)] default_mode: Option<ThemeMode>,
+    #[prop(optional)] brand: Option<BrandCustomizationConfig>,
+    /// Remember the chosen mode in localStorage (default: true)
+    #[prop(optional)] persist: Option<bool>,
+    children: Children,
+) -> impl IntoView {
+    let persist = persist.unwrap_or(true);
+    let initial_mode = persist
+        .then(stored_mode)
+        .flatten()
+        .or(default_mode)
+        .unwrap_or(ThemeMode::System);
+
+    let context = ThemeContext {
+        mode: RwSignal::new(initial_mode),
+        system_dark: RwSignal::new(system_prefers_dark()),
+        brand: RwSignal::new(brand.as_ref().map(BrandOverrides::from_config).unwrap_or_default()),
+    };
+    provide_context(context);
+
+    Effect::new(move |_| {
+        let dark = context.is_dark();
+        let style = context.brand.with(|brand| context.design_system().css_variables(brand));
+
+        if let Some(root) = document().document_element() {
+            let _ = root.set_attribute("style", &style);
+            let _ = root.set_attribute("data-theme", if dark { "dark" } else { "light" });
+            let classes = root.class_list();
+            let _ = if dark { classes.add_1("dark") } else { classes.remove_1("dark") };
+        }
+
+        if persist {
+            if let Ok(Some(storage)) = window().local_storage() {
+                let _ = storage.set_item(THEME_STORAGE_KEY, context.mode.get().as_str());
+            }
+        }
+    });
+
+    children()
+}
+
+/// Button that flips between light and dark mode
+#[component]
+pub fn ThemeToggle(
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let theme = use_theme();
+
+    view! {
+        <button
+            type="button"
+            class=cn(&[
+                "inline-flex h-10 w-10 items-center justify-center rounded-md hover:bg-accent hover:text-accent-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring",
+                class.unwrap_or(""),
+            ])
+            aria-label=move || if theme.is_dark() { "Switch to light mode" } else { "Switch to dark mode" }
+            aria-pressed=move || theme.is_dark().to_string()
+            on:click=move |_| theme.toggle()
+        >
+            <span aria-hidden="true">{move || if theme.is_dark() { "☀️" } else { "🌙" }}</span>
+        </button>
+    }
+}
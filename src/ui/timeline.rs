@@ -0,0 +1,251 @@
+use leptos::prelude::*;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use std::collections::{BTreeMap, HashSet};
+use crate::i18n::{translate, Locale};
+use crate::ui::{cn, use_i18n, EmptyState, Icon, IconSize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimelineEventKind {
+    Visit,
+    Prescription,
+    LabResult,
+    Emergency,
+    Other,
+}
+
+impl TimelineEventKind {
+    /// Translation key of the kind's label
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            TimelineEventKind::Visit => "timeline.kind.visit",
+            TimelineEventKind::Prescription => "timeline.kind.prescription",
+            TimelineEventKind::LabResult => "timeline.kind.lab_result",
+            TimelineEventKind::Emergency => "timeline.kind.emergency",
+            TimelineEventKind::Other => "timeline.kind.other",
+        }
+    }
+
+    /// Registry name of the default icon
+    pub fn icon(&self) -> &'static str {
+        match self {
+            TimelineEventKind::Visit => "medical-stethoscope",
+            TimelineEventKind::Prescription => "medical-prescription",
+            TimelineEventKind::LabResult => "medical-thermometer",
+            TimelineEventKind::Emergency => "emergency-ambulance",
+            TimelineEventKind::Other => "interface-calendar",
+        }
+    }
+
+    fn marker_classes(&self) -> &'static str {
+        match self {
+            TimelineEventKind::Visit => "bg-blue-100 text-blue-700",
+            TimelineEventKind::Prescription => "bg-purple-100 text-purple-700",
+            TimelineEventKind::LabResult => "bg-amber-100 text-amber-700",
+            TimelineEventKind::Emergency => "bg-red-100 text-red-700",
+            TimelineEventKind::Other => "bg-muted text-muted-foreground",
+        }
+    }
+}
+
+/// One entry in a patient's history, in local time
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEvent {
+    pub id: String,
+    pub kind: TimelineEventKind,
+    pub title: String,
+    pub occurred_at: NaiveDateTime,
+    pub summary: Option<String>,
+    /// Label/value pairs shown when the entry is expanded
+    pub details: Vec<(String, String)>,
+    /// Overrides the kind's icon with another registered icon
+    pub icon: Option<String>,
+}
+
+impl TimelineEvent {
+    pub fn new(
+        id: impl Into<String>,
+        kind: TimelineEventKind,
+        title: impl Into<String>,
+        occurred_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            kind,
+            title: title.into(),
+            occurred_at,
+            summary: None,
+            details: Vec::new(),
+            icon: None,
+        }
+    }
+
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    pub fn with_detail(mut self, label: impl Into<String>, value: impl Into<String>) -> Self {
+        self.details.push((label.into(), value.into()));
+        self
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+}
+
+/// Events grouped by calendar day, days and events within a day in chronological
+/// order, or newest first when `newest_first` is set
+pub fn group_by_day(events: &[TimelineEvent], newest_first: bool) -> Vec<(NaiveDate, Vec<TimelineEvent>)> {
+    let mut days: BTreeMap<NaiveDate, Vec<TimelineEvent>> = BTreeMap::new();
+    for event in events {
+        days.entry(event.occurred_at.date()).or_default().push(event.clone());
+    }
+    let mut grouped: Vec<_> = days.into_iter().collect();
+    for (_, events) in grouped.iter_mut() {
+        events.sort_by_key(|e| e.occurred_at);
+        if newest_first {
+            events.reverse();
+        }
+    }
+    if newest_first {
+        grouped.reverse();
+    }
+    grouped
+}
+
+fn day_heading(locale: Locale, date: NaiveDate, today: NaiveDate) -> String {
+    if date == today {
+        translate(locale, "timeline.today").to_string()
+    } else if date == today - Duration::days(1) {
+        translate(locale, "timeline.yesterday").to_string()
+    } else {
+        date.format("%a, %-d %b %Y").to_string()
+    }
+}
+
+/// Chronological patient history grouped by day. Entries with details expand
+/// in place.
+#[component]
+pub fn Timeline(
+    #[prop(into)] events: Signal<Vec<TimelineEvent>>,
+    /// Show the most recent events first (default: true)
+    #[prop(optional)] newest_first: Option<bool>,
+    /// Called with the event id when an entry is expanded
+    #[prop(optional)] on_expand: Option<Callback<String>>,
+    #[prop(optional, into)] empty_message: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let newest_first = newest_first.unwrap_or(true);
+    let i18n = use_i18n();
+    let expanded = RwSignal::new(HashSet::<String>::new());
+    let timeline_id = uuid::Uuid::new_v4().simple().to_string();
+
+    let toggle = move |id: String| {
+        let opened = expanded.try_update(|open| {
+            if open.remove(&id) {
+                false
+            } else {
+                open.insert(id.clone());
+                true
+            }
+        });
+        if let (Some(true), Some(callback)) = (opened, on_expand) {
+            callback.run(id);
+        }
+    };
+
+    let entry = move |event: TimelineEvent| {
+        let has_details = !event.details.is_empty();
+        let panel_id = format!("timeline-{}-{}", timeline_id, event.id);
+        let event_id = event.id.clone();
+        let is_open = {
+            let id = event.id.clone();
+            move || expanded.with(|open| open.contains(&id))
+        };
+        let icon = event.icon.clone().unwrap_or_else(|| event.kind.icon().to_string());
+        let details = event
+            .details
+            .into_iter()
+            .map(|(label, value)| view! {
+                <div class="flex gap-2">
+                    <dt class="text-muted-foreground">{label}</dt>
+                    <dd class="font-medium">{value}</dd>
+                </div>
+            })
+            .collect_view();
+
+        view! {
+            <li class="relative flex gap-3 pb-4 last:pb-0">
+                <span class="absolute left-4 top-9 -bottom-0 w-px bg-border" aria-hidden="true"></span>
+                <span class=cn(&["relative z-10 flex h-8 w-8 shrink-0 items-center justify-center rounded-full", event.kind.marker_classes()])>
+                    <Icon name=icon size=IconSize::Sm />
+                </span>
+                <div class="min-w-0 flex-1 pt-1">
+                    <div class="flex flex-wrap items-baseline justify-between gap-x-2">
+                        {if has_details {
+                            let is_open = is_open.clone();
+                            view! {
+                                <button
+                                    type="button"
+                                    class="text-left text-sm font-medium hover:underline focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring"
+                                    aria-expanded=move || is_open().to_string()
+                                    aria-controls=panel_id.clone()
+                                    on:click=move |_| toggle(event_id.clone())
+                                >
+                                    {event.title.clone()}
+                                </button>
+                            }
+                            .into_any()
+                        } else {
+                            view! { <span class="text-sm font-medium">{event.title.clone()}</span> }.into_any()
+                        }}
+                        <time class="text-xs text-muted-foreground" datetime=event.occurred_at.format("%Y-%m-%dT%H:%M").to_string()>
+                            {format!("{} · {}", i18n.t(event.kind.label_key()), event.occurred_at.format("%H:%M"))}
+                        </time>
+                    </div>
+                    {event.summary.map(|summary| view! { <p class="text-sm text-muted-foreground">{summary}</p> })}
+                    {has_details.then(|| view! {
+                        <dl
+                            id=panel_id
+                            class=move || cn(&["mt-2 space-y-1 rounded-md border bg-muted/30 p-3 text-sm", if is_open() { "" } else { "hidden" }])
+                        >
+                            {details}
+                        </dl>
+                    })}
+                </div>
+            </li>
+        }
+    };
+
+    let container_classes = cn(&["w-full space-y-6", class.unwrap_or("")]);
+
+    view! {
+        <div class=container_classes>
+            {move || {
+                let groups = events.with(|events| group_by_day(events, newest_first));
+                if groups.is_empty() {
+                    return view! { <EmptyState icon="interface-clock" title=empty_message.clone().unwrap_or_else(|| i18n.t("timeline.empty")) compact=true /> }.into_any();
+                }
+                let today = Local::now().date_naive();
+                let locale = i18n.locale();
+                groups
+                    .into_iter()
+                    .map(|(date, events)| {
+                        let entry = entry.clone();
+                        view! {
+                            <section aria-label=day_heading(locale, date, today)>
+                                <h3 class="sticky top-0 z-20 mb-3 bg-background py-1 text-xs font-semibold uppercase tracking-wide text-muted-foreground">
+                                    {day_heading(locale, date, today)}
+                                </h3>
+                                <ol class="space-y-0">{events.into_iter().map(entry).collect_view()}</ol>
+                            </section>
+                        }
+                    })
+                    .collect_view()
+                    .into_any()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,361 @@
+use leptos::prelude::*;
+use std::collections::VecDeque;
+use crate::events::{AlertSeverity, WebSocketEvent};
+use crate::ui::cn;
+
+/// Toasts shown at once before further ones wait in the queue
+pub const DEFAULT_MAX_VISIBLE_TOASTS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastVariant {
+    Default,
+    Success,
+    Error,
+    Warning,
+    Emergency,
+}
+
+impl ToastVariant {
+    /// Auto-dismiss delay; emergencies stay until dismissed
+    pub fn default_duration_ms(&self) -> Option<u32> {
+        match self {
+            ToastVariant::Default | ToastVariant::Success => Some(4000),
+            ToastVariant::Warning => Some(6000),
+            ToastVariant::Error => Some(8000),
+            ToastVariant::Emergency => None,
+        }
+    }
+
+    pub fn classes(&self) -> &'static str {
+        match self {
+            ToastVariant::Default => "border bg-background text-foreground",
+            ToastVariant::Success => "border-green-500/50 bg-green-50 text-green-900",
+            ToastVariant::Error => "border-destructive/50 bg-red-50 text-red-900",
+            ToastVariant::Warning => "border-yellow-500/50 bg-yellow-50 text-yellow-900",
+            ToastVariant::Emergency => "border-2 border-red-700 bg-red-600 text-white shadow-xl",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ToastVariant::Default => "ℹ️",
+            ToastVariant::Success => "✅",
+            ToastVariant::Error => "❌",
+            ToastVariant::Warning => "⚠️",
+            ToastVariant::Emergency => "🚨",
+        }
+    }
+}
+
+/// Button rendered inside a toast, e.g. "View booking"
+#[derive(Clone)]
+pub struct ToastAction {
+    pub label: String,
+    pub on_action: Callback<()>,
+}
+
+impl std::fmt::Debug for ToastAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToastAction")
+            .field("label", &self.label)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub variant: ToastVariant,
+    pub title: String,
+    pub description: Option<String>,
+    pub duration_ms: Option<u32>,
+    pub action: Option<ToastAction>,
+}
+
+impl Toast {
+    pub fn new(variant: ToastVariant, title: impl Into<String>) -> Self {
+        Self {
+            id: 0,
+            variant,
+            title: title.into(),
+            description: None,
+            duration_ms: variant.default_duration_ms(),
+            action: None,
+        }
+    }
+
+    pub fn success(title: impl Into<String>) -> Self {
+        Self::new(ToastVariant::Success, title)
+    }
+
+    pub fn error(title: impl Into<String>) -> Self {
+        Self::new(ToastVariant::Error, title)
+    }
+
+    pub fn warning(title: impl Into<String>) -> Self {
+        Self::new(ToastVariant::Warning, title)
+    }
+
+    pub fn emergency(title: impl Into<String>) -> Self {
+        Self::new(ToastVariant::Emergency, title)
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_duration(mut self, duration_ms: u32) -> Self {
+        self.duration_ms = Some(duration_ms);
+        self
+    }
+
+    /// Keep the toast on screen until the user dismisses it
+    pub fn sticky(mut self) -> Self {
+        self.duration_ms = None;
+        self
+    }
+
+    pub fn with_action(mut self, label: impl Into<String>, on_action: Callback<()>) -> Self {
+        self.action = Some(ToastAction {
+            label: label.into(),
+            on_action,
+        });
+        self
+    }
+
+    /// Transient notification for a real-time event, if it warrants one
+    pub fn from_event(event: &WebSocketEvent) -> Option<Self> {
+        let toast = match event {
+            WebSocketEvent::PaymentStatusUpdate { status, .. } => match status.to_lowercase().as_str() {
+                "succeeded" | "completed" | "paid" => Toast::success("Payment received"),
+                "failed" | "declined" => Toast::error("Payment failed")
+                    .with_description("Please try again or use another payment method"),
+                "refunded" => Toast::new(ToastVariant::Default, "Payment refunded"),
+                _ => Toast::new(ToastVariant::Default, format!("Payment {}", status)),
+            },
+            WebSocketEvent::BookingStatusUpdate { status, .. } => match status.to_lowercase().as_str() {
+                "confirmed" => Toast::success("Booking confirmed"),
+                "cancelled" | "rejected" => Toast::warning(format!("Booking {}", status.to_lowercase())),
+                _ => Toast::new(ToastVariant::Default, format!("Booking {}", status)),
+            },
+            WebSocketEvent::AppointmentScheduled { .. } => Toast::success("Appointment scheduled"),
+            WebSocketEvent::AppointmentCancelled { reason, .. } => {
+                Toast::warning("Appointment cancelled").with_description(reason.clone())
+            }
+            WebSocketEvent::AppointmentReminder { minutes_until, .. } => Toast::new(
+                ToastVariant::Default,
+                format!("Appointment starts in {} minutes", minutes_until),
+            ),
+            WebSocketEvent::EmergencyAlert { alert } => {
+                let toast = match alert.severity {
+                    AlertSeverity::Critical | AlertSeverity::High => Toast::emergency("Emergency alert"),
+                    AlertSeverity::Medium => Toast::warning("Alert"),
+                    AlertSeverity::Low | AlertSeverity::Info => Toast::new(ToastVariant::Default, "Notice"),
+                };
+                toast.with_description(alert.message.clone())
+            }
+            WebSocketEvent::SystemNotification { notification } => {
+                let toast = Toast::new(ToastVariant::Default, notification.title.clone())
+                    .with_description(notification.message.clone());
+                if notification.is_dismissible { toast } else { toast.sticky() }
+            }
+            WebSocketEvent::SystemMaintenance { message, .. } => {
+                Toast::warning("Scheduled maintenance").with_description(message.clone())
+            }
+            _ => return None,
+        };
+        Some(toast)
+    }
+}
+
+/// Handle returned by [`use_toast`] for showing and dismissing toasts
+#[derive(Debug, Clone, Copy)]
+pub struct ToastContext {
+    visible: RwSignal<Vec<Toast>>,
+    queued: RwSignal<VecDeque<Toast>>,
+    next_id: StoredValue<u64>,
+    max_visible: usize,
+}
+
+impl ToastContext {
+    pub fn new(max_visible: usize) -> Self {
+        Self {
+            visible: RwSignal::new(Vec::new()),
+            queued: RwSignal::new(VecDeque::new()),
+            next_id: StoredValue::new(1),
+            max_visible: max_visible.max(1),
+        }
+    }
+
+    /// Toasts currently on screen
+    pub fn toasts(&self) -> Signal<Vec<Toast>> {
+        self.visible.into()
+    }
+
+    /// Number of toasts waiting for a free slot
+    pub fn queued_count(&self) -> usize {
+        self.queued.with(|queue| queue.len())
+    }
+
+    /// Show a toast, or queue it if the screen is full. Emergencies jump the queue.
+    pub fn show(&self, mut toast: Toast) -> u64 {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        toast.id = id;
+
+        if self.visible.with(|toasts| toasts.len()) < self.max_visible {
+            self.display(toast);
+        } else if toast.variant == ToastVariant::Emergency {
+            self.queued.update(|queue| queue.push_front(toast));
+        } else {
+            self.queued.update(|queue| queue.push_back(toast));
+        }
+        id
+    }
+
+    pub fn success(&self, title: impl Into<String>) -> u64 {
+        self.show(Toast::success(title))
+    }
+
+    pub fn error(&self, title: impl Into<String>) -> u64 {
+        self.show(Toast::error(title))
+    }
+
+    pub fn warning(&self, title: impl Into<String>) -> u64 {
+        self.show(Toast::warning(title))
+    }
+
+    pub fn emergency(&self, title: impl Into<String>) -> u64 {
+        self.show(Toast::emergency(title))
+    }
+
+    /// Surface a real-time event as a toast when it has a mapping
+    pub fn show_event(&self, event: &WebSocketEvent) -> Option<u64> {
+        Toast::from_event(event).map(|toast| self.show(toast))
+    }
+
+    pub fn dismiss(&self, id: u64) {
+        let was_visible = self.visible.with(|toasts| toasts.iter().any(|t| t.id == id));
+        if !was_visible {
+            self.queued.update(|queue| queue.retain(|t| t.id != id));
+            return;
+        }
+
+        self.visible.update(|toasts| toasts.retain(|t| t.id != id));
+        let mut next = None;
+        self.queued.update(|queue| next = queue.pop_front());
+        if let Some(toast) = next {
+            self.display(toast);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.queued.set(VecDeque::new());
+        self.visible.set(Vec::new());
+    }
+
+    fn display(&self, toast: Toast) {
+        let id = toast.id;
+        let duration_ms = toast.duration_ms;
+        self.visible.update(|toasts| toasts.push(toast));
+
+        if let Some(duration_ms) = duration_ms {
+            let context = *self;
+            gloo_timers::callback::Timeout::new(duration_ms, move || context.dismiss(id)).forget();
+        }
+    }
+}
+
+/// Access the toast API provided by the nearest [`ToastProvider`]
+pub fn use_toast() -> ToastContext {
+    expect_context::<ToastContext>()
+}
+
+/// Provides [`use_toast`] to descendants and renders the toast stack
+#[component]
+pub fn ToastProvider(
+    #[prop(optional)] max_visible: Option<usize>,
+    children: Children,
+) -> impl IntoView {
+    let context = ToastContext::new(max_visible.unwrap_or(DEFAULT_MAX_VISIBLE_TOASTS));
+    provide_context(context);
+
+    view! {
+        {children()}
+        <ToastViewport />
+    }
+}
+
+#[component]
+pub fn ToastViewport(
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let context = use_toast();
+
+    let viewport_classes = cn(&[
+        "fixed bottom-0 right-0 z-[100] flex max-h-screen w-full flex-col gap-2 p-4 sm:max-w-sm",
+        class.unwrap_or(""),
+    ]);
+
+    view! {
+        <section class=viewport_classes aria-label="Notifications">
+            <For
+                each=move || context.toasts().get()
+                key=|toast| toast.id
+                children=move |toast| view! { <ToastItem toast=toast context=context /> }
+            />
+        </section>
+    }
+}
+
+#[component]
+fn ToastItem(toast: Toast, context: ToastContext) -> impl IntoView {
+    let id = toast.id;
+    let is_emergency = toast.variant == ToastVariant::Emergency;
+
+    let toast_classes = cn(&[
+        "pointer-events-auto flex w-full items-start gap-3 rounded-md border p-4 shadow-lg transition-all",
+        toast.variant.classes(),
+    ]);
+
+    view! {
+        <div
+            class=toast_classes
+            role=if is_emergency { "alert" } else { "status" }
+            aria-live=if is_emergency { "assertive" } else { "polite" }
+            aria-atomic="true"
+        >
+            <span class="text-lg" aria-hidden="true">{toast.variant.icon()}</span>
+            <div class="flex-1 space-y-1">
+                <p class="text-sm font-semibold">{toast.title}</p>
+                {toast.description.map(|description| view! {
+                    <p class="text-sm opacity-90">{description}</p>
+                })}
+                {toast.action.map(|action| {
+                    let on_action = action.on_action;
+                    view! {
+                        <button
+                            type="button"
+                            class="mt-2 inline-flex h-8 items-center rounded-md border px-3 text-sm font-medium hover:bg-black/5 focus:outline-none focus:ring-2 focus:ring-ring"
+                            on:click=move |_| {
+                                on_action.run(());
+                                context.dismiss(id);
+                            }
+                        >
+                            {action.label}
+                        </button>
+                    }
+                })}
+            </div>
+            <button
+                type="button"
+                class="rounded-sm opacity-70 transition-opacity hover:opacity-100 focus:outline-none focus:ring-2 focus:ring-ring"
+                aria-label="Dismiss notification"
+                on:click=move |_| context.dismiss(id)
+            >
+                <span aria-hidden="true">"✕"</span>
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,209 @@
+use leptos::prelude::*;
+use crate::ui::cn;
+
+/// Lowest and highest values of the 1–10 severity carried by emergency alerts
+pub const MIN_SEVERITY: u8 = 1;
+pub const MAX_SEVERITY: u8 = 10;
+
+/// Emergency Severity Index, the five-level triage scale used by emergency departments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EsiLevel {
+    Resuscitation = 1,
+    Emergent = 2,
+    Urgent = 3,
+    LessUrgent = 4,
+    NonUrgent = 5,
+}
+
+impl EsiLevel {
+    pub const ALL: [EsiLevel; 5] = [
+        EsiLevel::Resuscitation,
+        EsiLevel::Emergent,
+        EsiLevel::Urgent,
+        EsiLevel::LessUrgent,
+        EsiLevel::NonUrgent,
+    ];
+
+    pub fn level(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EsiLevel::Resuscitation => "Resuscitation",
+            EsiLevel::Emergent => "Emergent",
+            EsiLevel::Urgent => "Urgent",
+            EsiLevel::LessUrgent => "Less urgent",
+            EsiLevel::NonUrgent => "Non-urgent",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            EsiLevel::Resuscitation => "Immediate life-saving intervention needed",
+            EsiLevel::Emergent => "High risk, confused or in severe pain",
+            EsiLevel::Urgent => "Stable but needs several resources",
+            EsiLevel::LessUrgent => "Stable, needs one resource",
+            EsiLevel::NonUrgent => "Stable, no resources expected",
+        }
+    }
+
+    /// Equivalent 1–10 alert severity (ESI 1 is the most severe)
+    pub fn to_severity(&self) -> u8 {
+        match self {
+            EsiLevel::Resuscitation => 10,
+            EsiLevel::Emergent => 8,
+            EsiLevel::Urgent => 6,
+            EsiLevel::LessUrgent => 4,
+            EsiLevel::NonUrgent => 2,
+        }
+    }
+
+    /// Closest ESI level for a 1–10 severity
+    pub fn from_severity(severity: u8) -> Self {
+        match severity.clamp(MIN_SEVERITY, MAX_SEVERITY) {
+            10 => EsiLevel::Resuscitation,
+            8..=9 => EsiLevel::Emergent,
+            6..=7 => EsiLevel::Urgent,
+            4..=5 => EsiLevel::LessUrgent,
+            _ => EsiLevel::NonUrgent,
+        }
+    }
+}
+
+/// Colour classes for a 1–10 severity: green, yellow, orange, then red
+pub fn severity_classes(severity: u8) -> &'static str {
+    match severity {
+        0..=3 => "bg-green-500 text-white",
+        4..=6 => "bg-yellow-400 text-yellow-950",
+        7..=8 => "bg-orange-500 text-white",
+        _ => "bg-red-600 text-white",
+    }
+}
+
+pub fn severity_description(severity: u8) -> &'static str {
+    match severity {
+        0..=3 => "Minor – can wait for a routine visit",
+        4..=6 => "Moderate – needs attention today",
+        7..=8 => "Serious – needs prompt care",
+        _ => "Critical – life-threatening",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriageScale {
+    /// 1–10 slider
+    Numeric,
+    /// Five ESI levels
+    Esi,
+}
+
+/// Severity picker for emergency flows. Either scale writes the 1–10
+/// severity expected by emergency alerts into `value`.
+#[component]
+pub fn TriageSelector(
+    value: RwSignal<u8>,
+    #[prop(optional)] scale: Option<TriageScale>,
+    #[prop(optional)] on_change: Option<Callback<u8>>,
+    #[prop(optional, into)] label: Option<String>,
+    #[prop(optional)] disabled: Option<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let scale = scale.unwrap_or(TriageScale::Numeric);
+    let label = label.unwrap_or_else(|| "Severity".to_string());
+    let disabled = disabled.unwrap_or(false);
+    let label_id = format!("triage-{}", uuid::Uuid::new_v4().simple());
+
+    let set = move |severity: u8| {
+        let severity = severity.clamp(MIN_SEVERITY, MAX_SEVERITY);
+        if value.get_untracked() != severity {
+            value.set(severity);
+            if let Some(callback) = on_change {
+                callback.run(severity);
+            }
+        }
+    };
+
+    let control = match scale {
+        TriageScale::Numeric => view! {
+            <div class="space-y-2">
+                <div class="flex items-center gap-3">
+                    <input
+                        type="range"
+                        class="w-full accent-primary"
+                        min=MIN_SEVERITY
+                        max=MAX_SEVERITY
+                        step="1"
+                        aria-labelledby=label_id.clone()
+                        aria-valuetext=move || format!("{} of 10, {}", value.get(), severity_description(value.get()))
+                        disabled=disabled
+                        prop:value=move || value.get().to_string()
+                        on:input=move |ev| {
+                            if let Ok(severity) = event_target_value(&ev).parse() {
+                                set(severity);
+                            }
+                        }
+                    />
+                    <span class=move || cn(&["inline-flex h-9 w-9 shrink-0 items-center justify-center rounded-full text-sm font-bold", severity_classes(value.get())])>
+                        {move || value.get()}
+                    </span>
+                </div>
+                <div class="flex justify-between text-xs text-muted-foreground" aria-hidden="true">
+                    <span>"1 · Minor"</span>
+                    <span>"10 · Critical"</span>
+                </div>
+            </div>
+        }
+        .into_any(),
+        TriageScale::Esi => {
+            let options = EsiLevel::ALL
+                .into_iter()
+                .map(|level| {
+                    let selected = move || EsiLevel::from_severity(value.get()) == level;
+                    let option_classes = move || {
+                        cn(&[
+                            "flex w-full items-start gap-3 rounded-md border p-3 text-left transition-colors focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring disabled:opacity-50",
+                            if selected() { "border-primary bg-accent" } else { "hover:bg-accent/50" },
+                        ])
+                    };
+                    view! {
+                        <button
+                            type="button"
+                            role="radio"
+                            aria-checked=move || selected().to_string()
+                            class=option_classes
+                            disabled=disabled
+                            on:click=move |_| set(level.to_severity())
+                        >
+                            <span class=cn(&["inline-flex h-8 w-8 shrink-0 items-center justify-center rounded-full text-sm font-bold", severity_classes(level.to_severity())])>
+                                {level.level()}
+                            </span>
+                            <span class="flex flex-col">
+                                <span class="text-sm font-medium">{format!("ESI {} – {}", level.level(), level.label())}</span>
+                                <span class="text-xs text-muted-foreground">{level.description()}</span>
+                            </span>
+                        </button>
+                    }
+                })
+                .collect_view();
+            view! {
+                <div role="radiogroup" aria-labelledby=label_id.clone() class="space-y-2">{options}</div>
+            }
+            .into_any()
+        }
+    };
+
+    let container_classes = cn(&["w-full space-y-2", class.unwrap_or("")]);
+
+    view! {
+        <div class=container_classes>
+            <div class="flex items-baseline justify-between">
+                <span id=label_id class="text-sm font-medium">{label}</span>
+                <span class="text-xs text-muted-foreground" aria-live="polite">
+                    {move || severity_description(value.get())}
+                </span>
+            </div>
+            {control}
+        </div>
+    }
+}
@@ -0,0 +1,443 @@
+use leptos::prelude::*;
+use chrono::Utc;
+use std::rc::Rc;
+use std::time::Duration;
+use web_sys::{HtmlAudioElement, HtmlVideoElement};
+use crate::chat_simple::validate_message_content;
+use crate::ui::{cn, use_i18n, ConfirmDialog};
+use crate::utils::datetime::{format_datetime, format_relative, parse_timezone, DEFAULT_TIMEZONE};
+use crate::websocket_simple::ChatMessage;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CallState {
+    Connecting,
+    Connected,
+    Reconnecting,
+    Ended,
+}
+
+impl CallState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CallState::Connecting => "Connecting…",
+            CallState::Connected => "Connected",
+            CallState::Reconnecting => "Reconnecting…",
+            CallState::Ended => "Call ended",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionQuality {
+    Good,
+    Fair,
+    Poor,
+    Disconnected,
+}
+
+impl ConnectionQuality {
+    /// Rate a connection from WebRTC stats: round-trip time in milliseconds
+    /// and packet loss as a fraction from 0.0 to 1.0
+    pub fn from_stats(round_trip_ms: f64, packet_loss: f64) -> Self {
+        if packet_loss > 0.1 || round_trip_ms > 500.0 {
+            ConnectionQuality::Poor
+        } else if packet_loss > 0.03 || round_trip_ms > 250.0 {
+            ConnectionQuality::Fair
+        } else {
+            ConnectionQuality::Good
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConnectionQuality::Good => "Good connection",
+            ConnectionQuality::Fair => "Fair connection",
+            ConnectionQuality::Poor => "Poor connection",
+            ConnectionQuality::Disconnected => "Disconnected",
+        }
+    }
+
+    /// Filled bars out of three
+    pub fn bars(&self) -> u8 {
+        match self {
+            ConnectionQuality::Good => 3,
+            ConnectionQuality::Fair => 2,
+            ConnectionQuality::Poor => 1,
+            ConnectionQuality::Disconnected => 0,
+        }
+    }
+}
+
+/// Updates pushed by a [`MediaEngine`] while a call is running
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaEvent {
+    StateChanged(CallState),
+    QualityChanged(ConnectionQuality),
+    /// Whether the other participant is currently sending video
+    RemoteVideoChanged(bool),
+    RemoteAudioChanged(bool),
+}
+
+/// Transport behind a [`VideoConsultation`], e.g. a WebRTC peer connection or
+/// a hosted video SDK. The UI only drives these calls and never touches
+/// signalling or media streams itself.
+pub trait MediaEngine {
+    fn attach_local_video(&self, video: &HtmlVideoElement);
+    fn attach_remote_video(&self, video: &HtmlVideoElement);
+    fn set_audio_enabled(&self, enabled: bool);
+    fn set_video_enabled(&self, enabled: bool);
+    /// Audio-only calls have no video element; engines that play remote audio
+    /// themselves can ignore this
+    fn attach_remote_audio(&self, _audio: &HtmlAudioElement) {}
+    /// Route audio to the loudspeaker instead of the earpiece, where the platform allows it
+    fn set_speaker_enabled(&self, _enabled: bool) {}
+    /// Register the listener for call updates. Called whenever a call UI
+    /// mounts, e.g. after escalating from audio to video; replaces any earlier listener.
+    fn subscribe(&self, listener: Box<dyn Fn(MediaEvent)>);
+    fn end_call(&self);
+}
+
+/// Three-bar signal indicator
+#[component]
+pub fn ConnectionQualityIndicator(
+    #[prop(into)] quality: Signal<ConnectionQuality>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let bar_color = move || match quality.get() {
+        ConnectionQuality::Good => "bg-green-500",
+        ConnectionQuality::Fair => "bg-yellow-400",
+        _ => "bg-red-500",
+    };
+    let bars = [1u8, 2, 3]
+        .into_iter()
+        .map(|bar| {
+            let heights = ["h-1.5", "h-2.5", "h-3.5"];
+            view! {
+                <span class=move || cn(&[
+                    "w-1 rounded-sm",
+                    heights[bar as usize - 1],
+                    if quality.get().bars() >= bar { bar_color() } else { "bg-white/30" },
+                ])></span>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <span
+            class=cn(&["inline-flex items-end gap-0.5", class.unwrap_or("")])
+            role="img"
+            aria-label=move || quality.get().label()
+            title=move || quality.get().label()
+        >
+            {bars}
+        </span>
+    }
+}
+
+/// Video surface with a name label and muted/camera-off overlays
+#[component]
+pub fn VideoTile(
+    video_ref: NodeRef<leptos::html::Video>,
+    #[prop(into)] label: String,
+    /// Mute playback; set for the local preview to avoid echo
+    #[prop(optional)] muted: Option<bool>,
+    #[prop(optional, into)] video_off: Signal<bool>,
+    #[prop(optional, into)] audio_off: Signal<bool>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let initials: String = label
+        .split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .collect::<String>()
+        .to_uppercase();
+
+    view! {
+        <div class=cn(&["relative overflow-hidden rounded-lg bg-gray-900", class.unwrap_or("")])>
+            <video
+                node_ref=video_ref
+                class=move || cn(&["h-full w-full object-cover", if video_off.get() { "invisible" } else { "" }])
+                autoplay=true
+                playsinline=true
+                prop:muted=muted.unwrap_or(false)
+            ></video>
+            <Show when=move || video_off.get()>
+                <div class="absolute inset-0 flex items-center justify-center">
+                    <span class="flex h-16 w-16 items-center justify-center rounded-full bg-gray-700 text-xl font-semibold text-white">
+                        {initials.clone()}
+                    </span>
+                </div>
+            </Show>
+            <div class="absolute bottom-2 left-2 flex items-center gap-1 rounded bg-black/60 px-2 py-0.5 text-xs text-white">
+                <Show when=move || audio_off.get()>
+                    <span class="text-red-400">"Muted ·"</span>
+                </Show>
+                {label.clone()}
+            </div>
+        </div>
+    }
+}
+
+/// Chat panel shown beside the video during a call
+#[component]
+pub fn InCallChat(
+    #[prop(into)] messages: Signal<Vec<ChatMessage>>,
+    #[prop(into)] current_user_id: String,
+    on_send: Callback<String>,
+    /// IANA timezone for message times (default: Asia/Kolkata)
+    #[prop(optional, into)] timezone: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let draft = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+    let i18n = use_i18n();
+    let tz = timezone
+        .as_deref()
+        .and_then(|tz| parse_timezone(tz).ok())
+        .unwrap_or(DEFAULT_TIMEZONE);
+
+    // Keeps "5 min ago" labels current while the chat is open
+    let now = RwSignal::new(Utc::now());
+    if let Ok(handle) = set_interval_with_handle(move || now.set(Utc::now()), Duration::from_secs(30)) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let send = move || {
+        let content = draft.get_untracked();
+        match validate_message_content(&content) {
+            Ok(()) => {
+                on_send.run(content.trim().to_string());
+                draft.set(String::new());
+                error.set(None);
+            }
+            Err(message) => error.set(Some(message)),
+        }
+    };
+
+    let list = move || {
+        let (now, locale) = (now.get(), i18n.locale());
+        messages
+            .get()
+            .into_iter()
+            .map(|message| {
+                let own = message.sender_id == current_user_id;
+                let sent_at = message.timestamp;
+                view! {
+                    <li class=cn(&["flex flex-col", if own { "items-end" } else { "items-start" }])>
+                        <span class=cn(&[
+                            "max-w-[85%] whitespace-pre-wrap break-words rounded-lg px-3 py-1.5 text-sm",
+                            if own { "bg-primary text-primary-foreground" } else { "bg-muted" },
+                        ])>
+                            {message.content}
+                        </span>
+                        <time
+                            class="mt-0.5 text-[10px] text-muted-foreground"
+                            datetime=sent_at.to_rfc3339()
+                            title=format_datetime(&sent_at, &tz, locale)
+                        >
+                            {format_relative(&sent_at, &now, &tz, locale)}
+                        </time>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <aside class=cn(&["flex h-full flex-col border-l bg-background", class.unwrap_or("")]) aria-label="In-call chat">
+            <h3 class="border-b px-4 py-3 text-sm font-semibold">"Chat"</h3>
+            <ul class="flex-1 space-y-2 overflow-y-auto p-4" aria-live="polite">{list}</ul>
+            <form
+                class="space-y-1 border-t p-3"
+                on:submit=move |ev| {
+                    ev.prevent_default();
+                    send();
+                }
+            >
+                <div class="flex gap-2">
+                    <input
+                        type="text"
+                        class="flex h-9 w-full rounded-md border border-input bg-background px-3 text-sm focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-ring"
+                        placeholder="Type a message"
+                        aria-label="Message"
+                        prop:value=move || draft.get()
+                        on:input=move |ev| draft.set(event_target_value(&ev))
+                    />
+                    <button
+                        type="submit"
+                        class="h-9 rounded-md bg-primary px-3 text-sm font-medium text-primary-foreground hover:bg-primary/90 disabled:opacity-50"
+                        disabled=move || draft.with(|d| d.trim().is_empty())
+                    >
+                        "Send"
+                    </button>
+                </div>
+                {move || error.get().map(|message| view! { <p class="text-xs text-destructive" role="alert">{message}</p> })}
+            </form>
+        </aside>
+    }
+}
+
+fn control_classes(active: bool) -> String {
+    cn(&[
+        "inline-flex h-11 min-w-[5.5rem] items-center justify-center rounded-full px-4 text-sm font-medium transition-colors focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-white",
+        if active { "bg-white/15 text-white hover:bg-white/25" } else { "bg-white text-gray-900 hover:bg-white/90" },
+    ])
+}
+
+/// Telehealth video call: remote video with a local preview, mute and camera
+/// toggles, connection quality, optional in-call chat and a confirmed hang-up.
+///
+/// Media is handled entirely by the supplied [`MediaEngine`]. Chat is shown
+/// when `on_send_message` is set.
+#[component]
+pub fn VideoConsultation(
+    engine: Rc<dyn MediaEngine>,
+    #[prop(into)] remote_name: String,
+    /// Called after the engine has ended the call
+    on_end: Callback<()>,
+    #[prop(optional, into)] local_name: Option<String>,
+    #[prop(optional, into)] messages: Signal<Vec<ChatMessage>>,
+    #[prop(optional, into)] current_user_id: Option<String>,
+    #[prop(optional)] on_send_message: Option<Callback<String>>,
+    /// IANA timezone for chat message times
+    #[prop(optional, into)] timezone: Option<String>,
+    #[prop(optional)] class: Option<&'static str>,
+) -> impl IntoView {
+    let local_name = local_name.unwrap_or_else(|| "You".to_string());
+    let timezone = timezone.unwrap_or_else(|| DEFAULT_TIMEZONE.name().to_string());
+    let engine = StoredValue::new_local(engine);
+
+    let state = RwSignal::new(CallState::Connecting);
+    let quality = RwSignal::new(ConnectionQuality::Good);
+    let remote_video = RwSignal::new(true);
+    let remote_audio = RwSignal::new(true);
+    let audio_enabled = RwSignal::new(true);
+    let video_enabled = RwSignal::new(true);
+    let chat_open = RwSignal::new(false);
+    let confirm_end = RwSignal::new(false);
+
+    let local_ref = NodeRef::<leptos::html::Video>::new();
+    let remote_ref = NodeRef::<leptos::html::Video>::new();
+
+    engine.with_value(|engine| {
+        engine.subscribe(Box::new(move |event| match event {
+            MediaEvent::StateChanged(value) => state.set(value),
+            MediaEvent::QualityChanged(value) => quality.set(value),
+            MediaEvent::RemoteVideoChanged(value) => remote_video.set(value),
+            MediaEvent::RemoteAudioChanged(value) => remote_audio.set(value),
+        }));
+    });
+
+    Effect::new(move |_| {
+        if let Some(video) = local_ref.get() {
+            engine.with_value(|engine| engine.attach_local_video(&video));
+        }
+    });
+    Effect::new(move |_| {
+        if let Some(video) = remote_ref.get() {
+            engine.with_value(|engine| engine.attach_remote_video(&video));
+        }
+    });
+
+    let toggle_audio = move |_| {
+        let enabled = !audio_enabled.get_untracked();
+        engine.with_value(|engine| engine.set_audio_enabled(enabled));
+        audio_enabled.set(enabled);
+    };
+    let toggle_video = move |_| {
+        let enabled = !video_enabled.get_untracked();
+        engine.with_value(|engine| engine.set_video_enabled(enabled));
+        video_enabled.set(enabled);
+    };
+    let end_call = Callback::new(move |_| {
+        confirm_end.set(false);
+        engine.with_value(|engine| engine.end_call());
+        state.set(CallState::Ended);
+        on_end.run(());
+    });
+
+    let has_chat = on_send_message.is_some();
+    let current_user_id = current_user_id.unwrap_or_default();
+    let container_classes = cn(&["flex h-full min-h-[24rem] w-full overflow-hidden rounded-lg bg-gray-950", class.unwrap_or("")]);
+
+    view! {
+        <section class=container_classes aria-label=format!("Video consultation with {}", remote_name)>
+            <div class="relative flex flex-1 flex-col">
+                <div class="absolute left-3 top-3 z-10 flex items-center gap-2 rounded-full bg-black/60 px-3 py-1 text-xs text-white">
+                    <ConnectionQualityIndicator quality=quality />
+                    <span aria-live="polite">{move || state.get().label()}</span>
+                </div>
+                <VideoTile
+                    video_ref=remote_ref
+                    label=remote_name.clone()
+                    video_off=Signal::derive(move || !remote_video.get())
+                    audio_off=Signal::derive(move || !remote_audio.get())
+                    class="flex-1 rounded-none"
+                />
+                <VideoTile
+                    video_ref=local_ref
+                    label=local_name
+                    muted=true
+                    video_off=Signal::derive(move || !video_enabled.get())
+                    audio_off=Signal::derive(move || !audio_enabled.get())
+                    class="absolute bottom-20 right-3 aspect-video w-40 shadow-lg ring-1 ring-white/20 sm:w-56"
+                />
+                <div class="flex items-center justify-center gap-3 bg-black/80 p-3" role="toolbar" aria-label="Call controls">
+                    <button
+                        type="button"
+                        class=move || control_classes(audio_enabled.get())
+                        aria-pressed=move || (!audio_enabled.get()).to_string()
+                        on:click=toggle_audio
+                    >
+                        {move || if audio_enabled.get() { "Mute" } else { "Unmute" }}
+                    </button>
+                    <button
+                        type="button"
+                        class=move || control_classes(video_enabled.get())
+                        aria-pressed=move || (!video_enabled.get()).to_string()
+                        on:click=toggle_video
+                    >
+                        {move || if video_enabled.get() { "Stop video" } else { "Start video" }}
+                    </button>
+                    {has_chat.then(|| view! {
+                        <button
+                            type="button"
+                            class=move || control_classes(!chat_open.get())
+                            aria-pressed=move || chat_open.get().to_string()
+                            on:click=move |_| chat_open.update(|open| *open = !*open)
+                        >
+                            "Chat"
+                        </button>
+                    })}
+                    <button
+                        type="button"
+                        class="inline-flex h-11 items-center justify-center rounded-full bg-red-600 px-5 text-sm font-medium text-white transition-colors hover:bg-red-700 focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-white disabled:opacity-50"
+                        disabled=move || state.get() == CallState::Ended
+                        on:click=move |_| confirm_end.set(true)
+                    >
+                        "End call"
+                    </button>
+                </div>
+            </div>
+            {on_send_message.map(|on_send| view! {
+                <Show when=move || chat_open.get()>
+                    <InCallChat
+                        messages=messages
+                        current_user_id=current_user_id.clone()
+                        on_send=on_send
+                        timezone=timezone.clone()
+                        class="w-80 shrink-0"
+                    />
+                </Show>
+            })}
+            <ConfirmDialog
+                open=confirm_end
+                on_confirm=end_call
+                on_cancel=Callback::new(move |_| confirm_end.set(false))
+                title="End consultation?"
+                message=format!("This will disconnect you from {}.", remote_name)
+                confirm_label="End call"
+            />
+        </section>
+    }
+}
@@ -0,0 +1,286 @@
+//! Units of measure for clinical values, with conversions that refuse to mix dimensions
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::errors::{SharedError, SharedResult};
+
+/// Physical quantity a unit measures; conversion only happens within one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Dimension {
+    /// Mass or molar concentration in blood, e.g. glucose
+    Concentration,
+    Temperature,
+    Mass,
+    Pressure,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Unit {
+    #[serde(rename = "mg/dL")]
+    MilligramsPerDeciliter,
+    #[serde(rename = "mmol/L")]
+    MillimolesPerLiter,
+    #[serde(rename = "°C")]
+    Celsius,
+    #[serde(rename = "°F")]
+    Fahrenheit,
+    #[serde(rename = "kg")]
+    Kilograms,
+    #[serde(rename = "lb")]
+    Pounds,
+    #[serde(rename = "mmHg")]
+    MillimetersOfMercury,
+    #[serde(rename = "kPa")]
+    Kilopascals,
+}
+
+impl Unit {
+    pub const ALL: [Unit; 8] = [
+        Unit::MilligramsPerDeciliter,
+        Unit::MillimolesPerLiter,
+        Unit::Celsius,
+        Unit::Fahrenheit,
+        Unit::Kilograms,
+        Unit::Pounds,
+        Unit::MillimetersOfMercury,
+        Unit::Kilopascals,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Unit::MilligramsPerDeciliter => "mg/dL",
+            Unit::MillimolesPerLiter => "mmol/L",
+            Unit::Celsius => "°C",
+            Unit::Fahrenheit => "°F",
+            Unit::Kilograms => "kg",
+            Unit::Pounds => "lb",
+            Unit::MillimetersOfMercury => "mmHg",
+            Unit::Kilopascals => "kPa",
+        }
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            Unit::MilligramsPerDeciliter | Unit::MillimolesPerLiter => Dimension::Concentration,
+            Unit::Celsius | Unit::Fahrenheit => Dimension::Temperature,
+            Unit::Kilograms | Unit::Pounds => Dimension::Mass,
+            Unit::MillimetersOfMercury | Unit::Kilopascals => Dimension::Pressure,
+        }
+    }
+
+    /// Decimal places clinicians expect when the unit is displayed
+    pub fn display_precision(&self) -> usize {
+        match self {
+            Unit::MilligramsPerDeciliter | Unit::MillimetersOfMercury => 0,
+            _ => 1,
+        }
+    }
+
+    /// Parse a unit symbol as written by devices and labs, e.g. "mg/dl", "degF", "lbs"
+    pub fn parse(symbol: &str) -> Option<Self> {
+        let normalized: String = symbol
+            .trim()
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '°' && *c != 'º')
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match normalized.as_str() {
+            "mg/dl" => Some(Unit::MilligramsPerDeciliter),
+            "mmol/l" => Some(Unit::MillimolesPerLiter),
+            "c" | "degc" | "cel" | "celsius" => Some(Unit::Celsius),
+            "f" | "degf" | "[degf]" | "fahrenheit" => Some(Unit::Fahrenheit),
+            "kg" | "kgs" => Some(Unit::Kilograms),
+            "lb" | "lbs" | "[lb_av]" => Some(Unit::Pounds),
+            "mmhg" | "mm[hg]" => Some(Unit::MillimetersOfMercury),
+            "kpa" => Some(Unit::Kilopascals),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Analyte whose molar mass links mg/dL and mmol/L
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Analyte {
+    Glucose,
+    Cholesterol,
+    Triglycerides,
+}
+
+impl Analyte {
+    /// mg/dL equivalent to 1 mmol/L
+    pub fn mg_dl_per_mmol_l(&self) -> f64 {
+        match self {
+            Analyte::Glucose => 18.016,
+            Analyte::Cholesterol => 38.67,
+            Analyte::Triglycerides => 88.57,
+        }
+    }
+
+    /// Analyte measured by a LOINC test code
+    pub fn from_loinc(code: &str) -> Option<Self> {
+        match code.trim() {
+            "2345-7" | "2339-0" | "14749-6" | "15074-8" | "41653-7" => Some(Analyte::Glucose),
+            "2093-3" | "14647-2" | "2085-9" | "2089-1" | "13457-7" => Some(Analyte::Cholesterol),
+            "2571-8" | "14927-8" => Some(Analyte::Triglycerides),
+            _ => None,
+        }
+    }
+
+    /// Best guess from a display name such as "Fasting blood glucose"
+    pub fn infer(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.contains("glucose") || name.contains("sugar") {
+            Some(Analyte::Glucose)
+        } else if name.contains("cholesterol") || name.contains("hdl") || name.contains("ldl") {
+            Some(Analyte::Cholesterol)
+        } else if name.contains("triglyceride") {
+            Some(Analyte::Triglycerides)
+        } else {
+            None
+        }
+    }
+}
+
+const KG_PER_LB: f64 = 0.453_592_37;
+const KPA_PER_MMHG: f64 = 0.133_322_387_415;
+
+/// Numeric value tagged with its unit. Conversions fail instead of guessing:
+/// across dimensions, or between mg/dL and mmol/L without an analyte.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: Unit,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analyte: Option<Analyte>,
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Self { value, unit, analyte: None }
+    }
+
+    /// Concentration of a specific analyte, convertible between mg/dL and mmol/L
+    pub fn of(analyte: Analyte, value: f64, unit: Unit) -> Self {
+        Self { value, unit, analyte: Some(analyte) }
+    }
+
+    /// Parse a value and a unit symbol, e.g. ("98.6", "°F")
+    pub fn parse(value: &str, unit: &str) -> Option<Self> {
+        let value = value.trim().parse::<f64>().ok().filter(|v| v.is_finite())?;
+        Some(Self::new(value, Unit::parse(unit)?))
+    }
+
+    pub fn with_analyte(mut self, analyte: Option<Analyte>) -> Self {
+        self.analyte = analyte;
+        self
+    }
+
+    pub fn convert_to(&self, unit: Unit) -> SharedResult<Quantity> {
+        if self.unit == unit {
+            return Ok(*self);
+        }
+        if self.unit.dimension() != unit.dimension() {
+            return Err(SharedError::ValidationError(format!(
+                "Cannot convert {} to {}: different dimensions",
+                self.unit, unit
+            )));
+        }
+
+        let value = match (self.unit, unit) {
+            (Unit::Celsius, Unit::Fahrenheit) => self.value * 9.0 / 5.0 + 32.0,
+            (Unit::Fahrenheit, Unit::Celsius) => (self.value - 32.0) * 5.0 / 9.0,
+            (Unit::Pounds, Unit::Kilograms) => self.value * KG_PER_LB,
+            (Unit::Kilograms, Unit::Pounds) => self.value / KG_PER_LB,
+            (Unit::MillimetersOfMercury, Unit::Kilopascals) => self.value * KPA_PER_MMHG,
+            (Unit::Kilopascals, Unit::MillimetersOfMercury) => self.value / KPA_PER_MMHG,
+            (Unit::MillimolesPerLiter, Unit::MilligramsPerDeciliter) => self.value * self.molar_factor()?,
+            (Unit::MilligramsPerDeciliter, Unit::MillimolesPerLiter) => self.value / self.molar_factor()?,
+            (from, to) => {
+                return Err(SharedError::ValidationError(format!(
+                    "No conversion from {} to {}",
+                    from, to
+                )))
+            }
+        };
+        Ok(Quantity { value, unit, analyte: self.analyte })
+    }
+
+    /// Value in `unit`, for comparisons against thresholds kept in a fixed unit
+    pub fn value_in(&self, unit: Unit) -> SharedResult<f64> {
+        self.convert_to(unit).map(|quantity| quantity.value)
+    }
+
+    fn molar_factor(&self) -> SharedResult<f64> {
+        self.analyte.map(|analyte| analyte.mg_dl_per_mmol_l()).ok_or_else(|| {
+            SharedError::ValidationError(
+                "Converting between mg/dL and mmol/L requires the analyte".to_string(),
+            )
+        })
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*} {}", self.unit.display_precision(), self.value, self.unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 0.01, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let fever = Quantity::new(38.5, Unit::Celsius);
+        assert_close(fever.value_in(Unit::Fahrenheit).unwrap(), 101.3);
+        assert_close(Quantity::new(98.6, Unit::Fahrenheit).value_in(Unit::Celsius).unwrap(), 37.0);
+        assert_close(Quantity::new(70.0, Unit::Kilograms).value_in(Unit::Pounds).unwrap(), 154.32);
+        assert_close(Quantity::new(120.0, Unit::MillimetersOfMercury).value_in(Unit::Kilopascals).unwrap(), 16.0);
+
+        let glucose = Quantity::of(Analyte::Glucose, 5.5, Unit::MillimolesPerLiter);
+        let mg_dl = glucose.convert_to(Unit::MilligramsPerDeciliter).unwrap();
+        assert_close(mg_dl.value, 99.09);
+        assert_close(mg_dl.value_in(Unit::MillimolesPerLiter).unwrap(), 5.5);
+
+        for unit in Unit::ALL {
+            let quantity = Quantity::of(Analyte::Cholesterol, 42.0, unit);
+            assert_eq!(quantity.convert_to(unit).unwrap(), quantity);
+        }
+    }
+
+    #[test]
+    fn test_unit_mixing_is_rejected() {
+        assert!(Quantity::new(70.0, Unit::Kilograms).convert_to(Unit::Celsius).is_err());
+        assert!(Quantity::new(120.0, Unit::MillimetersOfMercury).convert_to(Unit::MilligramsPerDeciliter).is_err());
+        // Glucose and cholesterol convert with different factors, so the analyte must be known
+        assert!(Quantity::new(5.5, Unit::MillimolesPerLiter).convert_to(Unit::MilligramsPerDeciliter).is_err());
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(Unit::parse("mg/dl"), Some(Unit::MilligramsPerDeciliter));
+        assert_eq!(Unit::parse(" °F "), Some(Unit::Fahrenheit));
+        assert_eq!(Unit::parse("mm[Hg]"), Some(Unit::MillimetersOfMercury));
+        assert_eq!(Unit::parse("bpm"), None);
+        assert_eq!(Quantity::parse("98.6", "degF"), Some(Quantity::new(98.6, Unit::Fahrenheit)));
+        assert_eq!(Quantity::parse("120/80", "mmHg"), None);
+
+        assert_eq!(Quantity::new(99.09, Unit::MilligramsPerDeciliter).to_string(), "99 mg/dL");
+        assert_eq!(Quantity::new(37.04, Unit::Celsius).to_string(), "37.0 °C");
+        assert_eq!(serde_json::to_string(&Unit::MillimolesPerLiter).unwrap(), "\"mmol/L\"");
+        assert_eq!(Analyte::from_loinc("2345-7"), Some(Analyte::Glucose));
+        assert_eq!(Analyte::infer("Fasting Blood Sugar"), Some(Analyte::Glucose));
+    }
+}
@@ -51,26 +51,18 @@ pub mod validation {
         }
     }
 
-    /// Validate password strength
+    /// Validate a new password against the default [`crate::auth::PasswordPolicy`]
     pub fn validate_password(password: &str) -> SharedResult<()> {
-        if password.len() < 12 {
-            return Err(SharedError::ValidationError(
-                "Password must be at least 12 characters long".to_string()
-            ));
-        }
-
-        let has_upper = password.chars().any(|c| c.is_ascii_uppercase());
-        let has_lower = password.chars().any(|c| c.is_ascii_lowercase());
-        let has_digit = password.chars().any(|c| c.is_ascii_digit());
-        let has_special = password.chars().any(|c| "!@#$%^&*()_+-=[]{}|;:,.<>?".contains(c));
+        crate::auth::PasswordValidator::default().validate(password, &[])
+    }
 
-        if !(has_upper && has_lower && has_digit && has_special) {
-            return Err(SharedError::ValidationError(
-                "Password must contain uppercase, lowercase, digit, and special character".to_string()
-            ));
+    /// Validate a one-time passcode (6 digits)
+    pub fn validate_otp_code(code: &str) -> SharedResult<()> {
+        if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+            Ok(())
+        } else {
+            Err(SharedError::ValidationError("OTP must be exactly 6 digits".to_string()))
         }
-
-        Ok(())
     }
 
     /// Validate date of birth (must be realistic for healthcare)
@@ -95,10 +87,189 @@ pub mod validation {
     }
 }
 
+/// Typed, normalized contact details. Parsing is the only way to construct
+/// them, so a value in hand is always valid.
+pub mod contact {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use std::fmt;
+
+    /// Country calling code assumed for numbers written without one
+    pub const DEFAULT_COUNTRY_CODE: &str = "91";
+
+    /// Phone number in E.164 form, e.g. "+919876543210"
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[serde(try_from = "String", into = "String")]
+    pub struct PhoneNumber(String);
+
+    impl PhoneNumber {
+        /// Parse with India (+91) as the default country
+        pub fn parse(input: &str) -> SharedResult<Self> {
+            Self::parse_with_default(input, DEFAULT_COUNTRY_CODE)
+        }
+
+        /// Accepts "+44 20 7946 0958", "0044 20...", "098765 43210", "(987) 654-3210".
+        /// Numbers without an international prefix get `default_country_code`.
+        pub fn parse_with_default(input: &str, default_country_code: &str) -> SharedResult<Self> {
+            let invalid = || SharedError::ValidationError(format!("Invalid phone number: {}", input));
+            let trimmed = input.trim();
+            if trimmed.is_empty()
+                || !trimmed
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || matches!(c, '+' | ' ' | '-' | '.' | '(' | ')'))
+                || trimmed.rfind('+').is_some_and(|i| i != 0)
+            {
+                return Err(invalid());
+            }
+            let digits: String = trimmed.chars().filter(char::is_ascii_digit).collect();
+
+            let international = if trimmed.starts_with('+') {
+                digits
+            } else if let Some(rest) = digits.strip_prefix("00") {
+                rest.to_string()
+            } else {
+                let national = digits.trim_start_matches('0');
+                // "919876543210" typed without the plus
+                if default_country_code == "91" && national.len() == 12 && national.starts_with("91") {
+                    national.to_string()
+                } else {
+                    format!("{}{}", default_country_code, national)
+                }
+            };
+
+            if international.starts_with('0') || !(8..=15).contains(&international.len()) {
+                return Err(invalid());
+            }
+            if let Some(national) = international.strip_prefix("91") {
+                if national.len() != 10 || national.starts_with('0') {
+                    return Err(invalid());
+                }
+            }
+            Ok(Self(format!("+{}", international)))
+        }
+
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+
+        pub fn is_indian(&self) -> bool {
+            self.0.starts_with("+91")
+        }
+
+        /// Indian mobiles start with 6-9 after the country code
+        pub fn is_indian_mobile(&self) -> bool {
+            self.is_indian() && self.0[3..].starts_with(['6', '7', '8', '9'])
+        }
+
+        /// Readable form, "+91 98765 43210" for Indian numbers and E.164 otherwise
+        pub fn formatted(&self) -> String {
+            if self.is_indian() {
+                format!("+91 {} {}", &self.0[3..8], &self.0[8..])
+            } else {
+                self.0.clone()
+            }
+        }
+
+        /// Last four digits only, for logs and notifications
+        pub fn masked(&self) -> String {
+            let visible = &self.0[self.0.len() - 4..];
+            format!("{}{}", "*".repeat(self.0.len() - 4), visible)
+        }
+    }
+
+    impl TryFrom<String> for PhoneNumber {
+        type Error = SharedError;
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            Self::parse(&value)
+        }
+    }
+
+    impl From<PhoneNumber> for String {
+        fn from(phone: PhoneNumber) -> Self {
+            phone.0
+        }
+    }
+
+    impl fmt::Display for PhoneNumber {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    /// Email address, trimmed with the domain lower-cased
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[serde(try_from = "String", into = "String")]
+    pub struct EmailAddress(String);
+
+    impl EmailAddress {
+        pub fn parse(input: &str) -> SharedResult<Self> {
+            let invalid = || SharedError::ValidationError(format!("Invalid email address: {}", input));
+            let trimmed = input.trim();
+            let (local, domain) = trimmed.split_once('@').ok_or_else(invalid)?;
+
+            let local_ok = (1..=64).contains(&local.len())
+                && local
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "._%+-'".contains(c))
+                && !local.starts_with('.')
+                && !local.ends_with('.')
+                && !local.contains("..");
+            let labels: Vec<&str> = domain.split('.').collect();
+            let domain_ok = domain.len() <= 253
+                && labels.len() >= 2
+                && labels.iter().all(|label| {
+                    (1..=63).contains(&label.len())
+                        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                        && !label.starts_with('-')
+                        && !label.ends_with('-')
+                })
+                && labels
+                    .last()
+                    .is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()));
+
+            if local_ok && domain_ok && trimmed.len() <= 254 {
+                Ok(Self(format!("{}@{}", local, domain.to_ascii_lowercase())))
+            } else {
+                Err(invalid())
+            }
+        }
+
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+
+        pub fn domain(&self) -> &str {
+            self.0.rsplit_once('@').map_or("", |(_, domain)| domain)
+        }
+    }
+
+    impl TryFrom<String> for EmailAddress {
+        type Error = SharedError;
+
+        fn try_from(value: String) -> Result<Self, Self::Error> {
+            Self::parse(&value)
+        }
+    }
+
+    impl From<EmailAddress> for String {
+        fn from(email: EmailAddress) -> Self {
+            email.0
+        }
+    }
+
+    impl fmt::Display for EmailAddress {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+}
+
 /// Date and time utilities for healthcare scheduling
 pub mod datetime {
     use super::*;
-    use chrono::{DateTime, Utc, NaiveTime, Weekday, Datelike, Timelike, Duration};
+    use chrono::{DateTime, Utc, NaiveDate, NaiveTime, Weekday, Datelike, Timelike, Duration};
+    use crate::i18n::{translate, translate_plural, Locale};
 
     /// Check if a time slot is within business hours
     pub fn is_business_hours(datetime: &DateTime<Utc>, timezone: &str) -> SharedResult<bool> {
@@ -147,6 +318,577 @@ pub mod datetime {
 
         Ok(next_slot)
     }
+
+    /// Split a local working window on `date` into back-to-back slots, returned as UTC start times
+    pub fn generate_slots(
+        date: chrono::NaiveDate,
+        window_start: NaiveTime,
+        window_end: NaiveTime,
+        duration_minutes: i64,
+        timezone: &str,
+    ) -> SharedResult<Vec<DateTime<Utc>>> {
+        use chrono::TimeZone;
+
+        if duration_minutes <= 0 {
+            return Err(SharedError::ValidationError("Slot duration must be positive".to_string()));
+        }
+        let tz: chrono_tz::Tz = timezone.parse()
+            .map_err(|e| SharedError::ValidationError(format!("Invalid timezone: {}", e)))?;
+
+        let duration = Duration::minutes(duration_minutes);
+        let window_end = date.and_time(window_end);
+        let mut slot_start = date.and_time(window_start);
+        let mut slots = Vec::new();
+
+        while slot_start + duration <= window_end {
+            // Skip local times that do not exist (DST gaps)
+            if let Some(local) = tz.from_local_datetime(&slot_start).earliest() {
+                slots.push(local.with_timezone(&Utc));
+            }
+            slot_start += duration;
+        }
+
+        Ok(slots)
+    }
+
+    /// Parse a wall-clock time such as "14:30", "14:30:00", "2:30 PM" or "9 AM"
+    pub fn parse_clock_time(value: &str) -> Option<NaiveTime> {
+        let value = value.trim().to_uppercase();
+        ["%H:%M", "%H:%M:%S", "%I:%M %p", "%I:%M%p"]
+            .iter()
+            .find_map(|format| NaiveTime::parse_from_str(&value, format).ok())
+            .or_else(|| {
+                let (hour, meridiem) = value.split_at(value.find(['A', 'P'])?);
+                let hour: u32 = hour.trim().parse().ok()?;
+                match (meridiem, hour) {
+                    ("AM", 1..=12) => NaiveTime::from_hms_opt(hour % 12, 0, 0),
+                    ("PM", 1..=12) => NaiveTime::from_hms_opt(hour % 12 + 12, 0, 0),
+                    _ => None,
+                }
+            })
+    }
+
+    /// Parse a human duration such as "30", "45 min", "1 hour" or "1h 30m" into minutes
+    pub fn parse_duration_minutes(value: &str) -> Option<i64> {
+        let value = value.trim().to_lowercase();
+        if let Ok(minutes) = value.parse::<i64>() {
+            return (minutes > 0).then_some(minutes);
+        }
+
+        let mut total = 0.0;
+        let mut rest = value.as_str();
+        while !rest.is_empty() {
+            let number_end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            let amount: f64 = rest[..number_end].parse().ok()?;
+            rest = rest[number_end..].trim_start();
+
+            let unit_end = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+            let factor = match &rest[..unit_end] {
+                "h" | "hr" | "hrs" | "hour" | "hours" => 60.0,
+                "m" | "min" | "mins" | "minute" | "minutes" => 1.0,
+                _ => return None,
+            };
+            total += amount * factor;
+            rest = rest[unit_end..].trim_start();
+        }
+
+        let minutes = total.round() as i64;
+        (minutes > 0).then_some(minutes)
+    }
+
+    /// Parse an RFC 3339 timestamp, "YYYY-MM-DD HH:MM[:SS]" or a bare date (midnight).
+    /// Offsets are normalised to UTC.
+    pub fn parse_timestamp(value: &str) -> Option<chrono::NaiveDateTime> {
+        let value = value.trim();
+        if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+            return Some(parsed.naive_utc());
+        }
+        ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+            .or_else(|| {
+                chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })
+    }
+
+    /// Elapsed time as a call timer, "MM:SS" or "H:MM:SS" from one hour
+    pub fn format_elapsed(seconds: u64) -> String {
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{:02}:{:02}", minutes, seconds)
+        }
+    }
+
+    /// Whether the half-open intervals `[a_start, a_end)` and `[b_start, b_end)` overlap
+    pub fn intervals_overlap<T: PartialOrd>(a_start: T, a_end: T, b_start: T, b_end: T) -> bool {
+        a_start < b_end && b_start < a_end
+    }
+
+    /// Timezone assumed for users who have not set one
+    pub const DEFAULT_TIMEZONE: chrono_tz::Tz = chrono_tz::Asia::Kolkata;
+
+    /// Parse an IANA timezone name such as "Asia/Kolkata"
+    pub fn parse_timezone(name: &str) -> SharedResult<chrono_tz::Tz> {
+        name.trim()
+            .parse()
+            .map_err(|e| SharedError::ValidationError(format!("Invalid timezone: {}", e)))
+    }
+
+    /// Completed years between `dob` and `on`; zero for dates before birth
+    pub fn age_in_years(dob: NaiveDate, on: NaiveDate) -> u32 {
+        on.years_since(dob).unwrap_or(0)
+    }
+
+    /// Age as written on a chart: days for newborns, months under two years, then years
+    pub fn format_age(dob: NaiveDate, on: NaiveDate) -> String {
+        let plural = |count: u32, unit: &str| {
+            format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+        };
+        let months = (on.year() - dob.year()) * 12 + on.month() as i32 - dob.month() as i32
+            - i32::from(on.day() < dob.day());
+        match months {
+            months if months < 1 => plural((on - dob).num_days().max(0) as u32, "day"),
+            months if months < 24 => plural(months as u32, "month"),
+            _ => plural(age_in_years(dob, on), "year"),
+        }
+    }
+
+    /// Date in the user's timezone: "16 Oct 2026" in English, "16/10/2026" in Hindi
+    pub fn format_date(datetime: &DateTime<Utc>, tz: &chrono_tz::Tz, locale: Locale) -> String {
+        let local = datetime.with_timezone(tz);
+        match locale {
+            Locale::En => local.format("%-d %b %Y").to_string(),
+            Locale::Hi => local.format("%d/%m/%Y").to_string(),
+        }
+    }
+
+    /// Clock time in the user's timezone: "2:30 PM" in English, "14:30" in Hindi
+    pub fn format_time(datetime: &DateTime<Utc>, tz: &chrono_tz::Tz, locale: Locale) -> String {
+        let local = datetime.with_timezone(tz);
+        match locale {
+            Locale::En => local.format("%-I:%M %p").to_string(),
+            Locale::Hi => local.format("%H:%M").to_string(),
+        }
+    }
+
+    pub fn format_datetime(datetime: &DateTime<Utc>, tz: &chrono_tz::Tz, locale: Locale) -> String {
+        format!("{}, {}", format_date(datetime, tz, locale), format_time(datetime, tz, locale))
+    }
+
+    /// Time relative to `now`, e.g. "Just now", "5 min ago", "in 2 hours" or
+    /// "Yesterday". Day boundaries follow `tz`; from a week away the date is shown.
+    pub fn format_relative(
+        datetime: &DateTime<Utc>,
+        now: &DateTime<Utc>,
+        tz: &chrono_tz::Tz,
+        locale: Locale,
+    ) -> String {
+        let seconds = (*datetime - *now).num_seconds();
+        let past = seconds < 0;
+        let (minutes, hours) = (seconds.unsigned_abs() / 60, seconds.unsigned_abs() / 3600);
+        let days = (datetime.with_timezone(tz).date_naive() - now.with_timezone(tz).date_naive())
+            .num_days()
+            .unsigned_abs();
+        let relative = |key: &str, count: u64| {
+            let key = format!("time.{}_{}", key, if past { "ago" } else { "from_now" });
+            translate_plural(locale, &key, count, &[])
+        };
+
+        if minutes == 0 {
+            translate(locale, "time.just_now").to_string()
+        } else if hours == 0 {
+            relative("minutes", minutes)
+        } else if hours < 24 && days <= 1 {
+            relative("hours", hours)
+        } else if days == 1 {
+            translate(locale, if past { "timeline.yesterday" } else { "time.tomorrow" }).to_string()
+        } else if days < 7 {
+            relative("days", days)
+        } else {
+            format_date(datetime, tz, locale)
+        }
+    }
+}
+
+/// Colour conversions for theming
+pub mod color {
+    /// Convert a CSS colour to the bare HSL components used by the theme
+    /// variables (e.g. `"210 100% 50%"`). Accepts `#rgb`, `#rrggbb`,
+    /// `hsl(...)` and already-bare components.
+    pub fn to_hsl_components(value: &str) -> Option<String> {
+        let value = value.trim();
+        if let Some(hex) = value.strip_prefix('#') {
+            let (r, g, b) = parse_hex(hex)?;
+            return Some(rgb_to_hsl_components(r, g, b));
+        }
+
+        let inner = value
+            .strip_prefix("hsl(")
+            .and_then(|v| v.strip_suffix(')'))
+            .unwrap_or(value);
+        let parts: Vec<&str> = inner
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty())
+            .collect();
+        let [hue, saturation, lightness] = parts.as_slice() else {
+            return None;
+        };
+
+        hue.trim_end_matches("deg").parse::<f64>().ok()?;
+        for percent in [saturation, lightness] {
+            let number: f64 = percent.strip_suffix('%')?.parse().ok()?;
+            if !(0.0..=100.0).contains(&number) {
+                return None;
+            }
+        }
+        Some(format!("{} {} {}", hue.trim_end_matches("deg"), saturation, lightness))
+    }
+
+    fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+        if !hex.is_ascii() {
+            return None;
+        }
+        let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+        match hex.len() {
+            3 => {
+                let expand = |i: usize| channel(&hex[i..i + 1].repeat(2));
+                Some((expand(0)?, expand(1)?, expand(2)?))
+            }
+            6 => Some((channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?)),
+            _ => None,
+        }
+    }
+
+    fn rgb_to_hsl_components(r: u8, g: u8, b: u8) -> String {
+        let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+
+        let (hue, saturation) = if delta == 0.0 {
+            (0.0, 0.0)
+        } else {
+            let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+            let hue = if max == r {
+                60.0 * ((g - b) / delta).rem_euclid(6.0)
+            } else if max == g {
+                60.0 * ((b - r) / delta + 2.0)
+            } else {
+                60.0 * ((r - g) / delta + 4.0)
+            };
+            (hue, saturation)
+        };
+
+        format!("{:.0} {:.0}% {:.0}%", hue, saturation * 100.0, lightness * 100.0)
+    }
+
+    /// WCAG contrast ratio between two `#rgb`/`#rrggbb` colours, from 1 to 21
+    pub fn contrast_ratio(foreground: &str, background: &str) -> Option<f64> {
+        let luminance = |value: &str| -> Option<f64> {
+            let (r, g, b) = parse_hex(value.trim().strip_prefix('#')?)?;
+            let linear = |channel: u8| {
+                let c = channel as f64 / 255.0;
+                if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+            };
+            Some(0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b))
+        };
+        let (a, b) = (luminance(foreground)?, luminance(background)?);
+        Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+    }
+
+    /// WCAG 2.1 AA: 4.5:1 for body text, 3:1 for large text
+    pub fn meets_contrast_aa(foreground: &str, background: &str, large_text: bool) -> bool {
+        let required = if large_text { 3.0 } else { 4.5 };
+        contrast_ratio(foreground, background).is_some_and(|ratio| ratio >= required)
+    }
+}
+
+/// Accessibility checks. `audit_markup` is a lightweight harness for tests:
+/// render a component to HTML (SSR or `inner_html` in a browser test) and
+/// assert that it reports no issues.
+pub mod a11y {
+    use regex::Regex;
+    use std::collections::{HashMap, HashSet};
+
+    /// Keys that activate a focused control with `role="button"`
+    pub fn is_activation_key(key: &str) -> bool {
+        matches!(key, "Enter" | " " | "Spacebar")
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum A11yRule {
+        ImageMissingAlt,
+        ButtonMissingName,
+        RoleButtonNotFocusable,
+        InputMissingLabel,
+        PositiveTabindex,
+        InvalidAriaLive,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct A11yIssue {
+        pub rule: A11yRule,
+        /// Start tag of the offending element
+        pub element: String,
+    }
+
+    struct Tag {
+        name: String,
+        attrs: HashMap<String, String>,
+        source: String,
+        end: usize,
+    }
+
+    fn parse_tags(html: &str) -> Vec<Tag> {
+        let tag_re = Regex::new(r#"<([a-zA-Z][a-zA-Z0-9-]*)((?:\s+[^\s=>/]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+))?)*)\s*/?>"#).unwrap();
+        let attr_re = Regex::new(r#"([^\s=/>]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+)))?"#).unwrap();
+        tag_re
+            .captures_iter(html)
+            .map(|caps| {
+                let whole = caps.get(0).unwrap();
+                let attrs = attr_re
+                    .captures_iter(caps.get(2).map_or("", |m| m.as_str()))
+                    .map(|attr| {
+                        let value = attr.get(2).or(attr.get(3)).or(attr.get(4)).map_or("", |m| m.as_str());
+                        (attr[1].to_ascii_lowercase(), value.to_string())
+                    })
+                    .collect();
+                Tag {
+                    name: caps[1].to_ascii_lowercase(),
+                    attrs,
+                    source: whole.as_str().to_string(),
+                    end: whole.end(),
+                }
+            })
+            .collect()
+    }
+
+    fn text_content(html: &str) -> String {
+        let tags = Regex::new(r"<[^>]*>").unwrap();
+        tags.replace_all(html, "").trim().to_string()
+    }
+
+    fn has_name(attrs: &HashMap<String, String>) -> bool {
+        ["aria-label", "aria-labelledby", "title"]
+            .iter()
+            .any(|attr| attrs.get(*attr).is_some_and(|value| !value.trim().is_empty()))
+    }
+
+    /// Common WCAG failures in rendered markup: unlabelled images, buttons
+    /// and inputs, `role="button"` elements that can't be reached by keyboard,
+    /// positive tab indexes and invalid live regions
+    pub fn audit_markup(html: &str) -> Vec<A11yIssue> {
+        let tags = parse_tags(html);
+        let labelled_ids: HashSet<&str> = tags
+            .iter()
+            .filter(|tag| tag.name == "label")
+            .filter_map(|tag| tag.attrs.get("for").map(String::as_str))
+            .collect();
+
+        let mut issues = Vec::new();
+        let mut flag = |rule, tag: &Tag| issues.push(A11yIssue { rule, element: tag.source.clone() });
+
+        for tag in &tags {
+            let attr = |name: &str| tag.attrs.get(name).map(String::as_str);
+            match tag.name.as_str() {
+                "img" if attr("alt").is_none() && attr("aria-hidden") != Some("true") => {
+                    flag(A11yRule::ImageMissingAlt, tag)
+                }
+                "button" => {
+                    let inner = html[tag.end..].split("</button>").next().unwrap_or("");
+                    if !has_name(&tag.attrs) && text_content(inner).is_empty() {
+                        flag(A11yRule::ButtonMissingName, tag);
+                    }
+                }
+                "input" | "select" | "textarea"
+                    if !matches!(attr("type"), Some("hidden" | "submit" | "button"))
+                        && !has_name(&tag.attrs)
+                        && !attr("id").is_some_and(|id| labelled_ids.contains(id)) =>
+                {
+                    flag(A11yRule::InputMissingLabel, tag)
+                }
+                _ => {}
+            }
+
+            let natively_focusable = matches!(tag.name.as_str(), "button" | "a" | "input" | "select" | "textarea");
+            if attr("role") == Some("button") && !natively_focusable && attr("tabindex").is_none() {
+                flag(A11yRule::RoleButtonNotFocusable, tag);
+            }
+            if attr("tabindex").and_then(|t| t.parse::<i32>().ok()).is_some_and(|t| t > 0) {
+                flag(A11yRule::PositiveTabindex, tag);
+            }
+            if attr("aria-live").is_some_and(|live| !matches!(live, "off" | "polite" | "assertive")) {
+                flag(A11yRule::InvalidAriaLive, tag);
+            }
+        }
+        issues
+    }
+}
+
+/// Clinical calculators. Inputs are metric; use the conversion helpers for
+/// imperial or SI laboratory units.
+pub mod clinical {
+    use super::*;
+
+    pub const KG_PER_LB: f64 = 0.453_592_37;
+    pub const CM_PER_INCH: f64 = 2.54;
+    /// Creatinine: 1 mg/dL = 88.42 µmol/L
+    pub const CREATININE_UMOL_PER_MG_DL: f64 = 88.42;
+
+    pub fn lb_to_kg(pounds: f64) -> f64 {
+        pounds * KG_PER_LB
+    }
+
+    pub fn inches_to_cm(inches: f64) -> f64 {
+        inches * CM_PER_INCH
+    }
+
+    pub fn creatinine_umol_to_mg_dl(umol_per_l: f64) -> f64 {
+        umol_per_l / CREATININE_UMOL_PER_MG_DL
+    }
+
+    fn check_range(name: &str, value: f64, min: f64, max: f64, unit: &str) -> SharedResult<f64> {
+        if value.is_finite() && (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(SharedError::ValidationError(format!(
+                "{} must be between {} and {} {}",
+                name, min, max, unit
+            )))
+        }
+    }
+
+    fn check_weight(weight_kg: f64) -> SharedResult<f64> {
+        check_range("Weight", weight_kg, 0.3, 650.0, "kg")
+    }
+
+    fn check_height(height_cm: f64) -> SharedResult<f64> {
+        check_range("Height", height_cm, 30.0, 280.0, "cm")
+    }
+
+    /// Body mass index in kg/m²
+    pub fn bmi(weight_kg: f64, height_cm: f64) -> SharedResult<f64> {
+        let height_m = check_height(height_cm)? / 100.0;
+        Ok(check_weight(weight_kg)? / (height_m * height_m))
+    }
+
+    /// WHO adult BMI categories
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BmiCategory {
+        Underweight,
+        Normal,
+        Overweight,
+        Obese,
+    }
+
+    impl BmiCategory {
+        pub fn from_bmi(bmi: f64) -> Self {
+            if bmi < 18.5 {
+                BmiCategory::Underweight
+            } else if bmi < 25.0 {
+                BmiCategory::Normal
+            } else if bmi < 30.0 {
+                BmiCategory::Overweight
+            } else {
+                BmiCategory::Obese
+            }
+        }
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                BmiCategory::Underweight => "Underweight",
+                BmiCategory::Normal => "Healthy weight",
+                BmiCategory::Overweight => "Overweight",
+                BmiCategory::Obese => "Obese",
+            }
+        }
+    }
+
+    /// Body surface area in m² (Mosteller formula)
+    pub fn bsa(weight_kg: f64, height_cm: f64) -> SharedResult<f64> {
+        Ok((check_height(height_cm)? * check_weight(weight_kg)? / 3600.0).sqrt())
+    }
+
+    /// Weight-based dose in mg, capped at `max_dose_mg` (usually the adult dose)
+    pub fn weight_based_dose(weight_kg: f64, mg_per_kg: f64, max_dose_mg: Option<f64>) -> SharedResult<f64> {
+        let weight_kg = check_weight(weight_kg)?;
+        let mg_per_kg = check_range("Dose", mg_per_kg, 0.0, 1000.0, "mg/kg")?;
+        let dose = weight_kg * mg_per_kg;
+        Ok(match max_dose_mg {
+            Some(max) if max > 0.0 => dose.min(max),
+            _ => dose,
+        })
+    }
+
+    /// Sex as used by the eGFR equation
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Sex {
+        Female,
+        Male,
+    }
+
+    /// Estimated GFR in mL/min/1.73m² using the race-free CKD-EPI 2021
+    /// creatinine equation. Adults only.
+    pub fn egfr(creatinine_mg_dl: f64, age_years: u32, sex: Sex) -> SharedResult<f64> {
+        let creatinine = check_range("Creatinine", creatinine_mg_dl, 0.1, 30.0, "mg/dL")?;
+        if !(18..=120).contains(&age_years) {
+            return Err(SharedError::ValidationError(
+                "eGFR (CKD-EPI) is only valid for ages 18 to 120".to_string(),
+            ));
+        }
+        let (kappa, alpha, factor) = match sex {
+            Sex::Female => (0.7, -0.241, 1.012),
+            Sex::Male => (0.9, -0.302, 1.0),
+        };
+        let ratio = creatinine / kappa;
+        Ok(142.0
+            * ratio.min(1.0).powf(alpha)
+            * ratio.max(1.0).powf(-1.200)
+            * 0.9938_f64.powi(age_years as i32)
+            * factor)
+    }
+
+    /// KDIGO GFR categories
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CkdStage {
+        G1,
+        G2,
+        G3a,
+        G3b,
+        G4,
+        G5,
+    }
+
+    impl CkdStage {
+        pub fn from_egfr(egfr: f64) -> Self {
+            match egfr {
+                e if e >= 90.0 => CkdStage::G1,
+                e if e >= 60.0 => CkdStage::G2,
+                e if e >= 45.0 => CkdStage::G3a,
+                e if e >= 30.0 => CkdStage::G3b,
+                e if e >= 15.0 => CkdStage::G4,
+                _ => CkdStage::G5,
+            }
+        }
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                CkdStage::G1 => "G1 – normal or high",
+                CkdStage::G2 => "G2 – mildly decreased",
+                CkdStage::G3a => "G3a – mildly to moderately decreased",
+                CkdStage::G3b => "G3b – moderately to severely decreased",
+                CkdStage::G4 => "G4 – severely decreased",
+                CkdStage::G5 => "G5 – kidney failure",
+            }
+        }
+    }
 }
 
 /// String manipulation utilities
@@ -180,9 +922,11 @@ pub mod strings {
             .collect()
     }
 
-    /// Format medical record number
+    /// Format medical record number derived from a patient UUID, with its check character
     pub fn format_medical_record_number(patient_id: &uuid::Uuid) -> String {
-        format!("MRN-{}", patient_id.to_string().replace("-", "").to_uppercase())
+        crate::ids::MedicalRecordNumber::from_parts("MRN", &patient_id.simple().to_string())
+            .expect("UUID hex is alphanumeric")
+            .into()
     }
 
     /// Truncate string with ellipsis
@@ -193,6 +937,21 @@ pub mod strings {
             format!("{}...", &s[..max_len.saturating_sub(3)])
         }
     }
+
+    /// Human-readable byte count, e.g. "1.5 MB"
+    pub fn format_file_size(bytes: u64) -> String {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+        if bytes < 1024 {
+            return format!("{} B", bytes);
+        }
+        let mut size = bytes as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        format!("{:.1} {}", size, UNITS[unit])
+    }
 }
 
 /// Encryption and security utilities
@@ -313,6 +1072,187 @@ pub mod http {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_contrast_ratio() {
+        let ratio = color::contrast_ratio("#000", "#ffffff").unwrap();
+        assert!((ratio - 21.0).abs() < 0.01);
+        assert!(color::meets_contrast_aa("#1d4ed8", "#ffffff", false));
+        assert!(!color::meets_contrast_aa("#9ca3af", "#ffffff", false));
+        assert!(color::contrast_ratio("red", "#fff").is_none());
+    }
+
+    #[test]
+    fn test_audit_markup() {
+        use a11y::{audit_markup, A11yRule};
+
+        let accessible = r#"
+            <div role="button" tabindex="0" aria-label="Open patient">Jane</div>
+            <button type="button"><span class="sr-only">Close</span></button>
+            <label for="dob">Date of birth</label><input id="dob" type="date">
+            <img src="x.png" alt="">
+            <div aria-live="polite"></div>
+        "#;
+        assert!(audit_markup(accessible).is_empty());
+
+        let rules: Vec<A11yRule> = audit_markup(r#"
+            <div role="button" class="card">Jane</div>
+            <button type="button"><svg></svg></button>
+            <input type="text">
+            <img src="x.png">
+            <a href="/" tabindex="3">Home</a>
+            <div aria-live="loud"></div>
+        "#)
+        .into_iter()
+        .map(|issue| issue.rule)
+        .collect();
+        assert_eq!(rules, vec![
+            A11yRule::RoleButtonNotFocusable,
+            A11yRule::ButtonMissingName,
+            A11yRule::InputMissingLabel,
+            A11yRule::ImageMissingAlt,
+            A11yRule::PositiveTabindex,
+            A11yRule::InvalidAriaLive,
+        ]);
+        assert!(a11y::is_activation_key(" "));
+    }
+
+    #[test]
+    fn test_generate_slots() {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let start = chrono::NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let end = chrono::NaiveTime::from_hms_opt(10, 40, 0).unwrap();
+
+        let slots = datetime::generate_slots(date, start, end, 30, "Asia/Kolkata").unwrap();
+        assert_eq!(slots.len(), 3);
+        assert_eq!(slots[0].to_rfc3339(), "2024-01-15T03:30:00+00:00");
+        assert!(datetime::generate_slots(date, start, end, 0, "Asia/Kolkata").is_err());
+    }
+
+    #[test]
+    fn test_parse_clock_time() {
+        let half_two = chrono::NaiveTime::from_hms_opt(14, 30, 0).unwrap();
+        assert_eq!(datetime::parse_clock_time("14:30"), Some(half_two));
+        assert_eq!(datetime::parse_clock_time("2:30 pm"), Some(half_two));
+        assert_eq!(datetime::parse_clock_time("12 AM"), chrono::NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(datetime::parse_clock_time("25:00"), None);
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let expected = chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        assert_eq!(datetime::parse_timestamp("2024-03-01T09:30:00Z"), Some(expected));
+        assert_eq!(datetime::parse_timestamp("2024-03-01T11:30:00+02:00"), Some(expected));
+        assert_eq!(datetime::parse_timestamp("2024-03-01 09:30"), Some(expected));
+        assert_eq!(
+            datetime::parse_timestamp("2024-03-01"),
+            chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(0, 0, 0)
+        );
+        assert_eq!(datetime::parse_timestamp("yesterday"), None);
+    }
+
+    #[test]
+    fn test_clinical_calculators() {
+        let bmi = clinical::bmi(70.0, 175.0).unwrap();
+        assert!((bmi - 22.86).abs() < 0.01);
+        assert_eq!(clinical::BmiCategory::from_bmi(bmi), clinical::BmiCategory::Normal);
+        assert!(clinical::bmi(70.0, 0.0).is_err());
+
+        assert!((clinical::bsa(70.0, 175.0).unwrap() - 1.845).abs() < 0.001);
+
+        assert_eq!(clinical::weight_based_dose(20.0, 15.0, None).unwrap(), 300.0);
+        assert_eq!(clinical::weight_based_dose(80.0, 15.0, Some(1000.0)).unwrap(), 1000.0);
+        assert!(clinical::weight_based_dose(-1.0, 15.0, None).is_err());
+
+        let egfr = clinical::egfr(1.0, 50, clinical::Sex::Male).unwrap();
+        assert!((egfr - 92.0).abs() < 1.0);
+        assert_eq!(clinical::CkdStage::from_egfr(egfr), clinical::CkdStage::G1);
+        assert!(clinical::egfr(1.0, 12, clinical::Sex::Female).is_err());
+
+        assert!((clinical::lb_to_kg(154.0) - 69.85).abs() < 0.01);
+        assert!((clinical::creatinine_umol_to_mg_dl(88.42) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(datetime::format_elapsed(0), "00:00");
+        assert_eq!(datetime::format_elapsed(65), "01:05");
+        assert_eq!(datetime::format_elapsed(3599), "59:59");
+        assert_eq!(datetime::format_elapsed(3725), "1:02:05");
+    }
+
+    #[test]
+    fn test_parse_duration_minutes() {
+        assert_eq!(datetime::parse_duration_minutes("30"), Some(30));
+        assert_eq!(datetime::parse_duration_minutes("45 min"), Some(45));
+        assert_eq!(datetime::parse_duration_minutes("1 hour"), Some(60));
+        assert_eq!(datetime::parse_duration_minutes("1h 30m"), Some(90));
+        assert_eq!(datetime::parse_duration_minutes("1.5 hours"), Some(90));
+        assert_eq!(datetime::parse_duration_minutes("soon"), None);
+        assert!(datetime::intervals_overlap(1, 3, 2, 4));
+        assert!(!datetime::intervals_overlap(1, 2, 2, 3));
+    }
+
+    #[test]
+    fn test_age_formatting() {
+        let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(datetime::age_in_years(date(1990, 10, 17), date(2026, 10, 16)), 35);
+        assert_eq!(datetime::age_in_years(date(1990, 10, 16), date(2026, 10, 16)), 36);
+        assert_eq!(datetime::age_in_years(date(2027, 1, 1), date(2026, 10, 16)), 0);
+        assert_eq!(datetime::format_age(date(2026, 10, 4), date(2026, 10, 16)), "12 days");
+        assert_eq!(datetime::format_age(date(2026, 9, 16), date(2026, 10, 16)), "1 month");
+        assert_eq!(datetime::format_age(date(2025, 1, 20), date(2026, 10, 16)), "20 months");
+        assert_eq!(datetime::format_age(date(1990, 10, 17), date(2026, 10, 16)), "35 years");
+    }
+
+    #[test]
+    fn test_relative_and_local_time() {
+        use crate::i18n::Locale;
+        use chrono::TimeZone;
+
+        let tz = datetime::DEFAULT_TIMEZONE;
+        // 16 Oct 2026, 20:00 IST
+        let now = chrono::Utc.with_ymd_and_hms(2026, 10, 16, 14, 30, 0).unwrap();
+        let relative = |minutes: i64| {
+            datetime::format_relative(&(now + chrono::Duration::minutes(minutes)), &now, &tz, Locale::En)
+        };
+        assert_eq!(relative(0), "Just now");
+        assert_eq!(relative(-5), "5 min ago");
+        assert_eq!(relative(90), "in 1 hour");
+        assert_eq!(relative(-20 * 60), "20 hours ago");
+        assert_eq!(relative(-30 * 60), "Yesterday");
+        assert_eq!(relative(25 * 60), "Tomorrow");
+        assert_eq!(relative(3 * 24 * 60), "in 3 days");
+        assert_eq!(relative(-10 * 24 * 60), "6 Oct 2026");
+        assert_eq!(
+            datetime::format_relative(&(now - chrono::Duration::minutes(5)), &now, &tz, Locale::Hi),
+            "5 मिनट पहले"
+        );
+
+        assert_eq!(datetime::format_datetime(&now, &tz, Locale::En), "16 Oct 2026, 8:00 PM");
+        assert_eq!(datetime::format_datetime(&now, &tz, Locale::Hi), "16/10/2026, 20:00");
+        let utc = datetime::parse_timezone("UTC").unwrap();
+        assert_eq!(datetime::format_time(&now, &utc, Locale::En), "2:30 PM");
+        assert!(datetime::parse_timezone("Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn test_color_to_hsl_components() {
+        assert_eq!(color::to_hsl_components("#0080ff").as_deref(), Some("210 100% 50%"));
+        assert_eq!(color::to_hsl_components("#fff").as_deref(), Some("0 0% 100%"));
+        assert_eq!(color::to_hsl_components("hsl(160 60% 45%)").as_deref(), Some("160 60% 45%"));
+        assert_eq!(color::to_hsl_components("142 76% 36%").as_deref(), Some("142 76% 36%"));
+        assert_eq!(color::to_hsl_components("blue"), None);
+        assert_eq!(color::to_hsl_components("#12345"), None);
+    }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(strings::format_file_size(512), "512 B");
+        assert_eq!(strings::format_file_size(1536), "1.5 KB");
+        assert_eq!(strings::format_file_size(20 * 1024 * 1024), "20.0 MB");
+        assert_eq!(strings::format_file_size(2 * 1024 * 1024 * 1024), "2.0 GB");
+    }
+
     #[test]
     fn test_email_validation() {
         assert!(validation::validate_email("test@example.com").is_ok());
@@ -326,6 +1266,49 @@ mod tests {
         assert!(validation::validate_phone("invalid").is_err());
     }
 
+    #[test]
+    fn test_phone_number_normalization() {
+        use contact::PhoneNumber;
+
+        for input in ["9876543210", "098765 43210", "+91 98765-43210", "919876543210", "0091 9876543210"] {
+            assert_eq!(PhoneNumber::parse(input).unwrap().as_str(), "+919876543210", "{}", input);
+        }
+        let london = PhoneNumber::parse("+44 20 7946 0958").unwrap();
+        assert_eq!(london.as_str(), "+442079460958");
+        assert!(!london.is_indian());
+        assert_eq!(PhoneNumber::parse_with_default("(415) 555-2671", "1").unwrap().as_str(), "+14155552671");
+
+        let mobile = PhoneNumber::parse("9876543210").unwrap();
+        assert!(mobile.is_indian_mobile());
+        assert_eq!(mobile.formatted(), "+91 98765 43210");
+        assert_eq!(mobile.masked(), "*********3210");
+
+        for input in ["", "12345", "98765432", "+91 12345", "98765 4321x", "+1 +2 345678901", "+0123456789"] {
+            assert!(PhoneNumber::parse(input).is_err(), "{}", input);
+        }
+        assert!(serde_json::from_str::<PhoneNumber>("\"not a phone\"").is_err());
+    }
+
+    #[test]
+    fn test_email_address_normalization() {
+        use contact::EmailAddress;
+
+        let email = EmailAddress::parse("  Asha.Rao+clinic@Example.CO.IN ").unwrap();
+        assert_eq!(email.as_str(), "Asha.Rao+clinic@example.co.in");
+        assert_eq!(email.domain(), "example.co.in");
+        for input in ["plainaddress", "a@b", "a..b@example.com", ".a@example.com", "a@-example.com", "a@example.c0m", "a b@example.com"] {
+            assert!(EmailAddress::parse(input).is_err(), "{}", input);
+        }
+        assert_eq!(serde_json::to_string(&email).unwrap(), "\"Asha.Rao+clinic@example.co.in\"");
+    }
+
+    #[test]
+    fn test_otp_validation() {
+        assert!(validation::validate_otp_code("042917").is_ok());
+        assert!(validation::validate_otp_code("42917").is_err());
+        assert!(validation::validate_otp_code("04291a").is_err());
+    }
+
     #[test]
     fn test_password_validation() {
         assert!(validation::validate_password("SecurePass123!").is_ok());
@@ -0,0 +1,314 @@
+//! Typed vital signs with physiological validation and age-adjusted normal ranges
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::errors::{SharedError, SharedResult};
+use crate::units::{Dimension, Quantity, Unit};
+
+/// Paediatric age bands used for vital sign reference ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgeGroup {
+    /// Under 1 year
+    Infant,
+    /// 1–2 years
+    Toddler,
+    /// 3–5 years
+    Preschool,
+    /// 6–12 years
+    SchoolAge,
+    /// 13–17 years
+    Adolescent,
+    Adult,
+    /// 65 and over
+    OlderAdult,
+}
+
+impl AgeGroup {
+    pub fn from_years(age: u8) -> Self {
+        match age {
+            0 => AgeGroup::Infant,
+            1..=2 => AgeGroup::Toddler,
+            3..=5 => AgeGroup::Preschool,
+            6..=12 => AgeGroup::SchoolAge,
+            13..=17 => AgeGroup::Adolescent,
+            18..=64 => AgeGroup::Adult,
+            _ => AgeGroup::OlderAdult,
+        }
+    }
+}
+
+/// Outcome of comparing a reading against its reference range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VitalSeverity {
+    Normal,
+    /// Outside the normal range but not immediately dangerous
+    Abnormal,
+    Critical,
+}
+
+/// Normal range with the critical limits beyond it, both inclusive
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VitalRange {
+    pub low: f64,
+    pub high: f64,
+    pub critical_low: f64,
+    pub critical_high: f64,
+}
+
+impl VitalRange {
+    const fn new(low: f64, high: f64, critical_low: f64, critical_high: f64) -> Self {
+        Self { low, high, critical_low, critical_high }
+    }
+
+    pub fn classify(&self, value: f64) -> VitalSeverity {
+        if value < self.critical_low || value > self.critical_high {
+            VitalSeverity::Critical
+        } else if value < self.low || value > self.high {
+            VitalSeverity::Abnormal
+        } else {
+            VitalSeverity::Normal
+        }
+    }
+}
+
+fn heart_rate_range(age: AgeGroup) -> VitalRange {
+    match age {
+        AgeGroup::Infant => VitalRange::new(100.0, 160.0, 80.0, 200.0),
+        AgeGroup::Toddler => VitalRange::new(90.0, 150.0, 70.0, 190.0),
+        AgeGroup::Preschool => VitalRange::new(80.0, 140.0, 60.0, 180.0),
+        AgeGroup::SchoolAge => VitalRange::new(70.0, 120.0, 55.0, 160.0),
+        AgeGroup::Adolescent => VitalRange::new(60.0, 100.0, 45.0, 140.0),
+        AgeGroup::Adult | AgeGroup::OlderAdult => VitalRange::new(60.0, 100.0, 40.0, 130.0),
+    }
+}
+
+fn respiratory_rate_range(age: AgeGroup) -> VitalRange {
+    match age {
+        AgeGroup::Infant => VitalRange::new(30.0, 60.0, 20.0, 70.0),
+        AgeGroup::Toddler => VitalRange::new(24.0, 40.0, 16.0, 55.0),
+        AgeGroup::Preschool => VitalRange::new(22.0, 34.0, 14.0, 45.0),
+        AgeGroup::SchoolAge => VitalRange::new(18.0, 30.0, 12.0, 40.0),
+        AgeGroup::Adolescent | AgeGroup::Adult | AgeGroup::OlderAdult => VitalRange::new(12.0, 20.0, 8.0, 30.0),
+    }
+}
+
+fn systolic_range(age: AgeGroup) -> VitalRange {
+    match age {
+        AgeGroup::Infant => VitalRange::new(70.0, 100.0, 60.0, 130.0),
+        AgeGroup::Toddler => VitalRange::new(80.0, 110.0, 70.0, 135.0),
+        AgeGroup::Preschool => VitalRange::new(80.0, 110.0, 70.0, 140.0),
+        AgeGroup::SchoolAge => VitalRange::new(90.0, 120.0, 75.0, 150.0),
+        AgeGroup::Adolescent => VitalRange::new(100.0, 130.0, 85.0, 170.0),
+        AgeGroup::Adult => VitalRange::new(90.0, 130.0, 70.0, 180.0),
+        AgeGroup::OlderAdult => VitalRange::new(90.0, 140.0, 80.0, 180.0),
+    }
+}
+
+fn diastolic_range(age: AgeGroup) -> VitalRange {
+    match age {
+        AgeGroup::Infant => VitalRange::new(35.0, 65.0, 25.0, 85.0),
+        AgeGroup::Toddler => VitalRange::new(45.0, 70.0, 35.0, 90.0),
+        AgeGroup::Preschool => VitalRange::new(50.0, 75.0, 40.0, 95.0),
+        AgeGroup::SchoolAge => VitalRange::new(55.0, 80.0, 45.0, 100.0),
+        AgeGroup::Adolescent => VitalRange::new(60.0, 85.0, 45.0, 110.0),
+        AgeGroup::Adult => VitalRange::new(60.0, 85.0, 40.0, 120.0),
+        AgeGroup::OlderAdult => VitalRange::new(60.0, 90.0, 40.0, 120.0),
+    }
+}
+
+/// Saturation does not vary with age once past the newborn period
+const SPO2_RANGE: VitalRange = VitalRange::new(95.0, 100.0, 90.0, 100.0);
+/// Core temperature in °C; fever starts at 38.0
+const TEMPERATURE_RANGE_C: VitalRange = VitalRange::new(36.0, 37.9, 35.0, 40.0);
+
+/// A single vital sign reading. Construct through the checked constructors or
+/// call [`VitalSign::validate`] on deserialized input, so that typos such as a
+/// heart rate of 720 are rejected instead of raising a false emergency.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum VitalSign {
+    /// Beats per minute
+    HeartRate(u16),
+    /// mmHg
+    BloodPressure { systolic: u16, diastolic: u16 },
+    /// Peripheral oxygen saturation in percent
+    #[serde(rename = "spo2")]
+    SpO2(u8),
+    /// Body temperature in °C or °F
+    Temperature(Quantity),
+    /// Breaths per minute
+    RespiratoryRate(u16),
+}
+
+impl VitalSign {
+    pub fn heart_rate(bpm: u16) -> SharedResult<Self> {
+        Self::checked(VitalSign::HeartRate(bpm))
+    }
+
+    pub fn blood_pressure(systolic: u16, diastolic: u16) -> SharedResult<Self> {
+        Self::checked(VitalSign::BloodPressure { systolic, diastolic })
+    }
+
+    pub fn spo2(percent: u8) -> SharedResult<Self> {
+        Self::checked(VitalSign::SpO2(percent))
+    }
+
+    pub fn temperature(value: f64, unit: Unit) -> SharedResult<Self> {
+        Self::checked(VitalSign::Temperature(Quantity::new(value, unit)))
+    }
+
+    pub fn respiratory_rate(breaths_per_minute: u16) -> SharedResult<Self> {
+        Self::checked(VitalSign::RespiratoryRate(breaths_per_minute))
+    }
+
+    fn checked(vital: VitalSign) -> SharedResult<Self> {
+        vital.validate()?;
+        Ok(vital)
+    }
+
+    /// Reject values no living patient can have, which are almost always entry or sensor errors
+    pub fn validate(&self) -> SharedResult<()> {
+        let plausible = match self {
+            VitalSign::HeartRate(bpm) => (20..=300).contains(bpm),
+            VitalSign::BloodPressure { systolic, diastolic } => {
+                (40..=300).contains(systolic) && (20..=200).contains(diastolic) && systolic > diastolic
+            }
+            VitalSign::SpO2(percent) => (50..=100).contains(percent),
+            VitalSign::Temperature(quantity) => {
+                quantity.unit.dimension() == Dimension::Temperature
+                    && quantity.value_in(Unit::Celsius).is_ok_and(|celsius| (25.0..=45.0).contains(&celsius))
+            }
+            VitalSign::RespiratoryRate(rate) => *rate <= 100,
+        };
+        if plausible {
+            Ok(())
+        } else {
+            Err(SharedError::ValidationError(format!(
+                "Implausible {} reading: {}",
+                self.name().to_lowercase(),
+                self
+            )))
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            VitalSign::HeartRate(_) => "Heart rate",
+            VitalSign::BloodPressure { .. } => "Blood pressure",
+            VitalSign::SpO2(_) => "SpO2",
+            VitalSign::Temperature(_) => "Temperature",
+            VitalSign::RespiratoryRate(_) => "Respiratory rate",
+        }
+    }
+
+    pub fn unit_symbol(&self) -> &'static str {
+        match self {
+            VitalSign::HeartRate(_) => "bpm",
+            VitalSign::BloodPressure { .. } => "mmHg",
+            VitalSign::SpO2(_) => "%",
+            VitalSign::Temperature(quantity) => quantity.unit.symbol(),
+            VitalSign::RespiratoryRate(_) => "breaths/min",
+        }
+    }
+
+    /// Value without its unit, e.g. "120/80" or "37.2"
+    pub fn value_text(&self) -> String {
+        match self {
+            VitalSign::HeartRate(bpm) => bpm.to_string(),
+            VitalSign::BloodPressure { systolic, diastolic } => format!("{}/{}", systolic, diastolic),
+            VitalSign::SpO2(percent) => percent.to_string(),
+            VitalSign::Temperature(quantity) => format!("{:.1}", quantity.value),
+            VitalSign::RespiratoryRate(rate) => rate.to_string(),
+        }
+    }
+
+    /// Normal range for the age group in the reading's own unit, e.g. "60–100"
+    pub fn normal_range_text(&self, age: AgeGroup) -> String {
+        let span = |range: VitalRange| format!("{}–{}", range.low, range.high);
+        match self {
+            VitalSign::HeartRate(_) => span(heart_rate_range(age)),
+            VitalSign::BloodPressure { .. } => format!("{}/{}", span(systolic_range(age)), span(diastolic_range(age))),
+            VitalSign::SpO2(_) => span(SPO2_RANGE),
+            VitalSign::Temperature(quantity) => {
+                let bound = |celsius: f64| {
+                    Quantity::new(celsius, Unit::Celsius)
+                        .value_in(quantity.unit)
+                        .map_or(celsius, |value| (value * 10.0).round() / 10.0)
+                };
+                format!("{}–{}", bound(TEMPERATURE_RANGE_C.low), bound(TEMPERATURE_RANGE_C.high))
+            }
+            VitalSign::RespiratoryRate(_) => span(respiratory_rate_range(age)),
+        }
+    }
+
+    /// Classify against age-adjusted ranges. Blood pressure takes the worse of
+    /// its systolic and diastolic components.
+    pub fn severity(&self, age: AgeGroup) -> VitalSeverity {
+        match self {
+            VitalSign::HeartRate(bpm) => heart_rate_range(age).classify(f64::from(*bpm)),
+            VitalSign::BloodPressure { systolic, diastolic } => systolic_range(age)
+                .classify(f64::from(*systolic))
+                .max(diastolic_range(age).classify(f64::from(*diastolic))),
+            VitalSign::SpO2(percent) => SPO2_RANGE.classify(f64::from(*percent)),
+            VitalSign::Temperature(quantity) => quantity
+                .value_in(Unit::Celsius)
+                .map_or(VitalSeverity::Critical, |celsius| TEMPERATURE_RANGE_C.classify(celsius)),
+            VitalSign::RespiratoryRate(rate) => respiratory_rate_range(age).classify(f64::from(*rate)),
+        }
+    }
+}
+
+impl fmt::Display for VitalSign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value_text(), self.unit_symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges_are_age_adjusted() {
+        let heart_rate = VitalSign::heart_rate(140).unwrap();
+        assert_eq!(heart_rate.severity(AgeGroup::from_years(0)), VitalSeverity::Normal);
+        assert_eq!(heart_rate.severity(AgeGroup::from_years(40)), VitalSeverity::Critical);
+
+        let breathing = VitalSign::respiratory_rate(26).unwrap();
+        assert_eq!(breathing.severity(AgeGroup::Toddler), VitalSeverity::Normal);
+        assert_eq!(breathing.severity(AgeGroup::Adult), VitalSeverity::Abnormal);
+        assert_eq!(VitalSign::spo2(92).unwrap().severity(AgeGroup::Adult), VitalSeverity::Abnormal);
+        assert_eq!(VitalSign::spo2(85).unwrap().severity(AgeGroup::Adult), VitalSeverity::Critical);
+    }
+
+    #[test]
+    fn test_blood_pressure_and_temperature() {
+        assert!(VitalSign::blood_pressure(125, 125).is_err());
+        // Normal systolic does not mask a dangerous diastolic
+        let bp = VitalSign::blood_pressure(128, 122).unwrap();
+        assert_eq!(bp.severity(AgeGroup::Adult), VitalSeverity::Critical);
+        assert_eq!(VitalSign::blood_pressure(120, 80).unwrap().severity(AgeGroup::Adult), VitalSeverity::Normal);
+        assert_eq!(bp.to_string(), "128/122 mmHg");
+
+        let fever = VitalSign::temperature(101.3, Unit::Fahrenheit).unwrap();
+        assert_eq!(fever.severity(AgeGroup::Adult), VitalSeverity::Abnormal);
+        assert_eq!(fever.normal_range_text(AgeGroup::Adult), "96.8–100.2");
+        assert_eq!(VitalSign::temperature(36.8, Unit::Celsius).unwrap().severity(AgeGroup::Infant), VitalSeverity::Normal);
+    }
+
+    #[test]
+    fn test_implausible_readings_are_rejected() {
+        assert!(VitalSign::heart_rate(720).is_err());
+        assert!(VitalSign::spo2(101).is_err());
+        assert!(VitalSign::temperature(98.6, Unit::Celsius).is_err());
+        assert!(VitalSign::temperature(70.0, Unit::Kilograms).is_err());
+        assert!(VitalSign::RespiratoryRate(400).validate().is_err());
+
+        let json = serde_json::to_string(&VitalSign::HeartRate(72)).unwrap();
+        assert_eq!(json, r#"{"kind":"heart_rate","value":72}"#);
+        assert_eq!(serde_json::from_str::<VitalSign>(&json).unwrap(), VitalSign::HeartRate(72));
+    }
+}
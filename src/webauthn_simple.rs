@@ -0,0 +1,221 @@
+// MyDR24 Healthcare Platform - WebAuthn Browser Bindings
+// Fingerprint / face unlock via navigator.credentials, used for sign-in step-up
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::Utc;
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use uuid::Uuid;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::api_client::ApiClient;
+use crate::auth::{
+    AuthenticatorAttachment, SessionManager, StepUpMethod, WebAuthnAssertion, WebAuthnAttestation,
+    WebAuthnCreationOptions, WebAuthnCredential, WebAuthnRequestOptions,
+};
+
+// COSE algorithm ids, in order of preference: ES256, then RS256 for older Windows Hello
+const PUBLIC_KEY_ALGORITHMS: [i32; 2] = [-7, -257];
+
+fn js_error(error: JsValue) -> String {
+    error
+        .as_string()
+        .or_else(|| Reflect::get(&error, &"message".into()).ok().and_then(|m| m.as_string()))
+        .unwrap_or_else(|| format!("{:?}", error))
+}
+
+fn get(target: &JsValue, key: &str) -> Result<JsValue, String> {
+    Reflect::get(target, &JsValue::from_str(key)).map_err(js_error)
+}
+
+fn set(target: &Object, key: &str, value: &JsValue) -> Result<(), String> {
+    Reflect::set(target, &JsValue::from_str(key), value).map(|_| ()).map_err(js_error)
+}
+
+fn to_buffer(field: &str, value: &str) -> Result<Uint8Array, String> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| format!("WebAuthn {} is not base64url", field))?;
+    Ok(Uint8Array::from(bytes.as_slice()))
+}
+
+fn from_buffer(buffer: &JsValue) -> String {
+    URL_SAFE_NO_PAD.encode(Uint8Array::new(buffer).to_vec())
+}
+
+fn credential_descriptors(credential_ids: &[String]) -> Result<Array, String> {
+    let descriptors = Array::new();
+    for id in credential_ids {
+        let descriptor = Object::new();
+        set(&descriptor, "type", &"public-key".into())?;
+        set(&descriptor, "id", &to_buffer("credential id", id)?)?;
+        descriptors.push(&descriptor);
+    }
+    Ok(descriptors)
+}
+
+fn window_origin() -> Result<String, String> {
+    let window = web_sys::window().ok_or("No window object")?;
+    get(&get(&window, "location")?, "origin")?
+        .as_string()
+        .ok_or_else(|| "Window has no origin".to_string())
+}
+
+fn credentials_container() -> Result<JsValue, String> {
+    let window = web_sys::window().ok_or("No window object")?;
+    let credentials = get(&window.navigator(), "credentials")?;
+    if credentials.is_undefined() || !is_webauthn_supported() {
+        return Err("WebAuthn is not supported in this browser".to_string());
+    }
+    Ok(credentials)
+}
+
+// Calls `navigator.credentials[method]({ publicKey })` and waits for the user
+async fn call_credentials(method: &str, public_key: Object) -> Result<JsValue, String> {
+    let credentials = credentials_container()?;
+    let options = Object::new();
+    set(&options, "publicKey", &public_key)?;
+    let function: Function = get(&credentials, method)?.dyn_into().map_err(js_error)?;
+    let promise: Promise = function.call1(&credentials, &options).map_err(js_error)?.dyn_into().map_err(js_error)?;
+    let credential = JsFuture::from(promise).await.map_err(js_error)?;
+    if credential.is_null() {
+        return Err("No credential was returned".to_string());
+    }
+    Ok(credential)
+}
+
+fn attachment_of(credential: &JsValue) -> Option<AuthenticatorAttachment> {
+    match get(credential, "authenticatorAttachment").ok()?.as_string()?.as_str() {
+        "platform" => Some(AuthenticatorAttachment::Platform),
+        "cross-platform" => Some(AuthenticatorAttachment::CrossPlatform),
+        _ => None,
+    }
+}
+
+fn credential_id_of(credential: &JsValue) -> Result<String, String> {
+    Ok(from_buffer(&get(credential, "rawId")?))
+}
+
+// True if the browser exposes the WebAuthn API at all
+pub fn is_webauthn_supported() -> bool {
+    web_sys::window()
+        .and_then(|window| Reflect::get(&window, &"PublicKeyCredential".into()).ok())
+        .is_some_and(|constructor| !constructor.is_undefined())
+}
+
+// True if the device has a built-in authenticator that verifies the user
+// (fingerprint, face, or device PIN); use it to decide whether to offer biometric unlock
+pub async fn is_biometric_available() -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+    let Ok(constructor) = Reflect::get(&window, &"PublicKeyCredential".into()) else {
+        return false;
+    };
+    let Ok(check) = get(&constructor, "isUserVerifyingPlatformAuthenticatorAvailable")
+        .and_then(|f| f.dyn_into::<Function>().map_err(js_error))
+    else {
+        return false;
+    };
+    match check.call0(&constructor).map(|p| p.dyn_into::<Promise>()) {
+        Ok(Ok(promise)) => JsFuture::from(promise).await.ok().and_then(|v| v.as_bool()).unwrap_or(false),
+        _ => false,
+    }
+}
+
+// Prompt the user to create a new credential for the server-issued options
+pub async fn create_credential(options: &WebAuthnCreationOptions) -> Result<WebAuthnAttestation, String> {
+    let public_key = Object::new();
+    set(&public_key, "challenge", &to_buffer("challenge", &options.challenge)?)?;
+
+    let rp = Object::new();
+    set(&rp, "id", &options.rp_id.as_str().into())?;
+    set(&rp, "name", &options.rp_name.as_str().into())?;
+    set(&public_key, "rp", &rp)?;
+
+    let user = Object::new();
+    set(&user, "id", &to_buffer("user id", &options.user_id)?)?;
+    set(&user, "name", &options.user_name.as_str().into())?;
+    set(&user, "displayName", &options.user_display_name.as_str().into())?;
+    set(&public_key, "user", &user)?;
+
+    let params = Array::new();
+    for alg in PUBLIC_KEY_ALGORITHMS {
+        let param = Object::new();
+        set(&param, "type", &"public-key".into())?;
+        set(&param, "alg", &alg.into())?;
+        params.push(&param);
+    }
+    set(&public_key, "pubKeyCredParams", &params)?;
+    set(&public_key, "excludeCredentials", &credential_descriptors(&options.exclude_credentials)?)?;
+
+    let selection = Object::new();
+    if let Some(attachment) = options.authenticator_attachment {
+        set(&selection, "authenticatorAttachment", &attachment.as_str().into())?;
+    }
+    set(&selection, "userVerification", &options.user_verification.as_str().into())?;
+    set(&public_key, "authenticatorSelection", &selection)?;
+    if let Some(timeout) = options.timeout_ms {
+        set(&public_key, "timeout", &timeout.into())?;
+    }
+
+    let credential = call_credentials("create", public_key).await?;
+    let response = get(&credential, "response")?;
+    Ok(WebAuthnAttestation {
+        credential_id: credential_id_of(&credential)?,
+        client_data_json: from_buffer(&get(&response, "clientDataJSON")?),
+        attestation_object: from_buffer(&get(&response, "attestationObject")?),
+        authenticator_attachment: attachment_of(&credential),
+    })
+}
+
+// Prompt the user to sign the server's challenge with a registered credential
+pub async fn get_assertion(options: &WebAuthnRequestOptions) -> Result<WebAuthnAssertion, String> {
+    let public_key = Object::new();
+    set(&public_key, "challenge", &to_buffer("challenge", &options.challenge)?)?;
+    set(&public_key, "rpId", &options.rp_id.as_str().into())?;
+    set(&public_key, "allowCredentials", &credential_descriptors(&options.allow_credentials)?)?;
+    set(&public_key, "userVerification", &options.user_verification.as_str().into())?;
+    if let Some(timeout) = options.timeout_ms {
+        set(&public_key, "timeout", &timeout.into())?;
+    }
+
+    let credential = call_credentials("get", public_key).await?;
+    let response = get(&credential, "response")?;
+    let user_handle = get(&response, "userHandle")?;
+    Ok(WebAuthnAssertion {
+        credential_id: credential_id_of(&credential)?,
+        client_data_json: from_buffer(&get(&response, "clientDataJSON")?),
+        authenticator_data: from_buffer(&get(&response, "authenticatorData")?),
+        signature: from_buffer(&get(&response, "signature")?),
+        user_handle: (!user_handle.is_null() && !user_handle.is_undefined()).then(|| from_buffer(&user_handle)),
+        authenticator_attachment: attachment_of(&credential),
+    })
+}
+
+// Enroll this device's fingerprint / face unlock for the signed-in user
+pub async fn register_biometric(client: &ApiClient) -> Result<WebAuthnCredential, String> {
+    let options = client.begin_webauthn_registration().await?;
+    let attestation = create_credential(&options).await?;
+    attestation.check(&options, &window_origin()?).map_err(|e| e.to_string())?;
+    client.finish_webauthn_registration(attestation).await
+}
+
+// Re-authenticate with a registered credential before a sensitive screen and
+// record the step-up on the session; the server verifies the signature
+pub async fn biometric_step_up(
+    client: &ApiClient,
+    sessions: &mut SessionManager,
+    session_id: Uuid,
+) -> Result<StepUpMethod, String> {
+    let options = client.begin_webauthn_step_up().await?;
+    let assertion = get_assertion(&options).await?;
+    assertion.check(&options, &window_origin()?).map_err(|e| e.to_string())?;
+    let method = assertion.step_up_method();
+    client.finish_webauthn_step_up(assertion).await?;
+    sessions
+        .record_step_up(session_id, method, Utc::now())
+        .map_err(|e| e.to_string())?;
+    Ok(method)
+}
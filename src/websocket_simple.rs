@@ -12,6 +12,15 @@ use gloo_net::websocket::{futures::WebSocket, Message, WebSocketError};
 use gloo_timers::future::TimeoutFuture;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::console;
+use crate::auth::{AuthClaims, DeviceKeyPair, RevocationList, RevocationUpdate, Role};
+#[cfg(feature = "post-quantum")]
+use crate::auth::ChatKeyEnvelope;
+use crate::events::EventBus;
+use crate::geo::GeoPoint;
+use crate::offline_queue::ReplayOutcome;
+use crate::outbox::{OutboxEntry, OutboxFuture, OutboxSink};
+use crate::secrets::SecretString;
+use crate::ids::{BookingId, PatientId, ProviderId};
 
 // WebSocket message types matching backend
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -24,10 +33,18 @@ pub enum MessageType {
     EmergencyAlert,
     #[serde(rename = "chat_message")]
     ChatMessage,
+    #[serde(rename = "chat_key")]
+    ChatKey,
     #[serde(rename = "payment_notification")]
     PaymentNotification,
+    #[serde(rename = "token_revoked")]
+    TokenRevoked,
+    #[serde(rename = "domain_event")]
+    DomainEvent,
     #[serde(rename = "connection_ack")]
     ConnectionAck,
+    #[serde(rename = "reauth")]
+    Reauth,
     #[serde(rename = "heartbeat")]
     Heartbeat,
     #[serde(rename = "error")]
@@ -59,9 +76,11 @@ pub struct WebSocketMessage {
 #[derive(Debug, Clone)]
 pub struct WebSocketConfig {
     pub url: String,
-    pub auth_token: Option<String>,
+    pub auth_token: Option<SecretString>,
+    pub ticket: Option<WsTicket>, // Preferred over auth_token; see `ApiClient::get_ws_ticket`
     pub user_id: String,
-    pub user_role: String,
+    pub user_role: Role,
+    pub device_key: Option<Arc<DeviceKeyPair>>, // Signs the connect handshake
     pub auto_reconnect: bool,
     pub max_reconnect_attempts: u32,
     pub heartbeat_interval: u64,
@@ -73,8 +92,10 @@ impl Default for WebSocketConfig {
         Self {
             url: "ws://127.0.0.1:8000/api/v1/websocket/connect".to_string(),
             auth_token: None,
+            ticket: None,
             user_id: String::new(),
-            user_role: "patient".to_string(),
+            user_role: Role::Patient,
+            device_key: None,
             auto_reconnect: true,
             max_reconnect_attempts: 5,
             heartbeat_interval: 30,
@@ -83,10 +104,73 @@ impl Default for WebSocketConfig {
     }
 }
 
+// Short-lived, single-purpose credential for opening a WebSocket.
+// Keeps long-lived bearer tokens out of URLs, which end up in proxy and server logs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsTicket {
+    pub ticket: SecretString,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl WsTicket {
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+}
+
+impl WebSocketConfig {
+    pub fn with_ticket(mut self, ticket: WsTicket) -> Self {
+        self.ticket = Some(ticket);
+        self.auth_token = None;
+        self
+    }
+
+    pub fn with_device_key(mut self, key: Arc<DeviceKeyPair>) -> Self {
+        self.device_key = Some(key);
+        self
+    }
+
+    // Take the user id and role from the token so the handshake can't claim a different identity
+    pub fn with_auth_token(mut self, token: impl Into<SecretString>) -> Result<Self, String> {
+        let token = token.into();
+        let claims = AuthClaims::from_token_unverified(token.expose()).map_err(|e| e.to_string())?;
+        if claims.is_expired() {
+            return Err("Auth token expired; sign in again".to_string());
+        }
+        self.user_id = claims.user_id;
+        self.user_role = claims.role;
+        self.auth_token = Some(token);
+        Ok(self)
+    }
+
+    // Connection URL with credentials; tickets win over the legacy token parameter
+    pub fn connection_url(&self) -> Result<String, String> {
+        let credential = match (&self.ticket, &self.auth_token) {
+            (Some(ticket), _) if ticket.is_expired() => {
+                return Err("WebSocket ticket expired; request a new one with ApiClient::get_ws_ticket".to_string());
+            }
+            (Some(ticket), _) => Some(format!("ticket={}", js_sys::encode_uri_component(ticket.ticket.expose()))),
+            (None, Some(token)) => {
+                console::warn_1(&"Sending bearer token in WebSocket URL; prefer a WS ticket".into());
+                Some(format!("token={}", token.expose()))
+            }
+            (None, None) => None,
+        };
+
+        Ok(match credential {
+            Some(credential) => format!(
+                "{}?{}&user_id={}&role={}",
+                self.url, credential, self.user_id, self.user_role
+            ),
+            None => self.url.clone(),
+        })
+    }
+}
+
 // Real-time location data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocationUpdate {
-    pub provider_id: String,
+    pub provider_id: ProviderId,
     pub latitude: f64,
     pub longitude: f64,
     pub accuracy: f64,
@@ -94,10 +178,16 @@ pub struct LocationUpdate {
     pub status: String,
 }
 
+impl LocationUpdate {
+    pub fn point(&self) -> GeoPoint {
+        GeoPoint { latitude: self.latitude, longitude: self.longitude }
+    }
+}
+
 // Booking status updates
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BookingStatusUpdate {
-    pub booking_id: String,
+    pub booking_id: BookingId,
     pub status: String,
     pub message: Option<String>,
     pub estimated_time: Option<String>,
@@ -108,7 +198,7 @@ pub struct BookingStatusUpdate {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmergencyAlert {
     pub alert_id: String,
-    pub patient_id: String,
+    pub patient_id: PatientId,
     pub alert_type: String,
     pub severity: String,
     pub location: Location,
@@ -129,6 +219,12 @@ pub struct Location {
     pub timestamp: DateTime<Utc>,
 }
 
+impl Location {
+    pub fn point(&self) -> GeoPoint {
+        GeoPoint { latitude: self.latitude, longitude: self.longitude }
+    }
+}
+
 // Chat message structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
@@ -147,7 +243,7 @@ pub struct ChatMessage {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentNotification {
     pub payment_id: String,
-    pub booking_id: String,
+    pub booking_id: BookingId,
     pub amount: f64,
     pub status: String,
     pub gateway_response: Option<String>,
@@ -164,6 +260,7 @@ pub struct SimpleWebSocketClient {
     state: Arc<Mutex<ConnectionState>>,
     callbacks: Arc<Mutex<HashMap<MessageType, Vec<MessageCallback>>>>,
     reconnect_attempts: Arc<Mutex<u32>>,
+    event_bus: Option<EventBus>,
 }
 
 impl SimpleWebSocketClient {
@@ -173,8 +270,15 @@ impl SimpleWebSocketClient {
             state: Arc::new(Mutex::new(ConnectionState::Disconnected)),
             callbacks: Arc::new(Mutex::new(HashMap::new())),
             reconnect_attempts: Arc::new(Mutex::new(0)),
+            event_bus: None,
         }
     }
+
+    /// Also publish every received message on `bus` as a [`WebSocketMessage`] event
+    pub fn with_event_bus(mut self, bus: EventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
     
     // Get current connection state
     pub fn get_state(&self) -> ConnectionState {
@@ -190,6 +294,22 @@ impl SimpleWebSocketClient {
         callbacks.entry(message_type).or_insert_with(Vec::new).push(Arc::new(callback));
     }
     
+    // Apply revocations pushed by the server to `revocations` as they arrive. A push
+    // after a missed one is refused and leaves the list stale, so the next
+    // `ApiClient::sync_revocations` fetches it in full.
+    pub fn subscribe_revocations(&self, revocations: RevocationList) {
+        self.on_message(MessageType::TokenRevoked, move |message| {
+            match serde_json::from_value::<RevocationUpdate>(message.payload) {
+                Ok(update) => {
+                    if let Err(e) = revocations.apply(update, Utc::now()) {
+                        console::warn_1(&format!("Revocation update not applied: {}", e).into());
+                    }
+                }
+                Err(e) => console::error_1(&format!("Invalid revocation update: {}", e).into()),
+            }
+        });
+    }
+
     // Connect to WebSocket server
     pub async fn connect(&self) -> Result<(), String> {
         console::log_1(&"Connecting to WebSocket...".into());
@@ -201,11 +321,13 @@ impl SimpleWebSocketClient {
         }
         
         // Build connection URL with authentication
-        let mut url = self.config.url.clone();
-        if let Some(token) = &self.config.auth_token {
-            url = format!("{}?token={}&user_id={}&role={}", 
-                url, token, self.config.user_id, self.config.user_role);
-        }
+        let url = match self.config.connection_url() {
+            Ok(url) => url,
+            Err(e) => {
+                *self.state.lock().unwrap() = ConnectionState::Error(e.clone());
+                return Err(e);
+            }
+        };
         
         // Establish WebSocket connection
         let ws = WebSocket::open(&url).map_err(|e| format!("WebSocket open error: {:?}", e))?;
@@ -226,14 +348,19 @@ impl SimpleWebSocketClient {
         let (mut write, mut read) = ws.split();
         let callbacks = Arc::clone(&self.callbacks);
         let state = Arc::clone(&self.state);
+        let event_bus = self.event_bus.clone();
         
-        // Send connection acknowledgment
+        // Send connection acknowledgment, signed by the device key when there is one;
+        // the handshake's nonce makes a captured frame useless for a second connection
+        let device = self.config.device_key.as_ref()
+            .map(|key| key.sign_handshake(&self.config.user_id, Utc::now()));
         let connect_msg = json!({
             "id": Uuid::new_v4().to_string(),
             "message_type": "connection_ack",
             "payload": {
                 "user_id": self.config.user_id,
-                "role": self.config.user_role
+                "role": self.config.user_role,
+                "device": device
             },
             "timestamp": Utc::now(),
             "sender_id": self.config.user_id
@@ -259,6 +386,9 @@ impl SimpleWebSocketClient {
                                     }
                                 }
                             }
+                            if let Some(bus) = &event_bus {
+                                bus.publish(ws_message);
+                            }
                         }
                     }
                     Ok(Message::Bytes(_)) => {
@@ -281,11 +411,7 @@ impl SimpleWebSocketClient {
     pub async fn send_message(&self, message: WebSocketMessage) -> Result<(), String> {
         // For now, we'll use a simple approach and reconnect each time
         // In a production app, you'd maintain the connection
-        let mut url = self.config.url.clone();
-        if let Some(token) = &self.config.auth_token {
-            url = format!("{}?token={}&user_id={}&role={}", 
-                url, token, self.config.user_id, self.config.user_role);
-        }
+        let url = self.config.connection_url()?;
         
         match WebSocket::open(&url) {
             Ok(ws) => {
@@ -302,6 +428,21 @@ impl SimpleWebSocketClient {
         }
     }
     
+    // Send an outbox event; the entry id is the message id so the server can drop duplicates
+    pub async fn send_outbox_entry(&self, entry: &OutboxEntry) -> Result<(), String> {
+        let message = WebSocketMessage {
+            id: entry.id.to_string(),
+            message_type: MessageType::DomainEvent,
+            payload: serde_json::to_value(&entry.event)
+                .map_err(|e| format!("Serialization error: {}", e))?,
+            timestamp: entry.event.occurred_at,
+            sender_id: self.config.user_id.clone(),
+            recipient_id: None,
+        };
+
+        self.send_message(message).await
+    }
+
     // Send emergency alert
     pub async fn send_emergency_alert(&self, alert: EmergencyAlert) -> Result<(), String> {
         let message = WebSocketMessage {
@@ -347,6 +488,49 @@ impl SimpleWebSocketClient {
         self.send_message(message).await
     }
     
+    // Deliver a chat key envelope to the one participant who can open it
+    #[cfg(feature = "post-quantum")]
+    pub async fn send_chat_key(&self, envelope: ChatKeyEnvelope) -> Result<(), String> {
+        let message = WebSocketMessage {
+            id: Uuid::new_v4().to_string(),
+            message_type: MessageType::ChatKey,
+            recipient_id: Some(envelope.recipient_id.clone()),
+            payload: serde_json::to_value(envelope)
+                .map_err(|e| format!("Serialization error: {}", e))?,
+            timestamp: Utc::now(),
+            sender_id: self.config.user_id.clone(),
+        };
+
+        self.send_message(message).await
+    }
+
+    // Swap a refreshed token into the session. With a device key the frame carries a
+    // single-use handshake bound to the new token, so it can't be replayed or re-pointed.
+    pub async fn reauthenticate(&mut self, token: impl Into<SecretString>) -> Result<(), String> {
+        let token = token.into();
+        let config = self.config.clone().with_auth_token(token.clone())?;
+        if config.user_id != self.config.user_id {
+            return Err("Refreshed token belongs to another user; reconnect instead".to_string());
+        }
+        let now = Utc::now();
+        let device = config.device_key.as_ref()
+            .map(|key| key.sign_reauth(&config.user_id, token.expose(), now));
+        let message = WebSocketMessage {
+            id: Uuid::new_v4().to_string(),
+            message_type: MessageType::Reauth,
+            payload: json!({
+                "token": token,
+                "device": device
+            }),
+            timestamp: now,
+            sender_id: config.user_id.clone(),
+            recipient_id: None,
+        };
+
+        self.config = config;
+        self.send_message(message).await
+    }
+
     // Disconnect from server
     pub fn disconnect(&self) {
         let mut state = self.state.lock().unwrap();
@@ -355,14 +539,26 @@ impl SimpleWebSocketClient {
     }
 }
 
+impl OutboxSink for SimpleWebSocketClient {
+    fn deliver<'a>(&'a self, entry: &'a OutboxEntry) -> OutboxFuture<'a> {
+        Box::pin(async move {
+            match self.send_outbox_entry(entry).await {
+                Ok(()) => ReplayOutcome::Succeeded,
+                Err(e) if e.starts_with("Serialization error") => ReplayOutcome::Conflict(e),
+                Err(e) => ReplayOutcome::RetryLater(e),
+            }
+        })
+    }
+}
+
 // Helper functions for creating messages
 pub fn create_emergency_alert(
-    patient_id: String,
-    location: Option<(f64, f64)>,
+    patient_id: PatientId,
+    location: Option<GeoPoint>,
     medical_condition: Option<String>,
     emergency_contact: Option<String>,
 ) -> EmergencyAlert {
-    let (latitude, longitude) = location.unwrap_or((0.0, 0.0));
+    let GeoPoint { latitude, longitude } = location.unwrap_or(GeoPoint { latitude: 0.0, longitude: 0.0 });
     EmergencyAlert {
         alert_id: Uuid::new_v4().to_string(),
         patient_id,
@@ -384,7 +580,7 @@ pub fn create_emergency_alert(
 }
 
 pub fn create_location_update(
-    provider_id: String,
+    provider_id: ProviderId,
     latitude: f64,
     longitude: f64,
     accuracy: f64,